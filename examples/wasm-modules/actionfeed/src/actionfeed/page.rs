@@ -153,6 +153,7 @@ impl ActionfeedPage {
                     weapon_container: &mut self.weapon_container,
                     toolkit_render: &self.toolkit_render,
                     ninja_container: &mut self.ninja_container,
+                    layout: Default::default(),
                 },
             ),
             ui_state,
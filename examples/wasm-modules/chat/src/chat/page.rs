@@ -102,6 +102,7 @@ impl ChatPage {
                     stream_handle: &self.stream_handle,
                     skin_container: &mut self.skin_container,
                     render_tee: &self.render_tee,
+                    layout: Default::default(),
                 },
             ),
             ui_state,
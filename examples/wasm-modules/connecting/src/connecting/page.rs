@@ -12,22 +12,25 @@ use client_ui::{
     events::UiEvents,
 };
 use game_config::config::Config;
-use graphics::graphics::graphics::Graphics;
+use graphics::{
+    graphics::graphics::Graphics,
+    handles::{canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle},
+};
 use shared_base::network::server_info::ServerInfo;
 use ui_base::types::{UiRenderPipe, UiState};
 use ui_traits::traits::UiPageInterface;
 
-pub struct Connecting {}
-
-impl Default for Connecting {
-    fn default() -> Self {
-        Self::new()
-    }
+pub struct Connecting {
+    stream_handle: GraphicsStreamHandle,
+    canvas_handle: GraphicsCanvasHandle,
 }
 
 impl Connecting {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(graphics: &Graphics) -> Self {
+        Self {
+            stream_handle: graphics.stream_handle.clone(),
+            canvas_handle: graphics.canvas_handle.clone(),
+        }
     }
 
     fn render_impl(
@@ -45,8 +48,12 @@ impl Connecting {
                     mode: &ConnectMode::new(ConnectModes::Connecting),
                     config: &mut Default::default(),
                     events: &UiEvents::new(),
+                    icon: None,
+                    stream_handle: &self.stream_handle,
+                    canvas_handle: &self.canvas_handle,
                 },
             },
+            ui_state,
             main_frame_only,
         );
     }
@@ -2,7 +2,7 @@
 
 pub mod connecting;
 
-use api::graphics::graphics::GraphicsBackend;
+use api::{graphics::graphics::GraphicsBackend, GRAPHICS};
 use ui_traits::traits::UiPageInterface;
 
 pub use api_ui::ui_impl::*;
@@ -10,5 +10,7 @@ pub use api_ui_game::render::*;
 
 #[no_mangle]
 fn mod_ui_new() -> Box<dyn UiPageInterface<()>> {
-    Box::new(connecting::page::Connecting::new())
+    Box::new(connecting::page::Connecting::new(unsafe {
+        &GRAPHICS.borrow()
+    }))
 }
@@ -67,6 +67,7 @@ impl Console {
                     msg: &mut "te".to_string(),
                     cursor: &mut 0,
                     select_index: &mut Some(0),
+                    history: &mut Default::default(),
                 },
             },
             ui_state,
@@ -49,6 +49,10 @@ impl DemoPlayerPage {
                     speed: &Default::default(),
                     state: &mut self.state,
                     name: "example_demo",
+                    waveform: None,
+                    povs: &["main".to_string()],
+                    cur_pov: &0,
+                    bookmarks: &[Duration::from_secs(1)],
                 },
             ),
             main_frame_only,
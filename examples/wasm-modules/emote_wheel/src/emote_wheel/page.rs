@@ -56,6 +56,7 @@ impl EmoteWheelPage {
                     emoticon: &Default::default(),
                     skin: &Default::default(),
                     skin_info: &None,
+                    recent_emoticons: &[],
                 },
             ),
             ui_state,
@@ -157,6 +157,7 @@ impl IngameMenu {
                     max_players: 64,
                     passworded: false,
                     cert_sha256_fingerprint: Default::default(),
+                    icon: None,
                 },
                 address: "127.0.0.1:8303".into(),
                 location: "default".to_string(),
@@ -172,6 +173,8 @@ impl IngameMenu {
                         ddnet_info: &Default::default(),
                         demos: &Default::default(),
                         demo_info: &None,
+                        demo_thumbnails: &mut Default::default(),
+                        server_icons: &mut Default::default(),
                         icons: &mut Default::default(),
 
                         server_info: &Default::default(),
@@ -153,6 +153,7 @@ impl MainMenu {
                     max_players: 64,
                     passworded: false,
                     cert_sha256_fingerprint: Default::default(),
+                    icon: None,
                 },
                 address: format!("127.0.0.1:{i}"),
                 location: "default".to_string(),
@@ -258,6 +259,8 @@ impl MainMenu {
                     ddnet_info: &Default::default(),
                     demos: &self.demos,
                     demo_info: &None,
+                    demo_thumbnails: &mut Default::default(),
+                    server_icons: &mut Default::default(),
                     icons: &mut Default::default(),
 
                     server_info: &Default::default(),
@@ -245,6 +245,7 @@ impl Scoreboard {
                     skin_container: &mut self.skin_container,
                     render_tee: &self.render_tee,
                     flags_container: &mut self.flags_container,
+                    layout: Default::default(),
                 },
             ),
             ui_state,
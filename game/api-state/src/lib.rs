@@ -9,7 +9,8 @@ use game_database::traits::DbInterface;
 use game_interface::client_commands::ClientCommand;
 use game_interface::events::{EventClientInfo, EventId, GameEvents};
 use game_interface::interface::{GameStateCreate, GameStateCreateOptions, GameStateStaticInfo};
-use game_interface::types::character_info::NetworkCharacterInfo;
+use game_interface::server_commands::ServerCommand;
+use game_interface::types::character_info::{CharacterInfoRedaction, NetworkCharacterInfo};
 use game_interface::types::emoticons::EmoticonType;
 use game_interface::types::game::GameEntityId;
 use game_interface::types::input::{
@@ -172,6 +173,9 @@ impl GameStateInterface for APIState {
     #[guest_func_call_from_host_auto]
     fn client_command(&mut self, player_id: &GameEntityId, cmd: ClientCommand) {}
 
+    #[guest_func_call_from_host_auto]
+    fn server_command(&mut self, cmd: ServerCommand) {}
+
     #[guest_func_call_from_host_auto]
     fn collect_characters_info(&self) -> PoolLinkedHashMap<GameEntityId, CharacterInfo> {}
 
@@ -219,6 +223,14 @@ impl GameStateInterface for APIState {
     fn build_from_snapshot(&mut self, snapshot: &MtPoolCow<'static, [u8]>) -> SnapshotLocalPlayers {
     }
 
+    #[guest_func_call_from_host_auto]
+    fn redact_snapshot(
+        &self,
+        snapshot: &MtPoolCow<'static, [u8]>,
+        redaction: &CharacterInfoRedaction,
+    ) -> MtPoolCow<'static, [u8]> {
+    }
+
     #[guest_func_call_from_host_auto]
     fn snapshot_for_hotreload(&self) -> Option<MtPoolCow<'static, [u8]>> {}
 
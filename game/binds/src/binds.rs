@@ -2,7 +2,7 @@ use std::{collections::HashMap, ops::Range};
 
 use anyhow::anyhow;
 use command_parser::parser::{Command, Syn};
-use game_interface::types::weapons::WeaponType;
+use game_interface::types::{emoticons::EmoticonType, weapons::WeaponType};
 use native::input::binds::{BindKey, KeyCode, PhysicalKey};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -17,9 +17,13 @@ pub enum BindActionsLocalPlayer {
     Weapon(WeaponType),
     OpenMenu,
     ActivateChatInput,
+    /// Like [`Self::ActivateChatInput`], but the message is only sent to the own team.
+    ActivateTeamChatInput,
     ShowScoreboard,
     ShowChatHistory,
     ShowEmoteWheel,
+    /// Shows the emoticon directly, without the emote wheel.
+    Emoticon(EmoticonType),
     Kill,
     ToggleDummyCopyMoves,
     ToggleDummyHammerFly,
@@ -30,7 +34,7 @@ pub enum BindActionsLocalPlayer {
     ZoomReset,
 }
 
-const LOCAL_PLAYER_ACTIONS: [(&str, BindActionsLocalPlayer); 23] = [
+const LOCAL_PLAYER_ACTIONS: [(&str, BindActionsLocalPlayer); 42] = [
     ("+left", BindActionsLocalPlayer::MoveLeft),
     ("+right", BindActionsLocalPlayer::MoveRight),
     ("+jump", BindActionsLocalPlayer::Jump),
@@ -59,7 +63,82 @@ const LOCAL_PLAYER_ACTIONS: [(&str, BindActionsLocalPlayer); 23] = [
     // weapons end
     ("ingame_menu", BindActionsLocalPlayer::OpenMenu),
     ("+show_chat", BindActionsLocalPlayer::ActivateChatInput),
+    (
+        "+show_team_chat",
+        BindActionsLocalPlayer::ActivateTeamChatInput,
+    ),
     ("+scoreboard", BindActionsLocalPlayer::ShowScoreboard),
+    (
+        "+show_chat_history",
+        BindActionsLocalPlayer::ShowChatHistory,
+    ),
+    ("+emote_wheel", BindActionsLocalPlayer::ShowEmoteWheel),
+    // emoticons
+    (
+        "emote_oop",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::OOP),
+    ),
+    (
+        "emote_exclamation",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::EXCLAMATION),
+    ),
+    (
+        "emote_hearts",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::HEARTS),
+    ),
+    (
+        "emote_drop",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::DROP),
+    ),
+    (
+        "emote_dotdot",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::DOTDOT),
+    ),
+    (
+        "emote_music",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::MUSIC),
+    ),
+    (
+        "emote_sorry",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::SORRY),
+    ),
+    (
+        "emote_ghost",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::GHOST),
+    ),
+    (
+        "emote_sushi",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::SUSHI),
+    ),
+    (
+        "emote_splattee",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::SPLATTEE),
+    ),
+    (
+        "emote_deviltee",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::DEVILTEE),
+    ),
+    (
+        "emote_zomg",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::ZOMG),
+    ),
+    (
+        "emote_zzz",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::ZZZ),
+    ),
+    (
+        "emote_wtf",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::WTF),
+    ),
+    (
+        "emote_eyes",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::EYES),
+    ),
+    (
+        "emote_question",
+        BindActionsLocalPlayer::Emoticon(EmoticonType::QUESTION),
+    ),
+    // emoticons end
     ("vote_yes", BindActionsLocalPlayer::VoteYes),
     ("vote_no", BindActionsLocalPlayer::VoteNo),
     ("kill", BindActionsLocalPlayer::Kill),
@@ -123,35 +202,18 @@ fn action_to_action_str(
 fn bind_keys_str_to_bind_keys(bind_keys_str: &str) -> anyhow::Result<Vec<BindKey>> {
     let mut bind_keys: Vec<BindKey> = Vec::new();
     for bind_key_str in bind_keys_str.split('+') {
-        let mut cap_bind_key_str = bind_key_str.to_string();
-        cap_bind_key_str.make_ascii_lowercase();
-        cap_bind_key_str = {
-            let str_len = cap_bind_key_str.chars().count();
-            let mut res: Vec<_> = cap_bind_key_str
-                .chars()
-                .enumerate()
-                .collect::<Vec<(usize, char)>>()
-                .windows(2)
-                .flat_map(|arg| {
-                    let [(_, c1), (c2_index, c2)] = [arg[0], arg[1]];
-                    if c1.is_ascii_uppercase() {
-                        vec![]
-                    } else if c1 == '_' {
-                        vec![c2.to_ascii_uppercase()]
-                    } else if str_len - 1 == c2_index {
-                        vec![c1, c2]
-                    } else {
-                        vec![c1]
-                    }
-                })
-                .collect();
-            if res.is_empty() {
-                cap_bind_key_str.to_ascii_uppercase()
-            } else {
-                res[0] = res[0].to_ascii_uppercase();
-                res.into_iter().collect()
-            }
-        };
+        // snake case to pascal case, e.g. `shift_left` => `ShiftLeft`
+        let cap_bind_key_str: String = bind_key_str
+            .to_ascii_lowercase()
+            .split('_')
+            .map(|part| {
+                let mut chars = part.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect();
         let bind_key_str = format!("\"{cap_bind_key_str}\"");
         if let Ok(key_code) = serde_json::from_str::<KeyCode>(&bind_key_str) {
             bind_keys.push(BindKey::Key(PhysicalKey::Code(key_code)));
@@ -303,10 +365,12 @@ pub fn bind_to_str(
 #[cfg(test)]
 mod test {
 
-    use native::input::binds::{BindKey, KeyCode, MouseButton, MouseExtra, PhysicalKey};
+    use game_interface::types::emoticons::{EmoticonType, IntoEnumIterator};
+    use native::input::binds::{BindKey, Binds, KeyCode, MouseButton, MouseExtra, PhysicalKey};
 
     use crate::binds::{
-        bind_to_str, gen_local_player_action_hash_map_rev, BindActions, BindActionsLocalPlayer,
+        bind_to_str, gen_local_player_action_hash_map, gen_local_player_action_hash_map_rev,
+        str_to_bind, BindActions, BindActionsLocalPlayer,
     };
 
     #[test]
@@ -344,4 +408,56 @@ mod test {
         )
         .contains("bind wheel_down "));
     }
+
+    #[test]
+    fn bind_str_round_trip() {
+        let map = gen_local_player_action_hash_map();
+        let map_rev = gen_local_player_action_hash_map_rev();
+        for keys in [
+            vec![BindKey::Key(PhysicalKey::Code(KeyCode::KeyA))],
+            vec![BindKey::Key(PhysicalKey::Code(KeyCode::Digit1))],
+            vec![BindKey::Key(PhysicalKey::Code(KeyCode::ShiftLeft))],
+            vec![BindKey::Key(PhysicalKey::Code(KeyCode::NumpadSubtract))],
+            vec![BindKey::Key(PhysicalKey::Code(KeyCode::ArrowLeft))],
+            vec![BindKey::Key(PhysicalKey::Code(KeyCode::ControlRight))],
+            vec![
+                BindKey::Key(PhysicalKey::Code(KeyCode::ControlLeft)),
+                BindKey::Key(PhysicalKey::Code(KeyCode::KeyT)),
+            ],
+            vec![BindKey::Mouse(MouseButton::Right)],
+            vec![BindKey::Extra(MouseExtra::WheelDown)],
+        ] {
+            let bind_str = bind_to_str(
+                &keys,
+                vec![BindActions::LocalPlayer(BindActionsLocalPlayer::Jump)],
+                &map_rev,
+            );
+            let (bind_keys, action) = str_to_bind(&bind_str, &map);
+            assert_eq!(bind_keys, keys);
+            assert_eq!(action, BindActionsLocalPlayer::Jump);
+        }
+    }
+
+    #[test]
+    fn bound_keys() {
+        let map = gen_local_player_action_hash_map();
+        let mut binds: Binds<BindActionsLocalPlayer> = Default::default();
+        for bind in ["bind arrow_left +left", "bind control_left+t +show_chat"] {
+            let (keys, action) = str_to_bind(bind, &map);
+            binds.register_bind(&keys, action);
+        }
+        // keys of key chains count, too
+        for key in [KeyCode::ArrowLeft, KeyCode::ControlLeft, KeyCode::KeyT] {
+            assert!(binds.is_bound(&BindKey::Key(PhysicalKey::Code(key))));
+        }
+        assert!(!binds.is_bound(&BindKey::Key(PhysicalKey::Code(KeyCode::KeyA))));
+    }
+
+    #[test]
+    fn every_emoticon_has_a_bind() {
+        let map = gen_local_player_action_hash_map_rev();
+        for emoticon in EmoticonType::iter() {
+            assert!(map.contains_key(&BindActionsLocalPlayer::Emoticon(emoticon)));
+        }
+    }
 }
@@ -1,7 +1,10 @@
+use std::path::PathBuf;
+
 use base::system::{self, SystemTimeInterface};
+use base_io::{io::Io, io_batcher::IoBatcherTask};
 use client_render::generic_ui_renderer;
 use client_types::console::{entries_to_parser, ConsoleEntry};
-use client_ui::console::{page::ConsoleUi, user_data::UserData};
+use client_ui::console::{history::ConsoleHistory, page::ConsoleUi, user_data::UserData};
 use command_parser::parser::parse;
 use config::config::ConfigEngine;
 use egui::Color32;
@@ -30,6 +33,11 @@ pub struct ConsoleRender<E, T> {
     pub cursor: usize,
     pub selected_index: Option<usize>,
     pub console_ui: ConsoleUi,
+    pub history: ConsoleHistory,
+
+    /// where the history is saved to, see [`ConsoleRender::load_history`]
+    history_file: Option<(Io, PathBuf)>,
+    history_task: Option<IoBatcherTask<ConsoleHistory>>,
 
     console_events: Box<dyn ConsoleEvents<E>>,
     pub user: T,
@@ -53,11 +61,58 @@ impl<E, T> ConsoleRender<E, T> {
             selected_index: None,
             cursor: 0,
             console_ui: ConsoleUi::new(bg_color),
+            history: Default::default(),
+            history_file: None,
+            history_task: None,
             console_events,
             user,
         }
     }
 
+    /// Loads the command history with the given name
+    /// and saves it there whenever a command was executed.
+    pub fn load_history(&mut self, io: &Io, name: &str) {
+        let path: PathBuf = format!("console_history/{name}.txt").into();
+        let fs = io.fs.clone();
+        let read_path = path.clone();
+        self.history_task = Some(io.io_batcher.spawn(async move {
+            let file = fs.read_file(&read_path).await?;
+            Ok(ConsoleHistory::from_lines(&String::from_utf8_lossy(&file)))
+        }));
+        self.history_file = Some((io.clone(), path));
+    }
+
+    fn update_history(&mut self) {
+        if self
+            .history_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            // a missing file simply means there is no history yet
+            if let Some(history) = self
+                .history_task
+                .take()
+                .and_then(|task| task.get_storage().ok())
+            {
+                self.history.prepend(history);
+            }
+        }
+
+        if self.history.changed && self.history_task.is_none() {
+            self.history.changed = false;
+            if let Some((io, path)) = &self.history_file {
+                let fs = io.fs.clone();
+                let path = path.clone();
+                let file = self.history.to_lines().into_bytes();
+                io.io_batcher.spawn_without_lifetime(async move {
+                    fs.create_dir("console_history".as_ref()).await?;
+                    fs.write_file(&path, file).await?;
+                    Ok(())
+                });
+            }
+        }
+    }
+
     pub fn parse_cmd(
         &self,
         cmd: &str,
@@ -88,6 +143,7 @@ impl<E, T> ConsoleRender<E, T> {
             msg: &mut self.text,
             cursor: &mut self.cursor,
             select_index: &mut self.selected_index,
+            history: &mut self.history,
             config: pipe.config,
         };
         let mut ui_pipe = UiRenderPipe::new(pipe.sys.time_get_nanoseconds(), &mut user_data);
@@ -104,6 +160,8 @@ impl<E, T> ConsoleRender<E, T> {
             inp,
         );
 
+        self.update_history();
+
         (self.get_events(), res)
     }
 
@@ -6,7 +6,7 @@ use binds::binds::{
 };
 use client_types::console::{ConsoleEntry, ConsoleEntryCmd, ConsoleEntryVariable};
 use client_ui::console::utils::syn_vec_to_config_val;
-use command_parser::parser::{CommandArg, CommandArgType};
+use command_parser::parser::{CommandArg, CommandArgType, Syn};
 use config::{
     config::ConfigEngine,
     traits::{ConfigFromStrFlags, ConfigInterface, ConfigValue},
@@ -21,6 +21,16 @@ use super::console::ConsoleRender;
 #[derive(Debug, Hiarc)]
 pub enum LocalConsoleEvent {
     Quit,
+    /// Shows or hides the history of notifications.
+    NotificationHistory,
+    /// Opens the directory of the crash reports.
+    CrashReports,
+    /// Plays a demo for the given time, collects render statistics
+    /// and quits afterwards.
+    Benchmark {
+        demo: String,
+        seconds: u64,
+    },
 }
 
 #[hiarc_safer_rc_refcell]
@@ -46,6 +56,9 @@ impl super::console::ConsoleEvents<LocalConsoleEvent> for LocalConsoleEvents {
 
 pub type LocalConsole = ConsoleRender<LocalConsoleEvent, ()>;
 
+/// Sends a chat template of `cl.chat_templates`, handled by the binds.
+pub const CHAT_TEMPLATE_CMD: &str = "chat_template";
+
 #[derive(Debug, Default)]
 pub struct LocalConsoleBuilder {}
 
@@ -287,11 +300,23 @@ impl LocalConsoleBuilder {
             }));
         }
 
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: CHAT_TEMPLATE_CMD.into(),
+            usage: "chat_template <name>: sends the chat message of cl.chat_templates, \
+                meant for binds"
+                .into(),
+            cmd: Rc::new(move |_config_engine, _config_game, _path| Ok(())),
+            args: vec![CommandArg {
+                expected_ty: CommandArgType::Text,
+            }],
+        }));
+
         list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
             name: "bind".into(),
             usage: "bind <keys> <commands>".into(),
             cmd: Rc::new(move |_config_engine, config_game, path| {
                 let (keys, action) = syn_to_bind(path, &actions_map)?;
+                // merged over the default binds, see `ConfigPlayer::binds_with_defaults`
                 config_game.players[0]
                     .binds
                     .push(bind_to_str(&keys, action, &actions_map_rev));
@@ -327,15 +352,62 @@ impl LocalConsoleBuilder {
                 },
             ],
         }));
+        let events = console_events.clone();
         list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
             name: "quit".into(),
             usage: "quit the client".into(),
             cmd: Rc::new(move |_, _, _| {
-                console_events.push(LocalConsoleEvent::Quit);
+                events.push(LocalConsoleEvent::Quit);
+                Ok(())
+            }),
+            args: vec![],
+        }));
+        let events = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "notifications".into(),
+            usage: "show or hide the history of notifications".into(),
+            cmd: Rc::new(move |_, _, _| {
+                events.push(LocalConsoleEvent::NotificationHistory);
+                Ok(())
+            }),
+            args: vec![],
+        }));
+        let events = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "crash_reports".into(),
+            usage: "open the directory of the crash reports".into(),
+            cmd: Rc::new(move |_, _, _| {
+                events.push(LocalConsoleEvent::CrashReports);
                 Ok(())
             }),
             args: vec![],
         }));
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "benchmark".into(),
+            usage: "benchmark <seconds> <demo>: plays the demo, \
+                writes a render performance report to benchmarks/ and quits"
+                .into(),
+            cmd: Rc::new(move |_, _, path| {
+                let (Some((Syn::Number(seconds), _)), Some((Syn::Text(demo), _))) =
+                    (path.first(), path.get(1))
+                else {
+                    return Err(anyhow::anyhow!("usage: benchmark <seconds> <demo>"));
+                };
+                console_events.push(LocalConsoleEvent::Benchmark {
+                    demo: demo.clone(),
+                    seconds: seconds.parse()?,
+                });
+                Ok(())
+            }),
+            args: vec![
+                CommandArg {
+                    expected_ty: CommandArgType::Number,
+                },
+                CommandArg {
+                    expected_ty: CommandArgType::Text,
+                },
+            ],
+        }));
     }
 
     pub fn build(creator: &UiCreator) -> LocalConsole {
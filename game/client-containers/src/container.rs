@@ -11,9 +11,13 @@ use std::{
 
 use anyhow::anyhow;
 use base::hash::{fmt_hash, Hash};
-use base_io_traits::{fs_traits::FileSystemInterface, http_traits::HttpClientInterface};
+use base_io_traits::{
+    fs_traits::FileSystemInterface,
+    http_traits::{HttpClientInterface, HttpError},
+};
 
 use base_io::{io::Io, io_batcher::IoBatcherTask};
+use bytes::Bytes;
 use either::Either;
 use game_interface::types::resource_key::ResourceKey;
 use graphics::{
@@ -43,6 +47,13 @@ pub struct ContainerItemLoadData {
     pub data: GraphicsBackendMemory,
 }
 
+impl ContainerItemLoadData {
+    /// The memory the texture will use on the GPU, assuming RGBA.
+    pub fn texture_memory_usage(&self) -> usize {
+        self.width as usize * self.height as usize * self.depth as usize * 4
+    }
+}
+
 #[derive(Debug, Hiarc, Clone)]
 pub struct ContainerLoadedItemDir {
     /// key is the relative path
@@ -72,6 +83,8 @@ pub enum ContainerLoadedItem {
 struct ContainerItem<A> {
     item: A,
     used_last_in: Duration,
+    /// See [`ContainerLoad::texture_memory_usage`].
+    texture_memory: usize,
 }
 
 pub type ContainerKey = ResourceKey;
@@ -172,6 +185,12 @@ pub struct Container<A, L> {
     /// last time the container was updated by [Self::update]
     last_update_time: Option<Duration>,
     last_update_interval_time: Option<Duration>,
+
+    /// If set, the least recently used items are unloaded
+    /// as soon as the items use more texture memory than this (in bytes).
+    texture_memory_budget: Option<usize>,
+    /// If set, items bigger than this (in bytes) are not downloaded.
+    download_size_limit: Option<usize>,
 }
 
 pub trait ContainerLoad<A>
@@ -187,6 +206,13 @@ where
         sound_mt: &SoundMultiThreaded,
     ) -> anyhow::Result<Self>;
 
+    /// The memory the textures of this item will use on the GPU.
+    /// Only items that report their usage count
+    /// towards the texture memory budget of a container.
+    fn texture_memory_usage(&self) -> usize {
+        0
+    }
+
     fn convert(
         self,
         texture_handle: &GraphicsTextureHandle,
@@ -267,6 +293,9 @@ where
 
             last_update_time: None,
             last_update_interval_time: None,
+
+            texture_memory_budget: None,
+            download_size_limit: None,
         }
     }
 
@@ -283,11 +312,13 @@ where
                 })
                 .unwrap();
             self.default_loaded_item = Arc::new(default_loaded_item);
+            let texture_memory = default_item.texture_memory_usage();
             self.items.insert(
                 (*self.default_key).clone(),
                 ContainerItem {
                     item: default_item.convert(&self.texture_handle, &self.sound_object_handle),
                     used_last_in: Duration::ZERO,
+                    texture_memory,
                 },
             );
         }
@@ -330,12 +361,79 @@ where
                     break;
                 }
             }
+
+            if let Some(budget) = self.texture_memory_budget {
+                self.evict_over_budget(budget);
+            }
+
             let item = self.items.to_back(&self.default_key).unwrap();
             item.used_last_in = *cur_time;
         }
         self.last_update_time = Some(*cur_time);
     }
 
+    /// Unloads the least recently used items, until the items fit
+    /// into the texture memory budget again.
+    /// Items that were used since the last update are kept,
+    /// else they would be loaded again right away.
+    /// Unloaded items are loaded again on demand by [`Self::get_or_default`].
+    fn evict_over_budget(&mut self, budget: usize) {
+        let mut usage = self.texture_memory_usage();
+        let mut evict_items: Vec<ContainerKey> = Default::default();
+        for (name, item) in self.items.iter() {
+            if usage <= budget
+                || self
+                    .last_update_time
+                    .is_some_and(|last_update_time| item.used_last_in >= last_update_time)
+            {
+                break;
+            }
+            if !name.eq(&self.default_key) {
+                usage -= item.texture_memory;
+                evict_items.push(name.clone());
+            }
+        }
+        if !evict_items.is_empty() {
+            log::debug!(
+                target: &self.container_name,
+                "unloading {} items to stay in the texture memory budget",
+                evict_items.len()
+            );
+        }
+        for name in evict_items {
+            self.items.remove(&name);
+        }
+    }
+
+    /// Sets the texture memory budget in bytes, `None` means unlimited.
+    /// Applied by the next [`Self::update`].
+    pub fn set_texture_memory_budget(&mut self, budget: Option<usize>) {
+        self.texture_memory_budget = budget;
+    }
+
+    /// Sets the maximum size of a single item download in bytes,
+    /// `None` means unlimited. Only affects items that start loading afterwards.
+    pub fn set_download_size_limit(&mut self, limit: Option<usize>) {
+        self.download_size_limit = limit;
+    }
+
+    /// The texture memory all loaded items use, in bytes.
+    pub fn texture_memory_usage(&self) -> usize {
+        self.items.values().map(|item| item.texture_memory).sum()
+    }
+
+    async fn download_binary(
+        http: &dyn HttpClientInterface,
+        url: Url,
+        hash: &Hash,
+        download_size_limit: Option<usize>,
+    ) -> Result<Bytes, HttpError> {
+        match download_size_limit {
+            Some(max_size) => http.download_binary_limited(url, hash, max_size).await,
+            None => http.download_binary(url, hash).await,
+        }
+    }
+
     async fn load_container_item(
         container_name: String,
         fs: Arc<dyn FileSystemInterface>,
@@ -347,6 +445,7 @@ where
             TokioArcMutex<Option<anyhow::Result<ContainerItemIndexEntries>>>,
             Url,
         )>,
+        download_size_limit: Option<usize>,
     ) -> anyhow::Result<ContainerLoadedItem> {
         let read_tar = |file: &[u8]| {
             let mut file = tar::Archive::new(std::io::Cursor::new(file));
@@ -426,7 +525,14 @@ where
                     url.join(&format!("{}_{}.tar", key.name.as_str(), fmt_hash(&hash)))
                         .ok()
                 }) {
-                    if let Ok(file) = http.download_binary(game_server_http, &hash).await {
+                    if let Ok(file) = Self::download_binary(
+                        http.as_ref(),
+                        game_server_http,
+                        &hash,
+                        download_size_limit,
+                    )
+                    .await
+                    {
                         if let Ok(tar_files) = read_tar(&file) {
                             files = Some(ContainerLoadedItem::Directory(
                                 ContainerLoadedItemDir::new(tar_files),
@@ -443,7 +549,14 @@ where
                     url.join(&format!("{}_{}.png", key.name.as_str(), fmt_hash(&hash)))
                         .ok()
                 }) {
-                    if let Ok(file) = http.download_binary(game_server_http, &hash).await {
+                    if let Ok(file) = Self::download_binary(
+                        http.as_ref(),
+                        game_server_http,
+                        &hash,
+                        download_size_limit,
+                    )
+                    .await
+                    {
                         files = Some(ContainerLoadedItem::SingleFile(file.to_vec()));
                     }
                 }
@@ -547,7 +660,9 @@ where
                             .ok()
                     },
                 ) {
-                    if let Ok(file) = http.download_binary(url, &hash).await {
+                    if let Ok(file) =
+                        Self::download_binary(http.as_ref(), url, &hash, download_size_limit).await
+                    {
                         if ty == "tar" {
                             if let Ok(tar_files) = read_tar(&file) {
                                 files = Some(ContainerLoadedItem::Directory(
@@ -620,6 +735,7 @@ where
                 "default".try_into().unwrap(),
                 None,
                 None,
+                None,
             )
             .await
         })
@@ -638,6 +754,7 @@ where
             TokioArcMutex<Option<anyhow::Result<ContainerItemIndexEntries>>>,
             Url,
         )>,
+        download_size_limit: Option<usize>,
         default_loaded_item: Arc<ContainerLoadedItemDir>,
     ) -> IoBatcherTask<L> {
         let fs = io.fs.clone();
@@ -655,6 +772,7 @@ where
                 key,
                 game_server_http,
                 resource_http_download,
+                download_size_limit,
             )
             .await;
 
@@ -695,6 +813,7 @@ where
                         let loaded_item = load_item.get_storage();
                         match loaded_item {
                             Ok(item) => {
+                                let texture_memory = item.texture_memory_usage();
                                 let new_item =
                                     item.convert(&self.texture_handle, &self.sound_object_handle);
                                 self.items.insert(
@@ -702,6 +821,7 @@ where
                                     ContainerItem {
                                         item: new_item,
                                         used_last_in: self.last_update_time.unwrap_or_default(),
+                                        texture_memory,
                                     },
                                 );
                                 self.loading_tasks.remove(name.borrow());
@@ -740,6 +860,7 @@ where
                         key,
                         game_server_http,
                         resource_http.map(|url| (self.resource_http_download_index.clone(), url)),
+                        self.download_size_limit,
                         default_loaded_item,
                     )),
                 );
@@ -176,6 +176,18 @@ impl ContainerLoad<Entities> for LoadEntities {
         }
     }
 
+    fn texture_memory_usage(&self) -> usize {
+        self.physics
+            .values()
+            .chain([
+                &self.text_overlay_top,
+                &self.text_overlay_bottom,
+                &self.text_overlay_center,
+            ])
+            .map(|img| img.texture_memory_usage())
+            .sum()
+    }
+
     fn convert(
         self,
         texture_handle: &GraphicsTextureHandle,
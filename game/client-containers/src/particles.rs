@@ -290,6 +290,23 @@ impl ContainerLoad<Particle> for LoadParticle {
         }
     }
 
+    fn texture_memory_usage(&self) -> usize {
+        [
+            &self.slice,
+            &self.ball,
+            &self.smoke,
+            &self.shell,
+            &self.airjump,
+        ]
+        .into_iter()
+        .chain(self.splats.iter())
+        .chain(self.explosions.iter())
+        .chain(self.hits.iter())
+        .chain(self.stars.iter())
+        .map(|img| img.texture_memory_usage())
+        .sum()
+    }
+
     fn convert(
         self,
         texture_handle: &GraphicsTextureHandle,
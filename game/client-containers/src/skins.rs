@@ -555,6 +555,30 @@ pub struct LoadSkinTextures {
 }
 
 impl LoadSkinTextures {
+    fn texture_memory_usage(&self) -> usize {
+        [
+            &self.body,
+            &self.body_outline,
+            &self.marking,
+            &self.marking_outline,
+            &self.decoration,
+            &self.decoration_outline,
+            &self.left_hand,
+            &self.left_hand_outline,
+            &self.right_hand,
+            &self.right_hand_outline,
+            &self.left_foot,
+            &self.left_foot_outline,
+            &self.right_foot,
+            &self.right_foot_outline,
+        ]
+        .into_iter()
+        .chain(self.left_eyes.iter())
+        .chain(self.right_eyes.iter())
+        .map(|img| img.texture_memory_usage())
+        .sum()
+    }
+
     fn load_skin_into_texture(
         self,
         skin_name: &str,
@@ -1204,6 +1228,10 @@ impl ContainerLoad<Rc<Skin>> for LoadSkin {
         }
     }
 
+    fn texture_memory_usage(&self) -> usize {
+        self.textures.texture_memory_usage() + self.grey_scaled_textures.texture_memory_usage()
+    }
+
     fn convert(
         self,
         texture_handle: &GraphicsTextureHandle,
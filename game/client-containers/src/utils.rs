@@ -234,4 +234,20 @@ impl RenderGameContainers {
         self.game_container.clear_except_default();
         self.flags_container.clear_except_default();
     }
+
+    /// See [`crate::container::Container::set_download_size_limit`].
+    pub fn set_download_size_limit(&mut self, limit: Option<usize>) {
+        self.skin_container.set_download_size_limit(limit);
+        self.weapon_container.set_download_size_limit(limit);
+        self.hook_container.set_download_size_limit(limit);
+        self.ctf_container.set_download_size_limit(limit);
+        self.ninja_container.set_download_size_limit(limit);
+        self.freeze_container.set_download_size_limit(limit);
+        self.entities_container.set_download_size_limit(limit);
+        self.hud_container.set_download_size_limit(limit);
+        self.emoticons_container.set_download_size_limit(limit);
+        self.particles_container.set_download_size_limit(limit);
+        self.game_container.set_download_size_limit(limit);
+        self.flags_container.set_download_size_limit(limit);
+    }
 }
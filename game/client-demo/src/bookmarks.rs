@@ -0,0 +1,114 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use base_io::{io::Io, io_batcher::IoBatcherTask};
+
+/// Bookmarks closer than this to the current time are skipped
+/// when jumping to the previous one, so it's possible to jump
+/// back while the demo is playing.
+const PREV_BOOKMARK_GRACE: Duration = Duration::from_secs(1);
+
+/// Bookmarked moments of a demo.
+///
+/// They are stored next to the demo in a sidecar file
+/// (`<demo>.bookmarks`), one time in seconds per line.
+#[derive(Debug)]
+pub struct DemoBookmarks {
+    path: PathBuf,
+    bookmarks: Vec<Duration>,
+    load_task: Option<IoBatcherTask<Vec<Duration>>>,
+    /// Bookmarks were added before the sidecar file was loaded.
+    save_after_load: bool,
+}
+
+impl DemoBookmarks {
+    pub fn new(io: &Io, demo_path: &Path) -> Self {
+        let path = demo_path.with_extension("bookmarks");
+        let fs = io.fs.clone();
+        let path_thread = path.clone();
+        let load_task = io.io_batcher.spawn(async move {
+            let file = fs.read_file(&path_thread).await?;
+            Ok(String::from_utf8_lossy(&file)
+                .lines()
+                .filter_map(|line| line.trim().parse::<f64>().ok())
+                .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                .map(Duration::from_secs_f64)
+                .collect())
+        });
+        Self {
+            path,
+            bookmarks: Default::default(),
+            load_task: Some(load_task),
+            save_after_load: false,
+        }
+    }
+
+    /// Takes the bookmarks from the sidecar file, once they are loaded.
+    pub fn update(&mut self, io: &Io) {
+        if self
+            .load_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            // a demo without a sidecar file simply has no bookmarks yet
+            if let Ok(bookmarks) = self.load_task.take().unwrap().get_storage() {
+                self.bookmarks.extend(bookmarks);
+                self.bookmarks.sort();
+                self.bookmarks.dedup();
+            }
+            if std::mem::take(&mut self.save_after_load) {
+                self.save(io);
+            }
+        }
+    }
+
+    pub fn bookmarks(&self) -> &[Duration] {
+        &self.bookmarks
+    }
+
+    /// Adds a bookmark & writes all bookmarks to the sidecar file.
+    ///
+    /// While the sidecar file is still loading, the write waits for it,
+    /// so the bookmarks in it are not overwritten.
+    pub fn add(&mut self, io: &Io, time: Duration) {
+        if let Err(index) = self.bookmarks.binary_search(&time) {
+            self.bookmarks.insert(index, time);
+            if self.load_task.is_some() {
+                self.save_after_load = true;
+            } else {
+                self.save(io);
+            }
+        }
+    }
+
+    pub fn next(&self, time: Duration) -> Option<Duration> {
+        self.bookmarks
+            .iter()
+            .find(|&&bookmark| bookmark > time)
+            .copied()
+    }
+
+    pub fn prev(&self, time: Duration) -> Option<Duration> {
+        self.bookmarks
+            .iter()
+            .rev()
+            .find(|&&bookmark| bookmark < time.saturating_sub(PREV_BOOKMARK_GRACE))
+            .copied()
+    }
+
+    fn save(&self, io: &Io) {
+        let fs = io.fs.clone();
+        let path = self.path.clone();
+        let file: String = self
+            .bookmarks
+            .iter()
+            .map(|bookmark| format!("{}\n", bookmark.as_secs_f64()))
+            .collect();
+        io.io_batcher.spawn_without_lifetime(async move {
+            fs.write_file(&path, file.into_bytes()).await?;
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use base_io::io_batcher::{IoBatcher, IoBatcherTask};
+use base_io_traits::fs_traits::FileSystemInterface;
+use demo::{
+    encryption::DemoKey,
+    utils::{
+        chunks_group_head_size, chunks_group_size, demo_header_size, read_chunks_group_partial,
+        read_chunks_partial, read_demo_header, read_demo_header_ext, read_demo_tail,
+    },
+    DemoHeader, DemoHeaderExt, DemoTail,
+};
+use serde::de::DeserializeOwned;
+
+/// How many groups of chunks around the current tick
+/// are read in advance while streaming a demo.
+const DEMO_PREFETCH_GROUPS: usize = 4;
+
+/// The chunks of a demo, still untouched (compressed, serialized).
+#[derive(Debug, Clone)]
+pub enum DemoChunks {
+    /// All chunks are in memory, used for demos outside of the save directory
+    /// and for unfinished demos, since their tail is rebuilt from all chunks anyway.
+    Memory(Arc<Vec<u8>>),
+    /// Only the groups of chunks that are needed are read from the demo file,
+    /// so that long demos don't have to be kept in memory.
+    Stream {
+        fs: Arc<dyn FileSystemInterface>,
+        /// The path of the demo file inside the save directory.
+        path: PathBuf,
+        /// The offset of the chunks in the demo file.
+        start: u64,
+    },
+}
+
+/// Reads the headers and the tail of the demo file without its chunks,
+/// see [`DemoChunks::Stream`].
+///
+/// Returns `None` for unfinished demos, see [`demo::utils::read_demo`].
+pub async fn read_demo_streamed(
+    fs: &Arc<dyn FileSystemInterface>,
+    path: &Path,
+) -> anyhow::Result<Option<(DemoHeader, DemoHeaderExt, DemoChunks, DemoTail)>> {
+    let (header, header_size) = read_demo_header(
+        &fs.read_file_range(path, 0..demo_header_size() as u64)
+            .await?,
+    )?;
+    if header.size_chunks == 0 {
+        return Ok(None);
+    }
+
+    let ext_start = header_size as u64;
+    let header_ext = read_demo_header_ext(
+        &fs.read_file_range(path, ext_start..ext_start + header.size_ext)
+            .await?,
+    )?;
+    let chunks_start = ext_start + header.size_ext;
+    let tail = read_demo_tail(
+        &fs.read_file_range(path, chunks_start + header.size_chunks..u64::MAX)
+            .await?,
+    )?;
+    anyhow::ensure!(
+        !tail.snapshots_index.is_empty(),
+        "no snapshot index found in demo tail."
+    );
+
+    Ok(Some((
+        header,
+        header_ext,
+        DemoChunks::Stream {
+            fs: fs.clone(),
+            path: path.to_path_buf(),
+            start: chunks_start,
+        },
+        tail,
+    )))
+}
+
+/// Reads the whole group of chunks that starts at `start` in the file.
+async fn read_group(
+    fs: &Arc<dyn FileSystemInterface>,
+    path: &Path,
+    start: u64,
+) -> anyhow::Result<Vec<u8>> {
+    let head = fs
+        .read_file_range(path, start..start + chunks_group_head_size() as u64)
+        .await?;
+    let size = chunks_group_size(&head)? as u64;
+    Ok(fs.read_file_range(path, start..start + size).await?)
+}
+
+impl DemoChunks {
+    /// Reads the group of chunks at `offset` up to the first corrupted chunk,
+    /// see [`read_chunks_partial`].
+    pub async fn read<A: DeserializeOwned>(
+        &self,
+        offset: usize,
+        key: Option<&DemoKey>,
+    ) -> (BTreeMap<u64, A>, Option<anyhow::Error>) {
+        match self {
+            Self::Memory(chunks) => read_chunks_partial(chunks, offset, key),
+            Self::Stream { fs, path, start } => {
+                match read_group(fs, path, *start + offset as u64).await {
+                    Ok(group) => read_chunks_group_partial(&group, offset, key),
+                    Err(err) => (Default::default(), Some(err)),
+                }
+            }
+        }
+    }
+}
+
+/// Reads the groups of chunks of a streamed demo in the background,
+/// so that the render thread never waits for the file system.
+///
+/// Groups that were not requested since the last call to
+/// [`DemoChunksPrefetch::update`] are dropped, their reads are canceled.
+#[derive(Debug)]
+pub struct DemoChunksPrefetch {
+    io_batcher: IoBatcher,
+
+    loaded: HashMap<u64, Vec<u8>>,
+    reading: HashMap<u64, IoBatcherTask<Vec<u8>>>,
+    requested: HashSet<u64>,
+}
+
+impl DemoChunksPrefetch {
+    pub fn new(io_batcher: IoBatcher) -> Self {
+        Self {
+            io_batcher,
+
+            loaded: Default::default(),
+            reading: Default::default(),
+            requested: Default::default(),
+        }
+    }
+
+    /// Starts reading the group of chunks at `offset`, if it's not read already.
+    fn request(&mut self, chunks: &DemoChunks, offset: u64) {
+        let DemoChunks::Stream { fs, path, start } = chunks else {
+            return;
+        };
+        self.requested.insert(offset);
+        if self.loaded.contains_key(&offset) || self.reading.contains_key(&offset) {
+            return;
+        }
+        let fs = fs.clone();
+        let path = path.clone();
+        let start = *start + offset;
+        self.reading.insert(
+            offset,
+            self.io_batcher
+                .spawn(async move { read_group(&fs, &path, start).await })
+                .cancelable(),
+        );
+    }
+
+    /// Starts reading the groups around `tick` in the `index`,
+    /// so that they are ready once the playback reaches them.
+    pub fn request_around(&mut self, chunks: &DemoChunks, index: &BTreeMap<u64, u64>, tick: u64) {
+        let before = index.range(..=tick).rev().take(2);
+        let after = index.range(tick + 1..).take(DEMO_PREFETCH_GROUPS);
+        for (_, &offset) in before.chain(after) {
+            self.request(chunks, offset);
+        }
+    }
+
+    /// Reads the group of chunks at `offset` up to the first corrupted chunk.
+    ///
+    /// Returns `None` while the group is still being read,
+    /// unless `wait` is set. Groups that were waited for are not kept,
+    /// since they are usually read one after another anyway.
+    pub fn read<A: DeserializeOwned>(
+        &mut self,
+        chunks: &DemoChunks,
+        offset: u64,
+        key: Option<&DemoKey>,
+        wait: bool,
+    ) -> Option<(BTreeMap<u64, A>, Option<anyhow::Error>)> {
+        if let DemoChunks::Memory(chunks) = chunks {
+            return Some(read_chunks_partial(chunks, offset as usize, key));
+        }
+        self.request(chunks, offset);
+        if let Some(group) = self.loaded.get(&offset) {
+            return Some(read_chunks_group_partial(group, offset as usize, key));
+        }
+
+        let task = self.reading.remove(&offset)?;
+        if !wait && !task.is_finished() {
+            self.reading.insert(offset, task);
+            return None;
+        }
+        Some(match task.get_storage() {
+            Ok(group) => {
+                let res = read_chunks_group_partial(&group, offset as usize, key);
+                if !wait {
+                    self.loaded.insert(offset, group);
+                }
+                res
+            }
+            Err(err) => (Default::default(), Some(err)),
+        })
+    }
+
+    /// Drops the groups that were not requested since the last call,
+    /// should be called once per frame.
+    pub fn update(&mut self) {
+        let requested = std::mem::take(&mut self.requested);
+        self.loaded.retain(|offset, _| requested.contains(offset));
+        self.reading.retain(|offset, _| requested.contains(offset));
+    }
+}
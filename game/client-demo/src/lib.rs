@@ -1,13 +1,21 @@
 #![allow(clippy::too_many_arguments)]
 
+pub mod bookmarks;
+pub mod chunks;
+pub mod markers;
+pub mod thumbnails;
 pub mod ui;
+pub mod waveform;
 
 use std::{
     collections::BTreeMap,
     ops::RangeBounds,
     path::{Path, PathBuf},
     rc::Rc,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 
@@ -15,29 +23,49 @@ use anyhow::anyhow;
 use av_encoder::encoder::AudioVideoEncoder;
 pub use av_encoder::encoder::EncoderSettings;
 use base::system::{System, SystemTime, SystemTimeInterface};
-use base_io::{io::Io, io_batcher::IoBatcherTask};
-use client_map::client_map::{ClientMapFile, ClientMapLoading, GameMap};
-use client_render_base::map::render_pipe::GameTimeInfo;
+use base_io::{
+    io::Io,
+    io_batcher::{IoBatcher, IoBatcherTask},
+};
+use bookmarks::DemoBookmarks;
+use chunks::{read_demo_streamed, DemoChunks, DemoChunksPrefetch};
+use client_map::{
+    client_map::{ClientMapFile, ClientMapLoading, GameMap},
+    mod_check::check_game_mods,
+};
+use client_render_base::map::{render_pipe::GameTimeInfo, render_tools::RenderTools};
 use client_render_game::render_game::{
     RenderForPlayer, RenderGameCreateOptions, RenderGameForPlayer, RenderGameInput,
     RenderGameInterface, RenderGameSettings, RenderPlayerCameraMode,
 };
-use client_ui::demo_player::user_data::{DemoViewerEvent, DemoViewerUiState, UserData};
+use client_ui::demo_player::user_data::{
+    DemoPlayerActionQuery, DemoViewerCamera, DemoViewerEvent, DemoViewerUiState, UserData,
+};
 use config::config::ConfigEngine;
 use demo::{
+    encryption::DemoKey,
     recorder::{DemoRecorder, DemoRecorderCreateProps},
-    utils::{decomp, deser, deser_ex},
-    ChunkHeader, DemoEvent, DemoEvents, DemoHeader, DemoHeaderExt, DemoSnapshot, DemoTail,
+    redact::DemoRedaction,
+    utils::{has_event_kind, read_demo},
+    DemoEvent, DemoEventKind, DemoEvents, DemoHeader, DemoHeaderExt, DemoSnapshot, DemoTail,
 };
 use egui::Rect;
 use game_config::config::ConfigMap;
-use game_interface::{interface::GameStateInterface, types::game::GameTickType};
+use game_interface::{
+    interface::GameStateInterface,
+    types::{
+        game::{GameEntityId, GameTickType},
+        render::scoreboard::Scoreboard,
+    },
+    votes::{VoteState, Voted},
+};
 use graphics::{
     graphics::graphics::Graphics,
     handles::{
         canvas::canvas::GraphicsCanvasHandle,
         stream::stream::{GraphicsStreamHandle, QuadStreamHandle},
         stream_types::StreamedQuad,
+        texture::texture::TextureContainer,
     },
 };
 use graphics_backend::backend::GraphicsBackend;
@@ -46,21 +74,43 @@ use graphics_types::{
     rendering::{BlendType, ColorMaskMode, State},
 };
 use hiarc::hi_closure;
+use markers::DemoEventIndex;
 use math::math::vector::{ffixed, ubvec4, vec2};
 use pool::datatypes::{PoolBTreeMap, PoolLinkedHashMap, PoolLinkedHashSet, PoolVec, PoolVecDeque};
 use pool::mt_datatypes::PoolCow as MtPoolCow;
+use pool::rc::PoolRc;
 use serde::de::DeserializeOwned;
+use shared::render::render_wasm_manager::RENDER_MODS_DEFAULT;
 use shared_base::game_types::intra_tick_time_to_ratio;
 use sound::{
     commands::{SceneAirMode, SoundSceneCreateProps},
     sound::SoundManager,
 };
 use sound_backend::sound_backend::SoundBackend;
+use thumbnails::{DemoThumbnails, DEMO_THUMBNAIL_HEIGHT, DEMO_THUMBNAIL_WIDTH};
 use ui::render::{DemoPlayerUiRender, DemoPlayerUiRenderPipe};
 use ui_base::{font_data::UiFontData, ui::UiCreator};
+use url::Url;
 
 const DEMO_OFFSCREEN_ID: u64 = 9380;
 const DEMO_VIDEO_ENCODER_OFFSCREEN_ID: u64 = 9_380_326;
+const DEMO_THUMBNAIL_OFFSCREEN_ID: u64 = 9_380_327;
+/// How much faster than the playback speed the demo
+/// is rewound or fast-forwarded while seeking.
+const DEMO_SEEK_SPEED: f64 = 4.0;
+
+/// The speeds at which game sounds are time-stretched to keep their pitch.
+///
+/// Outside of these the stretched sounds get too many artifacts,
+/// so they are resampled instead.
+const DEMO_PRESERVE_SOUND_PITCH_SPEEDS: std::ops::RangeInclusive<f64> = 0.5..=2.0;
+
+/// Seeking while the backward or forward button is held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DemoSeek {
+    Backward,
+    Forward,
+}
 
 #[derive(Debug, Clone)]
 pub struct DemoContainer {
@@ -69,19 +119,65 @@ pub struct DemoContainer {
     /// Dynamic sized header
     pub header_ext: DemoHeaderExt,
     /// Demo chunks, still untouched (compressed, serialized)
-    pub demo_chunks: Vec<u8>,
+    pub chunks: DemoChunks,
     pub tail: DemoTail,
+    /// The key to decrypt the chunks, if the demo is encrypted
+    pub key: Option<DemoKey>,
+    /// The passphrase the key was derived from,
+    /// used to encrypt exported demos again.
+    pub passphrase: Option<String>,
+    /// Set once a corrupted chunk was skipped,
+    /// the playback continues with the intact chunks.
+    pub corrupted: Arc<AtomicBool>,
+    /// Mismatches between the game mods of the demo and the local ones,
+    /// that still allow the playback.
+    pub mod_warnings: Vec<String>,
+}
+
+impl DemoContainer {
+    /// Reads the chunks at `offset` up to the first corrupted one.
+    pub async fn read_chunks<A: DeserializeOwned>(&self, offset: u64) -> BTreeMap<u64, A> {
+        let (chunks, err) = self.chunks.read(offset as usize, self.key.as_ref()).await;
+        if err.is_some() {
+            self.corrupted.store(true, Ordering::Relaxed);
+        }
+        chunks
+    }
 }
 
 #[derive(Debug)]
 pub struct DemoViewerInner {
-    demo: DemoContainer,
+    demo: Arc<DemoContainer>,
+    /// Reads the chunks of streamed demos in the background.
+    prefetch: DemoChunksPrefetch,
     cur_snapshots: BTreeMap<u64, DemoSnapshot>,
     cur_events: BTreeMap<u64, DemoEvents>,
+    /// The last recorded vote and the tick it was recorded at
+    cur_vote: Option<(PoolRc<VoteState>, Option<Voted>, u64)>,
+    /// The last recorded scoreboard
+    cur_scoreboard: Option<Scoreboard>,
     cur_time: Duration,
     is_closed: bool,
     is_paused: bool,
+    show_scoreboard: bool,
     speed: ffixed,
+    /// The point of view that is played back,
+    /// `0` is the main one, everything above is an index into
+    /// [`DemoTail::povs`] (plus one).
+    cur_pov: usize,
+    /// Set for every frame the seek button is held,
+    /// consumed by the next frame.
+    seek: Option<DemoSeek>,
+    /// See [`DEMO_PRESERVE_SOUND_PITCH_SPEEDS`].
+    preserve_sound_pitch: bool,
+    camera: DemoViewerCamera,
+    /// The position of the followed character,
+    /// where the free camera starts.
+    camera_pos: vec2,
+    /// The characters of the current snapshot with their names.
+    characters: Vec<(GameEntityId, String)>,
+    /// A second character that is rendered side by side with the camera.
+    split_screen: Option<GameEntityId>,
 }
 
 impl DemoViewerInner {
@@ -101,87 +197,121 @@ impl DemoViewerInner {
         self.cur_time = time;
         self.cur_snapshots.clear();
         self.cur_events.clear();
+        self.cur_vote = None;
+        self.cur_scoreboard = None;
+    }
+
+    fn sound_preserve_pitch(&self) -> bool {
+        self.preserve_sound_pitch
+            && DEMO_PRESERVE_SOUND_PITCH_SPEEDS.contains(&self.speed.to_num::<f64>())
     }
 
+    /// Reads the chunks at `offset` up to the first corrupted one,
+    /// `None` while they are still being read, see [`DemoChunksPrefetch::read`].
     fn read_chunks<A: DeserializeOwned>(
         demo: &DemoContainer,
-        offset: usize,
-    ) -> anyhow::Result<BTreeMap<u64, A>> {
-        let file = &demo.demo_chunks[offset..];
-
-        // unpack all chunks
-        let mut data: Vec<u8> = Default::default();
-
-        anyhow::ensure!(
-            file.len() >= std::mem::size_of::<u64>(),
-            "file not huge enough to read u64 for compressed size"
-        );
-        let chunks_size = u64::from_le_bytes(file[0..std::mem::size_of::<u64>()].try_into()?);
-        let file = &file[std::mem::size_of::<u64>()..];
-
-        decomp(&file[0..chunks_size as usize], &mut data)?;
-        let file = data.as_slice();
-
-        // read item count in this chunk
-        let (len, read_size) = deser::<u64>(file)?;
-
-        let mut file = &file[read_size..];
-
-        let mut res: BTreeMap<u64, A> = Default::default();
-
-        let mut last_data: Option<Vec<u8>> = None;
-
-        let mut writer: Vec<u8> = Default::default();
-
-        for _ in 0..len {
-            let (header, read_size) = deser::<ChunkHeader>(file)?;
-            file = &file[read_size..];
-
-            let data = if header.size > 0 {
-                let data_slice = &file[..header.size as usize];
-                let res = if let Some(last_data) = &last_data {
-                    writer.clear();
-                    bin_patch::patch(last_data, data_slice, &mut writer)?;
-                    writer.as_slice()
-                } else {
-                    decomp(data_slice, &mut writer)?;
-                    writer.as_slice()
-                };
-                last_data = Some(res.to_vec());
-                let (data, _) = deser(res)?;
-                file = &file[header.size as usize..];
-                Some(data)
-            } else {
-                None
-            };
-
-            if let Some(data) = data {
-                res.insert(header.monotonic_tick, data);
-            }
+        prefetch: &mut DemoChunksPrefetch,
+        offset: u64,
+        wait: bool,
+    ) -> Option<BTreeMap<u64, A>> {
+        let (chunks, err) = prefetch.read(&demo.chunks, offset, demo.key.as_ref(), wait)?;
+        if err.is_some() {
+            demo.corrupted.store(true, Ordering::Relaxed);
         }
+        Some(chunks)
+    }
 
-        Ok(res)
+    fn snapshots_index(demo: &DemoContainer, cur_pov: usize) -> &BTreeMap<u64, u64> {
+        cur_pov
+            .checked_sub(1)
+            .and_then(|pov| demo.tail.povs.get(pov))
+            .map(|pov| &pov.snapshots_index)
+            .unwrap_or(&demo.tail.snapshots_index)
+    }
+
+    fn first_tick(&self) -> u64 {
+        self.demo
+            .tail
+            .snapshots_index
+            .first_key_value()
+            .map(|(tick, _)| *tick)
+            .unwrap_or(u64::MAX)
+            .min(
+                self.demo
+                    .tail
+                    .events_index
+                    .first_key_value()
+                    .map(|(tick, _)| *tick)
+                    .unwrap_or(u64::MAX),
+            )
     }
 
     fn time_to_tick_impl(&self, time: Duration) -> u64 {
         (time.as_nanos()
             / (Duration::from_secs(1).as_nanos()
                 / self.demo.header_ext.ticks_per_second.get() as u128)) as u64
-            + (self
-                .demo
+            + self.first_tick()
+    }
+
+    fn tick_to_time(&self, monotonic_tick: u64) -> Duration {
+        Duration::from_nanos(
+            monotonic_tick.saturating_sub(self.first_tick())
+                * (Duration::from_secs(1).as_nanos() as u64
+                    / self.demo.header_ext.ticks_per_second.get()),
+        )
+    }
+
+    /// Finds the monotonic tick of the next (or previous) event of `kind`,
+    /// relative to the current time.
+    /// Only the chunks that might contain such an event are read, in the background.
+    fn find_event(
+        &self,
+        io_batcher: &IoBatcher,
+        kind: DemoEventKind<'static>,
+        next: bool,
+    ) -> IoBatcherTask<Option<u64>> {
+        let tick = self.time_to_tick();
+        let offsets: Vec<u64> = if next {
+            self.demo
                 .tail
-                .snapshots_index
-                .first_key_value()
-                .map(|(tick, _)| *tick)
-                .unwrap_or(u64::MAX)
-                .min(
-                    self.demo
-                        .tail
-                        .events_index
-                        .first_key_value()
-                        .map(|(tick, _)| *tick)
-                        .unwrap_or(u64::MAX),
-                ))
+                .events_chunks_after(tick, &kind)
+                .map(|(_, offset)| offset)
+                .collect()
+        } else {
+            self.demo
+                .tail
+                .events_chunks_before(tick, &kind)
+                .map(|(_, offset)| offset)
+                .collect()
+        };
+        let chunks = self.demo.chunks.clone();
+        let key = self.demo.key.clone();
+        io_batcher
+            .spawn(async move {
+                for offset in offsets {
+                    let (events, err) = chunks
+                        .read::<DemoEvents>(offset as usize, key.as_ref())
+                        .await;
+                    if let Some(err) = err {
+                        return Err(err);
+                    }
+                    let found = if next {
+                        events
+                            .range(tick + 1..)
+                            .find(|(_, events)| has_event_kind(events, &kind))
+                    } else {
+                        events
+                            .range(..tick)
+                            .rev()
+                            .find(|(_, events)| has_event_kind(events, &kind))
+                    };
+                    if let Some((&found, _)) = found {
+                        return Ok(Some(found));
+                    }
+                }
+                Ok(None)
+            })
+            .cancelable()
     }
 
     fn time_to_tick(&self) -> u64 {
@@ -209,38 +339,86 @@ impl DemoViewerInner {
         }
     }
 
+    /// Returns `false` if the chunk is still being read.
     fn try_load_chunks<R: RangeBounds<u64>, A: DeserializeOwned>(
         demo: &DemoContainer,
+        prefetch: &mut DemoChunksPrefetch,
         tick_range: R,
         rev: bool,
         cur_data: &mut BTreeMap<u64, A>,
         index: &BTreeMap<u64, u64>,
-    ) {
+        wait: bool,
+    ) -> bool {
         let mut it = index.range(tick_range);
-        if let Some((_, chunk_byte_offset)) = if rev { it.next_back() } else { it.next() } {
-            if let Ok(mut chunks) = Self::read_chunks::<A>(demo, *chunk_byte_offset as usize) {
-                cur_data.append(&mut chunks);
-            }
+        if let Some((_, &chunk_byte_offset)) = if rev { it.next_back() } else { it.next() } {
+            let Some(mut chunks) = Self::read_chunks::<A>(demo, prefetch, chunk_byte_offset, wait)
+            else {
+                return false;
+            };
+            cur_data.append(&mut chunks);
         }
+        true
     }
 
+    /// Returns `false` if some of the needed chunks are still being read,
+    /// `wait` waits for them instead.
     fn check_chunks<A: DeserializeOwned>(
         demo: &DemoContainer,
+        prefetch: &mut DemoChunksPrefetch,
         cur_data: &mut BTreeMap<u64, A>,
         index: &BTreeMap<u64, u64>,
         monotonic_tick: u64,
-    ) {
+        wait: bool,
+    ) -> bool {
+        prefetch.request_around(&demo.chunks, index, monotonic_tick);
+
+        // when rewinding or skipping whole chunks while fast-forwarding,
+        // start over at the chunk of the current tick.
+        // The loaded ticks must be continuous, else the snapshots
+        // at both ends of the gap would be interpolated.
+        let chunk_tick = index
+            .range(..=monotonic_tick)
+            .next_back()
+            .map(|(&tick, _)| tick);
+        if cur_data
+            .first_key_value()
+            .is_some_and(|(&tick, _)| tick > monotonic_tick)
+            || cur_data
+                .last_key_value()
+                .zip(chunk_tick)
+                .is_some_and(|((&last_tick, _), chunk_tick)| chunk_tick > last_tick + 1)
+        {
+            cur_data.clear();
+        }
+
         // try to load chunks if needed
         let first_tick = cur_data
             .first_key_value()
             .map_or(u64::MAX, |(&tick, _)| tick);
-        if first_tick > monotonic_tick {
-            Self::try_load_chunks(demo, ..=monotonic_tick, true, cur_data, index)
+        if first_tick > monotonic_tick
+            && !Self::try_load_chunks(
+                demo,
+                prefetch,
+                ..=monotonic_tick,
+                true,
+                cur_data,
+                index,
+                wait,
+            )
+        {
+            return false;
         }
         let last_tick = cur_data.last_key_value().map_or(0, |(&tick, _)| tick);
-        if last_tick < monotonic_tick + 1 {
-            Self::try_load_chunks(demo, last_tick + 1.., false, cur_data, index)
-        }
+        let loaded = last_tick > monotonic_tick
+            || Self::try_load_chunks(
+                demo,
+                prefetch,
+                last_tick + 1..,
+                false,
+                cur_data,
+                index,
+                wait,
+            );
 
         // we want exactly one chunk before the current tick
         // this allows the second chunks to be used for the tick
@@ -248,6 +426,32 @@ impl DemoViewerInner {
         while cur_data.range(0..=monotonic_tick).count() > 1 {
             cur_data.pop_first();
         }
+        loaded
+    }
+
+    /// Loads the snapshots & events around the current time.
+    ///
+    /// Returns `false` while some of them are still being read
+    /// in the background, `wait` waits for them instead.
+    fn load_chunks(&mut self, wait: bool) -> bool {
+        let monotonic_tick = self.time_to_tick();
+        let snapshots_loaded = Self::check_chunks(
+            &self.demo,
+            &mut self.prefetch,
+            &mut self.cur_snapshots,
+            Self::snapshots_index(&self.demo, self.cur_pov),
+            monotonic_tick,
+            wait,
+        );
+        let events_loaded = Self::check_chunks(
+            &self.demo,
+            &mut self.prefetch,
+            &mut self.cur_events,
+            &self.demo.tail.events_index,
+            monotonic_tick,
+            wait,
+        );
+        snapshots_loaded && events_loaded
     }
 }
 
@@ -256,6 +460,10 @@ pub struct DemoStaticData {
     stream_handle: GraphicsStreamHandle,
 
     av_encoder: Option<(AudioVideoEncoder, DemoVideoEncodeProperties)>,
+    /// How many frames were encoded so far.
+    /// The demo time & the audio samples are derived from it,
+    /// so that rounding errors don't add up over a long video.
+    encoded_frames: u64,
 
     config_map: ConfigMap,
 }
@@ -275,9 +483,14 @@ pub struct DemoViewerImpl {
 
     inner: DemoViewerInner,
     preview: DemoViewerInner,
-    should_show_preview: Option<Rect>,
+    should_show_preview: Option<(Rect, Duration)>,
+    /// Previews of the timeline, rendered in the background.
+    /// Not generated while encoding a video.
+    thumbnails: Option<DemoThumbnails>,
 
     demo_name: String,
+    /// Names of all point of views, the first one is the main one.
+    povs: Vec<String>,
 
     io: Io,
 
@@ -289,6 +502,22 @@ pub struct DemoViewerImpl {
 
     events: Vec<DemoViewerEvent>,
     ui_state: DemoViewerUiState,
+
+    /// Loudness over the whole demo, generated in the background, see [`waveform::generate`]
+    waveform: Option<Vec<f32>>,
+    waveform_task: Option<IoBatcherTask<Vec<f32>>>,
+    /// Notable moments on the timeline & player actions, generated in the background
+    event_index: DemoEventIndex,
+    event_index_task: Option<IoBatcherTask<DemoEventIndex>>,
+    /// The last action search and its results.
+    action_query: Option<DemoPlayerActionQuery>,
+    action_results: Vec<Duration>,
+    /// The range that is played over and over (A-B repeat).
+    loop_range: Option<(Duration, Duration)>,
+    /// The search for the next or previous kill, see [`DemoViewerInner::find_event`].
+    event_jump_task: Option<IoBatcherTask<Option<u64>>>,
+
+    bookmarks: DemoBookmarks,
 }
 
 impl DemoViewerImpl {
@@ -304,7 +533,33 @@ impl DemoViewerImpl {
         ui_creator: &UiCreator,
         encode_to_video: Option<DemoVideoEncodeProperties>,
         name: String,
+        bookmarks: DemoBookmarks,
     ) -> Self {
+        let demo = Arc::new(demo);
+        let waveform_task = encode_to_video.is_none().then(|| {
+            let demo = demo.clone();
+            let fs = io.fs.clone();
+            io.io_batcher
+                .spawn(async move { waveform::generate(&demo, &fs).await })
+        });
+        let event_index_task = encode_to_video.is_none().then(|| {
+            let demo = demo.clone();
+            io.io_batcher
+                .spawn(async move { markers::generate(&demo).await })
+        });
+        let povs = std::iter::once("main".to_string())
+            .chain(demo.tail.povs.iter().map(|pov| pov.name.clone()))
+            .collect();
+        let thumbnails = encode_to_video.is_none().then(|| {
+            // without thumbnails the preview is simply simulated on hover
+            DemoThumbnails::new(
+                graphics,
+                backend,
+                DEMO_THUMBNAIL_OFFSCREEN_ID,
+                demo.header.len,
+            )
+            .ok()
+        });
         Self {
             data: DemoStaticData {
                 canvas_handle: graphics.canvas_handle.clone(),
@@ -324,6 +579,7 @@ impl DemoViewerImpl {
                         props,
                     )
                 }),
+                encoded_frames: 0,
                 config_map: Default::default(),
             },
 
@@ -331,6 +587,7 @@ impl DemoViewerImpl {
             preview_client_map,
 
             demo_name: name,
+            povs,
 
             io: io.clone(),
             time: sys.time.clone(),
@@ -339,35 +596,69 @@ impl DemoViewerImpl {
 
             preview: DemoViewerInner {
                 demo: demo.clone(),
+                prefetch: DemoChunksPrefetch::new(io.io_batcher.clone()),
 
                 cur_snapshots: Default::default(),
                 cur_events: Default::default(),
+                cur_vote: None,
+                cur_scoreboard: None,
 
                 cur_time: Duration::ZERO,
 
                 is_closed: false,
                 // Always paused
                 is_paused: true,
+                show_scoreboard: false,
                 speed: ffixed::from_num(1.0),
+                cur_pov: 0,
+                seek: None,
+                preserve_sound_pitch: true,
+                camera: Default::default(),
+                camera_pos: Default::default(),
+                characters: Default::default(),
+                split_screen: None,
             },
             should_show_preview: None,
+            thumbnails: thumbnails.flatten(),
             inner: DemoViewerInner {
                 demo,
+                prefetch: DemoChunksPrefetch::new(io.io_batcher.clone()),
 
                 cur_snapshots: Default::default(),
                 cur_events: Default::default(),
+                cur_vote: None,
+                cur_scoreboard: None,
 
                 cur_time: Duration::ZERO,
 
                 is_closed: false,
                 is_paused: false,
+                show_scoreboard: false,
                 speed: ffixed::from_num(1.0),
+                cur_pov: 0,
+                seek: None,
+                preserve_sound_pitch: true,
+                camera: Default::default(),
+                camera_pos: Default::default(),
+                characters: Default::default(),
+                split_screen: None,
             },
 
             demo_ui: DemoPlayerUiRender::new(graphics, ui_creator),
 
             events: Default::default(),
             ui_state: Default::default(),
+
+            waveform: None,
+            waveform_task,
+            event_index: Default::default(),
+            event_index_task,
+            action_query: None,
+            action_results: Vec::new(),
+            loop_range: None,
+            event_jump_task: None,
+
+            bookmarks,
         }
     }
 
@@ -403,31 +694,43 @@ impl DemoViewerImpl {
         last_monotonic_tick: &mut Option<GameTickType>,
         for_video_encode: bool,
     ) -> anyhow::Result<()> {
-        if !viewer.is_paused() && !viewer.is_finished() {
-            viewer.cur_time += Duration::from_secs_f64(
-                (cur_time.saturating_sub(last_time).as_secs_f64() * viewer.speed.to_num::<f64>())
-                    .clamp(0.0, f64::MAX),
-            );
+        let time_diff = Duration::from_secs_f64(
+            (cur_time.saturating_sub(last_time).as_secs_f64() * viewer.speed.to_num::<f64>())
+                .clamp(0.0, f64::MAX),
+        );
+        let seek = viewer.seek.take();
+        let last_demo_time = viewer.cur_time;
+        match seek {
+            // seeking works while paused, too
+            Some(DemoSeek::Backward) => {
+                viewer.cur_time = viewer
+                    .cur_time
+                    .saturating_sub(time_diff.mul_f64(DEMO_SEEK_SPEED));
+            }
+            Some(DemoSeek::Forward) => {
+                viewer.cur_time = viewer
+                    .cur_time
+                    .saturating_add(time_diff.mul_f64(DEMO_SEEK_SPEED))
+                    .min(viewer.demo.header.len);
+            }
+            None => {
+                if !viewer.is_paused() && !viewer.is_finished() {
+                    viewer.cur_time += time_diff;
+                }
+            }
         }
 
+        // the playback waits for chunks that are still being read,
+        // a video has to contain every frame, so it waits right here
+        if !viewer.load_chunks(for_video_encode) {
+            viewer.cur_time = last_demo_time;
+            return Ok(());
+        }
         let monotonic_tick = viewer.time_to_tick();
 
-        DemoViewerInner::check_chunks(
-            &viewer.demo,
-            &mut viewer.cur_snapshots,
-            &viewer.demo.tail.snapshots_index,
-            monotonic_tick,
-        );
-        DemoViewerInner::check_chunks(
-            &viewer.demo,
-            &mut viewer.cur_events,
-            &viewer.demo.tail.events_index,
-            monotonic_tick,
-        );
-
         let map = client_map.try_get_mut().unwrap();
 
-        let ClientMapFile::Game(GameMap { render, game }) = map else {
+        let ClientMapFile::Game(GameMap { render, game, .. }) = map else {
             panic!("not a game map")
         };
 
@@ -456,21 +759,9 @@ impl DemoViewerImpl {
             return Err(anyhow!("no snapshots inside this demo"));
         };
 
-        let (player_id, _) = local_players.iter().next().unwrap();
+        let (&recorder_id, _) = local_players.iter().next().unwrap();
         let intra_tick_time = viewer.intra_tick_time(monotonic_tick, prev_tick, next_tick);
 
-        let render_for_player = RenderForPlayer {
-            chat_info: None,
-            emote_wheel_input: None,
-            scoreboard_active: false,
-            chat_show_all: false,
-
-            local_player_info: game.collect_character_local_render_info(player_id),
-
-            zoom: 1.0,
-            cam_mode: RenderPlayerCameraMode::Default,
-        };
-
         let game_time_info = GameTimeInfo {
             ticks_per_second: game.game_tick_speed(),
             intra_tick_time,
@@ -482,9 +773,68 @@ impl DemoViewerImpl {
 
         let stages = game.all_stages(intra_tick_ratio);
 
-        let scoreboard_info = None; // game.collect_scoreboard_info();
+        viewer.characters = character_infos
+            .iter()
+            .map(|(&id, character)| (id, character.info.name.as_str().to_string()))
+            .collect();
+        // the free camera still renders for the recorder, e.g. its HUD
+        let player_id = match viewer.camera {
+            DemoViewerCamera::Follow(Some(id)) if character_infos.contains_key(&id) => id,
+            _ => recorder_id,
+        };
+        let (zoom, cam_mode) = match viewer.camera {
+            DemoViewerCamera::Follow(_) => {
+                if let Some(character) = character_infos
+                    .get(&player_id)
+                    .and_then(|c| c.stage_id)
+                    .and_then(|stage_id| stages.get(&stage_id))
+                    .and_then(|stage| stage.world.characters.get(&player_id))
+                {
+                    viewer.camera_pos = character.lerped_pos;
+                }
+                (1.0, RenderPlayerCameraMode::Default)
+            }
+            DemoViewerCamera::Free { pos, zoom } => (zoom, RenderPlayerCameraMode::AtPos(pos)),
+        };
+
+        let render_for_player = RenderForPlayer {
+            chat_info: None,
+            emote_wheel_input: None,
+            recent_emoticons: Vec::new(),
+            scoreboard_active: viewer.show_scoreboard,
+            chat_show_all: false,
+
+            local_player_info: game.collect_character_local_render_info(&player_id),
 
-        let load_events = !last_monotonic_tick.is_some_and(|tick| tick == monotonic_tick);
+            zoom,
+            cam_mode,
+        };
+        // the second view always follows its character
+        let split_screen = viewer
+            .split_screen
+            .filter(|&id| id != player_id && character_infos.contains_key(&id))
+            .map(|id| {
+                (
+                    id,
+                    RenderForPlayer {
+                        chat_info: None,
+                        emote_wheel_input: None,
+                        recent_emoticons: Vec::new(),
+                        scoreboard_active: false,
+                        chat_show_all: false,
+
+                        local_player_info: game.collect_character_local_render_info(&id),
+
+                        zoom: 1.0,
+                        cam_mode: RenderPlayerCameraMode::Default,
+                    },
+                )
+            });
+
+        // events are not played backwards, e.g. sounds would play
+        // in the wrong order
+        let load_events = !last_monotonic_tick.is_some_and(|tick| tick == monotonic_tick)
+            && seek != Some(DemoSeek::Backward);
         last_monotonic_tick.replace(monotonic_tick);
         let (events, chat_msgs) = if load_events {
             let demo_events = viewer
@@ -503,6 +853,14 @@ impl DemoViewerImpl {
                     DemoEvent::Chat(msg) => {
                         chat_msgs.push_back(msg);
                     }
+                    DemoEvent::Vote(vote) => {
+                        viewer.cur_vote = vote.map(|(vote, voted)| {
+                            (PoolRc::from_item_without_pool(vote), voted, monotonic_tick)
+                        });
+                    }
+                    DemoEvent::Scoreboard(scoreboard) => {
+                        viewer.cur_scoreboard = Some(scoreboard);
+                    }
                 }
             }
             (events, chat_msgs)
@@ -513,24 +871,42 @@ impl DemoViewerImpl {
             )
         };
 
+        let vote = viewer.cur_vote.as_ref().map(|(vote, voted, tick)| {
+            let nanos_per_tick = Duration::from_secs(1).as_nanos()
+                / viewer.demo.header_ext.ticks_per_second.get() as u128;
+            let time_since_vote = Duration::from_nanos(
+                (monotonic_tick.saturating_sub(*tick) as u128 * nanos_per_tick) as u64,
+            );
+            (
+                vote.clone(),
+                *voted,
+                vote.remaining_time.saturating_sub(time_since_vote),
+            )
+        });
+
         let mut render_game_input = RenderGameInput {
             players: PoolLinkedHashMap::new_without_pool(),
             dummies: PoolLinkedHashSet::new_without_pool(),
             events,
-            vote: None,
+            vote,
             character_infos,
             stages,
-            scoreboard_info,
+            scoreboard_info: viewer.cur_scoreboard.clone(),
             chat_msgs,
             game_time_info,
             settings: RenderGameSettings {
                 // TODO: add config for demos
-                sound_playback_speed: viewer.speed.to_num::<f64>(),
+                sound_playback_speed: viewer.speed.to_num(),
+                sound_preserve_pitch: viewer.sound_preserve_pitch(),
                 spartial_sound: false,
                 map_sound_volume: 0.3,
                 ingame_sound_volume: 0.3,
+                ducking_volume: 1.0,
                 nameplates: true,
                 nameplate_own: false,
+                hud_layout: Default::default(),
+                texture_memory_budget: 0,
+                resource_download_size_limit: 0,
             },
         };
 
@@ -542,7 +918,18 @@ impl DemoViewerImpl {
 
         render_game_input
             .players
-            .insert(*player_id, render_for_player);
+            .insert(player_id, render_for_player);
+        // multiple players are rendered side by side
+        if let Some((id, render_for_player)) = split_screen {
+            render_game_input.players.insert(
+                id,
+                RenderGameForPlayer {
+                    render_for_player,
+                    observed_players: PoolVec::new_without_pool(),
+                    observed_anchored_size_props: Default::default(),
+                },
+            );
+        }
 
         if let Some(settings) = for_video_encode
             .then(|| data.av_encoder.as_ref().map(|e| &e.1))
@@ -567,8 +954,16 @@ impl DemoViewerImpl {
             data.canvas_handle
                 .switch_canvas(CommandSwitchCanvasModeType::Onscreen);
 
-            let num_samples = settings.encoder_settings.sample_rate / settings.encoder_settings.fps;
-            render.render_offair_sound(num_samples);
+            // the samples of all frames so far, minus the ones already rendered,
+            // works for sample rates that are no multiple of the fps, too.
+            let samples_until = |frames: u64| {
+                frames * settings.encoder_settings.sample_rate as u64
+                    / settings.encoder_settings.fps as u64
+            };
+            let num_samples =
+                samples_until(data.encoded_frames + 1) - samples_until(data.encoded_frames);
+            render.render_offair_sound(num_samples as u32);
+            data.encoded_frames += 1;
         }
 
         Ok(())
@@ -581,11 +976,11 @@ impl DemoViewerImpl {
             if enc.overloaded() {
                 return Ok(());
             }
-            let cur_time = self.last_time.unwrap_or_default()
-                + Duration::from_nanos(
-                    (Duration::from_secs(1).as_nanos() / settings.encoder_settings.fps as u128)
-                        as u64,
-                );
+            // fixed time step, independent of how long rendering takes
+            let cur_time = Duration::from_nanos(
+                ((self.data.encoded_frames + 1) as u128 * Duration::from_secs(1).as_nanos()
+                    / settings.encoder_settings.fps as u128) as u64,
+            );
             (
                 cur_time,
                 self.last_time.replace(cur_time).unwrap_or_default(),
@@ -597,6 +992,16 @@ impl DemoViewerImpl {
                 self.last_time.replace(cur_time).unwrap_or(cur_time),
             )
         };
+        // the state is reset like for seeking, so nothing of the end of the range
+        // is left over. A video would never end, so the loop is ignored there.
+        if let Some((from, until)) = self.loop_range {
+            if !do_encoding && !self.inner.is_paused() && self.inner.cur_time >= until {
+                Self::set_time_and_reset_state(&mut self.client_map, &mut self.inner, from);
+            }
+        }
+        // drop the chunks that were not needed in the last frame
+        self.inner.prefetch.update();
+        self.preview.prefetch.update();
         Self::render_game(
             &mut self.inner,
             &mut self.data,
@@ -606,6 +1011,49 @@ impl DemoViewerImpl {
             &mut self.last_monotonic_tick,
             do_encoding,
         )?;
+        if self
+            .waveform_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            // without a waveform the seek bar simply stays as is
+            self.waveform = self.waveform_task.take().unwrap().get_storage().ok();
+        }
+        if self
+            .event_index_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            self.event_index = self
+                .event_index_task
+                .take()
+                .unwrap()
+                .get_storage()
+                .unwrap_or_default();
+            // the search might have happened before the index was ready
+            if let Some(query) = &self.action_query {
+                self.action_results = self.event_index.search(query);
+            }
+        }
+        if self
+            .event_jump_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            // a broken chunk simply has no events to jump to
+            if let Some(tick) = self
+                .event_jump_task
+                .take()
+                .unwrap()
+                .get_storage()
+                .ok()
+                .flatten()
+            {
+                let time = self.inner.tick_to_time(tick);
+                Self::set_time_and_reset_state(&mut self.client_map, &mut self.inner, time);
+            }
+        }
+        self.bookmarks.update(&self.io);
         self.demo_ui.render(
             &mut DemoPlayerUiRenderPipe {
                 cur_time: &self.time.time_get_nanoseconds(),
@@ -616,9 +1064,22 @@ impl DemoViewerImpl {
                     cur_duration: &self.inner.cur_time,
                     max_duration: &self.inner.demo.header.len,
                     speed: &self.inner.speed,
+                    preserve_sound_pitch: &self.inner.preserve_sound_pitch,
                     events: &mut self.events,
                     state: &mut self.ui_state,
                     name: &self.demo_name,
+                    waveform: self.waveform.as_deref(),
+                    povs: &self.povs,
+                    cur_pov: &self.inner.cur_pov,
+                    camera: &self.inner.camera,
+                    characters: &self.inner.characters,
+                    split_screen: &self.inner.split_screen,
+                    bookmarks: self.bookmarks.bookmarks(),
+                    markers: &self.event_index.markers,
+                    action_results: &self.action_results,
+                    loop_range: &self.loop_range,
+                    corrupted: &self.inner.demo.corrupted.load(Ordering::Relaxed),
+                    mod_warnings: &self.inner.demo.mod_warnings,
                 },
             },
             input,
@@ -665,8 +1126,12 @@ impl DemoViewerImpl {
                             as u64,
                     ));
                 }
-                DemoViewerEvent::Backward => todo!(),
-                DemoViewerEvent::Forward => todo!(),
+                DemoViewerEvent::Backward => {
+                    self.inner.seek = Some(DemoSeek::Backward);
+                }
+                DemoViewerEvent::Forward => {
+                    self.inner.seek = Some(DemoSeek::Forward);
+                }
                 DemoViewerEvent::SpeedSlower => {
                     self.inner.speed /= ffixed::from_num(2);
                     self.inner.speed = self
@@ -684,10 +1149,56 @@ impl DemoViewerImpl {
                 DemoViewerEvent::SpeedReset => {
                     self.inner.speed = ffixed::from_num(1.0);
                 }
+                DemoViewerEvent::SetSpeed { speed } => {
+                    self.inner.speed = ffixed::from_num(speed.clamp(0.1, 8.0));
+                }
+                DemoViewerEvent::PreserveSoundPitchToggle => {
+                    self.inner.preserve_sound_pitch = !self.inner.preserve_sound_pitch;
+                }
+                DemoViewerEvent::ScoreboardToggle => {
+                    self.inner.show_scoreboard = !self.inner.show_scoreboard;
+                }
+                DemoViewerEvent::FollowCharacter { id } => {
+                    self.inner.camera = DemoViewerCamera::Follow(id);
+                }
+                DemoViewerEvent::SplitScreen { id } => {
+                    self.inner.split_screen = id;
+                }
+                DemoViewerEvent::FreeCamera => {
+                    if !matches!(self.inner.camera, DemoViewerCamera::Free { .. }) {
+                        self.inner.camera = DemoViewerCamera::Free {
+                            pos: self.inner.camera_pos,
+                            zoom: 1.0,
+                        };
+                    }
+                }
+                DemoViewerEvent::FreeCameraMove { diff } => {
+                    if let DemoViewerCamera::Free { pos, zoom } = &mut self.inner.camera {
+                        let (mut width, mut height) = (0.0, 0.0);
+                        RenderTools::calc_canvas_params(
+                            self.data.canvas_handle.canvas_aspect(),
+                            *zoom,
+                            &mut width,
+                            &mut height,
+                        );
+                        *pos += vec2::new(diff.x * width, diff.y * height);
+                    }
+                }
+                DemoViewerEvent::FreeCameraZoom { factor } => {
+                    if let DemoViewerCamera::Free { zoom, .. } = &mut self.inner.camera {
+                        *zoom = (*zoom * factor).clamp(0.2, 5.0);
+                    }
+                }
+                DemoViewerEvent::SwitchPov { pov } => {
+                    self.inner.cur_pov = pov;
+                    // the snapshots of the old point of view must not be mixed with the new ones
+                    let time = self.inner.cur_time;
+                    Self::set_time_and_reset_state(&mut self.client_map, &mut self.inner, time);
+                }
                 DemoViewerEvent::Export(data) => {
                     let demo = &self.inner.demo;
                     let ext = &demo.header_ext;
-                    let mut recorder = DemoRecorder::new(
+                    let recorder = DemoRecorder::new(
                         DemoRecorderCreateProps {
                             map: ext.map.clone(),
                             map_hash: ext.map_hash,
@@ -696,39 +1207,89 @@ impl DemoViewerImpl {
                             physics_module: ext.physics_mod.clone(),
                             render_module: ext.render_mod.clone(),
                             physics_group_name: ext.physics_group_name.clone(),
+                            encryption_passphrase: demo.passphrase.clone(),
+                            split: Default::default(),
+                            record_inputs: false,
+                            signing_key: None,
+                            on_saved: None,
                             io: self.io.clone(),
                         },
                         ext.ticks_per_second,
                         Some(data.name),
                     );
+                    let mut recorder = match recorder {
+                        Ok(recorder) => recorder,
+                        Err(err) => {
+                            log::error!(target: "demo", "failed to export the demo: {err}");
+                            continue;
+                        }
+                    };
                     self.preview.set_time_and_clear_chunks(data.left);
 
                     let last_monotonic_tick = self.preview.time_to_tick_impl(data.right);
                     let mut monotonic_tick = self.preview.time_to_tick();
 
+                    // the snapshots are opaque, so the game is needed to redact them
+                    let mut redaction = data.redaction.map(|redaction| {
+                        let ClientMapFile::Game(GameMap { game, .. }) =
+                            self.preview_client_map.try_get_mut().unwrap()
+                        else {
+                            panic!("not a game map")
+                        };
+                        (game, DemoRedaction::new(redaction.clone()), redaction)
+                    });
+
                     while monotonic_tick <= last_monotonic_tick {
+                        // the export needs every chunk, so it waits for them
                         DemoViewerInner::check_chunks(
                             &self.preview.demo,
+                            &mut self.preview.prefetch,
                             &mut self.preview.cur_snapshots,
                             &self.preview.demo.tail.snapshots_index,
                             monotonic_tick,
+                            true,
                         );
                         DemoViewerInner::check_chunks(
                             &self.preview.demo,
+                            &mut self.preview.prefetch,
                             &mut self.preview.cur_events,
                             &self.preview.demo.tail.events_index,
                             monotonic_tick,
+                            true,
                         );
 
                         if let Some(snapshot) = self.preview.cur_snapshots.get(&monotonic_tick) {
-                            recorder.add_snapshot(monotonic_tick, snapshot.clone());
+                            match &mut redaction {
+                                Some((game, redaction, snapshot_redaction)) => {
+                                    let mut snapshot_cow = MtPoolCow::new_without_pool();
+                                    snapshot_cow.to_mut().extend(snapshot.iter());
+                                    // remember the names to replace them in chat messages too
+                                    let _ = game.build_from_snapshot(&snapshot_cow);
+                                    for (id, character) in game.collect_characters_info().iter() {
+                                        redaction.add_player(id, &character.info.name);
+                                    }
+                                    recorder.add_snapshot(
+                                        monotonic_tick,
+                                        game.redact_snapshot(&snapshot_cow, snapshot_redaction)
+                                            .to_vec(),
+                                    );
+                                }
+                                None => recorder.add_snapshot(monotonic_tick, snapshot.clone()),
+                            }
                         }
                         if let Some(events) = self.preview.cur_events.get(&monotonic_tick) {
-                            for event in events
-                                .iter()
-                                .filter(|ev| !matches!(ev, DemoEvent::Chat(_)) || !data.remove_chat)
-                            {
-                                recorder.add_event(monotonic_tick, event.clone());
+                            for event in events.iter() {
+                                let event = match &mut redaction {
+                                    Some((_, redaction, _)) => {
+                                        redaction.redact_event(event.clone(), data.remove_chat)
+                                    }
+                                    None => (!matches!(event, DemoEvent::Chat(_))
+                                        || !data.remove_chat)
+                                        .then(|| event.clone()),
+                                };
+                                if let Some(event) = event {
+                                    recorder.add_event(monotonic_tick, event);
+                                }
                             }
                         }
 
@@ -738,13 +1299,67 @@ impl DemoViewerImpl {
                 DemoViewerEvent::SkipTo { time } => {
                     Self::set_time_and_reset_state(&mut self.client_map, &mut self.inner, time);
                 }
+                DemoViewerEvent::AddBookmark => {
+                    self.bookmarks.add(&self.io, self.inner.cur_time);
+                }
+                DemoViewerEvent::NextBookmark => {
+                    if let Some(time) = self.bookmarks.next(self.inner.cur_time) {
+                        Self::set_time_and_reset_state(&mut self.client_map, &mut self.inner, time);
+                    }
+                }
+                DemoViewerEvent::PrevBookmark => {
+                    if let Some(time) = self.bookmarks.prev(self.inner.cur_time) {
+                        Self::set_time_and_reset_state(&mut self.client_map, &mut self.inner, time);
+                    }
+                }
+                DemoViewerEvent::NextKill => {
+                    self.event_jump_task = Some(self.inner.find_event(
+                        &self.io.io_batcher,
+                        DemoEventKind::Kill,
+                        true,
+                    ));
+                }
+                DemoViewerEvent::PrevKill => {
+                    self.event_jump_task = Some(self.inner.find_event(
+                        &self.io.io_batcher,
+                        DemoEventKind::Kill,
+                        false,
+                    ));
+                }
+                DemoViewerEvent::SearchActions(query) => {
+                    self.action_results = self.event_index.search(&query);
+                    self.action_query = Some(query);
+                }
+                DemoViewerEvent::Loop { from, until } => {
+                    let (from, until) = (from.min(until), from.max(until));
+                    if from < until {
+                        self.loop_range = Some((from, until));
+                        if !(from..until).contains(&self.inner.cur_time) {
+                            Self::set_time_and_reset_state(
+                                &mut self.client_map,
+                                &mut self.inner,
+                                from,
+                            );
+                        }
+                    }
+                }
+                DemoViewerEvent::StopLoop => {
+                    self.loop_range = None;
+                }
                 DemoViewerEvent::PreviewAt { rect, time } => {
-                    Self::set_time_and_reset_state(
-                        &mut self.preview_client_map,
-                        &mut self.preview,
-                        time,
-                    );
-                    self.should_show_preview = Some(rect);
+                    if self
+                        .thumbnails
+                        .as_ref()
+                        .and_then(|thumbnails| thumbnails.get(time))
+                        .is_none()
+                    {
+                        Self::set_time_and_reset_state(
+                            &mut self.preview_client_map,
+                            &mut self.preview,
+                            time,
+                        );
+                    }
+                    self.should_show_preview = Some((rect, time));
                 }
                 DemoViewerEvent::Close => {
                     self.inner.is_closed = true;
@@ -752,29 +1367,83 @@ impl DemoViewerImpl {
             }
         }
 
-        if let Some(rect) = self.should_show_preview {
-            self.data
-                .canvas_handle
-                .switch_canvas(CommandSwitchCanvasModeType::Offscreen {
-                    id: DEMO_OFFSCREEN_ID,
-                    width: rect.width() as u32,
-                    height: rect.height() as u32,
-                    pixels_per_point: 0.25,
-                    has_multi_sampling: None,
-                });
+        // render the next thumbnail, the preview is reset on every hover anyway
+        if let Some(time) = self
+            .thumbnails
+            .as_mut()
+            .and_then(|thumbnails| thumbnails.next_to_render())
+        {
+            Self::set_time_and_reset_state(&mut self.preview_client_map, &mut self.preview, time);
+            if self.preview.load_chunks(false) {
+                self.data
+                    .canvas_handle
+                    .switch_canvas(CommandSwitchCanvasModeType::Offscreen {
+                        id: DEMO_THUMBNAIL_OFFSCREEN_ID,
+                        width: DEMO_THUMBNAIL_WIDTH,
+                        height: DEMO_THUMBNAIL_HEIGHT,
+                        pixels_per_point: 0.25,
+                        has_multi_sampling: None,
+                    });
+                Self::render_game(
+                    &mut self.preview,
+                    &mut self.data,
+                    &mut self.preview_client_map,
+                    Duration::ZERO,
+                    Duration::ZERO,
+                    &mut Default::default(),
+                    false,
+                )?;
+                self.data
+                    .canvas_handle
+                    .switch_canvas(CommandSwitchCanvasModeType::Onscreen);
+            } else if let Some(thumbnails) = &mut self.thumbnails {
+                thumbnails.render_later();
+            }
+            if let Some((_, time)) = self.should_show_preview {
+                Self::set_time_and_reset_state(
+                    &mut self.preview_client_map,
+                    &mut self.preview,
+                    time,
+                );
+            }
+        }
 
-            Self::render_game(
-                &mut self.preview,
-                &mut self.data,
-                &mut self.preview_client_map,
-                Duration::ZERO,
-                Duration::ZERO,
-                &mut Default::default(),
-                false,
-            )?;
-            self.data
-                .canvas_handle
-                .switch_canvas(CommandSwitchCanvasModeType::Onscreen);
+        // without a thumbnail, the preview is shown once its chunks are read
+        if let Some((rect, time)) = self.should_show_preview.filter(|&(_, time)| {
+            self.thumbnails
+                .as_ref()
+                .and_then(|thumbnails| thumbnails.get(time))
+                .is_some()
+                || self.preview.load_chunks(false)
+        }) {
+            let thumbnail = self
+                .thumbnails
+                .as_ref()
+                .and_then(|thumbnails| thumbnails.get(time));
+            if thumbnail.is_none() {
+                self.data
+                    .canvas_handle
+                    .switch_canvas(CommandSwitchCanvasModeType::Offscreen {
+                        id: DEMO_OFFSCREEN_ID,
+                        width: rect.width() as u32,
+                        height: rect.height() as u32,
+                        pixels_per_point: 0.25,
+                        has_multi_sampling: None,
+                    });
+
+                Self::render_game(
+                    &mut self.preview,
+                    &mut self.data,
+                    &mut self.preview_client_map,
+                    Duration::ZERO,
+                    Duration::ZERO,
+                    &mut Default::default(),
+                    false,
+                )?;
+                self.data
+                    .canvas_handle
+                    .switch_canvas(CommandSwitchCanvasModeType::Onscreen);
+            }
 
             let mut state = State::new();
             state.map_canvas(
@@ -790,8 +1459,12 @@ impl DemoViewerImpl {
             self.data.stream_handle.render_quads(
                 hi_closure!([
                     rect: &Rect,
+                    thumbnail: Option<&'a TextureContainer>
                 ], |mut stream_handle: QuadStreamHandle<'_>| -> () {
-                    stream_handle.set_offscreen_attachment_texture(DEMO_OFFSCREEN_ID);
+                    match thumbnail {
+                        Some(thumbnail) => stream_handle.set_texture(thumbnail),
+                        None => stream_handle.set_offscreen_attachment_texture(DEMO_OFFSCREEN_ID),
+                    }
                     stream_handle
                         .add_vertices(
                             StreamedQuad::default().from_pos_and_size(
@@ -832,6 +1505,9 @@ pub struct DemoViewerLoading {
     pub fonts: Arc<UiFontData>,
     encode_to_video: Option<DemoVideoEncodeProperties>,
     name: String,
+    bookmarks: DemoBookmarks,
+    /// Downloads missing maps & game mods of the demo.
+    resource_server: Option<Url>,
 }
 
 pub struct DemoViewerLoadingComponents {
@@ -841,6 +1517,7 @@ pub struct DemoViewerLoadingComponents {
     pub io: Io,
     encode_to_video: Option<DemoVideoEncodeProperties>,
     name: String,
+    bookmarks: DemoBookmarks,
 }
 
 pub enum DemoViewer {
@@ -857,43 +1534,68 @@ impl DemoViewer {
         demo_path: &Path,
         fonts: Arc<UiFontData>,
         encode_to_video: Option<DemoVideoEncodeProperties>,
+        passphrase: Option<String>,
+        resource_server: Option<Url>,
     ) -> Self {
         let fs = io.fs.clone();
         let demo_path_thread = demo_path.to_path_buf();
-        let read_demo = io.io_batcher.spawn(async move {
-            let demo = fs.read_file(&demo_path_thread).await?;
-
-            let mut writer: Vec<u8> = Default::default();
-
-            // read header
-            let (header, file_off): (DemoHeader, usize) = deser_ex(&demo, true)?;
-            let demo = &demo[file_off..];
-
-            // read header ext
-            let (header_ext, _): (DemoHeaderExt, usize) =
-                deser(decomp(&demo[0..header.size_ext as usize], &mut writer)?)?;
-
-            let demo = &demo[header.size_ext as usize..];
-            let chunks = &demo[0..header.size_chunks as usize];
-            let tail = &demo[header.size_chunks as usize..];
+        let can_download = resource_server.is_some();
+        let demo_task = io.io_batcher.spawn(async move {
+            // demos in the save directory are streamed from their file,
+            // others are read completely
+            let streamed = if fs.file_exists(&demo_path_thread).await {
+                read_demo_streamed(&fs, &demo_path_thread).await?
+            } else {
+                None
+            };
+            let (header, header_ext, chunks, tail) = match streamed {
+                Some(demo) => demo,
+                None => {
+                    let demo = fs.read_file(&demo_path_thread).await?;
+                    let (header, header_ext, chunks, tail) = read_demo(&demo)?;
+                    (
+                        header,
+                        header_ext,
+                        DemoChunks::Memory(Arc::new(chunks.to_vec())),
+                        tail,
+                    )
+                }
+            };
 
-            // read tail
-            let (tail, _): (DemoTail, usize) = deser(decomp(tail, &mut writer)?)?;
-            anyhow::ensure!(
-                !tail.snapshots_index.is_empty(),
-                "no snapshot index found in demo tail."
-            );
+            let key = header_ext
+                .encryption
+                .as_ref()
+                .map(|encryption| {
+                    encryption.key(
+                        passphrase
+                            .as_deref()
+                            .ok_or_else(|| anyhow!("this demo is encrypted, a key is required."))?,
+                    )
+                })
+                .transpose()?;
+
+            // fail early, instead of desyncing later
+            let mod_warnings = check_game_mods(
+                &fs,
+                &header_ext.physics_mod,
+                &header_ext.render_mod,
+                can_download,
+            )
+            .await?;
 
-            // read all chunks
             Ok(DemoContainer {
                 header,
                 header_ext,
-                demo_chunks: chunks.to_vec(),
+                chunks,
                 tail,
+                key,
+                passphrase,
+                corrupted: Default::default(),
+                mod_warnings,
             })
         });
         Self::Loading(Box::new(DemoViewerLoading {
-            task: read_demo,
+            task: demo_task,
             io: io.clone(),
             thread_pool: thread_pool.clone(),
             fonts,
@@ -903,6 +1605,8 @@ impl DemoViewer {
                 .unwrap_or_default()
                 .to_string_lossy()
                 .to_string(),
+            bookmarks: DemoBookmarks::new(io, demo_path),
+            resource_server,
         }))
     }
 
@@ -943,6 +1647,8 @@ impl DemoViewer {
                     fonts,
                     encode_to_video,
                     name,
+                    bookmarks,
+                    resource_server,
                 } = *loading;
                 if task.is_finished() {
                     let demo_container = task.get_storage()?;
@@ -961,10 +1667,12 @@ impl DemoViewer {
                                     .header_ext
                                     .physics_group_name
                                     .clone(),
-                                resource_download_server: None,
+                                resource_download_server: resource_server.clone(),
                                 fonts: fonts.clone(),
                                 sound_props,
                             },
+                            // same as the one checked by `check_game_mods`
+                            RENDER_MODS_DEFAULT,
                         )
                     };
                     *self = Self::LoadingComponents(Box::new(DemoViewerLoadingComponents {
@@ -988,6 +1696,7 @@ impl DemoViewer {
                         io,
                         encode_to_video,
                         name,
+                        bookmarks,
                     }));
                 } else {
                     *self = Self::Loading(Box::new(DemoViewerLoading {
@@ -997,6 +1706,8 @@ impl DemoViewer {
                         fonts,
                         encode_to_video,
                         name,
+                        bookmarks,
+                        resource_server,
                     }));
                 }
             }
@@ -1008,6 +1719,7 @@ impl DemoViewer {
                     io,
                     encode_to_video,
                     name,
+                    bookmarks,
                 } = *loading;
                 if client_map
                     .continue_loading(sound, graphics, backend, config, sys)
@@ -1029,6 +1741,7 @@ impl DemoViewer {
                         ui_creator,
                         encode_to_video,
                         name,
+                        bookmarks,
                     )));
                 } else {
                     *self = Self::LoadingComponents(Box::new(DemoViewerLoadingComponents {
@@ -1038,6 +1751,7 @@ impl DemoViewer {
                         io,
                         encode_to_video,
                         name,
+                        bookmarks,
                     }));
                 }
             }
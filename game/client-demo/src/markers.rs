@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use client_ui::demo_player::user_data::{
+    DemoMarker, DemoMarkerKind, DemoPlayerActionKind, DemoPlayerActionQuery,
+};
+use demo::{DemoEvent, DemoEvents};
+use game_interface::{
+    events::{
+        GameFlagEvent, GameFlagEventSound, GameWorldAction, GameWorldActionKillWeapon,
+        GameWorldEntityEvent, GameWorldEvent, GameWorldGlobalEvent, GameWorldStatEvent,
+    },
+    types::{game::GameEntityId, weapons::WeaponType},
+};
+
+use crate::DemoContainer;
+
+fn marker_kind(ev: &GameWorldEvent) -> Option<DemoMarkerKind> {
+    match ev {
+        GameWorldEvent::Global(GameWorldGlobalEvent::Action(GameWorldAction::Kill { .. })) => {
+            Some(DemoMarkerKind::Kill)
+        }
+        GameWorldEvent::Global(GameWorldGlobalEvent::Stat(GameWorldStatEvent::RoundEnd)) => {
+            Some(DemoMarkerKind::RoundEnd)
+        }
+        GameWorldEvent::Positioned(ev) => match ev.ev {
+            GameWorldEntityEvent::Flag {
+                ev: GameFlagEvent::Sound(GameFlagEventSound::Capture),
+            } => Some(DemoMarkerKind::FlagCapture),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Something a player did, see [`DemoEventIndex::search`].
+#[derive(Debug, Clone, Copy)]
+pub struct DemoPlayerAction {
+    pub time: Duration,
+    pub player: GameEntityId,
+    pub kind: DemoPlayerActionKind,
+    /// The weapon of kills & deaths, `None` for ninja & world kills.
+    pub weapon: Option<WeaponType>,
+}
+
+fn player_actions(ev: &GameWorldEvent, time: Duration, actions: &mut Vec<DemoPlayerAction>) {
+    match ev {
+        GameWorldEvent::Global(GameWorldGlobalEvent::Action(GameWorldAction::Kill {
+            killer,
+            victims,
+            weapon,
+            ..
+        })) => {
+            let weapon = match weapon {
+                GameWorldActionKillWeapon::Weapon { weapon } => Some(*weapon),
+                GameWorldActionKillWeapon::Ninja | GameWorldActionKillWeapon::World => None,
+            };
+            actions.extend(killer.iter().map(|&player| DemoPlayerAction {
+                time,
+                player,
+                kind: DemoPlayerActionKind::Kill,
+                weapon,
+            }));
+            actions.extend(victims.iter().map(|&player| DemoPlayerAction {
+                time,
+                player,
+                kind: DemoPlayerActionKind::Death,
+                weapon,
+            }));
+        }
+        GameWorldEvent::Positioned(ev) => {
+            if let (
+                Some(player),
+                GameWorldEntityEvent::Flag {
+                    ev: GameFlagEvent::Sound(GameFlagEventSound::Capture),
+                },
+            ) = (ev.owner_id, &ev.ev)
+            {
+                actions.push(DemoPlayerAction {
+                    time,
+                    player,
+                    kind: DemoPlayerActionKind::FlagCapture,
+                    weapon: None,
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The notable moments & player actions of the whole demo, sorted by time.
+#[derive(Debug, Default)]
+pub struct DemoEventIndex {
+    pub markers: Vec<DemoMarker>,
+    pub actions: Vec<DemoPlayerAction>,
+}
+
+impl DemoEventIndex {
+    /// The times of all actions that match the query.
+    pub fn search(&self, query: &DemoPlayerActionQuery) -> Vec<Duration> {
+        let mut times: Vec<Duration> = self
+            .actions
+            .iter()
+            .filter(|action| {
+                action.kind == query.kind
+                    && query.player.map_or(true, |player| action.player == player)
+                    && (action.kind == DemoPlayerActionKind::FlagCapture
+                        || query
+                            .weapon
+                            .map_or(true, |weapon| action.weapon == Some(weapon)))
+            })
+            .map(|action| action.time)
+            .collect();
+        // e.g. multi kills
+        times.dedup();
+        times
+    }
+}
+
+/// Collects the kills, flag captures & round ends of the whole demo
+/// and the actions of all players.
+/// Multiple markers of the same kind in the same tick are merged.
+///
+/// Like [`crate::waveform::generate`], this reads all event chunks.
+pub async fn generate(demo: &DemoContainer) -> anyhow::Result<DemoEventIndex> {
+    let first_tick = demo
+        .tail
+        .snapshots_index
+        .keys()
+        .next()
+        .copied()
+        .unwrap_or(u64::MAX)
+        .min(
+            demo.tail
+                .events_index
+                .keys()
+                .next()
+                .copied()
+                .unwrap_or(u64::MAX),
+        );
+    let tick_time = Duration::from_secs(1) / demo.header_ext.ticks_per_second.get() as u32;
+
+    let mut markers = Vec::new();
+    let mut actions = Vec::new();
+    for &chunk_offset in demo.tail.events_index.values() {
+        let chunks = demo.read_chunks::<DemoEvents>(chunk_offset).await;
+        for (tick, events) in chunks {
+            let time = tick_time * tick.saturating_sub(first_tick) as u32;
+            let world_events = events
+                .iter()
+                .filter_map(|ev| match ev {
+                    DemoEvent::Game(evs) => Some(evs),
+                    _ => None,
+                })
+                .flat_map(|evs| evs.worlds.values())
+                .flat_map(|world| world.events.values());
+            world_events
+                .clone()
+                .for_each(|ev| player_actions(ev, time, &mut actions));
+            let kinds = world_events
+                .filter_map(marker_kind)
+                .fold(Vec::new(), |mut kinds, kind| {
+                    if !kinds.contains(&kind) {
+                        kinds.push(kind);
+                    }
+                    kinds
+                });
+            markers.extend(kinds.into_iter().map(|kind| DemoMarker { time, kind }));
+        }
+    }
+    markers.sort_by_key(|marker| marker.time);
+    actions.sort_by_key(|action| action.time);
+
+    Ok(DemoEventIndex { markers, actions })
+}
@@ -0,0 +1,176 @@
+use std::{
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use graphics::{
+    graphics::graphics::Graphics,
+    graphics_mt::GraphicsMultiThreaded,
+    handles::texture::texture::{GraphicsTextureHandle, TextureContainer},
+};
+use graphics_backend::backend::GraphicsBackend;
+use graphics_backend_traits::{
+    frame_fetcher_plugin::{
+        BackendFrameFetcher, BackendPresentedImageData, FetchCanvasError, FetchCanvasIndex,
+        OffscreenCanvasId,
+    },
+    traits::GraphicsBackendInterface,
+};
+use graphics_types::{
+    commands::{TexFlags, TexFormat},
+    types::{GraphicsMemoryAllocationType, ImageFormat},
+};
+
+/// How many thumbnails are generated, evenly distributed over the demo.
+const DEMO_THUMBNAIL_COUNT: usize = 64;
+pub const DEMO_THUMBNAIL_WIDTH: u32 = 256;
+pub const DEMO_THUMBNAIL_HEIGHT: u32 = 144;
+
+const FETCHER_NAME: &str = "demo-thumbnails";
+
+/// Fetches the offscreen canvas the thumbnails are rendered to.
+#[derive(Debug)]
+struct DemoThumbnailFetcher {
+    offscreen_id: OffscreenCanvasId,
+    frame: Mutex<Option<BackendPresentedImageData>>,
+    /// The backend failed to fetch the canvas.
+    failed: AtomicBool,
+}
+
+impl BackendFrameFetcher for DemoThumbnailFetcher {
+    fn next_frame(&self, frame_data: BackendPresentedImageData) {
+        *self.frame.lock().unwrap() = Some(frame_data);
+    }
+
+    fn current_fetch_index(&self) -> FetchCanvasIndex {
+        FetchCanvasIndex::Offscreen(self.offscreen_id)
+    }
+
+    fn fetch_err(&self, err: FetchCanvasError) {
+        // the canvas only exists in frames that render a thumbnail
+        if !matches!(err, FetchCanvasError::CanvasNotFound) {
+            self.failed.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Small previews of the demo at evenly distributed times,
+/// so that hovering the timeline does not re-simulate the demo every time.
+///
+/// The thumbnails are rendered one after another in the background,
+/// see [`DemoThumbnails::next_to_render`].
+pub struct DemoThumbnails {
+    backend: Rc<GraphicsBackend>,
+    texture_handle: GraphicsTextureHandle,
+    graphics_mt: GraphicsMultiThreaded,
+    fetcher: Arc<DemoThumbnailFetcher>,
+
+    len: Duration,
+    textures: Vec<Option<TextureContainer>>,
+    /// A thumbnail was rendered, but its frame was not fetched yet.
+    waiting: bool,
+}
+
+impl DemoThumbnails {
+    pub fn new(
+        graphics: &Graphics,
+        backend: &Rc<GraphicsBackend>,
+        offscreen_id: OffscreenCanvasId,
+        len: Duration,
+    ) -> anyhow::Result<Self> {
+        let fetcher = Arc::new(DemoThumbnailFetcher {
+            offscreen_id,
+            frame: Default::default(),
+            failed: Default::default(),
+        });
+        backend.attach_frame_fetcher(FETCHER_NAME.into(), fetcher.clone())?;
+        Ok(Self {
+            backend: backend.clone(),
+            texture_handle: graphics.texture_handle.clone(),
+            graphics_mt: graphics.get_graphics_mt(),
+            fetcher,
+            len,
+            textures: (0..DEMO_THUMBNAIL_COUNT).map(|_| None).collect(),
+            waiting: false,
+        })
+    }
+
+    fn time_of(&self, index: usize) -> Duration {
+        self.len
+            .mul_f64(index as f64 / (DEMO_THUMBNAIL_COUNT - 1) as f64)
+    }
+
+    /// The thumbnail closest to the time, if it's already rendered.
+    pub fn get(&self, time: Duration) -> Option<&TextureContainer> {
+        let index = (time.as_secs_f64() / self.len.as_secs_f64().max(f64::EPSILON)
+            * (DEMO_THUMBNAIL_COUNT - 1) as f64)
+            .round() as usize;
+        self.textures
+            .get(index.min(DEMO_THUMBNAIL_COUNT - 1))
+            .and_then(|texture| texture.as_ref())
+    }
+
+    /// Uploads the fetched thumbnail, if any, and returns the time of the
+    /// thumbnail that has to be rendered to the offscreen canvas in this frame.
+    ///
+    /// Only one thumbnail is rendered at a time, so that the fetched frame
+    /// clearly belongs to it.
+    pub fn next_to_render(&mut self) -> Option<Duration> {
+        let index = self.textures.iter().position(|texture| texture.is_none())?;
+        if self.waiting {
+            let frame = self.fetcher.frame.lock().unwrap().take();
+            let Some(frame) = frame else {
+                // render it again, if fetching failed
+                self.waiting = !self.fetcher.failed.swap(false, Ordering::Relaxed);
+                return (!self.waiting).then(|| self.time_of(index));
+            };
+            self.waiting = false;
+            let mut mem = self
+                .graphics_mt
+                .mem_alloc(GraphicsMemoryAllocationType::Texture {
+                    width: frame.width as usize,
+                    height: frame.height as usize,
+                    depth: 1,
+                    is_3d_tex: false,
+                    flags: TexFlags::empty(),
+                });
+            mem.as_mut_slice().copy_from_slice(&frame.dest_data_buffer);
+            // the offscreen canvas is not opaque everywhere
+            mem.as_mut_slice()
+                .chunks_exact_mut(4)
+                .for_each(|pixel| pixel[3] = 255);
+            self.textures[index] = self
+                .texture_handle
+                .load_texture(
+                    frame.width as usize,
+                    frame.height as usize,
+                    ImageFormat::Rgba,
+                    mem,
+                    TexFormat::Rgba,
+                    TexFlags::empty(),
+                    "demo-thumbnail",
+                )
+                .ok();
+        }
+        let index = self.textures.iter().position(|texture| texture.is_none())?;
+        self.waiting = true;
+        Some(self.time_of(index))
+    }
+
+    /// The thumbnail of [`DemoThumbnails::next_to_render`] could not be rendered
+    /// in this frame, e.g. because its chunks are still being read.
+    /// It's returned again by the next call.
+    pub fn render_later(&mut self) {
+        self.waiting = false;
+    }
+}
+
+impl Drop for DemoThumbnails {
+    fn drop(&mut self) {
+        let _ = self.backend.detach_frame_fetcher(FETCHER_NAME.into());
+    }
+}
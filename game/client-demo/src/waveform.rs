@@ -0,0 +1,280 @@
+use std::{collections::HashMap, io::Cursor, path::Path, sync::Arc};
+
+use base_io_traits::fs_traits::FileSystemInterface;
+use demo::{DemoEvent, DemoEvents};
+use game_interface::{
+    events::{
+        GameBuffEvent, GameBuffNinjaEvent, GameBuffNinjaEventSound, GameCharacterEvent,
+        GameCharacterEventSound, GameDebuffEvent, GameDebuffFrozenEvent, GameFlagEvent,
+        GameFlagEventSound, GameGrenadeEvent, GameGrenadeEventSound, GameLaserEvent,
+        GameLaserEventSound, GamePickupArmorEvent, GamePickupArmorEventSound, GamePickupEvent,
+        GamePickupHeartEvent, GamePickupHeartEventSound, GameShotgunEvent, GameShotgunEventSound,
+        GameWorldEntityEvent, GameWorldEvent,
+    },
+    types::{flag::FlagType, weapons::WeaponType},
+};
+use kira::sound::static_sound::StaticSoundData;
+
+use crate::DemoContainer;
+
+/// Number of samples of a waveform, independent of the demo length.
+pub const WAVEFORM_SAMPLES: usize = 512;
+
+/// The sound of an event: the directory of the default container
+/// and the name of the sound files, e.g. `death` for `death1.ogg`, `death2.ogg`, ...
+type EventSound = (&'static str, &'static str);
+
+const SKIN_SOUNDS: &str = "skins/default/audio";
+const HOOK_SOUNDS: &str = "hooks/default";
+const NINJA_SOUNDS: &str = "ninjas/default/audio";
+const FREEZE_SOUNDS: &str = "freezes/default/audio";
+const CTF_SOUNDS: &str = "ctfs/default/audio";
+
+fn weapon_sounds(weapon: WeaponType) -> &'static str {
+    match weapon {
+        WeaponType::Hammer => "weapons/default/hammer",
+        WeaponType::Gun => "weapons/default/gun",
+        WeaponType::Shotgun => "weapons/default/shotgun",
+        WeaponType::Grenade => "weapons/default/grenade",
+        WeaponType::Laser => "weapons/default/laser",
+    }
+}
+
+/// The sound that the render module plays for the event.
+fn event_sound(ev: &GameWorldEntityEvent) -> Option<EventSound> {
+    Some(match ev {
+        GameWorldEntityEvent::Character {
+            ev: GameCharacterEvent::Sound(ev),
+        } => match ev {
+            GameCharacterEventSound::WeaponSwitch { new_weapon } => {
+                (weapon_sounds(*new_weapon), "switch")
+            }
+            GameCharacterEventSound::NoAmmo { weapon } => (weapon_sounds(*weapon), "noammo"),
+            GameCharacterEventSound::HammerFire => (weapon_sounds(WeaponType::Hammer), "fire"),
+            GameCharacterEventSound::GunFire => (weapon_sounds(WeaponType::Gun), "fire"),
+            GameCharacterEventSound::GrenadeFire => (weapon_sounds(WeaponType::Grenade), "fire"),
+            GameCharacterEventSound::LaserFire => (weapon_sounds(WeaponType::Laser), "fire"),
+            GameCharacterEventSound::ShotgunFire => (weapon_sounds(WeaponType::Shotgun), "fire"),
+            GameCharacterEventSound::GroundJump => (SKIN_SOUNDS, "ground_jump"),
+            GameCharacterEventSound::AirJump => (SKIN_SOUNDS, "air_jump"),
+            GameCharacterEventSound::HookHitPlayer => (HOOK_SOUNDS, "hit_player"),
+            GameCharacterEventSound::HookHitHookable => (HOOK_SOUNDS, "hit_hookable"),
+            GameCharacterEventSound::HookHitUnhookable => (HOOK_SOUNDS, "hit_unhookable"),
+            GameCharacterEventSound::Spawn => (SKIN_SOUNDS, "spawn"),
+            GameCharacterEventSound::Death => (SKIN_SOUNDS, "death"),
+            GameCharacterEventSound::Pain { long: true } => (SKIN_SOUNDS, "pain_long"),
+            GameCharacterEventSound::Pain { long: false } => (SKIN_SOUNDS, "pain_short"),
+            GameCharacterEventSound::Hit { strong: true } => (SKIN_SOUNDS, "hit_strong"),
+            GameCharacterEventSound::Hit { strong: false } => (SKIN_SOUNDS, "hit_weak"),
+            GameCharacterEventSound::HammerHit => (weapon_sounds(WeaponType::Hammer), "hit"),
+        },
+        GameWorldEntityEvent::Character {
+            ev: GameCharacterEvent::Buff(GameBuffEvent::Ninja(GameBuffNinjaEvent::Sound(ev))),
+        } => match ev {
+            GameBuffNinjaEventSound::Spawn => (NINJA_SOUNDS, "spawn"),
+            GameBuffNinjaEventSound::Collect => (NINJA_SOUNDS, "collect"),
+            GameBuffNinjaEventSound::Attack => (NINJA_SOUNDS, "attack"),
+            GameBuffNinjaEventSound::Hit => (NINJA_SOUNDS, "hit"),
+        },
+        GameWorldEntityEvent::Character {
+            ev: GameCharacterEvent::Debuff(GameDebuffEvent::Frozen(ev)),
+        } => match ev {
+            GameDebuffFrozenEvent::Sound(_) => (FREEZE_SOUNDS, "attack"),
+            GameDebuffFrozenEvent::Effect(_) => return None,
+        },
+        GameWorldEntityEvent::Grenade {
+            ev: GameGrenadeEvent::Sound(ev),
+        } => match ev {
+            GameGrenadeEventSound::Spawn => (weapon_sounds(WeaponType::Grenade), "spawn"),
+            GameGrenadeEventSound::Collect => (weapon_sounds(WeaponType::Grenade), "collect"),
+            GameGrenadeEventSound::Explosion => (weapon_sounds(WeaponType::Grenade), "explosion"),
+        },
+        GameWorldEntityEvent::Laser {
+            ev: GameLaserEvent::Sound(ev),
+        } => match ev {
+            GameLaserEventSound::Spawn => (weapon_sounds(WeaponType::Laser), "spawn"),
+            GameLaserEventSound::Collect => (weapon_sounds(WeaponType::Laser), "collect"),
+            GameLaserEventSound::Bounce => (weapon_sounds(WeaponType::Laser), "bounce"),
+        },
+        GameWorldEntityEvent::Shotgun {
+            ev: GameShotgunEvent::Sound(ev),
+        } => match ev {
+            GameShotgunEventSound::Spawn => (weapon_sounds(WeaponType::Shotgun), "spawn"),
+            GameShotgunEventSound::Collect => (weapon_sounds(WeaponType::Shotgun), "collect"),
+        },
+        GameWorldEntityEvent::Flag {
+            ev: GameFlagEvent::Sound(ev),
+        } => match ev {
+            GameFlagEventSound::Collect(FlagType::Red) => (CTF_SOUNDS, "collect_friendly"),
+            GameFlagEventSound::Collect(FlagType::Blue) => (CTF_SOUNDS, "collect_opponents"),
+            GameFlagEventSound::Capture => (CTF_SOUNDS, "capture"),
+            GameFlagEventSound::Drop => (CTF_SOUNDS, "drop"),
+            GameFlagEventSound::Return => (CTF_SOUNDS, "return"),
+        },
+        GameWorldEntityEvent::Pickup {
+            ev: GamePickupEvent::Heart(GamePickupHeartEvent::Sound(ev)),
+        } => match ev {
+            GamePickupHeartEventSound::Spawn => ("games/default/audio/heart", "spawn"),
+            GamePickupHeartEventSound::Collect => ("games/default/audio/heart", "collect"),
+        },
+        GameWorldEntityEvent::Pickup {
+            ev: GamePickupEvent::Armor(GamePickupArmorEvent::Sound(ev)),
+        } => match ev {
+            GamePickupArmorEventSound::Spawn => ("games/default/audio/shield", "spawn"),
+            GamePickupArmorEventSound::Collect => ("games/default/audio/shield", "collect"),
+        },
+        _ => return None,
+    })
+}
+
+/// Whether the file is one of the random variants of the sound,
+/// e.g. `death2.ogg` for `death`.
+fn is_variant_of(file: &str, name: &str) -> bool {
+    file.strip_suffix(".ogg")
+        .and_then(|file| file.strip_prefix(name))
+        .is_some_and(|num| num.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// The energy (the squared samples over time) and the length in seconds
+/// of a sound, both the mean over its variants, since one of them is picked randomly.
+/// A sound that can't be loaded is silent.
+async fn load_sound(fs: &Arc<dyn FileSystemInterface>, (dir, name): EventSound) -> (f64, f64) {
+    let Ok(entries) = fs.entries_in_dir(Path::new(dir)).await else {
+        return (0.0, 0.0);
+    };
+    let mut sounds = Vec::new();
+    for file in entries.keys().filter(|file| is_variant_of(file, name)) {
+        let Ok(file) = fs.read_file(&Path::new(dir).join(file)).await else {
+            continue;
+        };
+        let Ok(sound) = StaticSoundData::from_cursor(Cursor::new(file)) else {
+            continue;
+        };
+        let sample_rate = sound.sample_rate.max(1) as f64;
+        let energy = sound
+            .frames
+            .iter()
+            .map(|frame| {
+                let sample = (frame.left as f64 + frame.right as f64) / 2.0;
+                sample * sample
+            })
+            .sum::<f64>()
+            / sample_rate;
+        sounds.push((energy, sound.frames.len() as f64 / sample_rate));
+    }
+    if sounds.is_empty() {
+        return (0.0, 0.0);
+    }
+    let (energy, len) = sounds.iter().fold((0.0, 0.0), |(energy, len), sound| {
+        (energy + sound.0, len + sound.1)
+    });
+    (energy / sounds.len() as f64, len / sounds.len() as f64)
+}
+
+/// Spreads the energy of a sound evenly over the samples it covers,
+/// `start` & `len` are in samples.
+fn add_energy(samples: &mut [f64], start: f64, len: f64, energy: f64) {
+    if energy <= 0.0 {
+        return;
+    }
+    let end = start + len.max(f64::EPSILON);
+    let mut pos = start;
+    while pos < end {
+        let index = pos as usize;
+        let Some(sample) = samples.get_mut(index) else {
+            break;
+        };
+        let next = (index as f64 + 1.0).min(end);
+        *sample += energy * (next - pos) / (end - start);
+        pos = next;
+    }
+}
+
+/// Generates the waveform of the whole demo.
+///
+/// Demos don't contain an audio track, the sounds are played
+/// from the recorded game events. So the waveform is mixed from the
+/// decoded sounds of these events, like the sound backend would do it,
+/// except that the distance to the sounds and custom skins are ignored.
+///
+/// Every sample is the loudness (RMS) in the range `[0, 1]`
+/// and covers an equal part of the demo length.
+/// This reads all event chunks, so it should be run as an io task.
+pub async fn generate(
+    demo: &DemoContainer,
+    fs: &Arc<dyn FileSystemInterface>,
+) -> anyhow::Result<Vec<f32>> {
+    let mut samples = vec![0.0_f64; WAVEFORM_SAMPLES];
+
+    let first_tick = demo
+        .tail
+        .snapshots_index
+        .keys()
+        .next()
+        .copied()
+        .unwrap_or(u64::MAX)
+        .min(
+            demo.tail
+                .events_index
+                .keys()
+                .next()
+                .copied()
+                .unwrap_or(u64::MAX),
+        );
+    let ticks_per_second = demo.header_ext.ticks_per_second.get() as f64;
+    let len = demo.header.len.as_secs_f64().max(0.0001);
+    let sample_len = len / WAVEFORM_SAMPLES as f64;
+
+    let mut sounds: HashMap<EventSound, (f64, f64)> = Default::default();
+    for &chunk_offset in demo.tail.events_index.values() {
+        let event_sounds: Vec<(f64, EventSound)> = demo
+            .read_chunks::<DemoEvents>(chunk_offset)
+            .await
+            .iter()
+            .flat_map(|(&tick, events)| {
+                let time = tick.saturating_sub(first_tick) as f64 / ticks_per_second;
+                events
+                    .iter()
+                    .filter_map(|ev| match ev {
+                        DemoEvent::Game(evs) => Some(evs),
+                        _ => None,
+                    })
+                    .flat_map(|evs| evs.worlds.values())
+                    .flat_map(|world| world.events.values())
+                    .filter_map(move |ev| match ev {
+                        GameWorldEvent::Positioned(ev) => event_sound(&ev.ev).map(|s| (time, s)),
+                        GameWorldEvent::Global(_) => None,
+                    })
+            })
+            .collect();
+
+        for (time, sound) in event_sounds {
+            let (energy, sound_len) = match sounds.get(&sound) {
+                Some(&loaded) => loaded,
+                None => {
+                    let loaded = load_sound(fs, sound).await;
+                    sounds.insert(sound, loaded);
+                    loaded
+                }
+            };
+            add_energy(
+                &mut samples,
+                time / sample_len,
+                sound_len / sample_len,
+                energy,
+            );
+        }
+    }
+
+    // the mean power of every sample, as amplitude
+    let samples: Vec<f32> = samples
+        .into_iter()
+        .map(|energy| (energy / sample_len).sqrt() as f32)
+        .collect();
+    let max = samples.iter().copied().fold(0.0, f32::max);
+    Ok(if max > 0.0 {
+        samples.into_iter().map(|sample| sample / max).collect()
+    } else {
+        samples
+    })
+}
@@ -6,7 +6,7 @@ use client_render_game::{
     map::render_map_base::{ClientMapRender, RenderMapLoading},
     render_game::{RenderGameCreateOptions, RenderGameInterface},
 };
-use config::config::ConfigEngine;
+use config::config::{ConfigDebug, ConfigEngine};
 
 use game_database::dummy::DummyDb;
 use game_interface::interface::GameStateCreateOptions;
@@ -21,13 +21,15 @@ use graphics_backend::backend::GraphicsBackend;
 use map::map::Map;
 use rayon::ThreadPool;
 use shared::{
-    game::state_wasm_manager::{GameStateMod, GameStateWasmManager, STATE_MODS_PATH},
+    game::state_wasm_manager::{GameStateMod, GameStateWasmManager},
     render::render_wasm_manager::RenderGameWasmManager,
 };
 
 use shared_base::network::messages::GameModification;
 use sound::sound::SoundManager;
 
+use crate::mod_check::state_mod_file_name;
+
 pub enum ClientGameStateModTask {
     Native,
     Ddnet,
@@ -63,6 +65,7 @@ pub struct ClientMapLoadingFile {
     pub game_mod_task: ClientGameStateModTask,
     pub game_options: GameStateCreateOptions,
     props: RenderGameCreateOptions,
+    render_mod: String,
 }
 
 impl ClientMapLoadingFile {
@@ -76,6 +79,7 @@ impl ClientMapLoadingFile {
         as_menu_map: bool,
         game_options: GameStateCreateOptions,
         props: RenderGameCreateOptions,
+        render_mod: &str,
     ) -> Self {
         let map_file_name = if let Some(map_hash) = map_hash {
             base_path.join(format!(
@@ -143,12 +147,7 @@ impl ClientMapLoadingFile {
                     file: {
                         let fs = io.fs.clone();
                         let http = io.http.clone();
-                        let game_mod_file_name = format!(
-                            "{}/{}_{}.wasm",
-                            STATE_MODS_PATH,
-                            name.as_str(),
-                            fmt_hash(&hash)
-                        );
+                        let game_mod_file_name = state_mod_file_name(name.as_str(), &hash);
                         let resource_download_server_thread =
                             props.resource_download_server.clone();
 
@@ -194,10 +193,12 @@ impl ClientMapLoadingFile {
             },
             props,
             game_options,
+            render_mod: render_mod.to_string(),
         }
     }
 }
 
+#[derive(Clone)]
 pub struct GameCreateProps {
     sound: SoundManager,
     graphics: Graphics,
@@ -208,6 +209,7 @@ pub struct GameCreateProps {
     map_file: Vec<u8>,
     props: RenderGameCreateOptions,
     config: ConfigEngine,
+    render_mod: String,
 }
 
 pub enum GameLoading {
@@ -215,7 +217,51 @@ pub enum GameLoading {
         task: IoBatcherTask<Vec<u8>>,
         props: Box<GameCreateProps>,
     },
-    Game(RenderGameWasmManager),
+    Game {
+        render: RenderGameWasmManager,
+        /// Kept to be able to switch the render module later.
+        props: Box<GameCreateProps>,
+    },
+}
+
+impl GameLoading {
+    fn new(props: Box<GameCreateProps>) -> Self {
+        let fs = props.io.fs.clone();
+        let render_mod = props.render_mod.clone();
+        Self::Task {
+            task: props
+                .io
+                .io_batcher
+                .spawn(async move { RenderGameWasmManager::load_module(&fs, &render_mod).await }),
+            props,
+        }
+    }
+
+    /// Creates the render game, as soon as the module was read.
+    fn continue_loading(self) -> Self {
+        match self {
+            Self::Task { task, props } if task.is_finished() => {
+                let file = task.get_storage().ok();
+                Self::Game {
+                    render: RenderGameWasmManager::new(
+                        &props.sound,
+                        &props.graphics,
+                        &props.backend,
+                        &props.io,
+                        &props.thread_pool,
+                        &props.sys,
+                        props.map_file.clone(),
+                        &props.config,
+                        &props.render_mod,
+                        file,
+                        props.props.clone(),
+                    ),
+                    props,
+                }
+            }
+            loading => loading,
+        }
+    }
 }
 
 pub enum ClientMapComponentLoadingType {
@@ -241,6 +287,7 @@ impl ClientMapComponentLoading {
         config: &ConfigEngine,
         as_menu_map: bool,
         props: RenderGameCreateOptions,
+        render_mod: String,
     ) -> Self {
         Self {
             ty: if as_menu_map {
@@ -255,23 +302,18 @@ impl ClientMapComponentLoading {
                     config,
                 )))
             } else {
-                let fs = io.fs.clone();
-                ClientMapComponentLoadingType::Game(GameLoading::Task {
-                    task: io
-                        .io_batcher
-                        .spawn(async move { RenderGameWasmManager::load_module(&fs).await }),
-                    props: Box::new(GameCreateProps {
-                        sound: sound.clone(),
-                        graphics: graphics.clone(),
-                        backend: backend.clone(),
-                        io: io.clone(),
-                        thread_pool: thread_pool.clone(),
-                        sys: sys.clone(),
-                        map_file: file,
-                        config: config.clone(),
-                        props,
-                    }),
-                })
+                ClientMapComponentLoadingType::Game(GameLoading::new(Box::new(GameCreateProps {
+                    sound: sound.clone(),
+                    graphics: graphics.clone(),
+                    backend: backend.clone(),
+                    io: io.clone(),
+                    thread_pool: thread_pool.clone(),
+                    sys: sys.clone(),
+                    map_file: file,
+                    config: config.clone(),
+                    props,
+                    render_mod,
+                })))
             },
             io,
             thread_pool,
@@ -283,6 +325,58 @@ pub struct GameMap {
     pub render: RenderGameWasmManager,
     // client local calculated game
     pub game: GameStateWasmManager,
+
+    render_props: Box<GameCreateProps>,
+    /// A render module that is loaded in the background,
+    /// the current one is used until it's ready.
+    next_render: Option<GameLoading>,
+}
+
+impl GameMap {
+    /// Loads another render module for the current map,
+    /// see [`GameMap::continue_render_mod_switch`].
+    ///
+    /// The game state is not touched, so this works on a running game.
+    pub fn switch_render_mod(&mut self, render_mod: &str) {
+        let mut props = self.render_props.clone();
+        props.render_mod = render_mod.to_string();
+        self.next_render = Some(GameLoading::new(props));
+    }
+
+    /// The render module that is currently loaded in the background, if any.
+    pub fn next_render_mod(&self) -> Option<&str> {
+        self.next_render.as_ref().map(|loading| match loading {
+            GameLoading::Task { props, .. } | GameLoading::Game { props, .. } => {
+                props.render_mod.as_str()
+            }
+        })
+    }
+
+    /// Replaces the render game with the one of [`GameMap::switch_render_mod`],
+    /// once it finished loading.
+    /// Returns `true` if the render game was replaced.
+    pub fn continue_render_mod_switch(&mut self, config: &ConfigDebug) -> bool {
+        let Some(loading) = self.next_render.take() else {
+            return false;
+        };
+        match loading.continue_loading() {
+            GameLoading::Game { mut render, props } => {
+                if render.continue_map_loading(config) {
+                    render.set_chat_commands(self.game.info.chat_commands.clone());
+                    self.render = render;
+                    self.render_props = props;
+                    true
+                } else {
+                    self.next_render = Some(GameLoading::Game { render, props });
+                    false
+                }
+            }
+            loading => {
+                self.next_render = Some(loading);
+                false
+            }
+        }
+    }
 }
 
 pub enum ClientMapFile {
@@ -319,6 +413,7 @@ impl ClientMapLoading {
         as_menu_map: bool,
         game_options: GameStateCreateOptions,
         props: RenderGameCreateOptions,
+        render_mod: &str,
     ) -> Self {
         Self::File(ClientMapLoadingFile::new(
             base_path,
@@ -330,6 +425,7 @@ impl ClientMapLoading {
             as_menu_map,
             game_options,
             props,
+            render_mod,
         ))
     }
 
@@ -383,6 +479,7 @@ impl ClientMapLoading {
                         config,
                         file.as_menu_map,
                         file.props,
+                        file.render_mod,
                     );
 
                     *self = Self::PrepareComponents {
@@ -404,27 +501,8 @@ impl ClientMapLoading {
                 game_options,
             } => {
                 match render.ty {
-                    ClientMapComponentLoadingType::Game(mut load_game) => {
-                        if let GameLoading::Task { task, props } = load_game {
-                            if task.is_finished() {
-                                let file = task.get_storage().ok();
-                                load_game = GameLoading::Game(RenderGameWasmManager::new(
-                                    &props.sound,
-                                    &props.graphics,
-                                    &props.backend,
-                                    &props.io,
-                                    &props.thread_pool,
-                                    &props.sys,
-                                    props.map_file,
-                                    &props.config,
-                                    file,
-                                    props.props,
-                                ));
-                            } else {
-                                load_game = GameLoading::Task { task, props };
-                            }
-                        }
-                        match load_game {
+                    ClientMapComponentLoadingType::Game(load_game) => {
+                        match load_game.continue_loading() {
                             GameLoading::Task { task, props } => {
                                 *self = Self::PrepareComponents {
                                     render: ClientMapComponentLoading {
@@ -440,7 +518,10 @@ impl ClientMapLoading {
                                     game_options,
                                 }
                             }
-                            GameLoading::Game(mut load_game) => {
+                            GameLoading::Game {
+                                render: mut load_game,
+                                props,
+                            } => {
                                 if load_game.continue_map_loading(&config.dbg) {
                                     let game = GameStateWasmManager::new(
                                         game_mod,
@@ -457,12 +538,17 @@ impl ClientMapLoading {
                                     *self = Self::Map(ClientMapFile::Game(GameMap {
                                         render: load_game,
                                         game,
+                                        render_props: props,
+                                        next_render: None,
                                     }));
                                 } else {
                                     *self = Self::PrepareComponents {
                                         render: ClientMapComponentLoading {
                                             ty: ClientMapComponentLoadingType::Game(
-                                                GameLoading::Game(load_game),
+                                                GameLoading::Game {
+                                                    render: load_game,
+                                                    props,
+                                                },
                                             ),
                                             io: render.io,
                                             thread_pool: render.thread_pool,
@@ -1,3 +1,4 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod client_map;
+pub mod mod_check;
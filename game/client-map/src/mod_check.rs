@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use base::hash::{fmt_hash, generate_hash_for, Hash};
+use base_io_traits::fs_traits::FileSystemInterface;
+use shared::{
+    game::state_wasm_manager::STATE_MODS_PATH,
+    render::render_wasm_manager::{RENDER_MODS_DEFAULT, RENDER_MODS_PATH},
+};
+use shared_base::network::messages::GameModification;
+
+/// The path of a physics mod, the hash makes sure that exactly
+/// the version the server (or demo) uses is loaded.
+pub fn state_mod_file_name(name: &str, hash: &Hash) -> String {
+    format!("{}/{}_{}.wasm", STATE_MODS_PATH, name, fmt_hash(hash))
+}
+
+fn mod_name(game_mod: &GameModification) -> String {
+    match game_mod {
+        GameModification::Native => "native".to_string(),
+        GameModification::Ddnet => "ddnet".to_string(),
+        GameModification::Wasm { name, hash } => format!("{} ({})", name.as_str(), fmt_hash(hash)),
+    }
+}
+
+/// Compares the game mods a demo was recorded with against the
+/// locally available modules.
+///
+/// Returns a warning for every mismatch that still allows the playback,
+/// e.g. a different render mod.
+/// A missing physics mod is an error, unless `can_download` is set,
+/// in which case it is downloaded from the resource server while loading.
+pub async fn check_game_mods(
+    fs: &Arc<dyn FileSystemInterface>,
+    physics_mod: &GameModification,
+    render_mod: &GameModification,
+    can_download: bool,
+) -> anyhow::Result<Vec<String>> {
+    let mut warnings = Vec::new();
+
+    if let GameModification::Wasm { name, hash } = physics_mod {
+        match fs
+            .read_file(state_mod_file_name(name.as_str(), hash).as_ref())
+            .await
+        {
+            Ok(file) => {
+                if generate_hash_for(&file) != *hash {
+                    warnings.push(format!(
+                        "The local physics mod {} was modified, the playback might desync.",
+                        mod_name(physics_mod)
+                    ));
+                }
+            }
+            Err(_) => anyhow::ensure!(
+                can_download,
+                "The physics mod {} of this demo is missing. \
+                Set a resource server to download it.",
+                mod_name(physics_mod)
+            ),
+        }
+    }
+
+    // the render mod is always the local one
+    let local_render_mod = fs
+        .read_file(format!("{RENDER_MODS_PATH}/{RENDER_MODS_DEFAULT}.wasm").as_ref())
+        .await
+        .ok()
+        .map(|file| generate_hash_for(&file));
+    let matches = match (render_mod, local_render_mod) {
+        (GameModification::Wasm { hash, .. }, Some(local_hash)) => *hash == local_hash,
+        (GameModification::Wasm { .. }, None) => false,
+        (_, local_hash) => local_hash.is_none(),
+    };
+    if !matches {
+        warnings.push(format!(
+            "The demo was recorded with the render mod {}, \
+            the local render mod is used instead.",
+            mod_name(render_mod)
+        ));
+    }
+
+    Ok(warnings)
+}
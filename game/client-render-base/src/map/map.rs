@@ -660,9 +660,10 @@ impl RenderMap {
         cur_quad_offset: &Cell<usize>,
         animations: &AnimationsSkeleton<AN, AS>,
         quads: &[Quad],
+        opacity: f32,
     ) {
         for (i, quad) in quads.iter().enumerate() {
-            let color = if let Some(anim) = {
+            let mut color = if let Some(anim) = {
                 if let Some(color_anim) = quad.color_anim {
                     animations.color.get(color_anim)
                 } else {
@@ -684,6 +685,9 @@ impl RenderMap {
                     nffixed::from_num(1),
                 )
             };
+            if opacity < 1.0 {
+                color.set_a(nffixed::from_num(color.a().to_num::<f32>() * opacity));
+            }
 
             let mut offset_x = 0.0;
             let mut offset_y = 0.0;
@@ -745,6 +749,7 @@ impl RenderMap {
         visuals: &QuadLayerVisuals,
         animations: &AnimationsSkeleton<AN, AS>,
         quads: &Vec<Quad>,
+        opacity: f32,
     ) {
         if let Some(buffer_container_index) = &visuals.buffer_object_index {
             let map_graphics = &self.map_graphics;
@@ -760,6 +765,7 @@ impl RenderMap {
                             cur_quad_offset: &Cell<usize>,
                             animations: &AnimationsSkeleton<AN, AS>,
                             quads: &Vec<Quad>,
+                            opacity: f32,
                         ],
                     |stream_handle: StreamedUniforms<
                         '_,
@@ -772,7 +778,8 @@ impl RenderMap {
                             cur_anim_time,
                             cur_quad_offset,
                             animations,
-                            quads
+                            quads,
+                            *opacity
                         );
                      }),
                     hi_closure!([map_graphics: &MapGraphics, state: &State, texture: &TextureType, buffer_container_index: &BufferObject, cur_quad_offset: &Cell<usize>], |instance: usize, count: usize| -> () {
@@ -942,6 +949,8 @@ impl RenderMap {
         layer: &MapVisualLayerBase<T, Q, S, A>,
         // this can be used to overwrite the layer's texture. only useful for the editor
         forced_texture: Option<ForcedTexture>,
+        // multiplied with the alpha of the layer. only useful for the editor
+        opacity: f32,
     ) where
         T: Borrow<TileLayerVisuals>,
         Q: Borrow<QuadLayerVisuals>,
@@ -992,7 +1001,8 @@ impl RenderMap {
                     b: layer.attr.color.b().to_num::<f32>(),
                     a: layer.attr.color.a().to_num::<f32>()
                         * (100 - config.physics_layer_opacity) as f32
-                        / 100.0,
+                        / 100.0
+                        * opacity,
                 };
 
                 state.blend(BlendType::Alpha);
@@ -1033,6 +1043,7 @@ impl RenderMap {
                         visual,
                         animations,
                         &layer.quads,
+                        opacity,
                     );
                 }
             }
@@ -1197,6 +1208,7 @@ impl RenderMap {
                 &group.attr,
                 &group.layers[render_info.layer_index],
                 None,
+                1.0,
             );
         }
     }
@@ -126,6 +126,7 @@ impl MapSoundProcess {
         layer: &MapLayerSoundSkeleton<S>,
         camera: &Camera,
         map_sound_volume: f64,
+        solo: Option<usize>,
     ) where
         S: Borrow<SoundLayerSounds>,
     {
@@ -170,13 +171,18 @@ impl MapSoundProcess {
                     volume *= sound_volume.x.to_num::<f64>();
                 }
 
-                let interact = Self::camera_sound_interaction(
-                    &camera.pos,
-                    &pos,
-                    rot,
-                    &sound.shape,
-                    sound.falloff,
-                );
+                // if a sound is played solo, all others are muted
+                let interact = if solo.is_some_and(|solo| solo != index) {
+                    None
+                } else {
+                    Self::camera_sound_interaction(
+                        &camera.pos,
+                        &pos,
+                        rot,
+                        &sound.shape,
+                        sound.falloff,
+                    )
+                };
                 // check if the sound should play, else play or update
                 let sounds: &SoundLayerSounds = layer.user.borrow();
                 if interact.is_some() {
@@ -190,6 +196,7 @@ impl MapSoundProcess {
                         volume: volume * falloff.x.max(falloff.y) as f64 * map_sound_volume,
                         panning: panning as f64,
                         playback_speed: 1.0,
+                        preserve_pitch: false,
                     };
                     if !sounds.is_playing(index) {
                         sounds.play(
@@ -244,6 +251,7 @@ impl MapSoundProcess {
                 layer,
                 camera,
                 map_sound_volume,
+                None,
             );
         }
     }
@@ -6,6 +6,7 @@ use client_containers::{
 };
 use client_render::hud::page::{HudRender, HudRenderPipe};
 use client_render_base::render::tee::RenderTee;
+use client_types::hud_layout::HudElementLayout;
 use game_interface::types::{
     emoticons::EnumCount,
     game::{GameEntityId, GameTickType, NonZeroGameTickType},
@@ -44,8 +45,16 @@ pub struct RenderHudPipe<'a> {
     pub skin_renderer: &'a RenderTee,
     pub ctf_container: &'a mut CtfContainer,
     pub character_infos: &'a LinkedHashMap<GameEntityId, CharacterInfo>,
+    pub layout: &'a HudElementLayout,
 }
 
+/// Top left corner of the health & ammo bar.
+const HEALTH_AMMO_X: f32 = 5.0;
+const HEALTH_AMMO_Y: f32 = 5.0;
+/// How far overhealed hearts & shields are moved,
+/// so they look stacked on the normal ones.
+const OVERHEAL_OFFSET: f32 = 3.0;
+
 pub struct RenderHud {
     quad_container: QuadContainer,
 
@@ -64,8 +73,8 @@ impl RenderHud {
     pub fn new(graphics: &Graphics, creator: &UiCreator) -> Self {
         let mut quads: Vec<Quad> = Default::default();
 
-        let x = 5.0;
-        let y = 5.0;
+        let x = HEALTH_AMMO_X;
+        let y = HEALTH_AMMO_Y;
 
         // ammo of the different weapons
         let weapon_ammo_offsets = (0..WeaponType::COUNT)
@@ -129,7 +138,14 @@ impl RenderHud {
         let hud = pipe.hud_container.get_or_default_opt(pipe.hud_key);
         let weapon = pipe.weapon_container.get_or_default_opt(pipe.weapon_key);
         let mut state = State::default();
-        state.map_canvas(0.0, 0.0, 300.0 * self.canvas_handle.canvas_aspect(), 300.0);
+        let w = 300.0 * self.canvas_handle.canvas_aspect();
+        let h = 300.0;
+        // move & scale the canvas, so that the bar's top left corner
+        // ends up at its default position + the user's offset.
+        let scale = pipe.layout.scale.max(0.01);
+        let tl_x = HEALTH_AMMO_X - (HEALTH_AMMO_X + pipe.layout.x * w) / scale;
+        let tl_y = HEALTH_AMMO_Y - (HEALTH_AMMO_Y + pipe.layout.y * h) / scale;
+        state.map_canvas(tl_x, tl_y, tl_x + w / scale, tl_y + h / scale);
 
         let mut draw_scope = quad_scope_begin();
         draw_scope.set_state(&state);
@@ -160,6 +176,22 @@ impl RenderHud {
             texture.into(),
         );
 
+        // render overhealed hearts stacked on the full hearts
+        let overheal_health = pipe.local_player_render_info.overheal_health.min(10) as usize;
+        if overheal_health > 0 {
+            let texture = &hud.heart;
+            self.quad_container.render_quad_container(
+                self.heart_offset,
+                &QuadContainerRenderCount::Count(overheal_health),
+                OVERHEAL_OFFSET,
+                OVERHEAL_OFFSET,
+                1.0,
+                1.0,
+                draw_scope,
+                texture.into(),
+            );
+        }
+
         // render shields
         let cur_armor = pipe.local_player_render_info.armor.min(10) as usize;
         let texture = &hud.shield;
@@ -186,6 +218,22 @@ impl RenderHud {
             texture.into(),
         );
 
+        // render overhealed shields stacked on the full shields
+        let overheal_armor = pipe.local_player_render_info.overheal_armor.min(10) as usize;
+        if overheal_armor > 0 {
+            let texture = &hud.shield;
+            self.quad_container.render_quad_container(
+                self.shield_offset,
+                &QuadContainerRenderCount::Count(overheal_armor),
+                OVERHEAL_OFFSET,
+                OVERHEAL_OFFSET,
+                1.0,
+                1.0,
+                draw_scope,
+                texture.into(),
+            );
+        }
+
         // render ammo
         let cur_weapon = weapon.by_type(pipe.cur_weapon);
         if !cur_weapon.projectiles.is_empty()
@@ -477,21 +477,29 @@ impl Players {
         {
             let pos = &player_render_info.lerped_pos;
             let character_info = character_infos.get(character_id);
-            if let Some(name) = character_info
+            let name = character_info
                 .map(|c| c.info.name.as_str())
                 .and_then(|n| (!n.is_empty()).then_some(n))
                 .and_then(|n| {
                     (nameplates
                         && (own_nameplate || !own_character.is_some_and(|id| *id == *character_id)))
                     .then_some(n)
-                })
-            {
+                });
+            // e.g. teammates unfreezing the character,
+            // always shown, even without nameplates
+            let progress = player_render_info
+                .debuffs
+                .get(&CharacterDebuff::Freeze)
+                .and_then(|debuff| debuff.progress)
+                .filter(|&progress| progress > 0.0);
+            if name.is_some() || progress.is_some() {
                 self.nameplate_renderer.render(&mut NameplateRenderPipe {
                     cur_time,
-                    name,
+                    name: name.unwrap_or_default(),
                     state: &state,
                     pos,
                     camera_zoom: camera.zoom,
+                    progress,
                 });
             }
         }
@@ -377,6 +377,9 @@ impl RenderMapLoading {
                                 physics_group.attr.width.get() as u32,
                                 physics_group.attr.height.get() as u32,
                                 physics_group.get_game_layer_tiles(),
+                                physics_group
+                                    .get_front_layer_tiles()
+                                    .map(|tiles| tiles.as_slice()),
                                 None,
                             );
 
@@ -31,6 +31,7 @@ use client_render_base::{
 use client_types::{
     actionfeed::{Action, ActionInFeed, ActionKill, ActionPlayer},
     chat::{ChatMsg, ChatMsgPlayerChannel, MsgSystem, ServerMsg},
+    hud_layout::HudLayout,
 };
 use client_ui::{
     chat::user_data::{ChatEvent, MsgInChat},
@@ -50,9 +51,10 @@ use game_interface::{
         GameLaserEventSound, GamePickupArmorEvent, GamePickupArmorEventSound, GamePickupEvent,
         GamePickupHeartEvent, GamePickupHeartEventSound, GameShotgunEvent, GameShotgunEventSound,
         GameWorldAction, GameWorldEntityEvent, GameWorldEvent, GameWorldGlobalEvent,
-        GameWorldPositionedEvent, GameWorldSystemMessage,
+        GameWorldPositionedEvent, GameWorldSystemMessage, GameWorldVoteEvent,
     },
     types::{
+        emoticons::EmoticonType,
         flag::FlagType,
         game::{GameEntityId, GameTickType},
         network_string::NetworkReducedAsciiString,
@@ -62,7 +64,7 @@ use game_interface::{
             stage::StageRenderInfo,
         },
     },
-    votes::{VoteState, VoteType, Voted},
+    votes::{GameVoteAction, VoteState, VoteType, Voted},
 };
 use graphics::{
     graphics::graphics::Graphics,
@@ -92,7 +94,7 @@ pub enum PlayerFeedbackEvent {
     EmoteWheel(EmoteWheelEvent),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RenderGameCreateOptions {
     pub physics_group_name: NetworkReducedAsciiString<24>,
     pub resource_download_server: Option<Url>,
@@ -116,6 +118,8 @@ pub enum RenderPlayerCameraMode {
 pub struct RenderForPlayer {
     pub chat_info: Option<(String, Option<egui::RawInput>)>,
     pub emote_wheel_input: Option<Option<egui::RawInput>>,
+    /// The recently used emoticons, newest first, for the emote wheel.
+    pub recent_emoticons: Vec<EmoticonType>,
     pub local_player_info: LocalCharacterRenderInfo,
     pub chat_show_all: bool,
     pub scoreboard_active: bool,
@@ -186,13 +190,27 @@ pub struct RenderGameForPlayer {
 pub struct RenderGameSettings {
     pub spartial_sound: bool,
     pub sound_playback_speed: f64,
+    /// See [`sound::types::SoundPlayBaseProps::preserve_pitch`].
+    pub sound_preserve_pitch: bool,
     /// For music from the map
     pub map_sound_volume: f64,
     /// For all the various sounds ingame
     pub ingame_sound_volume: f64,
+    /// Volume of the whole ingame & map sound scenes,
+    /// lowered while more important sounds play (e.g. the spatial chat).
+    pub ducking_volume: f64,
 
     pub nameplates: bool,
     pub nameplate_own: bool,
+
+    pub hud_layout: HudLayout,
+
+    /// Texture memory budget in MiB for the skins, entities & particles
+    /// containers each, 0 for unlimited.
+    pub texture_memory_budget: u64,
+    /// Maximum size in KiB of a resource that is downloaded
+    /// automatically (e.g. a skin), 0 for unlimited.
+    pub resource_download_size_limit: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -251,6 +269,8 @@ pub struct RenderGame {
     world_sound_scene: SceneObject,
     world_sound_listeners: HashMap<GameEntityId, SoundListener>,
     world_sound_listeners_pool: Pool<HashMap<GameEntityId, SoundListener>>,
+    /// the ducking volume that was last applied to the sound scenes
+    ducking_volume: f64,
     rng: Rng,
 }
 
@@ -339,6 +359,7 @@ impl RenderGame {
             world_sound_scene: scene,
             world_sound_listeners: Default::default(),
             world_sound_listeners_pool: Pool::with_capacity(2),
+            ducking_volume: 1.0,
             rng: Rng::new(0),
         }
     }
@@ -563,6 +584,7 @@ impl RenderGame {
                         options: ChatRenderOptions {
                             is_chat_input_active: chat_active,
                             show_chat_history: player_render_info.chat_show_all,
+                            layout: render_info.settings.hud_layout.chat,
                         },
                         input: dummy_state,
                         player_id,
@@ -603,6 +625,7 @@ impl RenderGame {
                                 .map(|c| c.info.skin.borrow())
                                 .unwrap_or(&*skin_default_key),
                             skin_info: &character_info.map(|c| c.skin_info),
+                            recent_emoticons: &player_render_info.recent_emoticons,
                         })
                         .into_iter()
                         .map(PlayerFeedbackEvent::EmoteWheel),
@@ -622,6 +645,7 @@ impl RenderGame {
             weapon_container: &mut self.containers.weapon_container,
             toolkit_render: &self.players.toolkit_renderer,
             ninja_container: &mut self.containers.ninja_container,
+            layout: render_info.settings.hud_layout.killfeed,
         });
 
         // hud + scoreboard
@@ -648,6 +672,7 @@ impl RenderGame {
                 skin_renderer: &self.players.tee_renderer,
                 ctf_container: &mut self.containers.ctf_container,
                 character_infos: &render_info.character_infos,
+                layout: &render_info.settings.hud_layout.health_ammo,
             });
             if let Some(scoreboard_info) = local_render_info
                 .scoreboard_active
@@ -662,6 +687,7 @@ impl RenderGame {
                     skin_container: &mut self.containers.skin_container,
                     tee_render: &mut self.players.tee_renderer,
                     flags_container: &mut self.containers.flags_container,
+                    layout: render_info.settings.hud_layout.scoreboard,
                 });
             }
         }
@@ -736,6 +762,44 @@ impl RenderGame {
         }
     }
 
+    fn convert_vote_ev(
+        ev: GameWorldVoteEvent,
+        character_infos: &PoolLinkedHashMap<GameEntityId, CharacterInfo>,
+    ) -> Option<String> {
+        let name = |id: &GameEntityId| {
+            character_infos
+                .get(id)
+                .map(|c| c.info.name.to_string())
+                .unwrap_or_default()
+        };
+        let action = |action: &GameVoteAction| match action {
+            GameVoteAction::KickPlayer { player_id } => format!("kick \"{}\"", name(player_id)),
+            GameVoteAction::SpecPlayer { player_id } => {
+                format!("move \"{}\" to spectators", name(player_id))
+            }
+            GameVoteAction::ChangeMap { name } => format!("change map to \"{}\"", name.as_str()),
+        };
+        match ev {
+            GameWorldVoteEvent::Started {
+                action: a,
+                started_by,
+                ..
+            } => Some(format!(
+                "\"{}\" called a vote to {}.",
+                name(&started_by),
+                action(&a)
+            )),
+            // shown by the vote ui
+            GameWorldVoteEvent::Votes { .. } => None,
+            GameWorldVoteEvent::Passed { action: a } => {
+                Some(format!("Vote to {} passed.", action(&a)))
+            }
+            GameWorldVoteEvent::Failed { action: a } => {
+                Some(format!("Vote to {} failed.", action(&a)))
+            }
+        }
+    }
+
     fn handle_action_feed(
         &mut self,
         cur_time: &Duration,
@@ -858,6 +922,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -873,6 +938,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -889,6 +955,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -904,6 +971,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -920,6 +988,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -936,6 +1005,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -952,6 +1022,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -967,6 +1038,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -982,6 +1054,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -997,6 +1070,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1012,6 +1086,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1026,6 +1101,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1040,6 +1116,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1054,6 +1131,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1075,6 +1153,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1095,6 +1174,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1110,6 +1190,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1145,6 +1226,7 @@ impl RenderGame {
                                     SoundPlayProps::new_with_pos(pos)
                                         .with_with_spartial(settings.spartial_sound)
                                         .with_playback_speed(settings.sound_playback_speed)
+                                        .with_preserve_pitch(settings.sound_preserve_pitch)
                                         .with_volume(settings.ingame_sound_volume),
                                 )
                                 .detatch();
@@ -1158,6 +1240,7 @@ impl RenderGame {
                                     SoundPlayProps::new_with_pos(pos)
                                         .with_with_spartial(settings.spartial_sound)
                                         .with_playback_speed(settings.sound_playback_speed)
+                                        .with_preserve_pitch(settings.sound_preserve_pitch)
                                         .with_volume(settings.ingame_sound_volume),
                                 )
                                 .detatch();
@@ -1172,6 +1255,7 @@ impl RenderGame {
                                     SoundPlayProps::new_with_pos(pos)
                                         .with_with_spartial(settings.spartial_sound)
                                         .with_playback_speed(settings.sound_playback_speed)
+                                        .with_preserve_pitch(settings.sound_preserve_pitch)
                                         .with_volume(settings.ingame_sound_volume),
                                 )
                                 .detatch();
@@ -1186,6 +1270,7 @@ impl RenderGame {
                                     SoundPlayProps::new_with_pos(pos)
                                         .with_with_spartial(settings.spartial_sound)
                                         .with_playback_speed(settings.sound_playback_speed)
+                                        .with_preserve_pitch(settings.sound_preserve_pitch)
                                         .with_volume(settings.ingame_sound_volume),
                                 )
                                 .detatch();
@@ -1206,6 +1291,7 @@ impl RenderGame {
                                     SoundPlayProps::new_with_pos(pos)
                                         .with_with_spartial(settings.spartial_sound)
                                         .with_playback_speed(settings.sound_playback_speed)
+                                        .with_preserve_pitch(settings.sound_preserve_pitch)
                                         .with_volume(settings.ingame_sound_volume),
                                 )
                                 .detatch();
@@ -1238,6 +1324,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1252,6 +1339,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1267,6 +1355,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1301,6 +1390,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1315,6 +1405,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1330,6 +1421,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1359,6 +1451,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1373,6 +1466,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1401,6 +1495,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1416,6 +1511,7 @@ impl RenderGame {
                                 SoundPlayProps::new_with_pos(pos)
                                     .with_with_spartial(settings.spartial_sound)
                                     .with_playback_speed(settings.sound_playback_speed)
+                                    .with_preserve_pitch(settings.sound_preserve_pitch)
                                     .with_volume(settings.ingame_sound_volume),
                             )
                             .detatch();
@@ -1429,6 +1525,7 @@ impl RenderGame {
                                 SoundPlayProps::new_with_pos(pos)
                                     .with_with_spartial(settings.spartial_sound)
                                     .with_playback_speed(settings.sound_playback_speed)
+                                    .with_preserve_pitch(settings.sound_preserve_pitch)
                                     .with_volume(settings.ingame_sound_volume),
                             )
                             .detatch();
@@ -1443,6 +1540,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1456,6 +1554,7 @@ impl RenderGame {
                             SoundPlayProps::new_with_pos(pos)
                                 .with_with_spartial(settings.spartial_sound)
                                 .with_playback_speed(settings.sound_playback_speed)
+                                .with_preserve_pitch(settings.sound_preserve_pitch)
                                 .with_volume(settings.ingame_sound_volume),
                         )
                         .detatch();
@@ -1486,6 +1585,7 @@ impl RenderGame {
                                 SoundPlayProps::new_with_pos(pos)
                                     .with_with_spartial(settings.spartial_sound)
                                     .with_playback_speed(settings.sound_playback_speed)
+                                    .with_preserve_pitch(settings.sound_preserve_pitch)
                                     .with_volume(settings.ingame_sound_volume),
                             )
                             .detatch();
@@ -1501,6 +1601,7 @@ impl RenderGame {
                                 SoundPlayProps::new_with_pos(pos)
                                     .with_with_spartial(settings.spartial_sound)
                                     .with_playback_speed(settings.sound_playback_speed)
+                                    .with_preserve_pitch(settings.sound_preserve_pitch)
                                     .with_volume(settings.ingame_sound_volume),
                             )
                             .detatch();
@@ -1519,6 +1620,7 @@ impl RenderGame {
                                 SoundPlayProps::new_with_pos(pos)
                                     .with_with_spartial(settings.spartial_sound)
                                     .with_playback_speed(settings.sound_playback_speed)
+                                    .with_preserve_pitch(settings.sound_preserve_pitch)
                                     .with_volume(settings.ingame_sound_volume),
                             )
                             .detatch();
@@ -1534,6 +1636,7 @@ impl RenderGame {
                                 SoundPlayProps::new_with_pos(pos)
                                     .with_with_spartial(settings.spartial_sound)
                                     .with_playback_speed(settings.sound_playback_speed)
+                                    .with_preserve_pitch(settings.sound_preserve_pitch)
                                     .with_volume(settings.ingame_sound_volume),
                             )
                             .detatch();
@@ -1628,6 +1731,31 @@ impl RenderGame {
                                             ev,
                                         );
                                     }
+                                    GameWorldGlobalEvent::Stat(_) => {
+                                        // only interesting for statistics
+                                    }
+                                    GameWorldGlobalEvent::PlayerMessage { msg, .. } => {
+                                        self.chat.msgs.push_back(MsgInChat {
+                                            msg: ServerMsg::System(MsgSystem {
+                                                msg: msg.to_string(),
+                                            }),
+                                            add_time: *cur_time,
+                                        })
+                                    }
+                                    GameWorldGlobalEvent::Kick { .. }
+                                    | GameWorldGlobalEvent::ExportRoundReport { .. } => {
+                                        // handled by the server
+                                    }
+                                    GameWorldGlobalEvent::Vote(ev) => {
+                                        if let Some(msg) =
+                                            Self::convert_vote_ev(ev, &input.character_infos)
+                                        {
+                                            self.chat.msgs.push_back(MsgInChat {
+                                                msg: ServerMsg::System(MsgSystem { msg }),
+                                                add_time: *cur_time,
+                                            })
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -1783,6 +1911,7 @@ impl RenderGame {
                             &RenderForPlayer {
                                 chat_info: None,
                                 emote_wheel_input: None,
+                                recent_emoticons: Vec::new(),
                                 local_player_info: *local_player_info,
                                 chat_show_all: false,
                                 scoreboard_active: false,
@@ -1823,9 +1952,12 @@ impl RenderGame {
                                 &RenderForPlayer {
                                     chat_info: None,
                                     emote_wheel_input: None,
+                                    recent_emoticons: Vec::new(),
                                     local_player_info: LocalCharacterRenderInfo {
                                         health: 10,
                                         armor: 10,
+                                        overheal_health: 0,
+                                        overheal_armor: 0,
                                         ammo_of_weapon: None,
                                     },
                                     chat_show_all: false,
@@ -1846,7 +1978,26 @@ impl RenderGame {
         &mut self,
         cur_time: &Duration,
         character_infos: &PoolLinkedHashMap<GameEntityId, CharacterInfo>,
+        settings: &RenderGameSettings,
     ) {
+        // community servers can have many different skins etc.,
+        // so these containers are limited by the texture memory they use
+        let texture_memory_budget = (settings.texture_memory_budget > 0)
+            .then_some(settings.texture_memory_budget as usize * 1024 * 1024);
+        self.containers
+            .skin_container
+            .set_texture_memory_budget(texture_memory_budget);
+        self.containers
+            .entities_container
+            .set_texture_memory_budget(texture_memory_budget);
+        self.containers
+            .particles_container
+            .set_texture_memory_budget(texture_memory_budget);
+        self.containers.set_download_size_limit(
+            (settings.resource_download_size_limit > 0)
+                .then_some(settings.resource_download_size_limit as usize * 1024),
+        );
+
         self.containers.skin_container.update(
             cur_time,
             &Duration::from_secs(5),
@@ -1924,7 +2075,7 @@ impl RenderGameInterface for RenderGame {
         mut input: RenderGameInput,
     ) -> RenderGameResult {
         // as a first step, update all containers
-        self.update_containers(cur_time, &input.character_infos);
+        self.update_containers(cur_time, &input.character_infos, &input.settings);
 
         // keep scene active
         self.world_sound_scene.stay_active();
@@ -1933,6 +2084,17 @@ impl RenderGameInterface for RenderGame {
         let map = self.map.try_get().unwrap();
         self.particles.update(cur_time, &map.data.collision);
 
+        if self.ducking_volume != input.settings.ducking_volume {
+            self.ducking_volume = input.settings.ducking_volume;
+            self.world_sound_scene.set_volume(self.ducking_volume);
+            map.data
+                .buffered_map
+                .map_visual
+                .user
+                .sound_scene
+                .set_volume(self.ducking_volume);
+        }
+
         self.handle_chat_msgs(cur_time, &mut input);
         self.handle_events(cur_time, &mut input);
 
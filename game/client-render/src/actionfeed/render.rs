@@ -2,7 +2,7 @@ use std::{collections::VecDeque, time::Duration};
 
 use client_containers::{ninja::NinjaContainer, skins::SkinContainer, weapons::WeaponContainer};
 use client_render_base::render::{tee::RenderTee, toolkit::ToolkitRender};
-use client_types::actionfeed::ActionInFeed;
+use client_types::{actionfeed::ActionInFeed, hud_layout::HudElementLayout};
 use client_ui::actionfeed::{page::ActionFeedUi, user_data::UserData};
 use egui::Color32;
 use graphics::{
@@ -28,6 +28,7 @@ pub struct ActionfeedRenderPipe<'a> {
     pub weapon_container: &'a mut WeaponContainer,
     pub toolkit_render: &'a ToolkitRender,
     pub ninja_container: &'a mut NinjaContainer,
+    pub layout: HudElementLayout,
 }
 
 pub struct ActionfeedRender {
@@ -35,6 +36,7 @@ pub struct ActionfeedRender {
     feed_ui: ActionFeedUi,
 
     pub msgs: RememberMut<VecDeque<ActionInFeed>>,
+    last_layout: HudElementLayout,
 
     backend_handle: GraphicsBackendHandle,
     canvas_handle: GraphicsCanvasHandle,
@@ -51,6 +53,7 @@ impl ActionfeedRender {
             feed_ui: ActionFeedUi::new(),
 
             msgs: Default::default(),
+            last_layout: Default::default(),
 
             backend_handle: graphics.backend_handle.clone(),
             canvas_handle: graphics.canvas_handle.clone(),
@@ -68,7 +71,8 @@ impl ActionfeedRender {
         let window_height = self.canvas_handle.window_height();
         let window_pixels_per_point = self.canvas_handle.window_pixels_per_point();
 
-        let force_rerender = self.msgs.was_accessed_mut();
+        let force_rerender = self.msgs.was_accessed_mut() || self.last_layout != pipe.layout;
+        self.last_layout = pipe.layout;
 
         let mut user_data = UserData {
             entries: &self.msgs,
@@ -79,6 +83,7 @@ impl ActionfeedRender {
             weapon_container: pipe.weapon_container,
             toolkit_render: pipe.toolkit_render,
             ninja_container: pipe.ninja_container,
+            layout: pipe.layout,
         };
         let mut inner_pipe = UiRenderPipe::new(*pipe.cur_time, &mut user_data);
         let (screen_rect, full_output, zoom_level) = self.ui.render_cached(
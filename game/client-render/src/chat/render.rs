@@ -2,6 +2,7 @@ use std::{collections::VecDeque, time::Duration};
 
 use client_containers::skins::SkinContainer;
 use client_render_base::render::tee::RenderTee;
+use client_types::hud_layout::HudElementLayout;
 use client_ui::chat::{
     page::ChatUi,
     user_data::{ChatEvent, MsgInChat, UserData},
@@ -24,10 +25,11 @@ use ui_base::{
 };
 use ui_traits::traits::UiPageInterface;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ChatRenderOptions {
     pub is_chat_input_active: bool,
     pub show_chat_history: bool,
+    pub layout: HudElementLayout,
 }
 
 pub struct ChatRenderPipe<'a> {
@@ -99,6 +101,7 @@ impl ChatRender {
             stream_handle: &self.stream_handle,
             skin_container: pipe.skin_container,
             render_tee: pipe.tee_render,
+            layout: pipe.options.layout,
         };
         let mut dummy_pipe = UiRenderPipe::new(*pipe.cur_time, &mut user_data);
         let (screen_rect, full_output, zoom_level) = self.ui.render_cached(
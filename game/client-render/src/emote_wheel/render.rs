@@ -7,7 +7,9 @@ use client_ui::emote_wheel::{
     user_data::{EmoteWheelEvent, UserData},
 };
 use egui::Color32;
-use game_interface::types::{character_info::NetworkSkinInfo, resource_key::ResourceKey};
+use game_interface::types::{
+    character_info::NetworkSkinInfo, emoticons::EmoticonType, resource_key::ResourceKey,
+};
 use graphics::{
     graphics::graphics::Graphics,
     handles::{
@@ -33,6 +35,7 @@ pub struct EmoteWheelRenderPipe<'a> {
     pub emoticons: &'a ResourceKey,
     pub skin: &'a ResourceKey,
     pub skin_info: &'a Option<NetworkSkinInfo>,
+    pub recent_emoticons: &'a [EmoticonType],
 }
 
 pub struct EmoteWheelRender {
@@ -73,6 +76,7 @@ impl EmoteWheelRender {
             emoticon: pipe.emoticons,
             skin: pipe.skin,
             skin_info: pipe.skin_info,
+            recent_emoticons: pipe.recent_emoticons,
         };
         let mut dummy_pipe = UiRenderPipe::new(*pipe.cur_time, &mut user_data);
         generic_ui_renderer::render(
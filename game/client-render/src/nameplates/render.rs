@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use egui::{pos2, Align2, Color32, FontId};
+use egui::{pos2, vec2 as evec2, Align2, Color32, FontId, Rect, Rounding};
 use graphics::{
     graphics::graphics::Graphics,
     handles::{
@@ -23,6 +23,9 @@ pub struct NameplateRenderPipe<'a> {
     pub state: &'a State,
     pub pos: &'a vec2,
     pub camera_zoom: f32,
+    /// A progress bar below the name (`0.0..=1.0`),
+    /// e.g. while the character is unfrozen.
+    pub progress: Option<f32>,
 }
 
 pub struct NameplateRender {
@@ -75,16 +78,31 @@ impl NameplateRender {
 
                 let width_scale = size.x / w;
                 let height_scale = size.y / h;
+                let name_pos = pos2(
+                    (pipe.pos.x - x0) * width_scale,
+                    (pipe.pos.y - y0 - 70.0 / 64.0) * height_scale,
+                );
                 ui.painter().text(
-                    pos2(
-                        (pipe.pos.x - x0) * width_scale,
-                        (pipe.pos.y - y0 - 70.0 / 64.0) * height_scale,
-                    ),
+                    name_pos,
                     Align2::CENTER_BOTTOM,
                     pipe.name,
                     FontId::proportional((1.0 * name_scale) * height_scale),
                     Color32::WHITE,
                 );
+                if let Some(progress) = pipe.progress {
+                    let bar_size = evec2(1.0 * width_scale, 0.15 * height_scale);
+                    let bar = Rect::from_min_size(
+                        name_pos + evec2(-bar_size.x / 2.0, 0.05 * height_scale),
+                        bar_size,
+                    );
+                    let rounding = Rounding::same(bar_size.y / 2.0);
+                    ui.painter()
+                        .rect_filled(bar, rounding, Color32::from_black_alpha(150));
+                    let mut filled = bar;
+                    filled.set_width(bar.width() * progress.clamp(0.0, 1.0));
+                    ui.painter()
+                        .rect_filled(filled, rounding, Color32::from_rgb(120, 200, 255));
+                }
             },
             &mut dummy_pipe,
             Default::default(),
@@ -2,6 +2,7 @@ use std::time::Duration;
 
 use client_containers::{flags::FlagsContainer, skins::SkinContainer};
 use client_render_base::render::tee::RenderTee;
+use client_types::hud_layout::HudElementLayout;
 use client_ui::scoreboard::{page::ScoreboardUi, user_data::UserData};
 use egui::Color32;
 use graphics::{
@@ -31,6 +32,7 @@ pub struct ScoreboardRenderPipe<'a> {
     pub skin_container: &'a mut SkinContainer,
     pub tee_render: &'a mut RenderTee,
     pub flags_container: &'a mut FlagsContainer,
+    pub layout: HudElementLayout,
 }
 
 pub struct ScoreboardRender {
@@ -75,6 +77,7 @@ impl ScoreboardRender {
                     skin_container: pipe.skin_container,
                     render_tee: pipe.tee_render,
                     flags_container: pipe.flags_container,
+                    layout: pipe.layout,
                 },
             ),
             Default::default(),
@@ -6,6 +6,7 @@ use shared_base::network::types::chat::NetChatMsgPlayerChannel;
 pub enum ChatMsgPlayerChannel {
     Global,
     GameTeam,
+    Stage,
     Whisper(String), // sender name
 }
 
@@ -14,6 +15,7 @@ impl ChatMsgPlayerChannel {
         match msg {
             NetChatMsgPlayerChannel::Global => Self::Global,
             NetChatMsgPlayerChannel::GameTeam => Self::GameTeam,
+            NetChatMsgPlayerChannel::Stage => Self::Stage,
             NetChatMsgPlayerChannel::Whisper(_) => Self::Whisper(String::new()), // TODO: not implemented + should use PoolString
         }
     }
@@ -0,0 +1,51 @@
+use game_config::config::{ConfigHudElement, ConfigHudLayout};
+use serde::{Deserialize, Serialize};
+
+/// Position & scale of a HUD element, relative to its default layout.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HudElementLayout {
+    /// offset relative to the screen width
+    pub x: f32,
+    /// offset relative to the screen height
+    pub y: f32,
+    pub scale: f32,
+}
+
+impl Default for HudElementLayout {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            scale: 1.0,
+        }
+    }
+}
+
+impl From<&ConfigHudElement> for HudElementLayout {
+    fn from(value: &ConfigHudElement) -> Self {
+        Self {
+            x: value.x as f32,
+            y: value.y as f32,
+            scale: value.scale as f32,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HudLayout {
+    pub health_ammo: HudElementLayout,
+    pub killfeed: HudElementLayout,
+    pub chat: HudElementLayout,
+    pub scoreboard: HudElementLayout,
+}
+
+impl From<&ConfigHudLayout> for HudLayout {
+    fn from(value: &ConfigHudLayout) -> Self {
+        Self {
+            health_ammo: (&value.health_ammo).into(),
+            killfeed: (&value.killfeed).into(),
+            chat: (&value.chat).into(),
+            scoreboard: (&value.scoreboard).into(),
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod actionfeed;
 pub mod chat;
 pub mod console;
+pub mod hud_layout;
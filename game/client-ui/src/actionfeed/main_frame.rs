@@ -2,6 +2,8 @@ use egui::{Pos2, Rect, UiBuilder, Vec2};
 
 use ui_base::types::{UiRenderPipe, UiState};
 
+use crate::utils::apply_hud_layout;
+
 use super::user_data::UserData;
 
 /// not required
@@ -32,4 +34,10 @@ pub fn render(
             super::feed_list::render(ui, pipe, ui_state, &full_rect)
         }
     });
+    apply_hud_layout(
+        ui,
+        &pipe.user_data.layout,
+        render_rect.right_top(),
+        &full_rect,
+    );
 }
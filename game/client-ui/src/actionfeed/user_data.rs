@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 
 use client_containers::{ninja::NinjaContainer, skins::SkinContainer, weapons::WeaponContainer};
 use client_render_base::render::{tee::RenderTee, toolkit::ToolkitRender};
-use client_types::actionfeed::ActionInFeed;
+use client_types::{actionfeed::ActionInFeed, hud_layout::HudElementLayout};
 use graphics::handles::{
     canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
 };
@@ -16,4 +16,5 @@ pub struct UserData<'a> {
     pub weapon_container: &'a mut WeaponContainer,
     pub toolkit_render: &'a ToolkitRender,
     pub ninja_container: &'a mut NinjaContainer,
+    pub layout: HudElementLayout,
 }
@@ -2,6 +2,8 @@ use egui::{Pos2, Rect, UiBuilder, Vec2};
 
 use ui_base::types::{UiRenderPipe, UiState};
 
+use crate::utils::apply_hud_layout;
+
 use super::user_data::UserData;
 
 /// not required
@@ -41,4 +43,11 @@ pub fn render(
             super::chat_list::render(ui, pipe, ui_state, &full_rect)
         }
     });
+    // the chat grows from the bottom
+    apply_hud_layout(
+        ui,
+        &pipe.user_data.layout,
+        render_rect.left_bottom(),
+        &full_rect,
+    );
 }
@@ -2,7 +2,7 @@ use std::{collections::VecDeque, time::Duration};
 
 use client_containers::skins::SkinContainer;
 use client_render_base::render::tee::RenderTee;
-use client_types::chat::ServerMsg;
+use client_types::{chat::ServerMsg, hud_layout::HudElementLayout};
 use graphics::handles::{
     canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
 };
@@ -32,4 +32,5 @@ pub struct UserData<'a> {
     pub canvas_handle: &'a GraphicsCanvasHandle,
     pub skin_container: &'a mut SkinContainer,
     pub render_tee: &'a RenderTee,
+    pub layout: HudElementLayout,
 }
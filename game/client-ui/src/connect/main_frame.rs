@@ -2,17 +2,64 @@ use std::net::SocketAddr;
 
 use egui::{Color32, Pos2, Rect, UiBuilder, Vec2};
 
-use ui_base::types::UiRenderPipe;
+use ui_base::types::{UiRenderPipe, UiState};
 
-use crate::events::UiEvent;
+use crate::{
+    events::UiEvent,
+    main_menu::server_icon::{self, ServerIcon},
+};
 
-use super::user_data::{ConnectModes, UserData};
+use super::user_data::{ConnectModes, DisconnectReason, UserData};
+
+/// How long to wait before connecting again after a temporary disconnect.
+const AUTO_RECONNECT_SECS: f64 = 5.0;
+
+/// The space for the server icon above the connect info.
+const ICON_SIZE: Vec2 = Vec2::new(150.0, 75.0);
+
+fn disconnect_reason_text(reason: &DisconnectReason) -> String {
+    match reason {
+        DisconnectReason::Left => "the connection was closed".to_string(),
+        DisconnectReason::Kicked(reason) if reason.is_empty() => {
+            "kicked from the server".to_string()
+        }
+        DisconnectReason::Kicked(reason) => format!("kicked from the server: {reason}"),
+        DisconnectReason::Banned { reason, duration } => {
+            let mut text = match duration {
+                Some(duration) => format!(
+                    "banned from the server for {} minutes",
+                    duration.as_secs().div_ceil(60)
+                ),
+                None => "banned from the server".to_string(),
+            };
+            if !reason.is_empty() {
+                text.push_str(&format!(": {reason}"));
+            }
+            text
+        }
+        DisconnectReason::ServerShutdown => "the server shut down".to_string(),
+        DisconnectReason::MapChange => "the server changed the map".to_string(),
+        DisconnectReason::Timeout => "timed out".to_string(),
+        DisconnectReason::VersionMismatch { server_version } => {
+            format!("the server runs an incompatible version ({server_version})")
+        }
+        DisconnectReason::Other(err) => err.clone(),
+    }
+}
 
 /// top bar
 /// big square, rounded edges
-pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_only: bool) {
+pub fn render(
+    ui: &mut egui::Ui,
+    pipe: &mut UiRenderPipe<UserData>,
+    ui_state: &mut UiState,
+    main_frame_only: bool,
+) {
+    let has_icon = matches!(pipe.user_data.icon, Some(ServerIcon::Icon { .. }));
     let width = ui.available_width().min(150.0);
-    let height = ui.available_height().min(50.0);
+    let height = ui
+        .available_height()
+        .min(50.0 + if has_icon { ICON_SIZE.y } else { 0.0 });
     let offset_x = (ui.available_width() / 2.0) - (width / 2.0);
     let offset_y = (ui.available_height() / 2.0) - (height / 2.0);
     ui.allocate_new_ui(
@@ -28,6 +75,17 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                     Color32::from_rgba_unmultiplied(0, 0, 0, 255),
                 );
             } else {
+                if has_icon {
+                    server_icon::render(
+                        ui,
+                        ui_state,
+                        pipe.user_data.stream_handle,
+                        pipe.user_data.canvas_handle,
+                        ui.ctx().screen_rect(),
+                        pipe.user_data.icon,
+                        ICON_SIZE,
+                    );
+                }
                 match pipe.user_data.mode.get() {
                     ConnectModes::Connecting => {
                         ui.vertical(|ui| {
@@ -79,7 +137,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                             }
                         });
                     }
-                    ConnectModes::DisconnectErr { msg } => {
+                    ConnectModes::DisconnectErr { reason, reconnect } => {
                         ui.vertical(|ui| {
                             ui.label(format!(
                                 "connection to {} lost:\n{}",
@@ -88,9 +146,33 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                                     .storage_opt::<SocketAddr>("server-addr")
                                     .map(|a| a.to_string())
                                     .unwrap_or_default(),
-                                msg
+                                disconnect_reason_text(&reason)
                             ));
+                            let reconnect_id = egui::Id::new("connect-auto-reconnect");
+                            let mut reconnect_clicked = false;
+                            if reconnect {
+                                let now = ui.input(|i| i.time);
+                                let since = ui.ctx().data_mut(|d| {
+                                    *d.get_temp_mut_or_insert_with(reconnect_id, || now)
+                                });
+                                let remaining = (AUTO_RECONNECT_SECS - (now - since)).max(0.0);
+                                ui.label(format!("reconnecting in {:.0}s", remaining.ceil()));
+                                reconnect_clicked = remaining <= 0.0;
+                            }
+                            reconnect_clicked |= ui.button("reconnect").clicked();
+                            if reconnect_clicked {
+                                ui.ctx().data_mut(|d| d.remove::<f64>(reconnect_id));
+                                if let Some(addr) = pipe.user_data.config.storage_opt("server-addr")
+                                {
+                                    pipe.user_data.events.push(UiEvent::Connect {
+                                        addr,
+                                        cert_hash: pipe.user_data.config.storage("server-cert"),
+                                        rcon_secret: pipe.user_data.config.storage("rcon-secret"),
+                                    });
+                                }
+                            }
                             if ui.button("return").clicked() {
+                                ui.ctx().data_mut(|d| d.remove::<f64>(reconnect_id));
                                 pipe.user_data.events.push(UiEvent::Disconnect);
                                 pipe.user_data.config.engine.ui.path.route("");
                             }
@@ -1,8 +1,21 @@
+use base::hash::Hash;
+use base_io::io::Io;
 use game_config::config::Config;
+use graphics::{
+    graphics::graphics::Graphics,
+    graphics_mt::GraphicsMultiThreaded,
+    handles::{
+        canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
+        texture::texture::GraphicsTextureHandle,
+    },
+};
 use ui_base::types::{UiRenderPipe, UiState};
 use ui_traits::traits::UiPageInterface;
 
-use crate::events::UiEvents;
+use crate::{
+    events::UiEvents,
+    main_menu::server_icon::{self, ServerIcons},
+};
 
 use super::{
     main_frame,
@@ -12,19 +25,51 @@ use super::{
 pub struct ConnectingUi {
     mode: ConnectMode,
     events: UiEvents,
+
+    io: Io,
+    icons: ServerIcons,
+
+    stream_handle: GraphicsStreamHandle,
+    canvas_handle: GraphicsCanvasHandle,
+    texture_handle: GraphicsTextureHandle,
+    graphics_mt: GraphicsMultiThreaded,
 }
 
 impl ConnectingUi {
-    pub fn new(mode: ConnectMode, events: UiEvents) -> Self {
-        Self { mode, events }
+    pub fn new(graphics: &Graphics, io: Io, mode: ConnectMode, events: UiEvents) -> Self {
+        Self {
+            mode,
+            events,
+
+            io,
+            icons: Default::default(),
+
+            stream_handle: graphics.stream_handle.clone(),
+            canvas_handle: graphics.canvas_handle.clone(),
+            texture_handle: graphics.texture_handle.clone(),
+            graphics_mt: graphics.get_graphics_mt(),
+        }
     }
 
     fn render_impl(
         &mut self,
         ui: &mut egui::Ui,
         pipe: &mut UiRenderPipe<Config>,
+        ui_state: &mut UiState,
         main_frame_only: bool,
     ) {
+        // the server browser already downloaded the icon of the selected server
+        let icon_hash = pipe.user_data.storage::<Option<Hash>>("server-icon");
+        if let Some(hash) = icon_hash {
+            if !self.icons.contains_key(&hash) {
+                self.icons.insert(
+                    hash,
+                    server_icon::load_cached(&self.io, &self.graphics_mt, &hash),
+                );
+            }
+        }
+        server_icon::update(&mut self.icons, &self.texture_handle);
+
         main_frame::render(
             ui,
             &mut UiRenderPipe {
@@ -33,8 +78,12 @@ impl ConnectingUi {
                     mode: &self.mode,
                     config: pipe.user_data,
                     events: &self.events,
+                    icon: icon_hash.and_then(|hash| self.icons.get(&hash)),
+                    stream_handle: &self.stream_handle,
+                    canvas_handle: &self.canvas_handle,
                 },
             },
+            ui_state,
             main_frame_only,
         );
     }
@@ -49,17 +98,17 @@ impl UiPageInterface<Config> for ConnectingUi {
         &mut self,
         ui: &mut egui::Ui,
         pipe: &mut UiRenderPipe<Config>,
-        _ui_state: &mut UiState,
+        ui_state: &mut UiState,
     ) {
-        self.render_impl(ui, pipe, true)
+        self.render_impl(ui, pipe, ui_state, true)
     }
 
     fn render(
         &mut self,
         ui: &mut egui::Ui,
         pipe: &mut UiRenderPipe<Config>,
-        _ui_state: &mut UiState,
+        ui_state: &mut UiState,
     ) {
-        self.render_impl(ui, pipe, false)
+        self.render_impl(ui, pipe, ui_state, false)
     }
 }
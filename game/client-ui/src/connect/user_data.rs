@@ -1,14 +1,49 @@
+use std::time::Duration;
+
 use game_config::config::Config;
+use graphics::handles::{
+    canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
+};
 use hiarc::{hiarc_safer_rc_refcell, Hiarc};
 
-use crate::events::UiEvents;
+use crate::{events::UiEvents, main_menu::server_icon::ServerIcon};
+
+/// Why the connection to the server was lost, like the disconnect reason
+/// of the network, which the ui can't depend on.
+/// The ui turns it into text, so it can be localized.
+#[derive(Debug, Clone, Hiarc)]
+pub enum DisconnectReason {
+    Left,
+    Kicked(String),
+    /// Without a duration the ban is permanent.
+    Banned {
+        reason: String,
+        duration: Option<Duration>,
+    },
+    ServerShutdown,
+    MapChange,
+    Timeout,
+    VersionMismatch {
+        server_version: u64,
+    },
+    Other(String),
+}
 
 #[derive(Debug, Clone, Hiarc)]
 pub enum ConnectModes {
     Connecting,
-    Queue { msg: String },
-    ConnectingErr { msg: String },
-    DisconnectErr { msg: String },
+    Queue {
+        msg: String,
+    },
+    ConnectingErr {
+        msg: String,
+    },
+    DisconnectErr {
+        reason: DisconnectReason,
+        /// the disconnect was likely temporary,
+        /// so the client connects again automatically
+        reconnect: bool,
+    },
 }
 
 #[hiarc_safer_rc_refcell]
@@ -36,4 +71,9 @@ pub struct UserData<'a> {
     pub mode: &'a ConnectMode,
     pub config: &'a mut Config,
     pub events: &'a UiEvents,
+
+    /// the icon of the server the client connects to
+    pub icon: Option<&'a ServerIcon>,
+    pub stream_handle: &'a GraphicsStreamHandle,
+    pub canvas_handle: &'a GraphicsCanvasHandle,
 }
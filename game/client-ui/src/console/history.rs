@@ -0,0 +1,226 @@
+/// Command history of a console,
+/// the newest command is the last entry.
+#[derive(Debug, Default)]
+pub struct ConsoleHistory {
+    pub entries: Vec<String>,
+
+    /// Index of the entry that is currently shown while navigating
+    /// the history with the arrow keys.
+    index: Option<usize>,
+    /// The input that was typed before the navigation started.
+    draft: String,
+
+    /// The query of the reverse search (ctrl + r), if active.
+    pub search: Option<String>,
+    /// Index of the entry that matches the search query.
+    pub search_index: Option<usize>,
+
+    /// The history changed since it was saved the last time.
+    pub changed: bool,
+}
+
+impl ConsoleHistory {
+    pub const MAX_ENTRIES: usize = 1000;
+
+    /// One command per line.
+    pub fn from_lines(lines: &str) -> Self {
+        let mut entries: Vec<String> = lines
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.to_string())
+            .collect();
+        entries.drain(0..entries.len().saturating_sub(Self::MAX_ENTRIES));
+        Self {
+            entries,
+            ..Default::default()
+        }
+    }
+
+    pub fn to_lines(&self) -> String {
+        let mut res = self.entries.join("\n");
+        res.push('\n');
+        res
+    }
+
+    /// Prepends older entries (e.g. loaded from disk) to the history.
+    pub fn prepend(&mut self, mut older: ConsoleHistory) {
+        older.entries.append(&mut self.entries);
+        older
+            .entries
+            .drain(0..older.entries.len().saturating_sub(Self::MAX_ENTRIES));
+        self.entries = older.entries;
+        self.reset_navigation();
+    }
+
+    /// Adds an executed command, consecutive duplicates are ignored.
+    pub fn push(&mut self, cmd: &str) {
+        self.reset_navigation();
+        let cmd = cmd.trim();
+        if cmd.is_empty() || self.entries.last().is_some_and(|last| last == cmd) {
+            return;
+        }
+        self.entries.push(cmd.to_string());
+        self.entries
+            .drain(0..self.entries.len().saturating_sub(Self::MAX_ENTRIES));
+        self.changed = true;
+    }
+
+    pub fn reset_navigation(&mut self) {
+        self.index = None;
+        self.draft.clear();
+    }
+
+    /// Moves to the next older entry, `cur` is the current input.
+    pub fn prev(&mut self, cur: &str) -> Option<&str> {
+        let index = match self.index {
+            Some(index) => index.checked_sub(1)?,
+            None => {
+                let index = self.entries.len().checked_sub(1)?;
+                self.draft = cur.to_string();
+                index
+            }
+        };
+        self.index = Some(index);
+        self.entries.get(index).map(|s| s.as_str())
+    }
+
+    /// Moves to the next newer entry, after the newest entry
+    /// the input from before the navigation is restored.
+    pub fn next(&mut self) -> Option<String> {
+        let index = self.index? + 1;
+        if index < self.entries.len() {
+            self.index = Some(index);
+            Some(self.entries[index].clone())
+        } else {
+            self.index = None;
+            Some(std::mem::take(&mut self.draft))
+        }
+    }
+
+    pub fn start_search(&mut self) {
+        self.search = Some(String::new());
+        self.search_index = None;
+    }
+
+    pub fn stop_search(&mut self) {
+        self.search = None;
+        self.search_index = None;
+    }
+
+    /// Searches the newest entry containing the query that is older
+    /// than the current match (if `older` is `true`) or starting from
+    /// the current match otherwise.
+    pub fn search_next(&mut self, older: bool) {
+        let Some(query) = &self.search else {
+            return;
+        };
+        let end = match self.search_index {
+            Some(index) if older => index,
+            Some(index) => index + 1,
+            None => self.entries.len(),
+        };
+        let found = self.entries[..end.min(self.entries.len())]
+            .iter()
+            .rposition(|entry| entry.contains(query.as_str()));
+        // keep the last match if there is no older one
+        if found.is_some() || !older {
+            self.search_index = found;
+        }
+    }
+
+    pub fn search_match(&self) -> Option<&str> {
+        self.search_index
+            .and_then(|index| self.entries.get(index))
+            .map(|s| s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ConsoleHistory;
+
+    fn history(entries: &[&str]) -> ConsoleHistory {
+        let mut history = ConsoleHistory::default();
+        for entry in entries {
+            history.push(entry);
+        }
+        history
+    }
+
+    #[test]
+    fn push_skips_duplicates_and_empty_commands() {
+        let history = history(&["a", "b", "b", " ", " c "]);
+        assert_eq!(history.entries, ["a", "b", "c"]);
+        assert!(history.changed);
+
+        let mut history = ConsoleHistory::default();
+        for i in 0..ConsoleHistory::MAX_ENTRIES + 1 {
+            history.push(&i.to_string());
+        }
+        assert_eq!(history.entries.len(), ConsoleHistory::MAX_ENTRIES);
+        assert_eq!(history.entries[0], "1");
+    }
+
+    #[test]
+    fn navigate_and_restore_the_draft() {
+        let mut history = history(&["a", "b", "c"]);
+        assert_eq!(history.prev("draft"), Some("c"));
+        assert_eq!(history.prev("c"), Some("b"));
+        assert_eq!(history.prev("b"), Some("a"));
+        // the oldest entry stays selected
+        assert_eq!(history.prev("a"), None);
+
+        assert_eq!(history.next().as_deref(), Some("b"));
+        assert_eq!(history.next().as_deref(), Some("c"));
+        assert_eq!(history.next().as_deref(), Some("draft"));
+        assert_eq!(history.next(), None);
+
+        // executing a command starts the navigation at the newest entry again
+        history.prev("");
+        history.prev("");
+        history.push("d");
+        assert_eq!(history.prev(""), Some("d"));
+    }
+
+    #[test]
+    fn load_and_prepend_older_entries() {
+        let older = ConsoleHistory::from_lines("a\n\n  \nb\n");
+        assert_eq!(older.entries, ["a", "b"]);
+        assert!(!older.changed);
+
+        let mut history = history(&["c"]);
+        history.prepend(older);
+        assert_eq!(history.entries, ["a", "b", "c"]);
+        assert_eq!(history.to_lines(), "a\nb\nc\n");
+        assert_eq!(
+            ConsoleHistory::from_lines(&history.to_lines()).entries,
+            history.entries
+        );
+    }
+
+    #[test]
+    fn reverse_search() {
+        let mut history = history(&["say hi", "kill", "say bye"]);
+        history.start_search();
+        history.search = Some("say".to_string());
+        history.search_next(false);
+        assert_eq!(history.search_match(), Some("say bye"));
+        history.search_next(true);
+        assert_eq!(history.search_match(), Some("say hi"));
+        // the last match is kept if there is no older one
+        history.search_next(true);
+        assert_eq!(history.search_match(), Some("say hi"));
+
+        // a changed query starts at the current match
+        history.search = Some("hi".to_string());
+        history.search_next(false);
+        assert_eq!(history.search_match(), Some("say hi"));
+        history.search = Some("not found".to_string());
+        history.search_next(false);
+        assert_eq!(history.search_match(), None);
+
+        history.stop_search();
+        assert!(history.search.is_none());
+        assert_eq!(history.search_match(), None);
+    }
+}
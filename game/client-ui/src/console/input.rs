@@ -6,6 +6,7 @@ use client_types::console::{
 use command_parser::parser::{parse, CommandParseResult, CommandType, CommandsTyped};
 use egui::{
     text::{CCursor, LayoutJob},
+    text_edit::TextEditState,
     text_selection::CCursorRange,
     Color32, FontId, Id, Layout, RichText, TextFormat,
 };
@@ -17,6 +18,75 @@ use super::{
     utils::{find_matches, run_commands},
 };
 
+const INPUT_ID: &str = "console-input";
+
+fn set_cursor_to_end(ui: &egui::Ui, state: &mut TextEditState, msg: &str, id: Id) {
+    state.cursor.set_char_range(Some(CCursorRange::one(CCursor {
+        index: msg.chars().count(),
+        ..Default::default()
+    })));
+    state.clone().store(ui.ctx(), id);
+}
+
+/// reverse search (ctrl + r) through the command history,
+/// enter puts the match into the console input.
+fn render_reverse_search(
+    ui: &mut egui::Ui,
+    pipe: &mut UiRenderPipe<UserData>,
+    ui_state: &mut UiState,
+    has_text_selection: bool,
+) {
+    let mouse_is_down = ui.input(|i| i.any_touches() || i.pointer.any_down());
+
+    ui.style_mut().spacing.item_spacing.x = 0.0;
+    ui.horizontal(|ui| {
+        ui.add_space(5.0);
+        ui.label(RichText::new("(reverse-i-search)`").font(FontId::monospace(12.0)));
+        let history = &mut *pipe.user_data.history;
+        let query = history.search.get_or_insert_with(Default::default);
+        let search = egui::TextEdit::singleline(query)
+            .font(FontId::monospace(12.0))
+            .id(Id::new("console-search-input"))
+            .desired_width(150.0)
+            .frame(false)
+            .show(ui);
+        ui.label(RichText::new("`: ").font(FontId::monospace(12.0)));
+        ui.label(
+            RichText::new(history.search_match().unwrap_or_default())
+                .font(FontId::monospace(12.0))
+                .color(Color32::GOLD),
+        );
+
+        let (enter, older) = ui.input(|i| {
+            (
+                i.key_pressed(egui::Key::Enter),
+                i.modifiers.ctrl && i.key_pressed(egui::Key::R),
+            )
+        });
+        if search.response.changed() {
+            history.search_next(false);
+        }
+        if older {
+            history.search_next(true);
+        }
+
+        if search.response.lost_focus() {
+            if enter {
+                if let Some(entry) = history.search_match() {
+                    *pipe.user_data.msg = entry.to_string();
+                    let inp_id = Id::new(INPUT_ID);
+                    let mut state = TextEditState::load(ui.ctx(), inp_id).unwrap_or_default();
+                    set_cursor_to_end(ui, &mut state, pipe.user_data.msg, inp_id);
+                }
+            }
+            history.stop_search();
+            history.reset_navigation();
+        } else if (!mouse_is_down && !has_text_selection) || ui_state.hint_had_input {
+            search.response.request_focus();
+        }
+    });
+}
+
 /// console input
 pub fn render(
     ui: &mut egui::Ui,
@@ -25,6 +95,11 @@ pub fn render(
     has_text_selection: bool,
     cmds: &CommandsTyped,
 ) {
+    if pipe.user_data.history.search.is_some() {
+        render_reverse_search(ui, pipe, ui_state, has_text_selection);
+        return;
+    }
+
     let mouse_is_down = ui.input(|i| i.any_touches() || i.pointer.any_down());
 
     let msg_before_inp = pipe.user_data.msg.clone();
@@ -37,7 +112,7 @@ pub fn render(
         ui.with_layout(
             Layout::left_to_right(egui::Align::Max).with_main_justify(true),
             |ui| {
-                let inp_id = Id::new("console-input");
+                let inp_id = Id::new(INPUT_ID);
 
                 let mut layouter = |ui: &egui::Ui, string: &str, _wrap_width: f32| {
                     let cmd = cmds.iter();
@@ -142,11 +217,14 @@ pub fn render(
                     .char_range()
                     .map(|cursor| cursor.primary.index)
                     .unwrap_or_default();
-                let (enter, tab, space, modifiers) = ui.input(|i| {
+                let (enter, tab, space, up, down, reverse_search, modifiers) = ui.input(|i| {
                     (
                         i.key_pressed(egui::Key::Enter),
                         i.key_pressed(egui::Key::Tab),
                         i.key_pressed(egui::Key::Space),
+                        i.key_pressed(egui::Key::ArrowUp),
+                        i.key_pressed(egui::Key::ArrowDown),
+                        i.modifiers.ctrl && i.key_pressed(egui::Key::R),
                         i.modifiers,
                     )
                 });
@@ -187,6 +265,7 @@ pub fn render(
                             &mut pipe.user_data.config.game,
                             pipe.user_data.msgs,
                         );
+                        pipe.user_data.history.push(pipe.user_data.msg);
                         pipe.user_data.msg.clear();
                     } else if tab {
                         // nothing to do here
@@ -260,6 +339,23 @@ pub fn render(
                 } else if (!mouse_is_down && !has_text_selection) || ui_state.hint_had_input {
                     label.response.request_focus();
                 }
+                if reverse_search {
+                    pipe.user_data.history.start_search();
+                    pipe.user_data.history.search_next(false);
+                } else if (up || down) && pipe.user_data.select_index.is_none() {
+                    let entry = if up {
+                        pipe.user_data
+                            .history
+                            .prev(pipe.user_data.msg)
+                            .map(|entry| entry.to_string())
+                    } else {
+                        pipe.user_data.history.next()
+                    };
+                    if let Some(entry) = entry {
+                        *pipe.user_data.msg = entry;
+                        set_cursor_to_end(ui, &mut label.state, pipe.user_data.msg, inp_id);
+                    }
+                }
                 if tab {
                     // select next entry
                     let entries = find_matches(
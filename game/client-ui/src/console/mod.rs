@@ -1,4 +1,5 @@
 pub mod console_list;
+pub mod history;
 pub mod input;
 pub mod input_err;
 pub mod main_frame;
@@ -1,6 +1,8 @@
 use client_types::console::ConsoleEntry;
 use game_config::config::Config;
 
+use super::history::ConsoleHistory;
+
 pub struct UserData<'a> {
     pub entries: &'a Vec<ConsoleEntry>,
     pub config: &'a mut Config,
@@ -8,4 +10,5 @@ pub struct UserData<'a> {
     pub msg: &'a mut String,
     pub cursor: &'a mut usize,
     pub select_index: &'a mut Option<usize>,
+    pub history: &'a mut ConsoleHistory,
 }
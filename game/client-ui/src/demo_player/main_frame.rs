@@ -2,8 +2,14 @@ use std::time::Duration;
 
 use base::duration_ext::DurationToRaceStr;
 use egui::{
-    Align2, Button, Color32, FontId, Frame, Grid, Layout, Rect, Rounding, Shadow, Stroke,
-    TopBottomPanel, Vec2, Window,
+    Align2, Button, Color32, ComboBox, FontId, Frame, Grid, Key, Layout, Rect, Rounding,
+    ScrollArea, Sense, Shadow, Slider, Stroke, TopBottomPanel, Vec2, Window,
+};
+use game_interface::types::{
+    character_info::{CharacterInfoRedaction, RedactedName},
+    emoticons::IntoEnumIterator,
+    game::GameEntityId,
+    weapons::WeaponType,
 };
 
 use ui_base::{
@@ -11,14 +17,249 @@ use ui_base::{
     utils::{add_horizontal_margins, icon_font_text_sized},
 };
 
-use crate::demo_player::user_data::{DemoViewerEvent, DemoViewerEventExport};
+use crate::demo_player::user_data::{
+    DemoMarkerKind, DemoPlayerActionKind, DemoPlayerActionQuery, DemoViewerCamera, DemoViewerEvent,
+    DemoViewerEventExport, DemoViewerUiState,
+};
 
 use super::user_data::UserData;
 
+const WAVEFORM_HEIGHT: f32 = 12.0;
+
+/// Draws the loudness of the demo as mirrored bars around the center of the rect.
+fn render_waveform(ui: &egui::Ui, rect: Rect, waveform: &[f32]) {
+    let bar_width = rect.width() / waveform.len().max(1) as f32;
+    for (index, sample) in waveform.iter().enumerate() {
+        let height = (rect.height() * sample.clamp(0.0, 1.0)).max(1.0);
+        ui.painter().rect_filled(
+            Rect::from_center_size(
+                egui::pos2(
+                    rect.min.x + bar_width * (index as f32 + 0.5),
+                    rect.center().y,
+                ),
+                egui::vec2(bar_width.max(1.0), height),
+            ),
+            Rounding::default(),
+            Color32::from_white_alpha(100),
+        );
+    }
+}
+
+fn marker_color_and_name(kind: DemoMarkerKind) -> (Color32, &'static str) {
+    match kind {
+        DemoMarkerKind::Kill => (Color32::from_rgb(220, 50, 50), "Kill"),
+        DemoMarkerKind::FlagCapture => (Color32::from_rgb(50, 120, 255), "Flag capture"),
+        DemoMarkerKind::RoundEnd => (Color32::from_rgb(50, 200, 50), "Round end"),
+    }
+}
+
+fn camera_name(camera: &DemoViewerCamera, characters: &[(GameEntityId, String)]) -> String {
+    match camera {
+        DemoViewerCamera::Follow(None) => "Recorder".to_string(),
+        DemoViewerCamera::Follow(Some(id)) => characters
+            .iter()
+            .find(|(character_id, _)| character_id == id)
+            .map(|(_, name)| name.clone())
+            .unwrap_or_else(|| "Recorder".to_string()),
+        DemoViewerCamera::Free { .. } => "Free camera".to_string(),
+    }
+}
+
+fn action_kind_name(kind: DemoPlayerActionKind) -> &'static str {
+    match kind {
+        DemoPlayerActionKind::Kill => "Kills",
+        DemoPlayerActionKind::Death => "Deaths",
+        DemoPlayerActionKind::FlagCapture => "Flag captures",
+    }
+}
+
+/// A window to search the actions of players,
+/// e.g. "all my grenade kills", the results skip to their time.
+fn render_action_search(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let Some(mut query) = pipe.user_data.state.action_search else {
+        return;
+    };
+    let characters = pipe.user_data.characters;
+    let mut open = true;
+    Window::new("Search player actions")
+        .open(&mut open)
+        .anchor(Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -60.0))
+        .show(ui.ctx(), |ui| {
+            Grid::new("demo-action-search-grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Player:");
+                    ComboBox::new("demo-action-search-player", "")
+                        .selected_text(
+                            query
+                                .player
+                                .and_then(|player| characters.iter().find(|(id, _)| *id == player))
+                                .map(|(_, name)| name.as_str())
+                                .unwrap_or("Everyone"),
+                        )
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut query.player, None, "Everyone");
+                            for (id, name) in characters {
+                                ui.selectable_value(&mut query.player, Some(*id), name);
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Action:");
+                    ComboBox::new("demo-action-search-kind", "")
+                        .selected_text(action_kind_name(query.kind))
+                        .show_ui(ui, |ui| {
+                            for kind in [
+                                DemoPlayerActionKind::Kill,
+                                DemoPlayerActionKind::Death,
+                                DemoPlayerActionKind::FlagCapture,
+                            ] {
+                                ui.selectable_value(&mut query.kind, kind, action_kind_name(kind));
+                            }
+                        });
+                    ui.end_row();
+
+                    if query.kind != DemoPlayerActionKind::FlagCapture {
+                        ui.label("Weapon:");
+                        ComboBox::new("demo-action-search-weapon", "")
+                            .selected_text(
+                                query
+                                    .weapon
+                                    .map(|weapon| format!("{weapon:?}"))
+                                    .unwrap_or_else(|| "Any".to_string()),
+                            )
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut query.weapon, None, "Any");
+                                for weapon in WeaponType::iter() {
+                                    ui.selectable_value(
+                                        &mut query.weapon,
+                                        Some(weapon),
+                                        format!("{weapon:?}"),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+                    }
+                });
+            ui.separator();
+
+            let results = pipe.user_data.action_results;
+            if results.is_empty() {
+                ui.label("Nothing found.");
+            }
+            ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                for &time in results {
+                    if ui.button(time.to_race_string()).clicked() {
+                        pipe.user_data.events.push(DemoViewerEvent::SkipTo { time });
+                    }
+                }
+            });
+        });
+
+    if !open {
+        pipe.user_data.state.action_search = None;
+    } else if pipe.user_data.state.action_search != Some(query) {
+        pipe.user_data.state.action_search = Some(query);
+        pipe.user_data
+            .events
+            .push(DemoViewerEvent::SearchActions(query));
+    }
+}
+
+/// The anonymization options of the export, as rows of its grid.
+fn render_export_redaction(
+    ui: &mut egui::Ui,
+    redaction: &mut Option<CharacterInfoRedaction>,
+    characters: &[(GameEntityId, String)],
+) {
+    ui.label("Anonymize players:");
+    let mut anonymize = redaction.is_some();
+    ui.checkbox(&mut anonymize, "");
+    ui.end_row();
+    if anonymize != redaction.is_some() {
+        *redaction = anonymize.then(Default::default);
+    }
+    let Some(redaction) = redaction else {
+        return;
+    };
+
+    ui.label("Players:");
+    ui.vertical(|ui| {
+        let mut all = redaction.players.is_none();
+        ui.checkbox(&mut all, "All players");
+        if all != redaction.players.is_none() {
+            redaction.players = (!all).then(Vec::new);
+        }
+        if let Some(players) = &mut redaction.players {
+            for (id, name) in characters {
+                let mut selected = players.contains(id);
+                if ui.checkbox(&mut selected, name).changed() {
+                    if selected {
+                        players.push(*id);
+                    } else {
+                        players.retain(|player| player != id);
+                    }
+                }
+            }
+        }
+    });
+    ui.end_row();
+
+    ui.label("Names:");
+    ComboBox::new("demo-export-redacted-name", "")
+        .selected_text(match redaction.name {
+            RedactedName::Pseudonym => "Pseudonym",
+            RedactedName::Strip => "Remove",
+        })
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut redaction.name, RedactedName::Pseudonym, "Pseudonym");
+            ui.selectable_value(&mut redaction.name, RedactedName::Strip, "Remove");
+        });
+    ui.end_row();
+
+    ui.label("Reset skins:");
+    ui.checkbox(&mut redaction.cosmetics, "")
+        .on_hover_text("Skins & colors can identify players too.");
+    ui.end_row();
+}
+
+/// Drag & scroll on the view that is not covered by the panel
+/// move & zoom the free camera.
+fn render_free_camera(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    if !matches!(pipe.user_data.camera, DemoViewerCamera::Free { .. }) {
+        return;
+    }
+    let rect = ui.ctx().screen_rect();
+    let view = ui.interact(rect, ui.id().with("demo-free-camera"), Sense::drag());
+    if view.dragged() {
+        let delta = view.drag_delta();
+        // dragging moves the map with the pointer
+        pipe.user_data.events.push(DemoViewerEvent::FreeCameraMove {
+            diff: egui::vec2(-delta.x / rect.width(), -delta.y / rect.height()),
+        });
+    }
+    if view.hovered() {
+        let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+        if scroll.abs() > 0.01 {
+            pipe.user_data.events.push(DemoViewerEvent::FreeCameraZoom {
+                factor: (-scroll / 200.0).exp(),
+            });
+        }
+    }
+}
+
 /// not required
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_only: bool) {
+    if !main_frame_only {
+        render_free_camera(ui, pipe);
+    }
+    let waveform = pipe.user_data.waveform;
     TopBottomPanel::bottom("demo-main")
-        .exact_height(40.0)
+        .exact_height(if waveform.is_some() {
+            40.0 + WAVEFORM_HEIGHT
+        } else {
+            40.0
+        })
         .frame(if main_frame_only {
             Frame::window(ui.style())
                 .shadow(Shadow::NONE)
@@ -41,6 +282,17 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
             ui.add_space(10.0);
 
             rect.set_height(10.0);
+            // the waveform strip can be used for seeking, too
+            let mut seek_rect = rect;
+            if let Some(waveform) = waveform {
+                ui.add_space(WAVEFORM_HEIGHT);
+                let waveform_rect = Rect::from_min_size(
+                    rect.left_bottom(),
+                    egui::vec2(rect.width(), WAVEFORM_HEIGHT),
+                );
+                render_waveform(ui, waveform_rect, waveform);
+                seek_rect = seek_rect.union(waveform_rect);
+            }
             let state = &mut *pipe.user_data.state;
             if let Some(pointer_pos) = ui.input(|i| {
                 ((state.pointer_on_timeline && i.pointer.primary_down())
@@ -50,7 +302,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                         .latest_pos()
                         .or(i.pointer.hover_pos())
                         .or(i.pointer.interact_pos())
-                        .and_then(|p| (state.pointer_on_timeline || rect.contains(p)).then_some(p)),
+                        .and_then(|p| {
+                            (state.pointer_on_timeline || seek_rect.contains(p)).then_some(p)
+                        }),
                 )
                 .flatten()
             }) {
@@ -67,9 +321,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
             }
 
             if let Some(pointer_pos) = ui.input(|i| {
-                i.pointer
-                    .hover_pos()
-                    .and_then(|p| (!state.pointer_on_timeline && rect.contains(p)).then_some(p))
+                i.pointer.hover_pos().and_then(|p| {
+                    (!state.pointer_on_timeline && seek_rect.contains(p)).then_some(p)
+                })
             }) {
                 let time = Duration::from_secs_f64(
                     (pipe.user_data.max_duration.as_secs_f64()
@@ -149,6 +403,50 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
             };
             draw_export_rect(state.left);
             draw_export_rect(state.right);
+            for bookmark in pipe.user_data.bookmarks {
+                let at = (bookmark.as_secs_f32()
+                    / pipe.user_data.max_duration.as_secs_f32().max(0.0001))
+                .clamp(0.0, 1.0);
+                ui.painter().rect_filled(
+                    Rect::from_center_size(
+                        egui::pos2(
+                            rect.left_center().x + len_rect.width() * at,
+                            rect.left_center().y,
+                        ),
+                        egui::vec2(2.0, rect.height()),
+                    ),
+                    Rounding::default(),
+                    Color32::from_rgb(255, 200, 0),
+                );
+            }
+            for (index, marker) in pipe.user_data.markers.iter().enumerate() {
+                let at = (marker.time.as_secs_f32()
+                    / pipe.user_data.max_duration.as_secs_f32().max(0.0001))
+                .clamp(0.0, 1.0);
+                let marker_rect = Rect::from_center_size(
+                    egui::pos2(
+                        rect.left_center().x + len_rect.width() * at,
+                        rect.left_center().y,
+                    ),
+                    egui::vec2(4.0, rect.height()),
+                );
+                let (color, name) = marker_color_and_name(marker.kind);
+                ui.painter()
+                    .rect_filled(marker_rect, Rounding::default(), color);
+                if ui
+                    .interact(
+                        marker_rect,
+                        ui.id().with(("demo-marker", index)),
+                        Sense::click(),
+                    )
+                    .on_hover_text(format!("{name} at {}", marker.time.to_race_string()))
+                    .clicked()
+                {
+                    pipe.user_data
+                        .events
+                        .push(DemoViewerEvent::SkipTo { time: marker.time });
+                }
+            }
             if let Some((left, right)) = state.left.zip(state.right) {
                 let at = (left.as_secs_f32()
                     / pipe.user_data.max_duration.as_secs_f32().max(0.0001))
@@ -171,6 +469,65 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                 );
             }
 
+            if let Some((from, until)) = *pipe.user_data.loop_range {
+                let at = (from.as_secs_f32()
+                    / pipe.user_data.max_duration.as_secs_f32().max(0.0001))
+                .clamp(0.0, 1.0);
+                let until = (until.as_secs_f32()
+                    / pipe.user_data.max_duration.as_secs_f32().max(0.0001))
+                .clamp(0.0, 1.0);
+                let width = (until - at) * len_rect.width();
+                ui.painter().rect_filled(
+                    Rect::from_center_size(
+                        egui::pos2(
+                            rect.left_center().x + len_rect.width() * at + width / 2.0,
+                            rect.left_center().y,
+                        ),
+                        egui::vec2(width, rect.height()),
+                    ),
+                    Rounding::default(),
+                    Color32::from_rgba_unmultiplied(0, 150, 0, 100),
+                );
+            }
+            let toggle_loop = |state: &DemoViewerUiState,
+                               loop_range: &Option<(Duration, Duration)>,
+                               events: &mut Vec<DemoViewerEvent>| {
+                if loop_range.is_some() {
+                    events.push(DemoViewerEvent::StopLoop);
+                } else if let Some((from, until)) = state.left.zip(state.right) {
+                    events.push(DemoViewerEvent::Loop { from, until });
+                }
+            };
+
+            // bookmark & kill hotkeys, as long as no text field wants the keys
+            if !ui.ctx().wants_keyboard_input() {
+                ui.input(|i| {
+                    if i.key_pressed(Key::L) {
+                        toggle_loop(
+                            pipe.user_data.state,
+                            pipe.user_data.loop_range,
+                            pipe.user_data.events,
+                        );
+                    }
+                    if i.key_pressed(Key::B) {
+                        pipe.user_data.events.push(DemoViewerEvent::AddBookmark);
+                    }
+                    if i.key_pressed(Key::Comma) {
+                        pipe.user_data.events.push(DemoViewerEvent::PrevBookmark);
+                    }
+                    if i.key_pressed(Key::Period) {
+                        pipe.user_data.events.push(DemoViewerEvent::NextBookmark);
+                    }
+                    if i.key_pressed(Key::K) {
+                        pipe.user_data.events.push(if i.modifiers.shift {
+                            DemoViewerEvent::PrevKill
+                        } else {
+                            DemoViewerEvent::NextKill
+                        });
+                    }
+                });
+            }
+
             const FONT_SIZE: f32 = 20.0;
             add_horizontal_margins(ui, |ui| {
                 let rect = ui.available_rect_before_wrap();
@@ -235,16 +592,16 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
 
                         ui.add_space(15.0);
 
-                        // backward, forward
+                        // backward, forward, seek as long as the button is held
                         if ui
                             .button(icon_font_text_sized("\u{f04a}", FONT_SIZE))
-                            .clicked()
+                            .is_pointer_button_down_on()
                         {
                             pipe.user_data.events.push(DemoViewerEvent::Backward);
                         }
                         if ui
                             .button(icon_font_text_sized("\u{f04e}", FONT_SIZE))
-                            .clicked()
+                            .is_pointer_button_down_on()
                         {
                             pipe.user_data.events.push(DemoViewerEvent::Forward);
                         }
@@ -272,9 +629,44 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                         {
                             pipe.user_data.events.push(DemoViewerEvent::SpeedFaster);
                         }
+                        let mut speed = pipe.user_data.speed.to_num::<f64>();
+                        if ui
+                            .add(
+                                Slider::new(&mut speed, 0.1..=8.0)
+                                    .logarithmic(true)
+                                    .show_value(false),
+                            )
+                            .changed()
+                        {
+                            pipe.user_data
+                                .events
+                                .push(DemoViewerEvent::SetSpeed { speed });
+                        }
+                        if ui
+                            .selectable_label(
+                                *pipe.user_data.preserve_sound_pitch,
+                                icon_font_text_sized("\u{f028}", FONT_SIZE),
+                            )
+                            .on_hover_text("Keep the pitch of the sounds between 0.5x and 2x speed")
+                            .clicked()
+                        {
+                            pipe.user_data
+                                .events
+                                .push(DemoViewerEvent::PreserveSoundPitchToggle);
+                        }
 
                         ui.add_space(15.0);
                         ui.colored_label(Color32::WHITE, pipe.user_data.name);
+                        if *pipe.user_data.corrupted {
+                            ui.colored_label(Color32::YELLOW, "corrupted parts skipped")
+                                .on_hover_text(
+                                    "Parts of this demo are corrupted and can not be played back",
+                                );
+                        }
+                        if !pipe.user_data.mod_warnings.is_empty() {
+                            ui.colored_label(Color32::YELLOW, "game mods differ")
+                                .on_hover_text(pipe.user_data.mod_warnings.join("\n"));
+                        }
                     });
                     ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                         // exit
@@ -287,6 +679,153 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
 
                         ui.add_space(15.0);
 
+                        // scoreboard
+                        if ui
+                            .button(icon_font_text_sized("\u{f0cb}", FONT_SIZE))
+                            .clicked()
+                        {
+                            pipe.user_data
+                                .events
+                                .push(DemoViewerEvent::ScoreboardToggle);
+                        }
+
+                        // search the actions of players
+                        if ui
+                            .button(icon_font_text_sized("\u{f002}", FONT_SIZE))
+                            .on_hover_text("Search player actions, e.g. all grenade kills")
+                            .clicked()
+                        {
+                            let state = &mut *pipe.user_data.state;
+                            if state.action_search.take().is_none() {
+                                let query = DemoPlayerActionQuery::default();
+                                state.action_search = Some(query);
+                                pipe.user_data
+                                    .events
+                                    .push(DemoViewerEvent::SearchActions(query));
+                            }
+                        }
+
+                        // bookmark (hotkey: B)
+                        if ui
+                            .button(icon_font_text_sized("\u{f02e}", FONT_SIZE))
+                            .on_hover_text("Bookmark (B), previous/next bookmark (, / .)")
+                            .clicked()
+                        {
+                            pipe.user_data.events.push(DemoViewerEvent::AddBookmark);
+                        }
+
+                        // point of view, only merged demos have multiple
+                        let povs = pipe.user_data.povs;
+                        if povs.len() > 1 {
+                            let cur_pov = *pipe.user_data.cur_pov;
+                            ComboBox::new("demo-pov", "")
+                                .selected_text(
+                                    povs.get(cur_pov)
+                                        .map(|pov| pov.as_str())
+                                        .unwrap_or_default(),
+                                )
+                                .show_ui(ui, |ui| {
+                                    for (pov, name) in povs.iter().enumerate() {
+                                        if ui.selectable_label(pov == cur_pov, name).clicked() {
+                                            pipe.user_data
+                                                .events
+                                                .push(DemoViewerEvent::SwitchPov { pov });
+                                        }
+                                    }
+                                });
+                        }
+
+                        // camera, follows a character or is free
+                        let camera = *pipe.user_data.camera;
+                        let characters = pipe.user_data.characters;
+                        ComboBox::new("demo-camera", "")
+                            .selected_text(camera_name(&camera, characters))
+                            .show_ui(ui, |ui| {
+                                if ui
+                                    .selectable_label(
+                                        camera == DemoViewerCamera::Follow(None),
+                                        "Recorder",
+                                    )
+                                    .clicked()
+                                {
+                                    pipe.user_data
+                                        .events
+                                        .push(DemoViewerEvent::FollowCharacter { id: None });
+                                }
+                                for (id, name) in characters {
+                                    if ui
+                                        .selectable_label(
+                                            camera == DemoViewerCamera::Follow(Some(*id)),
+                                            name,
+                                        )
+                                        .clicked()
+                                    {
+                                        pipe.user_data.events.push(
+                                            DemoViewerEvent::FollowCharacter { id: Some(*id) },
+                                        );
+                                    }
+                                }
+                                if ui
+                                    .selectable_label(
+                                        matches!(camera, DemoViewerCamera::Free { .. }),
+                                        "Free camera",
+                                    )
+                                    .clicked()
+                                {
+                                    pipe.user_data.events.push(DemoViewerEvent::FreeCamera);
+                                }
+                            });
+
+                        // a second character side by side, e.g. for duels
+                        let split_screen = *pipe.user_data.split_screen;
+                        ComboBox::new("demo-split-screen", "")
+                            .selected_text(
+                                split_screen
+                                    .and_then(|split_id| {
+                                        characters.iter().find(|(id, _)| *id == split_id)
+                                    })
+                                    .map(|(_, name)| name.as_str())
+                                    .unwrap_or("No split screen"),
+                            )
+                            .show_ui(ui, |ui| {
+                                if ui
+                                    .selectable_label(split_screen.is_none(), "No split screen")
+                                    .clicked()
+                                {
+                                    pipe.user_data
+                                        .events
+                                        .push(DemoViewerEvent::SplitScreen { id: None });
+                                }
+                                for (id, name) in characters {
+                                    if ui
+                                        .selectable_label(split_screen == Some(*id), name)
+                                        .clicked()
+                                    {
+                                        pipe.user_data
+                                            .events
+                                            .push(DemoViewerEvent::SplitScreen { id: Some(*id) });
+                                    }
+                                }
+                            });
+
+                        ui.add_space(15.0);
+
+                        // loop between the brackets (hotkey: L)
+                        let loop_range = pipe.user_data.loop_range;
+                        if ui
+                            .add_enabled(
+                                loop_range.is_some()
+                                    || (pipe.user_data.state.left.is_some()
+                                        && pipe.user_data.state.right.is_some()),
+                                Button::new(icon_font_text_sized("\u{f01e}", FONT_SIZE))
+                                    .selected(loop_range.is_some()),
+                            )
+                            .on_hover_text("Loop between the brackets (L)")
+                            .clicked()
+                        {
+                            toggle_loop(pipe.user_data.state, loop_range, pipe.user_data.events);
+                        }
+
                         // left bracket, right bracket, share (in reverse order)
                         let state = &mut *pipe.user_data.state;
                         if ui
@@ -301,6 +840,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                                 right: state.right.unwrap_or_default(),
                                 name: pipe.user_data.name.to_string(),
                                 remove_chat: false,
+                                redaction: None,
                             });
                         }
                         if ui
@@ -346,6 +886,12 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                                             ui.checkbox(&mut data.remove_chat, "");
                                             ui.end_row();
 
+                                            render_export_redaction(
+                                                ui,
+                                                &mut data.redaction,
+                                                characters,
+                                            );
+
                                             let cur_data = data.clone();
                                             if ui.button("Abort").clicked() {
                                                 state.export.take();
@@ -370,4 +916,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                 });
             });
         });
+
+    if !main_frame_only {
+        render_action_search(ui, pipe);
+    }
 }
@@ -1,10 +1,13 @@
 use std::time::Duration;
 
 use egui::Rect;
+use game_interface::types::{
+    character_info::CharacterInfoRedaction, game::GameEntityId, weapons::WeaponType,
+};
 use graphics::handles::{
     canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
 };
-use math::math::vector::ffixed;
+use math::math::vector::{ffixed, vec2};
 
 #[derive(Debug, Clone)]
 pub struct DemoViewerEventExport {
@@ -12,6 +15,59 @@ pub struct DemoViewerEventExport {
     pub right: Duration,
     pub name: String,
     pub remove_chat: bool,
+    /// Anonymize the players of the redaction in the snapshots & events,
+    /// `None` keeps all players as they are.
+    pub redaction: Option<CharacterInfoRedaction>,
+}
+
+/// What the camera of the demo viewer shows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DemoViewerCamera {
+    /// Follows a character, `None` follows the player that recorded the demo.
+    Follow(Option<GameEntityId>),
+    /// A camera that is moved by dragging the view
+    /// and zoomed by scrolling.
+    Free { pos: vec2, zoom: f32 },
+}
+
+impl Default for DemoViewerCamera {
+    fn default() -> Self {
+        Self::Follow(None)
+    }
+}
+
+/// Notable moments of a demo, that are marked on the timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DemoMarkerKind {
+    Kill,
+    FlagCapture,
+    RoundEnd,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DemoMarker {
+    pub time: Duration,
+    pub kind: DemoMarkerKind,
+}
+
+/// What a player did, that can be searched for in a demo.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DemoPlayerActionKind {
+    #[default]
+    Kill,
+    Death,
+    FlagCapture,
+}
+
+/// A search for the actions of players in the demo,
+/// e.g. all grenade kills of a player.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct DemoPlayerActionQuery {
+    /// `None` searches the actions of all players.
+    pub player: Option<GameEntityId>,
+    pub kind: DemoPlayerActionKind,
+    /// Only used for kills & deaths, `None` matches all weapons.
+    pub weapon: Option<WeaponType>,
 }
 
 #[derive(Debug, Clone)]
@@ -25,17 +81,74 @@ pub enum DemoViewerEvent {
     BackwardStep,
     ForwardStep,
 
+    /// Rewinds the demo, sent for every frame the button is held.
     Backward,
+    /// Fast-forwards the demo, sent for every frame the button is held.
     Forward,
 
     SpeedSlower,
     SpeedFaster,
     SpeedReset,
+    /// Set the speed from the slider.
+    SetSpeed {
+        speed: f64,
+    },
+    PreserveSoundPitchToggle,
+
+    ScoreboardToggle,
+    /// Follow a character, `None` follows the player that recorded the demo.
+    FollowCharacter {
+        id: Option<GameEntityId>,
+    },
+    /// Switch to the free camera, it starts where the camera currently is.
+    FreeCamera,
+    /// Watch a second character side by side with the camera,
+    /// `None` disables the split screen.
+    SplitScreen {
+        id: Option<GameEntityId>,
+    },
+    /// Move the free camera, relative to the size of the view.
+    FreeCameraMove {
+        diff: egui::Vec2,
+    },
+    /// Multiply the zoom of the free camera.
+    FreeCameraZoom {
+        factor: f32,
+    },
+    /// Switch to another point of view of a merged demo.
+    SwitchPov {
+        pov: usize,
+    },
 
     Export(DemoViewerEventExport),
 
-    SkipTo { time: Duration },
-    PreviewAt { rect: Rect, time: Duration },
+    SkipTo {
+        time: Duration,
+    },
+    /// Bookmark the current time.
+    AddBookmark,
+    /// Skip to the next bookmark after the current time.
+    NextBookmark,
+    /// Skip to the previous bookmark before the current time.
+    PrevBookmark,
+    /// Skip to the next kill after the current time.
+    NextKill,
+    /// Skip to the previous kill before the current time.
+    PrevKill,
+    /// Search the actions of players, the results are
+    /// in [`UserData::action_results`].
+    SearchActions(DemoPlayerActionQuery),
+    /// Play the range between the two times over and over (A-B repeat).
+    Loop {
+        from: Duration,
+        until: Duration,
+    },
+    /// Stop looping, playback continues normally.
+    StopLoop,
+    PreviewAt {
+        rect: Rect,
+        time: Duration,
+    },
 
     Close,
 }
@@ -47,6 +160,8 @@ pub struct DemoViewerUiState {
     pub left: Option<Duration>,
     pub right: Option<Duration>,
     pub export: Option<DemoViewerEventExport>,
+    /// The query of the open action search window.
+    pub action_search: Option<DemoPlayerActionQuery>,
 }
 
 pub struct UserData<'a> {
@@ -57,7 +172,35 @@ pub struct UserData<'a> {
     pub cur_duration: &'a Duration,
     pub max_duration: &'a Duration,
     pub speed: &'a ffixed,
+    /// Game sounds keep their pitch at common speeds.
+    pub preserve_sound_pitch: &'a bool,
     pub name: &'a str,
+    /// Loudness samples (`[0, 1]`) evenly spread over the demo,
+    /// `None` while still generating.
+    pub waveform: Option<&'a [f32]>,
+    /// Names of the point of views inside the demo,
+    /// only merged demos have more than one.
+    pub povs: &'a [String],
+    pub cur_pov: &'a usize,
+    pub camera: &'a DemoViewerCamera,
+    /// All characters of the current snapshot with their names,
+    /// that the camera can follow.
+    pub characters: &'a [(GameEntityId, String)],
+    /// The character that is watched side by side with the camera.
+    pub split_screen: &'a Option<GameEntityId>,
+    /// Bookmarked times, sorted.
+    pub bookmarks: &'a [Duration],
+    /// Kills, flag captures & round ends, sorted.
+    /// Empty while still generating.
+    pub markers: &'a [DemoMarker],
+    /// The times of the actions found by the last [`DemoViewerEvent::SearchActions`].
+    pub action_results: &'a [Duration],
+    /// The range that is played over and over, see [`DemoViewerEvent::Loop`].
+    pub loop_range: &'a Option<(Duration, Duration)>,
+    /// Corrupted parts of the demo were skipped.
+    pub corrupted: &'a bool,
+    /// Mismatches between the game mods of the demo and the local ones.
+    pub mod_warnings: &'a [String],
 
     pub events: &'a mut Vec<DemoViewerEvent>,
 
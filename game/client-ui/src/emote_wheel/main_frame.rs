@@ -169,4 +169,44 @@ pub fn render(
             eye,
         );
     }
+
+    // render the recently used emoticons in a row below the wheel
+    let recent = pipe.user_data.recent_emoticons;
+    let size = radius(7.0);
+    let center = rect.center();
+    let mut pos = vec2::new(
+        center.x - (recent.len().saturating_sub(1)) as f32 * size / 2.0,
+        center.y + outer_radius + size,
+    );
+    for (index, &emote) in recent.iter().enumerate() {
+        let selected = ui.input(|i| {
+            i.pointer.hover_pos().is_some_and(|p| {
+                egui::Rect::from_center_size(pos2(pos.x, pos.y), vec2(size, size)).contains(p)
+            })
+        });
+        if selected {
+            pipe.user_data
+                .events
+                .push(EmoteWheelEvent::EmoticonSelected(emote));
+        }
+        let val = ui.ctx().animate_value_with_time(
+            Id::new(format!("emote-wheel-anims-recent-{index}")),
+            if selected { 1.5 } else { 1.0 },
+            0.15,
+        );
+        render_emoticon_for_ui(
+            pipe.user_data.stream_handle,
+            pipe.user_data.canvas_handle,
+            pipe.user_data.emoticons_container,
+            ui,
+            ui_state,
+            rect,
+            None,
+            pipe.user_data.emoticon,
+            pos,
+            size * val,
+            emote,
+        );
+        pos.x += size;
+    }
 }
@@ -26,4 +26,6 @@ pub struct UserData<'a> {
     pub emoticon: &'a ResourceKey,
     pub skin: &'a ResourceKey,
     pub skin_info: &'a Option<NetworkSkinInfo>,
+    /// Newest first.
+    pub recent_emoticons: &'a [EmoticonType],
 }
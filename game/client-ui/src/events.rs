@@ -11,10 +11,14 @@ use math::math::vector::ubvec4;
 pub enum UiEvent {
     PlayDemo {
         name: PathBuf,
+        /// passphrase for encrypted demos
+        key: Option<String>,
     },
     EncodeDemoToVideo {
         name: PathBuf,
         video_name: String,
+        /// passphrase for encrypted demos
+        key: Option<String>,
     },
     RecordDemo,
     StartEditor,
@@ -10,7 +10,7 @@ use egui_extras::{Size, StripBuilder};
 use game_interface::types::render::{
     character::TeeEye,
     game::{
-        game_match::{LeadingCharacter, MatchStandings},
+        game_match::{LeadingCharacter, MatchRoundTimer, MatchSide, MatchStandings},
         GameRenderInfo,
     },
 };
@@ -36,14 +36,33 @@ pub fn render(
     if main_frame_only {
         // we don't need this
     } else {
-        let tick_time_nanos =
-            Duration::from_secs(1).as_nanos() as u64 / pipe.user_data.ticks_per_second.get();
-        let secs = *pipe.user_data.race_timer_counter / pipe.user_data.ticks_per_second.get();
-        let nanos = (*pipe.user_data.race_timer_counter % pipe.user_data.ticks_per_second.get())
-            * tick_time_nanos;
-        let race_time = Duration::new(secs, nanos as u32);
+        let ticks_per_second = pipe.user_data.ticks_per_second.get();
+        let ticks_to_duration = |ticks: u64| {
+            let tick_time_nanos = Duration::from_secs(1).as_nanos() as u64 / ticks_per_second;
+            let secs = ticks / ticks_per_second;
+            let nanos = (ticks % ticks_per_second) * tick_time_nanos;
+            Duration::new(secs, nanos as u32)
+        };
 
-        let time_str = race_time.to_race_string();
+        // matches with a time limit count down instead
+        let time_str = match pipe.user_data.game {
+            Some(GameRenderInfo::Match {
+                round_timer: MatchRoundTimer::Warmup { ticks_left },
+                ..
+            }) => format!("Warmup {}", ticks_to_duration(*ticks_left).to_race_string()),
+            Some(GameRenderInfo::Match {
+                round_timer:
+                    MatchRoundTimer::Running {
+                        ticks_left: Some(ticks_left),
+                    },
+                ..
+            }) => ticks_to_duration(*ticks_left).to_race_string(),
+            Some(GameRenderInfo::Match {
+                round_timer: MatchRoundTimer::SuddenDeath,
+                ..
+            }) => "Sudden death".to_string(),
+            _ => ticks_to_duration(*pipe.user_data.race_timer_counter).to_race_string(),
+        };
 
         let color_a = |color: Color32, a: u8| {
             Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), a)
@@ -54,9 +73,11 @@ pub fn render(
         let (max_height, rounding) = match pipe.user_data.game {
             Some(GameRenderInfo::Match {
                 standings: MatchStandings::Solo { .. },
+                ..
             }) => (60.0, Rounding::same(0.0)),
             Some(GameRenderInfo::Match {
                 standings: MatchStandings::Sided { .. },
+                ..
             }) => (
                 40.0,
                 Rounding {
@@ -79,7 +100,7 @@ pub fn render(
                            side: Side| {
             let rect = ui.available_rect_before_wrap();
             match pipe.user_data.game {
-                Some(GameRenderInfo::Match { standings }) => match standings {
+                Some(GameRenderInfo::Match { standings, .. }) => match standings {
                     MatchStandings::Solo { leading_characters } => {
                         let mut render_char =
                             |leading_character: &Option<LeadingCharacter>, left: bool| {
@@ -184,7 +205,45 @@ pub fn render(
                     MatchStandings::Sided {
                         score_red,
                         score_blue,
+                        capture_points,
                     } => {
+                        if matches!(side, Side::Right) {
+                            // one box per capture point, in the color of its owner
+                            let side_color = |side: &MatchSide| match side {
+                                MatchSide::Red => Color32::RED,
+                                MatchSide::Blue => Color32::BLUE,
+                            };
+                            for (index, capture_point) in capture_points.iter().enumerate() {
+                                let text = if capture_point.contested {
+                                    "!".to_string()
+                                } else if capture_point.capturing_side.is_some() {
+                                    format!("{:.0}%", capture_point.progress * 100.0)
+                                } else {
+                                    format!("{}", index + 1)
+                                };
+                                Frame::none()
+                                    .fill(
+                                        capture_point
+                                            .owner
+                                            .as_ref()
+                                            .map(|owner| color_a(side_color(owner), 150))
+                                            .unwrap_or(color_a(Color32::BLACK, 50)),
+                                    )
+                                    .stroke((
+                                        1.0,
+                                        capture_point
+                                            .capturing_side
+                                            .as_ref()
+                                            .map(side_color)
+                                            .unwrap_or(Color32::TRANSPARENT),
+                                    ))
+                                    .rounding(ROUNDING)
+                                    .inner_margin(Margin::same(MARGIN))
+                                    .show(ui, |ui| {
+                                        ui.colored_label(Color32::WHITE, text);
+                                    });
+                            }
+                        }
                         if let Side::Bottom(rect) = side {
                             // no spacing for points
                             ui.style_mut().spacing.item_spacing = Default::default();
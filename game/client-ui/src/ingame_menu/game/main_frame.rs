@@ -268,6 +268,17 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                                                     btn(ConfigDummyScreenAnchor::BottomLeft);
                                                     btn(ConfigDummyScreenAnchor::BottomRight);
                                                 });
+
+                                            ui.checkbox(
+                                                &mut dummy.split_screen,
+                                                "Play with the dummy in split screen.",
+                                            )
+                                            .on_hover_text(
+                                                "The dummy uses the keyboard binds of the \
+                                                dummy profile, by default the arrow keys, \
+                                                right control & right shift. Reconnect the \
+                                                dummy after changing this.",
+                                            );
                                         });
                                     if !open {
                                         config.path().query.remove("dummy_settings");
@@ -52,9 +52,11 @@ pub fn render(
                                     browser_data: pipe.user_data.browser_menu.browser_data,
                                     ddnet_info: pipe.user_data.browser_menu.ddnet_info,
                                     icons: pipe.user_data.browser_menu.icons,
+                                    server_icons: pipe.user_data.browser_menu.server_icons,
 
                                     demos: pipe.user_data.browser_menu.demos,
                                     demo_info: pipe.user_data.browser_menu.demo_info,
+                                    demo_thumbnails: pipe.user_data.browser_menu.demo_thumbnails,
                                     server_info: pipe.user_data.browser_menu.server_info,
                                     render_options: pipe.user_data.browser_menu.render_options,
                                     main_menu: pipe.user_data.browser_menu.main_menu,
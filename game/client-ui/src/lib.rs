@@ -12,6 +12,7 @@ pub mod events;
 pub mod hud;
 pub mod ingame_menu;
 pub mod main_menu;
+pub mod notifications;
 pub mod scoreboard;
 pub mod sort;
 pub mod utils;
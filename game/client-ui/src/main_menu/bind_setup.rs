@@ -0,0 +1,253 @@
+use egui::{Align2, ComboBox, Key, PointerButton, Vec2};
+use game_config::config::{ConfigKeyboardLayout, ConfigPlayer};
+use ui_base::types::UiRenderPipe;
+
+use super::user_data::UserData;
+
+/// The actions the bind setup walks through.
+const BIND_SETUP_ACTIONS: [(&str, &str); 9] = [
+    ("+left", "Move left"),
+    ("+right", "Move right"),
+    ("+jump", "Jump"),
+    ("+fire", "Fire"),
+    ("+hook", "Hook"),
+    ("+show_chat", "Chat"),
+    ("+scoreboard", "Scoreboard"),
+    ("ingame_menu", "Menu"),
+    ("kill", "Kill"),
+];
+
+const BIND_SETUP_STEP: &str = "bind-setup-step";
+const BIND_SETUP_BINDS: &str = "bind-setup-binds";
+const BIND_SETUP_LISTENING: &str = "bind-setup-listening";
+
+/// Splits `bind <keys> <action>` into keys & action.
+fn split_bind(bind: &str) -> Option<(&str, &str)> {
+    bind.strip_prefix("bind ")?.split_once(' ')
+}
+
+/// The keys of a bind like they are printed on the keyboard.
+fn keys_label(keys: &str, layout: ConfigKeyboardLayout) -> String {
+    keys.split('+')
+        .map(|key| {
+            let label = match (layout, key) {
+                (ConfigKeyboardLayout::Azerty, "q") => "a",
+                (ConfigKeyboardLayout::Azerty, "a") => "q",
+                (ConfigKeyboardLayout::Azerty, "w") => "z",
+                (ConfigKeyboardLayout::Azerty, "z") => "w",
+                (ConfigKeyboardLayout::Azerty, "semicolon") => "m",
+                (ConfigKeyboardLayout::Azerty, "m") => ",",
+                (ConfigKeyboardLayout::Qwertz, "y") => "z",
+                (ConfigKeyboardLayout::Qwertz, "z") => "y",
+                (_, "left") => "left mouse button",
+                (_, "right") => "right mouse button",
+                (_, "middle") => "middle mouse button",
+                (_, key) => key.strip_prefix("digit").unwrap_or(key),
+            };
+            let mut chars = label.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().to_string() + &chars.as_str().replace('_', " "))
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// The name of the key inside a bind, see `bind_to_str`.
+fn key_bind_name(key: Key) -> Option<String> {
+    let name = match key {
+        Key::Space => "space",
+        Key::Enter => "enter",
+        Key::Tab => "tab",
+        Key::Escape => "escape",
+        Key::Backspace => "backspace",
+        Key::ArrowLeft => "arrow_left",
+        Key::ArrowRight => "arrow_right",
+        Key::ArrowUp => "arrow_up",
+        Key::ArrowDown => "arrow_down",
+        key => {
+            let name = key.name();
+            let is_letter = name.len() == 1 && name.chars().all(|c| c.is_ascii_alphabetic());
+            let is_function_key = name.starts_with('F') && name[1..].parse::<u8>().is_ok();
+            return if name.len() == 1 && name.chars().all(|c| c.is_ascii_digit()) {
+                Some(format!("digit{name}"))
+            } else if is_letter || is_function_key {
+                Some(name.to_lowercase())
+            } else {
+                None
+            };
+        }
+    };
+    Some(name.to_string())
+}
+
+fn pointer_bind_name(button: PointerButton) -> &'static str {
+    match button {
+        PointerButton::Primary => "left",
+        PointerButton::Secondary => "right",
+        PointerButton::Middle => "middle",
+        PointerButton::Extra1 => "back",
+        PointerButton::Extra2 => "forward",
+    }
+}
+
+/// The first key or mouse button that was pressed in this frame.
+fn pressed_bind_name(ui: &egui::Ui) -> Option<String> {
+    ui.input(|i| {
+        i.events.iter().find_map(|ev| match ev {
+            egui::Event::Key {
+                key,
+                physical_key,
+                pressed: true,
+                repeat: false,
+                ..
+            } => key_bind_name(physical_key.unwrap_or(*key)),
+            egui::Event::PointerButton {
+                button,
+                pressed: true,
+                ..
+            } => Some(pointer_bind_name(*button).to_string()),
+            _ => None,
+        })
+    })
+}
+
+/// Binds the action to the keys.
+/// Other keys of the action & other actions of the keys are unbound.
+fn set_bind(binds: &mut Vec<String>, keys: &str, action: &str) {
+    binds.retain(|bind| {
+        !split_bind(bind)
+            .is_some_and(|(bind_keys, bind_action)| bind_keys == keys || bind_action == action)
+    });
+    binds.push(format!("bind {keys} {action}"));
+}
+
+/// First run setup of the binds of the main player profile.
+///
+/// Shown as long as the profile has no binds. Starts with the default binds
+/// and walks through the most important actions, so the user can change their keys.
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let config = &mut *pipe.user_data.config;
+    let main_profile = config.game.profiles.main as usize;
+    if !config
+        .game
+        .players
+        .get(main_profile)
+        .is_some_and(|player| player.binds.is_empty())
+    {
+        return;
+    }
+
+    let mut binds: Vec<String> = config
+        .storage_opt(BIND_SETUP_BINDS)
+        .unwrap_or_else(|| ConfigPlayer::default_binds(config.game.cl.active_keyboard_layout()));
+    let mut step: usize = config.storage(BIND_SETUP_STEP);
+    let mut listening: bool = config.storage(BIND_SETUP_LISTENING);
+    let mut finish = false;
+
+    egui::Window::new("Bind setup")
+        .anchor(Align2::CENTER_CENTER, Vec2::default())
+        .collapsible(false)
+        .resizable(false)
+        .show(ui.ctx(), |ui| {
+            let cl = &mut config.game.cl;
+            match step
+                .checked_sub(1)
+                .and_then(|index| BIND_SETUP_ACTIONS.get(index))
+            {
+                None => {
+                    ui.label("Set up the keys for the most important actions.");
+                    ui.label(
+                        "The keyboard layout is detected while you type, \
+                        but you can also select it yourself:",
+                    );
+                    let prev_layout = cl.active_keyboard_layout();
+                    let detected = cl.detected_keyboard_layout;
+                    let layout_label = |layout: ConfigKeyboardLayout| match layout {
+                        ConfigKeyboardLayout::Auto => format!("Auto ({detected:?})"),
+                        layout => format!("{layout:?}"),
+                    };
+                    ComboBox::new("bind-setup-layout", "")
+                        .selected_text(layout_label(cl.keyboard_layout))
+                        .show_ui(ui, |ui| {
+                            for option in [
+                                ConfigKeyboardLayout::Auto,
+                                ConfigKeyboardLayout::Qwerty,
+                                ConfigKeyboardLayout::Azerty,
+                                ConfigKeyboardLayout::Qwertz,
+                            ] {
+                                ui.selectable_value(
+                                    &mut cl.keyboard_layout,
+                                    option,
+                                    layout_label(option),
+                                );
+                            }
+                        });
+                    // unchanged defaults follow the layout
+                    let layout = cl.active_keyboard_layout();
+                    if layout != prev_layout && binds == ConfigPlayer::default_binds(prev_layout) {
+                        binds = ConfigPlayer::default_binds(layout);
+                    }
+                }
+                Some((action, action_name)) => {
+                    ui.heading(format!(
+                        "{action_name} ({step}/{})",
+                        BIND_SETUP_ACTIONS.len()
+                    ));
+                    let keys = binds
+                        .iter()
+                        .filter_map(|bind| split_bind(bind))
+                        .filter(|(_, bind_action)| bind_action == action)
+                        .map(|(keys, _)| keys_label(keys, cl.active_keyboard_layout()))
+                        .collect::<Vec<_>>();
+                    if keys.is_empty() {
+                        ui.label("Not bound");
+                    } else {
+                        ui.label(keys.join(", "));
+                    }
+                    if listening {
+                        ui.label("Press a key or mouse button...");
+                        if let Some(keys) = pressed_bind_name(ui) {
+                            set_bind(&mut binds, &keys, action);
+                            listening = false;
+                        }
+                    } else if ui.button("Change").clicked() {
+                        listening = true;
+                    }
+                }
+            }
+
+            // while listening every click is a new bind
+            if listening {
+                return;
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Use defaults").clicked() {
+                    binds = ConfigPlayer::default_binds(cl.active_keyboard_layout());
+                    finish = true;
+                }
+                if step > 0 && ui.button("Back").clicked() {
+                    step -= 1;
+                }
+                if step < BIND_SETUP_ACTIONS.len() {
+                    if ui.button("Next").clicked() {
+                        step += 1;
+                    }
+                } else if ui.button("Finish").clicked() {
+                    finish = true;
+                }
+            });
+        });
+
+    if finish {
+        config.game.players[main_profile].binds = binds;
+        config.rem_storage(BIND_SETUP_BINDS);
+        config.rem_storage(BIND_SETUP_STEP);
+        config.rem_storage(BIND_SETUP_LISTENING);
+    } else {
+        config.set_storage(BIND_SETUP_BINDS, &binds);
+        config.set_storage(BIND_SETUP_STEP, &step);
+        config.set_storage(BIND_SETUP_LISTENING, &listening);
+    }
+}
@@ -1,12 +1,17 @@
 use egui::{epaint::RectShape, Color32, Layout, Sense, Shape};
 use egui_extras::{Column, Size, StripBuilder, TableBuilder};
 
-use ui_base::types::UiRenderPipe;
+use ui_base::types::{UiRenderPipe, UiState};
 
 use crate::main_menu::user_data::UserData;
 
 /// table header + server list
-pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, cur_page: &str) {
+pub fn render(
+    ui: &mut egui::Ui,
+    pipe: &mut UiRenderPipe<UserData>,
+    ui_state: &mut UiState,
+    cur_page: &str,
+) {
     StripBuilder::new(ui)
         .size(Size::exact(0.0))
         .size(Size::remainder())
@@ -43,7 +48,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, cur_page: &s
                                 super::header::render(&mut header, pipe.user_data.config);
                             })
                             .body(|body| {
-                                super::server_list::frame::render(body, pipe, cur_page);
+                                super::server_list::frame::render(body, pipe, ui_state, cur_page);
                             });
                     });
                 });
@@ -4,7 +4,11 @@ use shared_base::server_browser::ServerBrowserServer;
 use ui_base::utils::icon_font_text_for_text;
 
 /// single server list entry
-pub fn render(mut row: TableRow<'_, '_>, server: &ServerBrowserServer) -> bool {
+pub fn render(
+    mut row: TableRow<'_, '_>,
+    server: &ServerBrowserServer,
+    render_icon: impl FnOnce(&mut egui::Ui),
+) -> bool {
     let mut clicked = false;
     clicked |= row
         .col(|ui| {
@@ -19,6 +23,9 @@ pub fn render(mut row: TableRow<'_, '_>, server: &ServerBrowserServer) -> bool {
         .clicked();
     clicked |= row
         .col(|ui| {
+            if server.info.icon.is_some() {
+                render_icon(ui);
+            }
             clicked |= ui.label(&server.info.name).clicked();
         })
         .1
@@ -4,10 +4,12 @@ use egui_extras::TableBody;
 use game_config::config::Config;
 use shared_base::server_browser::{ServerBrowserInfo, ServerBrowserServer};
 
-use ui_base::types::UiRenderPipe;
+use ui_base::types::{UiRenderPipe, UiState};
 
 use crate::{
-    main_menu::{ddnet_info::DdnetInfo, favorite_player::FavoritePlayers, user_data::UserData},
+    main_menu::{
+        ddnet_info::DdnetInfo, favorite_player::FavoritePlayers, server_icon, user_data::UserData,
+    },
     sort::{SortDir, TableSort},
 };
 
@@ -68,7 +70,12 @@ fn servers_sorted(servers: &mut [&ServerBrowserServer], config: &Config) {
 }
 
 /// server list frame (scrollable)
-pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_page: &str) {
+pub fn render(
+    mut body: TableBody<'_>,
+    pipe: &mut UiRenderPipe<UserData>,
+    ui_state: &mut UiState,
+    cur_page: &str,
+) {
     let ddnet_info = &pipe.user_data.ddnet_info;
     let favorites = pipe
         .user_data
@@ -98,6 +105,7 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_pa
                 max_players: u32::MAX,
                 passworded: false,
                 cert_sha256_fingerprint: Default::default(),
+                icon: None,
             },
             address: "127.0.0.1:".to_string()
                 + &server_info
@@ -140,6 +148,8 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_pa
         .map(|a| a.to_string())
         .unwrap_or_default();
 
+    server_icon::update(pipe.user_data.server_icons, pipe.user_data.texture_handle);
+
     body.rows(
         30.0,
         if cur_page != "LAN" {
@@ -172,10 +182,38 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_pa
                 None
             };
 
+            // icons are downloaded for the servers that were shown at least once
+            if let (Some(icon), Ok(addr)) =
+                (&server.info.icon, server.address.parse::<SocketAddr>())
+            {
+                if !pipe.user_data.server_icons.contains_key(&icon.blake3) {
+                    let server_icon = server_icon::load_or_download(
+                        pipe.user_data.io,
+                        pipe.user_data.graphics_mt,
+                        addr,
+                        icon,
+                    );
+                    pipe.user_data.server_icons.insert(icon.blake3, server_icon);
+                }
+            }
+
             let is_selected = server.address == cur_addr;
             row.set_selected(is_selected);
-            let clicked = super::entry::render(row, server)
-                || (cur_page == "LAN" && lan_server.len() == 1)
+            let icon = server
+                .info
+                .icon
+                .and_then(|icon| pipe.user_data.server_icons.get(&icon.blake3));
+            let clicked = super::entry::render(row, server, |ui| {
+                server_icon::render(
+                    ui,
+                    ui_state,
+                    pipe.user_data.stream_handle,
+                    pipe.user_data.canvas_handle,
+                    pipe.user_data.full_rect,
+                    icon,
+                    egui::vec2(40.0, 20.0),
+                )
+            }) || (cur_page == "LAN" && lan_server.len() == 1)
                 || select_index
                     .and_then(|index| {
                         if cur_page != "LAN" {
@@ -192,6 +230,9 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_pa
                     if clicked {
                         pipe.user_data.config.set_storage("server-addr", &addr);
                     }
+                    pipe.user_data
+                        .config
+                        .set_storage("server-icon", &server.info.icon.map(|icon| icon.blake3));
                     pipe.user_data.config.set_storage(
                         "server-cert",
                         &if cur_page != "LAN" {
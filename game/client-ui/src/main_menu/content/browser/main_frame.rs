@@ -52,7 +52,7 @@ pub fn render(
                                         super::filter::render(ui, pipe, ui_state);
                                     });
                                     strip.cell(|ui| {
-                                        super::list::list::render(ui, pipe, cur_page);
+                                        super::list::list::render(ui, pipe, ui_state, cur_page);
                                     });
                                     strip.cell(|ui| {
                                         super::bottom_bar::render(ui, pipe);
@@ -9,6 +9,7 @@ use crate::{
     events::UiEvent,
     main_menu::{
         demo_list::{DemoList, DemoListEntry},
+        demo_thumbnail::{self, DemoThumbnail},
         user_data::UserData,
     },
     sort::{SortDir, TableSort},
@@ -88,12 +89,34 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>) {
         && body.ui_mut().ctx().memory(|m| m.focused().is_none());
 
     let selected_demo: String = pipe.user_data.config.storage::<String>("selected-demo");
+    let demo_path: String = pipe.user_data.config.storage("demo-path");
+    let demo_path: PathBuf = demo_path.into();
+
+    demo_thumbnail::update(
+        pipe.user_data.demo_thumbnails,
+        pipe.user_data.texture_handle,
+    );
 
     body.rows(30.0, demos_filtered.len(), |mut row| {
         let row_index = row.index();
 
         let demo = &demos_filtered[row_index];
 
+        // thumbnails are generated for the demos that were shown at least once
+        if let DemoListEntry::File { name, .. } = demo {
+            let file_path = demo_path.join(name);
+            if !pipe.user_data.demo_thumbnails.contains_key(&file_path) {
+                let task = demo_thumbnail::load_or_generate(
+                    pipe.user_data.io,
+                    pipe.user_data.graphics_mt,
+                    &file_path,
+                );
+                pipe.user_data
+                    .demo_thumbnails
+                    .insert(file_path, DemoThumbnail::Loading(task));
+            }
+        }
+
         let select_index = if select_prev {
             Some(row_index + 1)
         } else if select_next {
@@ -153,9 +176,11 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>) {
                 }
                 DemoListEntry::File { name, .. } => {
                     let new_path = cur_path.join(name);
-                    pipe.user_data
-                        .events
-                        .push(UiEvent::PlayDemo { name: new_path });
+                    let key = crate::main_menu::demo::demo_key(&response.ctx);
+                    pipe.user_data.events.push(UiEvent::PlayDemo {
+                        name: new_path,
+                        key,
+                    });
                 }
             }
         }
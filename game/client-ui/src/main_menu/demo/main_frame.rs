@@ -1,16 +1,22 @@
 use std::path::PathBuf;
 
 use base::duration_ext::DurationToRaceStr;
-use egui::{Align2, Color32, ComboBox, DragValue, Grid, Vec2};
+use egui::{Align2, Color32, ComboBox, DragValue, Grid, Sense, Vec2};
 use egui_extras::{Size, StripBuilder};
+use math::math::vector::vec2;
 use ui_base::{
-    types::UiRenderPipe,
+    types::{UiRenderPipe, UiState},
     utils::{add_horizontal_margins, icon_font_plus_text},
 };
 
 use crate::{
     events::UiEvent,
-    main_menu::{constants::MENU_DEMO_NAME, user_data::UserData},
+    main_menu::{
+        constants::MENU_DEMO_NAME,
+        demo_thumbnail::{DemoThumbnail, THUMBNAIL_HEIGHT, THUMBNAIL_WIDTH},
+        user_data::UserData,
+    },
+    utils::render_texture_for_ui,
 };
 
 fn record_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
@@ -47,8 +53,35 @@ fn record_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                 ui.add(DragValue::new(&mut config.cl.recorder.pixels_per_point));
                 ui.end_row();
 
-                ui.label("Crf (0 = lossless, 51 = worst):");
-                ui.add(DragValue::new(&mut config.cl.recorder.crf).range(0..=51));
+                ui.label("Bitrate (kbit/s, 0 = use crf):");
+                ui.add(DragValue::new(&mut config.cl.recorder.bitrate).speed(100));
+                ui.end_row();
+
+                ui.add_enabled(
+                    config.cl.recorder.bitrate == 0,
+                    egui::Label::new("Crf (0 = lossless, 51 = worst):"),
+                );
+                ui.add_enabled(
+                    config.cl.recorder.bitrate == 0,
+                    DragValue::new(&mut config.cl.recorder.crf).range(0..=51),
+                );
+                ui.end_row();
+
+                ui.label("Codec:");
+                ComboBox::new("codec_combobox", "")
+                    .selected_text(&config.cl.recorder.codec)
+                    .show_ui(ui, |ui| {
+                        if ui.button("H.264").clicked() {
+                            config.cl.recorder.codec = "h264".to_string();
+                        }
+                        if ui.button("HEVC (H.265)").clicked() {
+                            config.cl.recorder.codec = "hevc".to_string();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Output directory:");
+                ui.text_edit_singleline(&mut config.cl.recorder.path);
                 ui.end_row();
 
                 ui.label("Hardware acceleration (GPU):");
@@ -81,10 +114,12 @@ fn record_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                     let name: String = config.storage("selected-demo");
 
                     let demo_path = cur_path.join(name);
+                    let key = super::demo_key(ui.ctx());
 
                     pipe.user_data.events.push(UiEvent::EncodeDemoToVideo {
                         name: demo_path,
                         video_name,
+                        key,
                     });
                     config.path().query.remove("recorder-clicked");
                 }
@@ -92,9 +127,41 @@ fn record_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
         });
 }
 
+fn render_thumbnail(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    let cur_path: String = pipe.user_data.config.storage("demo-path");
+    let cur_path: PathBuf = cur_path.into();
+    let name: String = pipe.user_data.config.storage("selected-demo");
+    let Some(DemoThumbnail::Thumbnail(thumbnail)) =
+        pipe.user_data.demo_thumbnails.get(&cur_path.join(name))
+    else {
+        return;
+    };
+
+    let width = ui.available_width();
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(
+            width,
+            width * THUMBNAIL_HEIGHT as f32 / THUMBNAIL_WIDTH as f32,
+        ),
+        Sense::hover(),
+    );
+    render_texture_for_ui(
+        pipe.user_data.stream_handle,
+        pipe.user_data.canvas_handle,
+        thumbnail,
+        ui,
+        ui_state,
+        pipe.user_data.full_rect,
+        Some(ui.clip_rect()),
+        vec2::new(rect.center().x, rect.center().y),
+        vec2::new(rect.width(), rect.height()),
+    );
+}
+
 pub fn render(
     ui: &mut egui::Ui,
     pipe: &mut UiRenderPipe<UserData>,
+    ui_state: &mut UiState,
     cur_page: &str,
     main_frame_only: bool,
 ) {
@@ -137,8 +204,12 @@ pub fn render(
                                                             .storage("selected-demo");
 
                                                         let new_path = cur_path.join(name);
+                                                        let key = super::demo_key(ui.ctx());
                                                         pipe.user_data.events.push(
-                                                            UiEvent::PlayDemo { name: new_path },
+                                                            UiEvent::PlayDemo {
+                                                                name: new_path,
+                                                                key,
+                                                            },
                                                         );
                                                     }
                                                     if ui.button("record").clicked() {
@@ -204,6 +275,7 @@ pub fn render(
                                             });
                                         });
                                         strip.cell(|ui| {
+                                            render_thumbnail(ui, pipe, ui_state);
                                             if let Some((header, header_ext)) =
                                                 pipe.user_data.demo_info
                                             {
@@ -216,6 +288,20 @@ pub fn render(
                                                         ui.label("Length:");
                                                         ui.label(header.len.to_race_string());
                                                         ui.end_row();
+                                                        if header_ext.encryption.is_some() {
+                                                            ui.label("Key:");
+                                                            let mut key = super::demo_key(ui.ctx())
+                                                                .unwrap_or_default();
+                                                            ui.add(
+                                                                egui::TextEdit::singleline(
+                                                                    &mut key,
+                                                                )
+                                                                .password(true)
+                                                                .hint_text("Encrypted demo"),
+                                                            );
+                                                            super::set_demo_key(ui.ctx(), key);
+                                                            ui.end_row();
+                                                        }
                                                     },
                                                 );
                                             }
@@ -1,3 +1,18 @@
 pub mod list;
 pub mod main_frame;
 pub mod search;
+
+/// The key the user entered for the selected demo is kept in the
+/// temporary ui memory, never in the config, which might be saved.
+const DEMO_KEY_ID: &str = "demo-key";
+
+/// The key the user entered for the selected demo,
+/// only required for encrypted demos.
+pub fn demo_key(ctx: &egui::Context) -> Option<String> {
+    ctx.data(|d| d.get_temp::<String>(egui::Id::new(DEMO_KEY_ID)))
+        .filter(|key| !key.is_empty())
+}
+
+pub fn set_demo_key(ctx: &egui::Context, key: String) {
+    ctx.data_mut(|d| d.insert_temp(egui::Id::new(DEMO_KEY_ID), key));
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use base::hash::{fmt_hash, generate_hash_for};
+use base_io::{io::Io, io_batcher::IoBatcherTask};
+use base_io_traits::fs_traits::FileSystemInterface;
+use demo::{
+    utils::{read_chunks, read_demo},
+    DemoEvent, DemoEvents, DemoHeaderExt, DemoTail,
+};
+use game_interface::events::GameWorldEvent;
+use graphics::{
+    graphics_mt::GraphicsMultiThreaded,
+    handles::texture::texture::{GraphicsTextureHandle, TextureContainer},
+};
+use graphics_types::{
+    commands::{TexFlags, TexFormat},
+    types::{GraphicsBackendMemory, GraphicsMemoryAllocationType, ImageFormat},
+};
+use image::png::{load_png_image, save_png_image};
+use map::map::{groups::MapGroupPhysics, Map};
+use math::math::vector::vec2;
+use shared_base::mapdef_06::DdraceTileNum;
+
+pub const THUMBNAIL_WIDTH: u32 = 160;
+pub const THUMBNAIL_HEIGHT: u32 = 90;
+/// Size of a tile in pixels.
+const THUMBNAIL_TILE_SIZE: u32 = 2;
+
+/// Thumbnails are saved next to the demos, named after the hash of the demo file.
+pub const THUMBNAIL_SUFFIX: &str = ".thumbnail.png";
+
+#[derive(Debug)]
+pub enum DemoThumbnail {
+    Loading(IoBatcherTask<(GraphicsBackendMemory, usize, usize)>),
+    Thumbnail(TextureContainer),
+    /// The thumbnail could not be generated,
+    /// e.g. because the map of the demo is missing.
+    None,
+}
+
+/// The thumbnails by the path of the demo file.
+pub type DemoThumbnails = HashMap<PathBuf, DemoThumbnail>;
+
+/// The average position of all positioned game events (shots, hits, pickups etc.),
+/// which is usually where the action of the demo happens.
+fn action_pos(
+    demo_chunks: &[u8],
+    tail: &DemoTail,
+    header_ext: &DemoHeaderExt,
+) -> anyhow::Result<Option<vec2>> {
+    // the events of encrypted demos can't be read without a key
+    if header_ext.encryption.is_some() {
+        return Ok(None);
+    }
+
+    let mut sum = vec2::default();
+    let mut count = 0;
+    for &chunk_offset in tail.events_index.values() {
+        let chunks = read_chunks::<DemoEvents>(demo_chunks, chunk_offset as usize, None)?;
+        for ev in chunks
+            .values()
+            .flatten()
+            .filter_map(|ev| match ev {
+                DemoEvent::Game(evs) => Some(evs),
+                _ => None,
+            })
+            .flat_map(|evs| evs.worlds.values())
+            .flat_map(|world| world.events.values())
+        {
+            if let GameWorldEvent::Positioned(ev) = ev {
+                sum += ev.pos;
+                count += 1;
+            }
+        }
+    }
+    Ok((count > 0).then(|| sum / count as f32))
+}
+
+fn tile_color(index: u8) -> Option<[u8; 4]> {
+    match index {
+        i if i == DdraceTileNum::Solid as u8 => Some([170, 170, 180, 255]),
+        i if i == DdraceTileNum::NoHook as u8 => Some([90, 90, 100, 255]),
+        i if i == DdraceTileNum::Death as u8 => Some([190, 50, 50, 255]),
+        i if i == DdraceTileNum::Freeze as u8 || i == DdraceTileNum::DFreeze as u8 => {
+            Some([110, 150, 210, 255])
+        }
+        _ => None,
+    }
+}
+
+/// Draws the game layer around `center` (in tiles) as RGBA image,
+/// the whole map is centered if there is no position.
+fn render_physics(physics: &MapGroupPhysics, center: Option<vec2>) -> Vec<u8> {
+    const BACKGROUND: [u8; 4] = [30, 40, 60, 255];
+    const OUTSIDE: [u8; 4] = [15, 20, 30, 255];
+
+    let map_width = physics.attr.width.get() as i64;
+    let map_height = physics.attr.height.get() as i64;
+    let tiles = physics.get_game_layer_tiles();
+
+    let tiles_x = (THUMBNAIL_WIDTH / THUMBNAIL_TILE_SIZE) as i64;
+    let tiles_y = (THUMBNAIL_HEIGHT / THUMBNAIL_TILE_SIZE) as i64;
+    let center = center.unwrap_or_else(|| vec2::new(map_width as f32, map_height as f32) / 2.0);
+    // keep the map inside of the thumbnail if possible
+    let clamp_offset = |center: f32, tiles: i64, size: i64| {
+        let offset = center as i64 - tiles / 2;
+        if size <= tiles {
+            (size - tiles) / 2
+        } else {
+            offset.clamp(0, size - tiles)
+        }
+    };
+    let offset_x = clamp_offset(center.x, tiles_x, map_width);
+    let offset_y = clamp_offset(center.y, tiles_y, map_height);
+
+    let mut img = Vec::with_capacity((THUMBNAIL_WIDTH * THUMBNAIL_HEIGHT * 4) as usize);
+    for y in 0..THUMBNAIL_HEIGHT {
+        for x in 0..THUMBNAIL_WIDTH {
+            let tile_x = offset_x + (x / THUMBNAIL_TILE_SIZE) as i64;
+            let tile_y = offset_y + (y / THUMBNAIL_TILE_SIZE) as i64;
+            let color = if (0..map_width).contains(&tile_x) && (0..map_height).contains(&tile_y) {
+                tiles
+                    .get((tile_y * map_width + tile_x) as usize)
+                    .and_then(|tile| tile_color(tile.index))
+                    .unwrap_or(BACKGROUND)
+            } else {
+                OUTSIDE
+            };
+            img.extend(color);
+        }
+    }
+    img
+}
+
+/// Generates the thumbnail of a demo as png file.
+async fn generate(fs: &Arc<dyn FileSystemInterface>, demo: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let (_, header_ext, chunks, tail) = read_demo(demo)?;
+
+    let map_file = fs
+        .read_file(
+            format!(
+                "map/maps/{}_{}.twmap",
+                header_ext.map.as_str(),
+                fmt_hash(&header_ext.map_hash)
+            )
+            .as_ref(),
+        )
+        .await?;
+    let physics = Map::read_physics_group(&map_file)?;
+
+    let pos = action_pos(chunks, &tail, &header_ext)?;
+    save_png_image(
+        &render_physics(&physics, pos),
+        THUMBNAIL_WIDTH,
+        THUMBNAIL_HEIGHT,
+    )
+}
+
+/// Loads the cached thumbnail of a demo or generates it,
+/// if the demo has no thumbnail yet.
+pub fn load_or_generate(
+    io: &Io,
+    graphics_mt: &GraphicsMultiThreaded,
+    demo_path: &Path,
+) -> IoBatcherTask<(GraphicsBackendMemory, usize, usize)> {
+    let fs = io.fs.clone();
+    let graphics_mt = graphics_mt.clone();
+    let demo_path = demo_path.to_path_buf();
+    io.io_batcher.spawn(async move {
+        let demo = fs.read_file(&demo_path).await?;
+        let thumbnail_path = demo_path.with_file_name(format!(
+            "{}{}",
+            fmt_hash(&generate_hash_for(&demo)),
+            THUMBNAIL_SUFFIX
+        ));
+
+        let file = match fs.read_file(&thumbnail_path).await {
+            Ok(file) => file,
+            Err(_) => {
+                let file = generate(&fs, &demo).await?;
+                fs.write_file(&thumbnail_path, file.clone()).await?;
+                file
+            }
+        };
+
+        let mut img_mem = None;
+        load_png_image(&file, |width, height, _| {
+            img_mem = Some((
+                graphics_mt.mem_alloc(GraphicsMemoryAllocationType::Texture {
+                    width,
+                    height,
+                    depth: 1,
+                    is_3d_tex: false,
+                    flags: TexFlags::empty(),
+                }),
+                width,
+                height,
+            ));
+            img_mem.as_mut().unwrap().0.as_mut_slice()
+        })?;
+
+        Ok(img_mem.unwrap())
+    })
+}
+
+/// Uploads the thumbnails of all finished tasks.
+pub fn update(thumbnails: &mut DemoThumbnails, texture_handle: &GraphicsTextureHandle) {
+    for thumbnail in thumbnails.values_mut() {
+        if !matches!(thumbnail, DemoThumbnail::Loading(task) if task.is_finished()) {
+            continue;
+        }
+        let DemoThumbnail::Loading(task) = std::mem::replace(thumbnail, DemoThumbnail::None) else {
+            continue;
+        };
+        match task.get_storage().and_then(|(mem, width, height)| {
+            texture_handle.load_texture(
+                width,
+                height,
+                ImageFormat::Rgba,
+                mem,
+                TexFormat::Rgba,
+                TexFlags::empty(),
+                "demo-thumbnail",
+            )
+        }) {
+            Ok(texture) => {
+                *thumbnail = DemoThumbnail::Thumbnail(texture);
+            }
+            Err(err) => {
+                log::info!("no thumbnail for demo: {err}");
+            }
+        }
+    }
+}
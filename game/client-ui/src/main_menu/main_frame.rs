@@ -54,7 +54,13 @@ pub fn render(
                                 &cur_page,
                                 main_frame_only,
                             );
-                            super::demo::main_frame::render(ui, pipe, &cur_page, main_frame_only);
+                            super::demo::main_frame::render(
+                                ui,
+                                pipe,
+                                ui_state,
+                                &cur_page,
+                                main_frame_only,
+                            );
                             super::profile::main_frame::render(
                                 ui,
                                 pipe,
@@ -67,4 +73,8 @@ pub fn render(
             });
             strip.empty();
         });
+
+    if !main_frame_only {
+        super::bind_setup::render(ui, pipe);
+    }
 }
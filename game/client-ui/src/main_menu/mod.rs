@@ -1,9 +1,11 @@
+pub mod bind_setup;
 pub mod communities;
 pub mod constants;
 pub mod content;
 pub mod ddnet_info;
 pub mod demo;
 pub mod demo_list;
+pub mod demo_thumbnail;
 pub mod favorite_player;
 pub mod main_frame;
 pub mod monitors;
@@ -11,6 +13,7 @@ pub mod page;
 pub mod player_settings_ntfy;
 pub mod profile;
 pub mod profiles_interface;
+pub mod server_icon;
 pub mod settings;
 pub mod spatial_chat;
 pub mod theme_container;
@@ -12,6 +12,7 @@ use client_render_base::{
     render::{tee::RenderTee, toolkit::ToolkitRender},
 };
 use demo::{
+    signature::DEMO_SIGNATURE_EXTENSION,
     utils::{decomp, deser, deser_ex},
     DemoHeader, DemoHeaderExt,
 };
@@ -38,10 +39,12 @@ use super::{
     communities::CommunityIcons,
     ddnet_info::DdnetInfo,
     demo_list::{DemoList, DemoListEntry},
+    demo_thumbnail::{DemoThumbnails, THUMBNAIL_SUFFIX},
     main_frame,
     monitors::UiMonitors,
     player_settings_ntfy::PlayerSettingsSync,
     profiles_interface::ProfilesInterface,
+    server_icon::ServerIcons,
     spatial_chat::SpatialChat,
     theme_container::{ThemeContainer, THEME_CONTAINER_PATH},
     user_data::{ProfileTasks, RenderOptions, UserData},
@@ -75,9 +78,11 @@ pub struct MainMenuUi {
     pub(crate) browser_data: ServerBrowserData,
     pub(crate) ddnet_info: DdnetInfo,
     pub(crate) community_icons: CommunityIcons,
+    pub(crate) server_icons: ServerIcons,
 
     pub(crate) demos: DemoList,
     pub(crate) demo_info: Option<(DemoHeader, DemoHeaderExt)>,
+    pub(crate) demo_thumbnails: DemoThumbnails,
 
     menu_io: MainMenuIo,
     io: Io,
@@ -115,6 +120,9 @@ impl MainMenuUi {
                     .entries_in_dir(&path)
                     .await?
                     .into_iter()
+                    .filter(|(f, _)| {
+                        !f.ends_with(THUMBNAIL_SUFFIX) && !f.ends_with(DEMO_SIGNATURE_EXTENSION)
+                    })
                     .map(|(f, ty)| match ty {
                         FileSystemEntryTy::File { date } => DemoListEntry::File { name: f, date },
                         FileSystemEntryTy::Directory => DemoListEntry::Directory { name: f },
@@ -222,7 +230,9 @@ impl MainMenuUi {
             ddnet_info: DdnetInfo::default(),
             demos: DemoList::default(),
             demo_info: None,
+            demo_thumbnails: Default::default(),
             community_icons: Default::default(),
+            server_icons: Default::default(),
 
             menu_io: MainMenuIo {
                 io: io.clone(),
@@ -286,10 +296,12 @@ impl MainMenuUi {
             client_info: &self.client_info,
             ddnet_info: &self.ddnet_info,
             icons: &mut self.community_icons,
+            server_icons: &mut self.server_icons,
 
             browser_data: &mut self.browser_data,
             demos: &self.demos,
             demo_info: &self.demo_info,
+            demo_thumbnails: &mut self.demo_thumbnails,
 
             render_options: RenderOptions {
                 hide_buttons_icons: hide_buttons_right,
@@ -0,0 +1,199 @@
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf};
+
+use base::hash::{fmt_hash, Hash};
+use base_io::{io::Io, io_batcher::IoBatcherTask};
+use graphics::{
+    graphics_mt::GraphicsMultiThreaded,
+    handles::{
+        canvas::canvas::GraphicsCanvasHandle,
+        stream::stream::GraphicsStreamHandle,
+        texture::texture::{GraphicsTextureHandle, TextureContainer},
+    },
+};
+use graphics_types::{
+    commands::{TexFlags, TexFormat},
+    types::{GraphicsBackendMemory, GraphicsMemoryAllocationType, ImageFormat},
+};
+use math::math::vector::vec2;
+use shared_base::server_browser::{
+    load_server_icon, server_icon_path, ServerBrowserInfoIcon, SERVER_ICON_MAX_SIZE,
+};
+use ui_base::types::UiState;
+use url::Url;
+
+use crate::utils::render_texture_for_ui;
+
+#[derive(Debug)]
+pub enum ServerIcon {
+    Loading(IoBatcherTask<(GraphicsBackendMemory, usize, usize)>),
+    Icon {
+        texture: TextureContainer,
+        width: usize,
+        height: usize,
+    },
+    /// The icon could not be downloaded or is invalid.
+    None,
+}
+
+/// The server icons by the hash of the icon file.
+pub type ServerIcons = HashMap<Hash, ServerIcon>;
+
+/// Downloaded icons are cached by their hash,
+/// so servers that share an icon only download it once.
+fn cache_path(hash: &Hash) -> PathBuf {
+    format!("cache/{}", server_icon_path(hash)).into()
+}
+
+fn load_task(
+    io: &Io,
+    graphics_mt: &GraphicsMultiThreaded,
+    hash: Hash,
+    download_url: Option<Url>,
+) -> IoBatcherTask<(GraphicsBackendMemory, usize, usize)> {
+    let fs = io.fs.clone();
+    let http = io.http.clone();
+    let graphics_mt = graphics_mt.clone();
+    io.io_batcher.spawn(async move {
+        let path = cache_path(&hash);
+        let file = match fs.read_file(&path).await {
+            Ok(file) => file,
+            Err(err) => {
+                let Some(url) = download_url else {
+                    return Err(err.into());
+                };
+                // stops downloading as soon as the icon is too big
+                let file = http
+                    .download_binary_limited(url, &hash, SERVER_ICON_MAX_SIZE)
+                    .await
+                    .map_err(|err| anyhow::anyhow!("failed to download server icon: {err}"))?
+                    .to_vec();
+                if let Some(dir) = path.parent() {
+                    fs.create_dir(dir).await?;
+                }
+                fs.write_file(&path, file.clone()).await?;
+                file
+            }
+        };
+
+        let mut img_mem = None;
+        load_server_icon(&file, |width, height| {
+            img_mem = Some((
+                graphics_mt.mem_alloc(GraphicsMemoryAllocationType::Texture {
+                    width,
+                    height,
+                    depth: 1,
+                    is_3d_tex: false,
+                    flags: TexFlags::empty(),
+                }),
+                width,
+                height,
+            ));
+            img_mem.as_mut().unwrap().0.as_mut_slice()
+        })?;
+
+        Ok(img_mem.unwrap())
+    })
+}
+
+/// Loads the cached icon of a server or downloads it
+/// from the resource server of the server.
+pub fn load_or_download(
+    io: &Io,
+    graphics_mt: &GraphicsMultiThreaded,
+    addr: SocketAddr,
+    icon: &ServerBrowserInfoIcon,
+) -> ServerIcon {
+    // don't even try to download icons that are too big
+    if icon.size > SERVER_ICON_MAX_SIZE {
+        return ServerIcon::None;
+    }
+    let url = format!(
+        "http://{}:{}/{}",
+        addr.ip(),
+        icon.port,
+        server_icon_path(&icon.blake3)
+    );
+    match url.parse() {
+        Ok(url) => ServerIcon::Loading(load_task(io, graphics_mt, icon.blake3, Some(url))),
+        Err(err) => {
+            log::info!("invalid server icon url {url}: {err}");
+            ServerIcon::None
+        }
+    }
+}
+
+/// Only loads the icon from the cache,
+/// e.g. for the connect screen, where the browser already downloaded it.
+pub fn load_cached(io: &Io, graphics_mt: &GraphicsMultiThreaded, hash: &Hash) -> ServerIcon {
+    ServerIcon::Loading(load_task(io, graphics_mt, *hash, None))
+}
+
+/// Uploads the icons of all finished tasks.
+pub fn update(icons: &mut ServerIcons, texture_handle: &GraphicsTextureHandle) {
+    for (hash, icon) in icons.iter_mut() {
+        if !matches!(icon, ServerIcon::Loading(task) if task.is_finished()) {
+            continue;
+        }
+        let ServerIcon::Loading(task) = std::mem::replace(icon, ServerIcon::None) else {
+            continue;
+        };
+        match task.get_storage().and_then(|(mem, width, height)| {
+            texture_handle
+                .load_texture(
+                    width,
+                    height,
+                    ImageFormat::Rgba,
+                    mem,
+                    TexFormat::Rgba,
+                    TexFlags::empty(),
+                    "server-icon",
+                )
+                .map(|texture| (texture, width, height))
+        }) {
+            Ok((texture, width, height)) => {
+                *icon = ServerIcon::Icon {
+                    texture,
+                    width,
+                    height,
+                };
+            }
+            Err(err) => {
+                log::info!("no server icon {}: {err}", fmt_hash(hash));
+            }
+        }
+    }
+}
+
+/// Allocates `max_size` and renders the icon centered inside of it,
+/// keeping the aspect ratio of the icon.
+pub fn render(
+    ui: &mut egui::Ui,
+    ui_state: &mut UiState,
+    stream_handle: &GraphicsStreamHandle,
+    canvas_handle: &GraphicsCanvasHandle,
+    full_rect: egui::Rect,
+    icon: Option<&ServerIcon>,
+    max_size: egui::Vec2,
+) {
+    let (rect, _) = ui.allocate_exact_size(max_size, egui::Sense::hover());
+    let Some(ServerIcon::Icon {
+        texture,
+        width,
+        height,
+    }) = icon
+    else {
+        return;
+    };
+    let scale = (rect.width() / *width as f32).min(rect.height() / *height as f32);
+    render_texture_for_ui(
+        stream_handle,
+        canvas_handle,
+        texture,
+        ui,
+        ui_state,
+        full_rect,
+        Some(ui.clip_rect()),
+        vec2::new(rect.center().x, rect.center().y),
+        vec2::new(*width as f32 * scale, *height as f32 * scale),
+    );
+}
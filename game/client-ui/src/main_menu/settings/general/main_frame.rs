@@ -1,6 +1,89 @@
 use crate::main_menu::user_data::UserData;
+use egui::{ComboBox, DragValue, Grid, TextEdit};
+use game_config::config::ConfigPredictionMargin;
 use ui_base::types::{UiRenderPipe, UiState};
 
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    let cl = &mut pipe.user_data.config.game.cl;
+    let low_bandwidth = &mut cl.low_bandwidth;
+    Grid::new("general-network-settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Low bandwidth mode");
+            ui.checkbox(&mut low_bandwidth.active, "");
+            ui.end_row();
+            if low_bandwidth.active {
+                ui.label("Ticks per snapshot");
+                ui.add(DragValue::new(&mut low_bandwidth.ticks_per_snapshot).range(1..=25));
+                ui.end_row();
+                ui.label("High compression");
+                ui.checkbox(&mut low_bandwidth.high_compression, "");
+                ui.end_row();
+                ui.label("Reduced events");
+                ui.checkbox(&mut low_bandwidth.reduced_events, "");
+                ui.end_row();
+                ui.label("Max. resource download size (KiB)");
+                ui.add(
+                    DragValue::new(&mut low_bandwidth.resource_download_size_limit)
+                        .range(1..=65536),
+                );
+                ui.end_row();
+            }
+            ui.label("Prediction margin");
+            ComboBox::new("prediction-margin-select", "")
+                .selected_text(format!("{:?}", cl.prediction_margin))
+                .show_ui(ui, |ui| {
+                    for margin in [
+                        ConfigPredictionMargin::Aggressive,
+                        ConfigPredictionMargin::Balanced,
+                        ConfigPredictionMargin::Safe,
+                    ] {
+                        ui.selectable_value(
+                            &mut cl.prediction_margin,
+                            margin,
+                            format!("{:?}", margin),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "Aggressive lowers the latency, \
+                    safe causes fewer mispredictions on unstable connections.",
+                );
+            ui.end_row();
+            ui.label("Prediction margin offset (ms)");
+            ui.add(DragValue::new(&mut cl.prediction_margin_offset).range(-100..=200));
+            ui.end_row();
+        });
+    ui.separator();
+
+    Grid::new("general-render-settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Render module");
+            ui.horizontal(|ui| {
+                // only applied on click, every module in between would be loaded otherwise
+                let id = egui::Id::new("general-render-mod-edit");
+                let mut render_mod = ui.ctx().data_mut(|d| {
+                    d.get_temp_mut_or_insert_with(id, || cl.render_mod.clone())
+                        .clone()
+                });
+                ui.add(TextEdit::singleline(&mut render_mod).hint_text("built-in"))
+                    .on_hover_text(
+                        "A module in mods/render, without .wasm. \
+                        Can be switched while playing, empty uses the built-in renderer.",
+                    );
+                if ui
+                    .add_enabled(render_mod != cl.render_mod, egui::Button::new("Apply"))
+                    .clicked()
+                {
+                    cl.render_mod.clone_from(&render_mod);
+                }
+                ui.ctx().data_mut(|d| d.insert_temp(id, render_mod));
+            });
+            ui.end_row();
+        });
+    ui.separator();
+
     super::themes::theme_list(ui, pipe, ui_state)
 }
@@ -0,0 +1,156 @@
+use egui::{
+    Align2, Color32, DragValue, FontId, Grid, Layout, Pos2, Rect, Rounding, Sense, Stroke, Vec2,
+};
+use game_config::config::{ConfigHudElement, ConfigHudLayout};
+use ui_base::types::UiRenderPipe;
+
+use crate::main_menu::user_data::UserData;
+
+/// The default rect of a HUD element relative to the screen size,
+/// together with the point that stays fixed when the element is scaled.
+struct HudElementPreview {
+    name: &'static str,
+    rect: Rect,
+    anchor: Pos2,
+}
+
+fn element_previews(aspect: f32) -> [HudElementPreview; 4] {
+    // the health & ammo bar is rendered in a 300 units high canvas
+    let health_ammo = Rect::from_min_size(
+        Pos2::new(5.0 / (300.0 * aspect), 5.0 / 300.0),
+        Vec2::new(120.0 / (300.0 * aspect), 34.0 / 300.0),
+    );
+    let killfeed = Rect::from_min_size(Pos2::new(0.5, 0.02), Vec2::new(0.48, 0.3));
+    let chat = Rect::from_min_size(Pos2::new(0.02, 2.0 / 3.0), Vec2::new(0.4, 0.3));
+    let scoreboard = Rect::from_min_size(Pos2::new(0.1, 0.07), Vec2::new(0.8, 0.6));
+    [
+        HudElementPreview {
+            name: "Health & ammo",
+            rect: health_ammo,
+            anchor: health_ammo.left_top(),
+        },
+        HudElementPreview {
+            name: "Killfeed",
+            rect: killfeed,
+            anchor: killfeed.right_top(),
+        },
+        HudElementPreview {
+            name: "Chat",
+            rect: chat,
+            anchor: chat.left_bottom(),
+        },
+        HudElementPreview {
+            name: "Scoreboard",
+            rect: scoreboard,
+            anchor: scoreboard.center_top(),
+        },
+    ]
+}
+
+fn elements_mut(layout: &mut ConfigHudLayout) -> [&mut ConfigHudElement; 4] {
+    [
+        &mut layout.health_ammo,
+        &mut layout.killfeed,
+        &mut layout.chat,
+        &mut layout.scoreboard,
+    ]
+}
+
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
+        let layout = &mut pipe.user_data.config.game.cl.hud_layout;
+
+        ui.label("Drag the elements to move them, scroll over them to scale them.");
+
+        let screen_rect = ui.ctx().screen_rect();
+        let aspect = screen_rect.width() / screen_rect.height().max(1.0);
+        let width = ui
+            .available_width()
+            .min(ui.available_height() * 0.6 * aspect);
+        let (preview_rect, _) =
+            ui.allocate_exact_size(Vec2::new(width, width / aspect), Sense::hover());
+        ui.painter().rect_filled(
+            preview_rect,
+            Rounding::same(5.0),
+            Color32::from_black_alpha(100),
+        );
+
+        let to_preview = |p: Pos2| {
+            preview_rect.min + Vec2::new(p.x * preview_rect.width(), p.y * preview_rect.height())
+        };
+        let scroll_delta = ui.input(|i| i.smooth_scroll_delta.y);
+        for (preview, element) in element_previews(aspect)
+            .into_iter()
+            .zip(elements_mut(layout))
+        {
+            let scale = element.scale as f32;
+            let offset = Vec2::new(element.x as f32, element.y as f32);
+            let min = preview.anchor + (preview.rect.min - preview.anchor) * scale + offset;
+            let rect = Rect::from_min_max(
+                to_preview(min),
+                to_preview(min + preview.rect.size() * scale),
+            );
+
+            let res = ui.interact(rect, ui.id().with(preview.name), Sense::drag());
+            if res.dragged() {
+                let delta = res.drag_delta();
+                element.x = (element.x + (delta.x / preview_rect.width()) as f64).clamp(-1.0, 1.0);
+                element.y = (element.y + (delta.y / preview_rect.height()) as f64).clamp(-1.0, 1.0);
+            }
+            if res.hovered() && scroll_delta != 0.0 {
+                element.scale =
+                    (element.scale * (1.0 + scroll_delta as f64 * 0.002)).clamp(0.25, 4.0);
+            }
+
+            let active = res.hovered() || res.dragged();
+            ui.painter().rect(
+                rect,
+                Rounding::same(3.0),
+                Color32::from_rgba_unmultiplied(100, 150, 255, if active { 80 } else { 40 }),
+                Stroke::new(1.0, Color32::LIGHT_BLUE),
+            );
+            ui.painter().text(
+                rect.center(),
+                Align2::CENTER_CENTER,
+                preview.name,
+                FontId::proportional(12.0),
+                Color32::WHITE,
+            );
+        }
+
+        ui.add_space(10.0);
+        Grid::new("hud-layout-settings")
+            .num_columns(5)
+            .show(ui, |ui| {
+                let previews = element_previews(aspect);
+                for (preview, element) in previews.iter().zip(elements_mut(layout)) {
+                    ui.label(preview.name);
+                    ui.add(
+                        DragValue::new(&mut element.x)
+                            .range(-1.0..=1.0)
+                            .speed(0.005)
+                            .prefix("x: "),
+                    );
+                    ui.add(
+                        DragValue::new(&mut element.y)
+                            .range(-1.0..=1.0)
+                            .speed(0.005)
+                            .prefix("y: "),
+                    );
+                    ui.add(
+                        DragValue::new(&mut element.scale)
+                            .range(0.25..=4.0)
+                            .speed(0.01)
+                            .prefix("scale: "),
+                    );
+                    if ui.button("Reset").clicked() {
+                        *element = Default::default();
+                    }
+                    ui.end_row();
+                }
+            });
+        if ui.button("Reset all").clicked() {
+            *layout = Default::default();
+        }
+    });
+}
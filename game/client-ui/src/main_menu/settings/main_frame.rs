@@ -128,6 +128,7 @@ fn render_nav(
 
                 ui.add_space(10.0);
                 add_btn(ui, "Graphics", None);
+                add_btn(ui, "HUD", None);
 
                 let old_spacing_y =
                     std::mem::replace(&mut ui.style_mut().spacing.item_spacing.y, 0.0);
@@ -208,6 +209,9 @@ pub fn render(
                                                             ui, pipe,
                                                         );
                                                     }
+                                                    "HUD" => {
+                                                        super::hud::main_frame::render(ui, pipe);
+                                                    }
                                                     "Sound" => {
                                                         super::sound::main_frame::render(ui, pipe);
                                                     }
@@ -1,6 +1,7 @@
 pub mod constants;
 pub mod general;
 pub mod graphics;
+pub mod hud;
 pub mod list;
 pub mod main_frame;
 pub mod player;
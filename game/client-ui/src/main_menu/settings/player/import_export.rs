@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+
+use egui::Color32;
+use game_config::config::{ConfigPlayerProfilesExport, ConfigPlayerProfilesImportMode};
+use ui_base::types::UiRenderPipe;
+
+use crate::main_menu::user_data::UserData;
+
+const PATH_QUERY: &str = "profiles-file";
+const MSG_QUERY: &str = "profiles-file-msg";
+const ERR_QUERY: &str = "profiles-file-err";
+
+/// Applies the profiles of the file once it was read.
+fn import(
+    pipe: &mut UiRenderPipe<UserData>,
+    file: anyhow::Result<Vec<u8>>,
+    mode: ConfigPlayerProfilesImportMode,
+) -> anyhow::Result<()> {
+    let profiles = ConfigPlayerProfilesExport::from_json_string(&String::from_utf8(file?)?)?;
+    pipe.user_data
+        .config
+        .game
+        .import_player_profiles(profiles, mode);
+    pipe.user_data
+        .player_settings_sync
+        .set_player_info_changed();
+    pipe.user_data.player_settings_sync.set_controls_changed();
+    Ok(())
+}
+
+/// Export & import of all player profiles to/from a single file.
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let query = &mut pipe.user_data.config.engine.ui.path.query;
+    let file = query
+        .entry(PATH_QUERY.to_string())
+        .or_insert_with(|| "profiles.json".to_string());
+
+    let mut export = false;
+    let mut import_mode = None;
+    ui.horizontal(|ui| {
+        ui.label("Profiles file:");
+        ui.text_edit_singleline(file);
+        if ui
+            .button("Export")
+            .on_hover_text("Saves all profiles including skins & binds.")
+            .clicked()
+        {
+            export = true;
+        }
+        if ui
+            .button("Import")
+            .on_hover_text("Replaces all profiles with the ones of the file.")
+            .clicked()
+        {
+            import_mode = Some(ConfigPlayerProfilesImportMode::Replace);
+        }
+        if ui
+            .button("Merge")
+            .on_hover_text(
+                "Adds the profiles of the file, profiles with the same name are overwritten.",
+            )
+            .clicked()
+        {
+            import_mode = Some(ConfigPlayerProfilesImportMode::Merge);
+        }
+    });
+    let path = PathBuf::from(file.as_str());
+
+    let res = if export {
+        pipe.user_data
+            .config
+            .game
+            .export_player_profiles()
+            .to_json_string()
+            .map(|json| {
+                let fs = pipe.user_data.io.fs.clone();
+                let write_path = path.clone();
+                pipe.user_data
+                    .io
+                    .io_batcher
+                    .spawn_without_lifetime(async move {
+                        fs.write_file(&write_path, json.into_bytes()).await?;
+                        Ok(())
+                    });
+                format!("Exported the profiles to {}", path.display())
+            })
+            .map(Some)
+    } else if let Some(mode) = import_mode {
+        let fs = pipe.user_data.io.fs.clone();
+        let read_path = path.clone();
+        let task = pipe
+            .user_data
+            .io
+            .io_batcher
+            .spawn(async move { Ok(fs.read_file(&read_path).await?) });
+        pipe.user_data.profile_tasks.profiles_import = Some((task, mode, path.clone()));
+        Ok(Some(format!(
+            "Importing the profiles from {}...",
+            path.display()
+        )))
+    } else if pipe
+        .user_data
+        .profile_tasks
+        .profiles_import
+        .as_ref()
+        .is_some_and(|(task, _, _)| task.is_finished())
+    {
+        let (task, mode, path) = pipe.user_data.profile_tasks.profiles_import.take().unwrap();
+        import(pipe, task.get_storage(), mode)
+            .map(|_| Some(format!("Imported the profiles from {}", path.display())))
+    } else {
+        Ok(None)
+    };
+
+    let query = &mut pipe.user_data.config.engine.ui.path.query;
+    match res {
+        Ok(Some(msg)) => {
+            query.remove(ERR_QUERY);
+            query.insert(MSG_QUERY.to_string(), msg);
+        }
+        Ok(None) => {}
+        Err(err) => {
+            query.remove(MSG_QUERY);
+            query.insert(ERR_QUERY.to_string(), err.to_string());
+        }
+    }
+    if let Some(msg) = query.get(MSG_QUERY) {
+        ui.label(msg);
+    }
+    if let Some(err) = query.get(ERR_QUERY) {
+        ui.colored_label(Color32::RED, err);
+    }
+}
@@ -42,6 +42,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
         _ => {
             ui.label("Player profiles");
 
+            super::import_export::render(ui, pipe);
+
             let config = &mut pipe.user_data.config.game;
             let path = &mut pipe.user_data.config.engine.ui.path;
 
@@ -1,4 +1,5 @@
 pub mod assets;
+pub mod import_export;
 pub mod main_frame;
 pub mod misc;
 pub mod profile_selector;
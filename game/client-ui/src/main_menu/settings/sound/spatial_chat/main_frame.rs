@@ -187,6 +187,25 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                     ui.checkbox(&mut settings.from_non_account_users, "");
                     ui.end_row();
 
+                    ui.label("Mute all players")
+                        .on_hover_text("Priority speakers (e.g. casters) are still heard.");
+                    ui.checkbox(&mut settings.mute_all, "");
+                    ui.end_row();
+
+                    ui.label("Game sounds while someone speaks");
+                    ui.add(
+                        Slider::new(&mut settings.game_sound_ducking, 0.0..=1.0)
+                            .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)),
+                    );
+                    ui.end_row();
+
+                    ui.label("Other voices while a priority speaker speaks");
+                    ui.add(
+                        Slider::new(&mut settings.priority_speaker_ducking, 0.0..=1.0)
+                            .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)),
+                    );
+                    ui.end_row();
+
                     ui.label("Noise filter");
                     ui.end_row();
 
@@ -414,13 +433,22 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                     for player in entities.into_values() {
                         ui.label(icon_font_plus_text(
                             ui,
-                            if matches!(player.unique_id, PlayerUniqueId::Account(_)) {
+                            if player.priority {
+                                "\u{f130}"
+                            } else if matches!(player.unique_id, PlayerUniqueId::Account(_)) {
                                 "\u{f007}"
                             } else {
                                 ""
                             },
                             &player.name,
-                        ));
+                        ))
+                        // the id is needed to grant priority via rcon
+                        .on_hover_text(match player.unique_id {
+                            PlayerUniqueId::Account(account_id) => format!("acc_{}", account_id),
+                            PlayerUniqueId::CertFingerprint(hash) => {
+                                format!("cert_{}", fmt_hash(&hash))
+                            }
+                        });
                         ui.horizontal(|ui| {
                             fn conf_player<'a>(
                                 settings: &'a mut ConfigSpatialChat,
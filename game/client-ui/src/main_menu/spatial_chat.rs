@@ -41,6 +41,8 @@ pub enum EntitiesEvent {
 pub struct SpatialChatEntity {
     pub unique_id: PlayerUniqueId,
     pub name: String,
+    /// The server granted priority to this player (e.g. a caster).
+    pub priority: bool,
 }
 
 #[hiarc_safer_rc_refcell]
@@ -1,4 +1,7 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use base_io::{io::Io, io_batcher::IoBatcherTask};
 use client_containers::{
@@ -12,7 +15,7 @@ use client_render_base::{
     render::{tee::RenderTee, toolkit::ToolkitRender},
 };
 use demo::{DemoHeader, DemoHeaderExt};
-use game_config::config::Config;
+use game_config::config::{Config, ConfigPlayerProfilesImportMode};
 use graphics::{
     graphics_mt::GraphicsMultiThreaded,
     handles::{
@@ -30,11 +33,13 @@ use super::{
     communities::CommunityIcons,
     ddnet_info::DdnetInfo,
     demo_list::DemoList,
+    demo_thumbnail::DemoThumbnails,
     monitors::UiMonitors,
     player_settings_ntfy::PlayerSettingsSync,
     profiles_interface::{
         AccountInfo, AccountTokenError, CredentialAuthTokenError, ProfileData, ProfilesInterface,
     },
+    server_icon::ServerIcons,
     spatial_chat::SpatialChat,
     theme_container::ThemeContainer,
 };
@@ -200,6 +205,13 @@ pub enum ProfileState {
 pub struct ProfileTasks {
     pub state: ProfileState,
     pub user_interactions: Vec<IoBatcherTask<()>>,
+    /// The read of a player profiles file that is imported,
+    /// see [`crate::main_menu::settings::player::import_export`].
+    pub profiles_import: Option<(
+        IoBatcherTask<Vec<u8>>,
+        ConfigPlayerProfilesImportMode,
+        PathBuf,
+    )>,
 }
 
 impl ProfileTasks {
@@ -522,9 +534,11 @@ pub struct UserData<'a> {
 
     pub ddnet_info: &'a DdnetInfo,
     pub icons: &'a mut CommunityIcons,
+    pub server_icons: &'a mut ServerIcons,
 
     pub demos: &'a DemoList,
     pub demo_info: &'a Option<(DemoHeader, DemoHeaderExt)>,
+    pub demo_thumbnails: &'a mut DemoThumbnails,
 
     pub render_options: RenderOptions,
 
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use egui::{
+    Align, Align2, Color32, Frame, Layout, Pos2, Rect, RichText, ScrollArea, UiBuilder, Vec2,
+    Window,
+};
+
+use ui_base::types::UiRenderPipe;
+
+use super::{notifications::NotificationKind, user_data::UserData};
+
+/// How long a toast fades out before it disappears.
+const FADE_OUT: Duration = Duration::from_millis(500);
+const TOAST_WIDTH: f32 = 300.0;
+
+fn kind_color(kind: NotificationKind) -> Color32 {
+    match kind {
+        NotificationKind::Info => Color32::WHITE,
+        NotificationKind::Warning => Color32::from_rgb(255, 200, 0),
+        NotificationKind::Error => Color32::from_rgb(255, 80, 80),
+    }
+}
+
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let margin = 15.0;
+    let full_rect = ui.available_rect_before_wrap();
+    // the lower right quarter, newest toast at the bottom
+    let render_rect = Rect::from_min_max(
+        Pos2::new(full_rect.max.x - TOAST_WIDTH - margin, full_rect.center().y),
+        full_rect.max - Vec2::splat(margin),
+    );
+
+    ui.allocate_new_ui(
+        UiBuilder::new()
+            .max_rect(render_rect)
+            .layout(Layout::bottom_up(Align::Max)),
+        |ui| {
+            ui.set_clip_rect(render_rect);
+            for (until, notification) in pipe.user_data.toasts.iter().rev() {
+                let remaining = until.saturating_sub(pipe.cur_time);
+                let alpha = (remaining.as_secs_f32() / FADE_OUT.as_secs_f32()).clamp(0.0, 1.0);
+                Frame::none()
+                    .fill(Color32::from_black_alpha((180.0 * alpha) as u8))
+                    .rounding(5.0)
+                    .inner_margin(8.0)
+                    .show(ui, |ui| {
+                        ui.set_max_width(TOAST_WIDTH);
+                        ui.label(
+                            RichText::new(&notification.msg)
+                                .color(kind_color(notification.kind).gamma_multiply(alpha)),
+                        );
+                    });
+                ui.add_space(5.0);
+            }
+        },
+    );
+
+    if pipe.user_data.show_history {
+        Window::new("Notifications")
+            .anchor(Align2::RIGHT_TOP, Vec2::new(-margin, margin))
+            .collapsible(false)
+            .resizable(false)
+            .show(ui.ctx(), |ui| {
+                if pipe.user_data.history.is_empty() {
+                    ui.label("No notifications yet.");
+                }
+                ScrollArea::vertical()
+                    .max_height(300.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for notification in pipe.user_data.history.iter() {
+                            ui.colored_label(kind_color(notification.kind), &notification.msg);
+                        }
+                    });
+            });
+    }
+}
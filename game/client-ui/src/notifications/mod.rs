@@ -0,0 +1,3 @@
+pub mod main_frame;
+pub mod notifications;
+pub mod user_data;
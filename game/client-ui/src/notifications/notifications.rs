@@ -0,0 +1,50 @@
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub msg: String,
+}
+
+/// A lightweight handle to push notifications,
+/// which are shown as toasts above the game.
+///
+/// It can be cloned into any subsystem and also be sent to
+/// other threads, e.g. to notify once an io task is finished.
+#[derive(Debug, Clone, Default)]
+pub struct Notifications {
+    pending: Arc<Mutex<Vec<Notification>>>,
+}
+
+impl Notifications {
+    pub fn push(&self, kind: NotificationKind, msg: impl Into<String>) {
+        self.pending.lock().unwrap().push(Notification {
+            kind,
+            msg: msg.into(),
+        });
+    }
+
+    pub fn info(&self, msg: impl Into<String>) {
+        self.push(NotificationKind::Info, msg);
+    }
+
+    pub fn warning(&self, msg: impl Into<String>) {
+        self.push(NotificationKind::Warning, msg);
+    }
+
+    pub fn error(&self, msg: impl Into<String>) {
+        self.push(NotificationKind::Error, msg);
+    }
+
+    /// Takes all notifications that were pushed since the last call.
+    pub fn take(&self) -> Vec<Notification> {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+}
@@ -0,0 +1,12 @@
+use std::{collections::VecDeque, time::Duration};
+
+use super::notifications::Notification;
+
+pub struct UserData<'a> {
+    /// The toasts that are currently shown, oldest first,
+    /// with the time they will disappear.
+    pub toasts: &'a VecDeque<(Duration, Notification)>,
+    /// All recent notifications, oldest first.
+    pub history: &'a VecDeque<Notification>,
+    pub show_history: bool,
+}
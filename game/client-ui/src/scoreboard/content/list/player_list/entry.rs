@@ -170,7 +170,12 @@ pub fn render<'a>(
                                     ui.label(
                                         RichText::new(stats.ping.as_millis().to_string())
                                             .size(font_size),
-                                    );
+                                    )
+                                    .on_hover_text(format!(
+                                        "Packet loss: {:.1}%\nJitter: {}ms",
+                                        stats.packet_loss * 100.0,
+                                        stats.jitter.as_millis()
+                                    ));
                                 }
                                 ScoreboardConnectionType::Bot => {
                                     ui.label(RichText::new("BOT").size(font_size));
@@ -2,6 +2,8 @@ use egui::{Pos2, Rect, Vec2};
 
 use ui_base::types::{UiRenderPipe, UiState};
 
+use crate::utils::apply_hud_layout;
+
 use super::user_data::UserData;
 
 /// big square, rounded edges
@@ -67,4 +69,12 @@ pub fn render(
             available_rect,
         )
     });
+
+    // the scoreboard is anchored at the top center
+    apply_hud_layout(
+        ui,
+        &pipe.user_data.layout,
+        Pos2::new(available_rect.center().x, available_rect.top() + 50.0),
+        &available_rect,
+    );
 }
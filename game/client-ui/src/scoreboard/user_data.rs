@@ -1,5 +1,6 @@
 use client_containers::{flags::FlagsContainer, skins::SkinContainer};
 use client_render_base::render::tee::RenderTee;
+use client_types::hud_layout::HudElementLayout;
 use game_interface::types::{
     game::GameEntityId,
     render::{character::CharacterInfo, scoreboard::Scoreboard},
@@ -17,4 +18,5 @@ pub struct UserData<'a> {
     pub skin_container: &'a mut SkinContainer,
     pub render_tee: &'a RenderTee,
     pub flags_container: &'a mut FlagsContainer,
+    pub layout: HudElementLayout,
 }
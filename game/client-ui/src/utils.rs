@@ -20,7 +20,8 @@ use client_render_base::{
         toolkit::ToolkitRender,
     },
 };
-use egui::Rect;
+use client_types::hud_layout::HudElementLayout;
+use egui::{emath::TSTransform, Pos2, Rect};
 use game_interface::types::{
     character_info::NetworkSkinInfo, emoticons::EmoticonType, render::character::TeeEye,
     resource_key::ResourceKey, weapons::WeaponType,
@@ -704,3 +705,19 @@ pub fn render_texture_for_ui(
 
     ui_state.add_custom_paint(ui, render_rect, Rc::new(cb));
 }
+
+/// Moves & scales everything that was painted on the layer of the `ui` so far
+/// by the user's HUD layout. The `anchor` keeps its position when scaling.
+pub fn apply_hud_layout(ui: &egui::Ui, layout: &HudElementLayout, anchor: Pos2, full_rect: &Rect) {
+    if *layout == HudElementLayout::default() {
+        return;
+    }
+    let offset = egui::vec2(layout.x * full_rect.width(), layout.y * full_rect.height());
+    ui.ctx().transform_layer_shapes(
+        ui.layer_id(),
+        TSTransform::new(
+            anchor.to_vec2() * (1.0 - layout.scale) + offset,
+            layout.scale,
+        ),
+    );
+}
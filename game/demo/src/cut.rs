@@ -0,0 +1,130 @@
+use std::{collections::BTreeMap, ops::RangeInclusive, time::Duration};
+
+use anyhow::anyhow;
+use base_io::io::Io;
+
+use crate::{
+    recorder::{DemoRecorder, DemoRecorderCreateProps},
+    utils::{read_chunks_in_range, read_demo},
+    DemoEvent, DemoEvents, DemoSnapshot,
+};
+
+/// What is removed from the cut demo.
+///
+/// Replacing the names of the players requires the game mod to
+/// rewrite the snapshots, so it's only offered by the demo viewer.
+#[derive(Debug, Default, Clone)]
+pub struct DemoCutFilters {
+    pub remove_chat: bool,
+}
+
+/// Cuts the time `range` (relative to the start of the demo) out of the demo file
+/// and saves it as a new demo with the given name in the demos directory.
+///
+/// Encrypted demos need the `passphrase`, the cut demo is encrypted with it too.
+pub fn cut_demo(
+    io: &Io,
+    file: &[u8],
+    passphrase: Option<String>,
+    name: String,
+    range: RangeInclusive<Duration>,
+    filters: &DemoCutFilters,
+) -> anyhow::Result<()> {
+    let (_, header_ext, chunks, tail) = read_demo(file)?;
+    let key = header_ext
+        .encryption
+        .as_ref()
+        .map(|encryption| {
+            encryption.key(
+                passphrase
+                    .as_deref()
+                    .ok_or_else(|| anyhow!("this demo is encrypted, a key is required."))?,
+            )
+        })
+        .transpose()?;
+
+    anyhow::ensure!(
+        range.start() <= range.end(),
+        "the start of the cut must be before its end."
+    );
+
+    let first_tick = tail
+        .snapshots_index
+        .keys()
+        .chain(tail.events_index.keys())
+        .min()
+        .copied()
+        .unwrap_or_default();
+    let time_to_tick = |time: &Duration| {
+        first_tick
+            + (time.as_nanos()
+                / (Duration::from_secs(1).as_nanos() / header_ext.ticks_per_second.get() as u128))
+                as u64
+    };
+    let ticks = time_to_tick(range.start())..=time_to_tick(range.end());
+
+    let snapshots: BTreeMap<u64, DemoSnapshot> =
+        read_chunks_in_range(chunks, &tail.snapshots_index, key.as_ref(), ticks.clone())?;
+    anyhow::ensure!(
+        !snapshots.is_empty(),
+        "the demo has no snapshots in this time range."
+    );
+    let events: BTreeMap<u64, DemoEvents> =
+        read_chunks_in_range(chunks, &tail.events_index, key.as_ref(), ticks.clone())?;
+    let povs = tail
+        .povs
+        .iter()
+        .map(|pov| {
+            read_chunks_in_range::<DemoSnapshot>(
+                chunks,
+                &pov.snapshots_index,
+                key.as_ref(),
+                ticks.clone(),
+            )
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let mut recorder = DemoRecorder::new(
+        DemoRecorderCreateProps {
+            map: header_ext.map,
+            map_hash: header_ext.map_hash,
+            game_options: header_ext.game_options,
+            required_resources: header_ext.required_resources,
+            physics_module: header_ext.physics_mod,
+            render_module: header_ext.render_mod,
+            physics_group_name: header_ext.physics_group_name,
+            encryption_passphrase: passphrase,
+            split: Default::default(),
+            record_inputs: false,
+            signing_key: None,
+            on_saved: None,
+            io: io.clone(),
+        },
+        header_ext.ticks_per_second,
+        Some(name),
+    )?;
+    let pov_indices: Vec<usize> = tail
+        .povs
+        .iter()
+        .map(|pov| recorder.add_pov(pov.name.clone()))
+        .collect();
+
+    for (monotonic_tick, snapshot) in snapshots {
+        recorder.add_snapshot(monotonic_tick, snapshot);
+    }
+    for (pov, snapshots) in pov_indices.into_iter().zip(povs) {
+        for (monotonic_tick, snapshot) in snapshots {
+            recorder.add_pov_snapshot(pov, monotonic_tick, snapshot);
+        }
+    }
+    for (monotonic_tick, events) in events {
+        for event in events {
+            if filters.remove_chat && matches!(event, DemoEvent::Chat(_)) {
+                continue;
+            }
+            recorder.add_event(monotonic_tick, event);
+        }
+    }
+
+    recorder.finish()
+}
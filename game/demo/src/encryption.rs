@@ -0,0 +1,145 @@
+use anyhow::anyhow;
+use argon2::{Algorithm, Argon2, Params, Version};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN},
+    digest,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+
+/// Parameters of an encrypted demo, that are required to derive
+/// the key from a passphrase.
+///
+/// Only the chunks of a demo are encrypted, the headers & the tail
+/// stay readable, so a demo can be listed & inspected without the key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemoEncryption {
+    /// random salt for the key derivation (argon2id)
+    pub salt: [u8; 16],
+    /// argon2 memory cost in KiB
+    pub m_cost: u32,
+    /// argon2 iterations
+    pub t_cost: u32,
+    /// argon2 parallelism
+    pub p_cost: u32,
+    /// SHA256 of the derived key, to detect a wrong passphrase
+    /// before trying to decrypt any chunk.
+    pub key_check: [u8; 32],
+}
+
+impl DemoEncryption {
+    fn derive_key(&self, passphrase: &str) -> anyhow::Result<[u8; 32]> {
+        let argon2 = Argon2::new(
+            Algorithm::Argon2id,
+            Version::V0x13,
+            Params::new(self.m_cost, self.t_cost, self.p_cost, Some(32))
+                .map_err(|err| anyhow!(err))?,
+        );
+        let mut key = [0; 32];
+        argon2
+            .hash_password_into(passphrase.as_bytes(), &self.salt, &mut key)
+            .map_err(|err| anyhow!(err))?;
+        Ok(key)
+    }
+
+    fn key_check(key: &[u8; 32]) -> anyhow::Result<[u8; 32]> {
+        Ok(digest::digest(&digest::SHA256, key).as_ref().try_into()?)
+    }
+
+    /// Creates new encryption parameters with a random salt
+    /// and derives the key for the given passphrase.
+    pub fn new(passphrase: &str) -> anyhow::Result<(Self, DemoKey)> {
+        let mut salt = [0; 16];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| anyhow!("failed to generate a random salt"))?;
+        let mut res = Self {
+            salt,
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+            key_check: Default::default(),
+        };
+        let key = res.derive_key(passphrase)?;
+        res.key_check = Self::key_check(&key)?;
+        Ok((res, DemoKey { key }))
+    }
+
+    /// Derives the key from the passphrase.
+    ///
+    /// # Errors
+    /// Returns an error if the passphrase does not match the one
+    /// that was used to record the demo.
+    pub fn key(&self, passphrase: &str) -> anyhow::Result<DemoKey> {
+        let key = self.derive_key(passphrase)?;
+        anyhow::ensure!(
+            Self::key_check(&key)? == self.key_check,
+            "the key for this demo is wrong."
+        );
+        Ok(DemoKey { key })
+    }
+}
+
+/// The symmetric key that encrypts the demo chunks (ChaCha20-Poly1305).
+#[derive(Clone)]
+pub struct DemoKey {
+    key: [u8; 32],
+}
+
+impl std::fmt::Debug for DemoKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DemoKey").finish_non_exhaustive()
+    }
+}
+
+impl DemoKey {
+    /// Size the encrypted data grows by.
+    pub const TAG_LEN: usize = 16;
+
+    fn aead_key(&self) -> anyhow::Result<LessSafeKey> {
+        Ok(LessSafeKey::new(
+            UnboundKey::new(&CHACHA20_POLY1305, &self.key)
+                .map_err(|_| anyhow!("invalid demo key"))?,
+        ))
+    }
+
+    /// The file offset of a chunk is unique inside a demo
+    /// and every demo has its own key (random salt).
+    fn nonce(chunk_offset: u64) -> Nonce {
+        let mut nonce = [0; NONCE_LEN];
+        nonce[0..std::mem::size_of::<u64>()].copy_from_slice(&chunk_offset.to_le_bytes());
+        Nonce::assume_unique_for_key(nonce)
+    }
+
+    /// Encrypts the data of the chunk at the given offset (relative to the
+    /// beginning of all chunks) and appends the authentication tag.
+    pub fn encrypt_chunk(
+        &self,
+        chunk_offset: u64,
+        data: &mut Vec<u8>,
+        data_start: usize,
+    ) -> anyhow::Result<()> {
+        let tag = self
+            .aead_key()?
+            .seal_in_place_separate_tag(
+                Self::nonce(chunk_offset),
+                Aad::empty(),
+                &mut data[data_start..],
+            )
+            .map_err(|_| anyhow!("failed to encrypt demo chunk"))?;
+        data.extend_from_slice(tag.as_ref());
+        Ok(())
+    }
+
+    /// Decrypts the data of the chunk at the given offset in place,
+    /// the returned slice is the decrypted data without the tag.
+    pub fn decrypt_chunk<'a>(
+        &self,
+        chunk_offset: u64,
+        data: &'a mut [u8],
+    ) -> anyhow::Result<&'a mut [u8]> {
+        self.aead_key()?
+            .open_in_place(Self::nonce(chunk_offset), Aad::empty(), data)
+            .map_err(|_| anyhow!("failed to decrypt demo chunk, wrong key or corrupted demo"))
+    }
+}
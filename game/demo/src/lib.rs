@@ -1,8 +1,17 @@
 #![allow(clippy::too_many_arguments)]
 
+#[cfg(feature = "recorder")]
+pub mod cut;
+#[cfg(feature = "recorder")]
+pub mod merge;
 #[cfg(feature = "recorder")]
 pub mod recorder;
+#[cfg(feature = "recorder")]
+pub mod replay;
 
+pub mod encryption;
+pub mod redact;
+pub mod signature;
 pub mod utils;
 
 use std::{
@@ -10,20 +19,34 @@ use std::{
     time::Duration,
 };
 
-use base::{hash::Hash, reduced_ascii_str::ReducedAsciiString};
+use base::{
+    hash::{generate_hash_for, Hash},
+    reduced_ascii_str::ReducedAsciiString,
+};
+use encryption::DemoEncryption;
 use game_interface::{
-    events::GameEvents,
+    events::{GameEvents, GameWorldAction, GameWorldEvent, GameWorldGlobalEvent},
     interface::GameStateCreateOptions,
-    types::{game::NonZeroGameTickType, network_string::NetworkReducedAsciiString},
+    types::{
+        game::{GameEntityId, NonZeroGameTickType},
+        network_string::NetworkReducedAsciiString,
+        render::scoreboard::Scoreboard,
+    },
+    votes::{VoteState, Voted},
 };
 use serde::{Deserialize, Serialize};
-use shared_base::network::{messages::GameModification, types::chat::NetChatMsg};
+use shared_base::network::{
+    messages::{GameModification, PlayerInputChainable},
+    types::chat::NetChatMsg,
+};
 
 pub type DemoGameModification = GameModification;
 
 /// The demo header, of const size.
 /// A broken demo can be detected if [`DemoHeader::len`] or
-/// [`DemoHeader::size_chunks`] is zero.
+/// [`DemoHeader::size_chunks`] is zero, e.g. if the recording crashed.
+/// The tail of such a demo is rebuilt from the [`DemoChunksInfo`]s
+/// of the chunks, see [`utils::read_demo`].
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct DemoHeader {
     /// Length of the full demo
@@ -43,6 +66,182 @@ pub struct DemoTail {
     /// the key is the monotonic tick, while the value is the
     /// file offset relative to the beginning of the chunk.
     pub events_index: BTreeMap<u64, u64>,
+    /// Has the same keys as [`DemoTail::events_index`],
+    /// see [`DemoEventsChunkInfo`].
+    pub events_chunks: BTreeMap<u64, DemoEventsChunkInfo>,
+    /// Additional point of views, e.g. of merged demos.
+    /// The main point of view uses [`DemoTail::snapshots_index`].
+    pub povs: Vec<DemoPov>,
+    /// the key is the monotonic tick, while the value is the
+    /// file offset relative to the beginning of the chunk.
+    ///
+    /// Empty if the demo was recorded without inputs, see [`DemoInputs`].
+    pub inputs_index: BTreeMap<u64, u64>,
+}
+
+impl DemoTail {
+    /// The first monotonic ticks & file offsets of the events chunks
+    /// that end after `tick` and might contain events of `kind`, in order.
+    ///
+    /// Chunks without such events are skipped without decompressing them.
+    pub fn events_chunks_after<'a>(
+        &'a self,
+        tick: u64,
+        kind: &DemoEventKind<'_>,
+    ) -> impl Iterator<Item = (u64, u64)> + 'a {
+        let kind_bits = kind.bits();
+        self.events_chunks
+            .iter()
+            .filter(move |(_, info)| info.last_tick > tick && info.kinds.may_contain(kind_bits))
+            .filter_map(|(&first_tick, _)| {
+                self.events_index
+                    .get(&first_tick)
+                    .map(|&offset| (first_tick, offset))
+            })
+    }
+
+    /// Same as [`DemoTail::events_chunks_after`], but for the chunks
+    /// that start before `tick`, in reversed order.
+    pub fn events_chunks_before<'a>(
+        &'a self,
+        tick: u64,
+        kind: &DemoEventKind<'_>,
+    ) -> impl Iterator<Item = (u64, u64)> + 'a {
+        let kind_bits = kind.bits();
+        self.events_chunks
+            .range(..tick)
+            .rev()
+            .filter(move |(_, info)| info.kinds.may_contain(kind_bits))
+            .filter_map(|(&first_tick, _)| {
+                self.events_index
+                    .get(&first_tick)
+                    .map(|&offset| (first_tick, offset))
+            })
+    }
+}
+
+/// Kinds of events that can be searched for in a demo,
+/// e.g. to jump to the next kill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DemoEventKind<'a> {
+    Kill,
+    /// A race or team race finish.
+    Finish,
+    Chat,
+    Vote,
+    /// A custom action of a game mod, by its name.
+    CustomAction(&'a str),
+}
+
+impl<'a> DemoEventKind<'a> {
+    /// The kinds of a single event, a game event can contain multiple.
+    pub fn of(ev: &'a DemoEvent) -> Vec<Self> {
+        match ev {
+            DemoEvent::Game(evs) => evs
+                .worlds
+                .values()
+                .flat_map(|world| world.events.values())
+                .filter_map(|ev| match ev {
+                    GameWorldEvent::Global(GameWorldGlobalEvent::Action(action)) => {
+                        Some(match action {
+                            GameWorldAction::Kill { .. } => Self::Kill,
+                            GameWorldAction::RaceFinish { .. }
+                            | GameWorldAction::RaceTeamFinish { .. } => Self::Finish,
+                            GameWorldAction::Custom(name) => Self::CustomAction(name.as_str()),
+                        })
+                    }
+                    _ => None,
+                })
+                .collect(),
+            DemoEvent::Chat(_) => vec![Self::Chat],
+            DemoEvent::Vote(_) => vec![Self::Vote],
+            DemoEvent::Scoreboard(_) => Vec::new(),
+        }
+    }
+
+    /// The bits of this kind inside of [`DemoEventKinds`].
+    ///
+    /// They are derived from a hash of the kind,
+    /// so they must never change for existing kinds.
+    fn bits(&self) -> u64 {
+        let hash = match self {
+            Self::Kill => generate_hash_for(b"kill"),
+            Self::Finish => generate_hash_for(b"finish"),
+            Self::Chat => generate_hash_for(b"chat"),
+            Self::Vote => generate_hash_for(b"vote"),
+            Self::CustomAction(name) => generate_hash_for(format!("custom:{name}").as_bytes()),
+        };
+        hash.iter()
+            .take(DemoEventKinds::HASH_COUNT)
+            .fold(0, |bits, &byte| bits | (1 << (byte % u64::BITS as u8)))
+    }
+}
+
+/// A bloom filter of [`DemoEventKind`]s:
+/// If it does not contain a kind, there is certainly no such event,
+/// else there might be one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DemoEventKinds(u64);
+
+impl DemoEventKinds {
+    /// how many bits are set per kind
+    const HASH_COUNT: usize = 3;
+
+    /// Might contain every kind, used if the events are unknown.
+    pub fn all() -> Self {
+        Self(u64::MAX)
+    }
+
+    pub fn insert(&mut self, kind: &DemoEventKind<'_>) {
+        self.0 |= kind.bits();
+    }
+
+    fn may_contain(&self, kind_bits: u64) -> bool {
+        self.0 & kind_bits == kind_bits
+    }
+
+    pub fn may_contain_kind(&self, kind: &DemoEventKind<'_>) -> bool {
+        self.may_contain(kind.bits())
+    }
+}
+
+/// A summary of a chunk of events,
+/// so searching for events does not need to decompress every chunk.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DemoEventsChunkInfo {
+    /// The last monotonic tick of the chunk,
+    /// the first is the key inside of [`DemoTail::events_chunks`].
+    pub last_tick: u64,
+    pub kinds: DemoEventKinds,
+}
+
+impl DemoEventsChunkInfo {
+    pub fn new(events: &BTreeMap<u64, DemoEvents>) -> Self {
+        let mut kinds = DemoEventKinds::default();
+        events
+            .values()
+            .flatten()
+            .flat_map(DemoEventKind::of)
+            .for_each(|kind| kinds.insert(&kind));
+        Self {
+            last_tick: events
+                .last_key_value()
+                .map(|(&tick, _)| tick)
+                .unwrap_or_default(),
+            kinds,
+        }
+    }
+}
+
+/// An additional point of view (recorded snapshots of another player)
+/// of the same match.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DemoPov {
+    /// optional name of the point of view, e.g. the demo it was merged from
+    pub name: String,
+    /// the key is the monotonic tick, while the value is the
+    /// file offset relative to the beginning of the chunk.
+    pub snapshots_index: BTreeMap<u64, u64>,
 }
 
 /// A more flexible header, that can contain dynamic sized elements.
@@ -62,6 +261,31 @@ pub struct DemoHeaderExt {
     pub ticks_per_second: NonZeroGameTickType,
     pub game_options: GameStateCreateOptions,
     pub physics_group_name: NetworkReducedAsciiString<24>,
+    /// If `Some`, the chunks of the demo are encrypted
+    /// and can only be played back with the right key.
+    pub encryption: Option<DemoEncryption>,
+}
+
+/// What a group of chunks contains, see [`DemoChunksInfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DemoChunksKind {
+    Snapshots,
+    /// Snapshots of the point of view [`DemoChunksInfo::pov`].
+    PovSnapshots,
+    Events,
+    Inputs,
+}
+
+/// Written unencrypted in front of every group of chunks (of const size),
+/// so that the tail of a demo, that was never finished,
+/// can be rebuilt without decrypting the chunks.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DemoChunksInfo {
+    pub kind: DemoChunksKind,
+    /// Only used by [`DemoChunksKind::PovSnapshots`].
+    pub pov: u64,
+    pub first_tick: u64,
+    pub last_tick: u64,
 }
 
 /// When a chunk of snapshots or events ([`DemoRecorderChunk`]) is serialized, this header
@@ -70,6 +294,9 @@ pub struct DemoHeaderExt {
 pub struct ChunkHeader {
     pub monotonic_tick: u64,
     pub size: u64,
+    /// CRC-32 of the `size` bytes of data,
+    /// so corrupted chunks are detected before they are used.
+    pub checksum: u32,
 }
 
 pub type DemoSnapshot = Vec<u8>;
@@ -78,10 +305,29 @@ pub type DemoSnapshot = Vec<u8>;
 pub enum DemoEvent {
     Game(GameEvents),
     Chat(NetChatMsg),
+    /// The vote state as the recorder saw it.
+    /// `None` if the vote ended.
+    Vote(Option<(VoteState, Option<Voted>)>),
+    /// A periodic capture of the scoreboard,
+    /// see [`SCOREBOARD_CAPTURE_INTERVAL_SECONDS`].
+    Scoreboard(Scoreboard),
 }
 
 pub type DemoEvents = Vec<DemoEvent>;
 
+/// The raw input of a player, as it was sent to the server.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DemoPlayerInput {
+    pub player_id: GameEntityId,
+    pub inp: PlayerInputChainable,
+}
+
+/// The inputs of all recorded players of a monotonic tick.
+///
+/// Unlike the snapshots, these allow to replay the physics exactly
+/// or to analyze the inputs, but they are only recorded optionally.
+pub type DemoInputs = Vec<DemoPlayerInput>;
+
 // 50 here is the assumed snap send rate
 // so it writes up to 30 seconds full of chunks
 /// number of chunks to write at once
@@ -89,3 +335,6 @@ const DATA_PER_CHUNK_TO_WRITE: u64 = 30 * 50;
 /// time offset so that even late packets have a chance
 /// to be considered in the demo.
 const SECONDS_UNTIL_WRITE: u64 = 3;
+/// how often the scoreboard (and the active vote) is captured,
+/// so that seeking inside a demo restores them quickly.
+pub const SCOREBOARD_CAPTURE_INTERVAL_SECONDS: u64 = 1;
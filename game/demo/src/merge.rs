@@ -0,0 +1,150 @@
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::anyhow;
+use base_io::io::Io;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    encryption::DemoKey,
+    recorder::{DemoRecorder, DemoRecorderCreateProps},
+    utils::{read_chunks, read_demo},
+    DemoEvents, DemoSnapshot,
+};
+
+/// A demo that should be merged, see [`merge_demos`].
+#[derive(Debug, Clone)]
+pub struct DemoMergeInput {
+    /// The name of the point of view inside the merged demo.
+    pub name: String,
+    /// The demo file.
+    pub file: Vec<u8>,
+}
+
+struct DemoMergeData {
+    snapshots: BTreeMap<u64, DemoSnapshot>,
+    events: BTreeMap<u64, DemoEvents>,
+}
+
+fn read_all_chunks<A: DeserializeOwned>(
+    demo_chunks: &[u8],
+    index: &BTreeMap<u64, u64>,
+    key: Option<&DemoKey>,
+) -> anyhow::Result<BTreeMap<u64, A>> {
+    let mut res = BTreeMap::default();
+    for &offset in index.values() {
+        res.append(&mut read_chunks(demo_chunks, offset as usize, key)?);
+    }
+    Ok(res)
+}
+
+/// Merges demos that were recorded on the same server & map at the same time
+/// into one demo, where every demo is a point of view that can be selected during playback.
+///
+/// The demos are aligned by their monotonic ticks and only the time window
+/// that all demos recorded is kept. The first demo is the main point of view.
+/// Events that multiple demos recorded are only written once.
+pub fn merge_demos(
+    io: &Io,
+    demos: Vec<DemoMergeInput>,
+    passphrase: Option<String>,
+    name: String,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        demos.len() >= 2,
+        "at least two demos are required for merging."
+    );
+
+    let mut header_ext = None;
+    let mut merge_data = Vec::new();
+    for demo in &demos {
+        let (_, demo_header_ext, chunks, tail) = read_demo(&demo.file)?;
+
+        let key = demo_header_ext
+            .encryption
+            .as_ref()
+            .map(|encryption| {
+                encryption.key(passphrase.as_deref().ok_or_else(|| {
+                    anyhow!("demo {} is encrypted, a key is required.", demo.name)
+                })?)
+            })
+            .transpose()?;
+
+        let first_header_ext = header_ext.get_or_insert_with(|| demo_header_ext.clone());
+        anyhow::ensure!(
+            first_header_ext.map_hash == demo_header_ext.map_hash
+                && first_header_ext.ticks_per_second == demo_header_ext.ticks_per_second,
+            "demo {} was not recorded on the same map.",
+            demo.name
+        );
+
+        merge_data.push(DemoMergeData {
+            snapshots: read_all_chunks(chunks, &tail.snapshots_index, key.as_ref())?,
+            events: read_all_chunks(chunks, &tail.events_index, key.as_ref())?,
+        });
+    }
+    let header_ext = header_ext.ok_or_else(|| anyhow!("no demos to merge."))?;
+
+    // monotonic ticks come from the server, so all demos share them
+    let first_tick = merge_data
+        .iter()
+        .filter_map(|data| data.snapshots.first_key_value().map(|(&tick, _)| tick))
+        .max()
+        .unwrap_or_default();
+    let last_tick = merge_data
+        .iter()
+        .filter_map(|data| data.snapshots.last_key_value().map(|(&tick, _)| tick))
+        .min()
+        .unwrap_or_default();
+    anyhow::ensure!(
+        first_tick <= last_tick,
+        "the demos were not recorded in the same time window."
+    );
+
+    let mut recorder = DemoRecorder::new(
+        DemoRecorderCreateProps {
+            map: header_ext.map,
+            map_hash: header_ext.map_hash,
+            game_options: header_ext.game_options,
+            required_resources: header_ext.required_resources,
+            physics_module: header_ext.physics_mod,
+            render_module: header_ext.render_mod,
+            physics_group_name: header_ext.physics_group_name,
+            encryption_passphrase: passphrase,
+            split: Default::default(),
+            record_inputs: false,
+            signing_key: None,
+            on_saved: None,
+            io: io.clone(),
+        },
+        header_ext.ticks_per_second,
+        Some(name),
+    )?;
+    let povs: Vec<usize> = demos
+        .into_iter()
+        .skip(1)
+        .map(|demo| recorder.add_pov(demo.name))
+        .collect();
+
+    let mut written_events: HashSet<Vec<u8>> = Default::default();
+    for monotonic_tick in first_tick..=last_tick {
+        written_events.clear();
+        for (index, data) in merge_data.iter_mut().enumerate() {
+            if let Some(snapshot) = data.snapshots.remove(&monotonic_tick) {
+                match index.checked_sub(1) {
+                    Some(pov) => recorder.add_pov_snapshot(povs[pov], monotonic_tick, snapshot),
+                    None => recorder.add_snapshot(monotonic_tick, snapshot),
+                }
+            }
+
+            // events that were sent to all clients are recorded by every demo
+            for event in data.events.remove(&monotonic_tick).into_iter().flatten() {
+                let event_ser = bincode::serde::encode_to_vec(&event, bincode::config::standard())?;
+                if written_events.insert(event_ser) {
+                    recorder.add_event(monotonic_tick, event);
+                }
+            }
+        }
+    }
+
+    recorder.finish()
+}
@@ -4,7 +4,10 @@ use std::{
     fs::File,
     io::{Seek, Write},
     path::Path,
-    sync::mpsc::{Receiver, Sender},
+    sync::{
+        mpsc::{Receiver, Sender},
+        Arc,
+    },
     thread::JoinHandle,
     time::Duration,
 };
@@ -14,14 +17,23 @@ use base::{hash::Hash, reduced_ascii_str::ReducedAsciiString};
 use base_io::io::Io;
 use game_interface::{
     interface::GameStateCreateOptions,
-    types::{game::NonZeroGameTickType, network_string::NetworkReducedAsciiString},
+    types::{
+        game::{GameEntityId, NonZeroGameTickType},
+        network_string::NetworkReducedAsciiString,
+    },
 };
 use itertools::Itertools;
 use serde::Serialize;
+use shared_base::network::messages::PlayerInputChainable;
 
 use crate::{
-    ChunkHeader, DemoEvent, DemoEvents, DemoGameModification, DemoHeader, DemoHeaderExt,
-    DemoSnapshot, DemoTail, DATA_PER_CHUNK_TO_WRITE, SECONDS_UNTIL_WRITE,
+    encryption::{DemoEncryption, DemoKey},
+    signature::{signature_path, DemoSignature, DemoSigningKey},
+    utils::{checksum, ticks_to_duration},
+    ChunkHeader, DemoChunksInfo, DemoChunksKind, DemoEvent, DemoEvents, DemoEventsChunkInfo,
+    DemoGameModification, DemoHeader, DemoHeaderExt, DemoInputs, DemoPlayerInput, DemoPov,
+    DemoSnapshot, DemoTail, DATA_PER_CHUNK_TO_WRITE, SCOREBOARD_CAPTURE_INTERVAL_SECONDS,
+    SECONDS_UNTIL_WRITE,
 };
 
 #[derive(Debug, Clone)]
@@ -33,12 +45,64 @@ pub struct DemoRecorderCreateProps {
     pub physics_module: DemoGameModification,
     pub render_module: DemoGameModification,
     pub physics_group_name: NetworkReducedAsciiString<24>,
+    /// If `Some`, the demo chunks are encrypted with a key
+    /// derived from this passphrase.
+    pub encryption_passphrase: Option<String>,
+    /// Continue the recording in new demo files, see [`DemoRecorderSplit`].
+    pub split: DemoRecorderSplit,
+    /// Also record the inputs of the players, see [`DemoInputs`].
+    pub record_inputs: bool,
+    /// If `Some`, every demo file that was written is signed,
+    /// see [`DemoSignature`].
+    pub signing_key: Option<DemoSigningKey>,
+    /// Called with the name of every demo file that was written.
+    pub on_saved: Option<DemoSavedCb>,
     pub io: Io,
 }
 
+/// See [`DemoRecorderCreateProps::on_saved`].
+///
+/// Called from the writer thread.
+#[derive(Clone)]
+pub struct DemoSavedCb(pub Arc<dyn Fn(&str) + Send + Sync>);
+
+impl std::fmt::Debug for DemoSavedCb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DemoSavedCb").finish_non_exhaustive()
+    }
+}
+
+/// When a recording continues in a new demo file.
+/// Every file is a complete demo on its own, the files are
+/// named like the demo with an increasing number appended.
+///
+/// The limits are only checked between chunks,
+/// so a file can be slightly bigger or longer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DemoRecorderSplit {
+    /// The maximum length of a single demo file.
+    pub max_duration: Option<Duration>,
+    /// The maximum size of a single demo file in bytes.
+    pub max_size: Option<u64>,
+}
+
 enum DemoRecorderChunk {
-    Snapshots { snaps: BTreeMap<u64, DemoSnapshot> },
-    Events { events: BTreeMap<u64, DemoEvents> },
+    Snapshots {
+        snaps: BTreeMap<u64, DemoSnapshot>,
+    },
+    Events {
+        events: BTreeMap<u64, DemoEvents>,
+    },
+    Pov {
+        name: String,
+    },
+    PovSnapshots {
+        pov: usize,
+        snaps: BTreeMap<u64, DemoSnapshot>,
+    },
+    Inputs {
+        inputs: BTreeMap<u64, DemoInputs>,
+    },
 }
 
 /// Records demos from snapshots & events
@@ -48,20 +112,30 @@ pub struct DemoRecorder {
     /// current demo snapshots
     pub snapshots: BTreeMap<u64, DemoSnapshot>,
     pub events: BTreeMap<u64, DemoEvents>,
+    /// snapshots of the additional point of views, see [`DemoRecorder::add_pov`]
+    pub pov_snapshots: Vec<BTreeMap<u64, DemoSnapshot>>,
+    /// only filled if [`DemoRecorderCreateProps::record_inputs`] is set
+    pub inputs: BTreeMap<u64, DemoInputs>,
+    record_inputs: bool,
+    /// the monotonic tick of the last scoreboard capture
+    last_scoreboard_capture: Option<u64>,
+    /// name of the demo file (of the first segment, if split)
+    name: String,
 
     /// Event sender for the writer thread.
     /// Must stay to not be dropped
     thread_sender: Sender<DemoRecorderChunk>,
     /// the thread that writes all demo changes to disk
-    _writer_thread: JoinHandle<()>,
+    writer_thread: Option<JoinHandle<()>>,
 }
 
 impl DemoRecorder {
+    /// Fails if the encryption of the demo can't be set up.
     pub fn new(
         props: DemoRecorderCreateProps,
         ticks_per_second: NonZeroGameTickType,
         mut forced_name: Option<String>,
-    ) -> Self {
+    ) -> anyhow::Result<Self> {
         let (thread_sender, recv) = std::sync::mpsc::channel();
 
         let now = chrono::Utc::now();
@@ -69,6 +143,14 @@ impl DemoRecorder {
             .take()
             .unwrap_or_else(|| format!("{}_{}", props.map.as_str(), now.format("%Y_%m_%d_%H_%M")));
 
+        let (encryption, key) = props
+            .encryption_passphrase
+            .as_deref()
+            .map(DemoEncryption::new)
+            .transpose()
+            .map_err(|err| anyhow!("could not create the demo encryption: {err}"))?
+            .unzip();
+
         let demo_header_ext = DemoHeaderExt {
             server: "".into(),
             physics_mod: props.physics_module,
@@ -79,13 +161,21 @@ impl DemoRecorder {
             ticks_per_second,
             game_options: props.game_options,
             physics_group_name: props.physics_group_name,
+            encryption,
         };
 
         let io = props.io;
+        let split = props.split;
+        let record_inputs = props.record_inputs;
+        let on_saved = props.on_saved;
+        let signing_key = props.signing_key;
+        // only needed for new keys when splitting
+        let passphrase = props.encryption_passphrase;
 
         let tmp_demo_dir = io.fs.get_save_path().join("tmp/demos");
         let demo_dir = io.fs.get_save_path().join("demos");
         let demo_header_ext_thread = demo_header_ext.clone();
+        let name = demo_name.clone();
         let writer_thread = std::thread::Builder::new()
             .name(format!("demo-recorder-{}", demo_header_ext.map.as_str()))
             .spawn(move || {
@@ -95,33 +185,46 @@ impl DemoRecorder {
                     &demo_name,
                     recv,
                     demo_header_ext_thread,
+                    key,
+                    passphrase,
+                    split,
+                    signing_key,
+                    on_saved,
                 )
                 .unwrap()
             })
             .expect("could not spawn a demo-recorder thread.");
 
-        Self {
+        Ok(Self {
             demo_header_ext,
             snapshots: Default::default(),
             events: Default::default(),
+            pov_snapshots: Default::default(),
+            inputs: Default::default(),
+            record_inputs,
+            last_scoreboard_capture: None,
+            name,
 
             thread_sender,
-            _writer_thread: writer_thread,
-        }
+            writer_thread: Some(writer_thread),
+        })
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn writer_thread_run(
         tmp_path: &Path,
         final_path: &Path,
         demo_name: &str,
         recv: Receiver<DemoRecorderChunk>,
-        header_ext: DemoHeaderExt,
+        mut header_ext: DemoHeaderExt,
+        mut key: Option<DemoKey>,
+        passphrase: Option<String>,
+        split: DemoRecorderSplit,
+        signing_key: Option<DemoSigningKey>,
+        on_saved: Option<DemoSavedCb>,
     ) -> anyhow::Result<()> {
         std::fs::create_dir_all(tmp_path)?;
         std::fs::create_dir_all(final_path)?;
-        let mut tmp_file = tempfile::NamedTempFile::new_in(tmp_path)?;
-        let file = tmp_file.as_file_mut();
-        let size = Cell::new(0);
 
         fn ser_ex<'a, T: Serialize>(
             v: &T,
@@ -163,45 +266,11 @@ impl DemoRecorder {
             Ok(file.write_all(v)?)
         }
 
-        let mut write_ser = Vec::new();
-        let mut write_comp = Vec::new();
-        let mut write_dst = Vec::new();
-        let mut write_data = Vec::new();
-
-        let header_ext_file = comp(ser(&header_ext, &mut write_ser)?, &mut write_comp, true)?;
-        let header_ext_len = header_ext_file.len();
-
-        write(
-            &size,
-            &mut *file,
-            ser_ex(
-                &DemoHeader {
-                    len: Duration::ZERO,
-                    size_ext: header_ext_len as u64,
-                    // don't update this value before ending the demo
-                    // makes it easy to detect corrupted demos
-                    size_chunks: 0,
-                },
-                &mut write_ser,
-                true,
-                true,
-            )?,
-        )?;
-
-        write(&size, &mut *file, header_ext_file)?;
-
-        let mut first_monotonic_snaps = None;
-        let mut last_monotonic_snaps = None;
-        let mut first_monotonic_events = None;
-        let mut last_monotonic_events = None;
-
-        let mut events_index: BTreeMap<u64, u64> = Default::default();
-        let mut snapshots_index: BTreeMap<u64, u64> = Default::default();
-
-        let size_before_chunks = size.get();
-
         fn write_chunk<'a, A: Serialize>(
             chunk: BTreeMap<u64, A>,
+            info: &DemoChunksInfo,
+            chunk_offset: u64,
+            key: Option<&DemoKey>,
             writer: &'a mut Vec<u8>,
             tmp: &mut Vec<u8>,
             tmp_dst: &mut Vec<u8>,
@@ -235,6 +304,7 @@ impl DemoRecorder {
                     &ChunkHeader {
                         monotonic_tick,
                         size: data.as_ref().map(|s| s.len() as u64).unwrap_or_default(),
+                        checksum: data.as_ref().map(|s| checksum(s)).unwrap_or_default(),
                     },
                     &mut *tmp,
                 )?;
@@ -247,9 +317,15 @@ impl DemoRecorder {
 
             tmp_dst.clear();
             tmp_dst.extend(0_u64.to_le_bytes());
+            // the info stays unencrypted
+            ser_ex(info, tmp_dst, false, true)?;
+            let data_start = tmp_dst.len();
             comp(writer, tmp_dst, false)?;
+            if let Some(key) = key {
+                key.encrypt_chunk(chunk_offset, tmp_dst, data_start)?;
+            }
             // write size
-            let size = (tmp_dst.len() - std::mem::size_of::<u64>()) as u64;
+            let size = (tmp_dst.len() - data_start) as u64;
             tmp_dst[0..std::mem::size_of::<u64>()].copy_from_slice(&size.to_le_bytes());
             std::mem::swap(writer, tmp_dst);
             Ok(writer.as_mut_slice())
@@ -260,6 +336,9 @@ impl DemoRecorder {
             file: &mut File,
             index: &mut BTreeMap<u64, u64>,
             chunk: BTreeMap<u64, A>,
+            kind: DemoChunksKind,
+            pov: u64,
+            key: Option<&DemoKey>,
             size: &Cell<usize>,
             size_before_chunks: usize,
             first_monotonic: &mut Option<u64>,
@@ -280,12 +359,27 @@ impl DemoRecorder {
                 .map(|(c, _)| *c)
                 .ok_or_else(|| anyhow!("empty chunks are not allowed."))?;
 
-            index.insert(first_tick, (size.get() - size_before_chunks) as u64);
+            let chunk_offset = (size.get() - size_before_chunks) as u64;
+            index.insert(first_tick, chunk_offset);
 
             write(
                 size,
                 &mut *file,
-                write_chunk(chunk, write_ser, write_comp, write_dst, write_data)?,
+                write_chunk(
+                    chunk,
+                    &DemoChunksInfo {
+                        kind,
+                        pov,
+                        first_tick,
+                        last_tick,
+                    },
+                    chunk_offset,
+                    key,
+                    write_ser,
+                    write_comp,
+                    write_dst,
+                    write_data,
+                )?,
             )?;
 
             let monotonic_first_tick = *first_monotonic.get_or_insert(first_tick);
@@ -305,94 +399,256 @@ impl DemoRecorder {
             Ok(())
         }
 
-        while let Ok(event) = recv.recv() {
-            match event {
-                DemoRecorderChunk::Snapshots { snaps } => {
-                    serialize_and_write_chunk(
-                        file,
-                        &mut snapshots_index,
-                        snaps,
-                        &size,
-                        size_before_chunks,
-                        &mut first_monotonic_snaps,
-                        &mut last_monotonic_snaps,
-                        &mut write_ser,
-                        &mut write_comp,
-                        &mut write_dst,
-                        &mut write_data,
-                    )?;
-                }
-                DemoRecorderChunk::Events { events } => {
-                    serialize_and_write_chunk(
-                        file,
-                        &mut events_index,
-                        events,
-                        &size,
-                        size_before_chunks,
-                        &mut first_monotonic_events,
-                        &mut last_monotonic_events,
-                        &mut write_ser,
-                        &mut write_comp,
-                        &mut write_dst,
-                        &mut write_data,
-                    )?;
+        let mut write_ser = Vec::new();
+        let mut write_comp = Vec::new();
+        let mut write_dst = Vec::new();
+        let mut write_data = Vec::new();
+
+        let mut pov_names: Vec<String> = Default::default();
+        // the chunk that starts the next demo file
+        let mut next_chunk: Option<DemoRecorderChunk> = None;
+        let mut segment = 0;
+
+        loop {
+            if segment > 0 {
+                // the chunk offsets are used as nonces, which are only unique
+                // inside of a single file, so every file needs its own key.
+                if let Some(passphrase) = passphrase.as_deref() {
+                    let (encryption, segment_key) = DemoEncryption::new(passphrase)?;
+                    header_ext.encryption = Some(encryption);
+                    key = Some(segment_key);
                 }
             }
-        }
 
-        let chunks_size = size.get() - size_before_chunks;
+            let mut tmp_file = tempfile::NamedTempFile::new_in(tmp_path)?;
+            let file = tmp_file.as_file_mut();
+            let size = Cell::new(0);
 
-        // `or` to make sure None is never compared if there is one with Some
-        // having Some is a must for the next if check
-        let first_monotonic = (first_monotonic_snaps.or(first_monotonic_events))
-            .min(first_monotonic_events.or(first_monotonic_snaps));
-        let last_monotonic = (last_monotonic_snaps.or(last_monotonic_events))
-            .max(last_monotonic_events.or(last_monotonic_snaps));
+            let header_ext_file = comp(ser(&header_ext, &mut write_ser)?, &mut write_comp, true)?;
+            let header_ext_len = header_ext_file.len();
 
-        if let Some((first_monotonic, last_monotonic)) = first_monotonic.zip(last_monotonic) {
-            // write the demo tail
             write(
                 &size,
                 &mut *file,
-                comp(
-                    ser(
-                        &DemoTail {
-                            snapshots_index,
-                            events_index,
-                        },
-                        &mut write_ser,
-                    )?,
-                    &mut write_comp,
+                ser_ex(
+                    &DemoHeader {
+                        len: Duration::ZERO,
+                        size_ext: header_ext_len as u64,
+                        // don't update this value before ending the demo
+                        // makes it easy to detect corrupted demos
+                        size_chunks: 0,
+                    },
+                    &mut write_ser,
+                    true,
                     true,
                 )?,
             )?;
 
-            // write the final header
-            file.seek(std::io::SeekFrom::Start(0))?;
-            file.write_all(ser_ex(
-                &DemoHeader {
-                    len: {
-                        let secs = (last_monotonic - first_monotonic) / header_ext.ticks_per_second;
-                        let nanos = ((last_monotonic - first_monotonic)
-                            % header_ext.ticks_per_second)
-                            * (Duration::from_secs(1).as_nanos() as u64
-                                / header_ext.ticks_per_second);
-                        Duration::new(secs, nanos as u32)
+            write(&size, &mut *file, header_ext_file)?;
+
+            let mut first_monotonic_snaps = None;
+            let mut last_monotonic_snaps = None;
+            let mut first_monotonic_events = None;
+            let mut last_monotonic_events = None;
+            // inputs don't count into the length of the demo
+            let mut first_monotonic_inputs = None;
+            let mut last_monotonic_inputs = None;
+
+            let mut events_index: BTreeMap<u64, u64> = Default::default();
+            let mut events_chunks: BTreeMap<u64, DemoEventsChunkInfo> = Default::default();
+            let mut snapshots_index: BTreeMap<u64, u64> = Default::default();
+            let mut inputs_index: BTreeMap<u64, u64> = Default::default();
+            // every demo file contains all point of views
+            let mut povs: Vec<DemoPov> = pov_names
+                .iter()
+                .map(|name| DemoPov {
+                    name: name.clone(),
+                    snapshots_index: Default::default(),
+                })
+                .collect();
+            let mut povs_monotonic: Vec<(Option<u64>, Option<u64>)> =
+                vec![Default::default(); povs.len()];
+
+            let size_before_chunks = size.get();
+
+            while let Some(event) = next_chunk.take().or_else(|| recv.recv().ok()) {
+                match event {
+                    DemoRecorderChunk::Snapshots { snaps } => {
+                        // split before snapshots, so every demo file starts with one
+                        let exceeds_duration = split.max_duration.is_some_and(|max| {
+                            first_monotonic_snaps.zip(last_monotonic_snaps).is_some_and(
+                                |(first, last)| {
+                                    ticks_to_duration(last - first, header_ext.ticks_per_second)
+                                        >= max
+                                },
+                            )
+                        });
+                        let exceeds_size = split
+                            .max_size
+                            .is_some_and(|max_size| size.get() as u64 >= max_size);
+                        if first_monotonic_snaps.is_some() && (exceeds_duration || exceeds_size) {
+                            next_chunk = Some(DemoRecorderChunk::Snapshots { snaps });
+                            break;
+                        }
+                        serialize_and_write_chunk(
+                            file,
+                            &mut snapshots_index,
+                            snaps,
+                            DemoChunksKind::Snapshots,
+                            0,
+                            key.as_ref(),
+                            &size,
+                            size_before_chunks,
+                            &mut first_monotonic_snaps,
+                            &mut last_monotonic_snaps,
+                            &mut write_ser,
+                            &mut write_comp,
+                            &mut write_dst,
+                            &mut write_data,
+                        )?;
+                    }
+                    DemoRecorderChunk::Events { events } => {
+                        if let Some((&first_tick, _)) = events.first_key_value() {
+                            events_chunks.insert(first_tick, DemoEventsChunkInfo::new(&events));
+                        }
+                        serialize_and_write_chunk(
+                            file,
+                            &mut events_index,
+                            events,
+                            DemoChunksKind::Events,
+                            0,
+                            key.as_ref(),
+                            &size,
+                            size_before_chunks,
+                            &mut first_monotonic_events,
+                            &mut last_monotonic_events,
+                            &mut write_ser,
+                            &mut write_comp,
+                            &mut write_dst,
+                            &mut write_data,
+                        )?;
+                    }
+                    DemoRecorderChunk::Pov { name } => {
+                        pov_names.push(name.clone());
+                        povs.push(DemoPov {
+                            name,
+                            snapshots_index: Default::default(),
+                        });
+                        povs_monotonic.push(Default::default());
+                    }
+                    DemoRecorderChunk::PovSnapshots { pov, snaps } => {
+                        let (first_monotonic, last_monotonic) = povs_monotonic
+                            .get_mut(pov)
+                            .ok_or_else(|| anyhow!("point of view {pov} was never added."))?;
+                        serialize_and_write_chunk(
+                            file,
+                            &mut povs[pov].snapshots_index,
+                            snaps,
+                            DemoChunksKind::PovSnapshots,
+                            pov as u64,
+                            key.as_ref(),
+                            &size,
+                            size_before_chunks,
+                            first_monotonic,
+                            last_monotonic,
+                            &mut write_ser,
+                            &mut write_comp,
+                            &mut write_dst,
+                            &mut write_data,
+                        )?;
+                    }
+                    DemoRecorderChunk::Inputs { inputs } => {
+                        serialize_and_write_chunk(
+                            file,
+                            &mut inputs_index,
+                            inputs,
+                            DemoChunksKind::Inputs,
+                            0,
+                            key.as_ref(),
+                            &size,
+                            size_before_chunks,
+                            &mut first_monotonic_inputs,
+                            &mut last_monotonic_inputs,
+                            &mut write_ser,
+                            &mut write_comp,
+                            &mut write_dst,
+                            &mut write_data,
+                        )?;
+                    }
+                }
+            }
+
+            let chunks_size = size.get() - size_before_chunks;
+
+            // `or` to make sure None is never compared if there is one with Some
+            // having Some is a must for the next if check
+            let first_monotonic = (first_monotonic_snaps.or(first_monotonic_events))
+                .min(first_monotonic_events.or(first_monotonic_snaps));
+            let last_monotonic = (last_monotonic_snaps.or(last_monotonic_events))
+                .max(last_monotonic_events.or(last_monotonic_snaps));
+
+            if let Some((first_monotonic, last_monotonic)) = first_monotonic.zip(last_monotonic) {
+                // write the demo tail
+                write(
+                    &size,
+                    &mut *file,
+                    comp(
+                        ser(
+                            &DemoTail {
+                                snapshots_index,
+                                events_index,
+                                events_chunks,
+                                povs,
+                                inputs_index,
+                            },
+                            &mut write_ser,
+                        )?,
+                        &mut write_comp,
+                        true,
+                    )?,
+                )?;
+
+                // write the final header
+                file.seek(std::io::SeekFrom::Start(0))?;
+                file.write_all(ser_ex(
+                    &DemoHeader {
+                        len: ticks_to_duration(
+                            last_monotonic - first_monotonic,
+                            header_ext.ticks_per_second,
+                        ),
+                        size_ext: header_ext_len as u64,
+                        size_chunks: chunks_size as u64,
                     },
-                    size_ext: header_ext_len as u64,
-                    size_chunks: chunks_size as u64,
-                },
-                &mut write_ser,
-                true,
-                true,
-            )?)?;
+                    &mut write_ser,
+                    true,
+                    true,
+                )?)?;
 
-            file.flush()?;
+                file.flush()?;
 
-            let (_, path) = tmp_file.keep()?;
-            std::fs::rename(path, final_path.join(format!("{}.twdemo", demo_name)))?;
+                let name = if segment == 0 {
+                    demo_name.to_string()
+                } else {
+                    format!("{}_{}", demo_name, segment + 1)
+                };
+                let (_, path) = tmp_file.keep()?;
+                let demo_path = final_path.join(format!("{}.twdemo", name));
+                std::fs::rename(path, &demo_path)?;
+                if let Some(signing_key) = &signing_key {
+                    let signature = DemoSignature::sign(signing_key, &std::fs::read(&demo_path)?)?;
+                    std::fs::write(signature_path(&demo_path), signature.to_file()?)?;
+                }
+                if let Some(on_saved) = &on_saved {
+                    (on_saved.0)(&name);
+                }
+            }
+            // else the demo is invalid and can be dropped.
+
+            if next_chunk.is_none() {
+                break;
+            }
+            segment += 1;
         }
-        // else the demo is invalid and can be dropped.
 
         Ok(())
     }
@@ -468,6 +724,39 @@ impl DemoRecorder {
         }
     }
 
+    /// The name of the demo in the demos directory, without extension.
+    /// Split demos append the segment to the later files.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Adds an additional point of view to the demo,
+    /// returns its index for [`DemoRecorder::add_pov_snapshot`].
+    pub fn add_pov(&mut self, name: String) -> usize {
+        self.pov_snapshots.push(Default::default());
+        // ignore the error here, if the write thread died, so be it, can't recover anyway.
+        let _ = self.thread_sender.send(DemoRecorderChunk::Pov { name });
+        self.pov_snapshots.len() - 1
+    }
+
+    pub fn add_pov_snapshot(&mut self, pov: usize, monotonic_tick: u64, snapshot: Vec<u8>) {
+        let snapshots = &mut self.pov_snapshots[pov];
+        Self::try_write_chunks(
+            snapshots,
+            &self.demo_header_ext,
+            &self.thread_sender,
+            |snaps| DemoRecorderChunk::PovSnapshots { pov, snaps },
+        );
+
+        // make sure only snapshots of the last 3 seconds are handled
+        if Self::can_add_chunk(monotonic_tick, snapshots, &self.demo_header_ext) {
+            // if the entry already exist, update if, else create a new
+            let entry = snapshots.entry(monotonic_tick).or_default();
+
+            *entry = snapshot;
+        }
+    }
+
     pub fn add_event(&mut self, monotonic_tick: u64, event: DemoEvent) {
         Self::try_write_chunks(
             &mut self.events,
@@ -484,11 +773,66 @@ impl DemoRecorder {
             entry.push(event);
         }
     }
-}
 
-impl Drop for DemoRecorder {
-    fn drop(&mut self) {
-        // write remaining chunks
+    /// Records the input a player sent for a monotonic tick.
+    /// A later input of the same player for the same tick replaces the earlier one.
+    ///
+    /// Does nothing if [`DemoRecorderCreateProps::record_inputs`] is not set.
+    pub fn add_input(&mut self, player_id: GameEntityId, inp: PlayerInputChainable) {
+        if !self.record_inputs {
+            return;
+        }
+        Self::try_write_chunks(
+            &mut self.inputs,
+            &self.demo_header_ext,
+            &self.thread_sender,
+            |inputs| DemoRecorderChunk::Inputs { inputs },
+        );
+
+        let monotonic_tick = inp.for_monotonic_tick;
+        // make sure only inputs of the last 3 seconds are handled
+        if Self::can_add_chunk(monotonic_tick, &mut self.inputs, &self.demo_header_ext) {
+            let entry = self.inputs.entry(monotonic_tick).or_default();
+
+            if let Some(player_inp) = entry.iter_mut().find(|i| i.player_id == player_id) {
+                player_inp.inp = inp;
+            } else {
+                entry.push(DemoPlayerInput { player_id, inp });
+            }
+        }
+    }
+
+    /// Returns `true` if the scoreboard should be captured for this tick.
+    /// Captures happen every [`SCOREBOARD_CAPTURE_INTERVAL_SECONDS`].
+    pub fn should_capture_scoreboard(&mut self, monotonic_tick: u64) -> bool {
+        let interval =
+            self.demo_header_ext.ticks_per_second.get() * SCOREBOARD_CAPTURE_INTERVAL_SECONDS;
+        if self
+            .last_scoreboard_capture
+            .is_some_and(|tick| monotonic_tick < tick + interval && monotonic_tick >= tick)
+        {
+            false
+        } else {
+            self.last_scoreboard_capture = Some(monotonic_tick);
+            true
+        }
+    }
+
+    /// Writes all remaining chunks and waits until the demo file is written.
+    pub fn finish(mut self) -> anyhow::Result<()> {
+        self.write_remaining_chunks();
+        // closing the channel ends the writer thread
+        let (thread_sender, _) = std::sync::mpsc::channel();
+        drop(std::mem::replace(&mut self.thread_sender, thread_sender));
+        if let Some(writer_thread) = self.writer_thread.take() {
+            writer_thread
+                .join()
+                .map_err(|_| anyhow!("the demo writer thread panicked."))?;
+        }
+        Ok(())
+    }
+
+    fn write_remaining_chunks(&mut self) {
         fn check_write<A>(
             data: &mut BTreeMap<u64, Vec<A>>,
             thread_sender: &Sender<DemoRecorderChunk>,
@@ -513,5 +857,336 @@ impl Drop for DemoRecorder {
         check_write(&mut self.events, &self.thread_sender, |events| {
             DemoRecorderChunk::Events { events }
         });
+        for (pov, snapshots) in self.pov_snapshots.iter_mut().enumerate() {
+            check_write(snapshots, &self.thread_sender, |snaps| {
+                DemoRecorderChunk::PovSnapshots { pov, snaps }
+            });
+        }
+        check_write(&mut self.inputs, &self.thread_sender, |inputs| {
+            DemoRecorderChunk::Inputs { inputs }
+        });
+    }
+}
+
+impl Drop for DemoRecorder {
+    fn drop(&mut self) {
+        // write remaining chunks
+        self.write_remaining_chunks();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, ops::Range, time::Duration};
+
+    use base::reduced_ascii_str::ReducedAsciiString;
+    use game_interface::{events::GameEvents, types::game::NonZeroGameTickType};
+    use pool::datatypes::PoolLinkedHashMap;
+
+    use crate::{
+        utils::{
+            chunks_group_head_size, chunks_group_size, decomp, demo_header_size, deser,
+            find_next_event, find_prev_event, read_chunks, read_chunks_group_partial,
+            read_chunks_in_range, read_chunks_partial, read_demo, read_demo_header,
+            read_demo_header_ext, read_demo_tail, ticks_to_duration,
+        },
+        ChunkHeader, DemoEvent, DemoEventKind, DemoGameModification, DemoHeader, DemoHeaderExt,
+        DemoSnapshot,
+    };
+
+    use super::{DemoRecorder, DemoRecorderChunk, DemoRecorderSplit};
+
+    const TICKS_PER_SECOND: NonZeroGameTickType = NonZeroGameTickType::new(50).unwrap();
+
+    /// Writes the chunks like the writer thread of a recording
+    /// and returns all demo files in order.
+    fn record(chunks: Vec<DemoRecorderChunk>, split: DemoRecorderSplit) -> Vec<Vec<u8>> {
+        let dir = tempfile::tempdir().unwrap();
+        let (sender, recv) = std::sync::mpsc::channel();
+        for chunk in chunks {
+            sender.send(chunk).unwrap();
+        }
+        drop(sender);
+
+        let demo_dir = dir.path().join("demos");
+        DemoRecorder::writer_thread_run(
+            &dir.path().join("tmp"),
+            &demo_dir,
+            "test",
+            recv,
+            DemoHeaderExt {
+                server: Default::default(),
+                physics_mod: DemoGameModification::Native,
+                render_mod: DemoGameModification::Native,
+                required_resources: Default::default(),
+                map: ReducedAsciiString::from_str_lossy("test"),
+                map_hash: Default::default(),
+                ticks_per_second: TICKS_PER_SECOND,
+                game_options: Default::default(),
+                physics_group_name: "vanilla".try_into().unwrap(),
+                encryption: None,
+            },
+            None,
+            None,
+            split,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let mut files = vec![std::fs::read(demo_dir.join("test.twdemo")).unwrap()];
+        files.extend((2..).map_while(|segment| {
+            std::fs::read(demo_dir.join(format!("test_{segment}.twdemo"))).ok()
+        }));
+        files
+    }
+
+    fn snapshots(ticks: Range<u64>) -> BTreeMap<u64, DemoSnapshot> {
+        ticks
+            .map(|tick| (tick, tick.to_le_bytes().repeat(4)))
+            .collect()
+    }
+
+    #[test]
+    fn recover_unfinished_demo() {
+        let file = record(
+            (0..3)
+                .map(|i| DemoRecorderChunk::Snapshots {
+                    snaps: snapshots(i * 50..(i + 1) * 50),
+                })
+                .collect(),
+            Default::default(),
+        )
+        .remove(0);
+
+        // the recording crashed while the last group was written
+        let (header, header_size) = read_demo_header(&file).unwrap();
+        let chunks_end = header_size + (header.size_ext + header.size_chunks) as usize;
+        let mut file = file[..chunks_end - 1].to_vec();
+        file[..header_size].copy_from_slice(
+            &bincode::serde::encode_to_vec(
+                DemoHeader {
+                    len: Duration::ZERO,
+                    size_ext: header.size_ext,
+                    size_chunks: 0,
+                },
+                bincode::config::standard().with_fixed_int_encoding(),
+            )
+            .unwrap(),
+        );
+
+        let (header, _, chunks, tail) = read_demo(&file).unwrap();
+        assert_eq!(
+            tail.snapshots_index.keys().copied().collect::<Vec<_>>(),
+            vec![0, 50]
+        );
+        assert_eq!(header.len, ticks_to_duration(99, TICKS_PER_SECOND));
+        let mut snaps = BTreeMap::default();
+        for &offset in tail.snapshots_index.values() {
+            snaps.append(&mut read_chunks::<DemoSnapshot>(chunks, offset as usize, None).unwrap());
+        }
+        assert_eq!(snaps, snapshots(0..100));
+    }
+
+    #[test]
+    fn read_chunks_in_front_of_corrupted_chunk() {
+        let file = record(
+            vec![DemoRecorderChunk::Snapshots {
+                snaps: snapshots(0..10),
+            }],
+            Default::default(),
+        )
+        .remove(0);
+        let (_, _, chunks, _) = read_demo(&file).unwrap();
+
+        let head_size = chunks_group_head_size();
+        let group = &chunks[..chunks_group_size(chunks).unwrap()];
+        let mut data = Vec::new();
+        decomp(&group[head_size..], &mut data).unwrap();
+
+        // flip the last byte of the chunk at tick 5
+        let (len, mut pos) = deser::<u64>(&data).unwrap();
+        for _ in 0..len {
+            let (header, read_size) = deser::<ChunkHeader>(&data[pos..]).unwrap();
+            pos += read_size + header.size as usize;
+            if header.monotonic_tick == 5 {
+                data[pos - 1] ^= 1;
+                break;
+            }
+        }
+        let data = zstd::encode_all(data.as_slice(), 0).unwrap();
+        let mut corrupted = group[..head_size].to_vec();
+        corrupted[..std::mem::size_of::<u64>()].copy_from_slice(&(data.len() as u64).to_le_bytes());
+        corrupted.extend(data);
+
+        let (snaps, err) = read_chunks_partial::<DemoSnapshot>(&corrupted, 0, None);
+        assert!(err.is_some());
+        assert_eq!(snaps, snapshots(0..5));
+        assert!(read_chunks::<DemoSnapshot>(&corrupted, 0, None).is_err());
+    }
+    #[test]
+    fn find_events_by_kind() {
+        let no_action = || {
+            vec![DemoEvent::Game(GameEvents {
+                worlds: PoolLinkedHashMap::new_without_pool(),
+                event_id: Default::default(),
+            })]
+        };
+        let file = record(
+            vec![
+                DemoRecorderChunk::Snapshots {
+                    snaps: snapshots(0..100),
+                },
+                DemoRecorderChunk::Events {
+                    events: (0..50).map(|tick| (tick, no_action())).collect(),
+                },
+                DemoRecorderChunk::Events {
+                    events: [
+                        (50, no_action()),
+                        (70, vec![DemoEvent::Vote(None)]),
+                        (99, no_action()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                },
+            ],
+            Default::default(),
+        )
+        .remove(0);
+        let (_, _, chunks, tail) = read_demo(&file).unwrap();
+
+        let vote = DemoEventKind::Vote;
+        assert!(!tail.events_chunks[&0].kinds.may_contain_kind(&vote));
+        assert!(tail.events_chunks[&50].kinds.may_contain_kind(&vote));
+        assert_eq!(
+            tail.events_chunks_after(0, &vote).collect::<Vec<_>>(),
+            vec![(50, tail.events_index[&50])]
+        );
+
+        assert_eq!(
+            find_next_event(chunks, &tail, None, 0, &vote).unwrap(),
+            Some(70)
+        );
+        assert_eq!(
+            find_next_event(chunks, &tail, None, 70, &vote).unwrap(),
+            None
+        );
+        assert_eq!(
+            find_prev_event(chunks, &tail, None, 99, &vote).unwrap(),
+            Some(70)
+        );
+        assert_eq!(
+            find_prev_event(chunks, &tail, None, 70, &vote).unwrap(),
+            None
+        );
+    }
+    fn read_snapshots(file: &[u8]) -> BTreeMap<u64, DemoSnapshot> {
+        let (_, _, chunks, tail) = read_demo(file).unwrap();
+        let mut snaps = BTreeMap::default();
+        for &offset in tail.snapshots_index.values() {
+            snaps.append(&mut read_chunks(chunks, offset as usize, None).unwrap());
+        }
+        snaps
+    }
+
+    #[test]
+    fn split_recordings() {
+        let chunks = || {
+            (0..4)
+                .map(|i| DemoRecorderChunk::Snapshots {
+                    snaps: snapshots(i * 50..(i + 1) * 50),
+                })
+                .collect()
+        };
+
+        // the limit is checked before every group of snapshots
+        let files = record(
+            chunks(),
+            DemoRecorderSplit {
+                max_duration: Some(Duration::from_secs(1)),
+                max_size: None,
+            },
+        );
+        assert_eq!(files.len(), 2);
+        assert_eq!(read_snapshots(&files[0]), snapshots(0..100));
+        assert_eq!(read_snapshots(&files[1]), snapshots(100..200));
+        for file in &files {
+            let (header, _, _, _) = read_demo(file).unwrap();
+            assert_eq!(header.len, ticks_to_duration(99, TICKS_PER_SECOND));
+        }
+
+        let files = record(
+            chunks(),
+            DemoRecorderSplit {
+                max_duration: None,
+                max_size: Some(1),
+            },
+        );
+        assert_eq!(files.len(), 4);
+        let mut snaps = BTreeMap::default();
+        for file in &files {
+            snaps.append(&mut read_snapshots(file));
+        }
+        assert_eq!(snaps, snapshots(0..200));
+    }
+    #[test]
+    fn cut_across_groups() {
+        let file = record(
+            (0..3)
+                .map(|i| DemoRecorderChunk::Snapshots {
+                    snaps: snapshots(i * 50..(i + 1) * 50),
+                })
+                .collect(),
+            Default::default(),
+        )
+        .remove(0);
+        let (_, _, chunks, tail) = read_demo(&file).unwrap();
+
+        // the range starts inside of the first group and ends inside of the last
+        let snaps: BTreeMap<u64, DemoSnapshot> =
+            read_chunks_in_range(chunks, &tail.snapshots_index, None, 40..=110).unwrap();
+        assert_eq!(snaps, snapshots(40..111));
+
+        // recorded again like the cut demo
+        let cut = record(
+            vec![DemoRecorderChunk::Snapshots { snaps }],
+            Default::default(),
+        )
+        .remove(0);
+        let (header, _, _, _) = read_demo(&cut).unwrap();
+        assert_eq!(header.len, ticks_to_duration(70, TICKS_PER_SECOND));
+        assert_eq!(read_snapshots(&cut), snapshots(40..111));
+    }
+    #[test]
+    fn read_streamed_groups() {
+        let file = record(
+            (0..3)
+                .map(|i| DemoRecorderChunk::Snapshots {
+                    snaps: snapshots(i * 50..(i + 1) * 50),
+                })
+                .collect(),
+            Default::default(),
+        )
+        .remove(0);
+
+        // only the parts of the file that a streamed demo reads
+        let (header, header_size) = read_demo_header(&file[..demo_header_size()]).unwrap();
+        let chunks_start = header_size + header.size_ext as usize;
+        let header_ext = read_demo_header_ext(&file[header_size..chunks_start]).unwrap();
+        assert_eq!(header_ext.ticks_per_second, TICKS_PER_SECOND);
+        let tail = read_demo_tail(&file[chunks_start + header.size_chunks as usize..]).unwrap();
+        assert_eq!(tail.snapshots_index.len(), 3);
+
+        let mut snaps = BTreeMap::default();
+        for &offset in tail.snapshots_index.values() {
+            let start = chunks_start + offset as usize;
+            let size = chunks_group_size(&file[start..start + chunks_group_head_size()]).unwrap();
+            let (mut group, err) =
+                read_chunks_group_partial(&file[start..start + size], offset as usize, None);
+            assert!(err.is_none());
+            snaps.append(&mut group);
+        }
+        assert_eq!(snaps, read_snapshots(&file));
+        assert_eq!(snaps, snapshots(0..150));
     }
 }
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use game_interface::{
+    events::{GameWorldAction, GameWorldEvent, GameWorldGlobalEvent, GameWorldSystemMessage},
+    types::{
+        character_info::CharacterInfoRedaction, game::GameEntityId,
+        render::scoreboard::ScoreboardGameType,
+    },
+};
+
+use crate::DemoEvent;
+
+/// Removes the names of the players from the events of a demo,
+/// so demos can be published without leaking who played or what was said.
+///
+/// Names are replaced like in the redacted snapshots
+/// (see [`CharacterInfoRedaction::name_of`]), players that are not part
+/// of the redaction keep their names.
+/// Names inside of texts, e.g. chat messages, can only be replaced
+/// if the player is known, see [`DemoRedaction::add_player`].
+#[derive(Debug, Default)]
+pub struct DemoRedaction {
+    redaction: CharacterInfoRedaction,
+    /// The replacements by the real names of the players.
+    names: HashMap<String, String>,
+}
+
+impl DemoRedaction {
+    pub fn new(redaction: CharacterInfoRedaction) -> Self {
+        Self {
+            redaction,
+            names: Default::default(),
+        }
+    }
+
+    /// Remembers the name of a player,
+    /// e.g. from the character infos of a snapshot.
+    pub fn add_player(&mut self, id: &GameEntityId, name: &str) {
+        if !name.is_empty() && self.redaction.contains(id) {
+            self.names
+                .insert(name.to_string(), self.redaction.name_of(id));
+        }
+    }
+
+    fn redact_text(&self, text: &mut String) {
+        // longer names first, in case a name is part of another name
+        let mut names: Vec<_> = self.names.iter().collect();
+        names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+        for (name, pseudonym) in names {
+            if text.contains(name.as_str()) {
+                *text = text.replace(name.as_str(), pseudonym);
+            }
+        }
+    }
+
+    /// Redacts a single event.
+    /// Returns `None` if the event must be removed completely.
+    pub fn redact_event(&mut self, mut event: DemoEvent, remove_chat: bool) -> Option<DemoEvent> {
+        match &mut event {
+            DemoEvent::Game(events) => {
+                for ev in events
+                    .worlds
+                    .values_mut()
+                    .flat_map(|world| world.events.values_mut())
+                {
+                    let GameWorldEvent::Global(ev) = ev else {
+                        continue;
+                    };
+                    match ev {
+                        GameWorldGlobalEvent::System(
+                            GameWorldSystemMessage::PlayerJoined { id, name }
+                            | GameWorldSystemMessage::PlayerLeft { id, name },
+                        ) => {
+                            self.add_player(id, name);
+                            if self.redaction.contains(id) {
+                                **name = self.redaction.name_of(id);
+                            }
+                        }
+                        GameWorldGlobalEvent::System(GameWorldSystemMessage::Custom(msg))
+                        | GameWorldGlobalEvent::Action(GameWorldAction::Custom(msg))
+                        | GameWorldGlobalEvent::PlayerMessage { msg, .. }
+                        | GameWorldGlobalEvent::Kick { reason: msg, .. }
+                        | GameWorldGlobalEvent::ExportRoundReport { report: msg, .. } => {
+                            self.redact_text(msg);
+                        }
+                        GameWorldGlobalEvent::Action(GameWorldAction::RaceTeamFinish {
+                            team_name,
+                            ..
+                        }) => {
+                            self.redact_text(team_name);
+                        }
+                        GameWorldGlobalEvent::Action(
+                            GameWorldAction::Kill { .. } | GameWorldAction::RaceFinish { .. },
+                        )
+                        | GameWorldGlobalEvent::Stat(_)
+                        | GameWorldGlobalEvent::Vote(_) => {
+                            // only contains ids
+                        }
+                    }
+                }
+            }
+            DemoEvent::Chat(msg) => {
+                if remove_chat {
+                    return None;
+                }
+                self.redact_text(&mut msg.msg);
+            }
+            DemoEvent::Vote(_) => {
+                // only contains ids
+            }
+            DemoEvent::Scoreboard(scoreboard) => {
+                // stage names are chosen by players
+                let stages: Vec<_> = match &mut scoreboard.game {
+                    ScoreboardGameType::SidedPlay {
+                        red_stages,
+                        blue_stages,
+                        ..
+                    } => red_stages
+                        .values_mut()
+                        .chain(blue_stages.values_mut())
+                        .collect(),
+                    ScoreboardGameType::SoloPlay { stages, .. } => stages.values_mut().collect(),
+                };
+                for stage in stages {
+                    self.redact_text(&mut stage.name);
+                }
+            }
+        }
+        Some(event)
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use game_interface::types::game::NonZeroGameTickType;
+
+use crate::{
+    recorder::{DemoRecorder, DemoRecorderCreateProps},
+    DemoEvent, DemoEvents, DemoSnapshot,
+};
+
+/// Keeps the snapshots & events of the last ticks in memory,
+/// so they can be saved as a demo afterwards (e.g. an instant replay).
+#[derive(Debug, Default)]
+pub struct DemoReplayBuffer {
+    snapshots: BTreeMap<u64, DemoSnapshot>,
+    events: BTreeMap<u64, DemoEvents>,
+}
+
+impl DemoReplayBuffer {
+    /// Adds the snapshot and drops everything that is more than
+    /// `keep_ticks` older than it.
+    pub fn add_snapshot(&mut self, monotonic_tick: u64, snapshot: DemoSnapshot, keep_ticks: u64) {
+        self.snapshots.insert(monotonic_tick, snapshot);
+
+        let first_tick = monotonic_tick.saturating_sub(keep_ticks);
+        self.snapshots = self.snapshots.split_off(&first_tick);
+        self.events = self.events.split_off(&first_tick);
+    }
+
+    pub fn add_event(&mut self, monotonic_tick: u64, event: DemoEvent) {
+        self.events.entry(monotonic_tick).or_default().push(event);
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.events.clear();
+    }
+
+    /// Saves the buffered ticks as a demo with the given name in the demos directory.
+    /// The demo is written in the background.
+    pub fn save(
+        &self,
+        props: DemoRecorderCreateProps,
+        ticks_per_second: NonZeroGameTickType,
+        name: String,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !self.snapshots.is_empty(),
+            "there are no snapshots to save yet."
+        );
+
+        let mut recorder = DemoRecorder::new(props, ticks_per_second, Some(name))?;
+        for (&monotonic_tick, snapshot) in &self.snapshots {
+            recorder.add_snapshot(monotonic_tick, snapshot.clone());
+        }
+        for (&monotonic_tick, events) in &self.events {
+            for event in events {
+                recorder.add_event(monotonic_tick, event.clone());
+            }
+        }
+        // dropping the recorder writes the remaining chunks
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::DemoEvent;
+
+    use super::DemoReplayBuffer;
+
+    #[test]
+    fn keeps_the_last_ticks() {
+        let mut replay = DemoReplayBuffer::default();
+        for tick in 0..100 {
+            replay.add_event(tick, DemoEvent::Vote(None));
+            replay.add_snapshot(tick, tick.to_le_bytes().to_vec(), 10);
+        }
+
+        assert_eq!(
+            replay.snapshots.keys().copied().collect::<Vec<_>>(),
+            (89..100).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            replay.events.keys().copied().collect::<Vec<_>>(),
+            (89..100).collect::<Vec<_>>()
+        );
+
+        replay.clear();
+        assert!(replay.snapshots.is_empty() && replay.events.is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use ring::{
+    digest,
+    rand::{SecureRandom, SystemRandom},
+    signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519},
+};
+use serde::{Deserialize, Serialize};
+
+/// The extension of the file next to a demo that contains its signature,
+/// see [`DemoSignature`].
+pub const DEMO_SIGNATURE_EXTENSION: &str = "twdemo.sig";
+
+/// The path of the signature of the demo at `demo_path`.
+pub fn signature_path(demo_path: &Path) -> PathBuf {
+    demo_path.with_extension(DEMO_SIGNATURE_EXTENSION)
+}
+
+/// The Ed25519 key a server signs its demos with.
+///
+/// It must be kept over restarts (see [`DemoSigningKey::to_file`]),
+/// so that all demos of a server are verifiable with the same public key.
+#[derive(Clone)]
+pub struct DemoSigningKey {
+    seed: [u8; 32],
+}
+
+impl std::fmt::Debug for DemoSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DemoSigningKey").finish_non_exhaustive()
+    }
+}
+
+impl DemoSigningKey {
+    /// `seed` is the private key.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self { seed }
+    }
+
+    /// Creates a new random key.
+    pub fn generate() -> anyhow::Result<Self> {
+        let mut seed = [0; 32];
+        SystemRandom::new()
+            .fill(&mut seed)
+            .map_err(|_| anyhow!("could not generate a demo signing key"))?;
+        Ok(Self { seed })
+    }
+
+    pub fn to_file(&self) -> Vec<u8> {
+        self.seed.to_vec()
+    }
+
+    pub fn from_file(file: &[u8]) -> anyhow::Result<Self> {
+        Ok(Self {
+            seed: file
+                .try_into()
+                .map_err(|_| anyhow!("the demo signing key file is invalid"))?,
+        })
+    }
+
+    fn key_pair(&self) -> anyhow::Result<Ed25519KeyPair> {
+        Ed25519KeyPair::from_seed_unchecked(&self.seed)
+            .map_err(|_| anyhow!("invalid demo signing key"))
+    }
+
+    /// The public key that verifies the demos signed with this key.
+    pub fn public_key(&self) -> anyhow::Result<[u8; 32]> {
+        Ok(self.key_pair()?.public_key().as_ref().try_into()?)
+    }
+}
+
+/// Proves that a demo was recorded by the server with the
+/// [`DemoSignature::public_key`] and was not modified afterwards,
+/// e.g. to settle disputes in tournaments.
+///
+/// It's stored next to the demo (see [`signature_path`]) instead of inside it,
+/// because the signature covers the whole demo file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemoSignature {
+    /// Ed25519 public key of the server that recorded the demo
+    pub public_key: [u8; 32],
+    /// Ed25519 signature of the SHA256 of the whole demo file
+    pub signature: Vec<u8>,
+}
+
+impl DemoSignature {
+    fn demo_digest(demo_file: &[u8]) -> digest::Digest {
+        digest::digest(&digest::SHA256, demo_file)
+    }
+
+    /// Signs the finished demo file.
+    pub fn sign(key: &DemoSigningKey, demo_file: &[u8]) -> anyhow::Result<Self> {
+        let key_pair = key.key_pair()?;
+        Ok(Self {
+            public_key: key_pair.public_key().as_ref().try_into()?,
+            signature: key_pair
+                .sign(Self::demo_digest(demo_file).as_ref())
+                .as_ref()
+                .to_vec(),
+        })
+    }
+
+    /// Verifies that the demo file was signed by the server with the given
+    /// public key and that it was not modified since.
+    pub fn verify(&self, demo_file: &[u8], server_public_key: &[u8; 32]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.public_key == *server_public_key,
+            "the demo was signed by a different server."
+        );
+        UnparsedPublicKey::new(&ED25519, &self.public_key)
+            .verify(Self::demo_digest(demo_file).as_ref(), &self.signature)
+            .map_err(|_| anyhow!("the signature does not match, the demo was modified."))
+    }
+
+    pub fn to_file(&self) -> anyhow::Result<Vec<u8>> {
+        Ok(bincode::serde::encode_to_vec(
+            self,
+            bincode::config::standard(),
+        )?)
+    }
+
+    pub fn from_file(file: &[u8]) -> anyhow::Result<Self> {
+        Ok(bincode::serde::decode_from_slice(file, bincode::config::standard())?.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DemoSignature, DemoSigningKey};
+
+    #[test]
+    fn sign_and_verify() {
+        let key = DemoSigningKey::generate().unwrap();
+        let public_key = key.public_key().unwrap();
+        let demo = b"a demo file".to_vec();
+
+        let signature = DemoSignature::sign(&key, &demo).unwrap();
+        let signature = DemoSignature::from_file(&signature.to_file().unwrap()).unwrap();
+        assert!(signature.verify(&demo, &public_key).is_ok());
+
+        // the key is the same after a restart
+        let key = DemoSigningKey::from_file(&key.to_file()).unwrap();
+        assert_eq!(key.public_key().unwrap(), public_key);
+        assert!(DemoSigningKey::from_file(&[0; 31]).is_err());
+    }
+
+    #[test]
+    fn detect_tampering() {
+        let key = DemoSigningKey::generate().unwrap();
+        let public_key = key.public_key().unwrap();
+        let demo = b"a demo file".to_vec();
+        let signature = DemoSignature::sign(&key, &demo).unwrap();
+
+        let mut modified_demo = demo.clone();
+        modified_demo[0] ^= 1;
+        assert!(signature.verify(&modified_demo, &public_key).is_err());
+
+        let mut modified_signature = signature.clone();
+        modified_signature.signature[0] ^= 1;
+        assert!(modified_signature.verify(&demo, &public_key).is_err());
+
+        // signed by another server
+        let other_public_key = DemoSigningKey::generate().unwrap().public_key().unwrap();
+        assert!(signature.verify(&demo, &other_public_key).is_err());
+        let mut resigned =
+            DemoSignature::sign(&DemoSigningKey::generate().unwrap(), &demo).unwrap();
+        resigned.public_key = public_key;
+        assert!(resigned.verify(&demo, &public_key).is_err());
+    }
+}
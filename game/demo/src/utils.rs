@@ -1,7 +1,26 @@
-use std::io::Read;
+use std::{collections::BTreeMap, io::Read, ops::RangeInclusive, time::Duration};
 
+use anyhow::anyhow;
+use game_interface::types::game::NonZeroGameTickType;
 use serde::de::DeserializeOwned;
 
+use crate::{
+    encryption::DemoKey, ChunkHeader, DemoChunksInfo, DemoChunksKind, DemoEventKind,
+    DemoEventKinds, DemoEvents, DemoEventsChunkInfo, DemoHeader, DemoHeaderExt, DemoPov, DemoTail,
+};
+
+/// The CRC-32 (IEEE) of the data, see [`ChunkHeader::checksum`].
+pub fn checksum(data: &[u8]) -> u32 {
+    crc32fast::hash(data)
+}
+
+pub fn ticks_to_duration(ticks: u64, ticks_per_second: NonZeroGameTickType) -> Duration {
+    let secs = ticks / ticks_per_second;
+    let nanos =
+        (ticks % ticks_per_second) * (Duration::from_secs(1).as_nanos() as u64 / ticks_per_second);
+    Duration::new(secs, nanos as u32)
+}
+
 pub fn decomp<'a>(v: &[u8], writer: &'a mut Vec<u8>) -> anyhow::Result<&'a [u8]> {
     writer.clear();
     let mut decoder = zstd::Decoder::new(v)?;
@@ -26,3 +45,406 @@ pub fn deser_ex<T: DeserializeOwned>(v: &[u8], fixed_size: bool) -> anyhow::Resu
 pub fn deser<T: DeserializeOwned>(v: &[u8]) -> anyhow::Result<(T, usize)> {
     deser_ex(v, false)
 }
+
+/// The size of the [`DemoHeader`] at the start of every demo file.
+pub fn demo_header_size() -> usize {
+    bincode::serde::encode_to_vec(
+        DemoHeader::default(),
+        bincode::config::standard().with_fixed_int_encoding(),
+    )
+    .map(|header| header.len())
+    .unwrap_or_default()
+}
+
+/// Reads the [`DemoHeader`] at the start of the demo file,
+/// `file` must contain at least [`demo_header_size`] bytes.
+/// Returns the header together with its size.
+pub fn read_demo_header(file: &[u8]) -> anyhow::Result<(DemoHeader, usize)> {
+    deser_ex(file, true)
+}
+
+/// Reads the [`DemoHeaderExt`] that follows the header,
+/// `file` must contain at least [`DemoHeader::size_ext`] bytes.
+pub fn read_demo_header_ext(file: &[u8]) -> anyhow::Result<DemoHeaderExt> {
+    let mut writer: Vec<u8> = Default::default();
+    let (header_ext, _) = deser(decomp(file, &mut writer)?)?;
+    Ok(header_ext)
+}
+
+/// Reads the [`DemoTail`], `file` is everything after the chunks.
+pub fn read_demo_tail(file: &[u8]) -> anyhow::Result<DemoTail> {
+    let mut writer: Vec<u8> = Default::default();
+    let (tail, _) = deser(decomp(file, &mut writer)?)?;
+    Ok(tail)
+}
+
+/// Reads the headers and the tail of a demo file.
+/// Returns them together with the (still compressed) chunks of the demo.
+///
+/// If the demo was never finished (e.g. the recording crashed),
+/// the tail is rebuilt from all chunks that were written completely.
+pub fn read_demo(demo: &[u8]) -> anyhow::Result<(DemoHeader, DemoHeaderExt, &[u8], DemoTail)> {
+    // read header
+    let (mut header, file_off) = read_demo_header(demo)?;
+    let demo = &demo[file_off..];
+
+    // read header ext
+    let header_ext_file = demo
+        .get(0..header.size_ext as usize)
+        .ok_or_else(|| anyhow!("demo is too small to contain the extended header."))?;
+    let header_ext = read_demo_header_ext(header_ext_file)?;
+
+    let demo = &demo[header.size_ext as usize..];
+    let (chunks, tail) = if header.size_chunks == 0 {
+        let (size_chunks, len, tail) = recover_tail(demo, header_ext.ticks_per_second);
+        header.size_chunks = size_chunks as u64;
+        header.len = len;
+        (&demo[0..size_chunks], tail)
+    } else {
+        let chunks = demo
+            .get(0..header.size_chunks as usize)
+            .ok_or_else(|| anyhow!("demo is too small to contain all chunks."))?;
+        let tail = &demo[header.size_chunks as usize..];
+
+        // read tail
+        (chunks, read_demo_tail(tail)?)
+    };
+    anyhow::ensure!(
+        !tail.snapshots_index.is_empty(),
+        "no snapshot index found in demo tail."
+    );
+
+    Ok((header, header_ext, chunks, tail))
+}
+
+/// Reads the group of chunks that starts at `offset`.
+/// Returns its info, the (still compressed) chunks and
+/// the offset of the next group.
+fn read_chunks_group(
+    demo_chunks: &[u8],
+    offset: usize,
+) -> anyhow::Result<(DemoChunksInfo, &[u8], usize)> {
+    let file = demo_chunks
+        .get(offset..)
+        .ok_or_else(|| anyhow!("chunk offset is outside of the demo"))?;
+
+    anyhow::ensure!(
+        file.len() >= std::mem::size_of::<u64>(),
+        "file not huge enough to read u64 for compressed size"
+    );
+    let chunks_size = u64::from_le_bytes(file[0..std::mem::size_of::<u64>()].try_into()?);
+    anyhow::ensure!(chunks_size > 0, "empty chunks are not allowed.");
+    let file = &file[std::mem::size_of::<u64>()..];
+
+    let (info, read_size) = deser_ex::<DemoChunksInfo>(file, true)?;
+    let file = &file[read_size..];
+
+    let chunks = file
+        .get(0..chunks_size as usize)
+        .ok_or_else(|| anyhow!("the chunks are cut off, the demo is incomplete"))?;
+    Ok((
+        info,
+        chunks,
+        offset + std::mem::size_of::<u64>() + read_size + chunks.len(),
+    ))
+}
+
+/// The size of the head of every group of chunks,
+/// that is enough to know the size of the whole group, see [`chunks_group_size`].
+pub fn chunks_group_head_size() -> usize {
+    let info = DemoChunksInfo {
+        kind: DemoChunksKind::Snapshots,
+        pov: 0,
+        first_tick: 0,
+        last_tick: 0,
+    };
+    std::mem::size_of::<u64>()
+        + bincode::serde::encode_to_vec(info, bincode::config::standard().with_fixed_int_encoding())
+            .map(|info| info.len())
+            .unwrap_or_default()
+}
+
+/// The size of the group of chunks that starts with `head`,
+/// which must contain at least [`chunks_group_head_size`] bytes.
+pub fn chunks_group_size(head: &[u8]) -> anyhow::Result<usize> {
+    anyhow::ensure!(
+        head.len() >= std::mem::size_of::<u64>(),
+        "file not huge enough to read u64 for compressed size"
+    );
+    let chunks_size = u64::from_le_bytes(head[0..std::mem::size_of::<u64>()].try_into()?);
+    let (_, read_size) = deser_ex::<DemoChunksInfo>(&head[std::mem::size_of::<u64>()..], true)?;
+    Ok(std::mem::size_of::<u64>() + read_size + chunks_size as usize)
+}
+
+/// Rebuilds the tail of a demo that was never finished from the infos
+/// of all complete groups of chunks.
+/// Returns the size of these chunks & the length of the demo together with the tail.
+fn recover_tail(
+    demo_chunks: &[u8],
+    ticks_per_second: NonZeroGameTickType,
+) -> (usize, Duration, DemoTail) {
+    let mut tail = DemoTail::default();
+    let mut offset = 0;
+    // like the recorder, only snapshots & events count into the length
+    let mut ticks: Option<(u64, u64)> = None;
+    // the last group is usually cut off
+    while let Ok((info, _, next_offset)) = read_chunks_group(demo_chunks, offset) {
+        let index = match info.kind {
+            DemoChunksKind::Snapshots => &mut tail.snapshots_index,
+            DemoChunksKind::PovSnapshots => {
+                let pov = info.pov as usize;
+                while tail.povs.len() <= pov {
+                    tail.povs.push(DemoPov {
+                        name: format!("pov {}", tail.povs.len() + 1),
+                        snapshots_index: Default::default(),
+                    });
+                }
+                &mut tail.povs[pov].snapshots_index
+            }
+            DemoChunksKind::Events => {
+                // the kinds of the events are unknown without reading the chunks
+                tail.events_chunks.insert(
+                    info.first_tick,
+                    DemoEventsChunkInfo {
+                        last_tick: info.last_tick,
+                        kinds: DemoEventKinds::all(),
+                    },
+                );
+                &mut tail.events_index
+            }
+            DemoChunksKind::Inputs => &mut tail.inputs_index,
+        };
+        index.insert(info.first_tick, offset as u64);
+        if matches!(
+            info.kind,
+            DemoChunksKind::Snapshots | DemoChunksKind::Events
+        ) {
+            let (first, last) = ticks.get_or_insert((info.first_tick, info.last_tick));
+            *first = (*first).min(info.first_tick);
+            *last = (*last).max(info.last_tick);
+        }
+        offset = next_offset;
+    }
+    let len = ticks
+        .map(|(first, last)| ticks_to_duration(last - first, ticks_per_second))
+        .unwrap_or_default();
+    (offset, len, tail)
+}
+
+/// Reads all chunks that start at `offset` of the demo's chunk data,
+/// the offset is usually taken from the snapshot or event index of the demo tail.
+///
+/// Fails if any chunk is corrupted, see [`read_chunks_partial`].
+pub fn read_chunks<A: DeserializeOwned>(
+    demo_chunks: &[u8],
+    offset: usize,
+    key: Option<&DemoKey>,
+) -> anyhow::Result<BTreeMap<u64, A>> {
+    let group = demo_chunks
+        .get(offset..)
+        .ok_or_else(|| anyhow!("chunk offset is outside of the demo"))?;
+    let mut res = Default::default();
+    read_chunks_into(group, offset, key, &mut res)?;
+    Ok(res)
+}
+
+/// Same as [`read_chunks`], but keeps all chunks in front of
+/// the first corrupted one.
+/// The chunks after it can not be restored either, since every chunk
+/// only contains the difference to the chunk before.
+///
+/// Returns the error of the corrupted chunk, if any.
+pub fn read_chunks_partial<A: DeserializeOwned>(
+    demo_chunks: &[u8],
+    offset: usize,
+    key: Option<&DemoKey>,
+) -> (BTreeMap<u64, A>, Option<anyhow::Error>) {
+    match demo_chunks.get(offset..) {
+        Some(group) => read_chunks_group_partial(group, offset, key),
+        None => (
+            Default::default(),
+            Some(anyhow!("chunk offset is outside of the demo")),
+        ),
+    }
+}
+
+/// Same as [`read_chunks_partial`], but `group` only has to contain
+/// the group of chunks at `offset`, e.g. when the demo is streamed from its file.
+/// See [`chunks_group_size`].
+pub fn read_chunks_group_partial<A: DeserializeOwned>(
+    group: &[u8],
+    offset: usize,
+    key: Option<&DemoKey>,
+) -> (BTreeMap<u64, A>, Option<anyhow::Error>) {
+    let mut res = Default::default();
+    let err = read_chunks_into(group, offset, key, &mut res).err();
+    (res, err)
+}
+
+/// Reads the chunks of all groups of the index that might contain
+/// monotonic ticks of the range, only the ticks inside of it are kept.
+pub fn read_chunks_in_range<A: DeserializeOwned>(
+    demo_chunks: &[u8],
+    index: &BTreeMap<u64, u64>,
+    key: Option<&DemoKey>,
+    range: RangeInclusive<u64>,
+) -> anyhow::Result<BTreeMap<u64, A>> {
+    // the group that starts before the range might still contain ticks of it
+    let first_tick = index
+        .range(..=*range.start())
+        .next_back()
+        .map(|(&tick, _)| tick)
+        .unwrap_or(*range.start());
+    let mut res = BTreeMap::default();
+    for &offset in index
+        .range(first_tick..=*range.end())
+        .map(|(_, offset)| offset)
+    {
+        res.append(&mut read_chunks(demo_chunks, offset as usize, key)?);
+    }
+    res.retain(|tick, _| range.contains(tick));
+    Ok(res)
+}
+
+/// `group` starts with the group of chunks at `offset`.
+fn read_chunks_into<A: DeserializeOwned>(
+    group: &[u8],
+    offset: usize,
+    key: Option<&DemoKey>,
+    res: &mut BTreeMap<u64, A>,
+) -> anyhow::Result<()> {
+    let (_, file, _) = read_chunks_group(group, 0)?;
+
+    // unpack all chunks
+    let mut data: Vec<u8> = Default::default();
+
+    let mut decrypted: Vec<u8> = Default::default();
+    let file = if let Some(key) = key {
+        decrypted.extend_from_slice(file);
+        &*key.decrypt_chunk(offset as u64, &mut decrypted)?
+    } else {
+        file
+    };
+
+    decomp(file, &mut data)?;
+    let file = data.as_slice();
+
+    // read item count in this chunk
+    let (len, read_size) = deser::<u64>(file)?;
+
+    let mut file = &file[read_size..];
+
+    let mut last_data: Option<Vec<u8>> = None;
+
+    let mut writer: Vec<u8> = Default::default();
+
+    for _ in 0..len {
+        let (header, read_size) = deser::<ChunkHeader>(file)?;
+        file = &file[read_size..];
+
+        let data = if header.size > 0 {
+            let data_slice = file
+                .get(..header.size as usize)
+                .ok_or_else(|| anyhow!("chunk at tick {} is cut off", header.monotonic_tick))?;
+            anyhow::ensure!(
+                checksum(data_slice) == header.checksum,
+                "chunk at tick {} is corrupted",
+                header.monotonic_tick
+            );
+            let res = if let Some(last_data) = &last_data {
+                writer.clear();
+                bin_patch::patch(last_data, data_slice, &mut writer)?;
+                writer.as_slice()
+            } else {
+                decomp(data_slice, &mut writer)?;
+                writer.as_slice()
+            };
+            last_data = Some(res.to_vec());
+            let (data, _) = deser(res)?;
+            file = &file[header.size as usize..];
+            Some(data)
+        } else {
+            None
+        };
+
+        if let Some(data) = data {
+            res.insert(header.monotonic_tick, data);
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether any of the events is of `kind`.
+pub fn has_event_kind(events: &DemoEvents, kind: &DemoEventKind<'_>) -> bool {
+    events.iter().any(|ev| DemoEventKind::of(ev).contains(kind))
+}
+
+/// Finds the first monotonic tick after `tick` that has an event of `kind`.
+/// Only the chunks that might contain such an event are read.
+pub fn find_next_event(
+    demo_chunks: &[u8],
+    tail: &DemoTail,
+    key: Option<&DemoKey>,
+    tick: u64,
+    kind: &DemoEventKind<'_>,
+) -> anyhow::Result<Option<u64>> {
+    find_next_event_with(tail, tick, kind, |offset| {
+        read_chunks(demo_chunks, offset, key)
+    })
+}
+
+/// Same as [`find_next_event`], but the events chunks at an offset
+/// are read by `read_events`.
+pub fn find_next_event_with(
+    tail: &DemoTail,
+    tick: u64,
+    kind: &DemoEventKind<'_>,
+    mut read_events: impl FnMut(usize) -> anyhow::Result<BTreeMap<u64, DemoEvents>>,
+) -> anyhow::Result<Option<u64>> {
+    for (_, offset) in tail.events_chunks_after(tick, kind) {
+        let chunks = read_events(offset as usize)?;
+        if let Some((&found, _)) = chunks
+            .range(tick + 1..)
+            .find(|(_, events)| has_event_kind(events, kind))
+        {
+            return Ok(Some(found));
+        }
+    }
+    Ok(None)
+}
+
+/// Finds the last monotonic tick before `tick` that has an event of `kind`.
+/// Only the chunks that might contain such an event are read.
+pub fn find_prev_event(
+    demo_chunks: &[u8],
+    tail: &DemoTail,
+    key: Option<&DemoKey>,
+    tick: u64,
+    kind: &DemoEventKind<'_>,
+) -> anyhow::Result<Option<u64>> {
+    find_prev_event_with(tail, tick, kind, |offset| {
+        read_chunks(demo_chunks, offset, key)
+    })
+}
+
+/// Same as [`find_prev_event`], but the events chunks at an offset
+/// are read by `read_events`.
+pub fn find_prev_event_with(
+    tail: &DemoTail,
+    tick: u64,
+    kind: &DemoEventKind<'_>,
+    mut read_events: impl FnMut(usize) -> anyhow::Result<BTreeMap<u64, DemoEvents>>,
+) -> anyhow::Result<Option<u64>> {
+    for (_, offset) in tail.events_chunks_before(tick, kind) {
+        let chunks = read_events(offset as usize)?;
+        if let Some((&found, _)) = chunks
+            .range(..tick)
+            .rev()
+            .find(|(_, events)| has_event_kind(events, kind))
+        {
+            return Ok(Some(found));
+        }
+    }
+    Ok(None)
+}
@@ -16,7 +16,10 @@ use sound::sound_mt::SoundMultiThreaded;
 use crate::{
     action_logic::do_action,
     actions::actions::{EditorAction, EditorActionGroup},
-    event::{EditorEvent, EditorEventGenerator, EditorEventOverwriteMap, EditorNetEvent},
+    event::{
+        EditorAnnotationEvent, EditorChatMsg, EditorEvent, EditorEventGenerator,
+        EditorEventOverwriteMap, EditorNetEvent,
+    },
     map::EditorMap,
     network::EditorNetwork,
     notifications::{EditorNotification, EditorNotifications},
@@ -31,6 +34,8 @@ pub struct EditorClient {
 
     notifications: EditorNotifications,
     local_client: bool,
+
+    chat: Vec<EditorChatMsg>,
 }
 
 impl EditorClient {
@@ -56,6 +61,7 @@ impl EditorClient {
             event_generator,
             notifications,
             local_client,
+            chat: Default::default(),
         };
 
         res.network.send(EditorEvent::Auth {
@@ -113,6 +119,14 @@ impl EditorClient {
                         EditorEvent::Map(map) => {
                             res = Some(map);
                         }
+                        EditorEvent::Chat(msg) => {
+                            self.chat.push(msg);
+                        }
+                        EditorEvent::Annotation(ev) => {
+                            if !self.local_client {
+                                ev.apply(&mut map.user.annotations);
+                            }
+                        }
                     },
                     EditorNetEvent::NetworkEvent(ev) => self.network.handle_network_ev(id, ev),
                 }
@@ -132,4 +146,18 @@ impl EditorClient {
     pub fn execute_group(&mut self, action_group: EditorActionGroup) {
         self.network.send(EditorEvent::Action(action_group));
     }
+
+    /// The chat messages of this session, oldest first.
+    pub fn chat(&self) -> &[EditorChatMsg] {
+        &self.chat
+    }
+
+    pub fn send_chat(&mut self, from: String, msg: String) {
+        self.network
+            .send(EditorEvent::Chat(EditorChatMsg { from, msg }));
+    }
+
+    pub fn annotate(&mut self, ev: EditorAnnotationEvent) {
+        self.network.send(EditorEvent::Annotation(ev));
+    }
 }
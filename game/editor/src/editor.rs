@@ -22,6 +22,7 @@ use client_render_base::map::{
         ClientMapBufferPhysicsTileLayer, ClientMapBufferQuadLayer, ClientMapBufferTileLayer,
         SoundLayerSounds,
     },
+    map_pipeline::MapGraphics,
     render_pipe::Camera,
 };
 use config::config::ConfigEngine;
@@ -51,16 +52,13 @@ use image::png::load_png_image;
 use map::{
     map::{
         animations::{AnimBase, AnimPointCurveType, AnimPointPos},
-        config::Config,
         groups::{
             layers::{
                 design::{MapLayer, MapLayerQuad, MapLayerSound, MapLayerTile},
-                physics::{MapLayerPhysics, MapLayerTilePhysicsBase},
-                tiles::{MapTileLayerPhysicsTilesRef, TileBase},
+                physics::MapLayerPhysics,
             },
-            MapGroup, MapGroupAttr, MapGroupAttrClipping, MapGroupPhysicsAttr,
+            MapGroup, MapGroupAttr, MapGroupAttrClipping,
         },
-        metadata::Metadata,
         Map,
     },
     skeleton::{
@@ -103,19 +101,29 @@ use crate::{
         EditorSound, EditorSoundAnimation, EditorSoundLayerProps, EditorTileLayerProps,
         EditorTileLayerPropsSelection, ResourceSelection,
     },
+    map_templates::EditorMapTemplate,
     map_tools::{
         finish_design_quad_layer_buffer, finish_design_tile_layer_buffer,
         finish_physics_layer_buffer, upload_design_quad_layer_buffer,
         upload_design_tile_layer_buffer, upload_physics_layer_buffer,
     },
-    notifications::EditorNotifications,
+    notifications::{EditorNotification, EditorNotifications},
     server::EditorServer,
     tab::EditorTab,
     tools::{
-        quad_layer::{brush::QuadBrush, selection::QuadSelection},
+        collab::{annotations_from_memo, annotations_to_memo, EditorCollab},
+        map_diff::{EditorMapDiff, MapDiff},
+        map_search::EditorMapSearch,
+        prefabs::{EditorPrefab, EditorPrefabContent, EditorPrefabs},
+        quad_layer::{
+            brush::{QuadBrush, QuadBrushQuads},
+            selection::QuadSelection,
+        },
         sound_layer::brush::SoundBrush,
         tile_layer::{
-            auto_mapper::TileLayerAutoMapper, brush::TileBrush, selection::TileSelection,
+            auto_mapper::TileLayerAutoMapper,
+            brush::{TileBrush, TileBrushTiles},
+            selection::TileSelection,
         },
         tool::{
             ActiveTool, ActiveToolQuads, ActiveToolSounds, ActiveToolTiles, ToolQuadLayer,
@@ -140,6 +148,10 @@ pub struct Editor {
     // editor tool
     tools: Tools,
     auto_mapper: TileLayerAutoMapper,
+    prefabs: EditorPrefabs,
+    map_diff: EditorMapDiff,
+    map_search: EditorMapSearch,
+    collab: EditorCollab,
 
     middle_down_pointer_pos: Option<egui::Pos2>,
     current_pointer_pos: egui::Pos2,
@@ -177,6 +189,10 @@ pub struct Editor {
     is_closed: bool,
 }
 
+/// The opacity of all layers except the active one,
+/// if the active layer is isolated.
+const ISOLATED_LAYER_OPACITY: f32 = 0.2;
+
 #[derive(Debug, Clone)]
 struct LayerRect {
     group_clip: Option<MapGroupAttrClipping>,
@@ -298,6 +314,10 @@ impl Editor {
                 active_tool: ActiveTool::Tiles(ActiveToolTiles::Brush),
             },
             auto_mapper: TileLayerAutoMapper::new(io.clone().into(), tp.clone()),
+            prefabs: EditorPrefabs::new(io.clone().into()),
+            map_diff: EditorMapDiff::new(io.clone().into(), tp.clone()),
+            map_search: EditorMapSearch::new(),
+            collab: EditorCollab::new(),
             middle_down_pointer_pos: None,
             current_scroll_delta: Default::default(),
             current_pointer_pos: Default::default(),
@@ -345,6 +365,7 @@ impl Editor {
     pub fn new_map(
         &mut self,
         name: &str,
+        template: EditorMapTemplate,
         server_cert_hash: Option<Hash>,
         server_addr: Option<String>,
         server_password: Option<String>,
@@ -386,94 +407,36 @@ impl Editor {
             server_addr.is_none(),
         );
 
-        let physics_group_attr = MapGroupPhysicsAttr {
-            width: NonZeroU16MinusOne::new(50).unwrap(),
-            height: NonZeroU16MinusOne::new(50).unwrap(),
-        };
-        let game_layer = MapLayerTilePhysicsBase {
-            tiles: vec![TileBase::default(); 50 * 50],
-        };
-        let visuals = {
-            let buffer = self.thread_pool.install(|| {
-                upload_physics_layer_buffer(
-                    &self.graphics_mt,
-                    physics_group_attr.width,
-                    physics_group_attr.height,
-                    MapTileLayerPhysicsTilesRef::Game(&game_layer.tiles),
-                )
-            });
-            finish_physics_layer_buffer(&self.buffer_object_handle, &self.backend_handle, buffer)
-        };
-
-        let scene = self.scene_handle.create(Default::default());
-        let global_sound_listener = scene.sound_listener_handle.create(Default::default());
+        let fs = self.io.fs.clone();
+        let placeholder_files = template.placeholder_files();
+        let (files, errs) = self
+            .io
+            .io_batcher
+            .spawn(async move {
+                let mut files: HashMap<&'static str, Vec<u8>> = Default::default();
+                let mut errs = Vec::new();
+                for path in placeholder_files {
+                    match read_file_editor(&fs, path.as_ref()).await {
+                        Ok(file) => {
+                            files.insert(path, file);
+                        }
+                        Err(err) => errs.push(format!("placeholder {path} is missing: {err}")),
+                    }
+                }
+                Ok((files, errs))
+            })
+            .get_storage()
+            .unwrap_or_default();
+        for err in errs {
+            self.notifications.push(EditorNotification::Warning(err));
+        }
+        let (map, resources) = template.map(&files);
+        let map = self.map_to_editor_map(map, resources);
 
         self.tabs.insert(
             name.into(),
             EditorTab {
-                map: EditorMap {
-                    user: EditorMapProps {
-                        options: Default::default(),
-                        ui_values: Default::default(),
-                        sound_scene: scene,
-                        global_sound_listener,
-                        time: Duration::ZERO,
-                        time_scale: 0,
-                    },
-                    resources: EditorResources {
-                        images: Default::default(),
-                        image_arrays: Default::default(),
-                        sounds: Default::default(),
-                        user: (),
-                    },
-                    animations: EditorAnimations {
-                        pos: Default::default(),
-                        color: Default::default(),
-                        sound: Default::default(),
-                        user: EditorAnimationsProps::default(),
-                    },
-                    groups: EditorGroups {
-                        physics: EditorGroupPhysics {
-                            attr: physics_group_attr,
-                            layers: vec![EditorPhysicsLayer::Game(
-                                MapLayerTilePhysicsBaseSkeleton {
-                                    layer: game_layer,
-                                    user: EditorPhysicsLayerProps {
-                                        visuals,
-                                        attr: EditorCommonGroupOrLayerAttr::default(),
-                                        selected: Default::default(),
-                                        number_extra: Default::default(),
-                                        number_extra_texts: Default::default(),
-                                        context_menu_open: false,
-                                    },
-                                },
-                            )],
-                            user: EditorPhysicsGroupProps::default(),
-                        },
-                        background: Vec::new(),
-                        foreground: Vec::new(),
-                        user: EditorGroupsProps {
-                            pos: Default::default(),
-                            zoom: 1.0,
-                        },
-                    },
-                    config: EditorConfig {
-                        def: Config {
-                            commands: Default::default(),
-                        },
-                        user: (),
-                    },
-                    meta: EditorMetadata {
-                        def: Metadata {
-                            authors: Default::default(),
-                            licenses: Default::default(),
-                            version: Default::default(),
-                            credits: Default::default(),
-                            memo: Default::default(),
-                        },
-                        user: (),
-                    },
-                },
+                map,
                 map_render: RenderMap::new(
                     &self.backend_handle,
                     &self.canvas_handle,
@@ -494,9 +457,12 @@ impl Editor {
         backend_handle: &GraphicsBackendHandle,
         buffer_object_handle: &GraphicsBufferObjectHandle,
         texture_handle: &GraphicsTextureHandle,
-        map: Map,
+        mut map: Map,
         resources: HashMap<Hash, Vec<u8>>,
     ) -> EditorMap {
+        let (memo, annotations) = annotations_from_memo(&map.meta.memo);
+        map.meta.memo = memo;
+
         // load images into VRAM
         let (image_mems, image_array_mems, sound_mems): (Vec<_>, Vec<_>, Vec<_>) =
             tp.install(|| {
@@ -754,6 +720,7 @@ impl Editor {
                 global_sound_listener,
                 time: Duration::ZERO,
                 time_scale: 0,
+                annotations,
             },
             resources: EditorResources {
                 images: image_mems
@@ -1157,7 +1124,10 @@ impl Editor {
 
     pub fn save_map(&mut self, path: &Path) -> Option<IoBatcherTask<()>> {
         if let Some(tab) = self.tabs.get(&self.active_tab) {
-            let map: Map = tab.map.clone().into();
+            let mut map: Map = tab.map.clone().into();
+            if tab.map.user.options.save_annotations_in_memo {
+                map.meta.memo = annotations_to_memo(&map.meta.memo, &tab.map.user.annotations);
+            }
             let tp = self.thread_pool.clone();
             let fs = self.io.fs.clone();
             Some(self.io.io_batcher.spawn(async move {
@@ -1220,6 +1190,16 @@ impl Editor {
         for tab in removed_tabs {
             self.tabs.remove(&tab);
         }
+
+        if self.map_diff.needs_update {
+            self.map_diff.needs_update = false;
+            if let (Some(tab), Some(other)) =
+                (self.tabs.get(&self.active_tab), &self.map_diff.other)
+            {
+                let map: Map = tab.map.clone().into();
+                self.map_diff.diff = Some(MapDiff::new(&map, other));
+            }
+        }
     }
 
     fn render_tile_layer_rect(
@@ -1268,6 +1248,7 @@ impl Editor {
         group: &EditorGroup,
         layer: &EditorLayer,
         as_tile_numbers: Option<&TextureContainer2dArray>,
+        opacity: f32,
         layer_rect: &mut Vec<LayerRect>,
     ) {
         map_render.render_layer(
@@ -1336,6 +1317,7 @@ impl Editor {
                     }
                 }
             },
+            opacity,
         );
 
         if let Some(MapLayerSkeleton::Tile(layer)) = layer.editor_attr().active.then_some(layer) {
@@ -1370,18 +1352,34 @@ impl Editor {
         );
     }
 
+    /// The opacity a layer is rendered with, this is only a view option.
+    fn layer_opacity(map: &EditorMap, attr: &EditorCommonGroupOrLayerAttr) -> f32 {
+        let opacity = attr.opacity as f32 / 100.0;
+        if map.user.options.isolate_active_layer && !attr.active {
+            opacity * ISOLATED_LAYER_OPACITY
+        } else {
+            opacity
+        }
+    }
+
     fn render_design_groups(
         &self,
         map_render: &RenderMap,
         map: &EditorMap,
         groups: &[EditorGroup],
+        is_background: bool,
         tile_numbers_texture: TextureContainer2dArray,
         group_clips: &mut Vec<MapGroupAttrClipping>,
         layer_rect: &mut Vec<LayerRect>,
     ) {
-        for group in groups.iter().filter(|group| !group.editor_attr().hidden) {
-            for layer in group.layers.iter() {
+        for (group_index, group) in groups
+            .iter()
+            .enumerate()
+            .filter(|(_, group)| !group.editor_attr().hidden)
+        {
+            for (layer_index, layer) in group.layers.iter().enumerate() {
                 if !layer.editor_attr().hidden {
+                    let opacity = Self::layer_opacity(map, layer.editor_attr());
                     if map.user.ui_values.animations_panel_open {
                         self.render_design_layer(
                             map_render,
@@ -1390,6 +1388,7 @@ impl Editor {
                             group,
                             layer,
                             None,
+                            opacity,
                             layer_rect,
                         );
                     } else {
@@ -1400,6 +1399,7 @@ impl Editor {
                             group,
                             layer,
                             None,
+                            opacity,
                             layer_rect,
                         );
                     }
@@ -1411,10 +1411,26 @@ impl Editor {
                             group,
                             layer,
                             Some(&tile_numbers_texture),
+                            1.0,
                             layer_rect,
                         );
                     }
                     if let MapLayerSkeleton::Sound(layer) = layer {
+                        let solo = match map.user.ui_values.sound_solo {
+                            Some(solo)
+                                if solo.is_background == is_background
+                                    && solo.group_index == group_index
+                                    && solo.layer_index == layer_index =>
+                            {
+                                Some(solo.sound_index)
+                            }
+                            Some(_) => {
+                                // another sound is played solo
+                                layer.user.sounds.stop_all();
+                                continue;
+                            }
+                            None => None,
+                        };
                         let time = if map.user.ui_values.animations_panel_open {
                             map.user.ui_values.timeline.time()
                         } else {
@@ -1435,6 +1451,7 @@ impl Editor {
                                 zoom: map.groups.user.zoom,
                             },
                             0.3,
+                            solo,
                         );
                     }
                 } else if let MapLayerSkeleton::Sound(layer) = layer {
@@ -1456,6 +1473,7 @@ impl Editor {
         map: &EditorMap,
         layer: &EditorPhysicsLayer,
         as_tile_numbers: Option<&TextureContainer2dArray>,
+        opacity: f32,
     ) {
         let time = if map.user.ui_values.animations_panel_open {
             map.user.ui_values.timeline.time()
@@ -1479,7 +1497,7 @@ impl Editor {
                 (time.as_millis() / (1000 / 50)).max(1) as GameTickType,
                 &map.game_time_info().intra_tick_time,
             ),
-            100,
+            (opacity * 100.0) as u8,
             as_tile_numbers.map(ForcedTexture::TileLayer),
         );
     }
@@ -1500,7 +1518,14 @@ impl Editor {
             .iter()
             .filter(|&layer| !layer.user().attr.hidden)
         {
-            Self::render_physics_layer(entities_container, map_render, map, layer, None);
+            Self::render_physics_layer(
+                entities_container,
+                map_render,
+                map,
+                layer,
+                None,
+                Self::layer_opacity(map, layer.editor_attr()),
+            );
 
             if layer.editor_attr().active && map.user.options.show_tile_numbers {
                 Self::render_physics_layer(
@@ -1509,6 +1534,7 @@ impl Editor {
                     map,
                     layer,
                     Some(&tile_numbers_texture),
+                    1.0,
                 );
             }
 
@@ -1851,6 +1877,27 @@ impl Editor {
             if tab.map.user.ui_values.animations_panel_open {
                 Self::add_fake_anim_point(&mut self.tools, &mut tab.map);
             }
+            // stop playing a sound solo, if it was removed
+            if let Some(solo) = tab.map.user.ui_values.sound_solo {
+                let groups = if solo.is_background {
+                    &tab.map.groups.background
+                } else {
+                    &tab.map.groups.foreground
+                };
+                let exists = groups
+                    .get(solo.group_index)
+                    .and_then(|group| group.layers.get(solo.layer_index))
+                    .is_some_and(|layer| {
+                        matches!(
+                            layer,
+                            MapLayerSkeleton::Sound(layer)
+                                if solo.sound_index < layer.layer.sounds.len()
+                        )
+                    });
+                if !exists {
+                    tab.map.user.ui_values.sound_solo = None;
+                }
+            }
         }
         let active_tab = self.tabs.get(&self.active_tab);
         if let Some(tab) = active_tab {
@@ -1859,8 +1906,12 @@ impl Editor {
                 .get_or_default::<ContainerKey>(&"default".try_into().unwrap())
                 .text_overlay_bottom
                 .clone();
-            // we use sound
+            // we use sound, the listener follows the camera
             tab.map.user.sound_scene.stay_active();
+            tab.map
+                .user
+                .global_sound_listener
+                .update(tab.map.groups.user.pos);
             let mut group_clips: Vec<MapGroupAttrClipping> = Default::default();
             let mut layer_rects: Vec<LayerRect> = Default::default();
             // bg
@@ -1868,6 +1919,7 @@ impl Editor {
                 &tab.map_render,
                 &tab.map,
                 &tab.map.groups.background,
+                true,
                 tile_numbers_texture.clone(),
                 &mut group_clips,
                 &mut layer_rects,
@@ -1886,6 +1938,7 @@ impl Editor {
                 &tab.map_render,
                 &tab.map,
                 &tab.map.groups.foreground,
+                false,
                 tile_numbers_texture,
                 &mut group_clips,
                 &mut layer_rects,
@@ -1913,11 +1966,154 @@ impl Editor {
                     height,
                 );
             }
-            // sound update
-            tab.map
-                .user
-                .global_sound_listener
-                .update(tab.map.groups.user.pos);
+            // map diff overlay
+            self.map_diff
+                .render(&self.canvas_handle, &self.stream_handle, &tab.map);
+        }
+    }
+
+    fn save_prefab(&mut self, name: String) {
+        let content = match &self.tools.active_tool {
+            ActiveTool::Tiles(_) => {
+                self.tools
+                    .tiles
+                    .brush
+                    .brush
+                    .as_ref()
+                    .map(|brush| EditorPrefabContent::Tiles {
+                        w: brush.w,
+                        h: brush.h,
+                        tiles: brush.tiles.clone(),
+                    })
+            }
+            ActiveTool::Quads(_) => {
+                self.tools
+                    .quads
+                    .brush
+                    .brush
+                    .as_ref()
+                    .map(|brush| EditorPrefabContent::Quads {
+                        w: brush.w,
+                        h: brush.h,
+                        quads: brush.quads.clone(),
+                    })
+            }
+            ActiveTool::Sounds(_) => None,
+        };
+        match content {
+            Some(content) => {
+                if let Err(err) = self.prefabs.save(EditorPrefab { name, content }) {
+                    self.notifications.push(EditorNotification::Error(format!(
+                        "failed to save the prefab: {err}"
+                    )));
+                }
+            }
+            None => self.notifications.push(EditorNotification::Warning(
+                "select a tile or quad brush to save it as prefab.".to_string(),
+            )),
+        }
+    }
+
+    fn apply_prefab(&mut self, index: usize) {
+        let (Some(tab), Some(prefab)) = (
+            self.tabs.get(&self.active_tab),
+            self.prefabs
+                .prefabs
+                .get(index)
+                .map(|prefab| prefab.transformed(&self.thread_pool, self.prefabs.transform)),
+        ) else {
+            return;
+        };
+        let Some(layer) = tab.map.active_layer() else {
+            self.notifications.push(EditorNotification::Warning(
+                "select a layer to apply the prefab to.".to_string(),
+            ));
+            return;
+        };
+
+        match &prefab.content {
+            EditorPrefabContent::Tiles { .. } => {
+                let Some((w, h, tiles)) = prefab.tiles_for_layer(&tab.map, &layer) else {
+                    self.notifications.push(EditorNotification::Warning(
+                        "the prefab does not fit the active layer.".to_string(),
+                    ));
+                    return;
+                };
+                let texture = match &layer {
+                    EditorLayerUnionRef::Physics { .. } => self
+                        .entities_container
+                        .get_or_default::<ContainerKey>(&"default".try_into().unwrap())
+                        // TODO:
+                        .get_or_default("ddnet")
+                        .clone(),
+                    EditorLayerUnionRef::Design { layer, .. } => {
+                        let EditorLayer::Tile(layer) = layer else {
+                            return;
+                        };
+                        layer
+                            .layer
+                            .attr
+                            .image_array
+                            .map(|image| tab.map.resources.image_arrays[image].user.user.clone())
+                            .unwrap_or_else(|| self.fake_texture_array.clone())
+                    }
+                };
+
+                let render = TileBrush::create_brush_visual(
+                    &self.thread_pool,
+                    &self.graphics_mt,
+                    &self.buffer_object_handle,
+                    &self.backend_handle,
+                    w,
+                    h,
+                    &tiles,
+                );
+                self.tools.tiles.brush.brush = Some(TileBrushTiles {
+                    tiles,
+                    w,
+                    h,
+                    negative_offset: Default::default(),
+                    negative_offsetf: Default::default(),
+                    render,
+                    map_render: MapGraphics::new(&self.backend_handle),
+                    texture,
+                });
+                self.tools.active_tool = ActiveTool::Tiles(ActiveToolTiles::Brush);
+            }
+            EditorPrefabContent::Quads { w, h, quads } => {
+                let EditorLayerUnionRef::Design {
+                    layer: EditorLayer::Quad(layer),
+                    ..
+                } = &layer
+                else {
+                    self.notifications.push(EditorNotification::Warning(
+                        "quad prefabs can only be applied to quad layers.".to_string(),
+                    ));
+                    return;
+                };
+
+                let buffer =
+                    upload_design_quad_layer_buffer(&self.graphics_mt, &layer.layer.attr, quads);
+                let render = finish_design_quad_layer_buffer(
+                    &self.buffer_object_handle,
+                    &self.backend_handle,
+                    buffer,
+                );
+                self.tools.quads.brush.brush = Some(QuadBrushQuads {
+                    quads: quads.clone(),
+                    w: *w,
+                    h: *h,
+                    render,
+                    map_render: MapGraphics::new(&self.backend_handle),
+                    texture: layer
+                        .layer
+                        .attr
+                        .image
+                        .map(|image| tab.map.resources.images[image].user.user.clone())
+                        .unwrap_or_else(|| self.fake_texture.clone()),
+                });
+                self.tools.active_tool = ActiveTool::Quads(ActiveToolQuads::Brush);
+            }
         }
     }
 
@@ -1946,6 +2142,10 @@ impl Editor {
             canvas_size: &mut ui_canvas,
             tools: &mut self.tools,
             auto_mapper: &mut self.auto_mapper,
+            prefabs: &mut self.prefabs,
+            map_diff: &mut self.map_diff,
+            map_search: &mut self.map_search,
+            collab: &mut self.collab,
             io: &self.io,
         });
 
@@ -1956,6 +2156,9 @@ impl Editor {
                 EditorUiEvent::SaveFile { name } => {
                     let _ = self.save_map(&name);
                 }
+                EditorUiEvent::NewMap { name, template } => {
+                    self.new_map(&name, template, None, None, None)
+                }
                 EditorUiEvent::HostMap(host_map) => {
                     let EditorUiEventHostMap {
                         map_path,
@@ -1979,6 +2182,7 @@ impl Editor {
                     password,
                 } => self.new_map(
                     "loading",
+                    EditorMapTemplate::Empty,
                     Some(
                         (0..cert_hash.len())
                             .step_by(2)
@@ -1990,9 +2194,16 @@ impl Editor {
                     Some(ip_port),
                     Some(password),
                 ),
+                EditorUiEvent::SavePrefab { name } => self.save_prefab(name),
+                EditorUiEvent::ApplyPrefab { index } => self.apply_prefab(index),
                 EditorUiEvent::Close => self.is_closed = true,
             }
         }
+        for err in self.prefabs.take_io_errors() {
+            self.notifications.push(EditorNotification::Error(format!(
+                "failed to write the prefab library: {err}"
+            )));
+        }
         (unused_rect, input_state, ui_canvas, egui_output)
     }
 }
@@ -21,7 +21,10 @@ use ui_base::{
 
 use crate::{
     tab::EditorTab,
-    tools::{tile_layer::auto_mapper::TileLayerAutoMapper, tool::Tools},
+    tools::{
+        collab::EditorCollab, map_diff::EditorMapDiff, map_search::EditorMapSearch,
+        prefabs::EditorPrefabs, tile_layer::auto_mapper::TileLayerAutoMapper, tool::Tools,
+    },
     ui::{
         page::EditorUi,
         user_data::{EditorMenuDialogMode, EditorUiEvent, UserData},
@@ -40,6 +43,10 @@ pub struct EditorUiRenderPipe<'a> {
     pub canvas_size: &'a mut Option<UiCanvasSize>,
     pub tools: &'a mut Tools,
     pub auto_mapper: &'a mut TileLayerAutoMapper,
+    pub prefabs: &'a mut EditorPrefabs,
+    pub map_diff: &'a mut EditorMapDiff,
+    pub map_search: &'a mut EditorMapSearch,
+    pub collab: &'a mut EditorCollab,
     pub io: &'a Io,
 }
 
@@ -108,6 +115,10 @@ impl EditorUiRender {
                     tools: pipe.tools,
 
                     auto_mapper: pipe.auto_mapper,
+                    prefabs: pipe.prefabs,
+                    map_diff: pipe.map_diff,
+                    map_search: pipe.map_search,
+                    collab: pipe.collab,
 
                     pointer_is_used: &mut needs_pointer,
                     io: pipe.io,
@@ -13,7 +13,7 @@ use network::network::{
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 
-use crate::actions::actions::EditorActionGroup;
+use crate::{actions::actions::EditorActionGroup, map::EditorAnnotation};
 
 /// An editor command is the way the user expresses to
 /// issue a certain state change.
@@ -34,6 +34,34 @@ pub struct EditorEventOverwriteMap {
     pub resources: HashMap<Hash, Vec<u8>>,
 }
 
+/// A chat message between the mappers of a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorChatMsg {
+    pub from: String,
+    pub msg: String,
+}
+
+/// Changes to the annotations of the map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EditorAnnotationEvent {
+    Add(EditorAnnotation),
+    /// removes the first annotation that equals this one
+    Remove(EditorAnnotation),
+}
+
+impl EditorAnnotationEvent {
+    pub fn apply(self, annotations: &mut Vec<EditorAnnotation>) {
+        match self {
+            Self::Add(annotation) => annotations.push(annotation),
+            Self::Remove(annotation) => {
+                if let Some(index) = annotations.iter().position(|a| *a == annotation) {
+                    annotations.remove(index);
+                }
+            }
+        }
+    }
+}
+
 /// editor events are a collection of either actions or commands
 #[derive(Debug, Serialize, Deserialize)]
 pub enum EditorEvent {
@@ -46,6 +74,8 @@ pub enum EditorEvent {
         is_local_client: bool,
     },
     Map(EditorEventOverwriteMap),
+    Chat(EditorChatMsg),
+    Annotation(EditorAnnotationEvent),
 }
 
 pub enum EditorNetEvent {
@@ -147,5 +147,36 @@ pub const TEXT_TILE_BRUSH_MIRROR: &str =
 "\
 # Tile brush mirror\n\
 \n\
-Mirrors the tile brush horizontal or vertically.
+Mirrors the tile brush horizontal or vertically.  \n\
+If tiles are selected, the selected tiles are mirrored instead.  \n\
+Physics tiles without a direction keep their flags, speedups change their angle.
+";
+
+pub const TEXT_TILE_BRUSH_FLAGS: &str = 
+"\
+# Paint tile flags\n\
+\n\
+If enabled, the tile brush only paints the rotation & mirror flags of its tiles \
+onto the existing tiles of the layer, the tile indices are not changed.  \n\
+Air tiles are skipped and physics tiles only receive the flags if they have a direction.
+";
+
+pub const TEXT_TILE_BRUSH_FILL: &str = 
+"\
+# Tile brush fill\n\
+\n\
+Decides how the rect of a shift + drag is filled with the tiles of the brush:\n\
+- __Repeat__: the brush is repeated over the rect.\n\
+- __Gradient__: blends from the first to the last tile of the brush (e.g. two tiles next to each other), \
+dithered horizontally or vertically.\n\
+- __Noise__: scatters random tiles of the brush, the density decides how many tiles are replaced. \
+The seed changes after every fill.
+";
+
+pub const TEXT_TILE_SELECTION_ROTATE: &str = 
+"\
+# Rotate selection\n\
+\n\
+Rotates the selected tiles by 90° around the top left corner of the selection.  \n\
+The selection is updated to the rotated area, tiles outside of the layer are cut off.
 ";
@@ -10,6 +10,7 @@ pub mod event;
 pub mod explain;
 pub mod fs;
 pub mod map;
+pub mod map_templates;
 pub mod map_tools;
 pub mod network;
 pub mod notifications;
@@ -50,6 +50,7 @@ use map::{
     types::NonZeroU16MinusOne,
 };
 use math::math::vector::{ffixed, fvec2, vec2};
+use serde::{Deserialize, Serialize};
 use sound::{scene_object::SceneObject, sound_listener::SoundListener, sound_object::SoundObject};
 
 pub trait EditorCommonLayerOrGroupAttrInterface {
@@ -61,11 +62,24 @@ pub trait EditorDesignLayerInterface {
     fn is_selected(&self) -> bool;
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct EditorCommonGroupOrLayerAttr {
     pub hidden: bool,
     // active layer/group, e.g. a brush on a active tile layer would have effect
     pub active: bool,
+    /// opacity in percent the layer is rendered with in the editor,
+    /// only a view option, it's not saved to the map
+    pub opacity: u8,
+}
+
+impl Default for EditorCommonGroupOrLayerAttr {
+    fn default() -> Self {
+        Self {
+            hidden: false,
+            active: false,
+            opacity: 100,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -551,6 +565,16 @@ pub enum EditorGroupPanelTab {
     Sounds(EditorGroupPanelResources),
 }
 
+/// A sound that is played exclusively,
+/// so its falloff can be tuned without other sounds interfering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditorSoundSolo {
+    pub is_background: bool,
+    pub group_index: usize,
+    pub layer_index: usize,
+    pub sound_index: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct EditorMapPropsUiValues {
     pub groups_panel: EditorMapPropsUiWindow,
@@ -562,6 +586,8 @@ pub struct EditorMapPropsUiValues {
     pub resource_selector: EditorMapPropsUiWindow,
     pub quad_attr: EditorMapPropsUiWindow,
     pub sound_attr: EditorMapPropsUiWindow,
+    /// if set, only this sound is played
+    pub sound_solo: Option<EditorSoundSolo>,
     pub timeline: Timeline,
 }
 
@@ -587,6 +613,7 @@ impl Default for EditorMapPropsUiValues {
             resource_selector: Default::default(),
             quad_attr: Default::default(),
             sound_attr: Default::default(),
+            sound_solo: None,
             timeline: Timeline::new(),
         }
     }
@@ -599,6 +626,22 @@ pub struct EditorGlobalOptions {
     pub no_animations_with_properties: bool,
     /// show tile numbers for the current active tile layer
     pub show_tile_numbers: bool,
+    /// dim all layers except the active one
+    pub isolate_active_layer: bool,
+    /// don't render the annotation pins in the world
+    pub hide_annotations: bool,
+    /// save the annotations into the memo of the map's meta data
+    pub save_annotations_in_memo: bool,
+}
+
+/// A note attached to a position of the map,
+/// e.g. a TODO marker for the other mappers of the session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditorAnnotation {
+    /// position in the world, in tiles
+    pub pos: vec2,
+    pub author: String,
+    pub text: String,
 }
 
 #[derive(Debug, Clone)]
@@ -613,6 +656,9 @@ pub struct EditorMapProps {
     pub time: Duration,
     // the scale how much the time should be progress, 0 = paused, 1 = normal speed etc.
     pub time_scale: u32,
+
+    /// notes of the mappers, synchronized with the other clients
+    pub annotations: Vec<EditorAnnotation>,
 }
 
 pub type EditorMap = MapSkeleton<
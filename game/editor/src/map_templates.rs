@@ -0,0 +1,305 @@
+use std::{collections::HashMap, path::Path};
+
+use base::{hash::Hash, reduced_ascii_str::ReducedAsciiString};
+use map::{
+    map::{
+        animations::Animations,
+        config::Config,
+        groups::{
+            layers::{
+                design::{MapLayer, MapLayerQuad, MapLayerQuadsAttrs, MapLayerTile, Quad},
+                physics::{MapLayerPhysics, MapLayerTilePhysicsBase, MapLayerTilePhysicsTele},
+                tiles::MapTileLayerAttr,
+            },
+            MapGroup, MapGroupAttr, MapGroupPhysics, MapGroupPhysicsAttr, MapGroups,
+        },
+        metadata::Metadata,
+        resources::{MapResourceRef, Resources},
+        Map,
+    },
+    types::NonZeroU16MinusOne,
+};
+use math::math::vector::{ffixed, fvec2, nffixed, nfvec4};
+
+const TEMPLATE_WIDTH: u16 = 50;
+const TEMPLATE_HEIGHT: u16 = 50;
+
+/// The tileset of the scenery layer.
+const PLACEHOLDER_SCENERY: &str = "map/resources/images/\
+    grass_main_35308756635eb77e8fffc815bf65972b7a704667bdacbc738ee6de3b0940e5bc.png";
+/// The tileset of the detail tile layer.
+const PLACEHOLDER_DETAIL_TILES: &str = "map/resources/images/\
+    grass_doodads_b898e37623913b4d67956fb7dd1ddf53ff484cfe274e0ccfae815c7cfe7f9971.png";
+/// The image of the detail quad layer.
+const PLACEHOLDER_DETAIL_QUADS: &str = "map/resources/images/\
+    generic_lamps_e8abf7da7e44f6839439003183ca3710da7217d5b3bb362dd233431e55d19dd5.png";
+
+/// Predefined group & layer structures that a new map can start with.
+///
+/// The layers use placeholder images of the resources that ship with the game,
+/// so the mapper can replace them after the map was created.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EditorMapTemplate {
+    /// Only the game layer.
+    #[default]
+    Empty,
+    /// A background group with a sky quad & a tile layer.
+    Background,
+    /// Game, front & tele layers.
+    Physics,
+    /// A foreground group for detail tiles & quads.
+    ForegroundDetail,
+    /// All templates combined.
+    Full,
+}
+
+impl EditorMapTemplate {
+    pub const ALL: [Self; 5] = [
+        Self::Empty,
+        Self::Background,
+        Self::Physics,
+        Self::ForegroundDetail,
+        Self::Full,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Empty => "Empty",
+            Self::Background => "Background quads",
+            Self::Physics => "Game + front + tele",
+            Self::ForegroundDetail => "Foreground details",
+            Self::Full => "Full",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Empty => "Only a game layer.",
+            Self::Background => {
+                "A static background group with a sky quad and a tile layer for the scenery."
+            }
+            Self::Physics => "The game layer with a front and a tele layer.",
+            Self::ForegroundDetail => {
+                "A foreground group with high detail tile and quad layers for decorations."
+            }
+            Self::Full => "All groups and layers of the other templates.",
+        }
+    }
+
+    fn color(r: u8, g: u8, b: u8) -> nfvec4 {
+        nfvec4::new(
+            nffixed::from_num(r as f32 / 255.0),
+            nffixed::from_num(g as f32 / 255.0),
+            nffixed::from_num(b as f32 / 255.0),
+            nffixed::from_num(1),
+        )
+    }
+
+    /// The files of the placeholder images the template uses,
+    /// they have to be read before creating the map.
+    pub fn placeholder_files(&self) -> Vec<&'static str> {
+        let mut files = Vec::new();
+        if matches!(self, Self::Background | Self::Full) {
+            files.push(PLACEHOLDER_SCENERY);
+        }
+        if matches!(self, Self::ForegroundDetail | Self::Full) {
+            files.push(PLACEHOLDER_DETAIL_TILES);
+            files.push(PLACEHOLDER_DETAIL_QUADS);
+        }
+        files
+    }
+
+    /// Adds the placeholder image to the resources, if its file was read.
+    /// Returns the index of the image.
+    fn placeholder(
+        list: &mut Vec<MapResourceRef>,
+        resource_files: &mut HashMap<Hash, Vec<u8>>,
+        files: &HashMap<&'static str, Vec<u8>>,
+        path: &'static str,
+    ) -> Option<usize> {
+        let file = files.get(path)?;
+        let path = Path::new(path);
+        let (name, hash) = Map::name_and_hash(&path.file_stem()?.to_string_lossy(), file);
+        list.push(MapResourceRef {
+            name: ReducedAsciiString::from_str_autoconvert(&name),
+            blake3_hash: hash,
+            ty: ReducedAsciiString::from_str_autoconvert(&path.extension()?.to_string_lossy()),
+        });
+        resource_files.insert(hash, file.clone());
+        Some(list.len() - 1)
+    }
+
+    fn tile_layer(name: &str, high_detail: bool, image_array: Option<usize>) -> MapLayer {
+        MapLayer::Tile(MapLayerTile {
+            attr: MapTileLayerAttr {
+                width: NonZeroU16MinusOne::new(TEMPLATE_WIDTH).unwrap(),
+                height: NonZeroU16MinusOne::new(TEMPLATE_HEIGHT).unwrap(),
+                color: Self::color(255, 255, 255),
+                high_detail,
+                color_anim: None,
+                color_anim_offset: time::Duration::ZERO,
+                image_array,
+            },
+            tiles: vec![Default::default(); TEMPLATE_WIDTH as usize * TEMPLATE_HEIGHT as usize],
+            name: name.into(),
+        })
+    }
+
+    fn quad_layer(
+        name: &str,
+        quads: Vec<Quad>,
+        high_detail: bool,
+        image: Option<usize>,
+    ) -> MapLayer {
+        MapLayer::Quad(MapLayerQuad {
+            attr: MapLayerQuadsAttrs { image, high_detail },
+            quads,
+            name: name.into(),
+        })
+    }
+
+    /// A quad that covers the whole screen in a group without parallax,
+    /// with a gradient from the top to the bottom color.
+    fn sky_quad() -> Quad {
+        let (w, h) = (25.0, 18.75);
+        let top = Self::color(94, 132, 174);
+        let bottom = Self::color(204, 232, 255);
+        Quad {
+            points: [
+                fvec2::new(ffixed::from_num(-w), ffixed::from_num(-h)),
+                fvec2::new(ffixed::from_num(w), ffixed::from_num(-h)),
+                fvec2::new(ffixed::from_num(-w), ffixed::from_num(h)),
+                fvec2::new(ffixed::from_num(w), ffixed::from_num(h)),
+                fvec2::default(),
+            ],
+            colors: [top, top, bottom, bottom],
+            tex_coords: [
+                fvec2::new(ffixed::from_num(0), ffixed::from_num(0)),
+                fvec2::new(ffixed::from_num(1), ffixed::from_num(0)),
+                fvec2::new(ffixed::from_num(0), ffixed::from_num(1)),
+                fvec2::new(ffixed::from_num(1), ffixed::from_num(1)),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn group(name: &str, parallax: f32, layers: Vec<MapLayer>) -> MapGroup {
+        MapGroup {
+            attr: MapGroupAttr {
+                offset: Default::default(),
+                parallax: fvec2::new(ffixed::from_num(parallax), ffixed::from_num(parallax)),
+                clipping: None,
+            },
+            layers,
+            name: name.into(),
+        }
+    }
+
+    /// Creates the map for this template, `files` are the read
+    /// [`Self::placeholder_files`], missing ones are left out.
+    ///
+    /// Returns the map & the files of its resources by hash.
+    pub fn map(&self, files: &HashMap<&'static str, Vec<u8>>) -> (Map, HashMap<Hash, Vec<u8>>) {
+        let with_background = matches!(self, Self::Background | Self::Full);
+        let with_physics = matches!(self, Self::Physics | Self::Full);
+        let with_foreground = matches!(self, Self::ForegroundDetail | Self::Full);
+
+        let tiles_len = TEMPLATE_WIDTH as usize * TEMPLATE_HEIGHT as usize;
+        let mut physics_layers = vec![MapLayerPhysics::Game(MapLayerTilePhysicsBase {
+            tiles: vec![Default::default(); tiles_len],
+        })];
+        if with_physics {
+            physics_layers.push(MapLayerPhysics::Front(MapLayerTilePhysicsBase {
+                tiles: vec![Default::default(); tiles_len],
+            }));
+            physics_layers.push(MapLayerPhysics::Tele(MapLayerTilePhysicsTele {
+                base: MapLayerTilePhysicsBase {
+                    tiles: vec![Default::default(); tiles_len],
+                },
+                tele_names: Default::default(),
+            }));
+        }
+
+        let mut resource_files = HashMap::default();
+        let mut images = Vec::new();
+        let mut image_arrays = Vec::new();
+
+        let background = if with_background {
+            let scenery = Self::placeholder(
+                &mut image_arrays,
+                &mut resource_files,
+                files,
+                PLACEHOLDER_SCENERY,
+            );
+            vec![
+                Self::group(
+                    "Sky",
+                    0.0,
+                    vec![Self::quad_layer("Sky", vec![Self::sky_quad()], false, None)],
+                ),
+                Self::group(
+                    "Scenery",
+                    100.0,
+                    vec![Self::tile_layer("Scenery", false, scenery)],
+                ),
+            ]
+        } else {
+            Vec::new()
+        };
+        let foreground = if with_foreground {
+            let tiles = Self::placeholder(
+                &mut image_arrays,
+                &mut resource_files,
+                files,
+                PLACEHOLDER_DETAIL_TILES,
+            );
+            let quads = Self::placeholder(
+                &mut images,
+                &mut resource_files,
+                files,
+                PLACEHOLDER_DETAIL_QUADS,
+            );
+            vec![Self::group(
+                "Details",
+                100.0,
+                vec![
+                    Self::tile_layer("Detail tiles", true, tiles),
+                    Self::quad_layer("Detail quads", Vec::new(), true, quads),
+                ],
+            )]
+        } else {
+            Vec::new()
+        };
+
+        let map = Map {
+            resources: Resources {
+                images,
+                image_arrays,
+                sounds: Default::default(),
+            },
+            groups: MapGroups {
+                physics: MapGroupPhysics {
+                    attr: MapGroupPhysicsAttr {
+                        width: NonZeroU16MinusOne::new(TEMPLATE_WIDTH).unwrap(),
+                        height: NonZeroU16MinusOne::new(TEMPLATE_HEIGHT).unwrap(),
+                    },
+                    layers: physics_layers,
+                },
+                background,
+                foreground,
+            },
+            animations: Animations::default(),
+            config: Config {
+                commands: Default::default(),
+            },
+            meta: Metadata {
+                authors: Default::default(),
+                licenses: Default::default(),
+                version: Default::default(),
+                credits: Default::default(),
+                memo: Default::default(),
+            },
+        };
+        (map, resource_files)
+    }
+}
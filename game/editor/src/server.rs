@@ -26,6 +26,7 @@ use crate::{
     event::{EditorEvent, EditorEventGenerator, EditorEventOverwriteMap, EditorNetEvent},
     map::EditorMap,
     network::EditorNetwork,
+    tools::collab::annotations_to_memo,
 };
 
 #[derive(Debug, Default)]
@@ -121,7 +122,11 @@ impl EditorServer {
                                             }))
                                             .collect();
 
-                                        let map: Map = map.clone().into();
+                                        let annotations = map.user.annotations.clone();
+                                        let mut map: Map = map.clone().into();
+                                        // new clients get the annotations through the memo
+                                        map.meta.memo =
+                                            annotations_to_memo(&map.meta.memo, &annotations);
 
                                         let mut map_bytes = Vec::new();
                                         map.write(&mut map_bytes, tp).unwrap();
@@ -203,6 +208,30 @@ impl EditorServer {
                                     EditorEvent::Map { .. } => {
                                         // ignore
                                     }
+                                    EditorEvent::Chat(msg) => {
+                                        self.clients
+                                            .iter()
+                                            .filter(|(_, client)| client.is_authed)
+                                            .for_each(|(id, _)| {
+                                                self.network
+                                                    .send_to(id, EditorEvent::Chat(msg.clone()));
+                                            });
+                                    }
+                                    EditorEvent::Annotation(ev) => {
+                                        ev.clone().apply(&mut map.user.annotations);
+                                        // the local client shares the map with the server
+                                        self.clients
+                                            .iter()
+                                            .filter(|(_, client)| {
+                                                client.is_authed && !client.is_local_client
+                                            })
+                                            .for_each(|(id, _)| {
+                                                self.network.send_to(
+                                                    id,
+                                                    EditorEvent::Annotation(ev.clone()),
+                                                );
+                                            });
+                                    }
                                 }
                             }
                         }
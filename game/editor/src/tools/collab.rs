@@ -0,0 +1,62 @@
+use egui::{vec2 as egui_vec2, Rect};
+
+use crate::map::EditorAnnotation;
+
+/// Separates the annotations from the rest of the memo,
+/// everything after it is owned by the editor.
+const MEMO_ANNOTATIONS_MARKER: &str = "\n[editor annotations]\n";
+
+/// Splits the memo of the map's meta data into the memo
+/// the mappers wrote and the annotations saved by the editor.
+pub fn annotations_from_memo(memo: &str) -> (String, Vec<EditorAnnotation>) {
+    match memo.split_once(MEMO_ANNOTATIONS_MARKER) {
+        Some((memo, annotations)) => (
+            memo.to_string(),
+            serde_json::from_str(annotations).unwrap_or_default(),
+        ),
+        None => (memo.to_string(), Vec::new()),
+    }
+}
+
+/// Appends the annotations to the memo, see [`annotations_from_memo`].
+pub fn annotations_to_memo(memo: &str, annotations: &[EditorAnnotation]) -> String {
+    if annotations.is_empty() {
+        return memo.to_string();
+    }
+    format!(
+        "{memo}{MEMO_ANNOTATIONS_MARKER}{}",
+        serde_json::to_string(annotations).unwrap_or_default()
+    )
+}
+
+/// The chat & annotations window of a (shared) editor session.
+pub struct EditorCollab {
+    /// the name other mappers see
+    pub name: String,
+    pub chat_msg: String,
+    /// text of the next annotation
+    pub annotation_text: String,
+
+    // ui shown
+    pub active: bool,
+    pub window_rect: Rect,
+}
+
+impl Default for EditorCollab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EditorCollab {
+    pub fn new() -> Self {
+        Self {
+            name: "mapper".to_string(),
+            chat_msg: Default::default(),
+            annotation_text: Default::default(),
+
+            active: false,
+            window_rect: Rect::from_min_size(Default::default(), egui_vec2(50.0, 50.0)),
+        }
+    }
+}
@@ -0,0 +1,644 @@
+use std::{path::PathBuf, sync::Arc};
+
+use base_io::{io::IoFileSys, io_batcher::IoBatcherTask};
+use egui::{vec2 as egui_vec2, Rect};
+use graphics::handles::{
+    canvas::canvas::GraphicsCanvasHandle,
+    stream::stream::{GraphicsStreamHandle, QuadStreamHandle},
+    stream_types::StreamedQuad,
+};
+use hiarc::{hi_closure, Hiarc};
+use map::map::{
+    groups::{
+        layers::{
+            design::{MapLayer, Quad, Sound},
+            physics::MapLayerPhysics,
+            tiles::{SpeedupTile, SwitchTile, TeleTile, TileBase, TuneTile},
+        },
+        MapGroup, MapGroupAttr,
+    },
+    resources::MapResourceRef,
+    Map,
+};
+use math::math::vector::{ffixed, fvec2, ubvec4, vec2};
+
+use crate::{fs::read_file_editor, map::EditorMap, tools::utils::render_rect_state};
+
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Eq)]
+pub enum MapDiffChange {
+    /// only exists in the current map
+    Added,
+    /// only exists in the compared map
+    Removed,
+    Changed,
+}
+
+/// A changed tile, quad or sound.
+/// The position is in tiles (as the rest of the map).
+#[derive(Debug, Hiarc, Clone, Copy)]
+pub struct MapDiffItem {
+    pub pos: vec2,
+    pub change: MapDiffChange,
+}
+
+#[derive(Debug, Clone)]
+pub struct MapDiffLayer {
+    pub name: String,
+    /// `Some` if the whole layer only exists in one of the maps
+    pub layer_change: Option<MapDiffChange>,
+    /// tiles are items of size 1x1, quads & sounds are only points
+    pub is_tile_layer: bool,
+    pub items: Vec<MapDiffItem>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MapDiffGroup {
+    pub name: String,
+    /// `Some` if the whole group only exists in one of the maps
+    pub group_change: Option<MapDiffChange>,
+    pub parallax: fvec2,
+    pub offset: fvec2,
+    pub layers: Vec<MapDiffLayer>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MapDiffResource {
+    pub ty: &'static str,
+    pub name: String,
+    pub change: MapDiffChange,
+}
+
+/// The differences between the current map and another version of it.
+#[derive(Debug, Clone, Default)]
+pub struct MapDiff {
+    pub background: Vec<MapDiffGroup>,
+    pub physics: MapDiffGroup,
+    pub foreground: Vec<MapDiffGroup>,
+    pub resources: Vec<MapDiffResource>,
+}
+
+impl Default for MapDiffGroup {
+    fn default() -> Self {
+        Self {
+            name: "Physics".to_string(),
+            group_change: None,
+            parallax: fvec2::new(ffixed::from_num(100.0), ffixed::from_num(100.0)),
+            offset: fvec2::default(),
+            layers: Default::default(),
+        }
+    }
+}
+
+trait DiffTile: Copy {
+    fn base(&self) -> TileBase;
+    fn same(&self, other: &Self) -> bool;
+}
+
+impl DiffTile for TileBase {
+    fn base(&self) -> TileBase {
+        *self
+    }
+    fn same(&self, other: &Self) -> bool {
+        self.index == other.index && self.flags == other.flags
+    }
+}
+
+impl DiffTile for TeleTile {
+    fn base(&self) -> TileBase {
+        self.base
+    }
+    fn same(&self, other: &Self) -> bool {
+        self.base.same(&other.base) && self.number == other.number
+    }
+}
+
+impl DiffTile for SpeedupTile {
+    fn base(&self) -> TileBase {
+        self.base
+    }
+    fn same(&self, other: &Self) -> bool {
+        self.base.same(&other.base)
+            && self.force == other.force
+            && self.max_speed == other.max_speed
+            && self.angle == other.angle
+    }
+}
+
+impl DiffTile for SwitchTile {
+    fn base(&self) -> TileBase {
+        self.base
+    }
+    fn same(&self, other: &Self) -> bool {
+        self.base.same(&other.base) && self.number == other.number && self.delay == other.delay
+    }
+}
+
+impl DiffTile for TuneTile {
+    fn base(&self) -> TileBase {
+        self.base
+    }
+    fn same(&self, other: &Self) -> bool {
+        self.base.same(&other.base) && self.number == other.number
+    }
+}
+
+impl MapDiff {
+    fn diff_tiles<T: DiffTile>(
+        (w, h, tiles): (usize, usize, &[T]),
+        (other_w, other_h, other_tiles): (usize, usize, &[T]),
+    ) -> Vec<MapDiffItem> {
+        let mut res = Vec::new();
+        for y in 0..h.max(other_h) {
+            for x in 0..w.max(other_w) {
+                let tile = (x < w && y < h)
+                    .then(|| tiles[y * w + x])
+                    .filter(|tile| tile.base().index != 0);
+                let other_tile = (x < other_w && y < other_h)
+                    .then(|| other_tiles[y * other_w + x])
+                    .filter(|tile| tile.base().index != 0);
+                let change = match (tile, other_tile) {
+                    (Some(tile), Some(other_tile)) => {
+                        (!tile.same(&other_tile)).then_some(MapDiffChange::Changed)
+                    }
+                    (Some(_), None) => Some(MapDiffChange::Added),
+                    (None, Some(_)) => Some(MapDiffChange::Removed),
+                    (None, None) => None,
+                };
+                if let Some(change) = change {
+                    res.push(MapDiffItem {
+                        pos: vec2::new(x as f32, y as f32),
+                        change,
+                    });
+                }
+            }
+        }
+        res
+    }
+
+    fn diff_items<T: PartialEq>(
+        items: &[T],
+        other_items: &[T],
+        pos: impl Fn(&T) -> vec2,
+    ) -> Vec<MapDiffItem> {
+        (0..items.len().max(other_items.len()))
+            .filter_map(|i| match (items.get(i), other_items.get(i)) {
+                (Some(item), Some(other_item)) => (item != other_item).then(|| MapDiffItem {
+                    pos: pos(item),
+                    change: MapDiffChange::Changed,
+                }),
+                (Some(item), None) => Some(MapDiffItem {
+                    pos: pos(item),
+                    change: MapDiffChange::Added,
+                }),
+                (None, Some(other_item)) => Some(MapDiffItem {
+                    pos: pos(other_item),
+                    change: MapDiffChange::Removed,
+                }),
+                (None, None) => None,
+            })
+            .collect()
+    }
+
+    fn quad_pos(quad: &Quad) -> vec2 {
+        vec2::new(quad.points[4].x.to_num(), quad.points[4].y.to_num())
+    }
+
+    fn sound_pos(sound: &Sound) -> vec2 {
+        vec2::new(sound.pos.x.to_num(), sound.pos.y.to_num())
+    }
+
+    /// all items of a layer, that only exists in one of the maps
+    fn layer_items(layer: &MapLayer, change: MapDiffChange) -> (bool, Vec<MapDiffItem>) {
+        match layer {
+            MapLayer::Abritrary(_) => (false, Vec::new()),
+            MapLayer::Tile(layer) => {
+                let size = (
+                    layer.attr.width.get() as usize,
+                    layer.attr.height.get() as usize,
+                    layer.tiles.as_slice(),
+                );
+                let empty = (0, 0, [].as_slice());
+                let items = match change {
+                    MapDiffChange::Removed => Self::diff_tiles(empty, size),
+                    _ => Self::diff_tiles(size, empty),
+                };
+                (true, items)
+            }
+            MapLayer::Quad(layer) => {
+                let items = match change {
+                    MapDiffChange::Removed => Self::diff_items(&[], &layer.quads, Self::quad_pos),
+                    _ => Self::diff_items(&layer.quads, &[], Self::quad_pos),
+                };
+                (false, items)
+            }
+            MapLayer::Sound(layer) => {
+                let items = match change {
+                    MapDiffChange::Removed => Self::diff_items(&[], &layer.sounds, Self::sound_pos),
+                    _ => Self::diff_items(&layer.sounds, &[], Self::sound_pos),
+                };
+                (false, items)
+            }
+        }
+    }
+
+    fn diff_design_layer(layer: Option<&MapLayer>, other: Option<&MapLayer>) -> MapDiffLayer {
+        match (layer, other) {
+            (Some(MapLayer::Tile(layer)), Some(MapLayer::Tile(other))) => MapDiffLayer {
+                name: layer.name.clone(),
+                layer_change: None,
+                is_tile_layer: true,
+                items: Self::diff_tiles(
+                    (
+                        layer.attr.width.get() as usize,
+                        layer.attr.height.get() as usize,
+                        &layer.tiles,
+                    ),
+                    (
+                        other.attr.width.get() as usize,
+                        other.attr.height.get() as usize,
+                        &other.tiles,
+                    ),
+                ),
+            },
+            (Some(MapLayer::Quad(layer)), Some(MapLayer::Quad(other))) => MapDiffLayer {
+                name: layer.name.clone(),
+                layer_change: None,
+                is_tile_layer: false,
+                items: Self::diff_items(&layer.quads, &other.quads, Self::quad_pos),
+            },
+            (Some(MapLayer::Sound(layer)), Some(MapLayer::Sound(other))) => MapDiffLayer {
+                name: layer.name.clone(),
+                layer_change: None,
+                is_tile_layer: false,
+                items: Self::diff_items(&layer.sounds, &other.sounds, Self::sound_pos),
+            },
+            (Some(layer), other) => {
+                // a different layer type counts as replaced layer
+                let (is_tile_layer, items) = Self::layer_items(layer, MapDiffChange::Added);
+                MapDiffLayer {
+                    name: layer.name().to_string(),
+                    layer_change: Some(if other.is_some() {
+                        MapDiffChange::Changed
+                    } else {
+                        MapDiffChange::Added
+                    }),
+                    is_tile_layer,
+                    items,
+                }
+            }
+            (None, Some(other)) => {
+                let (is_tile_layer, items) = Self::layer_items(other, MapDiffChange::Removed);
+                MapDiffLayer {
+                    name: other.name().to_string(),
+                    layer_change: Some(MapDiffChange::Removed),
+                    is_tile_layer,
+                    items,
+                }
+            }
+            (None, None) => unreachable!("at least one layer must exist"),
+        }
+    }
+
+    fn diff_design_groups(groups: &[MapGroup], other_groups: &[MapGroup]) -> Vec<MapDiffGroup> {
+        let group_diff =
+            |attr: &MapGroupAttr, name: &str, change: Option<MapDiffChange>| MapDiffGroup {
+                name: name.to_string(),
+                group_change: change,
+                parallax: attr.parallax,
+                offset: attr.offset,
+                layers: Default::default(),
+            };
+        (0..groups.len().max(other_groups.len()))
+            .map(|g| {
+                let group = groups.get(g);
+                let other_group = other_groups.get(g);
+                let mut res = match (group, other_group) {
+                    (Some(group), Some(_)) => group_diff(&group.attr, &group.name, None),
+                    (Some(group), None) => {
+                        group_diff(&group.attr, &group.name, Some(MapDiffChange::Added))
+                    }
+                    (None, Some(other_group)) => group_diff(
+                        &other_group.attr,
+                        &other_group.name,
+                        Some(MapDiffChange::Removed),
+                    ),
+                    (None, None) => unreachable!("at least one group must exist"),
+                };
+                let layers = group.map(|g| g.layers.as_slice()).unwrap_or_default();
+                let other_layers = other_group.map(|g| g.layers.as_slice()).unwrap_or_default();
+                res.layers = (0..layers.len().max(other_layers.len()))
+                    .map(|l| Self::diff_design_layer(layers.get(l), other_layers.get(l)))
+                    .collect();
+                res
+            })
+            .collect()
+    }
+
+    fn physics_layer_name(layer: &MapLayerPhysics) -> &'static str {
+        match layer {
+            MapLayerPhysics::Arbitrary(_) => "Arbitrary",
+            MapLayerPhysics::Game(_) => "Game",
+            MapLayerPhysics::Front(_) => "Front",
+            MapLayerPhysics::Tele(_) => "Tele",
+            MapLayerPhysics::Speedup(_) => "Speedup",
+            MapLayerPhysics::Switch(_) => "Switch",
+            MapLayerPhysics::Tune(_) => "Tune",
+        }
+    }
+
+    fn diff_physics_layer(
+        (w, h, layer): (usize, usize, Option<&MapLayerPhysics>),
+        (other_w, other_h, other): (usize, usize, Option<&MapLayerPhysics>),
+    ) -> Vec<MapDiffItem> {
+        fn size<T>(w: usize, h: usize, tiles: Option<&Vec<T>>) -> (usize, usize, &[T]) {
+            match tiles {
+                Some(tiles) => (w, h, tiles.as_slice()),
+                None => (0, 0, &[]),
+            }
+        }
+        macro_rules! diff_tiles {
+            ($variant:ident, $tiles:ident => $get:expr) => {
+                Self::diff_tiles(
+                    size(
+                        w,
+                        h,
+                        match layer {
+                            Some(MapLayerPhysics::$variant($tiles)) => Some($get),
+                            _ => None,
+                        },
+                    ),
+                    size(
+                        other_w,
+                        other_h,
+                        match other {
+                            Some(MapLayerPhysics::$variant($tiles)) => Some($get),
+                            _ => None,
+                        },
+                    ),
+                )
+            };
+        }
+        match layer.or(other) {
+            Some(MapLayerPhysics::Arbitrary(_)) | None => Vec::new(),
+            Some(MapLayerPhysics::Game(_)) => diff_tiles!(Game, l => &l.tiles),
+            Some(MapLayerPhysics::Front(_)) => diff_tiles!(Front, l => &l.tiles),
+            Some(MapLayerPhysics::Tele(_)) => diff_tiles!(Tele, l => &l.base.tiles),
+            Some(MapLayerPhysics::Speedup(_)) => diff_tiles!(Speedup, l => &l.tiles),
+            Some(MapLayerPhysics::Switch(_)) => diff_tiles!(Switch, l => &l.base.tiles),
+            Some(MapLayerPhysics::Tune(_)) => diff_tiles!(Tune, l => &l.base.tiles),
+        }
+    }
+
+    fn find_physics_layer<'a>(
+        layers: &'a [MapLayerPhysics],
+        layer: &MapLayerPhysics,
+    ) -> Option<&'a MapLayerPhysics> {
+        layers
+            .iter()
+            .find(|l| std::mem::discriminant(*l) == std::mem::discriminant(layer))
+    }
+
+    fn diff_physics_group(map: &Map, other: &Map) -> MapDiffGroup {
+        let physics = &map.groups.physics;
+        let other_physics = &other.groups.physics;
+        let (w, h) = (
+            physics.attr.width.get() as usize,
+            physics.attr.height.get() as usize,
+        );
+        let (other_w, other_h) = (
+            other_physics.attr.width.get() as usize,
+            other_physics.attr.height.get() as usize,
+        );
+
+        // physics layers are unique by their type
+        let mut layers: Vec<&MapLayerPhysics> = physics.layers.iter().collect();
+        layers.extend(other_physics.layers.iter().filter(|other_layer| {
+            Self::find_physics_layer(&physics.layers, other_layer).is_none()
+        }));
+
+        MapDiffGroup {
+            layers: layers
+                .into_iter()
+                .map(|layer| {
+                    let cur = Self::find_physics_layer(&physics.layers, layer);
+                    let other = Self::find_physics_layer(&other_physics.layers, layer);
+                    MapDiffLayer {
+                        name: Self::physics_layer_name(layer).to_string(),
+                        layer_change: match (cur, other) {
+                            (Some(_), None) => Some(MapDiffChange::Added),
+                            (None, Some(_)) => Some(MapDiffChange::Removed),
+                            _ => None,
+                        },
+                        is_tile_layer: true,
+                        items: Self::diff_physics_layer((w, h, cur), (other_w, other_h, other)),
+                    }
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn diff_resources(
+        ty: &'static str,
+        resources: &[MapResourceRef],
+        other_resources: &[MapResourceRef],
+        res: &mut Vec<MapDiffResource>,
+    ) {
+        for resource in resources {
+            match other_resources
+                .iter()
+                .find(|other| other.name == resource.name)
+            {
+                Some(other) => {
+                    if other.blake3_hash != resource.blake3_hash {
+                        res.push(MapDiffResource {
+                            ty,
+                            name: resource.name.to_string(),
+                            change: MapDiffChange::Changed,
+                        });
+                    }
+                }
+                None => res.push(MapDiffResource {
+                    ty,
+                    name: resource.name.to_string(),
+                    change: MapDiffChange::Added,
+                }),
+            }
+        }
+        res.extend(
+            other_resources
+                .iter()
+                .filter(|other| !resources.iter().any(|r| r.name == other.name))
+                .map(|other| MapDiffResource {
+                    ty,
+                    name: other.name.to_string(),
+                    change: MapDiffChange::Removed,
+                }),
+        );
+    }
+
+    /// Compares the current `map` with the `other` version of it.
+    pub fn new(map: &Map, other: &Map) -> Self {
+        let mut resources = Vec::new();
+        Self::diff_resources(
+            "image",
+            &map.resources.images,
+            &other.resources.images,
+            &mut resources,
+        );
+        Self::diff_resources(
+            "image array",
+            &map.resources.image_arrays,
+            &other.resources.image_arrays,
+            &mut resources,
+        );
+        Self::diff_resources(
+            "sound",
+            &map.resources.sounds,
+            &other.resources.sounds,
+            &mut resources,
+        );
+
+        Self {
+            background: Self::diff_design_groups(&map.groups.background, &other.groups.background),
+            physics: Self::diff_physics_group(map, other),
+            foreground: Self::diff_design_groups(&map.groups.foreground, &other.groups.foreground),
+            resources,
+        }
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = &MapDiffGroup> {
+        self.background
+            .iter()
+            .chain(std::iter::once(&self.physics))
+            .chain(self.foreground.iter())
+    }
+}
+
+/// Compares the current map with another version of it
+/// (e.g. a backup or the state before a collaborative editing session).
+pub struct EditorMapDiff {
+    pub diff: Option<MapDiff>,
+    /// the map to compare with
+    pub other: Option<Map>,
+    /// the diff should be (re-)calculated against the current map
+    pub needs_update: bool,
+    pub show_overlay: bool,
+
+    // ui shown
+    pub active: bool,
+    pub window_rect: Rect,
+    /// path of the map to compare with
+    pub path: String,
+    pub err: Option<String>,
+
+    load_task: Option<IoBatcherTask<Map>>,
+    io: IoFileSys,
+    tp: Arc<rayon::ThreadPool>,
+}
+
+impl EditorMapDiff {
+    pub fn new(io: IoFileSys, tp: Arc<rayon::ThreadPool>) -> Self {
+        Self {
+            diff: None,
+            other: None,
+            needs_update: false,
+            show_overlay: true,
+
+            active: false,
+            window_rect: Rect::from_min_size(Default::default(), egui_vec2(50.0, 50.0)),
+            path: Default::default(),
+            err: None,
+
+            load_task: None,
+            io,
+            tp,
+        }
+    }
+
+    pub fn load(&mut self) {
+        let fs = self.io.fs.clone();
+        let tp = self.tp.clone();
+        let path = PathBuf::from(&self.path);
+        self.load_task = Some(self.io.io_batcher.spawn(async move {
+            let file = read_file_editor(&fs, &path).await?;
+            Map::read(&file, &tp)
+        }));
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.load_task.is_some()
+    }
+
+    pub fn update(&mut self) {
+        if self
+            .load_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            match self.load_task.take().unwrap().get_storage() {
+                Ok(map) => {
+                    self.other = Some(map);
+                    self.needs_update = true;
+                    self.err = None;
+                }
+                Err(err) => {
+                    self.err = Some(err.to_string());
+                }
+            }
+        }
+    }
+
+    /// Renders the changed tiles, quads & sounds on top of the map.
+    pub fn render(
+        &self,
+        canvas_handle: &GraphicsCanvasHandle,
+        stream_handle: &GraphicsStreamHandle,
+        map: &EditorMap,
+    ) {
+        let Some(diff) = self.diff.as_ref().filter(|_| self.show_overlay) else {
+            return;
+        };
+        for group in diff.groups() {
+            let state = render_rect_state(
+                canvas_handle,
+                map,
+                &vec2::new(group.parallax.x.to_num(), group.parallax.y.to_num()),
+                &vec2::new(group.offset.x.to_num(), group.offset.y.to_num()),
+            );
+            for layer in group.layers.iter().filter(|layer| !layer.items.is_empty()) {
+                let items = layer.items.clone();
+                let size = if layer.is_tile_layer { 1.0 } else { 0.5 };
+                stream_handle.render_quads(
+                    hi_closure!([items: Vec<MapDiffItem>, size: f32], |mut stream_handle: QuadStreamHandle<'_>| -> () {
+                        // quads & sounds are centered around their position
+                        let offset = if size < 1.0 { size / 2.0 } else { 0.0 };
+                        for item in items.iter() {
+                            let color = match item.change {
+                                MapDiffChange::Added => ubvec4::new(0, 255, 0, 100),
+                                MapDiffChange::Removed => ubvec4::new(255, 0, 0, 100),
+                                MapDiffChange::Changed => ubvec4::new(255, 255, 0, 100),
+                            };
+                            stream_handle.add_vertices(
+                                StreamedQuad::default()
+                                    .from_pos_and_size(
+                                        vec2::new(item.pos.x - offset, item.pos.y - offset),
+                                        vec2::new(size, size),
+                                    )
+                                    .color(color)
+                                    .into()
+                            );
+                        }
+                    }),
+                    state,
+                );
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.diff = None;
+        self.other = None;
+        self.needs_update = false;
+    }
+}
@@ -0,0 +1,333 @@
+use egui::{vec2 as egui_vec2, Rect};
+use map::{
+    map::groups::layers::{
+        physics::MapLayerPhysicsRef,
+        tiles::{SpeedupTile, SwitchTile, TeleTile, TileBase, TuneTile},
+    },
+    skeleton::groups::layers::design::MapLayerSkeleton,
+    types::NonZeroU16MinusOne,
+};
+use math::math::vector::vec2;
+
+use crate::{
+    map::{EditorGroup, EditorMap, EditorMapInterface, EditorMapSetLayer},
+    tools::{
+        quad_layer::{brush::QuadSelection, shared::QuadPointerDownPoint},
+        sound_layer::{brush::SoundSelection, shared::SoundPointerDownPoint},
+        tile_layer::selection::TileSelectionRange,
+        tool::{ActiveTool, ActiveToolQuads, ActiveToolSounds, ActiveToolTiles, Tools},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapSearchKind {
+    /// tiles with a given index in physics & design tile layers
+    TileIndex,
+    /// quads of quad layers that use a given image
+    QuadImage,
+    /// sound sources of sound layers that use a given sound
+    SoundResource,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum MapSearchItem {
+    Tile { x: u16, y: u16 },
+    Quad { index: usize },
+    Sound { index: usize },
+}
+
+#[derive(Debug, Clone)]
+pub struct MapSearchResult {
+    pub layer: EditorMapSetLayer,
+    /// group & layer name, as shown in the result list
+    pub layer_name: String,
+    pub item: MapSearchItem,
+    /// the camera position that centers the item,
+    /// respecting the parallax & offset of the group
+    pub camera_pos: vec2,
+}
+
+pub struct EditorMapSearch {
+    pub kind: MapSearchKind,
+    pub tile_index: u8,
+    /// the image (for quads) or sound resource to search for
+    pub resource: Option<usize>,
+    pub results: Vec<MapSearchResult>,
+    /// the result the camera last jumped to
+    pub cur_result: Option<usize>,
+
+    // ui shown
+    pub active: bool,
+    pub window_rect: Rect,
+}
+
+impl Default for EditorMapSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EditorMapSearch {
+    pub fn new() -> Self {
+        Self {
+            kind: MapSearchKind::TileIndex,
+            tile_index: 1,
+            resource: None,
+            results: Default::default(),
+            cur_result: None,
+
+            active: false,
+            window_rect: Rect::from_min_size(Default::default(), egui_vec2(50.0, 50.0)),
+        }
+    }
+
+    fn camera_pos(pos: vec2, group: Option<&EditorGroup>) -> vec2 {
+        let Some(group) = group else {
+            return pos;
+        };
+        // inverse of the canvas mapping:
+        // center = offset + camera * parallax / 100
+        let offset = vec2::new(group.attr.offset.x.to_num(), group.attr.offset.y.to_num());
+        let parallax = vec2::new(
+            group.attr.parallax.x.to_num(),
+            group.attr.parallax.y.to_num(),
+        );
+        let axis = |pos: f32, offset: f32, parallax: f32| {
+            if parallax == 0.0 {
+                // the group does not move with the camera at all
+                pos
+            } else {
+                (pos - offset) * 100.0 / parallax
+            }
+        };
+        vec2::new(
+            axis(pos.x, offset.x, parallax.x),
+            axis(pos.y, offset.y, parallax.y),
+        )
+    }
+
+    fn find_tiles<T>(
+        width: usize,
+        tiles: &[T],
+        index: u8,
+        base: impl Fn(&T) -> TileBase,
+    ) -> Vec<(u16, u16)> {
+        tiles
+            .iter()
+            .enumerate()
+            .filter(|&(_, tile)| base(tile).index == index)
+            .map(|(i, _)| ((i % width) as u16, (i / width) as u16))
+            .collect()
+    }
+
+    fn search_design_groups(
+        &mut self,
+        groups: &[EditorGroup],
+        is_background: bool,
+        groups_name: &str,
+    ) {
+        for (g, group) in groups.iter().enumerate() {
+            for (l, layer) in group.layers.iter().enumerate() {
+                let set_layer = if is_background {
+                    EditorMapSetLayer::Background { group: g, layer: l }
+                } else {
+                    EditorMapSetLayer::Foreground { group: g, layer: l }
+                };
+                let layer_name =
+                    format!("{groups_name} #{g} {} / #{l} {}", group.name, layer.name());
+                let mut push = |item: MapSearchItem, pos: vec2| {
+                    self.results.push(MapSearchResult {
+                        layer: set_layer,
+                        layer_name: layer_name.clone(),
+                        item,
+                        camera_pos: Self::camera_pos(pos, Some(group)),
+                    })
+                };
+                match (self.kind, layer) {
+                    (MapSearchKind::TileIndex, MapLayerSkeleton::Tile(layer)) => {
+                        let width = layer.layer.attr.width.get() as usize;
+                        for (x, y) in
+                            Self::find_tiles(width, &layer.layer.tiles, self.tile_index, |t| *t)
+                        {
+                            push(
+                                MapSearchItem::Tile { x, y },
+                                vec2::new(x as f32 + 0.5, y as f32 + 0.5),
+                            );
+                        }
+                    }
+                    (MapSearchKind::QuadImage, MapLayerSkeleton::Quad(layer))
+                        if layer.layer.attr.image == self.resource =>
+                    {
+                        for (index, quad) in layer.layer.quads.iter().enumerate() {
+                            push(
+                                MapSearchItem::Quad { index },
+                                vec2::new(quad.points[4].x.to_num(), quad.points[4].y.to_num()),
+                            );
+                        }
+                    }
+                    (MapSearchKind::SoundResource, MapLayerSkeleton::Sound(layer))
+                        if layer.layer.attr.sound == self.resource =>
+                    {
+                        for (index, sound) in layer.layer.sounds.iter().enumerate() {
+                            push(
+                                MapSearchItem::Sound { index },
+                                vec2::new(sound.pos.x.to_num(), sound.pos.y.to_num()),
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn search_physics(&mut self, map: &EditorMap) {
+        let physics = &map.groups.physics;
+        let width = physics.attr.width.get() as usize;
+        for (l, layer) in physics.layers.iter().enumerate() {
+            let index = self.tile_index;
+            let (name, tiles) = match layer.layer_ref() {
+                MapLayerPhysicsRef::Arbitrary(_) => continue,
+                MapLayerPhysicsRef::Game(layer) => (
+                    "Game",
+                    Self::find_tiles(width, &layer.tiles, index, |t: &TileBase| *t),
+                ),
+                MapLayerPhysicsRef::Front(layer) => (
+                    "Front",
+                    Self::find_tiles(width, &layer.tiles, index, |t: &TileBase| *t),
+                ),
+                MapLayerPhysicsRef::Tele(layer) => (
+                    "Tele",
+                    Self::find_tiles(width, &layer.base.tiles, index, |t: &TeleTile| t.base),
+                ),
+                MapLayerPhysicsRef::Speedup(layer) => (
+                    "Speedup",
+                    Self::find_tiles(width, &layer.tiles, index, |t: &SpeedupTile| t.base),
+                ),
+                MapLayerPhysicsRef::Switch(layer) => (
+                    "Switch",
+                    Self::find_tiles(width, &layer.base.tiles, index, |t: &SwitchTile| t.base),
+                ),
+                MapLayerPhysicsRef::Tune(layer) => (
+                    "Tune",
+                    Self::find_tiles(width, &layer.base.tiles, index, |t: &TuneTile| t.base),
+                ),
+            };
+            self.results
+                .extend(tiles.into_iter().map(|(x, y)| MapSearchResult {
+                    layer: EditorMapSetLayer::Physics { layer: l },
+                    layer_name: format!("Physics / {name}"),
+                    item: MapSearchItem::Tile { x, y },
+                    camera_pos: Self::camera_pos(vec2::new(x as f32 + 0.5, y as f32 + 0.5), None),
+                }));
+        }
+    }
+
+    /// Searches the whole map for the current query.
+    pub fn search(&mut self, map: &EditorMap) {
+        self.results.clear();
+        self.cur_result = None;
+
+        self.search_design_groups(&map.groups.background, true, "Background");
+        if self.kind == MapSearchKind::TileIndex {
+            self.search_physics(map);
+        }
+        self.search_design_groups(&map.groups.foreground, false, "Foreground");
+    }
+
+    /// Moves the camera to the result & selects it.
+    ///
+    /// Does nothing if the map changed in a way that
+    /// the result does not exist anymore.
+    pub fn jump_to(&mut self, index: usize, map: &mut EditorMap, tools: &mut Tools) {
+        let Some(result) = self.results.get(index) else {
+            return;
+        };
+        let (set_layer, camera_pos) = (result.layer, result.camera_pos);
+        let design_layer = |is_background: bool, group: usize, layer: usize| {
+            let groups = if is_background {
+                &map.groups.background
+            } else {
+                &map.groups.foreground
+            };
+            groups
+                .get(group)
+                .and_then(|g| g.layers.get(layer))
+                .map(|layer| (is_background, group, layer))
+        };
+        let (layer_index, design) = match set_layer {
+            EditorMapSetLayer::Physics { layer } => {
+                if layer >= map.groups.physics.layers.len() {
+                    return;
+                }
+                (layer, None)
+            }
+            EditorMapSetLayer::Background { group, layer } => {
+                let Some(design) = design_layer(true, group, layer) else {
+                    return;
+                };
+                (layer, Some(design))
+            }
+            EditorMapSetLayer::Foreground { group, layer } => {
+                let Some(design) = design_layer(false, group, layer) else {
+                    return;
+                };
+                (layer, Some(design))
+            }
+        };
+
+        match (result.item, design) {
+            (MapSearchItem::Tile { x, y }, _) => {
+                tools.tiles.selection.range = Some(TileSelectionRange {
+                    x,
+                    y,
+                    w: NonZeroU16MinusOne::new(1).unwrap(),
+                    h: NonZeroU16MinusOne::new(1).unwrap(),
+                });
+                tools.active_tool = ActiveTool::Tiles(ActiveToolTiles::Selection);
+            }
+            (
+                MapSearchItem::Quad { index },
+                Some((is_background, group, MapLayerSkeleton::Quad(layer))),
+            ) => {
+                let Some(quad) = layer.layer.quads.get(index) else {
+                    return;
+                };
+                tools.quads.brush.last_selection = Some(QuadSelection {
+                    is_background,
+                    group,
+                    layer: layer_index,
+                    quad_index: index,
+                    quad: quad.clone(),
+                    point: QuadPointerDownPoint::Center,
+                    cursor_in_world_pos: None,
+                });
+                tools.active_tool = ActiveTool::Quads(ActiveToolQuads::Brush);
+            }
+            (
+                MapSearchItem::Sound { index },
+                Some((is_background, group, MapLayerSkeleton::Sound(layer))),
+            ) => {
+                let Some(sound) = layer.layer.sounds.get(index) else {
+                    return;
+                };
+                tools.sounds.brush.last_selection = Some(SoundSelection {
+                    is_background,
+                    group,
+                    layer: layer_index,
+                    sound_index: index,
+                    sound: sound.clone(),
+                    point: SoundPointerDownPoint::Center,
+                    cursor_in_world_pos: None,
+                });
+                tools.active_tool = ActiveTool::Sounds(ActiveToolSounds::Brush);
+            }
+            // the layer type changed
+            _ => return,
+        }
+
+        map.set_active_layer(set_layer);
+        map.groups.user.pos = camera_pos;
+        self.cur_result = Some(index);
+    }
+}
@@ -1,3 +1,7 @@
+pub mod collab;
+pub mod map_diff;
+pub mod map_search;
+pub mod prefabs;
 pub mod quad_layer;
 pub mod shared;
 pub mod sound_layer;
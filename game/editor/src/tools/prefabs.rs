@@ -0,0 +1,388 @@
+use std::{path::PathBuf, sync::Arc};
+
+use base_io::{io::IoFileSys, io_batcher::IoBatcherTask};
+use base_io_traits::fs_traits::FileSystemEntryTy;
+use egui::{vec2, Rect};
+use map::{
+    map::groups::layers::{
+        design::Quad,
+        tiles::{
+            MapTileLayerPhysicsTiles, MapTileLayerTiles, SpeedupTile, SwitchTile, TeleTile,
+            TileBase, TuneTile,
+        },
+    },
+    types::NonZeroU16MinusOne,
+};
+use math::math::vector::ffixed;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    map::{EditorLayer, EditorLayerUnionRef, EditorMap, EditorPhysicsLayer},
+    ui::top_toolbar::tile_mirror::{transform_map_tiles, TileTransform},
+};
+
+const PREFABS_DIR: &str = "editor/prefabs";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EditorPrefabContent {
+    Tiles {
+        w: NonZeroU16MinusOne,
+        h: NonZeroU16MinusOne,
+        tiles: MapTileLayerTiles,
+    },
+    Quads {
+        w: f32,
+        h: f32,
+        quads: Vec<Quad>,
+    },
+}
+
+/// How a prefab is placed, see [`EditorPrefab::transformed`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EditorPrefabTransform {
+    pub mirror_x: bool,
+    pub mirror_y: bool,
+    /// Clockwise rotation in steps of 90 degrees.
+    pub rotation: u8,
+}
+
+impl EditorPrefabTransform {
+    /// The transforms in the order they are applied,
+    /// first mirrored, then rotated.
+    fn tile_transforms(&self) -> impl Iterator<Item = TileTransform> {
+        self.mirror_x
+            .then_some(TileTransform::MirrorX)
+            .into_iter()
+            .chain(self.mirror_y.then_some(TileTransform::MirrorY))
+            .chain((0..self.rotation % 4).map(|_| TileTransform::RotatePlus90))
+    }
+}
+
+/// a saved brush (tile pattern or quad arrangement) that
+/// can be reused across maps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorPrefab {
+    pub name: String,
+    pub content: EditorPrefabContent,
+}
+
+impl EditorPrefab {
+    /// A copy of this prefab that is mirrored & rotated.
+    ///
+    /// Tiles use the same transforms as the brush tools,
+    /// quads are transformed inside their bounding box.
+    pub fn transformed(
+        &self,
+        tp: &Arc<rayon::ThreadPool>,
+        transform: EditorPrefabTransform,
+    ) -> Self {
+        let content = match &self.content {
+            EditorPrefabContent::Tiles { w, h, tiles } => {
+                let mut tiles = tiles.clone();
+                let mut cur_w = w.get() as usize;
+                for tile_transform in transform.tile_transforms() {
+                    match transform_map_tiles(tp, cur_w, &mut tiles, tile_transform, false) {
+                        Some(new_w) => cur_w = new_w,
+                        // arbitrary tiles are kept as they are
+                        None => return self.clone(),
+                    }
+                }
+                let (new_w, new_h) = if transform.rotation % 2 == 1 {
+                    (*h, *w)
+                } else {
+                    (*w, *h)
+                };
+                EditorPrefabContent::Tiles {
+                    w: new_w,
+                    h: new_h,
+                    tiles,
+                }
+            }
+            EditorPrefabContent::Quads { w, h, quads } => {
+                let (mut w, mut h) = (*w, *h);
+                let mut quads = quads.clone();
+                for tile_transform in transform.tile_transforms() {
+                    let (fw, fh) = (ffixed::from_num(w), ffixed::from_num(h));
+                    for point in quads.iter_mut().flat_map(|quad| quad.points.iter_mut()) {
+                        match tile_transform {
+                            TileTransform::MirrorX => point.x = fw - point.x,
+                            TileTransform::MirrorY => point.y = fh - point.y,
+                            TileTransform::RotatePlus90 => {
+                                (point.x, point.y) = (fh - point.y, point.x);
+                            }
+                            TileTransform::RotateMinus90 => {
+                                (point.x, point.y) = (point.y, fw - point.x);
+                            }
+                        }
+                    }
+                    if matches!(
+                        tile_transform,
+                        TileTransform::RotatePlus90 | TileTransform::RotateMinus90
+                    ) {
+                        std::mem::swap(&mut w, &mut h);
+                    }
+                }
+                EditorPrefabContent::Quads { w, h, quads }
+            }
+        };
+        Self {
+            name: self.name.clone(),
+            content,
+        }
+    }
+
+    fn tile_bases(tiles: &MapTileLayerTiles) -> Option<Vec<TileBase>> {
+        match tiles {
+            MapTileLayerTiles::Design(tiles) => Some(tiles.clone()),
+            MapTileLayerTiles::Physics(tiles) => match tiles {
+                MapTileLayerPhysicsTiles::Arbitrary(_) => None,
+                MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
+                    Some(tiles.clone())
+                }
+                MapTileLayerPhysicsTiles::Tele(tiles) => {
+                    Some(tiles.iter().map(|tile| tile.base).collect())
+                }
+                MapTileLayerPhysicsTiles::Speedup(tiles) => {
+                    Some(tiles.iter().map(|tile| tile.base).collect())
+                }
+                MapTileLayerPhysicsTiles::Switch(tiles) => {
+                    Some(tiles.iter().map(|tile| tile.base).collect())
+                }
+                MapTileLayerPhysicsTiles::Tune(tiles) => {
+                    Some(tiles.iter().map(|tile| tile.base).collect())
+                }
+            },
+        }
+    }
+
+    /// Converts the tiles of this prefab to fit the given layer.
+    ///
+    /// If the prefab was created from a different layer type,
+    /// the tile indices & flags are kept and the physics layer specific
+    /// values (tele/switch/tune numbers) are taken from the currently
+    /// active numbers of the map.
+    pub fn tiles_for_layer(
+        &self,
+        map: &EditorMap,
+        layer: &EditorLayerUnionRef,
+    ) -> Option<(NonZeroU16MinusOne, NonZeroU16MinusOne, MapTileLayerTiles)> {
+        let EditorPrefabContent::Tiles { w, h, tiles } = &self.content else {
+            return None;
+        };
+        let physics = &map.groups.physics.user;
+        let tiles = match (layer, tiles) {
+            (
+                EditorLayerUnionRef::Design {
+                    layer: EditorLayer::Tile(_),
+                    ..
+                },
+                MapTileLayerTiles::Design(_),
+            ) => tiles.clone(),
+            (
+                EditorLayerUnionRef::Design {
+                    layer: EditorLayer::Tile(_),
+                    ..
+                },
+                tiles,
+            ) => MapTileLayerTiles::Design(Self::tile_bases(tiles)?),
+            (EditorLayerUnionRef::Physics { layer, .. }, tiles) => {
+                let bases = Self::tile_bases(tiles)?;
+                MapTileLayerTiles::Physics(match (layer, tiles) {
+                    (EditorPhysicsLayer::Arbitrary(_), _) => return None,
+                    (
+                        EditorPhysicsLayer::Game(_),
+                        MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Game(_)),
+                    )
+                    | (
+                        EditorPhysicsLayer::Front(_),
+                        MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Front(_)),
+                    )
+                    | (
+                        EditorPhysicsLayer::Tele(_),
+                        MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tele(_)),
+                    )
+                    | (
+                        EditorPhysicsLayer::Speedup(_),
+                        MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Speedup(_)),
+                    )
+                    | (
+                        EditorPhysicsLayer::Switch(_),
+                        MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Switch(_)),
+                    )
+                    | (
+                        EditorPhysicsLayer::Tune(_),
+                        MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tune(_)),
+                    ) => {
+                        let MapTileLayerTiles::Physics(tiles) = tiles else {
+                            return None;
+                        };
+                        tiles.clone()
+                    }
+                    (EditorPhysicsLayer::Game(_), _) => MapTileLayerPhysicsTiles::Game(bases),
+                    (EditorPhysicsLayer::Front(_), _) => MapTileLayerPhysicsTiles::Front(bases),
+                    (EditorPhysicsLayer::Tele(_), _) => MapTileLayerPhysicsTiles::Tele(
+                        bases
+                            .into_iter()
+                            .map(|base| TeleTile {
+                                base,
+                                number: physics.active_tele,
+                            })
+                            .collect(),
+                    ),
+                    (EditorPhysicsLayer::Speedup(_), _) => MapTileLayerPhysicsTiles::Speedup(
+                        bases
+                            .into_iter()
+                            .map(|base| SpeedupTile {
+                                base,
+                                // ddnet's default speedup
+                                force: 50,
+                                max_speed: 0,
+                                angle: 0,
+                            })
+                            .collect(),
+                    ),
+                    (EditorPhysicsLayer::Switch(_), _) => MapTileLayerPhysicsTiles::Switch(
+                        bases
+                            .into_iter()
+                            .map(|base| SwitchTile {
+                                base,
+                                number: physics.active_switch,
+                                delay: 0,
+                            })
+                            .collect(),
+                    ),
+                    (EditorPhysicsLayer::Tune(_), _) => MapTileLayerPhysicsTiles::Tune(
+                        bases
+                            .into_iter()
+                            .map(|base| TuneTile {
+                                base,
+                                number: physics.active_tune_zone,
+                            })
+                            .collect(),
+                    ),
+                })
+            }
+            _ => return None,
+        };
+        Some((*w, *h, tiles))
+    }
+}
+
+/// a library of prefabs, that are stored in the editor's
+/// directory on disk
+pub struct EditorPrefabs {
+    pub prefabs: Vec<EditorPrefab>,
+
+    // ui shown
+    pub active: bool,
+    pub window_rect: Rect,
+    /// name for the next prefab to save
+    pub name: String,
+    /// how the next applied prefab is placed
+    pub transform: EditorPrefabTransform,
+
+    load_task: Option<IoBatcherTask<Vec<EditorPrefab>>>,
+    /// writes & deletes of prefab files
+    io_tasks: Vec<IoBatcherTask<()>>,
+    io: IoFileSys,
+}
+
+impl EditorPrefabs {
+    pub fn new(io: IoFileSys) -> Self {
+        let mut res = Self {
+            prefabs: Default::default(),
+
+            active: false,
+            window_rect: Rect::from_min_size(Default::default(), vec2(50.0, 50.0)),
+            name: Default::default(),
+            transform: Default::default(),
+
+            load_task: None,
+            io_tasks: Default::default(),
+            io,
+        };
+        res.reload();
+        res
+    }
+
+    fn prefab_path(name: &str) -> PathBuf {
+        PathBuf::from(PREFABS_DIR).join(format!("{name}.json"))
+    }
+
+    /// (re-)loads all prefabs from disk
+    pub fn reload(&mut self) {
+        let fs = self.io.fs.clone();
+        self.load_task = Some(self.io.io_batcher.spawn(async move {
+            let entries = fs.entries_in_dir(PREFABS_DIR.as_ref()).await?;
+            let mut prefabs = Vec::new();
+            for (name, _) in entries.into_iter().filter(|(name, ty)| {
+                matches!(ty, FileSystemEntryTy::File { .. }) && name.ends_with(".json")
+            }) {
+                let file = fs
+                    .read_file(&PathBuf::from(PREFABS_DIR).join(&name))
+                    .await?;
+                if let Ok(prefab) = serde_json::from_slice::<EditorPrefab>(&file) {
+                    prefabs.push(prefab);
+                }
+            }
+            prefabs.sort_by(|p1, p2| p1.name.cmp(&p2.name));
+            Ok(prefabs)
+        }));
+    }
+
+    pub fn update(&mut self) {
+        if self
+            .load_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            // the directory might not exist yet, which is not an error
+            if let Ok(prefabs) = self.load_task.take().unwrap().get_storage() {
+                self.prefabs = prefabs;
+            }
+        }
+    }
+
+    /// Returns the errors of writes & deletes that finished since the last call.
+    pub fn take_io_errors(&mut self) -> Vec<String> {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.io_tasks)
+            .into_iter()
+            .partition(|task| task.is_finished());
+        self.io_tasks = running;
+        finished
+            .into_iter()
+            .filter_map(|task| task.get_storage().err())
+            .map(|err| err.to_string())
+            .collect()
+    }
+
+    /// saves the prefab to disk & adds it to the library,
+    /// a prefab with the same name is overwritten.
+    pub fn save(&mut self, prefab: EditorPrefab) -> anyhow::Result<()> {
+        let fs = self.io.fs.clone();
+        let path = Self::prefab_path(&prefab.name);
+        let file = serde_json::to_vec(&prefab)?;
+        self.io_tasks.push(self.io.io_batcher.spawn(async move {
+            fs.create_dir(PREFABS_DIR.as_ref()).await?;
+            fs.write_file(&path, file).await?;
+            Ok(())
+        }));
+
+        self.prefabs.retain(|p| p.name != prefab.name);
+        self.prefabs.push(prefab);
+        self.prefabs.sort_by(|p1, p2| p1.name.cmp(&p2.name));
+        Ok(())
+    }
+
+    pub fn delete(&mut self, index: usize) {
+        if index < self.prefabs.len() {
+            let prefab = self.prefabs.remove(index);
+            let fs = self.io.fs.clone();
+            let path = Self::prefab_path(&prefab.name);
+            self.io_tasks.push(self.io.io_batcher.spawn(async move {
+                fs.remove_file(&path).await?;
+                Ok(())
+            }));
+        }
+    }
+}
@@ -581,7 +581,8 @@ impl QuadBrush {
                         cur_anim_time,
                         cur_quad_offset,
                         animations,
-                        quads
+                        quads,
+                        1.0
                     );
                 }),
                 hi_closure!([
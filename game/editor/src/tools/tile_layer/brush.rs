@@ -31,6 +31,7 @@ use map::{
 };
 use math::math::vector::{dvec2, ivec2, ubvec4, usvec2, vec2, vec4};
 use pool::mt_datatypes::PoolVec;
+use rand::{Rng, SeedableRng};
 
 use crate::{
     actions::actions::{
@@ -49,7 +50,7 @@ use crate::{
     utils::{ui_pos_to_world_pos, UiCanvasSize},
 };
 
-use super::shared::TILE_VISUAL_SIZE;
+use super::shared::{physics_tile_has_direction, TILE_VISUAL_SIZE};
 
 // 20 ui pixels
 const TILE_PICKER_VISUAL_SIZE: f32 = 30.0;
@@ -105,6 +106,59 @@ pub struct TileBrushDownPos {
     pub ui: egui::Pos2,
 }
 
+/// How a shift + drag fills the rect with the tiles of the brush.
+#[derive(Debug, Hiarc, Default, Clone, Copy, PartialEq)]
+pub enum TileBrushFill {
+    /// Repeats the brush over the rect.
+    #[default]
+    Repeat,
+    /// Blends from the first to the last tile of the brush across the rect,
+    /// using ordered dithering.
+    Gradient { vertical: bool },
+    /// Scatters random tiles of the brush over the rect,
+    /// `density` (0-1) of the tiles are replaced, the others are kept.
+    Noise { density: f32, seed: u64 },
+}
+
+/// 4x4 ordered dithering thresholds.
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// The part of a filled rect that is inside the layer.
+struct TileBrushFillRect<'a> {
+    picks: &'a [Option<usize>],
+    rect_w: usize,
+    off_x: usize,
+    off_y: usize,
+
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+}
+
+impl TileBrushFillRect<'_> {
+    /// Returns the old & the new tiles of the layer.
+    fn fill<T: Copy>(
+        &self,
+        layer_tiles: &[T],
+        layer_width: usize,
+        brush_tiles: &[T],
+    ) -> (Vec<T>, Vec<T>) {
+        let old_tiles =
+            TileBrush::collect_tiles(layer_tiles, layer_width, self.x, self.w, self.y, self.h);
+        let new_tiles = old_tiles
+            .iter()
+            .enumerate()
+            .map(|(i, old_tile)| {
+                let pick =
+                    self.picks[(self.off_y + i / self.w) * self.rect_w + self.off_x + i % self.w];
+                pick.map(|index| brush_tiles[index]).unwrap_or(*old_tile)
+            })
+            .collect();
+        (old_tiles, new_tiles)
+    }
+}
+
 #[derive(Debug, Hiarc)]
 pub struct TileBrush {
     pub brush: Option<TileBrushTiles>,
@@ -115,6 +169,9 @@ pub struct TileBrush {
     pub shift_pointer_down_world_pos: Option<TileBrushDownPos>,
 
     pub parallax_aware_brush: bool,
+    /// Only paint the flags (rotation & mirror) of the brush onto the existing tiles.
+    pub paint_flags_only: bool,
+    pub fill: TileBrushFill,
 }
 
 impl TileBrush {
@@ -136,6 +193,8 @@ impl TileBrush {
             shift_pointer_down_world_pos: None,
 
             parallax_aware_brush: false,
+            paint_flags_only: false,
+            fill: TileBrushFill::Repeat,
         }
     }
 
@@ -645,6 +704,7 @@ impl TileBrush {
         brush_off_y: u16,
         max_brush_w: u16,
         max_brush_h: u16,
+        paint_flags_only: bool,
     ) {
         let (layer_width, layer_height) = layer.get_width_and_height();
 
@@ -711,7 +771,7 @@ impl TileBrush {
             }
         };
         if brush_w > 0 && brush_h > 0 && brush_matches_layer {
-            let (action, group_indentifier) = match layer {
+            let (mut action, group_indentifier) = match layer {
                 EditorLayerUnionRef::Physics {
                     layer,
                     group_attr,
@@ -939,10 +999,276 @@ impl TileBrush {
                     )
                 }
             };
+            if paint_flags_only {
+                Self::keep_tiles_except_flags(&mut action);
+            }
             client.execute(action, Some(&group_indentifier));
         }
     }
 
+    /// Replaces the new tiles of the action with the old tiles,
+    /// except for the flags of the brush, which are painted onto all non-air tiles.
+    fn keep_tiles_except_flags(action: &mut EditorAction) {
+        fn paint<T: Copy + AsMut<TileBase>>(
+            old_tiles: &[T],
+            new_tiles: &mut [T],
+            paint: impl Fn(&mut T, T),
+        ) {
+            new_tiles
+                .iter_mut()
+                .zip(old_tiles.iter())
+                .for_each(|(new_tile, old_tile)| {
+                    let brush_tile = *new_tile;
+                    *new_tile = *old_tile;
+                    if new_tile.as_mut().index != 0 {
+                        paint(new_tile, brush_tile);
+                    }
+                });
+        }
+        fn paint_physics<T: Copy + AsMut<TileBase>>(old_tiles: &[T], new_tiles: &mut [T]) {
+            paint(old_tiles, new_tiles, |tile, mut brush_tile| {
+                let flags = brush_tile.as_mut().flags;
+                let tile = tile.as_mut();
+                if physics_tile_has_direction(tile.index) {
+                    tile.flags = flags;
+                }
+            });
+        }
+
+        match action {
+            EditorAction::TileLayerReplaceTiles(act) => {
+                paint(
+                    &act.base.old_tiles,
+                    &mut act.base.new_tiles,
+                    |tile, brush_tile| {
+                        tile.flags = brush_tile.flags;
+                    },
+                );
+            }
+            EditorAction::TilePhysicsLayerReplaceTiles(act) => {
+                match (&act.base.old_tiles, &mut act.base.new_tiles) {
+                    (
+                        MapTileLayerPhysicsTiles::Game(old_tiles),
+                        MapTileLayerPhysicsTiles::Game(new_tiles),
+                    )
+                    | (
+                        MapTileLayerPhysicsTiles::Front(old_tiles),
+                        MapTileLayerPhysicsTiles::Front(new_tiles),
+                    ) => paint_physics(old_tiles, new_tiles),
+                    (
+                        MapTileLayerPhysicsTiles::Tele(old_tiles),
+                        MapTileLayerPhysicsTiles::Tele(new_tiles),
+                    ) => paint_physics(old_tiles, new_tiles),
+                    (
+                        MapTileLayerPhysicsTiles::Switch(old_tiles),
+                        MapTileLayerPhysicsTiles::Switch(new_tiles),
+                    ) => paint_physics(old_tiles, new_tiles),
+                    (
+                        MapTileLayerPhysicsTiles::Tune(old_tiles),
+                        MapTileLayerPhysicsTiles::Tune(new_tiles),
+                    ) => paint_physics(old_tiles, new_tiles),
+                    // speedups store their direction in the angle
+                    (
+                        MapTileLayerPhysicsTiles::Speedup(old_tiles),
+                        MapTileLayerPhysicsTiles::Speedup(new_tiles),
+                    ) => paint(old_tiles, new_tiles, |tile, brush_tile| {
+                        tile.angle = brush_tile.angle;
+                    }),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// For every tile of the rect the index of the brush tile to place,
+    /// `None` keeps the existing tile.
+    fn fill_picks(fill: TileBrushFill, brush_len: usize, w: u16, h: u16) -> Vec<Option<usize>> {
+        match fill {
+            TileBrushFill::Repeat => Vec::new(),
+            TileBrushFill::Gradient { vertical } => (0..h as usize)
+                .flat_map(|y| (0..w as usize).map(move |x| (x, y)))
+                .map(|(x, y)| {
+                    let t = if vertical {
+                        (y as f32 + 0.5) / h as f32
+                    } else {
+                        (x as f32 + 0.5) / w as f32
+                    };
+                    let threshold = (BAYER_4X4[y % 4][x % 4] as f32 + 0.5) / 16.0;
+                    Some(if t > threshold { brush_len - 1 } else { 0 })
+                })
+                .collect(),
+            TileBrushFill::Noise { density, seed } => {
+                let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+                let density = density.clamp(0.0, 1.0) as f64;
+                (0..w as usize * h as usize)
+                    .map(|_| rng.gen_bool(density).then(|| rng.gen_range(0..brush_len)))
+                    .collect()
+            }
+        }
+    }
+
+    /// Fills the rect with the tiles of the brush, see [`TileBrushFill`].
+    /// Like the brush itself, this emits a single replace tiles action.
+    fn apply_fill_internal(
+        layer: &EditorLayerUnionRef<'_>,
+        brush: &TileBrushTiles,
+        fill: TileBrushFill,
+        client: &mut EditorClient,
+        pos: ivec2,
+        width: NonZeroU16MinusOne,
+        height: NonZeroU16MinusOne,
+    ) {
+        let (layer_width, layer_height) = layer.get_width_and_height();
+        let x_min = pos.x.max(0);
+        let y_min = pos.y.max(0);
+        let x_max = (pos.x + width.get() as i32).min(layer_width.get() as i32);
+        let y_max = (pos.y + height.get() as i32).min(layer_height.get() as i32);
+        if x_min >= x_max || y_min >= y_max {
+            return;
+        }
+        let (x, y) = (x_min as u16, y_min as u16);
+        let (w, h) = ((x_max - x_min) as usize, (y_max - y_min) as usize);
+
+        // the picks are relative to the whole rect, even if the layer cuts it off
+        let brush_len = brush.w.get() as usize * brush.h.get() as usize;
+        let picks = Self::fill_picks(fill, brush_len, width.get(), height.get());
+        if picks.is_empty() {
+            return;
+        }
+        let rect = TileBrushFillRect {
+            picks: &picks,
+            rect_w: width.get() as usize,
+            off_x: (x_min - pos.x) as usize,
+            off_y: (y_min - pos.y) as usize,
+            x: x as usize,
+            y: y as usize,
+            w,
+            h,
+        };
+
+        let (action, group_indentifier) = match layer {
+            EditorLayerUnionRef::Physics {
+                layer,
+                group_attr,
+                layer_index,
+            } => {
+                let layer_width = group_attr.width.get() as usize;
+                let MapTileLayerTiles::Physics(brush_tiles) = &brush.tiles else {
+                    return;
+                };
+                let (old_tiles, new_tiles) = match (layer, brush_tiles) {
+                    (EditorPhysicsLayer::Game(layer), MapTileLayerPhysicsTiles::Game(tiles)) => {
+                        let (old, new) = rect.fill(&layer.layer.tiles, layer_width, tiles);
+                        (
+                            MapTileLayerPhysicsTiles::Game(old),
+                            MapTileLayerPhysicsTiles::Game(new),
+                        )
+                    }
+                    (EditorPhysicsLayer::Front(layer), MapTileLayerPhysicsTiles::Front(tiles)) => {
+                        let (old, new) = rect.fill(&layer.layer.tiles, layer_width, tiles);
+                        (
+                            MapTileLayerPhysicsTiles::Front(old),
+                            MapTileLayerPhysicsTiles::Front(new),
+                        )
+                    }
+                    (EditorPhysicsLayer::Tele(layer), MapTileLayerPhysicsTiles::Tele(tiles)) => {
+                        let (old, new) = rect.fill(&layer.layer.base.tiles, layer_width, tiles);
+                        (
+                            MapTileLayerPhysicsTiles::Tele(old),
+                            MapTileLayerPhysicsTiles::Tele(new),
+                        )
+                    }
+                    (
+                        EditorPhysicsLayer::Speedup(layer),
+                        MapTileLayerPhysicsTiles::Speedup(tiles),
+                    ) => {
+                        let (old, new) = rect.fill(&layer.layer.tiles, layer_width, tiles);
+                        (
+                            MapTileLayerPhysicsTiles::Speedup(old),
+                            MapTileLayerPhysicsTiles::Speedup(new),
+                        )
+                    }
+                    (
+                        EditorPhysicsLayer::Switch(layer),
+                        MapTileLayerPhysicsTiles::Switch(tiles),
+                    ) => {
+                        let (old, new) = rect.fill(&layer.layer.base.tiles, layer_width, tiles);
+                        (
+                            MapTileLayerPhysicsTiles::Switch(old),
+                            MapTileLayerPhysicsTiles::Switch(new),
+                        )
+                    }
+                    (EditorPhysicsLayer::Tune(layer), MapTileLayerPhysicsTiles::Tune(tiles)) => {
+                        let (old, new) = rect.fill(&layer.layer.base.tiles, layer_width, tiles);
+                        (
+                            MapTileLayerPhysicsTiles::Tune(old),
+                            MapTileLayerPhysicsTiles::Tune(new),
+                        )
+                    }
+                    _ => return,
+                };
+                (
+                    EditorAction::TilePhysicsLayerReplaceTiles(ActTilePhysicsLayerReplaceTiles {
+                        base: ActTilePhysicsLayerReplTilesBase {
+                            layer_index: *layer_index,
+                            old_tiles,
+                            new_tiles,
+                            x,
+                            y,
+                            w: NonZeroU16MinusOne::new(w as u16).unwrap(),
+                            h: NonZeroU16MinusOne::new(h as u16).unwrap(),
+                        },
+                    }),
+                    format!("tile-brush phy {}", layer_index),
+                )
+            }
+            EditorLayerUnionRef::Design {
+                layer,
+                layer_index,
+                group_index,
+                is_background,
+                ..
+            } => {
+                let EditorLayer::Tile(layer) = layer else {
+                    panic!("not a tile layer")
+                };
+                let (MapTileLayerTiles::Design(tiles)
+                | MapTileLayerTiles::Physics(
+                    MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles),
+                )) = &brush.tiles
+                else {
+                    return;
+                };
+                let (old_tiles, new_tiles) = rect.fill(
+                    &layer.layer.tiles,
+                    layer.layer.attr.width.get() as usize,
+                    tiles,
+                );
+                (
+                    EditorAction::TileLayerReplaceTiles(ActTileLayerReplaceTiles {
+                        base: ActTileLayerReplTilesBase {
+                            is_background: *is_background,
+                            group_index: *group_index,
+                            layer_index: *layer_index,
+                            old_tiles,
+                            new_tiles,
+                            x,
+                            y,
+                            w: NonZeroU16MinusOne::new(w as u16).unwrap(),
+                            h: NonZeroU16MinusOne::new(h as u16).unwrap(),
+                        },
+                    }),
+                    format!(
+                        "tile-brush {}-{}-{}",
+                        group_index, layer_index, is_background
+                    ),
+                )
+            }
+        };
+        client.execute(action, Some(&group_indentifier));
+    }
+
     fn apply_brush_repeating_internal(
         &self,
         brush: &TileBrushTiles,
@@ -983,6 +1309,7 @@ impl TileBrush {
                             brush_y,
                             brush_w,
                             1,
+                            self.paint_flags_only,
                         );
 
                         width -= brush_w;
@@ -1049,7 +1376,22 @@ impl TileBrush {
                 let height = (pos_cur.y - pos_old.y).unsigned_abs() as u16 + 1;
                 let pos_min = ivec2::new(pos_cur.x.min(pos_old.x), pos_cur.y.min(pos_old.y));
 
-                if !latest_pointer.primary_down() {
+                if !latest_pointer.primary_down() && self.fill != TileBrushFill::Repeat {
+                    Self::apply_fill_internal(
+                        &layer,
+                        brush,
+                        self.fill,
+                        client,
+                        pos_min,
+                        NonZeroU16MinusOne::new(width).unwrap(),
+                        NonZeroU16MinusOne::new(height).unwrap(),
+                    );
+                    // the next scatter should look different
+                    if let TileBrushFill::Noise { seed, .. } = &mut self.fill {
+                        *seed = seed.wrapping_add(1);
+                    }
+                    self.shift_pointer_down_world_pos = None;
+                } else if !latest_pointer.primary_down() {
                     self.apply_brush_repeating_internal(
                         brush,
                         layer,
@@ -1128,6 +1470,7 @@ impl TileBrush {
                     0,
                     brush.w.get(),
                     brush.h.get(),
+                    self.paint_flags_only,
                 );
             }
         }
@@ -1 +1,19 @@
+use shared_base::mapdef_06::DdraceTileNum;
+
 pub const TILE_VISUAL_SIZE: f32 = 1.0;
+
+/// Physics tiles that have a direction (e.g. stoppers) and therefore use the tile flags,
+/// the flags of all other physics tiles are ignored.
+pub fn physics_tile_has_direction(index: u8) -> bool {
+    [
+        DdraceTileNum::Stop as u8,
+        DdraceTileNum::StopS as u8,
+        DdraceTileNum::StopA as u8,
+        DdraceTileNum::CP as u8,
+        DdraceTileNum::CPF as u8,
+        DdraceTileNum::ThroughDir as u8,
+        DdraceTileNum::EntitiesOff1 as u8,
+        DdraceTileNum::EntitiesOff2 as u8,
+    ]
+    .contains(&index)
+}
@@ -0,0 +1,170 @@
+use egui::{Align2, Button, Color32, FontId, LayerId, Order, ScrollArea, TextEdit, Window};
+use ui_base::types::UiRenderPipe;
+
+use crate::{
+    event::EditorAnnotationEvent, map::EditorAnnotation, ui::user_data::UserData,
+    utils::world_pos_to_ui_pos,
+};
+
+/// How many chars of an annotation are shown next to its pin.
+const PIN_TEXT_LEN: usize = 32;
+
+pub fn render(pipe: &mut UiRenderPipe<UserData>, ui: &mut egui::Ui) {
+    let collab = &mut *pipe.user_data.collab;
+
+    let mut open = collab.active;
+    let window_res = Window::new("Chat & annotations")
+        .open(&mut open)
+        .show(ui.ctx(), |ui| {
+            let Some(tab) = pipe.user_data.editor_tab.as_mut() else {
+                ui.label("No map opened.");
+                return;
+            };
+
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.text_edit_singleline(&mut collab.name);
+            });
+            ui.separator();
+
+            ScrollArea::vertical()
+                .id_source("chat")
+                .max_height(200.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    if tab.client.chat().is_empty() {
+                        ui.label("No messages yet.");
+                    }
+                    for msg in tab.client.chat() {
+                        ui.label(format!("{}: {}", msg.from, msg.msg));
+                    }
+                });
+            ui.horizontal(|ui| {
+                let res = ui.add(TextEdit::singleline(&mut collab.chat_msg).hint_text("Message"));
+                let enter = res.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if (ui.button("Send").clicked() || enter) && !collab.chat_msg.trim().is_empty() {
+                    tab.client
+                        .send_chat(collab.name.clone(), std::mem::take(&mut collab.chat_msg));
+                    res.request_focus();
+                }
+            });
+            ui.separator();
+
+            ui.label("Annotations");
+            let mut remove = None;
+            ScrollArea::vertical()
+                .id_source("annotations")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    for annotation in tab.map.user.annotations.iter() {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(format!("{:.0}, {:.0}", annotation.pos.x, annotation.pos.y))
+                                .on_hover_text("Jump to the annotation")
+                                .clicked()
+                            {
+                                tab.map.groups.user.pos = annotation.pos;
+                            }
+                            ui.label(format!("{}: {}", annotation.author, annotation.text));
+                            if ui.button("\u{2716}").on_hover_text("Remove").clicked() {
+                                remove = Some(annotation.clone());
+                            }
+                        });
+                    }
+                });
+            if let Some(annotation) = remove {
+                tab.client
+                    .annotate(EditorAnnotationEvent::Remove(annotation));
+            }
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut collab.annotation_text).hint_text("Note"));
+                if ui
+                    .add_enabled(
+                        !collab.annotation_text.trim().is_empty(),
+                        Button::new("Pin at camera"),
+                    )
+                    .clicked()
+                {
+                    tab.client
+                        .annotate(EditorAnnotationEvent::Add(EditorAnnotation {
+                            pos: tab.map.groups.user.pos,
+                            author: collab.name.clone(),
+                            text: std::mem::take(&mut collab.annotation_text),
+                        }));
+                }
+            });
+        });
+    collab.active = open;
+
+    if let Some(window_res) = &window_res {
+        collab.window_rect = window_res.response.rect;
+    }
+
+    *pipe.user_data.pointer_is_used |= if let Some(window_res) = &window_res {
+        let intersected = ui.input(|i| {
+            if i.pointer.primary_down() {
+                Some((
+                    !window_res.response.rect.intersects({
+                        let min = i.pointer.interact_pos().unwrap_or_default();
+                        let max = min;
+                        [min, max].into()
+                    }),
+                    i.pointer.primary_pressed(),
+                ))
+            } else {
+                None
+            }
+        });
+        intersected.is_some_and(|(outside, _)| !outside)
+    } else {
+        false
+    };
+}
+
+/// Renders the annotations as pins at their position in the world.
+pub fn render_pins(pipe: &mut UiRenderPipe<UserData>, ui: &mut egui::Ui) {
+    let Some(tab) = pipe.user_data.editor_tab.as_ref() else {
+        return;
+    };
+    let map = &tab.map;
+    if map.user.options.hide_annotations || map.user.annotations.is_empty() {
+        return;
+    }
+
+    let canvas = ui.ctx().screen_rect();
+    let painter = ui
+        .ctx()
+        .layer_painter(LayerId::new(Order::Background, "editor-annotations".into()));
+    for annotation in map.user.annotations.iter() {
+        let pos = world_pos_to_ui_pos(
+            pipe.user_data.canvas_handle,
+            &canvas,
+            map.groups.user.zoom,
+            annotation.pos,
+            map.groups.user.pos.x,
+            map.groups.user.pos.y,
+            0.0,
+            0.0,
+            100.0,
+            100.0,
+        );
+        let pos = egui::pos2(pos.x, pos.y);
+        if !canvas.contains(pos) {
+            continue;
+        }
+        painter.circle(
+            pos,
+            6.0,
+            Color32::from_rgb(255, 200, 0),
+            (1.0, Color32::BLACK),
+        );
+        let text: String = annotation.text.chars().take(PIN_TEXT_LEN).collect();
+        painter.text(
+            pos + egui::vec2(10.0, 0.0),
+            Align2::LEFT_CENTER,
+            format!("{}: {text}", annotation.author),
+            FontId::proportional(12.0),
+            Color32::WHITE,
+        );
+    }
+}
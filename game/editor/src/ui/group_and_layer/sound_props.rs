@@ -14,7 +14,10 @@ use ui_base::{types::UiRenderPipe, utils::toggle_ui};
 
 use crate::{
     actions::actions::{ActChangeSoundAttr, EditorAction},
-    map::{EditorAnimations, EditorLayer, EditorLayerUnionRefMut, EditorMapGroupsInterface},
+    map::{
+        EditorAnimations, EditorLayer, EditorLayerUnionRefMut, EditorMapGroupsInterface,
+        EditorSoundSolo,
+    },
     tools::{
         sound_layer::shared::SoundPointerDownPoint,
         tool::{ActiveTool, ActiveToolSounds},
@@ -92,6 +95,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, main_
             sounds_count: usize,
             point: SoundPointerDownPoint,
             sound: &mut Sound,
+            // only for a single sound
+            solo: Option<&mut bool>,
             // make a "move pos" instead of x, y directly
             pos_offset: Option<&mut dvec2>,
             mut anim_pos: Option<&mut AnimPointPos>,
@@ -109,6 +114,12 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, main_
                         ui.label(format!("selected {sounds_count} sounds"));
                         ui.end_row();
                     }
+                    if let Some(solo) = solo {
+                        ui.label("solo")
+                            .on_hover_text("Mute all other sounds to tune this sound's falloff.");
+                        toggle_ui(ui, solo);
+                        ui.end_row();
+                    }
                     if !animations_panel_open || (can_change_pos_anim && sound.pos_anim.is_some()) {
                         if let Some(pos_offset) = pos_offset {
                             // x
@@ -409,6 +420,15 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, main_
                 let (index, sound) = selected_sounds.pop_first().unwrap();
                 let sound_cmp = sound.clone();
 
+                let solo_sound = EditorSoundSolo {
+                    is_background,
+                    group_index,
+                    layer_index,
+                    sound_index: index,
+                };
+                let mut solo = map.user.ui_values.sound_solo == Some(solo_sound);
+                let solo_cmp = solo;
+
                 if main_frame_only {
                     ui.painter().rect_filled(
                         window_props.rect,
@@ -428,6 +448,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, main_
                             sounds_count,
                             point,
                             sound,
+                            Some(&mut solo),
                             None,
                             None,
                             true,
@@ -438,6 +459,10 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, main_
                         )
                     });
 
+                    if solo != solo_cmp {
+                        map.user.ui_values.sound_solo = solo.then_some(solo_sound);
+                    }
+
                     if *sound != sound_cmp && !animations_panel_open {
                         let layer_sound = &layer.layer.sounds[index];
                         pipe.user_data.editor_tab.client.execute(
@@ -495,6 +520,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, main_
                             sounds_count,
                             point,
                             &mut sound,
+                            None,
                             pos_offset,
                             can_change_pos_anim.then_some(pos_anim).flatten(),
                             can_change_pos_anim,
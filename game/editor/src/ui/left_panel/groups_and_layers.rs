@@ -96,6 +96,12 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                             if ui.add(hide_btn).clicked() {
                                 layer.editor_attr_mut().hidden = !hidden;
                             }
+                            ui.add(
+                                egui::DragValue::new(&mut layer.editor_attr_mut().opacity)
+                                    .range(0..=100)
+                                    .suffix("%"),
+                            )
+                            .on_hover_text("Opacity, only affects the editor view");
 
                             ui.vertical_centered_justified(|ui| {
                                 let btn = ui.add(layer_btn);
@@ -0,0 +1,145 @@
+use egui::{Button, Color32, ScrollArea, TextEdit, Window};
+use ui_base::types::UiRenderPipe;
+
+use crate::{
+    tools::map_diff::{MapDiffChange, MapDiffGroup},
+    ui::user_data::UserData,
+};
+
+fn change_text(change: MapDiffChange) -> (&'static str, Color32) {
+    match change {
+        MapDiffChange::Added => ("added", Color32::GREEN),
+        MapDiffChange::Removed => ("removed", Color32::RED),
+        MapDiffChange::Changed => ("changed", Color32::YELLOW),
+    }
+}
+
+fn render_group(ui: &mut egui::Ui, group: &MapDiffGroup) {
+    let changed_layers = group
+        .layers
+        .iter()
+        .filter(|layer| layer.layer_change.is_some() || !layer.items.is_empty());
+    if group.group_change.is_none() && changed_layers.clone().count() == 0 {
+        return;
+    }
+    ui.horizontal(|ui| {
+        ui.strong(if group.name.is_empty() {
+            "Group"
+        } else {
+            &group.name
+        });
+        if let Some(change) = group.group_change {
+            let (text, color) = change_text(change);
+            ui.colored_label(color, text);
+        }
+    });
+    for layer in changed_layers {
+        ui.horizontal(|ui| {
+            ui.add_space(10.0);
+            ui.label(&layer.name);
+            if let Some(change) = layer.layer_change {
+                let (text, color) = change_text(change);
+                ui.colored_label(color, text);
+            }
+            for change in [
+                MapDiffChange::Added,
+                MapDiffChange::Removed,
+                MapDiffChange::Changed,
+            ] {
+                let count = layer
+                    .items
+                    .iter()
+                    .filter(|item| item.change == change)
+                    .count();
+                if count > 0 {
+                    let (text, color) = change_text(change);
+                    ui.colored_label(color, format!("{count} {text}"));
+                }
+            }
+        });
+    }
+}
+
+pub fn render(pipe: &mut UiRenderPipe<UserData>, ui: &mut egui::Ui) {
+    let map_diff = &mut *pipe.user_data.map_diff;
+    map_diff.update();
+
+    let mut open = map_diff.active;
+    let window_res = Window::new("Map diff")
+        .open(&mut open)
+        .show(ui.ctx(), |ui| {
+            ui.label("Compare the current map with another version of it.");
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut map_diff.path).hint_text("Path to the map file"));
+                let can_load = !map_diff.path.trim().is_empty() && !map_diff.is_loading();
+                if ui.add_enabled(can_load, Button::new("Load")).clicked() {
+                    map_diff.load();
+                }
+                if ui
+                    .add_enabled(map_diff.other.is_some(), Button::new("Refresh"))
+                    .on_hover_text("Compare again after the current map changed.")
+                    .clicked()
+                {
+                    map_diff.needs_update = true;
+                }
+                if ui
+                    .add_enabled(map_diff.other.is_some(), Button::new("Clear"))
+                    .clicked()
+                {
+                    map_diff.clear();
+                }
+            });
+            if map_diff.is_loading() {
+                ui.label("Loading..");
+            }
+            if let Some(err) = &map_diff.err {
+                ui.colored_label(Color32::RED, err);
+            }
+            ui.checkbox(&mut map_diff.show_overlay, "Show changes in the map");
+
+            if let Some(diff) = &map_diff.diff {
+                ui.separator();
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for group in diff.groups() {
+                        render_group(ui, group);
+                    }
+                    if !diff.resources.is_empty() {
+                        ui.strong("Resources");
+                        for resource in &diff.resources {
+                            ui.horizontal(|ui| {
+                                ui.add_space(10.0);
+                                ui.label(format!("{} ({})", resource.name, resource.ty));
+                                let (text, color) = change_text(resource.change);
+                                ui.colored_label(color, text);
+                            });
+                        }
+                    }
+                });
+            }
+        });
+    map_diff.active = open;
+
+    if let Some(window_res) = &window_res {
+        map_diff.window_rect = window_res.response.rect;
+    }
+
+    *pipe.user_data.pointer_is_used |= if let Some(window_res) = &window_res {
+        let intersected = ui.input(|i| {
+            if i.pointer.primary_down() {
+                Some((
+                    !window_res.response.rect.intersects({
+                        let min = i.pointer.interact_pos().unwrap_or_default();
+                        let max = min;
+                        [min, max].into()
+                    }),
+                    i.pointer.primary_pressed(),
+                ))
+            } else {
+                None
+            }
+        });
+        intersected.is_some_and(|(outside, _)| !outside)
+    } else {
+        false
+    };
+}
@@ -0,0 +1 @@
+pub mod map_diff;
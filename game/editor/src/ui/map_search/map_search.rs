@@ -0,0 +1,169 @@
+use egui::{Button, ComboBox, DragValue, ScrollArea, Window};
+use ui_base::types::UiRenderPipe;
+
+use crate::{
+    tools::map_search::{MapSearchItem, MapSearchKind},
+    ui::user_data::UserData,
+};
+
+fn kind_name(kind: MapSearchKind) -> &'static str {
+    match kind {
+        MapSearchKind::TileIndex => "Tile index",
+        MapSearchKind::QuadImage => "Quads by image",
+        MapSearchKind::SoundResource => "Sounds by resource",
+    }
+}
+
+pub fn render(pipe: &mut UiRenderPipe<UserData>, ui: &mut egui::Ui) {
+    let map_search = &mut *pipe.user_data.map_search;
+
+    let mut open = map_search.active;
+    let window_res = Window::new("Search map")
+        .open(&mut open)
+        .show(ui.ctx(), |ui| {
+            let Some(tab) = pipe.user_data.editor_tab.as_mut() else {
+                ui.label("No map opened.");
+                return;
+            };
+            let map = &mut tab.map;
+
+            ui.horizontal(|ui| {
+                ComboBox::new("map-search-kind", "")
+                    .selected_text(kind_name(map_search.kind))
+                    .show_ui(ui, |ui| {
+                        for kind in [
+                            MapSearchKind::TileIndex,
+                            MapSearchKind::QuadImage,
+                            MapSearchKind::SoundResource,
+                        ] {
+                            if ui
+                                .selectable_value(&mut map_search.kind, kind, kind_name(kind))
+                                .changed()
+                            {
+                                map_search.resource = None;
+                            }
+                        }
+                    });
+
+                let resources: Vec<&str> = match map_search.kind {
+                    MapSearchKind::TileIndex => Vec::new(),
+                    MapSearchKind::QuadImage => map
+                        .resources
+                        .images
+                        .iter()
+                        .map(|res| res.def.name.as_str())
+                        .collect(),
+                    MapSearchKind::SoundResource => map
+                        .resources
+                        .sounds
+                        .iter()
+                        .map(|res| res.def.name.as_str())
+                        .collect(),
+                };
+                if map_search.kind == MapSearchKind::TileIndex {
+                    ui.add(DragValue::new(&mut map_search.tile_index));
+                } else {
+                    ComboBox::new("map-search-resource", "")
+                        .selected_text(
+                            map_search
+                                .resource
+                                .and_then(|index| resources.get(index))
+                                .copied()
+                                .unwrap_or("None"),
+                        )
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut map_search.resource, None, "None");
+                            for (index, name) in resources.iter().enumerate() {
+                                ui.selectable_value(&mut map_search.resource, Some(index), *name);
+                            }
+                        });
+                }
+
+                if ui.button("Search").clicked() {
+                    map_search.search(map);
+                }
+            });
+
+            if map_search.results.is_empty() {
+                ui.label("No results.");
+                return;
+            }
+            ui.horizontal(|ui| {
+                ui.label(format!("{} results", map_search.results.len()));
+                let cur = map_search.cur_result;
+                if ui
+                    .add_enabled(cur.is_some_and(|i| i > 0), Button::new("Previous"))
+                    .clicked()
+                {
+                    let index = cur.unwrap_or_default().saturating_sub(1);
+                    map_search.jump_to(index, map, pipe.user_data.tools);
+                }
+                if ui
+                    .add_enabled(
+                        !cur.is_some_and(|i| i + 1 >= map_search.results.len()),
+                        Button::new("Next"),
+                    )
+                    .clicked()
+                {
+                    let index = cur.map(|i| i + 1).unwrap_or_default();
+                    map_search.jump_to(index, map, pipe.user_data.tools);
+                }
+            });
+            ui.separator();
+
+            let row_height = ui.text_style_height(&egui::TextStyle::Body);
+            let mut jump_to = None;
+            ScrollArea::vertical().max_height(300.0).show_rows(
+                ui,
+                row_height,
+                map_search.results.len(),
+                |ui, rows| {
+                    for index in rows {
+                        let result = &map_search.results[index];
+                        let item = match result.item {
+                            MapSearchItem::Tile { x, y } => format!("tile at {x}, {y}"),
+                            MapSearchItem::Quad { index } => format!("quad #{index}"),
+                            MapSearchItem::Sound { index } => format!("sound #{index}"),
+                        };
+                        if ui
+                            .selectable_label(
+                                map_search.cur_result == Some(index),
+                                format!("{}: {item}", result.layer_name),
+                            )
+                            .clicked()
+                        {
+                            jump_to = Some(index);
+                        }
+                    }
+                },
+            );
+            if let Some(index) = jump_to {
+                map_search.jump_to(index, map, pipe.user_data.tools);
+            }
+        });
+    map_search.active = open;
+
+    if let Some(window_res) = &window_res {
+        map_search.window_rect = window_res.response.rect;
+    }
+
+    *pipe.user_data.pointer_is_used |= if let Some(window_res) = &window_res {
+        let intersected = ui.input(|i| {
+            if i.pointer.primary_down() {
+                Some((
+                    !window_res.response.rect.intersects({
+                        let min = i.pointer.interact_pos().unwrap_or_default();
+                        let max = min;
+                        [min, max].into()
+                    }),
+                    i.pointer.primary_pressed(),
+                ))
+            } else {
+                None
+            }
+        });
+        intersected.is_some_and(|(outside, _)| !outside)
+    } else {
+        false
+    };
+}
@@ -1,10 +1,14 @@
 pub mod animation_panel;
 pub mod auto_mapper;
 pub mod bottom_panel;
+pub mod collab;
 pub mod group_and_layer;
 pub mod left_panel;
 pub mod main_frame;
+pub mod map_diff;
+pub mod map_search;
 pub mod page;
+pub mod prefabs;
 pub mod top_menu;
 pub mod top_toolbar;
 pub mod user_data;
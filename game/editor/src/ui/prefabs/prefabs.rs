@@ -0,0 +1,108 @@
+use egui::{Button, ScrollArea, TextEdit, Window};
+use ui_base::types::UiRenderPipe;
+
+use crate::{
+    tools::prefabs::EditorPrefabContent,
+    ui::{
+        user_data::{EditorUiEvent, UserData},
+        utils::icon_font_text,
+    },
+};
+
+pub fn render(pipe: &mut UiRenderPipe<UserData>, ui: &mut egui::Ui) {
+    let prefabs = &mut *pipe.user_data.prefabs;
+    prefabs.update();
+
+    let mut open = prefabs.active;
+    let window_res = Window::new("Prefabs").open(&mut open).show(ui.ctx(), |ui| {
+        ui.label("Apply a prefab to use it as brush, mirrored & rotated as set below.");
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut prefabs.transform.mirror_x, "Mirror X");
+            ui.checkbox(&mut prefabs.transform.mirror_y, "Mirror Y");
+            if ui
+                .button(format!(
+                    "Rotate ({}°)",
+                    prefabs.transform.rotation as u32 * 90
+                ))
+                .clicked()
+            {
+                prefabs.transform.rotation = (prefabs.transform.rotation + 1) % 4;
+            }
+        });
+        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            let mut delete = None;
+            for (index, prefab) in prefabs.prefabs.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let ty = match &prefab.content {
+                        EditorPrefabContent::Tiles { w, h, .. } => {
+                            format!("tiles {}x{}", w.get(), h.get())
+                        }
+                        EditorPrefabContent::Quads { quads, .. } => {
+                            format!("{} quads", quads.len())
+                        }
+                    };
+                    ui.label(format!("{} ({ty})", prefab.name));
+                    if ui.button("Apply").clicked() {
+                        pipe.user_data
+                            .ui_events
+                            .push(EditorUiEvent::ApplyPrefab { index });
+                    }
+                    // trash
+                    if ui.button(icon_font_text(ui, "\u{f1f8}")).clicked() {
+                        delete = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = delete {
+                prefabs.delete(index);
+            }
+        });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut prefabs.name).hint_text("Prefab name"));
+            let name = prefabs.name.trim();
+            let valid_name = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == ' ');
+            if ui
+                .add_enabled(valid_name, Button::new("Save current brush"))
+                .clicked()
+            {
+                pipe.user_data.ui_events.push(EditorUiEvent::SavePrefab {
+                    name: name.to_string(),
+                });
+                prefabs.name.clear();
+            }
+            if ui.button("Reload").clicked() {
+                prefabs.reload();
+            }
+        });
+    });
+    prefabs.active = open;
+
+    if let Some(window_res) = &window_res {
+        prefabs.window_rect = window_res.response.rect;
+    }
+
+    *pipe.user_data.pointer_is_used |= if let Some(window_res) = &window_res {
+        let intersected = ui.input(|i| {
+            if i.pointer.primary_down() {
+                Some((
+                    !window_res.response.rect.intersects({
+                        let min = i.pointer.interact_pos().unwrap_or_default();
+                        let max = min;
+                        [min, max].into()
+                    }),
+                    i.pointer.primary_pressed(),
+                ))
+            } else {
+                None
+            }
+        });
+        intersected.is_some_and(|(outside, _)| !outside)
+    } else {
+        false
+    };
+}
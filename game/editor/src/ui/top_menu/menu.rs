@@ -8,6 +8,7 @@ use ui_base::types::UiRenderPipe;
 
 use crate::{
     explain::TEXT_ANIM_PANEL_AND_PROPS,
+    map_templates::EditorMapTemplate,
     ui::{
         user_data::{
             EditorMenuDialogMode, EditorMenuHostDialogMode, EditorMenuHostNetworkOptions,
@@ -30,6 +31,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
 
                 ui.horizontal(|ui| {
                     ui.menu_button("File", |ui| {
+                        if ui.button("New map").clicked() {
+                            *menu_dialog_mode = EditorMenuDialogMode::new_map();
+                        }
                         if ui.button("Open map").clicked() {
                             *menu_dialog_mode = EditorMenuDialogMode::open(pipe.user_data.io);
                         }
@@ -51,6 +55,18 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                         if ui.button("Automapper-Creator").clicked() {
                             pipe.user_data.auto_mapper.active = true;
                         }
+                        if ui.button("Prefabs").clicked() {
+                            pipe.user_data.prefabs.active = true;
+                        }
+                        if ui.button("Map diff").clicked() {
+                            pipe.user_data.map_diff.active = true;
+                        }
+                        if ui.button("Search map").clicked() {
+                            pipe.user_data.map_search.active = true;
+                        }
+                        if ui.button("Chat & annotations").clicked() {
+                            pipe.user_data.collab.active = true;
+                        }
                     });
 
                     if let Some(tab) = &mut pipe.user_data.editor_tab {
@@ -81,6 +97,24 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                                 tab.map.user.options.show_tile_numbers =
                                     !tab.map.user.options.show_tile_numbers;
                             }
+                            let btn = Button::new("Isolate active layer")
+                                .selected(tab.map.user.options.isolate_active_layer);
+                            if ui.add(btn).clicked() {
+                                tab.map.user.options.isolate_active_layer =
+                                    !tab.map.user.options.isolate_active_layer;
+                            }
+                            let btn = Button::new("Show annotations")
+                                .selected(!tab.map.user.options.hide_annotations);
+                            if ui.add(btn).clicked() {
+                                tab.map.user.options.hide_annotations =
+                                    !tab.map.user.options.hide_annotations;
+                            }
+                            let btn = Button::new("Save annotations in the map's memo")
+                                .selected(tab.map.user.options.save_annotations_in_memo);
+                            if ui.add(btn).clicked() {
+                                tab.map.user.options.save_annotations_in_memo =
+                                    !tab.map.user.options.save_annotations_in_memo;
+                            }
                         });
                     }
                 });
@@ -220,6 +254,53 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                             false
                         };
                     }
+                } else if let EditorMenuDialogMode::NewMap { name, template } = menu_dialog_mode {
+                    if !main_frame_only {
+                        let window = egui::Window::new("New map")
+                            .resizable(false)
+                            .collapsible(false);
+
+                        let mut create = false;
+                        let mut cancel = false;
+                        let window_res = window.show(ui.ctx(), |ui| {
+                            ui.label("Name:");
+                            ui.text_edit_singleline(name);
+                            ui.label("Template:");
+                            for option in EditorMapTemplate::ALL {
+                                ui.radio_value(template, option, option.name())
+                                    .on_hover_text(option.description());
+                            }
+                            if ui.button("Create").clicked() && !name.is_empty() {
+                                create = true;
+                            }
+                            if ui.button("Cancel").clicked() {
+                                cancel = true;
+                            }
+                        });
+
+                        if create {
+                            let EditorMenuDialogMode::NewMap { name, template } =
+                                std::mem::replace(menu_dialog_mode, EditorMenuDialogMode::None)
+                            else {
+                                return;
+                            };
+                            pipe.user_data
+                                .ui_events
+                                .push(EditorUiEvent::NewMap { name, template });
+                        } else if cancel {
+                            *menu_dialog_mode = EditorMenuDialogMode::None;
+                        }
+
+                        *pipe.user_data.pointer_is_used |= window_res.is_some_and(|window_res| {
+                            ui.input(|i| {
+                                i.pointer.primary_down()
+                                    && window_res
+                                        .response
+                                        .rect
+                                        .contains(i.pointer.interact_pos().unwrap_or_default())
+                            })
+                        });
+                    }
                 } else if let EditorMenuDialogMode::Join {
                     ip_port,
                     cert_hash,
@@ -294,6 +375,21 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, main_frame_o
                 if !main_frame_only && pipe.user_data.auto_mapper.active {
                     crate::ui::auto_mapper::auto_mapper::render(main_frame_only, pipe, ui);
                 }
+                if !main_frame_only && pipe.user_data.prefabs.active {
+                    crate::ui::prefabs::prefabs::render(pipe, ui);
+                }
+                if !main_frame_only && pipe.user_data.map_diff.active {
+                    crate::ui::map_diff::map_diff::render(pipe, ui);
+                }
+                if !main_frame_only && pipe.user_data.map_search.active {
+                    crate::ui::map_search::map_search::render(pipe, ui);
+                }
+                if !main_frame_only {
+                    crate::ui::collab::collab::render_pins(pipe, ui);
+                }
+                if !main_frame_only && pipe.user_data.collab.active {
+                    crate::ui::collab::collab::render(pipe, ui);
+                }
             });
         });
 }
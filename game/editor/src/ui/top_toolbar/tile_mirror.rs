@@ -7,15 +7,16 @@ use graphics::{
         buffer_object::buffer_object::GraphicsBufferObjectHandle,
     },
 };
-use map::map::groups::layers::tiles::{
-    rotate_by_plus_90, MapTileLayerPhysicsTiles, MapTileLayerTiles, TileBase, TileFlags,
+use map::{
+    map::groups::layers::tiles::{
+        rotate_by_plus_90, MapTileLayerPhysicsTiles, MapTileLayerTiles, SpeedupTile, TileBase,
+        TileFlags,
+    },
+    types::NonZeroU16MinusOne,
 };
 use math::math::vector::{dvec2, usvec2};
 use rayon::{
-    iter::{
-        IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator,
-        ParallelIterator,
-    },
+    iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator},
     slice::{ParallelSlice, ParallelSliceMut},
 };
 
@@ -29,80 +30,225 @@ use crate::{
     tools::tile_layer::{
         brush::{TileBrush, TileBrushTiles},
         selection::TileSelectionRange,
+        shared::physics_tile_has_direction,
     },
 };
 
-fn mirror_y_tiles<T: Copy + Clone + Sync + Send + AsMut<TileBase>>(
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileTransform {
+    MirrorX,
+    MirrorY,
+    RotatePlus90,
+    RotateMinus90,
+}
+
+impl TileTransform {
+    fn is_rotation(self) -> bool {
+        matches!(self, Self::RotatePlus90 | Self::RotateMinus90)
+    }
+
+    fn flags(self, flags: &mut TileFlags) {
+        match self {
+            // for rotated tiles the x & y axis are swapped
+            Self::MirrorX => flags.toggle(if flags.contains(TileFlags::ROTATE) {
+                TileFlags::YFLIP
+            } else {
+                TileFlags::XFLIP
+            }),
+            Self::MirrorY => flags.toggle(if flags.contains(TileFlags::ROTATE) {
+                TileFlags::XFLIP
+            } else {
+                TileFlags::YFLIP
+            }),
+            Self::RotatePlus90 => rotate_by_plus_90(flags),
+            Self::RotateMinus90 => (0..3).for_each(|_| rotate_by_plus_90(flags)),
+        }
+    }
+
+    fn design(self, tile: &mut TileBase) {
+        self.flags(&mut tile.flags);
+    }
+
+    /// Only physics tiles that have a direction (e.g. stoppers) use the tile flags,
+    /// all other physics tiles are kept as they are.
+    fn physics<T: AsMut<TileBase>>(self, tile: &mut T) {
+        let tile = tile.as_mut();
+        if physics_tile_has_direction(tile.index) {
+            self.flags(&mut tile.flags);
+        }
+    }
+
+    /// Speedups store their direction as angle in degrees instead of in the tile flags.
+    fn speedup(self, tile: &mut SpeedupTile) {
+        if tile.base.index == 0 {
+            return;
+        }
+        tile.angle = match self {
+            Self::MirrorX => 180 - tile.angle,
+            Self::MirrorY => -tile.angle,
+            Self::RotatePlus90 => tile.angle + 90,
+            Self::RotateMinus90 => tile.angle - 90,
+        }
+        .rem_euclid(360);
+    }
+}
+
+fn mirror_x_positions<T: Copy + Send + Sync>(
+    tp: &Arc<rayon::ThreadPool>,
+    w: usize,
+    tiles: &mut [T],
+) {
+    tp.install(|| {
+        tiles
+            .par_chunks_exact_mut(w)
+            .for_each(|chunk| chunk.reverse());
+    });
+}
+
+fn mirror_y_positions<T: Copy + Send + Sync>(
     tp: &Arc<rayon::ThreadPool>,
     w: usize,
     tiles: &mut Vec<T>,
-) where
-    Vec<T>: rayon::iter::IntoParallelIterator,
-    Vec<T>: rayon::iter::FromParallelIterator<<Vec<T> as rayon::iter::IntoParallelIterator>::Item>,
-{
+) {
     let new_tiles = tp.install(|| {
-        let mut new_tiles: Vec<T> = tiles
+        tiles
             .par_chunks_exact(w)
             .rev()
-            .flat_map(|chunk| chunk.to_vec())
-            .collect();
-        new_tiles
-            .par_iter_mut()
-            .for_each(|tile| tile.as_mut().flags.toggle(TileFlags::YFLIP));
-        new_tiles
+            .flat_map_iter(|chunk| chunk.iter().copied())
+            .collect()
     });
     *tiles = new_tiles;
 }
-pub fn mirror_tiles_y(
+
+/// Returns the width after the rotation.
+fn rotate_positions_plus_90<T: Copy + Send + Sync>(
     tp: &Arc<rayon::ThreadPool>,
-    graphics_mt: &GraphicsMultiThreaded,
-    buffer_object_handle: &GraphicsBufferObjectHandle,
-    backend_handle: &GraphicsBackendHandle,
-    brush: &mut TileBrushTiles,
-    upload_new_layer: bool,
-) {
-    match &mut brush.tiles {
+    w: usize,
+    tiles: &mut Vec<T>,
+) -> usize {
+    let h = tiles.len() / w;
+    let new_tiles = tp.install(|| {
+        (0..tiles.len())
+            .into_par_iter()
+            .map(|index| {
+                // transpose & reverse every row
+                let (row, column) = (index / h, index % h);
+                tiles[(h - 1 - column) * w + row]
+            })
+            .collect()
+    });
+    *tiles = new_tiles;
+    h
+}
+
+/// Transforms the tiles of an area that is `w` tiles wide,
+/// if `only_flags` is `true` the tiles keep their position.
+///
+/// Returns the width of the area after the transform.
+fn transform_tiles<T: Copy + Send + Sync>(
+    tp: &Arc<rayon::ThreadPool>,
+    mut w: usize,
+    tiles: &mut Vec<T>,
+    transform: TileTransform,
+    only_flags: bool,
+    transform_tile: impl Fn(&mut T) + Send + Sync,
+) -> usize {
+    if !only_flags {
+        match transform {
+            TileTransform::MirrorX => mirror_x_positions(tp, w, tiles),
+            TileTransform::MirrorY => mirror_y_positions(tp, w, tiles),
+            TileTransform::RotatePlus90 => {
+                w = rotate_positions_plus_90(tp, w, tiles);
+            }
+            TileTransform::RotateMinus90 => {
+                for _ in 0..3 {
+                    w = rotate_positions_plus_90(tp, w, tiles);
+                }
+            }
+        }
+    }
+    tp.install(|| {
+        tiles
+            .par_chunks_mut(w.max(1))
+            .for_each(|chunk| chunk.iter_mut().for_each(&transform_tile));
+    });
+    w
+}
+
+/// Transforms the tiles of a layer that is `w` tiles wide,
+/// if `only_flags` is `true` the tiles keep their position.
+///
+/// Returns the width after the transform,
+/// or `None` for arbitrary tiles, which can't be transformed.
+pub fn transform_map_tiles(
+    tp: &Arc<rayon::ThreadPool>,
+    w: usize,
+    tiles: &mut MapTileLayerTiles,
+    transform: TileTransform,
+    only_flags: bool,
+) -> Option<usize> {
+    Some(match tiles {
         MapTileLayerTiles::Design(tiles) => {
-            mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+            transform_tiles(tp, w, tiles, transform, only_flags, |tile| {
+                transform.design(tile)
+            })
         }
         MapTileLayerTiles::Physics(ty) => match ty {
-            MapTileLayerPhysicsTiles::Arbitrary(_) => panic!("not implemented"),
+            MapTileLayerPhysicsTiles::Arbitrary(_) => return None,
             MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
-                mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+                transform_tiles(tp, w, tiles, transform, only_flags, |tile| {
+                    transform.physics(tile)
+                })
             }
             MapTileLayerPhysicsTiles::Tele(tiles) => {
-                mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+                transform_tiles(tp, w, tiles, transform, only_flags, |tile| {
+                    transform.physics(tile)
+                })
             }
             MapTileLayerPhysicsTiles::Speedup(tiles) => {
-                mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+                transform_tiles(tp, w, tiles, transform, only_flags, |tile| {
+                    transform.speedup(tile)
+                })
             }
             MapTileLayerPhysicsTiles::Switch(tiles) => {
-                mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+                transform_tiles(tp, w, tiles, transform, only_flags, |tile| {
+                    transform.physics(tile)
+                })
             }
             MapTileLayerPhysicsTiles::Tune(tiles) => {
-                mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+                transform_tiles(tp, w, tiles, transform, only_flags, |tile| {
+                    transform.physics(tile)
+                })
             }
         },
-    }
+    })
+}
 
-    if upload_new_layer {
-        upload_brush(tp, graphics_mt, buffer_object_handle, backend_handle, brush);
+fn transform_brush(
+    tp: &Arc<rayon::ThreadPool>,
+    brush: &mut TileBrushTiles,
+    transform: TileTransform,
+    only_flags: bool,
+) {
+    let w = brush.w.get() as usize;
+    if transform_map_tiles(tp, w, &mut brush.tiles, transform, only_flags).is_none() {
+        panic!("not implemented");
     }
 }
 
-fn mirror_x_tiles<T: Copy + Clone + Send + Sync + AsMut<TileBase>>(
+pub fn mirror_tiles_y(
     tp: &Arc<rayon::ThreadPool>,
-    w: usize,
-    tiles: &mut [T],
+    graphics_mt: &GraphicsMultiThreaded,
+    buffer_object_handle: &GraphicsBufferObjectHandle,
+    backend_handle: &GraphicsBackendHandle,
+    brush: &mut TileBrushTiles,
+    upload_new_layer: bool,
 ) {
-    tp.install(|| {
-        tiles
-            .par_chunks_exact_mut(w)
-            .for_each(|chunk| chunk.reverse());
-        tiles
-            .par_iter_mut()
-            .for_each(|tile| tile.as_mut().flags.toggle(TileFlags::XFLIP));
-    });
+    transform_brush(tp, brush, TileTransform::MirrorY, false);
+
+    if upload_new_layer {
+        upload_brush(tp, graphics_mt, buffer_object_handle, backend_handle, brush);
+    }
 }
 
 pub fn mirror_tiles_x(
@@ -113,29 +259,7 @@ pub fn mirror_tiles_x(
     brush: &mut TileBrushTiles,
     upload_new_layer: bool,
 ) {
-    match &mut brush.tiles {
-        MapTileLayerTiles::Design(tiles) => {
-            mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-        }
-        MapTileLayerTiles::Physics(ty) => match ty {
-            MapTileLayerPhysicsTiles::Arbitrary(_) => panic!("not implemented"),
-            MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
-                mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-            }
-            MapTileLayerPhysicsTiles::Tele(tiles) => {
-                mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-            }
-            MapTileLayerPhysicsTiles::Speedup(tiles) => {
-                mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-            }
-            MapTileLayerPhysicsTiles::Switch(tiles) => {
-                mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-            }
-            MapTileLayerPhysicsTiles::Tune(tiles) => {
-                mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-            }
-        },
-    }
+    transform_brush(tp, brush, TileTransform::MirrorX, false);
 
     if upload_new_layer {
         upload_brush(tp, graphics_mt, buffer_object_handle, backend_handle, brush);
@@ -150,58 +274,7 @@ pub fn rotate_tiles_plus_90(
     brush: &mut TileBrushTiles,
     upload_new_layer: bool,
 ) {
-    fn rotate_tiles<T: Copy + Clone + Send + Sync + AsMut<TileBase>>(
-        tp: &Arc<rayon::ThreadPool>,
-        w: usize,
-        tiles: &mut Vec<T>,
-    ) {
-        let h = tiles.len() / w;
-        let mut new_tiles = tiles.clone();
-
-        tp.install(|| {
-            // transpose
-            new_tiles
-                .par_iter_mut()
-                .enumerate()
-                .for_each(|(index, tile)| {
-                    let old_index = (index % h) * w + (index / h);
-                    *tile = tiles[old_index];
-                });
-            // reverse
-            new_tiles
-                .par_chunks_exact_mut(h)
-                .for_each(|chunk| chunk.reverse());
-
-            new_tiles
-                .par_iter_mut()
-                .for_each(|tile| rotate_by_plus_90(&mut tile.as_mut().flags));
-        });
-        *tiles = new_tiles;
-    }
-
-    match &mut brush.tiles {
-        MapTileLayerTiles::Design(tiles) => {
-            rotate_tiles(tp, brush.w.get() as usize, tiles);
-        }
-        MapTileLayerTiles::Physics(ty) => match ty {
-            MapTileLayerPhysicsTiles::Arbitrary(_) => panic!("not implemented"),
-            MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
-                rotate_tiles(tp, brush.w.get() as usize, tiles);
-            }
-            MapTileLayerPhysicsTiles::Tele(tiles) => {
-                rotate_tiles(tp, brush.w.get() as usize, tiles);
-            }
-            MapTileLayerPhysicsTiles::Speedup(tiles) => {
-                rotate_tiles(tp, brush.w.get() as usize, tiles);
-            }
-            MapTileLayerPhysicsTiles::Switch(tiles) => {
-                rotate_tiles(tp, brush.w.get() as usize, tiles);
-            }
-            MapTileLayerPhysicsTiles::Tune(tiles) => {
-                rotate_tiles(tp, brush.w.get() as usize, tiles);
-            }
-        },
-    }
+    transform_brush(tp, brush, TileTransform::RotatePlus90, false);
 
     let off_x = brush.negative_offsetf.x;
     let off_y = brush.negative_offsetf.y;
@@ -231,40 +304,7 @@ pub fn rotate_tile_flags_plus_90(
     brush: &mut TileBrushTiles,
     upload_new_layer: bool,
 ) {
-    fn rotate_tiles<T: Copy + Clone + Send + Sync + AsMut<TileBase>>(
-        tp: &Arc<rayon::ThreadPool>,
-        tiles: &mut Vec<T>,
-    ) {
-        tp.install(|| {
-            tiles
-                .par_iter_mut()
-                .for_each(|tile| rotate_by_plus_90(&mut tile.as_mut().flags));
-        });
-    }
-
-    match &mut brush.tiles {
-        MapTileLayerTiles::Design(tiles) => {
-            rotate_tiles(tp, tiles);
-        }
-        MapTileLayerTiles::Physics(ty) => match ty {
-            MapTileLayerPhysicsTiles::Arbitrary(_) => panic!("not implemented"),
-            MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
-                rotate_tiles(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Tele(tiles) => {
-                rotate_tiles(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Speedup(tiles) => {
-                rotate_tiles(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Switch(tiles) => {
-                rotate_tiles(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Tune(tiles) => {
-                rotate_tiles(tp, tiles);
-            }
-        },
-    }
+    transform_brush(tp, brush, TileTransform::RotatePlus90, true);
 
     if upload_new_layer {
         upload_brush(tp, graphics_mt, buffer_object_handle, backend_handle, brush);
@@ -289,154 +329,241 @@ fn upload_brush(
     );
 }
 
-pub fn mirror_layer_tiles_y(
+/// Transforms the tiles of `range` inside of the layer.
+///
+/// `area` is the area that is changed by the transform, it starts at the same position
+/// as the range, but a rotated range might cover other tiles than the original one.
+/// Tiles of the range that are not covered after the transform are cleared.
+fn transform_area<T: Copy + Default + Send + Sync>(
+    tp: &Arc<rayon::ThreadPool>,
+    tiles: &[T],
+    full_width: usize,
+    range: &TileSelectionRange,
+    area: &TileSelectionRange,
+    transform: TileTransform,
+    only_flags: bool,
+    transform_tile: impl Fn(&mut T) + Send + Sync,
+) -> (Vec<T>, Vec<T>) {
+    let collect = |w: usize, h: usize| -> Vec<T> {
+        tiles
+            .chunks_exact(full_width)
+            .skip(range.y as usize)
+            .take(h)
+            .flat_map(|tiles| {
+                tiles[range.x as usize..range.x as usize + w]
+                    .iter()
+                    .copied()
+            })
+            .collect()
+    };
+    let w = range.w.get() as usize;
+    let h = range.h.get() as usize;
+    let area_w = area.w.get() as usize;
+    let area_h = area.h.get() as usize;
+
+    let old_tiles = collect(area_w, area_h);
+    let mut range_tiles = collect(w, h);
+    let new_w = transform_tiles(
+        tp,
+        w,
+        &mut range_tiles,
+        transform,
+        only_flags,
+        transform_tile,
+    );
+
+    let mut new_tiles = old_tiles.clone();
+    new_tiles
+        .chunks_exact_mut(area_w)
+        .take(h)
+        .for_each(|tiles| tiles[..w].fill(T::default()));
+    new_tiles
+        .chunks_exact_mut(area_w)
+        .zip(range_tiles.chunks_exact(new_w))
+        .for_each(|(tiles, range_tiles)| {
+            let len = new_w.min(area_w);
+            tiles[..len].copy_from_slice(&range_tiles[..len]);
+        });
+    (old_tiles, new_tiles)
+}
+
+/// Transforms the selected tiles of the layer.
+///
+/// Returns the range the tiles cover after the transform,
+/// which differs from the selection for rotations.
+fn transform_layer_tiles(
     tp: &Arc<rayon::ThreadPool>,
     layer: EditorLayerUnionRef,
     range: &TileSelectionRange,
     client: &mut EditorClient,
-) {
-    fn get_mirror_tiles<T: Copy + Clone + Send + Sync + AsMut<TileBase>>(
-        tp: &Arc<rayon::ThreadPool>,
-        x: usize,
-        y: usize,
-        w: usize,
-        h: usize,
-        full_width: usize,
-        tiles: &[T],
-    ) -> (Vec<T>, Vec<T>)
-    where
-        Vec<T>: rayon::iter::FromParallelIterator<T>,
-    {
-        tp.install(|| {
-            let old_tiles: Vec<T> = tiles
-                .par_chunks_exact(full_width)
-                .skip(y)
-                .take(h)
-                .flat_map(|tiles| tiles.par_iter().skip(x).take(w).map(|tile| *tile))
-                .collect();
-            let mut new_tiles = old_tiles.clone();
-            mirror_y_tiles(tp, w, &mut new_tiles);
-            (old_tiles, new_tiles)
-        })
-    }
+    transform: TileTransform,
+    only_flags: bool,
+) -> TileSelectionRange {
+    let (layer_width, layer_height) = layer.get_width_and_height();
+    let max_w = layer_width.get() - range.x;
+    let max_h = layer_height.get() - range.y;
+    let (new_range, area) = if transform.is_rotation() && !only_flags {
+        let size = range.w.get().max(range.h.get());
+        (
+            TileSelectionRange {
+                x: range.x,
+                y: range.y,
+                w: NonZeroU16MinusOne::new(range.h.get().min(max_w)).unwrap(),
+                h: NonZeroU16MinusOne::new(range.w.get().min(max_h)).unwrap(),
+            },
+            TileSelectionRange {
+                x: range.x,
+                y: range.y,
+                w: NonZeroU16MinusOne::new(size.min(max_w)).unwrap(),
+                h: NonZeroU16MinusOne::new(size.min(max_h)).unwrap(),
+            },
+        )
+    } else {
+        (
+            TileSelectionRange { ..*range },
+            TileSelectionRange { ..*range },
+        )
+    };
+    let full_width = layer_width.get() as usize;
 
     let (old_tiles, new_tiles) = match &layer {
-        EditorLayerUnionRef::Physics {
-            layer, group_attr, ..
-        } => match layer {
-            EditorPhysicsLayer::Arbitrary(_) => panic!("not implemented"),
-            EditorPhysicsLayer::Game(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Game(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Game(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Front(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Front(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Front(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Tele(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.base.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tele(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tele(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Speedup(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Speedup(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Speedup(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Switch(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.base.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Switch(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Switch(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Tune(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.base.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tune(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tune(new_tiles)),
-                )
-            }
-        },
+        EditorLayerUnionRef::Physics { layer, .. } => {
+            let (old_tiles, new_tiles) = match layer {
+                EditorPhysicsLayer::Arbitrary(_) => panic!("not implemented"),
+                EditorPhysicsLayer::Game(layer) => {
+                    let (old_tiles, new_tiles) = transform_area(
+                        tp,
+                        &layer.layer.tiles,
+                        full_width,
+                        range,
+                        &area,
+                        transform,
+                        only_flags,
+                        |tile| transform.physics(tile),
+                    );
+                    (
+                        MapTileLayerPhysicsTiles::Game(old_tiles),
+                        MapTileLayerPhysicsTiles::Game(new_tiles),
+                    )
+                }
+                EditorPhysicsLayer::Front(layer) => {
+                    let (old_tiles, new_tiles) = transform_area(
+                        tp,
+                        &layer.layer.tiles,
+                        full_width,
+                        range,
+                        &area,
+                        transform,
+                        only_flags,
+                        |tile| transform.physics(tile),
+                    );
+                    (
+                        MapTileLayerPhysicsTiles::Front(old_tiles),
+                        MapTileLayerPhysicsTiles::Front(new_tiles),
+                    )
+                }
+                EditorPhysicsLayer::Tele(layer) => {
+                    let (old_tiles, new_tiles) = transform_area(
+                        tp,
+                        &layer.layer.base.tiles,
+                        full_width,
+                        range,
+                        &area,
+                        transform,
+                        only_flags,
+                        |tile| transform.physics(tile),
+                    );
+                    (
+                        MapTileLayerPhysicsTiles::Tele(old_tiles),
+                        MapTileLayerPhysicsTiles::Tele(new_tiles),
+                    )
+                }
+                EditorPhysicsLayer::Speedup(layer) => {
+                    let (old_tiles, new_tiles) = transform_area(
+                        tp,
+                        &layer.layer.tiles,
+                        full_width,
+                        range,
+                        &area,
+                        transform,
+                        only_flags,
+                        |tile| transform.speedup(tile),
+                    );
+                    (
+                        MapTileLayerPhysicsTiles::Speedup(old_tiles),
+                        MapTileLayerPhysicsTiles::Speedup(new_tiles),
+                    )
+                }
+                EditorPhysicsLayer::Switch(layer) => {
+                    let (old_tiles, new_tiles) = transform_area(
+                        tp,
+                        &layer.layer.base.tiles,
+                        full_width,
+                        range,
+                        &area,
+                        transform,
+                        only_flags,
+                        |tile| transform.physics(tile),
+                    );
+                    (
+                        MapTileLayerPhysicsTiles::Switch(old_tiles),
+                        MapTileLayerPhysicsTiles::Switch(new_tiles),
+                    )
+                }
+                EditorPhysicsLayer::Tune(layer) => {
+                    let (old_tiles, new_tiles) = transform_area(
+                        tp,
+                        &layer.layer.base.tiles,
+                        full_width,
+                        range,
+                        &area,
+                        transform,
+                        only_flags,
+                        |tile| transform.physics(tile),
+                    );
+                    (
+                        MapTileLayerPhysicsTiles::Tune(old_tiles),
+                        MapTileLayerPhysicsTiles::Tune(new_tiles),
+                    )
+                }
+            };
+            (
+                MapTileLayerTiles::Physics(old_tiles),
+                MapTileLayerTiles::Physics(new_tiles),
+            )
+        }
         EditorLayerUnionRef::Design { layer, .. } => {
             if let EditorLayer::Tile(layer) = layer {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
+                let (old_tiles, new_tiles) = transform_area(
                     tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    layer.layer.attr.width.get() as usize,
                     &layer.layer.tiles,
+                    full_width,
+                    range,
+                    &area,
+                    transform,
+                    only_flags,
+                    |tile| transform.design(tile),
                 );
                 (
                     MapTileLayerTiles::Design(old_tiles),
                     MapTileLayerTiles::Design(new_tiles),
                 )
             } else {
-                return;
+                return new_range;
             }
         }
     };
-    generate_client_action(layer, range, client, old_tiles, new_tiles);
+    generate_client_action(layer, &area, client, old_tiles, new_tiles);
+    new_range
+}
+
+pub fn mirror_layer_tiles_y(
+    tp: &Arc<rayon::ThreadPool>,
+    layer: EditorLayerUnionRef,
+    range: &TileSelectionRange,
+    client: &mut EditorClient,
+) {
+    transform_layer_tiles(tp, layer, range, client, TileTransform::MirrorY, false);
 }
 
 pub fn mirror_layer_tiles_x(
@@ -445,300 +572,41 @@ pub fn mirror_layer_tiles_x(
     range: &TileSelectionRange,
     client: &mut EditorClient,
 ) {
-    fn get_mirror_tiles<T: Copy + Clone + Send + Sync + AsMut<TileBase>>(
-        tp: &Arc<rayon::ThreadPool>,
-        x: usize,
-        y: usize,
-        w: usize,
-        h: usize,
-        full_width: usize,
-        tiles: &[T],
-    ) -> (Vec<T>, Vec<T>)
-    where
-        Vec<T>: rayon::iter::FromParallelIterator<T>,
-    {
-        tp.install(|| {
-            let old_tiles: Vec<T> = tiles
-                .par_chunks_exact(full_width)
-                .skip(y)
-                .take(h)
-                .flat_map(|tiles| tiles.par_iter().skip(x).take(w).map(|tile| *tile))
-                .collect();
-            let mut new_tiles = old_tiles.clone();
-            mirror_x_tiles(tp, w, &mut new_tiles);
-            (old_tiles, new_tiles)
-        })
-    }
+    transform_layer_tiles(tp, layer, range, client, TileTransform::MirrorX, false);
+}
 
-    let (old_tiles, new_tiles) = match &layer {
-        EditorLayerUnionRef::Physics {
-            layer, group_attr, ..
-        } => match layer {
-            EditorPhysicsLayer::Arbitrary(_) => panic!("not implemented"),
-            EditorPhysicsLayer::Game(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Game(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Game(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Front(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Front(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Front(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Tele(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.base.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tele(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tele(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Speedup(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Speedup(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Speedup(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Switch(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.base.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Switch(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Switch(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Tune(layer) => {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.base.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tune(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tune(new_tiles)),
-                )
-            }
+/// Rotates the selected tiles including their positions,
+/// the rotated tiles still start at the top left corner of the selection.
+///
+/// Returns the new selection.
+pub fn rotate_layer_tiles(
+    tp: &Arc<rayon::ThreadPool>,
+    layer: EditorLayerUnionRef,
+    range: &TileSelectionRange,
+    client: &mut EditorClient,
+    clockwise: bool,
+) -> TileSelectionRange {
+    transform_layer_tiles(
+        tp,
+        layer,
+        range,
+        client,
+        if clockwise {
+            TileTransform::RotatePlus90
+        } else {
+            TileTransform::RotateMinus90
         },
-        EditorLayerUnionRef::Design { layer, .. } => {
-            if let EditorLayer::Tile(layer) = layer {
-                let (old_tiles, new_tiles) = get_mirror_tiles(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    layer.layer.attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Design(old_tiles),
-                    MapTileLayerTiles::Design(new_tiles),
-                )
-            } else {
-                return;
-            }
-        }
-    };
-    generate_client_action(layer, range, client, old_tiles, new_tiles);
+        false,
+    )
 }
 
-pub fn rotate_layer_tiles_plus_90(
+pub fn rotate_layer_tile_flags_plus_90(
     tp: &Arc<rayon::ThreadPool>,
     layer: EditorLayerUnionRef,
     range: &TileSelectionRange,
     client: &mut EditorClient,
 ) {
-    fn rotate_tiles_flags<T: Copy + Clone + Send + Sync + AsMut<TileBase>>(
-        tp: &Arc<rayon::ThreadPool>,
-        x: usize,
-        y: usize,
-        w: usize,
-        h: usize,
-        full_width: usize,
-        tiles: &[T],
-    ) -> (Vec<T>, Vec<T>)
-    where
-        Vec<T>: rayon::iter::FromParallelIterator<T>,
-    {
-        tp.install(|| {
-            let old_tiles: Vec<T> = tiles
-                .par_chunks_exact(full_width)
-                .skip(y)
-                .take(h)
-                .flat_map(|tiles| tiles.par_iter().skip(x).take(w).map(|tile| *tile))
-                .collect();
-            let mut new_tiles = old_tiles.clone();
-            new_tiles
-                .iter_mut()
-                .for_each(|tile| rotate_by_plus_90(&mut tile.as_mut().flags));
-            (old_tiles, new_tiles)
-        })
-    }
-
-    let (old_tiles, new_tiles) = match &layer {
-        EditorLayerUnionRef::Physics {
-            layer, group_attr, ..
-        } => match layer {
-            EditorPhysicsLayer::Arbitrary(_) => panic!("not implemented"),
-            EditorPhysicsLayer::Game(layer) => {
-                let (old_tiles, new_tiles) = rotate_tiles_flags(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Game(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Game(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Front(layer) => {
-                let (old_tiles, new_tiles) = rotate_tiles_flags(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Front(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Front(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Tele(layer) => {
-                let (old_tiles, new_tiles) = rotate_tiles_flags(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.base.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tele(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tele(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Speedup(layer) => {
-                let (old_tiles, new_tiles) = rotate_tiles_flags(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Speedup(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Speedup(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Switch(layer) => {
-                let (old_tiles, new_tiles) = rotate_tiles_flags(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.base.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Switch(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Switch(new_tiles)),
-                )
-            }
-            EditorPhysicsLayer::Tune(layer) => {
-                let (old_tiles, new_tiles) = rotate_tiles_flags(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    group_attr.width.get() as usize,
-                    &layer.layer.base.tiles,
-                );
-                (
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tune(old_tiles)),
-                    MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Tune(new_tiles)),
-                )
-            }
-        },
-        EditorLayerUnionRef::Design { layer, .. } => {
-            if let EditorLayer::Tile(layer) = layer {
-                let (old_tiles, new_tiles) = rotate_tiles_flags(
-                    tp,
-                    range.x as usize,
-                    range.y as usize,
-                    range.w.get() as usize,
-                    range.h.get() as usize,
-                    layer.layer.attr.width.get() as usize,
-                    &layer.layer.tiles,
-                );
-                (
-                    MapTileLayerTiles::Design(old_tiles),
-                    MapTileLayerTiles::Design(new_tiles),
-                )
-            } else {
-                return;
-            }
-        }
-    };
-    generate_client_action(layer, range, client, old_tiles, new_tiles);
+    transform_layer_tiles(tp, layer, range, client, TileTransform::RotatePlus90, true);
 }
 
 fn generate_client_action(
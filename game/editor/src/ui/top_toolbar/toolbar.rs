@@ -2,15 +2,22 @@ use egui::Button;
 use ui_base::types::UiRenderPipe;
 
 use crate::{
-    explain::{TEXT_QUAD_SELECTION, TEXT_TILE_BRUSH, TEXT_TILE_BRUSH_MIRROR},
+    explain::{
+        TEXT_QUAD_SELECTION, TEXT_TILE_BRUSH, TEXT_TILE_BRUSH_FILL, TEXT_TILE_BRUSH_FLAGS,
+        TEXT_TILE_BRUSH_MIRROR, TEXT_TILE_SELECTION_ROTATE,
+    },
     map::EditorMapInterface,
-    tools::tool::{ActiveTool, ActiveToolQuads, ActiveToolSounds, ActiveToolTiles},
+    tools::{
+        tile_layer::brush::TileBrushFill,
+        tool::{ActiveTool, ActiveToolQuads, ActiveToolSounds, ActiveToolTiles},
+    },
     ui::{user_data::UserDataWithTab, utils::icon_font_text},
 };
 
 use super::tile_mirror::{
     mirror_layer_tiles_x, mirror_layer_tiles_y, mirror_tiles_x, mirror_tiles_y,
-    rotate_layer_tiles_plus_90, rotate_tile_flags_plus_90, rotate_tiles_plus_90,
+    rotate_layer_tile_flags_plus_90, rotate_layer_tiles, rotate_tile_flags_plus_90,
+    rotate_tiles_plus_90,
 };
 
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, main_frame_only: bool) {
@@ -262,25 +269,136 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, main_
                                                     );
                                                 }
                                             }
-                                        }
-                                        ActiveToolTiles::Selection => {
-                                            if let Some(layer) =
-                                                pipe.user_data.editor_tab.map.active_layer()
+                                            // only paint the flags of the brush
+                                            let btn = Button::new(icon_font_text(ui, "\u{f1fc}"))
+                                                .selected(tools.tiles.brush.paint_flags_only);
+                                            if ui
+                                                .add(btn)
+                                                .on_hover_ui(|ui| {
+                                                    let mut cache =
+                                                        egui_commonmark::CommonMarkCache::default();
+                                                    egui_commonmark::CommonMarkViewer::new(
+                                                        "tile-brush-flags-tooltip",
+                                                    )
+                                                    .show(ui, &mut cache, TEXT_TILE_BRUSH_FLAGS);
+                                                })
+                                                .clicked()
                                             {
-                                                // rotate inner tiles (flags) by 90°
-                                                let btn =
-                                                    Button::new(icon_font_text(ui, "\u{e4f6}"));
-                                                if ui.add(btn).clicked() {
-                                                    if let Some(range) =
-                                                        &tools.tiles.selection.range
-                                                    {
-                                                        rotate_layer_tiles_plus_90(
-                                                            pipe.user_data.tp,
-                                                            layer,
-                                                            range,
-                                                            &mut pipe.user_data.editor_tab.client,
-                                                        );
+                                                tools.tiles.brush.paint_flags_only =
+                                                    !tools.tiles.brush.paint_flags_only;
+                                            }
+                                            // how shift + drag fills the rect
+                                            let fill = &mut tools.tiles.brush.fill;
+                                            egui::ComboBox::new("tile-brush-fill", "")
+                                                .selected_text(match fill {
+                                                    TileBrushFill::Repeat => "Repeat",
+                                                    TileBrushFill::Gradient { .. } => "Gradient",
+                                                    TileBrushFill::Noise { .. } => "Noise",
+                                                })
+                                                .show_ui(ui, |ui| {
+                                                    if ui.button("Repeat").clicked() {
+                                                        *fill = TileBrushFill::Repeat;
+                                                    }
+                                                    if ui.button("Gradient").clicked() {
+                                                        *fill = TileBrushFill::Gradient {
+                                                            vertical: false,
+                                                        };
+                                                    }
+                                                    if ui.button("Noise").clicked() {
+                                                        *fill = TileBrushFill::Noise {
+                                                            density: 0.25,
+                                                            seed: 0,
+                                                        };
                                                     }
+                                                })
+                                                .response
+                                                .on_hover_ui(|ui| {
+                                                    let mut cache =
+                                                        egui_commonmark::CommonMarkCache::default();
+                                                    egui_commonmark::CommonMarkViewer::new(
+                                                        "tile-brush-fill-tooltip",
+                                                    )
+                                                    .show(ui, &mut cache, TEXT_TILE_BRUSH_FILL);
+                                                });
+                                            match fill {
+                                                TileBrushFill::Repeat => {}
+                                                TileBrushFill::Gradient { vertical } => {
+                                                    ui.checkbox(vertical, "Vertical");
+                                                }
+                                                TileBrushFill::Noise { density, seed } => {
+                                                    ui.add(
+                                                        egui::Slider::new(density, 0.0..=1.0)
+                                                            .text("Density"),
+                                                    );
+                                                    ui.add(
+                                                        egui::DragValue::new(seed).prefix("Seed: "),
+                                                    );
+                                                }
+                                            }
+                                        }
+                                        ActiveToolTiles::Selection => {
+                                            // rotate -90°
+                                            let btn = Button::new(icon_font_text(ui, "\u{f2ea}"));
+                                            let rotate_minus_90 = ui
+                                                .add(btn)
+                                                .on_hover_ui(|ui| {
+                                                    let mut cache =
+                                                        egui_commonmark::CommonMarkCache::default();
+                                                    egui_commonmark::CommonMarkViewer::new(
+                                                        "tile-selection-rotate-minus-90-tooltip",
+                                                    )
+                                                    .show(
+                                                        ui,
+                                                        &mut cache,
+                                                        TEXT_TILE_SELECTION_ROTATE,
+                                                    );
+                                                })
+                                                .clicked();
+                                            // rotate +90°
+                                            let btn = Button::new(icon_font_text(ui, "\u{f2f9}"));
+                                            let rotate_plus_90 = ui
+                                                .add(btn)
+                                                .on_hover_ui(|ui| {
+                                                    let mut cache =
+                                                        egui_commonmark::CommonMarkCache::default();
+                                                    egui_commonmark::CommonMarkViewer::new(
+                                                        "tile-selection-rotate-plus-90-tooltip",
+                                                    )
+                                                    .show(
+                                                        ui,
+                                                        &mut cache,
+                                                        TEXT_TILE_SELECTION_ROTATE,
+                                                    );
+                                                })
+                                                .clicked();
+                                            if rotate_minus_90 || rotate_plus_90 {
+                                                if let (Some(layer), Some(range)) = (
+                                                    pipe.user_data.editor_tab.map.active_layer(),
+                                                    &tools.tiles.selection.range,
+                                                ) {
+                                                    let range = rotate_layer_tiles(
+                                                        pipe.user_data.tp,
+                                                        layer,
+                                                        range,
+                                                        &mut pipe.user_data.editor_tab.client,
+                                                        rotate_plus_90,
+                                                    );
+                                                    tools.tiles.selection.range = Some(range);
+                                                }
+                                            }
+                                            // rotate inner tiles (flags) by 90°
+                                            let btn = Button::new(icon_font_text(ui, "\u{e4f6}"));
+                                            if ui.add(btn).clicked() {
+                                                if let (Some(layer), Some(range)) = (
+                                                    pipe.user_data.editor_tab.map.active_layer(),
+                                                    &tools.tiles.selection.range,
+                                                ) {
+                                                    rotate_layer_tile_flags_plus_90(
+                                                        pipe.user_data.tp,
+                                                        layer,
+                                                        range,
+                                                        &mut pipe.user_data.editor_tab.client,
+                                                    );
                                                 }
                                             }
                                         }
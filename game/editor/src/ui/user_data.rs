@@ -15,8 +15,12 @@ use graphics::{
 };
 
 use crate::{
+    map_templates::EditorMapTemplate,
     tab::EditorTab,
-    tools::{tile_layer::auto_mapper::TileLayerAutoMapper, tool::Tools},
+    tools::{
+        collab::EditorCollab, map_diff::EditorMapDiff, map_search::EditorMapSearch,
+        prefabs::EditorPrefabs, tile_layer::auto_mapper::TileLayerAutoMapper, tool::Tools,
+    },
     utils::UiCanvasSize,
 };
 
@@ -31,6 +35,10 @@ pub struct EditorUiEventHostMap {
 
 #[derive(Debug)]
 pub enum EditorUiEvent {
+    NewMap {
+        name: String,
+        template: EditorMapTemplate,
+    },
     OpenFile {
         name: PathBuf,
     },
@@ -43,6 +51,14 @@ pub enum EditorUiEvent {
         cert_hash: String,
         password: String,
     },
+    /// saves the current brush as prefab
+    SavePrefab {
+        name: String,
+    },
+    /// makes the prefab the current brush
+    ApplyPrefab {
+        index: usize,
+    },
     Close,
 }
 
@@ -61,6 +77,10 @@ pub enum EditorMenuHostDialogMode {
 
 pub enum EditorMenuDialogMode {
     None,
+    NewMap {
+        name: String,
+        template: EditorMapTemplate,
+    },
     Open {
         file_dialog: Box<FileDialog>,
     },
@@ -78,6 +98,12 @@ pub enum EditorMenuDialogMode {
 }
 
 impl EditorMenuDialogMode {
+    pub fn new_map() -> Self {
+        Self::NewMap {
+            name: "new_map".to_string(),
+            template: Default::default(),
+        }
+    }
     pub fn open(io: &Io) -> Self {
         let mut open_path = io.fs.get_save_path();
         open_path.push("map/maps");
@@ -149,6 +175,10 @@ pub struct UserData<'a> {
     pub menu_dialog_mode: &'a mut EditorMenuDialogMode,
     pub tools: &'a mut Tools,
     pub auto_mapper: &'a mut TileLayerAutoMapper,
+    pub prefabs: &'a mut EditorPrefabs,
+    pub map_diff: &'a mut EditorMapDiff,
+    pub map_search: &'a mut EditorMapSearch,
+    pub collab: &'a mut EditorCollab,
     pub pointer_is_used: &'a mut bool,
     pub io: &'a Io,
 
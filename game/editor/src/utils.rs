@@ -42,3 +42,36 @@ pub fn ui_pos_to_world_pos(
 
     vec2::new(x, y)
 }
+
+/// The inverse of [`ui_pos_to_world_pos`].
+pub fn world_pos_to_ui_pos(
+    canvas_handle: &GraphicsCanvasHandle,
+    ui_canvas: &UiCanvasSize,
+    zoom: f32,
+    inp: vec2,
+    center_x: f32,
+    center_y: f32,
+    offset_x: f32,
+    offset_y: f32,
+    parallax_x: f32,
+    parallax_y: f32,
+) -> vec2 {
+    let points = RenderTools::canvas_points_of_group_attr(
+        canvas_handle,
+        center_x,
+        center_y,
+        parallax_x,
+        parallax_y,
+        offset_x,
+        offset_y,
+        zoom,
+    );
+
+    let size = ui_canvas
+        .size()
+        .clamp(vec2(0.01, 0.01), vec2(f32::MAX, f32::MAX));
+    let x_ratio = (inp.x - points[0]) / (points[2] - points[0]);
+    let y_ratio = (inp.y - points[1]) / (points[3] - points[1]);
+
+    vec2::new(x_ratio * size.x, y_ratio * size.y)
+}
@@ -34,6 +34,13 @@ pub struct ConfigDummy {
     /// To where the mini screen is anchored.
     #[default = ConfigDummyScreenAnchor::TopRight]
     pub screen_anchor: ConfigDummyScreenAnchor,
+    /// Play with the dummy as a second local player on the same screen.
+    /// The screen is split in two halves, each with its own camera & hud.
+    /// The dummy is controlled with the keyboard binds of the dummy profile
+    /// (never copied from the main profile) merged over the split screen defaults,
+    /// see [`ConfigPlayer::split_screen_binds`]. The mouse stays with the main player.
+    #[default = false]
+    pub split_screen: bool,
 }
 
 #[config_default]
@@ -125,6 +132,19 @@ pub struct ConfigSpatialChat {
     /// don't have an account.
     #[default = false]
     pub from_non_account_users: bool,
+    /// Mute the voices of all players,
+    /// priority speakers (e.g. casters) are still heard.
+    #[default = false]
+    pub mute_all: bool,
+    /// The volume of the game sounds while someone speaks.
+    /// 1.0 keeps the game sounds at full volume.
+    #[conf_valid(range(min = 0.0, max = 1.0))]
+    #[default = 0.5]
+    pub game_sound_ducking: f64,
+    /// The volume of all other voices while a priority speaker speaks.
+    #[conf_valid(range(min = 0.0, max = 1.0))]
+    #[default = 0.3]
+    pub priority_speaker_ducking: f64,
     /// Users with an account that are permanentally muted. The key
     /// is the account id as string
     pub account_players: HashMap<String, ConfigSpatialChatPerPlayerOptions>,
@@ -159,8 +179,153 @@ pub struct ConfigDemoRecorder {
     /// "Constant Rate Factor" for x264.
     /// Where 0 is lossless and 51 is the worst.
     /// 18 is default.
+    /// Only used if `bitrate` is `0`.
     #[default = 18]
     pub crf: u8,
+    /// The target bitrate of the video in kbit/s.
+    /// `0` uses the constant quality of `crf` instead.
+    #[default = 0]
+    pub bitrate: u64,
+    /// The video codec, `h264` or `hevc`.
+    #[default = "h264"]
+    pub codec: String,
+    /// The directory videos are written to.
+    /// Relative paths are inside the config directory.
+    #[default = "videos"]
+    pub path: String,
+}
+
+#[config_default]
+#[derive(Debug, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigLowBandwidth {
+    /// Reduce the bandwidth usage, e.g. for mobile connections or hotspots.
+    #[default = false]
+    pub active: bool,
+    /// Request a snapshot only every n-th tick.
+    #[conf_valid(range(min = 1, max = 25))]
+    #[default = 4]
+    pub ticks_per_snapshot: u64,
+    /// Let the server compress packets more aggressively.
+    #[default = true]
+    pub high_compression: bool,
+    /// Only receive events relevant for the game play,
+    /// purely visual events of other players are skipped.
+    #[default = true]
+    pub reduced_events: bool,
+    /// Resources like skins that are bigger than this (in KiB)
+    /// are not downloaded automatically.
+    #[conf_valid(range(min = 1, max = 65536))]
+    #[default = 256]
+    pub resource_download_size_limit: u64,
+}
+
+#[config_default]
+#[derive(Debug, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigHighlights {
+    /// Save a screenshot when the local player killed
+    /// multiple players in a short time.
+    #[default = false]
+    pub multi_kill: bool,
+    /// How many kills count as multi kill.
+    #[conf_valid(range(min = 2, max = 10))]
+    #[default = 3]
+    pub multi_kill_count: u64,
+    /// Save a screenshot when the local player captured a flag.
+    #[default = false]
+    pub flag_capture: bool,
+    /// Save a screenshot when the local player finished the map.
+    #[default = false]
+    pub finish: bool,
+    /// Also save a demo of the last seconds before a highlight.
+    #[default = false]
+    pub replay: bool,
+    /// How many seconds before a highlight the replay demo covers.
+    #[conf_valid(range(min = 5, max = 60))]
+    #[default = 15]
+    pub replay_secs: u64,
+}
+
+#[config_default]
+#[derive(Debug, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigRecordingControl {
+    /// Send game events (match start & end, round start & end,
+    /// deaths of the local player) as JSON over a local websocket,
+    /// so streaming software can switch scenes or start & stop recordings.
+    #[default = false]
+    pub enabled: bool,
+    /// The port of the websocket, it only listens on localhost
+    /// and only accepts web pages of localhost.
+    #[conf_valid(range(min = 1, max = 65535))]
+    #[default = 4456]
+    pub port: u64,
+}
+
+#[config_default]
+#[derive(Debug, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigDemoSplit {
+    /// Continue the recording in a new demo file after this many minutes.
+    /// 0 means no limit.
+    #[default = 0]
+    pub max_minutes: u64,
+    /// Continue the recording in a new demo file once the current one
+    /// is bigger than this many MiB. 0 means no limit.
+    #[default = 0]
+    pub max_size_mib: u64,
+}
+
+#[config_default]
+#[derive(Debug, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigHudElement {
+    /// Horizontal offset from the default position,
+    /// relative to the screen width.
+    #[conf_valid(range(min = -1.0, max = 1.0))]
+    #[default = 0.0]
+    pub x: f64,
+    /// Vertical offset from the default position,
+    /// relative to the screen height.
+    #[conf_valid(range(min = -1.0, max = 1.0))]
+    #[default = 0.0]
+    pub y: f64,
+    /// Scale of the element.
+    #[conf_valid(range(min = 0.25, max = 4.0))]
+    #[default = 1.0]
+    pub scale: f64,
+}
+
+#[config_default]
+#[derive(Debug, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigHudLayout {
+    /// The health, armor & ammo bar.
+    pub health_ammo: ConfigHudElement,
+    /// The kill & race finish feed.
+    pub killfeed: ConfigHudElement,
+    /// The chat box.
+    pub chat: ConfigHudElement,
+    /// The scoreboard.
+    pub scoreboard: ConfigHudElement,
+}
+
+/// The layout of the keyboard.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, ConfigInterface, PartialEq, Eq)]
+pub enum ConfigKeyboardLayout {
+    /// The layout that is detected from pressed keys,
+    /// see [`ConfigClient::detected_keyboard_layout`].
+    #[default]
+    Auto,
+    Qwerty,
+    Azerty,
+    Qwertz,
+}
+
+/// How much of the measured jitter & packet loss the prediction margin covers.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, ConfigInterface, PartialEq, Eq)]
+pub enum ConfigPredictionMargin {
+    /// Lowest latency, but inputs are more likely to arrive too late.
+    Aggressive,
+    #[default]
+    Balanced,
+    /// More latency, but fewer mispredictions on unstable connections.
+    Safe,
 }
 
 #[config_default]
@@ -185,6 +350,71 @@ pub struct ConfigClient {
     pub spatial_chat: ConfigSpatialChat,
     /// Configurations for the demo video encoder.
     pub recorder: ConfigDemoRecorder,
+    /// If not empty, recorded demos are encrypted with this passphrase
+    /// and can only be played back with it.
+    #[default = ""]
+    pub demo_passphrase: String,
+    /// Split long recordings into multiple demo files.
+    pub demo_split: ConfigDemoSplit,
+    /// Also record the inputs of the local players in demos.
+    /// Allows exact replays & input analysis, but makes demos bigger.
+    #[default = false]
+    pub demo_record_inputs: bool,
+    /// Url of a resource server that missing maps & game mods of
+    /// played back demos are downloaded from. Empty disables downloads.
+    #[default = ""]
+    pub demo_resource_server: String,
+    /// Automatically save screenshots of highlights.
+    pub highlights: ConfigHighlights,
+    /// Control streaming & recording software by game events.
+    pub recording_control: ConfigRecordingControl,
+    /// Chat messages that binds send by name, e.g. `bind f3 chat_template gg`.
+    /// The variables `{name}`, `{last_killer}` & `{last_victim}`
+    /// are replaced when the message is sent.
+    pub chat_templates: HashMap<String, String>,
+    /// Settings to reduce the bandwidth usage.
+    pub low_bandwidth: ConfigLowBandwidth,
+    /// Positions & scales of the HUD elements.
+    pub hud_layout: ConfigHudLayout,
+    /// The keyboard layout, see [`Self::active_keyboard_layout`].
+    /// Binds use the position of a key, the layout is only used to
+    /// show keys like they are printed on the keyboard.
+    #[default = ConfigKeyboardLayout::Auto]
+    pub keyboard_layout: ConfigKeyboardLayout,
+    /// The last keyboard layout that was detected from pressed keys,
+    /// used while [`Self::keyboard_layout`] is `Auto`.
+    #[default = ConfigKeyboardLayout::Qwerty]
+    pub detected_keyboard_layout: ConfigKeyboardLayout,
+    /// Texture memory in MiB that the skins, entities & particles
+    /// may use each. The least recently used assets beyond that are unloaded
+    /// and loaded again when needed. 0 means unlimited.
+    #[conf_valid(range(min = 0, max = 8192))]
+    #[default = 256]
+    pub texture_memory_budget: u64,
+    /// How the margin of the client side prediction is calculated.
+    #[default = ConfigPredictionMargin::Balanced]
+    pub prediction_margin: ConfigPredictionMargin,
+    /// Manual offset in milliseconds that is added to the prediction margin,
+    /// e.g. on unstable Wi-Fi a higher margin causes fewer mispredictions.
+    #[conf_valid(range(min = -100, max = 200))]
+    #[default = 0]
+    pub prediction_margin_offset: i64,
+    /// The render module in `mods/render` (without `.wasm`) that renders the game.
+    /// Can be changed while playing. Empty uses the built-in renderer.
+    #[conf_valid(length(max = 64))]
+    #[default = "render_game"]
+    pub render_mod: String,
+}
+
+impl ConfigClient {
+    /// The keyboard layout selected by the user,
+    /// or the detected one while `Auto` is selected.
+    pub fn active_keyboard_layout(&self) -> ConfigKeyboardLayout {
+        match self.keyboard_layout {
+            ConfigKeyboardLayout::Auto => self.detected_keyboard_layout,
+            layout => layout,
+        }
+    }
 }
 
 #[config_default]
@@ -270,6 +500,98 @@ impl ConfigPlayer {
             ..Default::default()
         }
     }
+
+    /// The binds that are used for all keys a profile has no binds for,
+    /// see [`Self::binds_with_defaults`].
+    ///
+    /// Keys are named by their position on a qwerty keyboard,
+    /// so e.g. the movement keys are at the same place on every layout.
+    /// Keys that are picked for their letter, like `y` for the team chat,
+    /// are moved to where the `layout` prints that letter.
+    pub fn default_binds(layout: ConfigKeyboardLayout) -> Vec<String> {
+        let team_chat = match layout {
+            ConfigKeyboardLayout::Auto
+            | ConfigKeyboardLayout::Qwerty
+            | ConfigKeyboardLayout::Azerty => "y",
+            ConfigKeyboardLayout::Qwertz => "z",
+        };
+        [
+            "bind a +left",
+            "bind d +right",
+            "bind space +jump",
+            "bind escape ingame_menu",
+            "bind left +fire",
+            "bind right +hook",
+            "bind wheel_down +prevweapon",
+            "bind wheel_up +nextweapon",
+            "bind digit1 +weapon1",
+            "bind digit2 +weapon2",
+            "bind digit3 +weapon3",
+            "bind digit4 +weapon4",
+            "bind digit5 +weapon5",
+            "bind g dummy_copy_moves",
+            "bind enter +show_chat",
+            "bind t +show_chat",
+            format!("bind {team_chat} +show_team_chat").as_str(),
+            "bind tab +scoreboard",
+            "bind u +show_chat_history",
+            "bind shift_left +emote_wheel",
+            "bind q kill",
+            "bind f3 vote_yes",
+            "bind f4 vote_no",
+            "bind numpad_subtract zoom-",
+            "bind numpad_add zoom+",
+            "bind numpad_multiply zoom",
+        ]
+        .into_iter()
+        .map(|bind| bind.to_string())
+        .collect()
+    }
+
+    /// The default binds of the second player in split screen.
+    ///
+    /// They only use keys that are not part of [`Self::default_binds`],
+    /// so both players can share one keyboard.
+    pub fn split_screen_binds() -> Vec<String> {
+        [
+            "bind arrow_left +left",
+            "bind arrow_right +right",
+            "bind arrow_up +jump",
+            "bind control_right +fire",
+            "bind shift_right +hook",
+            "bind period +nextweapon",
+            "bind comma +prevweapon",
+        ]
+        .into_iter()
+        .map(|bind| bind.to_string())
+        .collect()
+    }
+
+    /// The binds of this profile merged over the default binds,
+    /// a bind of the profile replaces the default bind of the same keys.
+    pub fn binds_with_defaults(&self, layout: ConfigKeyboardLayout) -> Vec<String> {
+        self.binds_over(Self::default_binds(layout))
+    }
+
+    /// The binds of this profile merged over the given `defaults`,
+    /// see [`Self::binds_with_defaults`].
+    pub fn binds_over(&self, defaults: Vec<String>) -> Vec<String> {
+        fn bind_keys(bind: &str) -> Option<&str> {
+            bind.strip_prefix("bind ")?
+                .split_once(' ')
+                .map(|(keys, _)| keys)
+        }
+        let mut binds: Vec<String> = defaults
+            .into_iter()
+            .filter(|default_bind| {
+                !self.binds.iter().any(|bind| {
+                    bind_keys(bind).is_some() && bind_keys(bind) == bind_keys(default_bind)
+                })
+            })
+            .collect();
+        binds.extend(self.binds.iter().cloned());
+        binds
+    }
 }
 
 #[config_default]
@@ -295,6 +617,57 @@ pub struct ConfigPlayerProfiles {
     pub dummy: ConfigDummyProfile,
 }
 
+/// The version of [`ConfigPlayerProfilesExport`],
+/// increased whenever older exports can not be read anymore.
+pub const PLAYER_PROFILES_EXPORT_VERSION: u64 = 1;
+
+/// All player profiles (incl. skins, binds and the dummy profile)
+/// in a single file, e.g. to move them to another machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigPlayerProfilesExport {
+    pub version: u64,
+    pub players: Vec<ConfigPlayer>,
+    pub profiles: ConfigPlayerProfiles,
+}
+
+impl ConfigPlayerProfilesExport {
+    pub fn to_json_string(&self) -> anyhow::Result<String> {
+        let res = serde_json::to_string_pretty(self)?;
+        Ok(res)
+    }
+
+    /// Parses and validates an export.
+    ///
+    /// Invalid values of the profiles (e.g. too long names) are
+    /// corrected by the config validation.
+    pub fn from_json_string(json_str: &str) -> anyhow::Result<Self> {
+        let res: Self = serde_json::from_str(json_str)?;
+        anyhow::ensure!(
+            res.version == PLAYER_PROFILES_EXPORT_VERSION,
+            "unsupported profiles version: {}",
+            res.version
+        );
+        anyhow::ensure!(!res.players.is_empty(), "the file contains no profiles");
+        anyhow::ensure!(
+            (res.profiles.main as usize) < res.players.len()
+                && (res.profiles.dummy.index as usize) < res.players.len(),
+            "the player or dummy profile does not exist in the file"
+        );
+        Ok(res)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigPlayerProfilesImportMode {
+    /// All existing profiles are replaced by the imported ones.
+    #[default]
+    Replace,
+    /// The imported profiles are added, existing profiles
+    /// with the same name are overwritten.
+    /// The selected player & dummy profiles stay the same.
+    Merge,
+}
+
 #[config_default]
 #[derive(Debug, Serialize, Deserialize, ConfigInterface)]
 pub struct ConfigMap {
@@ -348,6 +721,118 @@ pub struct ConfigServerDatabase {
     pub connection_count: u64,
 }
 
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerVote {
+    /// Whether players that are spectating are allowed to vote.
+    #[default = true]
+    pub spectators_can_vote: bool,
+    /// How many seconds a client must be connected
+    /// before it is allowed to vote.
+    #[default = 0]
+    pub min_ingame_secs: u64,
+    /// If true, the vote of a client counts once for every player
+    /// it has in the game (e.g. dummies), instead of once per client.
+    #[default = false]
+    pub weight_per_player: bool,
+    /// How many minutes a player that was voted out is banned.
+    /// 0 bans the player until the server restarts.
+    #[default = 0]
+    pub kick_ban_minutes: u64,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerStartupChecks {
+    /// Check the map, the database, the network and the tick timing
+    /// when the server starts and log a report of the results.
+    #[default = true]
+    pub enabled: bool,
+    /// Refuse to start the server, if a critical check failed.
+    #[default = false]
+    pub strict: bool,
+    /// The database latency in milliseconds, above which the check warns.
+    #[default = 50]
+    pub max_db_latency_ms: u64,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerAutoRecord {
+    /// Record every round into its own demo, while players are on the server.
+    /// The demos contain all stages and are saved as `demos/auto_*.twdemo`.
+    #[default = false]
+    pub enabled: bool,
+    /// How many of the recorded demos are kept, the oldest are deleted.
+    /// 0 keeps all demos.
+    #[default = 10]
+    pub max_demos: u64,
+    /// Sign the demos with the key of the server certificate,
+    /// so that players can verify a demo was not modified,
+    /// e.g. in tournaments. The signature is saved next to the demo.
+    #[default = false]
+    pub sign: bool,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerWhitelist {
+    /// Whether only whitelisted players can join the server.
+    /// Can be toggled at runtime with the `whitelist` rcon command.
+    #[default = false]
+    pub enabled: bool,
+    /// The whitelisted players, either `acc_<account id>`
+    /// or `cert_<cert fingerprint>`.
+    #[default = Vec::new()]
+    pub players: Vec<String>,
+    /// The message players that are not whitelisted see, when they are rejected.
+    #[default = "This server is whitelisted."]
+    pub msg: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, ConfigInterface, PartialEq, Eq)]
+pub enum ConfigServerScheduledAction {
+    /// Sends [`ConfigServerScheduledEvent::arg`] as message to all players.
+    #[default]
+    Broadcast,
+    /// Changes the score limit to [`ConfigServerScheduledEvent::arg`].
+    /// The score limit is kept across map changes.
+    ScoreLimit,
+    /// Changes to the map that follows the current map in
+    /// [`ConfigServerScheduledEvent::arg`] (a comma separated list of maps).
+    RotateMap,
+    /// Starts a fresh game on the current map at exactly the scheduled time,
+    /// e.g. a daily reset at midnight (`0 0 * * *`).
+    /// [`ConfigServerScheduledEvent::arg`] is the countdown in seconds
+    /// that is announced to all players before, empty for none.
+    ResetState,
+    /// Like [`ConfigServerScheduledAction::ResetState`], but reads the map
+    /// & game mod again and keeps the game state.
+    ReloadMap,
+    /// Changes the player slots to [`ConfigServerScheduledEvent::arg`],
+    /// e.g. fewer slots at night (`0 23 * * *`).
+    /// When the server starts, the latest change of the last week applies
+    /// instead of [`ConfigServer::max_players`].
+    MaxPlayers,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerScheduledEvent {
+    /// When the event happens in a cron-like syntax (local time):
+    /// `minute hour day-of-month month day-of-week`.
+    /// Every field is `*`, a number, a range (`1-5`) or
+    /// a list of those (`0,30`), optionally with a step (`*/15`).
+    /// E.g. `0 20 * * 5` is every friday at 20:00.
+    #[default = ""]
+    pub schedule: String,
+    #[default = ConfigServerScheduledAction::Broadcast]
+    pub action: ConfigServerScheduledAction,
+    /// The argument of the action, see [`ConfigServerScheduledAction`].
+    #[default = ""]
+    pub arg: String,
+}
+
 #[config_default]
 #[derive(Debug, Serialize, Deserialize, ConfigInterface)]
 pub struct ConfigServer {
@@ -365,8 +850,19 @@ pub struct ConfigServer {
     pub max_players: u32,
     #[default = 4]
     pub max_players_per_ip: u32,
+    /// If the max players are reduced by rcon while more players are connected,
+    /// spectators are kicked until the players fit.
+    /// Otherwise all connected players stay and new players are queued.
+    #[default = false]
+    pub max_players_kick_spectators: bool,
     #[default = false]
     pub register: bool,
+    /// Path to a png file that clients show as icon or banner
+    /// of the server in the server browser and while connecting.
+    /// It must not be bigger than 64 KiB and 512x256 pixels.
+    /// An empty string disables the icon.
+    #[default = ""]
+    pub icon: String,
     /// The game mod module to load
     /// empty string, "default", "native", "vanilla" & "ddnet"
     /// are reserved names and will not cause
@@ -399,6 +895,39 @@ pub struct ConfigServer {
     /// traffic.
     #[default = false]
     pub spatial_chat: bool,
+    /// The map to switch to, after the server was empty for
+    /// [`ConfigServer::idle_map_change_minutes`].
+    /// An empty string uses [`ConfigServer::map`].
+    #[default = ""]
+    pub idle_map: String,
+    /// After how many minutes of no connected clients the server
+    /// switches to the idle map. 0 disables this.
+    #[default = 0]
+    pub idle_map_change_minutes: u64,
+    /// After how many seconds of no connected clients the server
+    /// goes to sleep. A sleeping server pauses the game and only
+    /// wakes up once per second, until a client connects.
+    /// The database is not pinged to keep its connections alive
+    /// while sleeping.
+    /// 0 disables this.
+    #[default = 0]
+    pub sleep_when_empty_secs: u64,
+    /// Rules about who is allowed to vote.
+    #[default = Default::default()]
+    pub vote: ConfigServerVote,
+    /// Events that are executed at certain times,
+    /// e.g. announcements or a map rotation.
+    #[default = Vec::new()]
+    pub scheduled_events: Vec<ConfigServerScheduledEvent>,
+    /// Restricts who can join the server.
+    #[default = Default::default()]
+    pub whitelist: ConfigServerWhitelist,
+    /// The self-check when the server starts.
+    #[default = Default::default()]
+    pub startup_checks: ConfigServerStartupChecks,
+    /// Automatically record demos on the server.
+    #[default = Default::default()]
+    pub auto_record: ConfigServerAutoRecord,
 }
 
 #[config_default]
@@ -479,6 +1008,41 @@ impl ConfigGame {
         let res = serde_json::from_str(json_str)?;
         Ok(res)
     }
+
+    pub fn export_player_profiles(&self) -> ConfigPlayerProfilesExport {
+        ConfigPlayerProfilesExport {
+            version: PLAYER_PROFILES_EXPORT_VERSION,
+            players: self.players.clone(),
+            profiles: self.profiles.clone(),
+        }
+    }
+
+    pub fn import_player_profiles(
+        &mut self,
+        import: ConfigPlayerProfilesExport,
+        mode: ConfigPlayerProfilesImportMode,
+    ) {
+        match mode {
+            ConfigPlayerProfilesImportMode::Replace => {
+                self.players = import.players;
+                self.profiles = import.profiles;
+            }
+            ConfigPlayerProfilesImportMode::Merge => {
+                for player in import.players {
+                    if let Some(existing) = self.players.iter_mut().find(|p| p.name == player.name)
+                    {
+                        *existing = player;
+                    } else {
+                        self.players.push(player);
+                    }
+                }
+            }
+        }
+        // there is always a player & a dummy profile
+        while self.players.len() < 2 {
+            self.players.push(ConfigPlayer::new("brainless tee"));
+        }
+    }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -11,6 +11,8 @@ pub struct ChatCommands {
     pub cmds: HashMap<String, Vec<CommandArg>>,
     /// list of prefixes that trigger a chat command (e.g. `/` for slash commands)
     pub prefixes: Vec<char>,
+    /// short description of the commands, e.g. to show next to the autocompletion
+    pub descriptions: HashMap<String, String>,
 }
 
 /// The command that comes from the client.
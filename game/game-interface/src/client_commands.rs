@@ -4,7 +4,10 @@ use serde::{Deserialize, Serialize};
 use crate::{
     chat_commands::ClientChatCommand,
     rcon_commands::ClientRconCommand,
-    types::{network_string::NetworkString, render::game::game_match::MatchSide},
+    types::{
+        game::GameEntityId, network_string::NetworkString, render::game::game_match::MatchSide,
+    },
+    votes::{GameVoteAction, Voted},
 };
 
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
@@ -33,4 +36,11 @@ pub enum ClientCommand {
     JoinSide(MatchSide),
     JoinSpectator,
     SwitchToFreeCam(ClientFreeCamMode),
+    /// A spectator wants to follow the character of this player,
+    /// `None` switches back to the free view.
+    SpectateTarget(Option<GameEntityId>),
+    /// The client wants to start a vote, if no other vote is running.
+    StartVote(GameVoteAction),
+    /// The client votes in the running vote.
+    Vote(Voted),
 }
@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use base::hash::Hash;
 use bitflags::bitflags;
 use hiarc::Hiarc;
 use math::math::vector::vec2;
@@ -9,11 +10,14 @@ use pool::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::types::{
-    flag::FlagType,
-    game::GameEntityId,
-    id_gen::{IdGenerator, IdGeneratorIdType},
-    weapons::WeaponType,
+use crate::{
+    types::{
+        flag::FlagType,
+        game::GameEntityId,
+        id_gen::{IdGenerator, IdGeneratorIdType},
+        weapons::WeaponType,
+    },
+    votes::GameVoteAction,
 };
 
 /// The id of an event
@@ -21,6 +25,9 @@ pub type EventId = IdGeneratorIdType;
 
 pub type EventIdGenerator = IdGenerator;
 
+/// The id of a shot, only unique for the character that fired it.
+pub type ShotId = u64;
+
 /// Sounds that a ninja spawns
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
 pub enum GameBuffNinjaEventSound {
@@ -302,12 +309,92 @@ pub enum GameWorldAction {
     Custom(PoolString),
 }
 
+/// Events that are only interesting for statistics,
+/// e.g. to calculate the accuracy per weapon.
+#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+pub enum GameWorldStatEvent {
+    /// A weapon was fired.
+    /// A shotgun shot counts as a single shot, regardless of the number of bullets.
+    ShotFired {
+        shooter: GameEntityId,
+        shot_id: ShotId,
+        weapon: WeaponType,
+    },
+    /// A shot hit another character.
+    /// A single shot can hit multiple times (shotgun bullets, explosions, laser bounces),
+    /// so the accuracy should count the unique shot ids.
+    ShotHit {
+        shooter: GameEntityId,
+        shot_id: ShotId,
+        weapon: WeaponType,
+        victim: GameEntityId,
+    },
+    /// A round ended and its report was exported,
+    /// tools can fetch the report by this id.
+    RoundReport { report_id: Hash },
+    /// A new round started, after the last one was over.
+    RoundStart,
+    /// The running round is over, e.g. the score limit was reached.
+    RoundEnd,
+}
+
+/// The course of a vote that is run by the game.
+#[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
+pub enum GameWorldVoteEvent {
+    Started {
+        action: GameVoteAction,
+        started_by: GameEntityId,
+        /// How long the players can vote.
+        duration: Duration,
+    },
+    /// The votes of the running vote changed,
+    /// only the votes of the `voters` count.
+    Votes {
+        yes: u64,
+        no: u64,
+        voters: u64,
+    },
+    /// The server has to execute the actions that the game can't,
+    /// see [`GameVoteAction`].
+    Passed {
+        action: GameVoteAction,
+    },
+    Failed {
+        action: GameVoteAction,
+    },
+}
+
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub enum GameWorldGlobalEvent {
     /// A system message
     System(GameWorldSystemMessage),
     /// A action that is displayed in an action feed, kill message or finish time etc.
     Action(GameWorldAction),
+    /// See [`GameWorldStatEvent`]
+    Stat(GameWorldStatEvent),
+    /// See [`GameWorldVoteEvent`]
+    Vote(GameWorldVoteEvent),
+    /// A system message that only the player receives,
+    /// e.g. a warning or the output of a command.
+    PlayerMessage {
+        player_id: GameEntityId,
+        msg: PoolString,
+    },
+    /// The server disconnects the player,
+    /// the game can't disconnect clients on its own.
+    /// Only part of the events if [`EventClientInfo::everything`] is requested.
+    Kick {
+        player_id: GameEntityId,
+        reason: PoolString,
+    },
+    /// The report of a round could not be stored in the database,
+    /// so the server writes it to disk instead, see [`GameWorldStatEvent::RoundReport`].
+    /// Only part of the events if [`EventClientInfo::everything`] is requested.
+    ExportRoundReport {
+        report_id: Hash,
+        /// The report, serialized as JSON.
+        report: PoolString,
+    },
 }
 
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
@@ -15,8 +15,9 @@ use crate::{
     client_commands::ClientCommand,
     events::{EventClientInfo, EventId, GameEvents},
     rcon_commands::RconCommands,
+    server_commands::ServerCommand,
     types::{
-        character_info::NetworkCharacterInfo,
+        character_info::{CharacterInfoRedaction, NetworkCharacterInfo},
         emoticons::EmoticonType,
         game::{GameEntityId, NonZeroGameTickType},
         input::{CharacterInput, CharacterInputConsumableDiff, CharacterPredictionInput},
@@ -220,6 +221,10 @@ pub trait GameStateInterface: GameStateCreate {
     /// a kill that was initiated by the user (to respawn itself)
     fn client_command(&mut self, player_id: &GameEntityId, cmd: ClientCommand);
 
+    /// A command issued by the server itself, see [`ServerCommand`].
+    /// This is only called on the server.
+    fn server_command(&mut self, cmd: ServerCommand);
+
     // stuff that is rendered
     /// Collects scoreboard information, see [`Scoreboard`]
     fn collect_scoreboard_info(&self) -> Scoreboard;
@@ -289,6 +294,18 @@ pub trait GameStateInterface: GameStateCreate {
     #[must_use]
     fn build_from_snapshot(&mut self, snapshot: &MtPoolCow<'static, [u8]>) -> SnapshotLocalPlayers;
 
+    /// Removes the personal information of the players of the redaction
+    /// from an opaque snapshot previously build by [`GameStateInterface::snapshot_for`],
+    /// e.g. to publish a demo.
+    /// The character infos are replaced by [`NetworkCharacterInfo::redacted`]
+    /// and accounts are removed.
+    #[must_use]
+    fn redact_snapshot(
+        &self,
+        snapshot: &MtPoolCow<'static, [u8]>,
+        redaction: &CharacterInfoRedaction,
+    ) -> MtPoolCow<'static, [u8]>;
+
     /// Builds an opaque snapshot out of the current game state, but for server side only.
     /// Normally this can share most code with [`GameStateInterface::snapshot_for`]
     /// Implementing it is optional.
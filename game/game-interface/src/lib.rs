@@ -8,5 +8,6 @@ pub mod events;
 pub mod interface;
 pub mod pooling;
 pub mod rcon_commands;
+pub mod server_commands;
 pub mod types;
 pub mod votes;
@@ -15,6 +15,7 @@ use crate::types::render::character::{
     CharacterRenderInfo,
 };
 use crate::types::render::flag::FlagRenderInfo;
+use crate::types::render::game::game_match::CapturePointRenderInfo;
 use crate::types::render::laser::LaserRenderInfo;
 use crate::types::render::pickup::PickupRenderInfo;
 use crate::types::render::projectiles::ProjectileRenderInfo;
@@ -34,6 +35,7 @@ pub struct GamePooling {
     pub entity_id_pool: MtPool<Vec<GameEntityId>>,
     pub projectile_render_info_pool: Pool<LinkedHashMap<GameEntityId, ProjectileRenderInfo>>,
     pub flag_render_info_pool: Pool<LinkedHashMap<GameEntityId, FlagRenderInfo>>,
+    pub capture_point_render_info_pool: Pool<Vec<CapturePointRenderInfo>>,
     pub laser_render_info_pool: Pool<LinkedHashMap<GameEntityId, LaserRenderInfo>>,
     pub pickup_render_info_pool: Pool<LinkedHashMap<GameEntityId, PickupRenderInfo>>,
     pub stage_scoreboard_pool: Pool<LinkedHashMap<GameEntityId, ScoreboardStageInfo>>,
@@ -59,6 +61,7 @@ impl GamePooling {
             entity_id_pool: MtPool::with_capacity(64),
             projectile_render_info_pool: Pool::with_capacity(64),
             flag_render_info_pool: Pool::with_capacity(64),
+            capture_point_render_info_pool: Pool::with_capacity(2),
             laser_render_info_pool: Pool::with_capacity(64),
             pickup_render_info_pool: Pool::with_capacity(64),
             stage_scoreboard_pool: Pool::with_capacity(64),
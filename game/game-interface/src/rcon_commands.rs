@@ -11,7 +11,9 @@ pub struct RconCommands {
     pub cmds: HashMap<String, Vec<CommandArg>>,
 }
 
-#[derive(Debug, Hiarc, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(
+    Debug, Hiarc, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
 pub enum AuthLevel {
     #[default]
     None,
@@ -0,0 +1,14 @@
+use hiarc::Hiarc;
+use serde::{Deserialize, Serialize};
+
+/// A command that is issued by the server itself,
+/// e.g. by the scheduled events of the server config.
+/// Commands that only concern the server (like changing a map)
+/// are processed by the server implementation directly.
+#[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
+pub enum ServerCommand {
+    /// A message that should be shown to all players.
+    Broadcast(String),
+    /// Change the score limit of the current match.
+    ScoreLimit(u64),
+}
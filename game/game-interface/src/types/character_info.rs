@@ -2,7 +2,7 @@ use hiarc::Hiarc;
 use math::math::vector::ubvec4;
 use serde::{Deserialize, Serialize};
 
-use crate::types::resource_key::NetworkResourceKey;
+use crate::types::{game::GameEntityId, resource_key::NetworkResourceKey};
 
 use super::network_string::NetworkString;
 
@@ -64,4 +64,67 @@ impl NetworkCharacterInfo {
             hook: "default".try_into().unwrap(),
         }
     }
+
+    /// The character info with all personal information removed,
+    /// e.g. for publishing demos.
+    /// The name is replaced as the redaction says, cosmetics like the skin
+    /// are only reset to the defaults if [`CharacterInfoRedaction::cosmetics`] is set.
+    pub fn redacted(&self, id: &GameEntityId, redaction: &CharacterInfoRedaction) -> Self {
+        let info = if redaction.cosmetics {
+            Self::explicit_default()
+        } else {
+            self.clone()
+        };
+        Self {
+            name: NetworkString::new(redaction.name_of(id)).unwrap_or_default(),
+            clan: Default::default(),
+            flag: NetworkString::new("default").unwrap(),
+            ..info
+        }
+    }
+}
+
+/// How the name of a redacted player is replaced.
+#[derive(Debug, Hiarc, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RedactedName {
+    /// Replaced by [`redacted_name`], so the players can still be told apart.
+    #[default]
+    Pseudonym,
+    /// Removed completely.
+    Strip,
+}
+
+/// Which players are redacted and what is removed,
+/// see [`NetworkCharacterInfo::redacted`].
+#[derive(Debug, Hiarc, Default, Clone, Serialize, Deserialize)]
+pub struct CharacterInfoRedaction {
+    /// The redacted players, `None` redacts all players.
+    pub players: Option<Vec<GameEntityId>>,
+    pub name: RedactedName,
+    /// Also reset the skin, its colors and all other cosmetics to the defaults,
+    /// since they can identify a player as well.
+    pub cosmetics: bool,
+}
+
+impl CharacterInfoRedaction {
+    pub fn contains(&self, id: &GameEntityId) -> bool {
+        self.players
+            .as_ref()
+            .map_or(true, |players| players.contains(id))
+    }
+
+    /// The name that replaces the name of a redacted player.
+    pub fn name_of(&self, id: &GameEntityId) -> String {
+        match self.name {
+            RedactedName::Pseudonym => redacted_name(id),
+            RedactedName::Strip => String::new(),
+        }
+    }
+}
+
+/// The name that replaces the name of a player in redacted demos.
+/// It's derived from the id of the player,
+/// so it stays the same during the whole demo.
+pub fn redacted_name(id: &GameEntityId) -> String {
+    format!("Player {id}")
 }
@@ -59,6 +59,12 @@ impl GameTickCooldown {
     pub fn is_none(&self) -> bool {
         self.0.is_none()
     }
+
+    /// the ticks until the cooldown is over,
+    /// 0 if no cooldown is active
+    pub fn ticks_left(&self) -> GameTickType {
+        self.0.map(|ticks| ticks.get()).unwrap_or_default()
+    }
 }
 
 impl From<GameTickType> for GameTickCooldown {
@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 pub struct PlayerNetworkStats {
     // the estimated RTT of the connection.
     pub ping: Duration,
-    // estimated amount of packet loss.
+    // estimated amount of packet loss (0.0 - 1.0) over the last few seconds.
     pub packet_loss: f32,
+    // the variation of the RTT, smoothed over recent pings.
+    pub jitter: Duration,
 }
@@ -13,6 +13,7 @@ use crate::types::{
     character_info::{NetworkCharacterInfo, NetworkSkinInfo},
     emoticons::EmoticonType,
     game::{GameEntityId, GameTickType},
+    render::mod_payload::ModRenderPayload,
     weapons::WeaponType,
 };
 
@@ -42,6 +43,10 @@ pub enum CharacterDebuff {
 pub struct CharacterDebuffInfo {
     /// the remaining time, or `None` if unknown
     pub remaining_time: Option<Duration>,
+    /// progress (`0.0..=1.0`) of removing the debuff,
+    /// e.g. while teammates unfreeze the character.
+    /// `None` if the debuff can not be removed this way.
+    pub progress: Option<f32>,
 }
 
 #[derive(
@@ -108,6 +113,9 @@ pub struct CharacterRenderInfo {
 
     /// emoticon ticks passed & emoticon type
     pub emoticon: Option<(GameTickType, EmoticonType)>,
+
+    /// Custom data of the physics mod for its render mod.
+    pub mod_payload: ModRenderPayload,
 }
 
 /// The camera mode of the local player
@@ -122,6 +130,9 @@ pub enum PlayerCameraMode {
     /// The camera is currently locked to a specific
     /// position in a map (e.g. a kill cam).
     LockedTo(vec2),
+    /// The camera follows the character with this id,
+    /// e.g. a spectator that watches a player.
+    FollowCharacter(GameEntityId),
 }
 
 /// Information about the the player of a character.
@@ -161,6 +172,10 @@ pub struct CharacterInfo {
 pub struct LocalCharacterRenderInfo {
     pub health: u32,
     pub armor: u32,
+    /// The part of [`Self::health`] that is above the normal max health.
+    pub overheal_health: u32,
+    /// The part of [`Self::armor`] that is above the normal max armor.
+    pub overheal_armor: u32,
 
     /// A value of `None` means unlimited
     pub ammo_of_weapon: Option<u32>,
@@ -1,6 +1,6 @@
 pub mod game_match;
 
-use game_match::MatchStandings;
+use game_match::{MatchRoundTimer, MatchStandings};
 use hiarc::Hiarc;
 use serde::{Deserialize, Serialize};
 
@@ -9,5 +9,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Hiarc, Serialize, Deserialize)]
 pub enum GameRenderInfo {
     Race {},
-    Match { standings: MatchStandings },
+    Match {
+        standings: MatchStandings,
+        round_timer: MatchRoundTimer,
+    },
 }
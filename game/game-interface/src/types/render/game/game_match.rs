@@ -1,7 +1,9 @@
 use hiarc::Hiarc;
+use math::math::vector::vec2;
+use pool::datatypes::PoolVec;
 use serde::{Deserialize, Serialize};
 
-use crate::types::game::GameEntityId;
+use crate::types::game::{GameEntityId, GameTickType};
 
 #[derive(Debug, Hiarc, Serialize, Deserialize, Clone, Copy)]
 pub struct LeadingCharacter {
@@ -11,8 +13,24 @@ pub struct LeadingCharacter {
     pub score: i64,
 }
 
-/// Current results for the match.
+/// A capture point of objective based game types,
+/// e.g. king of the hill.
 #[derive(Debug, Hiarc, Serialize, Deserialize, Clone, Copy)]
+pub struct CapturePointRenderInfo {
+    /// The center of the capture zone, in tiles.
+    pub pos: vec2,
+    /// The side that holds the point.
+    pub owner: Option<MatchSide>,
+    /// The side that is capturing the point.
+    pub capturing_side: Option<MatchSide>,
+    /// The capture progress of `capturing_side`, between 0 and 1.
+    pub progress: f32,
+    /// Both sides are inside the capture zone.
+    pub contested: bool,
+}
+
+/// Current results for the match.
+#[derive(Debug, Hiarc, Serialize, Deserialize)]
 pub enum MatchStandings {
     Solo {
         /// The top characters in the current match
@@ -23,9 +41,24 @@ pub enum MatchStandings {
         score_red: i64,
         /// Score for the blue side
         score_blue: i64,
+        /// The capture points of the map, empty if the
+        /// game type has no objectives.
+        capture_points: PoolVec<CapturePointRenderInfo>,
     },
 }
 
+/// The phase of the current round and the time that is left in it.
+#[derive(Debug, Hiarc, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MatchRoundTimer {
+    /// The round starts when the warmup is over.
+    Warmup { ticks_left: GameTickType },
+    /// `None` if the round has no time limit.
+    Running { ticks_left: Option<GameTickType> },
+    /// The time limit was hit without a leader,
+    /// the round goes on until someone leads.
+    SuddenDeath,
+}
+
 /// The side in the current match
 #[derive(Debug, Hiarc, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum MatchSide {
@@ -2,6 +2,7 @@ pub mod character;
 pub mod flag;
 pub mod game;
 pub mod laser;
+pub mod mod_payload;
 pub mod pickup;
 pub mod projectiles;
 pub mod scoreboard;
@@ -0,0 +1,20 @@
+use hiarc::Hiarc;
+use serde::{Deserialize, Serialize};
+
+/// Size in bytes of a [`ModRenderPayload`].
+pub const MOD_RENDER_PAYLOAD_SIZE: usize = 16;
+
+/// Opaque data that a physics mod attaches to an entity for its
+/// matching render mod, e.g. charges or stacks of a custom weapon.
+///
+/// Vanilla never sets or reads it, it's only passed through the snapshots
+/// and the render info untouched.
+/// All zeros means that the mod did not set anything.
+#[derive(Debug, Hiarc, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModRenderPayload(pub [u8; MOD_RENDER_PAYLOAD_SIZE]);
+
+impl ModRenderPayload {
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&byte| byte == 0)
+    }
+}
@@ -2,7 +2,7 @@ use hiarc::Hiarc;
 use math::math::vector::vec2;
 use serde::{Deserialize, Serialize};
 
-use crate::types::{game::GameEntityId, pickup::PickupType};
+use crate::types::{game::GameEntityId, pickup::PickupType, render::mod_payload::ModRenderPayload};
 
 /// The ingame metric is 1 tile = 1.0 float units
 #[derive(Debug, Hiarc, Copy, Clone, Serialize, Deserialize)]
@@ -15,4 +15,7 @@ pub struct PickupRenderInfo {
     /// In this specific case it _could_ be the nearest character for example.
     /// If unsure leave it to `None`.
     pub owner_id: Option<GameEntityId>,
+
+    /// Custom data of the physics mod for its render mod.
+    pub mod_payload: ModRenderPayload,
 }
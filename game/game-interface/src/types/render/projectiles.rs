@@ -2,7 +2,9 @@ use hiarc::Hiarc;
 use math::math::vector::vec2;
 use serde::{Deserialize, Serialize};
 
-use crate::types::{game::GameEntityId, weapons::WeaponType};
+use crate::types::{
+    game::GameEntityId, render::mod_payload::ModRenderPayload, weapons::WeaponType,
+};
 
 /// The ingame metric is 1 tile = 1.0 float units
 #[derive(Debug, Hiarc, Copy, Clone, Serialize, Deserialize)]
@@ -14,4 +16,7 @@ pub struct ProjectileRenderInfo {
     /// If this entity is owned by a character, this should be `Some` and
     /// include the characters id.
     pub owner_id: Option<GameEntityId>,
+
+    /// Custom data of the physics mod for its render mod.
+    pub mod_payload: ModRenderPayload,
 }
@@ -3,9 +3,11 @@ use math::math::vector::ubvec4;
 use pool::datatypes::{PoolLinkedHashMap, PoolString, PoolVec};
 use serde::{Deserialize, Serialize};
 
-use crate::types::{game::GameEntityId, network_stats::PlayerNetworkStats};
+use crate::types::{
+    game::GameEntityId, network_stats::PlayerNetworkStats, render::game::game_match::MatchSide,
+};
 
-#[derive(Debug, Hiarc, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub enum ScoreboardConnectionType {
     /// "Normal" network connection
     Network(PlayerNetworkStats),
@@ -13,14 +15,14 @@ pub enum ScoreboardConnectionType {
     Bot,
 }
 
-#[derive(Debug, Hiarc, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub struct ScoreboardCharacterInfo {
     pub id: GameEntityId,
     pub score: i64,
     pub ping: ScoreboardConnectionType,
 }
 
-#[derive(Debug, Hiarc, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub struct ScoreboardStageInfo {
     pub characters: PoolVec<ScoreboardCharacterInfo>,
     pub name: PoolString,
@@ -30,7 +32,7 @@ pub struct ScoreboardStageInfo {
 
 pub type ScoreboardPlayerSpectatorInfo = ScoreboardCharacterInfo;
 
-#[derive(Debug, Hiarc, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub enum ScoreboardGameType {
     /// side = vanilla team/side
     /// stage = ddrace team
@@ -57,14 +59,76 @@ pub enum ScoreboardGameType {
     },
 }
 
-#[derive(Debug, Hiarc, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub struct ScoreboardGameOptions {
     pub score_limit: u64,
     pub map_name: PoolString,
 }
 
-#[derive(Debug, Hiarc, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub struct Scoreboard {
     pub game: ScoreboardGameType,
     pub options: ScoreboardGameOptions,
 }
+
+/// Where a player is in the game, see [`Scoreboard::placement_of`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreboardPlacement {
+    /// `None` for spectators.
+    pub stage_id: Option<GameEntityId>,
+    /// `None` for solo play & spectators.
+    pub side: Option<MatchSide>,
+}
+
+impl Scoreboard {
+    /// The stage & side of the player, `None` if the player is not part of the scoreboard.
+    pub fn placement_of(&self, id: &GameEntityId) -> Option<ScoreboardPlacement> {
+        let in_stages = |stages: &PoolLinkedHashMap<GameEntityId, ScoreboardStageInfo>| {
+            stages.iter().find_map(|(stage_id, stage)| {
+                stage
+                    .characters
+                    .iter()
+                    .any(|c| c.id == *id)
+                    .then_some(*stage_id)
+            })
+        };
+        let spectator = |spectators: &PoolVec<ScoreboardPlayerSpectatorInfo>| {
+            spectators
+                .iter()
+                .any(|c| c.id == *id)
+                .then_some(ScoreboardPlacement {
+                    stage_id: None,
+                    side: None,
+                })
+        };
+        match &self.game {
+            ScoreboardGameType::SidedPlay {
+                red_stages,
+                blue_stages,
+                spectator_players,
+                ..
+            } => in_stages(red_stages)
+                .map(|stage_id| ScoreboardPlacement {
+                    stage_id: Some(stage_id),
+                    side: Some(MatchSide::Red),
+                })
+                .or_else(|| {
+                    in_stages(blue_stages).map(|stage_id| ScoreboardPlacement {
+                        stage_id: Some(stage_id),
+                        side: Some(MatchSide::Blue),
+                    })
+                })
+                .or_else(|| spectator(spectator_players)),
+            ScoreboardGameType::SoloPlay {
+                stages,
+                spectator_players,
+                ..
+            } => in_stages(stages)
+                .map(|stage_id| ScoreboardPlacement {
+                    stage_id: Some(stage_id),
+                    side: None,
+                })
+                .or_else(|| spectator(spectator_players)),
+        }
+    }
+}
@@ -33,7 +33,21 @@ pub enum VoteType {
     Misc(),
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+/// A vote that is run by the game itself,
+/// see [`crate::client_commands::ClientCommand::StartVote`].
+#[derive(Debug, Hiarc, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameVoteAction {
+    /// The server kicks the player, the game can't disconnect clients on its own.
+    KickPlayer { player_id: GameEntityId },
+    /// The game moves the player to the spectators.
+    SpecPlayer { player_id: GameEntityId },
+    /// The server changes to this map.
+    ChangeMap {
+        name: NetworkReducedAsciiString<MAX_MAP_NAME_LEN>,
+    },
+}
+
+#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
 pub enum Voted {
     Yes,
     No,
@@ -9,7 +9,7 @@ use crate::types::NonZeroU16MinusOne;
 
 use self::layers::{
     design::MapLayer,
-    physics::MapLayerPhysics,
+    physics::{MapLayerPhysics, MapLayerTilePhysicsTune},
     tiles::{TileBase, TuneTile},
 };
 
@@ -67,10 +67,25 @@ impl MapGroupPhysics {
             })
     }
 
+    pub fn get_front_layer_tiles(&self) -> Option<&Vec<TileBase>> {
+        self.layers.iter().find_map(|layer| {
+            if let MapLayerPhysics::Front(layer) = &layer {
+                Some(&layer.tiles)
+            } else {
+                None
+            }
+        })
+    }
+
     pub fn get_tune_layer_tiles(&self) -> Option<&Vec<TuneTile>> {
+        self.get_tune_layer().map(|layer| &layer.base.tiles)
+    }
+
+    /// The tune layer, including the tune zones of the map.
+    pub fn get_tune_layer(&self) -> Option<&MapLayerTilePhysicsTune> {
         self.layers.iter().find_map(|layer| {
             if let MapLayerPhysics::Tune(layer) = &layer {
-                Some(&layer.base.tiles)
+                Some(layer)
             } else {
                 None
             }
@@ -1 +1,2 @@
 pub mod prediction_timing;
+pub mod time_sync;
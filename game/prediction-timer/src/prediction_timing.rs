@@ -14,6 +14,24 @@ pub struct PredictionStatsCollection {
     pub packets_lost: u64,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct PredictionSnapStatsCollection {
+    pub snaps: u64,
+    /// snaps that arrived outside of the expected timing window
+    pub mispredicted_snaps: u64,
+}
+
+/// How much of the measured jitter & packet loss the prediction margin covers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PredictionMarginStrategy {
+    /// Lowest latency, but inputs arrive too late on the server more often.
+    Aggressive,
+    #[default]
+    Balanced,
+    /// Adds extra margin for unstable connections, at the cost of latency.
+    Safe,
+}
+
 #[derive(Debug, Clone)]
 pub struct PredictionTiming {
     /// last seconds of highest/lowest/average pings per second
@@ -36,6 +54,16 @@ pub struct PredictionTiming {
     pub jitter_range: Duration,
 
     pub last_secs_of_packets_stats: VecDeque<PredictionStatsCollection>,
+
+    /// last seconds of snapshots that were outside of the prediction margin
+    pub last_secs_of_snap_stats: VecDeque<PredictionSnapStatsCollection>,
+    pub cur_whole_second_snap_stats: u64,
+
+    pub margin_strategy: PredictionMarginStrategy,
+    /// manual offset in seconds, added on top of the calculated margin
+    pub margin_offset: f64,
+    /// the latency the last prediction margin added on top of the average ping
+    pub extra_latency: Duration,
 }
 
 impl PredictionTiming {
@@ -156,10 +184,30 @@ impl PredictionTimer {
                     packets_sent: 0,
                 }]
                 .into(),
+
+                last_secs_of_snap_stats: vec![PredictionSnapStatsCollection {
+                    snaps: 0,
+                    mispredicted_snaps: 0,
+                }]
+                .into(),
+                cur_whole_second_snap_stats: cur_time.as_secs(),
+
+                margin_strategy: Default::default(),
+                margin_offset: 0.0,
+                extra_latency: Duration::ZERO,
             },
         }
     }
 
+    /// Sets how the prediction margin is calculated.
+    ///
+    /// `offset` is in seconds, added on top of the calculated margin and may be negative,
+    /// e.g. users on unstable Wi-Fi might want a bigger margin.
+    pub fn set_margin(&mut self, strategy: PredictionMarginStrategy, offset: f64) {
+        self.timing.margin_strategy = strategy;
+        self.timing.margin_offset = offset;
+    }
+
     /// Take a snapshot of the predicting timing.
     /// Useful for debugging
     pub fn snapshot(&self) -> PredictionTiming {
@@ -337,6 +385,50 @@ impl PredictionTimer {
         self.timing.cur_whole_second_stats = whole_second;
     }
 
+    fn add_snap_stats(&mut self, timestamp: Duration, mispredicted: bool) {
+        let whole_second = timestamp
+            .as_secs()
+            .max(self.timing.cur_whole_second_snap_stats);
+
+        if whole_second > self.timing.cur_whole_second_snap_stats {
+            let diff = whole_second - self.timing.cur_whole_second_snap_stats;
+            let max_seconds = 5;
+            // only last seconds are of interest
+            self.timing
+                .last_secs_of_snap_stats
+                .truncate((max_seconds - diff.min(max_seconds)) as usize);
+            self.timing
+                .last_secs_of_snap_stats
+                .push_front(PredictionSnapStatsCollection {
+                    snaps: 1,
+                    mispredicted_snaps: mispredicted as u64,
+                });
+        } else {
+            let cur_stats = &mut self.timing.last_secs_of_snap_stats[0];
+            cur_stats.snaps += 1;
+            cur_stats.mispredicted_snaps += mispredicted as u64;
+        }
+        self.timing.cur_whole_second_snap_stats = whole_second;
+    }
+
+    /// The ratio of snapshots of the last seconds that arrived outside
+    /// of the expected timing window, which usually means that the
+    /// prediction was too short or too long.
+    pub fn misprediction_rate(&self) -> f64 {
+        self.timing
+            .last_secs_of_snap_stats
+            .iter()
+            .map(|s| s.mispredicted_snaps)
+            .sum::<u64>() as f64
+            / self
+                .timing
+                .last_secs_of_snap_stats
+                .iter()
+                .map(|s| s.snaps)
+                .sum::<u64>()
+                .max(1) as f64
+    }
+
     fn dist_ratio(x: f64, min: f64, max: f64, mid: f64) -> f64 {
         let relative_x = mid + x;
         let dist = if relative_x <= mid {
@@ -385,6 +477,7 @@ impl PredictionTimer {
         } else {
             None
         };
+        self.add_snap_stats(timestamp, lag_weight.is_some());
 
         let cur_duration_snap = self.timing.cur_duration_snap;
         if (timestamp > cur_duration_snap + self.calc_snap_farsight_of_jitter())
@@ -435,21 +528,39 @@ impl PredictionTimer {
     ///
     /// `time_unit_time` is the time one tick takes in the physics.
     pub fn pred_max_smooth(&mut self, time_unit_time: Duration) -> Duration {
-        let max_ping = self.ping_max() + Self::PREDICTION_MARGIN_FRAME_TIME;
+        let (ping, packet_loss_time) = match self.timing.margin_strategy {
+            // only half of the jitter & packet loss is respected
+            PredictionMarginStrategy::Aggressive => (
+                (self.ping_average() + self.ping_max()) / 2,
+                self.extra_prediction_margin_by_packet_loss(time_unit_time) / 2,
+            ),
+            PredictionMarginStrategy::Balanced => (
+                self.ping_max(),
+                self.extra_prediction_margin_by_packet_loss(time_unit_time),
+            ),
+            // respect an additional half of the jitter range & twice the packet loss
+            PredictionMarginStrategy::Safe => (
+                self.ping_max() + self.timing.jitter_range / 2,
+                self.extra_prediction_margin_by_packet_loss(time_unit_time) * 2,
+            ),
+        };
+        let max_ping = ping + Self::PREDICTION_MARGIN_FRAME_TIME;
         let max_frame_time = self.max_frametime() + Self::PREDICTION_MARGIN_FRAME_TIME;
-        let packet_loss_time = self.extra_prediction_margin_by_packet_loss(time_unit_time);
 
         // if the jitter is high, except some bigger jumps
         //dbg!(self.timing.smooth_max_ping * 1000.0, max.as_secs_f64() * 1000.0);
         self.timing.smooth_max_ping -= (self.timing.smooth_max_ping * 0.1).clamp(-0.01, 0.01);
 
-        Duration::from_secs_f64(
+        let res = Duration::from_secs_f64(
             (max_ping.as_secs_f64()
                 + max_frame_time.as_secs_f64()
                 + self.timing.smooth_max_ping
-                + packet_loss_time.as_secs_f64())
-            .clamp(0.0, f64::MAX),
-        )
+                + packet_loss_time.as_secs_f64()
+                + self.timing.margin_offset)
+                .clamp(0.0, f64::MAX),
+        );
+        self.timing.extra_latency = res.saturating_sub(self.ping_average());
+        res
     }
 
     /// How much a single frame should adjust the prediction time
@@ -486,7 +597,39 @@ mod test {
     use math::math::Rng;
     use textplots::{Chart, ColorPlot, Shape};
 
-    use super::PredictionTimer;
+    use super::{PredictionMarginStrategy, PredictionTimer};
+
+    #[test]
+    fn margin_strategies() {
+        let mut cur_time = Duration::from_secs(1);
+        let mut timer = PredictionTimer::new(Duration::from_millis(50), cur_time);
+        let mut rng = Rng::new(0);
+        for _ in 0..200 {
+            cur_time += Duration::from_millis(50);
+            timer.add_ping(
+                Duration::from_millis(50 + (rng.random_float() * 30.0) as u64),
+                cur_time,
+            );
+            timer.add_packet_stats(cur_time, 10, 1);
+        }
+        let tick_time = Duration::from_millis(20);
+        let mut margin = |strategy, offset| {
+            timer.set_margin(strategy, offset);
+            timer.pred_max_smooth(tick_time)
+        };
+        let aggressive = margin(PredictionMarginStrategy::Aggressive, 0.0);
+        let balanced = margin(PredictionMarginStrategy::Balanced, 0.0);
+        let safe = margin(PredictionMarginStrategy::Safe, 0.0);
+        assert!(aggressive < balanced);
+        assert!(balanced < safe);
+
+        let offset = margin(PredictionMarginStrategy::Balanced, 0.01);
+        assert!(offset > balanced);
+        assert_eq!(
+            margin(PredictionMarginStrategy::Balanced, -10.0),
+            Duration::ZERO
+        );
+    }
 
     #[test]
     fn jitter_tests() {
@@ -0,0 +1,254 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Copy)]
+struct TimeSyncSample {
+    /// local time when the response arrived
+    at: Duration,
+    rtt: Duration,
+    /// server time - client time in seconds
+    offset: f64,
+}
+
+/// The measured clock values, mostly interesting for debugging.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TimeSyncStats {
+    /// Estimated offset between the server & client clock
+    /// relative to the first measurement (in seconds).
+    pub offset: f64,
+    /// Estimated drift of the clocks (in seconds per second).
+    pub drift: f64,
+    /// The offset that was already corrected (in seconds).
+    pub applied_offset: f64,
+    /// RTT of the last time sync request.
+    pub rtt: Duration,
+}
+
+/// Estimates the drift between the client's & server's clock
+/// using explicit time sync requests (NTP like).
+///
+/// Unlike the [`crate::prediction_timing::PredictionTimer`], which
+/// reacts to snapshots arriving early/late, this only tracks
+/// the slow drift of the clocks over time and corrects it
+/// in tiny steps, so it never causes visible jumps.
+#[derive(Debug)]
+pub struct TimeSync {
+    samples: VecDeque<TimeSyncSample>,
+    pending_requests: BTreeMap<u64, Duration>,
+    next_request_id: u64,
+    last_request: Option<Duration>,
+
+    /// the offset of the very first measurement,
+    /// the clocks usually have a different base,
+    /// so only changes relative to this are of interest.
+    base_offset: Option<f64>,
+    /// linear estimation of the offset: `offset(t) = intercept + drift * t`
+    intercept: f64,
+    drift: f64,
+
+    applied_offset: f64,
+    last_correction: Option<Duration>,
+}
+
+impl Default for TimeSync {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSync {
+    /// How often a time sync request is sent.
+    pub const REQUEST_INTERVAL: Duration = Duration::from_secs(2);
+    /// Requests older than this are considered lost.
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+    /// Number of samples used for the drift estimation.
+    const MAX_SAMPLES: usize = 64;
+    /// The maximum correction per second (0.5ms per second),
+    /// small enough to not be noticeable.
+    pub const MAX_CORRECTION_PER_SEC: f64 = 0.0005;
+
+    pub fn new() -> Self {
+        Self {
+            samples: Default::default(),
+            pending_requests: Default::default(),
+            next_request_id: 0,
+            last_request: None,
+
+            base_offset: None,
+            intercept: 0.0,
+            drift: 0.0,
+
+            applied_offset: 0.0,
+            last_correction: None,
+        }
+    }
+
+    /// Returns the id of a new time sync request, if one should be sent.
+    pub fn try_request(&mut self, cur_time: Duration) -> Option<u64> {
+        if self
+            .last_request
+            .is_some_and(|last| cur_time.saturating_sub(last) < Self::REQUEST_INTERVAL)
+        {
+            return None;
+        }
+        self.last_request = Some(cur_time);
+
+        self.pending_requests
+            .retain(|_, sent_at| cur_time.saturating_sub(*sent_at) < Self::REQUEST_TIMEOUT);
+
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+        self.pending_requests.insert(id, cur_time);
+        Some(id)
+    }
+
+    /// Add the response of the server to a time sync request.
+    ///
+    /// `server_time` is the time the server received the request,
+    /// `overhead` the time the server needed until it responded.
+    pub fn add_response(
+        &mut self,
+        id: u64,
+        server_time: Duration,
+        overhead: Duration,
+        cur_time: Duration,
+    ) {
+        let Some(sent_at) = self.pending_requests.remove(&id) else {
+            return;
+        };
+
+        let rtt = cur_time.saturating_sub(sent_at).saturating_sub(overhead);
+        let offset = ((server_time.as_secs_f64() - sent_at.as_secs_f64())
+            + (server_time.as_secs_f64() + overhead.as_secs_f64() - cur_time.as_secs_f64()))
+            / 2.0;
+
+        let base_offset = *self.base_offset.get_or_insert(offset);
+
+        while self.samples.len() >= Self::MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(TimeSyncSample {
+            at: cur_time,
+            rtt,
+            offset: offset - base_offset,
+        });
+
+        self.estimate();
+    }
+
+    fn estimate(&mut self) {
+        // samples with a high rtt are usually affected by jitter,
+        // only use the better half of the samples.
+        let mut rtts: Vec<_> = self.samples.iter().map(|s| s.rtt).collect();
+        rtts.sort();
+        let max_rtt = rtts[rtts.len() / 2];
+        let samples: Vec<_> = self.samples.iter().filter(|s| s.rtt <= max_rtt).collect();
+
+        let count = samples.len() as f64;
+        let mean_t = samples.iter().map(|s| s.at.as_secs_f64()).sum::<f64>() / count;
+        let mean_offset = samples.iter().map(|s| s.offset).sum::<f64>() / count;
+
+        // least squares for the drift
+        let (cov, var) = samples.iter().fold((0.0, 0.0), |(cov, var), s| {
+            let dt = s.at.as_secs_f64() - mean_t;
+            (cov + dt * (s.offset - mean_offset), var + dt * dt)
+        });
+        self.drift = if samples.len() >= 3 && var > f64::EPSILON {
+            cov / var
+        } else {
+            0.0
+        };
+        self.intercept = mean_offset - self.drift * mean_t;
+    }
+
+    fn offset_at(&self, cur_time: Duration) -> f64 {
+        if self.samples.is_empty() {
+            0.0
+        } else {
+            self.intercept + self.drift * cur_time.as_secs_f64()
+        }
+    }
+
+    /// Returns the time (in seconds) that should be added to the game timer
+    /// since the last call.
+    /// A positive value means the server clock runs ahead.
+    pub fn correction(&mut self, cur_time: Duration) -> f64 {
+        let elapsed = self
+            .last_correction
+            .replace(cur_time)
+            .map(|last| cur_time.saturating_sub(last).as_secs_f64())
+            .unwrap_or_default();
+
+        let max_step = elapsed * Self::MAX_CORRECTION_PER_SEC;
+        let step = (self.offset_at(cur_time) - self.applied_offset).clamp(-max_step, max_step);
+        self.applied_offset += step;
+        step
+    }
+
+    pub fn stats(&self, cur_time: Duration) -> TimeSyncStats {
+        TimeSyncStats {
+            offset: self.offset_at(cur_time),
+            drift: self.drift,
+            applied_offset: self.applied_offset,
+            rtt: self.samples.back().map(|s| s.rtt).unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::TimeSync;
+
+    /// Simulates time sync requests to a server, whose clock drifts by `drift`
+    /// seconds per second, returns the time of the last response.
+    fn sync(time_sync: &mut TimeSync, drift: f64) -> Duration {
+        let latency = Duration::from_millis(30);
+        let mut cur_time = Duration::from_secs(100);
+        for _ in 0..30 {
+            let id = time_sync.try_request(cur_time).unwrap();
+            let server_time =
+                Duration::from_secs_f64((cur_time + latency).as_secs_f64() * (1.0 + drift));
+            cur_time += latency * 2;
+            time_sync.add_response(id, server_time, Duration::ZERO, cur_time);
+            cur_time += TimeSync::REQUEST_INTERVAL;
+        }
+        cur_time
+    }
+
+    #[test]
+    fn drift_estimation() {
+        // server clock runs 1ms per second faster
+        let drift = 0.001;
+
+        let mut time_sync = TimeSync::new();
+        let cur_time = sync(&mut time_sync, drift);
+
+        let stats = time_sync.stats(cur_time);
+        assert!((stats.drift - drift).abs() < 0.0001);
+
+        // correction never jumps
+        let step = time_sync.correction(cur_time);
+        assert_eq!(step, 0.0);
+        let step = time_sync.correction(cur_time + Duration::from_secs(1));
+        assert!(step.abs() <= TimeSync::MAX_CORRECTION_PER_SEC + f64::EPSILON);
+    }
+
+    #[test]
+    fn correction_sign() {
+        // the server clock runs ahead, so the game timer must advance faster
+        let mut time_sync = TimeSync::new();
+        let cur_time = sync(&mut time_sync, 0.001);
+        time_sync.correction(cur_time);
+        assert!(time_sync.correction(cur_time + Duration::from_secs(1)) > 0.0);
+
+        // the server clock runs behind, so the game timer must advance slower
+        let mut time_sync = TimeSync::new();
+        let cur_time = sync(&mut time_sync, -0.001);
+        time_sync.correction(cur_time);
+        assert!(time_sync.correction(cur_time + Duration::from_secs(1)) < 0.0);
+    }
+}
@@ -0,0 +1,118 @@
+use std::{path::Path, sync::Arc};
+
+use demo::{
+    recorder::{DemoRecorder, DemoRecorderCreateProps, DemoSavedCb},
+    signature::signature_path,
+    DemoEvent,
+};
+use game_interface::{
+    events::{GameEvents, GameWorldEvent, GameWorldGlobalEvent, GameWorldStatEvent},
+    types::game::{GameTickType, NonZeroGameTickType},
+};
+
+/// Only demos with this prefix are deleted by the rotation,
+/// so demos that were saved by hand are never touched.
+const AUTO_RECORD_PREFIX: &str = "auto_";
+
+/// Records every round on the server into its own demo,
+/// see [`game_config::config::ConfigServerAutoRecord`].
+///
+/// Unlike the client, the server records the snapshots & events
+/// of all stages, not from the point of view of a player.
+#[derive(Default)]
+pub struct AutoRecord {
+    recorder: Option<DemoRecorder>,
+}
+
+impl AutoRecord {
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Finishes the current demo, if any, and starts a new one.
+    /// Once saved, only the newest `max_demos` demos are kept (0 keeps all).
+    pub fn start(
+        &mut self,
+        mut props: DemoRecorderCreateProps,
+        ticks_per_second: NonZeroGameTickType,
+        max_demos: u64,
+    ) {
+        self.stop();
+
+        let demo_dir = props.io.fs.get_save_path().join("demos");
+        props.on_saved = Some(DemoSavedCb(Arc::new(move |name| {
+            log::info!(target: "auto-record", "saved demo {name}");
+            if let Err(err) = Self::remove_old_demos(&demo_dir, max_demos) {
+                log::warn!(target: "auto-record", "failed to remove old demos: {err}");
+            }
+        })));
+        // the time first, so that sorting by name sorts by age
+        let name = format!(
+            "{AUTO_RECORD_PREFIX}{}_{}",
+            chrono::Utc::now().format("%Y_%m_%d_%H_%M_%S"),
+            props.map.as_str()
+        );
+        self.recorder = DemoRecorder::new(props, ticks_per_second, Some(name))
+            .map_err(|err| log::error!(target: "auto-record", "failed to start recording: {err}"))
+            .ok();
+    }
+
+    /// Finishes the current demo, if any.
+    pub fn stop(&mut self) {
+        self.recorder = None;
+    }
+
+    pub fn add_snapshot(&mut self, monotonic_tick: GameTickType, snapshot: Vec<u8>) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.add_snapshot(monotonic_tick, snapshot);
+        }
+    }
+
+    /// Returns `true` if the events end the current round.
+    pub fn add_events(&mut self, monotonic_tick: GameTickType, events: GameEvents) -> bool {
+        let round_end = events
+            .worlds
+            .values()
+            .flat_map(|world| world.events.values())
+            .any(|ev| {
+                matches!(
+                    ev,
+                    GameWorldEvent::Global(GameWorldGlobalEvent::Stat(
+                        GameWorldStatEvent::RoundEnd
+                    ))
+                )
+            });
+        if let Some(recorder) = &mut self.recorder {
+            if !events.is_empty() {
+                recorder.add_event(monotonic_tick, DemoEvent::Game(events));
+            }
+        }
+        round_end
+    }
+
+    /// Deletes the oldest auto recorded demos, until at most `max_demos` are left.
+    fn remove_old_demos(demo_dir: &Path, max_demos: u64) -> anyhow::Result<()> {
+        if max_demos == 0 {
+            return Ok(());
+        }
+        let mut demos: Vec<_> = std::fs::read_dir(demo_dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(AUTO_RECORD_PREFIX) && name.ends_with(".twdemo")
+                    })
+            })
+            .collect();
+        demos.sort();
+        let remove_count = demos.len().saturating_sub(max_demos as usize);
+        for demo in demos.into_iter().take(remove_count) {
+            std::fs::remove_file(&demo)?;
+            // the demo might not be signed
+            let _ = std::fs::remove_file(signature_path(&demo));
+        }
+        Ok(())
+    }
+}
@@ -7,10 +7,10 @@ use std::{
 
 use game_interface::types::{game::GameEntityId, network_stats::PlayerNetworkStats};
 use hashlink::LinkedHashMap;
-use network::network::connection::NetworkConnectionId;
+use network::network::{connection::NetworkConnectionId, event::NetworkStats};
 use pool::{datatypes::PoolLinkedHashMap, pool::Pool};
 use shared_base::network::messages::PlayerInputChainable;
-use shared_network::messages::MsgSvInputAck;
+use shared_network::messages::{MsgClNetworkSettings, MsgSvInputAck};
 
 use crate::server_game::ClientAuth;
 
@@ -112,6 +112,9 @@ pub struct ServerClient {
 
     pub requested_account_rename: bool,
     pub requested_account_details: bool,
+
+    /// bandwidth related settings requested by the client
+    pub network_settings: MsgClNetworkSettings,
 }
 
 impl ServerClient {
@@ -143,6 +146,70 @@ impl ServerClient {
 
             requested_account_rename: false,
             requested_account_details: false,
+
+            network_settings: Default::default(),
+        }
+    }
+}
+
+/// The network stats of a connection only contain accumulated counters,
+/// this tracks them over time to measure the current connection quality.
+#[derive(Debug, Default)]
+pub struct ClientNetworkStatsTracker {
+    packets_sent: u64,
+    packets_lost: u64,
+    last_ping: Option<Duration>,
+
+    packet_loss: f32,
+    jitter: Duration,
+}
+
+impl ClientNetworkStatsTracker {
+    /// How much a new packet loss sample influences the tracked packet loss.
+    const PACKET_LOSS_SMOOTHING: f32 = 0.1;
+    /// Jitter is smoothed the same way as RTP does (RFC 3550).
+    const JITTER_SMOOTHING: u32 = 16;
+
+    pub fn new(initial_stats: &NetworkStats) -> Self {
+        Self {
+            packets_sent: initial_stats.packets_sent,
+            packets_lost: initial_stats.packets_lost,
+            last_ping: Some(initial_stats.ping),
+
+            packet_loss: initial_stats.packets_lost as f32
+                / initial_stats.packets_sent.clamp(1, u64::MAX) as f32,
+            jitter: Duration::ZERO,
+        }
+    }
+
+    pub fn update(&mut self, stats: &NetworkStats) -> PlayerNetworkStats {
+        let packets_sent = stats.packets_sent.saturating_sub(self.packets_sent);
+        if packets_sent > 0 {
+            let packets_lost = stats.packets_lost.saturating_sub(self.packets_lost);
+            let loss = (packets_lost as f32 / packets_sent as f32).clamp(0.0, 1.0);
+            self.packet_loss += (loss - self.packet_loss) * Self::PACKET_LOSS_SMOOTHING;
+            self.packets_sent = stats.packets_sent;
+            self.packets_lost = stats.packets_lost;
+        }
+
+        if let Some(last_ping) = self.last_ping {
+            let diff = stats.ping.abs_diff(last_ping);
+            if diff > self.jitter {
+                self.jitter += (diff - self.jitter) / Self::JITTER_SMOOTHING;
+            } else {
+                self.jitter -= (self.jitter - diff) / Self::JITTER_SMOOTHING;
+            }
+        }
+        self.last_ping = Some(stats.ping);
+
+        self.stats(stats.ping)
+    }
+
+    pub fn stats(&self, ping: Duration) -> PlayerNetworkStats {
+        PlayerNetworkStats {
+            ping,
+            packet_loss: self.packet_loss,
+            jitter: self.jitter,
         }
     }
 }
@@ -153,6 +220,9 @@ pub struct Clients {
     pub network_clients: HashMap<NetworkConnectionId, ServerNetworkClient>,
     pub clients: HashMap<NetworkConnectionId, ServerClient>,
 
+    /// connection quality of all connections, no matter in which state the client is
+    pub network_stats_trackers: HashMap<NetworkConnectionId, ClientNetworkStatsTracker>,
+
     // pools
     players_pool: Pool<LinkedHashMap<GameEntityId, ServerClientPlayer>>,
 }
@@ -166,11 +236,18 @@ impl Clients {
             network_queued_clients: Default::default(),
             network_clients: Default::default(),
             clients: Default::default(),
+            network_stats_trackers: Default::default(),
         }
     }
 }
 
 impl Clients {
+    /// Makes room in the pools for more players,
+    /// e.g. if the max player count was increased.
+    pub fn reserve_players(&self, additional: usize) {
+        self.players_pool.reserve(additional);
+    }
+
     pub fn try_client_ready(&mut self, con_id: &NetworkConnectionId) -> Option<&mut ServerClient> {
         // check if the client can be part of the game
         self.network_clients.remove(con_id).and_then(|net_client| {
@@ -0,0 +1,242 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use base_io::{io::Io, io_batcher::IoBatcherTask};
+use serde_json::Value;
+
+/// Rcon command that shows the effective config of the game mod
+/// and which layer each value comes from.
+pub const CONFIG_EFFECTIVE_RCON_CMD: &str = "config.effective";
+
+/// Where a value of the [`LayeredConfig`] comes from,
+/// later layers override earlier ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigLayer {
+    /// `config/global.json`, used for every game type & map.
+    Global,
+    /// `config/<game mod>.json`.
+    GameType,
+    /// `config/maps/<map>.json`.
+    Map,
+}
+
+impl Display for ConfigLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ConfigLayer::Global => "global",
+            ConfigLayer::GameType => "gametype",
+            ConfigLayer::Map => "map",
+        })
+    }
+}
+
+/// The config of the game mod, merged from the global defaults,
+/// the overrides of the game type and the overrides of the map
+/// when a map loads.
+///
+/// Objects are merged key by key, all other values replace
+/// the value of the earlier layer.
+#[derive(Debug, Default, Clone)]
+pub struct LayeredConfig {
+    config: Option<Value>,
+    /// The layer of every value, by its path (keys joined by `.`).
+    sources: BTreeMap<String, ConfigLayer>,
+}
+
+impl LayeredConfig {
+    /// Reads & merges all layers, missing layers are skipped.
+    pub fn read(io: &Io, mod_name: &str, map: &str) -> IoBatcherTask<Self> {
+        let files = [
+            (ConfigLayer::Global, "config/global.json".to_string()),
+            (ConfigLayer::GameType, format!("config/{mod_name}.json")),
+            (ConfigLayer::Map, format!("config/maps/{map}.json")),
+        ];
+        let fs = io.fs.clone();
+        io.io_batcher.spawn(async move {
+            let mut config = Self::default();
+            for (layer, path) in files {
+                let Ok(file) = fs.read_file(path.as_ref()).await else {
+                    continue;
+                };
+                match serde_json::from_slice::<Value>(&file) {
+                    Ok(value) if value.is_object() => config.merge(layer, value),
+                    Ok(_) => {
+                        log::warn!(target: "server", "config {path} is not a JSON object, ignored")
+                    }
+                    Err(err) => log::warn!(target: "server", "config {path} is invalid: {err}"),
+                }
+            }
+            Ok(config)
+        })
+    }
+
+    pub fn merge(&mut self, layer: ConfigLayer, value: Value) {
+        let config = self.config.get_or_insert(Value::Null);
+        Self::merge_value(&mut self.sources, config, "", layer, value);
+    }
+
+    fn merge_value(
+        sources: &mut BTreeMap<String, ConfigLayer>,
+        target: &mut Value,
+        path: &str,
+        layer: ConfigLayer,
+        value: Value,
+    ) {
+        let sub_path_prefix = format!("{path}.");
+        match value {
+            Value::Object(value) => {
+                if !target.is_object() {
+                    sources.remove(path);
+                    *target = Value::Object(Default::default());
+                }
+                if value.is_empty() && !path.is_empty() {
+                    sources.insert(path.to_string(), layer);
+                }
+                let target = target.as_object_mut().unwrap();
+                for (key, value) in value {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    let target = target.entry(key).or_insert(Value::Null);
+                    Self::merge_value(sources, target, &path, layer, value);
+                }
+            }
+            value => {
+                // an object of an earlier layer is replaced as a whole
+                sources.retain(|p, _| !p.starts_with(&sub_path_prefix));
+                sources.insert(path.to_string(), layer);
+                *target = value;
+            }
+        }
+    }
+
+    /// The merged config as passed to the game mod,
+    /// `None` if no layer exists.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        self.config
+            .as_ref()
+            .and_then(|config| serde_json::to_vec(config).ok())
+    }
+
+    /// Whether a layer other than [`ConfigLayer::GameType`] contributed values.
+    pub fn has_overrides(&self) -> bool {
+        self.sources
+            .values()
+            .any(|&layer| layer != ConfigLayer::GameType)
+    }
+
+    /// One line per value, with the layer it comes from.
+    pub fn format(&self) -> Vec<String> {
+        fn collect(value: &Value, path: &str, config: &LayeredConfig, lines: &mut Vec<String>) {
+            match value {
+                Value::Object(values) if !values.is_empty() || path.is_empty() => {
+                    for (key, value) in values {
+                        let path = if path.is_empty() {
+                            key.clone()
+                        } else {
+                            format!("{path}.{key}")
+                        };
+                        collect(value, &path, config, lines);
+                    }
+                }
+                value => lines.push(format!(
+                    "{path} = {value} ({})",
+                    config
+                        .sources
+                        .get(path)
+                        .map(|layer| layer.to_string())
+                        .unwrap_or_default()
+                )),
+            }
+        }
+
+        let mut lines = Vec::new();
+        match &self.config {
+            Some(config) => collect(config, "", self, &mut lines),
+            None => lines.push("no config layers exist, the game mod uses its defaults".into()),
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{ConfigLayer, LayeredConfig};
+
+    fn sources(config: &LayeredConfig) -> Vec<(&str, ConfigLayer)> {
+        config
+            .sources
+            .iter()
+            .map(|(path, &layer)| (path.as_str(), layer))
+            .collect()
+    }
+
+    #[test]
+    fn merge_objects_key_by_key() {
+        let mut config = LayeredConfig::default();
+        assert!(config.to_bytes().is_none());
+
+        config.merge(
+            ConfigLayer::GameType,
+            json!({"a": 1, "b": {"c": 2, "d": 3}}),
+        );
+        assert!(!config.has_overrides());
+        config.merge(ConfigLayer::Global, json!({"e": true}));
+        config.merge(ConfigLayer::Map, json!({"a": 5, "b": {"c": 4}}));
+        assert!(config.has_overrides());
+
+        assert_eq!(
+            config.config,
+            Some(json!({"a": 5, "b": {"c": 4, "d": 3}, "e": true}))
+        );
+        assert_eq!(
+            sources(&config),
+            [
+                ("a", ConfigLayer::Map),
+                ("b.c", ConfigLayer::Map),
+                ("b.d", ConfigLayer::GameType),
+                ("e", ConfigLayer::Global),
+            ]
+        );
+        assert_eq!(
+            config.format(),
+            [
+                "a = 5 (map)",
+                "b.c = 4 (map)",
+                "b.d = 3 (gametype)",
+                "e = true (global)",
+            ]
+        );
+    }
+
+    #[test]
+    fn replace_objects_and_values() {
+        let mut config = LayeredConfig::default();
+        config.merge(ConfigLayer::Global, json!({"a": {"b": 1}, "c": 2}));
+
+        // a value replaces the whole object of an earlier layer
+        config.merge(ConfigLayer::GameType, json!({"a": [1, 2]}));
+        // an object replaces the value of an earlier layer
+        config.merge(ConfigLayer::Map, json!({"c": {"d": 3}, "e": {}}));
+
+        assert_eq!(
+            config.config,
+            Some(json!({"a": [1, 2], "c": {"d": 3}, "e": {}}))
+        );
+        assert_eq!(
+            sources(&config),
+            [
+                ("a", ConfigLayer::GameType),
+                ("c.d", ConfigLayer::Map),
+                ("e", ConfigLayer::Map),
+            ]
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&config.to_bytes().unwrap()).unwrap(),
+            json!({"a": [1, 2], "c": {"d": 3}, "e": {}})
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    fmt::Display,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use base::hash::{fmt_hash, Hash};
+use base_io::io::Io;
+use game_interface::types::game::NonZeroGameTickType;
+use map::map::{groups::layers::physics::MapLayerPhysics, Map};
+use shared_base::{game_types::time_until_tick, mapdef_06::EEntityTiles};
+use sql::database::Database;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticStatus {
+    Ok,
+    Warning,
+    /// The server should not run like this.
+    Critical,
+}
+
+impl Display for DiagnosticStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagnosticStatus::Ok => write!(f, "ok"),
+            DiagnosticStatus::Warning => write!(f, "warning"),
+            DiagnosticStatus::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticCheck {
+    pub name: &'static str,
+    pub status: DiagnosticStatus,
+    pub msg: String,
+}
+
+impl DiagnosticCheck {
+    fn new(name: &'static str, status: DiagnosticStatus, msg: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            msg: msg.into(),
+        }
+    }
+}
+
+/// The results of the self-check at server start,
+/// see [`game_config::config::ConfigServerStartupChecks`].
+#[derive(Debug, Default)]
+pub struct DiagnosticsReport {
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+impl Display for DiagnosticsReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "server startup checks:")?;
+        let name_len = self
+            .checks
+            .iter()
+            .map(|check| check.name.len())
+            .max()
+            .unwrap_or_default();
+        for check in &self.checks {
+            writeln!(
+                f,
+                "  {:<name_len$}  {:<8}  {}",
+                check.name, check.status, check.msg
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl DiagnosticsReport {
+    pub fn critical(&self) -> impl Iterator<Item = &DiagnosticCheck> {
+        self.checks
+            .iter()
+            .filter(|check| check.status == DiagnosticStatus::Critical)
+    }
+
+    pub fn log(&self) {
+        if self.critical().next().is_some() {
+            log::error!(target: "server", "{self}");
+        } else if self
+            .checks
+            .iter()
+            .any(|check| check.status == DiagnosticStatus::Warning)
+        {
+            log::warn!(target: "server", "{self}");
+        } else {
+            log::info!(target: "server", "{self}");
+        }
+    }
+
+    /// The physics group must have a game layer,
+    /// every tile layer must cover the whole group and
+    /// players need somewhere to spawn.
+    pub fn check_map_physics(&mut self, map_file: &[u8]) {
+        const NAME: &str = "map physics";
+        let physics = match Map::read_physics_group(map_file) {
+            Ok(physics) => physics,
+            Err(err) => {
+                self.checks.push(DiagnosticCheck::new(
+                    NAME,
+                    DiagnosticStatus::Critical,
+                    format!("the physics group could not be read: {err}"),
+                ));
+                return;
+            }
+        };
+        let tile_count = physics.attr.width.get() as usize * physics.attr.height.get() as usize;
+        let mut has_game_layer = false;
+        for layer in physics.layers.iter() {
+            let tiles = match layer {
+                MapLayerPhysics::Arbitrary(_) => continue,
+                MapLayerPhysics::Game(layer) => {
+                    has_game_layer = true;
+                    layer.tiles.len()
+                }
+                MapLayerPhysics::Front(layer) => layer.tiles.len(),
+                MapLayerPhysics::Tele(layer) => layer.base.tiles.len(),
+                MapLayerPhysics::Speedup(layer) => layer.tiles.len(),
+                MapLayerPhysics::Switch(layer) => layer.base.tiles.len(),
+                MapLayerPhysics::Tune(layer) => layer.base.tiles.len(),
+            };
+            if tiles != tile_count {
+                self.checks.push(DiagnosticCheck::new(
+                    NAME,
+                    DiagnosticStatus::Critical,
+                    format!("a physics layer has {tiles} tiles, but the group has {tile_count}"),
+                ));
+                return;
+            }
+        }
+        if !has_game_layer {
+            self.checks.push(DiagnosticCheck::new(
+                NAME,
+                DiagnosticStatus::Critical,
+                "the map has no game layer",
+            ));
+            return;
+        }
+
+        let spawns = [
+            EEntityTiles::Spawn as u8,
+            EEntityTiles::SpawnRed as u8,
+            EEntityTiles::SpawnBlue as u8,
+        ];
+        let has_spawn = physics
+            .get_game_layer_tiles()
+            .iter()
+            .any(|tile| spawns.contains(&tile.index));
+        self.checks.push(if has_spawn {
+            DiagnosticCheck::new(
+                NAME,
+                DiagnosticStatus::Ok,
+                format!(
+                    "{}x{}, {} layers",
+                    physics.attr.width.get(),
+                    physics.attr.height.get(),
+                    physics.layers.len()
+                ),
+            )
+        } else {
+            DiagnosticCheck::new(
+                NAME,
+                DiagnosticStatus::Warning,
+                "the game layer has no spawn tiles",
+            )
+        });
+    }
+
+    pub fn check_database(&mut self, io: &Io, db: Option<&Arc<Database>>, max_latency: Duration) {
+        const NAME: &str = "database";
+        let Some(db) = db.cloned() else {
+            self.checks.push(DiagnosticCheck::new(
+                NAME,
+                DiagnosticStatus::Ok,
+                "not configured",
+            ));
+            return;
+        };
+        let ping = io
+            .io_batcher
+            .spawn(async move { db.ping().await })
+            .get_storage();
+        self.checks.push(match ping {
+            Ok(latency) if latency > max_latency => DiagnosticCheck::new(
+                NAME,
+                DiagnosticStatus::Warning,
+                format!(
+                    "latency of {}ms is above {}ms",
+                    latency.as_millis(),
+                    max_latency.as_millis()
+                ),
+            ),
+            Ok(latency) => DiagnosticCheck::new(
+                NAME,
+                DiagnosticStatus::Ok,
+                format!("latency of {}ms", latency.as_millis()),
+            ),
+            Err(err) => DiagnosticCheck::new(
+                NAME,
+                DiagnosticStatus::Critical,
+                format!("not reachable: {err}"),
+            ),
+        });
+    }
+
+    /// `port` is the configured port, 0 means any port.
+    pub fn check_ports(&mut self, port: u16, bound_port: u16, resource_port: Option<u16>) {
+        self.checks.push(if port != 0 && bound_port != port {
+            DiagnosticCheck::new(
+                "game port",
+                DiagnosticStatus::Warning,
+                format!("bound to {bound_port} instead of {port}"),
+            )
+        } else {
+            DiagnosticCheck::new("game port", DiagnosticStatus::Ok, bound_port.to_string())
+        });
+        self.checks.push(match resource_port {
+            Some(port) => {
+                DiagnosticCheck::new("resource port", DiagnosticStatus::Ok, port.to_string())
+            }
+            None => DiagnosticCheck::new(
+                "resource port",
+                DiagnosticStatus::Critical,
+                "the resource server is not running",
+            ),
+        });
+    }
+
+    /// Downloads the map from the resource server,
+    /// like clients that don't have it do.
+    pub fn check_resource_server(
+        &mut self,
+        io: &Io,
+        resource_port: Option<u16>,
+        map_name: &str,
+        map_hash: &Hash,
+    ) {
+        const NAME: &str = "resource server";
+        let Some(port) = resource_port else {
+            return;
+        };
+        let http = io.http.clone();
+        let url = format!(
+            "http://127.0.0.1:{port}/map/maps/{map_name}_{}.twmap",
+            fmt_hash(map_hash)
+        );
+        let map_hash = *map_hash;
+        let res = io
+            .io_batcher
+            .spawn(async move {
+                http.download_binary(url.as_str().try_into()?, &map_hash)
+                    .await?;
+                Ok(())
+            })
+            .get_storage();
+        self.checks.push(match res {
+            Ok(()) => DiagnosticCheck::new(NAME, DiagnosticStatus::Ok, "serves the map"),
+            Err(err) => DiagnosticCheck::new(
+                NAME,
+                DiagnosticStatus::Critical,
+                format!("the map could not be downloaded: {err}"),
+            ),
+        });
+    }
+
+    /// How much time of a tick is left, after the OS woke up the server.
+    pub fn check_tick_timing(&mut self, ticks_in_a_second: NonZeroGameTickType) {
+        const NAME: &str = "tick timing";
+        const SAMPLES: usize = 10;
+        let tick_time = time_until_tick(ticks_in_a_second);
+        let oversleep = (0..SAMPLES)
+            .map(|_| {
+                let start = Instant::now();
+                std::thread::sleep(Duration::from_millis(1));
+                start.elapsed().saturating_sub(Duration::from_millis(1))
+            })
+            .max()
+            .unwrap_or_default();
+        let headroom = tick_time.saturating_sub(oversleep);
+        let msg = format!(
+            "{:.1}ms of {:.1}ms per tick left after waking up",
+            headroom.as_secs_f64() * 1000.0,
+            tick_time.as_secs_f64() * 1000.0
+        );
+        self.checks.push(if headroom < tick_time / 2 {
+            DiagnosticCheck::new(NAME, DiagnosticStatus::Warning, msg)
+        } else {
+            DiagnosticCheck::new(NAME, DiagnosticStatus::Ok, msg)
+        });
+    }
+}
@@ -1,8 +1,16 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod auto_map_votes;
+pub mod auto_record;
 pub mod client;
+pub mod config_layers;
+pub mod diagnostics;
+pub mod max_players;
 pub mod rcon;
+pub mod reports;
+pub mod scheduler;
 pub mod server;
 pub mod server_game;
+pub mod sim_speed;
 pub mod spatial_chat;
+pub mod whitelist;
@@ -0,0 +1,18 @@
+use anyhow::anyhow;
+
+/// Rcon command that changes the max player count of the server at runtime.
+/// Only admins can use it, since it can kick spectators.
+pub const MAX_PLAYERS_RCON_CMD: &str = "max_players";
+
+/// Parses the argument of [`MAX_PLAYERS_RCON_CMD`].
+pub fn parse_max_players(arg: &str) -> anyhow::Result<usize> {
+    let max_players = arg
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| anyhow!("{MAX_PLAYERS_RCON_CMD}: expected a number, got \"{arg}\""))?;
+    anyhow::ensure!(
+        max_players > 0,
+        "{MAX_PLAYERS_RCON_CMD}: the server needs at least one slot"
+    );
+    Ok(max_players)
+}
@@ -1,10 +1,7 @@
 use std::collections::HashMap;
 
 use base_io::io::Io;
-use game_interface::{
-    rcon_commands::AuthLevel,
-    types::player_info::{AccountId, PlayerUniqueId},
-};
+use game_interface::{rcon_commands::AuthLevel, types::player_info::AccountId};
 use rand::Rng;
 
 use crate::client::ServerClient;
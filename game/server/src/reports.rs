@@ -0,0 +1,150 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    net::IpAddr,
+    time::Duration,
+};
+
+use game_interface::types::game::GameTickType;
+use serde::{Deserialize, Serialize};
+use shared_game::sql::player_report::StatementResult;
+
+/// Chat command players use to report another player,
+/// `report <player> "<reason>"`.
+pub const REPORT_CHAT_CMD: &str = "report";
+/// Rcon command that lists the latest reports.
+pub const REPORTS_LIST_RCON_CMD: &str = "reports.list";
+/// Rcon command that shows a single report with its context.
+pub const REPORTS_VIEW_RCON_CMD: &str = "reports.view";
+
+/// How many of the last chat messages are stored with a report.
+const RECENT_CHAT_MAX: usize = 20;
+/// How long a player has to wait between two reports.
+const REPORT_COOLDOWN: Duration = Duration::from_secs(60);
+/// How many reports `reports.list` shows.
+pub const REPORTS_LIST_MAX: i64 = 10;
+const REASON_MAX_LEN: usize = 256;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportChatMsg {
+    pub player: String,
+    pub msg: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportCharacterPos {
+    pub player: String,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// What happened around the time a player was reported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportContext {
+    pub tick: GameTickType,
+    pub positions: Vec<ReportCharacterPos>,
+    pub recent_chat: Vec<ReportChatMsg>,
+}
+
+/// A report of the chat command.
+#[derive(Debug)]
+pub struct ReportRequest {
+    pub player: String,
+    pub reason: String,
+}
+
+impl ReportRequest {
+    pub fn new(player: &str, reason: &str) -> anyhow::Result<Self> {
+        let reason = reason.trim();
+        anyhow::ensure!(!player.is_empty(), "the player name must not be empty");
+        anyhow::ensure!(!reason.is_empty(), "the reason must not be empty");
+        anyhow::ensure!(
+            reason.chars().count() <= REASON_MAX_LEN,
+            "the reason must not be longer than {REASON_MAX_LEN} characters"
+        );
+        Ok(Self {
+            player: player.to_string(),
+            reason: reason.to_string(),
+        })
+    }
+}
+
+/// State of the server side moderation queue.
+///
+/// The reports themselves are stored in the database,
+/// this only keeps what is needed to create them.
+/// Like the whitelist, it is owned by the server and kept across map changes.
+#[derive(Debug, Default)]
+pub struct Reports {
+    recent_chat: VecDeque<ReportChatMsg>,
+    /// When a client reported the last time, by ip,
+    /// so reconnecting does not reset the cooldown.
+    last_reports: HashMap<IpAddr, Duration>,
+}
+
+impl Reports {
+    /// Remembers a message of the global chat as context for reports.
+    pub fn add_chat_msg(&mut self, player: &str, msg: &str) {
+        if self.recent_chat.len() >= RECENT_CHAT_MAX {
+            self.recent_chat.pop_front();
+        }
+        self.recent_chat.push_back(ReportChatMsg {
+            player: player.to_string(),
+            msg: msg.to_string(),
+        });
+    }
+
+    pub fn recent_chat(&self) -> Vec<ReportChatMsg> {
+        self.recent_chat.iter().cloned().collect()
+    }
+
+    /// Checks the cooldown of the reporter and starts a new one.
+    ///
+    /// Returns the remaining cooldown, if the reporter has to wait.
+    pub fn try_report(&mut self, ip: IpAddr, cur_time: Duration) -> Result<(), Duration> {
+        self.last_reports
+            .retain(|_, last| cur_time.saturating_sub(*last) < REPORT_COOLDOWN);
+        if let Some(last) = self.last_reports.get(&ip) {
+            return Err(REPORT_COOLDOWN.saturating_sub(cur_time.saturating_sub(*last)));
+        }
+        self.last_reports.insert(ip, cur_time);
+        Ok(())
+    }
+
+    /// The line `reports.list` shows for a report.
+    pub fn format_summary(report: &StatementResult) -> String {
+        format!(
+            "#{} {} on {}: {} reported {}: {}",
+            report.id,
+            chrono::DateTime::from_timestamp(report.create_time.secs as i64, 0)
+                .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default(),
+            report.map,
+            report.reporter,
+            report.reported,
+            report.reason,
+        )
+    }
+
+    /// The lines `reports.view` shows for a report.
+    pub fn format_details(report: &StatementResult) -> Vec<String> {
+        let mut lines = vec![Self::format_summary(report)];
+        match serde_json::from_str::<ReportContext>(&report.context) {
+            Ok(context) => {
+                lines.push(format!("tick: {}", context.tick));
+                lines.extend(
+                    context.positions.iter().map(|pos| {
+                        format!("position of {}: {:.1}, {:.1}", pos.player, pos.x, pos.y)
+                    }),
+                );
+                lines.extend(
+                    context
+                        .recent_chat
+                        .iter()
+                        .map(|msg| format!("chat of {}: {}", msg.player, msg.msg)),
+                );
+            }
+            Err(err) => lines.push(format!("invalid context: {err}")),
+        }
+        lines
+    }
+}
@@ -0,0 +1,518 @@
+use std::ops::RangeInclusive;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Datelike, Local, TimeZone, Timelike};
+use game_config::config::{ConfigServerScheduledAction, ConfigServerScheduledEvent};
+
+use crate::max_players::parse_max_players;
+
+/// The parsed cron-like schedule of a scheduled event,
+/// every field is a bit set of the allowed values.
+#[derive(Debug, Clone, Copy)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// in cron a day matches if either the day of month or
+    /// the day of week matches, if both are restricted.
+    days_restricted: bool,
+}
+
+impl Schedule {
+    fn parse_field(field: &str, range: RangeInclusive<u32>) -> anyhow::Result<(u64, bool)> {
+        let mut res = 0;
+        let mut restricted = false;
+        for part in field.split(',') {
+            let (values, step) = match part.split_once('/') {
+                Some((values, step)) => (values, step.parse::<u32>()?),
+                None => (part, 1),
+            };
+            anyhow::ensure!(step > 0, "step must be at least 1");
+            let values = if values == "*" {
+                range.clone()
+            } else {
+                restricted = true;
+                match values.split_once('-') {
+                    Some((start, end)) => {
+                        let (start, end) = (start.parse()?, end.parse()?);
+                        anyhow::ensure!(start <= end, "{part} is a reversed range");
+                        start..=end
+                    }
+                    None => {
+                        let value = values.parse()?;
+                        value..=value
+                    }
+                }
+            };
+            anyhow::ensure!(
+                range.contains(values.start()) && range.contains(values.end()),
+                "{part} is outside of {}-{}",
+                range.start(),
+                range.end()
+            );
+            restricted |= step > 1;
+            for value in values.step_by(step as usize) {
+                res |= 1 << value;
+            }
+        }
+        Ok((res, restricted))
+    }
+
+    pub fn parse(schedule: &str) -> anyhow::Result<Self> {
+        let fields: Vec<_> = schedule.split_whitespace().collect();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields.as_slice() else {
+            return Err(anyhow!(
+                "expected 5 fields (minute hour day-of-month month day-of-week)"
+            ));
+        };
+        let (days_of_month, days_of_month_restricted) = Self::parse_field(days_of_month, 1..=31)?;
+        // 0 & 7 are both sunday
+        let (mut days_of_week, days_of_week_restricted) = Self::parse_field(days_of_week, 0..=7)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+        Ok(Self {
+            minutes: Self::parse_field(minutes, 0..=59)?.0,
+            hours: Self::parse_field(hours, 0..=23)?.0,
+            days_of_month: if days_of_month_restricted || !days_of_week_restricted {
+                days_of_month
+            } else {
+                0
+            },
+            months: Self::parse_field(months, 1..=12)?.0,
+            days_of_week: if days_of_week_restricted || !days_of_month_restricted {
+                days_of_week
+            } else {
+                0
+            },
+            days_restricted: days_of_month_restricted || days_of_week_restricted,
+        })
+    }
+
+    pub fn matches(&self, time: &DateTime<Local>) -> bool {
+        let has = |set: u64, value: u32| set & (1 << value) != 0;
+        let day_matches = if self.days_restricted {
+            has(self.days_of_month, time.day())
+                || has(self.days_of_week, time.weekday().num_days_from_sunday())
+        } else {
+            true
+        };
+        has(self.minutes, time.minute())
+            && has(self.hours, time.hour())
+            && has(self.months, time.month())
+            && day_matches
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduledReset {
+    /// A fresh game on the current map.
+    State,
+    /// The map & game mod are read again, the game state is kept.
+    Map,
+}
+
+#[derive(Debug)]
+pub enum ScheduledAction {
+    Broadcast(String),
+    ScoreLimit(u64),
+    /// the maps of the rotation
+    RotateMap(Vec<String>),
+    Reset(ScheduledReset),
+    /// the player slots
+    MaxPlayers(usize),
+}
+
+/// A reset that is announced, but not due yet.
+#[derive(Debug)]
+struct PendingReset {
+    reset: ScheduledReset,
+    /// the unix timestamp in seconds at which the reset happens
+    at: i64,
+    countdown: i64,
+    /// the remaining seconds that were announced last
+    announced: Option<i64>,
+}
+
+/// Executes the scheduled events of the server config.
+///
+/// The scheduler is owned by the server (and not by the game),
+/// so its state stays the same across map changes.
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    events: Vec<(Schedule, ConfigServerScheduledEvent)>,
+    /// the minute (since the unix epoch) that was checked last,
+    /// every minute is only checked once.
+    last_minute: Option<i64>,
+    /// the score limit that was set by a scheduled event,
+    /// it must be applied again after the game was reloaded.
+    pub score_limit: Option<u64>,
+    pending_resets: Vec<PendingReset>,
+}
+
+impl Scheduler {
+    /// If the server loop was blocked (e.g. by a map change),
+    /// only this many missed minutes are checked.
+    const MAX_MISSED_MINUTES: i64 = 10;
+    /// The remaining seconds of a reset countdown that are announced,
+    /// additionally to the start of the countdown.
+    const COUNTDOWN_ANNOUNCEMENTS: [i64; 10] = [600, 300, 60, 30, 10, 5, 4, 3, 2, 1];
+    /// How far back the latest change of the player slots is searched
+    /// when the server starts.
+    const MAX_PLAYERS_LOOKBACK_MINUTES: i64 = 7 * 24 * 60;
+
+    pub fn new(events: &[ConfigServerScheduledEvent]) -> Self {
+        let events = events
+            .iter()
+            .filter_map(|ev| match Schedule::parse(&ev.schedule) {
+                Ok(schedule) => Some((schedule, ev.clone())),
+                Err(err) => {
+                    log::error!(
+                        target: "server",
+                        "invalid schedule \"{}\" of a scheduled event: {err}",
+                        ev.schedule
+                    );
+                    None
+                }
+            })
+            // resets are checked every minute, so only warn once
+            .filter(|(_, ev)| match Self::reset_countdown(ev) {
+                Some(Err(err)) => {
+                    log::error!(
+                        target: "server",
+                        "invalid countdown \"{}\" of a scheduled reset: {err}",
+                        ev.arg
+                    );
+                    false
+                }
+                _ => true,
+            })
+            .collect();
+        Self {
+            events,
+            ..Default::default()
+        }
+    }
+
+    /// The kind & countdown in seconds of scheduled resets.
+    fn reset_countdown(
+        ev: &ConfigServerScheduledEvent,
+    ) -> Option<anyhow::Result<(ScheduledReset, i64)>> {
+        let reset = match ev.action {
+            ConfigServerScheduledAction::ResetState => ScheduledReset::State,
+            ConfigServerScheduledAction::ReloadMap => ScheduledReset::Map,
+            ConfigServerScheduledAction::Broadcast
+            | ConfigServerScheduledAction::ScoreLimit
+            | ConfigServerScheduledAction::RotateMap
+            | ConfigServerScheduledAction::MaxPlayers => return None,
+        };
+        let arg = ev.arg.trim();
+        Some(if arg.is_empty() {
+            Ok((reset, 0))
+        } else {
+            arg.parse::<u32>()
+                .map(|countdown| (reset, countdown as i64))
+                .map_err(|err| anyhow!(err))
+        })
+    }
+
+    fn countdown_msg(reset: ScheduledReset, remaining: i64) -> String {
+        let what = match reset {
+            ScheduledReset::State => "The game is reset",
+            ScheduledReset::Map => "The map is reloaded",
+        };
+        format!(
+            "{what} in {remaining} second{}",
+            if remaining == 1 { "" } else { "s" }
+        )
+    }
+
+    fn action(ev: &ConfigServerScheduledEvent) -> Option<ScheduledAction> {
+        match ev.action {
+            ConfigServerScheduledAction::Broadcast => {
+                Some(ScheduledAction::Broadcast(ev.arg.clone()))
+            }
+            ConfigServerScheduledAction::ScoreLimit => match ev.arg.trim().parse() {
+                Ok(score_limit) => Some(ScheduledAction::ScoreLimit(score_limit)),
+                Err(err) => {
+                    log::error!(target: "server", "invalid score limit \"{}\": {err}", ev.arg);
+                    None
+                }
+            },
+            ConfigServerScheduledAction::RotateMap => {
+                let maps: Vec<_> = ev
+                    .arg
+                    .split(',')
+                    .map(|map| map.trim())
+                    .filter(|map| !map.is_empty())
+                    .map(|map| map.to_string())
+                    .collect();
+                (!maps.is_empty()).then_some(ScheduledAction::RotateMap(maps))
+            }
+            ConfigServerScheduledAction::MaxPlayers => match parse_max_players(&ev.arg) {
+                Ok(max_players) => Some(ScheduledAction::MaxPlayers(max_players)),
+                Err(err) => {
+                    log::error!(target: "server", "invalid player slots \"{}\": {err}", ev.arg);
+                    None
+                }
+            },
+            // see `reset_countdown`
+            ConfigServerScheduledAction::ResetState | ConfigServerScheduledAction::ReloadMap => {
+                None
+            }
+        }
+    }
+
+    /// The latest change of the player slots before `minute`,
+    /// so that the slots of the time of day apply right after the start.
+    fn latest_max_players(&self, minute: i64) -> Option<ScheduledAction> {
+        let events: Vec<_> = self
+            .events
+            .iter()
+            .filter(|(_, ev)| ev.action == ConfigServerScheduledAction::MaxPlayers)
+            .collect();
+        if events.is_empty() {
+            return None;
+        }
+        (minute - Self::MAX_PLAYERS_LOOKBACK_MINUTES..minute)
+            .rev()
+            .find_map(|minute| {
+                let time = Local.timestamp_opt(minute * 60, 0).single()?;
+                // like in `update`, the last event of a minute wins
+                events
+                    .iter()
+                    .rev()
+                    .find(|(schedule, _)| schedule.matches(&time))
+                    .and_then(|(_, ev)| Self::action(ev))
+            })
+    }
+
+    /// Returns the actions of all events that are due since the last call.
+    ///
+    /// Resets happen at the exact time of their schedule (checked every call,
+    /// not only once per minute), their countdown is announced as broadcasts before.
+    pub fn update(&mut self, now: DateTime<Local>) -> Vec<ScheduledAction> {
+        let mut actions = Vec::new();
+        if self.events.is_empty() {
+            return actions;
+        }
+
+        let cur_minute = now.timestamp().div_euclid(60);
+        if self.last_minute.is_none() {
+            actions.extend(self.latest_max_players(cur_minute));
+        }
+        let first_minute = self
+            .last_minute
+            .map(|minute| minute + 1)
+            .unwrap_or(cur_minute)
+            .max(cur_minute - Self::MAX_MISSED_MINUTES);
+        self.last_minute = Some(cur_minute);
+
+        for minute in first_minute..=cur_minute {
+            let Some(time) = Local.timestamp_opt(minute * 60, 0).single() else {
+                continue;
+            };
+            for (schedule, ev) in &self.events {
+                if schedule.matches(&time) {
+                    if let Some(action) = Self::action(ev) {
+                        if let ScheduledAction::ScoreLimit(score_limit) = &action {
+                            self.score_limit = Some(*score_limit);
+                        }
+                        actions.push(action);
+                    }
+                }
+
+                // resets are found ahead of time, so the countdown can start in time
+                let Some(Ok((reset, countdown))) = Self::reset_countdown(ev) else {
+                    continue;
+                };
+                let reset_minute = minute + (countdown + 59) / 60;
+                let at = reset_minute * 60;
+                let Some(reset_time) = Local.timestamp_opt(at, 0).single() else {
+                    continue;
+                };
+                if schedule.matches(&reset_time)
+                    && !self.pending_resets.iter().any(|pending| pending.at == at)
+                {
+                    self.pending_resets.push(PendingReset {
+                        reset,
+                        at,
+                        countdown,
+                        announced: None,
+                    });
+                }
+            }
+        }
+
+        let now_ms = now.timestamp_millis();
+        self.pending_resets.retain_mut(|pending| {
+            let remaining_ms = pending.at * 1000 - now_ms;
+            if remaining_ms <= 0 {
+                actions.push(ScheduledAction::Reset(pending.reset));
+                return false;
+            }
+            let remaining = (remaining_ms + 999) / 1000;
+            let announce = match pending.announced {
+                None => remaining <= pending.countdown,
+                Some(announced) => {
+                    remaining < announced && Self::COUNTDOWN_ANNOUNCEMENTS.contains(&remaining)
+                }
+            };
+            if announce {
+                pending.announced = Some(remaining);
+                actions.push(ScheduledAction::Broadcast(Self::countdown_msg(
+                    pending.reset,
+                    remaining,
+                )));
+            }
+            true
+        });
+        actions
+    }
+
+    /// The map that follows the current map in the rotation.
+    pub fn next_map<'a>(maps: &'a [String], cur_map: &str) -> Option<&'a String> {
+        let next = maps
+            .iter()
+            .position(|map| map == cur_map)
+            .map(|index| (index + 1) % maps.len())
+            .unwrap_or_default();
+        maps.get(next)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Local, TimeZone};
+    use game_config::config::{ConfigServerScheduledAction, ConfigServerScheduledEvent};
+
+    use super::{Schedule, ScheduledAction, Scheduler};
+
+    #[test]
+    fn parse_fields() {
+        assert_eq!(
+            Schedule::parse_field("*", 0..=5).unwrap(),
+            (0b111111, false)
+        );
+        assert_eq!(Schedule::parse_field("3", 0..=59).unwrap(), (1 << 3, true));
+        // ranges
+        assert_eq!(
+            Schedule::parse_field("1-3", 0..=59).unwrap(),
+            (0b1110, true)
+        );
+        assert_eq!(
+            Schedule::parse_field("2-2", 0..=59).unwrap(),
+            (1 << 2, true)
+        );
+        // steps
+        assert_eq!(
+            Schedule::parse_field("*/2", 0..=5).unwrap(),
+            (0b010101, true)
+        );
+        assert_eq!(
+            Schedule::parse_field("1-5/2", 0..=59).unwrap(),
+            (0b101010, true)
+        );
+        assert_eq!(
+            Schedule::parse_field("*/1", 0..=5).unwrap(),
+            (0b111111, false)
+        );
+        // lists
+        assert_eq!(
+            Schedule::parse_field("1,4-5,0", 0..=59).unwrap(),
+            (0b110011, true)
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for field in [
+            "5-1", "60", "0-60", "*/0", "a", "1-", "-1", "", "1,,2", "*/x",
+        ] {
+            assert!(
+                Schedule::parse_field(field, 0..=59).is_err(),
+                "{field} must be rejected"
+            );
+        }
+        assert!(Schedule::parse("* * * *").is_err());
+        assert!(Schedule::parse("* * * * * *").is_err());
+        assert!(Schedule::parse("* * 0 * *").is_err());
+        assert!(Schedule::parse("* * * 13 *").is_err());
+        assert!(Schedule::parse("* * * * 8").is_err());
+    }
+
+    #[test]
+    fn matches() {
+        // 2024-01-01 is a monday
+        let time = |month, day, hour, minute| {
+            Local
+                .with_ymd_and_hms(2024, month, day, hour, minute, 0)
+                .unwrap()
+        };
+
+        let schedule = Schedule::parse("30 4 * * *").unwrap();
+        assert!(schedule.matches(&time(1, 1, 4, 30)));
+        assert!(!schedule.matches(&time(1, 1, 4, 31)));
+        assert!(!schedule.matches(&time(1, 1, 5, 30)));
+
+        let schedule = Schedule::parse("*/15 * * * *").unwrap();
+        assert!(schedule.matches(&time(1, 1, 0, 45)));
+        assert!(!schedule.matches(&time(1, 1, 0, 50)));
+
+        // 7 is sunday, like 0
+        let schedule = Schedule::parse("0 0 * * 7").unwrap();
+        assert!(schedule.matches(&time(1, 7, 0, 0)));
+        assert!(!schedule.matches(&time(1, 1, 0, 0)));
+
+        // either the day of month or the day of week has to match
+        let schedule = Schedule::parse("0 0 16 * 1").unwrap();
+        assert!(schedule.matches(&time(1, 1, 0, 0)));
+        assert!(schedule.matches(&time(1, 16, 0, 0)));
+        assert!(!schedule.matches(&time(1, 2, 0, 0)));
+
+        let schedule = Schedule::parse("0 0 1 6-8 *").unwrap();
+        assert!(schedule.matches(&time(7, 1, 0, 0)));
+        assert!(!schedule.matches(&time(9, 1, 0, 0)));
+    }
+
+    #[test]
+    fn max_players_of_the_time_of_day() {
+        let event = |schedule: &str, arg: &str| ConfigServerScheduledEvent {
+            schedule: schedule.to_string(),
+            action: ConfigServerScheduledAction::MaxPlayers,
+            arg: arg.to_string(),
+        };
+        let time = |day, hour, minute| {
+            Local
+                .with_ymd_and_hms(2024, 1, day, hour, minute, 0)
+                .unwrap()
+        };
+        let mut scheduler = Scheduler::new(&[event("0 8 * * *", "16"), event("0 23 * * *", "4")]);
+
+        // the slots of the time of day apply right after the start
+        assert!(matches!(
+            scheduler.update(time(2, 12, 0))[..],
+            [ScheduledAction::MaxPlayers(16)]
+        ));
+        assert!(scheduler.update(time(2, 12, 1)).is_empty());
+        assert!(matches!(
+            scheduler.update(time(2, 23, 0))[..],
+            [ScheduledAction::MaxPlayers(4)]
+        ));
+
+        let mut scheduler = Scheduler::new(&[event("0 8 * * *", "16"), event("0 23 * * *", "4")]);
+        assert!(matches!(
+            scheduler.update(time(3, 2, 0))[..],
+            [ScheduledAction::MaxPlayers(4)]
+        ));
+
+        // invalid slots are ignored
+        let mut scheduler = Scheduler::new(&[event("0 8 * * *", "16"), event("0 9 * * *", "0")]);
+        assert!(matches!(
+            scheduler.update(time(2, 12, 0))[..],
+            [ScheduledAction::MaxPlayers(16)]
+        ));
+    }
+}
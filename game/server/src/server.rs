@@ -1,8 +1,10 @@
 use std::{
+    collections::{HashMap, HashSet},
     fmt::Debug,
     net::IpAddr,
     num::NonZeroUsize,
-    path::Path,
+    path::{Path, PathBuf},
+    rc::Rc,
     sync::{atomic::AtomicBool, Arc},
     time::Duration,
 };
@@ -10,7 +12,7 @@ use std::{
 use accounts_shared::game_server::user_id::{UserId, VerifyingKey};
 use anyhow::anyhow;
 use base::{
-    hash::{fmt_hash, generate_hash_for, Hash},
+    hash::{decode_hash, fmt_hash, generate_hash_for, Hash},
     system::{System, SystemTimeInterface},
 };
 use base_fs::filesys::FileSystem;
@@ -21,6 +23,7 @@ use base_io::{
 };
 use client_http_fs::{cert_downloader::CertsDownloader, client::ClientHttpTokioFs, fs::Fs};
 use config::config::ConfigEngine;
+use demo::{recorder::DemoRecorderCreateProps, signature::DemoSigningKey};
 use ed25519_dalek::SigningKey;
 use game_config::config::{ConfigDebug, ConfigGame, ConfigServerDatabase};
 use game_database::{dummy::DummyDb, traits::DbInterface};
@@ -31,8 +34,9 @@ use master_server_types::response::RegisterResponse;
 use network::network::{
     connection::NetworkConnectionId,
     connection_ban::ConnectionBans,
-    event::NetworkEvent,
+    event::{NetworkDisconnectReason, NetworkEvent},
     network::{Network, NetworkServerCertAndKey, NetworkServerCertMode, NetworkServerInitOptions},
+    notifier::NetworkEventNotifier,
     packet_compressor::DefaultNetworkPacketCompressor,
     packet_dict::ZstdNetworkDictTrainer,
     plugins::{NetworkPluginPacket, NetworkPlugins},
@@ -42,69 +46,107 @@ use network::network::{
 use pool::{datatypes::PoolLinkedHashMap, mt_datatypes::PoolCow, pool::Pool};
 use rand::RngCore;
 use shared::game::state_wasm_manager::GameStateWasmManager;
-use shared_game::sql::account_info::AccountInfo;
+use shared_game::{
+    chat_commands::{ChatCmd, ChatCmds},
+    rcon::{arg_text, RconCmd, RconCmds},
+    sql::{
+        account_info::AccountInfo,
+        player_report::{self, PlayerReports},
+    },
+};
 use sql::database::{Database, DatabaseDetails};
 use x509_cert::der::Encode;
 
 use crate::{
     auto_map_votes::AutoMapVotes,
+    auto_record::AutoRecord,
     client::{
-        ClientSnapshotForDiff, ClientSnapshotStorage, Clients, ServerClient, ServerClientPlayer,
-        ServerNetworkClient, ServerNetworkQueuedClient,
+        ClientNetworkStatsTracker, ClientSnapshotForDiff, ClientSnapshotStorage, Clients,
+        ServerClient, ServerClientPlayer, ServerNetworkClient, ServerNetworkQueuedClient,
     },
+    config_layers::{LayeredConfig, CONFIG_EFFECTIVE_RCON_CMD},
+    diagnostics::DiagnosticsReport,
+    max_players::{parse_max_players, MAX_PLAYERS_RCON_CMD},
     rcon::Rcon,
+    reports::{
+        ReportCharacterPos, ReportContext, ReportRequest, Reports, REPORTS_LIST_MAX,
+        REPORTS_LIST_RCON_CMD, REPORTS_VIEW_RCON_CMD, REPORT_CHAT_CMD,
+    },
+    scheduler::{ScheduledAction, ScheduledReset, Scheduler},
     server_game::{
-        ClientAuth, ServerExtraVoteInfo, ServerGame, ServerVote, RESERVED_DDNET_NAMES,
-        RESERVED_VANILLA_NAMES,
+        ClientAuth, ServerExtraVoteInfo, ServerGame, ServerGameVote, ServerVote,
+        RESERVED_DDNET_NAMES, RESERVED_VANILLA_NAMES,
+    },
+    sim_speed::{SimSpeed, SIM_SPEED_RCON_CMD},
+    spatial_chat::PRIORITY_SPEAKER_RCON_CMD,
+    whitelist::{
+        Whitelist, WHITELIST_ADD_RCON_CMD, WHITELIST_KICK_RCON_CMD, WHITELIST_RCON_CMD,
+        WHITELIST_REMOVE_RCON_CMD,
     },
 };
 
 use shared_base::{
-    game_types::{is_next_tick, time_until_tick},
     network::{
         messages::{
-            MsgClChatMsg, MsgClLoadVotes, MsgClSnapshotAck, MsgSvChatMsg, MsgSvServerInfo,
-            PlayerInputChainable,
+            GameModification, MsgClChatMsg, MsgClLoadVotes, MsgClSnapshotAck, MsgSvChatMsg,
+            MsgSvServerInfo, PlayerInputChainable,
         },
         server_info::{ServerDbgGame, ServerInfo},
         types::chat::{NetChatMsg, NetChatMsgPlayerChannel},
     },
     server_browser::{
-        ServerBrowserInfo, ServerBrowserInfoMap, ServerBrowserPlayer, ServerBrowserSkin,
+        load_server_icon, ServerBrowserInfo, ServerBrowserInfoMap, ServerBrowserPlayer,
+        ServerBrowserSkin,
     },
 };
 
+use command_parser::parser::{CommandArg, CommandArgType, CommandParseResult, CommandType};
 use game_interface::{
     account_info,
     chat_commands::ClientChatCommand,
     client_commands::ClientCommand,
-    events::EventClientInfo,
-    interface::GameStateInterface,
+    events::{
+        EventClientInfo, GameCharacterEvent, GameGrenadeEvent, GameWorldEntityEvent,
+        GameWorldEvent, GameWorldGlobalEvent, GameWorldVoteEvent,
+    },
+    interface::{GameStateCreateOptions, GameStateInterface},
     rcon_commands::{AuthLevel, ClientRconCommand},
+    server_commands::ServerCommand,
     types::{
-        game::{GameEntityId, GameTickType},
+        game::{GameEntityId, GameTickType, NonZeroGameTickType},
         input::CharacterInput,
         network_stats::PlayerNetworkStats,
         network_string::NetworkReducedAsciiString,
         player_info::{PlayerClientInfo, PlayerDropReason, PlayerUniqueId},
+        render::scoreboard::ScoreboardGameType,
         snapshot::SnapshotClientInfo,
     },
-    votes::{MapVote, VoteState, VoteType, Voted},
+    votes::{GameVoteAction, MapVote, VoteState, VoteType, Voted},
 };
 
 use shared_network::{
     game_event_generator::{GameEventGenerator, GameEvents},
     messages::{
         ClientToServerMessage, ClientToServerPlayerMessage, GameMessage, MsgSvInputAck,
-        MsgSvLoadVotes, ServerToClientMessage,
+        MsgSvLoadVotes, ServerToClientMessage, NETWORK_VERSION,
     },
 };
 
+/// How often a sleeping server still runs its loop,
+/// e.g. to register to the master server.
+const SLEEP_WAKE_UP_INTERVAL: Duration = Duration::from_secs(1);
+/// How often an awake server pings the database,
+/// so that idle connections are not closed by the database.
+const DB_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(60);
+/// How long the players can vote in a vote of the server.
+const SERVER_VOTE_DURATION: Duration = Duration::from_secs(25);
+
 type DbSetup = (
     Option<Arc<Database>>,
     Arc<dyn DbInterface>,
     Option<Arc<account_game_server::shared::Shared>>,
     Option<AccountInfo>,
+    Option<PlayerReports>,
 );
 
 enum GameServerDbAccount {
@@ -124,6 +166,22 @@ enum GameServerDbAccount {
 
 enum GameServerDb {
     Account(GameServerDbAccount),
+    /// The answer to a report or a moderator's request,
+    /// sent as chat messages to the player.
+    Reports {
+        con_id: NetworkConnectionId,
+        player_id: GameEntityId,
+        msgs: Vec<String>,
+    },
+}
+
+/// A map change that waits until the database requests
+/// of the current game are finished.
+enum PendingMapLoad {
+    /// Loads the map with a new game.
+    Map(String),
+    /// Loads the current map again, but keeps the game state.
+    KeepState,
 }
 
 pub struct Server {
@@ -135,9 +193,12 @@ pub struct Server {
     // network
     network: QuinnNetwork,
     connection_bans: Arc<ConnectionBans>,
+    packet_compressor: Arc<DefaultNetworkPacketCompressor>,
 
     is_open: Arc<AtomicBool>,
 
+    /// wakes up the server from sleep, when a network event happens
+    network_notifier: NetworkEventNotifier,
     has_new_events_server: Arc<AtomicBool>,
     game_event_generator_server: Arc<GameEventGenerator>,
 
@@ -157,14 +218,48 @@ pub struct Server {
     last_register_serial: u32,
 
     last_network_stats_time: Duration,
+    /// since when no client is connected to the server
+    empty_since: Option<Duration>,
+    /// the idle map was loaded since the server became empty
+    idle_map_loaded: bool,
+    /// since when no client is connected, for the sleep mode
+    sleep_empty_since: Option<Duration>,
+    /// the game is paused, because the server was empty for too long
+    is_sleeping: bool,
+    /// when the database was pinged last, `None` pings it on the next tick
+    last_db_keepalive: Option<Duration>,
+
+    /// the scheduled events of the config
+    scheduler: Scheduler,
+    /// the idle map or a scheduled map change that is loaded
+    /// as soon as all database requests are finished
+    pending_map_load: Option<PendingMapLoad>,
 
     shared_info: Arc<ServerInfo>,
 
     // for server register
     cert_sha256_fingerprint: Hash,
+    /// the validated icon file, served to clients over the resource server
+    icon: Option<Vec<u8>>,
 
     // rcon
     rcon: Rcon,
+    /// The rcon commands the server handles itself,
+    /// all other commands are forwarded to the game.
+    rcon_cmds: RconCmds<Self>,
+    /// The chat commands the server handles itself, like for rcon.
+    chat_cmds: ChatCmds<Self>,
+    /// players that were granted priority in the spatial chat via rcon,
+    /// this is kept across map changes.
+    spatial_chat_priority_speakers: HashSet<PlayerUniqueId>,
+    whitelist: Whitelist,
+    sim_speed: SimSpeed,
+    reports: Reports,
+    auto_record: AutoRecord,
+    /// Signs the auto recorded demos, see [`Self::read_demo_signing_key`].
+    demo_signing_key: DemoSigningKey,
+    /// The config of the game mod of the current map.
+    mod_config: LayeredConfig,
 
     // votes
     map_votes: Vec<MapVote>,
@@ -174,6 +269,7 @@ pub struct Server {
     db: Option<Arc<Database>>,
     game_db: Arc<dyn DbInterface>,
     account_info: Option<AccountInfo>,
+    player_reports: Option<PlayerReports>,
     db_requests: Vec<IoBatcherTask<GameServerDb>>,
     db_requests_helper: Vec<IoBatcherTask<GameServerDb>>,
 
@@ -232,23 +328,60 @@ impl Server {
                 }
                 .unzip();
 
-                Ok((Some(db), game_db, accounts, account_info))
+                let player_reports = async {
+                    player_report::setup(game_db.clone()).await?;
+                    PlayerReports::new(game_db.clone()).await
+                }
+                .await;
+                if let Err(err) = &player_reports {
+                    log::error!(target: "server", "player reports are disabled: {err}");
+                }
+
+                Ok((
+                    Some(db),
+                    game_db,
+                    accounts,
+                    account_info,
+                    player_reports.ok(),
+                ))
             } else {
                 let game_db: Arc<dyn DbInterface> = Arc::new(DummyDb);
-                Ok((None, game_db, None, None))
+                Ok((None, game_db, None, None, None))
             }
         })
     }
 
-    fn read_mod_config(io: &Io, mod_name: &str) -> IoBatcherTask<Vec<u8>> {
-        let mod_name = mod_name.to_string();
+    /// Reads the server icon and makes sure clients can load it.
+    fn read_icon(io: &Io, path: &str) -> IoBatcherTask<Vec<u8>> {
+        let path = path.to_string();
         let fs = io.fs.clone();
         io.io_batcher.spawn(async move {
-            let config_mod = fs
-                .read_file(format!("config/{mod_name}.json").as_ref())
-                .await?;
+            let icon = fs.read_file(path.as_ref()).await?;
+
+            let mut img = Vec::new();
+            load_server_icon(&icon, |width, height| {
+                img = vec![0; width * height * 4];
+                &mut img
+            })?;
 
-            Ok(config_mod)
+            Ok(icon)
+        })
+    }
+
+    /// Reads the key the demos are signed with, or creates it on the first start.
+    /// It is independent from the certificate of the server, which is not persistent.
+    fn read_demo_signing_key(io: &Io) -> IoBatcherTask<DemoSigningKey> {
+        let fs = io.fs.clone();
+        io.io_batcher.spawn(async move {
+            let dir: &Path = "keys".as_ref();
+            let path = dir.join("demo_signing.key");
+            if fs.file_exists(&path).await {
+                return DemoSigningKey::from_file(&fs.read_file(&path).await?);
+            }
+            let key = DemoSigningKey::generate()?;
+            fs.create_dir(dir).await?;
+            fs.write_file(&path, key.to_file()).await?;
+            Ok(key)
         })
     }
 
@@ -293,7 +426,10 @@ impl Server {
 
         // load mod config
         let mod_name = Self::config_mod_name(&config_game);
-        let config_mod_task = Self::read_mod_config(&io, &mod_name);
+        let config_mod_task = LayeredConfig::read(&io, &mod_name, &config_game.sv.map);
+
+        let icon_task =
+            (!config_game.sv.icon.is_empty()).then(|| Self::read_icon(&io, &config_game.sv.icon));
 
         let http = io.http.clone();
         let path = io.fs.get_secure_path();
@@ -335,13 +471,23 @@ impl Server {
             )));
         }
 
-        if let Ok((client_send, server_send)) = zstd_dicts.get_storage() {
-            packet_plugins.push(Arc::new(DefaultNetworkPacketCompressor::new_with_dict(
+        let packet_compressor = if let Ok((client_send, server_send)) = zstd_dicts.get_storage() {
+            Arc::new(DefaultNetworkPacketCompressor::new_with_dict(
                 server_send,
                 client_send,
-            )));
+            ))
         } else {
-            packet_plugins.push(Arc::new(DefaultNetworkPacketCompressor::new()));
+            Arc::new(DefaultNetworkPacketCompressor::new())
+        };
+        packet_plugins.push(packet_compressor.clone());
+
+        let demo_signing_key = Self::read_demo_signing_key(&io).get_storage()?;
+        if config_game.sv.auto_record.sign {
+            log::info!(
+                target: "auto-record",
+                "demos are signed with the public key {}",
+                fmt_hash(&demo_signing_key.public_key()?)
+            );
         }
 
         let cert_sha256_fingerprint = cert_and_private_key
@@ -350,7 +496,7 @@ impl Server {
             .subject_public_key_info
             .fingerprint_bytes()?;
 
-        let (network_server, _cert, sock_addr, _notifer_server) = Network::init_server(
+        let (network_server, _cert, sock_addr, network_notifier) = Network::init_server(
             &format!("0.0.0.0:{port}"),
             game_event_generator_server.clone(),
             NetworkServerCertMode::FromCertAndPrivateKey(Box::new(NetworkServerCertAndKey {
@@ -382,7 +528,7 @@ impl Server {
         );
         *shared_info.sock_addr.lock().unwrap() = Some(sock_addr);
 
-        let (db, game_db, accounts, account_info) = task.get_storage()?;
+        let (db, game_db, accounts, account_info, player_reports) = task.get_storage()?;
         let account_certs_downloader = account_certs_downloader.get_storage().ok();
 
         let account_server_cert_downloader_task = if let Some(account_certs_downloader) =
@@ -418,13 +564,19 @@ impl Server {
         map_votes.sort_by(|v1, v2| v1.name.cmp(&v2.name));
         let map_votes_hash = generate_hash_for(&serde_json::to_vec(&map_votes).unwrap());
 
-        let config_mod = config_mod_task.get_storage().ok();
+        let mod_config = config_mod_task.get_storage().unwrap_or_default();
+
+        let icon = icon_task.and_then(|task| {
+            task.get_storage()
+                .map_err(|err| log::warn!(target: "server", "the server icon could not be loaded: {err}"))
+                .ok()
+        });
 
         let rcon = Rcon::new(&io);
         // share secret with client (if exists)
         *shared_info.rcon_secret.lock().unwrap() = Some(rcon.rcon_secret);
 
-        Ok(Self {
+        let server = Self {
             clients: Clients::new(
                 config_game.sv.max_players as usize,
                 config_game.sv.max_players_per_ip as usize,
@@ -434,7 +586,9 @@ impl Server {
             max_players: config_game.sv.max_players as usize,
 
             network: network_server,
+            network_notifier,
             connection_bans,
+            packet_compressor,
 
             is_open,
 
@@ -444,11 +598,14 @@ impl Server {
             game_server: ServerGame::new(
                 &config_game.sv.map,
                 &mod_name,
-                config_mod,
+                mod_config.to_bytes(),
+                !mod_config.has_overrides(),
                 &thread_pool,
                 &io,
                 &game_db,
                 config_game.sv.spatial_chat,
+                icon.clone(),
+                config_game.sv.max_players as usize,
             )?,
 
             last_tick_time: sys.time_get_nanoseconds(),
@@ -457,6 +614,13 @@ impl Server {
             last_register_serial: 0,
 
             last_network_stats_time: sys.time_get_nanoseconds(),
+            empty_since: None,
+            idle_map_loaded: false,
+            sleep_empty_since: None,
+            is_sleeping: false,
+            last_db_keepalive: None,
+            scheduler: Scheduler::new(&config_game.sv.scheduled_events),
+            pending_map_load: None,
 
             sys,
 
@@ -464,9 +628,19 @@ impl Server {
 
             // for server register
             cert_sha256_fingerprint,
+            icon,
 
             // rcon
             rcon,
+            rcon_cmds: Self::default_rcon_cmds(),
+            chat_cmds: Self::default_chat_cmds(),
+            spatial_chat_priority_speakers: Default::default(),
+            whitelist: Whitelist::new(&config_game.sv.whitelist),
+            sim_speed: Default::default(),
+            reports: Default::default(),
+            auto_record: Default::default(),
+            demo_signing_key,
+            mod_config,
 
             // votes
             map_votes,
@@ -478,6 +652,7 @@ impl Server {
             db_requests: Default::default(),
             db_requests_helper: Default::default(),
             account_info,
+            player_reports,
 
             accounts,
             account_server_certs_downloader: account_certs_downloader,
@@ -497,7 +672,52 @@ impl Server {
 
             config_game,
             server_port: sock_addr.port(),
-        })
+        };
+        server.startup_checks(port)?;
+
+        Ok(server)
+    }
+
+    /// Logs a [`DiagnosticsReport`] of the server and fails,
+    /// if a critical check failed and the checks are strict.
+    fn startup_checks(&self, port: u16) -> anyhow::Result<()> {
+        let config = &self.config_game.sv.startup_checks;
+        if !config.enabled || self.shared_info.is_internal_server {
+            return Ok(());
+        }
+
+        let resource_port = self
+            .game_server
+            .http_server
+            .as_ref()
+            .map(|server| server.port);
+        let mut report = DiagnosticsReport::default();
+        report.check_map_physics(&self.game_server.map.map_file);
+        report.check_database(
+            &self.io,
+            self.db.as_ref(),
+            Duration::from_millis(config.max_db_latency_ms),
+        );
+        report.check_ports(port, self.server_port, resource_port);
+        report.check_resource_server(
+            &self.io,
+            resource_port,
+            &self.game_server.map.name,
+            &self.game_server.map_blake3_hash,
+        );
+        report.check_tick_timing(self.game_server.game.game_tick_speed());
+        report.log();
+
+        if config.strict {
+            if let Some(check) = report.critical().next() {
+                return Err(anyhow!(
+                    "the startup check \"{}\" failed: {}",
+                    check.name,
+                    check.msg
+                ));
+            }
+        }
+        Ok(())
     }
 
     fn can_another_player_connect(&self) -> bool {
@@ -512,6 +732,22 @@ impl Server {
         cert: Arc<x509_cert::Certificate>,
         network_stats: PlayerNetworkStats,
     ) {
+        let auth = ClientAuth {
+            cert,
+            level: Default::default(),
+        };
+        let unique_id = self.player_unique_id(&auth);
+        if !self.whitelist.is_allowed(&unique_id) {
+            log::info!(target: "server", "rejected a client that is not whitelisted");
+            self.network.kick(
+                con_id,
+                NetworkDisconnectReason::Kicked(self.whitelist.msg.clone()),
+            );
+            return;
+        }
+        self.drop_stale_client(con_id, &unique_id);
+        let cert = auth.cert;
+
         // check if the client can be part of the game
         if self.can_another_player_connect() {
             self.clients.network_clients.insert(
@@ -568,6 +804,32 @@ impl Server {
         }
     }
 
+    /// A client that switched its network (e.g. from Wi-Fi to ethernet)
+    /// and could not migrate its connection connects again,
+    /// while its old connection did not time out yet.
+    /// The old connection is dropped like a timed out one,
+    /// so the new connection can reclaim its characters.
+    fn drop_stale_client(&mut self, con_id: &NetworkConnectionId, unique_id: &PlayerUniqueId) {
+        let Some(stale_con_id) = self
+            .clients
+            .clients
+            .iter()
+            .find(|(id, client)| *id != con_id && self.player_unique_id(&client.auth) == *unique_id)
+            .map(|(id, _)| *id)
+        else {
+            return;
+        };
+        log::info!(target: "server", "dropping the stale connection of a reconnecting client");
+        self.network
+            .kick(&stale_con_id, NetworkDisconnectReason::Timeout);
+        if let Some(players) = self.client_disconnect(&stale_con_id, "timeout") {
+            for player_id in players.keys() {
+                self.game_server
+                    .player_drop(player_id, PlayerDropReason::Timeout);
+            }
+        }
+    }
+
     fn drop_client_from_queue(
         &mut self,
         con_id: &NetworkConnectionId,
@@ -591,11 +853,38 @@ impl Server {
         self.clients.network_queued_clients.remove(con_id)
     }
 
+    /// Removes purely visual events of other characters.
+    fn reduce_events(events: &mut game_interface::events::GameEvents, client: &ServerClient) {
+        for world in events.worlds.values_mut() {
+            world.events.retain(|_, ev| match ev {
+                GameWorldEvent::Positioned(ev) => {
+                    let is_effect = matches!(
+                        ev.ev,
+                        GameWorldEntityEvent::Character {
+                            ev: GameCharacterEvent::Effect(_)
+                        } | GameWorldEntityEvent::Grenade {
+                            ev: GameGrenadeEvent::Effect(_)
+                        }
+                    );
+                    !is_effect
+                        || ev
+                            .owner_id
+                            .is_some_and(|owner_id| client.players.contains_key(&owner_id))
+                }
+                GameWorldEvent::Global(_) => true,
+            });
+        }
+        events.worlds.retain(|_, world| !world.events.is_empty());
+    }
+
     pub fn client_disconnect(
         &mut self,
         con_id: &NetworkConnectionId,
         _reason: &str,
     ) -> Option<PoolLinkedHashMap<GameEntityId, ServerClientPlayer>> {
+        self.packet_compressor.set_high_compression(con_id, false);
+        self.clients.network_stats_trackers.remove(con_id);
+
         // find client in queued clients
         if self.clients.network_queued_clients.contains_key(con_id) {
             self.drop_client_from_queue(con_id);
@@ -632,17 +921,8 @@ impl Server {
         if let Some(p) = found {
             // update vote if nessecary
             if let Some(vote) = &mut self.game_server.cur_vote {
-                if let Some(voted) = vote.participating_clients.remove(con_id) {
-                    match voted {
-                        Voted::Yes => vote.state.yes_votes -= 1,
-                        Voted::No => vote.state.no_votes -= 1,
-                    }
-                }
-                vote.state.allowed_to_vote_count -= 1;
-
-                let vote_state = vote.state.clone();
-                let started_at = vote.started_at;
-                self.send_vote(Some(vote_state), started_at);
+                vote.participating_clients.remove(con_id);
+                self.update_vote();
             }
             // update spatial world
             if let Some(spatial_world) = &mut self.game_server.spatial_world {
@@ -681,11 +961,11 @@ impl Server {
         });
     }
 
-    fn send_vote(&self, vote_state: Option<VoteState>, start_time: Duration) {
+    fn send_vote(&self, vote_state: Option<VoteState>, start_time: Duration, duration: Duration) {
         self.broadcast_in_order(
             GameMessage::ServerToClient(ServerToClientMessage::Vote(vote_state.map(
                 |mut vote_state| {
-                    vote_state.remaining_time = Duration::from_secs(25)
+                    vote_state.remaining_time = duration
                         .saturating_sub(self.sys.time_get_nanoseconds().saturating_sub(start_time));
                     vote_state
                 },
@@ -694,6 +974,290 @@ impl Server {
         )
     }
 
+    /// Sends a chat message only to the given player.
+    ///
+    /// There is no system channel, so it appears as whisper of the player itself.
+    fn send_chat_msg_to(
+        &self,
+        con_id: &NetworkConnectionId,
+        player_id: &GameEntityId,
+        msg: String,
+    ) {
+        self.network.send_in_order_to(
+            &GameMessage::ServerToClient(ServerToClientMessage::Chat(MsgSvChatMsg {
+                msg: NetChatMsg {
+                    player_id: *player_id,
+                    msg,
+                    channel: NetChatMsgPlayerChannel::Whisper(*player_id),
+                },
+            })),
+            con_id,
+            NetworkInOrderChannel::Custom(3841), // This number reads as "chat".
+        );
+    }
+
+    /// Sends the chat message of the player to all clients that have
+    /// a player in the scope of the channel, see [`NetChatMsgPlayerChannel::reaches`].
+    ///
+    /// The sender & the receiver of a whisper always get the message.
+    fn send_player_chat_msg(
+        &self,
+        player_id: &GameEntityId,
+        msg: String,
+        channel: NetChatMsgPlayerChannel,
+        whisper_receiver: Option<GameEntityId>,
+    ) {
+        let scoreboard = self.game_server.game.collect_scoreboard_info();
+        let sender = scoreboard.placement_of(player_id);
+        let packet = GameMessage::ServerToClient(ServerToClientMessage::Chat(MsgSvChatMsg {
+            msg: NetChatMsg {
+                player_id: *player_id,
+                msg,
+                channel: channel.clone(),
+            },
+        }));
+        for (con_id, client) in self.clients.clients.iter() {
+            let reaches = client.players.keys().any(|id| {
+                id == player_id
+                    || whisper_receiver == Some(*id)
+                    || channel.reaches(sender.as_ref(), scoreboard.placement_of(id).as_ref())
+            });
+            if reaches {
+                self.network.send_in_order_to(
+                    &packet,
+                    con_id,
+                    NetworkInOrderChannel::Custom(3841), // This number reads as "chat".
+                );
+            }
+        }
+    }
+
+    fn player_name(&self, player_id: &GameEntityId) -> String {
+        self.game_server
+            .game
+            .collect_characters_info()
+            .get(player_id)
+            .map(|c| c.info.name.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Stores a report of the chat command in the database,
+    /// together with the recent chat & the positions of all characters.
+    ///
+    /// The player is told once the database answered.
+    fn report_player(
+        &mut self,
+        player_id: &GameEntityId,
+        report: ReportRequest,
+    ) -> anyhow::Result<String> {
+        let player_reports = self
+            .player_reports
+            .clone()
+            .ok_or_else(|| anyhow!("reports are not enabled on this server"))?;
+        let con_id = self
+            .game_server
+            .players
+            .get(player_id)
+            .map(|player| player.network_id)
+            .ok_or_else(|| anyhow!("player is not connected"))?;
+        let characters = self.game_server.game.collect_characters_info();
+        let name = |id: &GameEntityId| {
+            characters
+                .get(id)
+                .map(|c| c.info.name.to_string())
+                .unwrap_or_default()
+        };
+        anyhow::ensure!(
+            characters
+                .values()
+                .any(|c| c.info.name.as_str() == report.player),
+            "player \"{}\" was not found",
+            report.player
+        );
+        if let Some(ip) = self.clients.clients.get(&con_id).map(|c| c.ip) {
+            self.reports
+                .try_report(ip, self.sys.time_get_nanoseconds())
+                .map_err(|remaining| {
+                    anyhow!(
+                        "you can report again in {} seconds",
+                        remaining.as_secs() + 1
+                    )
+                })?;
+        }
+
+        let context = ReportContext {
+            tick: self.game_server.cur_monotonic_tick,
+            positions: self
+                .game_server
+                .game
+                .all_stages(0.0)
+                .values()
+                .flat_map(|stage| stage.world.characters.iter())
+                .map(|(id, c)| ReportCharacterPos {
+                    player: name(id),
+                    x: c.lerped_pos.x,
+                    y: c.lerped_pos.y,
+                })
+                .collect(),
+            recent_chat: self.reports.recent_chat(),
+        };
+        let context = serde_json::to_string(&context)?;
+        let map = self.game_server.map.name.as_str().to_string();
+        let reporter = name(player_id);
+
+        let player_id = *player_id;
+        self.db_requests.push(self.io.io_batcher.spawn(async move {
+            let msg = match player_reports
+                .insert(map, reporter, report.player, report.reason, context)
+                .await
+            {
+                Ok(()) => "thanks, your report was sent to the moderators".to_string(),
+                Err(err) => {
+                    log::error!(target: "server", "failed to store report: {err}");
+                    "your report could not be stored".to_string()
+                }
+            };
+            Ok(GameServerDb::Reports {
+                con_id,
+                player_id,
+                msgs: vec![msg],
+            })
+        }));
+        Ok(String::new())
+    }
+
+    /// Handles the rcon commands of the moderation queue.
+    /// Lists the newest reports or shows the report with the `view_id`.
+    ///
+    /// The reports are sent to the player once the database answered.
+    fn moderate_reports(
+        &mut self,
+        player_id: &GameEntityId,
+        view_id: Option<i64>,
+    ) -> anyhow::Result<String> {
+        let player_reports = self
+            .player_reports
+            .clone()
+            .ok_or_else(|| anyhow!("reports are not enabled on this server"))?;
+        let con_id = self
+            .game_server
+            .players
+            .get(player_id)
+            .map(|player| player.network_id)
+            .ok_or_else(|| anyhow!("player is not connected"))?;
+
+        let player_id = *player_id;
+        self.db_requests.push(self.io.io_batcher.spawn(async move {
+            let msgs = if let Some(id) = view_id {
+                match player_reports.view(id).await {
+                    Ok(Some(report)) => Reports::format_details(&report),
+                    Ok(None) => vec![format!("report #{id} does not exist")],
+                    Err(err) => vec![err.to_string()],
+                }
+            } else {
+                match player_reports.list(REPORTS_LIST_MAX).await {
+                    Ok(reports) if reports.is_empty() => vec!["no reports".to_string()],
+                    Ok(reports) => reports.iter().map(Reports::format_summary).collect(),
+                    Err(err) => vec![err.to_string()],
+                }
+            };
+            Ok(GameServerDb::Reports {
+                con_id,
+                player_id,
+                msgs,
+            })
+        }));
+        Ok(String::new())
+    }
+
+    fn spectator_player_ids(&self) -> HashSet<GameEntityId> {
+        match self.game_server.game.collect_scoreboard_info().game {
+            ScoreboardGameType::SidedPlay {
+                spectator_players, ..
+            }
+            | ScoreboardGameType::SoloPlay {
+                spectator_players, ..
+            } => spectator_players.iter().map(|p| p.id).collect(),
+        }
+    }
+
+    /// How much the vote of a client counts.
+    /// Zero if the client is not allowed to vote.
+    fn vote_weight(
+        &self,
+        con_id: &NetworkConnectionId,
+        spectators: &HashSet<GameEntityId>,
+        cur_time: Duration,
+    ) -> usize {
+        let config = &self.config_game.sv.vote;
+        let Some(client) = self.clients.clients.get(con_id) else {
+            return 0;
+        };
+        if cur_time.saturating_sub(client.connect_timestamp)
+            < Duration::from_secs(config.min_ingame_secs)
+        {
+            return 0;
+        }
+        let players = client
+            .players
+            .keys()
+            .filter(|id| config.spectators_can_vote || !spectators.contains(id))
+            .count();
+        if config.weight_per_player {
+            players
+        } else {
+            players.min(1)
+        }
+    }
+
+    /// Recalculates the eligible voters & the votes of the current vote,
+    /// since they depend on the vote rules of the server,
+    /// and sends the new state to all clients.
+    fn update_vote(&mut self) {
+        if self.game_server.cur_vote.is_none() {
+            return;
+        }
+        let spectators = self.spectator_player_ids();
+        let cur_time = self.sys.time_get_nanoseconds();
+        let weights: HashMap<NetworkConnectionId, usize> = self
+            .clients
+            .clients
+            .keys()
+            .map(|con_id| (*con_id, self.vote_weight(con_id, &spectators, cur_time)))
+            .collect();
+
+        if let Some(vote) = &mut self.game_server.cur_vote {
+            vote.state.allowed_to_vote_count = weights.values().sum();
+            vote.state.yes_votes = 0;
+            vote.state.no_votes = 0;
+            for (con_id, voted) in vote.participating_clients.iter() {
+                let weight = weights.get(con_id).copied().unwrap_or_default();
+                match voted {
+                    Voted::Yes => vote.state.yes_votes += weight,
+                    Voted::No => vote.state.no_votes += weight,
+                }
+            }
+
+            let vote_state = vote.state.clone();
+            let started_at = vote.started_at;
+            self.send_vote(Some(vote_state), started_at, SERVER_VOTE_DURATION);
+        }
+    }
+
+    /// Only one vote can run at a time, either a vote of the server
+    /// or one of the game, see [`Self::handle_game_events`].
+    fn is_vote_running(&self) -> bool {
+        self.game_server.cur_vote.is_some() || self.game_server.cur_game_vote.is_some()
+    }
+
+    fn can_vote(&self, con_id: &NetworkConnectionId) -> bool {
+        self.vote_weight(
+            con_id,
+            &self.spectator_player_ids(),
+            self.sys.time_get_nanoseconds(),
+        ) > 0
+    }
+
     fn add_player_for_client(
         &mut self,
         con_id: &NetworkConnectionId,
@@ -770,42 +1334,50 @@ impl Server {
                                 .player_drop(player_id, PlayerDropReason::Disconnect);
                         }
                     }
-                    ClientToServerPlayerMessage::Chat(msg) => match msg {
-                        MsgClChatMsg::Global { msg } => {
-                            if !msg.is_empty() {
-                                if self
-                                    .game_server
-                                    .game
-                                    .info
-                                    .chat_commands
-                                    .prefixes
-                                    .contains(&msg.chars().next().unwrap())
-                                {
-                                    self.game_server.game.client_command(
-                                        player_id,
-                                        ClientCommand::Chat(ClientChatCommand {
-                                            raw: msg.chars().skip(1).collect(),
-                                        }),
-                                    );
-                                } else {
-                                    self.broadcast_in_order(
-                                        GameMessage::ServerToClient(ServerToClientMessage::Chat(
-                                            MsgSvChatMsg {
-                                                msg: NetChatMsg {
-                                                    player_id: *player_id,
-                                                    msg: msg.as_str().to_string(),
-                                                    channel: NetChatMsgPlayerChannel::Global,
-                                                },
-                                            },
-                                        )),
-                                        NetworkInOrderChannel::Custom(3841), // This number reads as "chat".
-                                    );
+                    ClientToServerPlayerMessage::Chat(msg) => {
+                        let (msg, channel, whisper_receiver) = match msg {
+                            MsgClChatMsg::Global { msg } => {
+                                (msg, NetChatMsgPlayerChannel::Global, None)
+                            }
+                            MsgClChatMsg::GameTeam { msg } => {
+                                (msg, NetChatMsgPlayerChannel::GameTeam, None)
+                            }
+                            MsgClChatMsg::Stage { msg } => {
+                                (msg, NetChatMsgPlayerChannel::Stage, None)
+                            }
+                            MsgClChatMsg::Whisper { receiver_id, msg } => (
+                                msg,
+                                NetChatMsgPlayerChannel::Whisper(*player_id),
+                                Some(receiver_id),
+                            ),
+                        };
+                        if !msg.is_empty() {
+                            if self
+                                .game_server
+                                .game
+                                .info
+                                .chat_commands
+                                .prefixes
+                                .contains(&msg.chars().next().unwrap())
+                            {
+                                let raw: String = msg.chars().skip(1).collect();
+                                self.exec_chat_cmd(con_id, player_id, raw);
+                            } else {
+                                // team chat & whispers are private,
+                                // only the public chat goes into reports
+                                if matches!(channel, NetChatMsgPlayerChannel::Global) {
+                                    let name = self.player_name(player_id);
+                                    self.reports.add_chat_msg(&name, msg.as_str());
                                 }
+                                self.send_player_chat_msg(
+                                    player_id,
+                                    msg.as_str().to_string(),
+                                    channel,
+                                    whisper_receiver,
+                                );
                             }
                         }
-                        MsgClChatMsg::GameTeam { .. } => todo!(),
-                        MsgClChatMsg::Whisper { .. } => todo!(),
-                    },
+                    }
                     ClientToServerPlayerMessage::Kill => {
                         self.game_server
                             .game
@@ -820,38 +1392,40 @@ impl Server {
                         match vote {
                             VoteType::Map(_) => {
                                 // if no current vote exist, try map vote
-                                if self.game_server.cur_vote.is_none() {
+                                if !self.is_vote_running() && self.can_vote(con_id) {
                                     self.game_server.cur_vote = Some(ServerVote {
                                         state: VoteState {
                                             vote,
                                             // filled on the fly instead
                                             remaining_time: Duration::ZERO,
-                                            // vote starter get a yes vote
-                                            yes_votes: 1,
+                                            // calculated by the vote rules instead
+                                            yes_votes: 0,
                                             no_votes: 0,
-                                            allowed_to_vote_count: self.clients.clients.len(),
+                                            allowed_to_vote_count: 0,
                                         },
                                         extra_vote_info: ServerExtraVoteInfo::None,
                                         started_at: self.sys.time_get_nanoseconds(),
+                                        // vote starter get a yes vote
                                         participating_clients: [(*con_id, Voted::Yes)]
                                             .into_iter()
                                             .collect(),
                                     });
-                                    let vote_state = self.game_server.cur_vote.as_ref().map(|v| {
-                                        let mut state = v.state.clone();
-                                        state.remaining_time = Duration::from_secs(25);
-                                        state
-                                    });
-                                    self.broadcast_in_order(
-                                        GameMessage::ServerToClient(ServerToClientMessage::Vote(
-                                            vote_state,
-                                        )),
-                                        NetworkInOrderChannel::Custom(7013), // This number reads as "vote".
-                                    )
+                                    self.update_vote();
                                 }
                             }
-                            VoteType::VoteSpecPlayer { voted_player_id }
-                            | VoteType::VoteKickPlayer { voted_player_id } => {
+                            VoteType::VoteSpecPlayer { voted_player_id } => {
+                                // the game moves players to the spectators,
+                                // so it also runs the vote
+                                if !self.is_vote_running() && self.can_vote(con_id) {
+                                    self.game_server.game.client_command(
+                                        player_id,
+                                        ClientCommand::StartVote(GameVoteAction::SpecPlayer {
+                                            player_id: voted_player_id,
+                                        }),
+                                    );
+                                }
+                            }
+                            VoteType::VoteKickPlayer { voted_player_id } => {
                                 if *player_id != voted_player_id {
                                     if let Some((kick_con_id, player)) =
                                         self.game_server.players.get(&voted_player_id).and_then(
@@ -864,19 +1438,16 @@ impl Server {
                                         )
                                     {
                                         // if the player exists and no current vote exists, start the vote
-                                        if self.game_server.cur_vote.is_none() {
+                                        if !self.is_vote_running() && self.can_vote(con_id) {
                                             self.game_server.cur_vote = Some(ServerVote {
                                                 state: VoteState {
                                                     vote,
                                                     // filled on the fly instead
                                                     remaining_time: Duration::ZERO,
-                                                    // vote starter get a yes vote
-                                                    yes_votes: 1,
+                                                    // calculated by the vote rules instead
+                                                    yes_votes: 0,
                                                     no_votes: 0,
-                                                    allowed_to_vote_count: self
-                                                        .clients
-                                                        .clients
-                                                        .len(),
+                                                    allowed_to_vote_count: 0,
                                                 },
                                                 extra_vote_info: ServerExtraVoteInfo::Player {
                                                     to_kick_player: kick_con_id,
@@ -884,45 +1455,31 @@ impl Server {
                                                     account_info: player.auth.clone(),
                                                 },
                                                 started_at: self.sys.time_get_nanoseconds(),
+                                                // vote starter get a yes vote
                                                 participating_clients: [(*con_id, Voted::Yes)]
                                                     .into_iter()
                                                     .collect(),
                                             });
-                                            let vote_state =
-                                                self.game_server.cur_vote.as_ref().map(|v| {
-                                                    let mut state = v.state.clone();
-                                                    state.remaining_time = Duration::from_secs(25);
-                                                    state
-                                                });
-                                            self.broadcast_in_order(
-                                                GameMessage::ServerToClient(
-                                                    ServerToClientMessage::Vote(vote_state),
-                                                ),
-                                                NetworkInOrderChannel::Custom(7013), // This number reads as "vote".
-                                            )
+                                            self.update_vote();
                                         }
                                     }
                                 }
                             }
-                            VoteType::Misc() => todo!(),
+                            VoteType::Misc() => {
+                                // not supported by the server
+                            }
                         }
                     }
                     ClientToServerPlayerMessage::Voted(voted) => {
                         if let Some(vote) = &mut self.game_server.cur_vote {
-                            let prev_vote = vote.participating_clients.insert(*con_id, voted);
-                            match voted {
-                                game_interface::votes::Voted::Yes => vote.state.yes_votes += 1,
-                                game_interface::votes::Voted::No => vote.state.no_votes += 1,
-                            }
-                            if let Some(prev_vote) = prev_vote {
-                                match prev_vote {
-                                    Voted::Yes => vote.state.yes_votes -= 1,
-                                    Voted::No => vote.state.no_votes -= 1,
-                                }
-                            }
-                            let vote_state = vote.state.clone();
-                            let started_at = vote.started_at;
-                            self.send_vote(Some(vote_state), started_at);
+                            // votes of clients that are not allowed to vote
+                            // simply have no weight.
+                            vote.participating_clients.insert(*con_id, voted);
+                            self.update_vote();
+                        } else if self.game_server.cur_game_vote.is_some() {
+                            self.game_server
+                                .game
+                                .client_command(player_id, ClientCommand::Vote(voted));
                         }
                     }
                     ClientToServerPlayerMessage::Emoticon(emoticon) => {
@@ -946,6 +1503,11 @@ impl Server {
                             .game
                             .client_command(player_id, ClientCommand::SwitchToFreeCam(mode));
                     }
+                    ClientToServerPlayerMessage::SpectateTarget(target) => {
+                        self.game_server
+                            .game
+                            .client_command(player_id, ClientCommand::SpectateTarget(target));
+                    }
                     ClientToServerPlayerMessage::UpdateCharacterInfo { info, version } => {
                         self.game_server
                             .game
@@ -970,6 +1532,125 @@ impl Server {
             .unwrap_or_else(|| PlayerUniqueId::CertFingerprint(user_id.public_key))
     }
 
+    /// Parses a player of a rcon command or the config,
+    /// either `acc_<account id>` or `cert_<cert fingerprint>`.
+    pub(crate) fn parse_player_unique_id(id: &str) -> Option<PlayerUniqueId> {
+        if let Some(account_id) = id.strip_prefix("acc_") {
+            account_id.parse().ok().map(PlayerUniqueId::Account)
+        } else if let Some(hash) = id.strip_prefix("cert_") {
+            decode_hash(hash).map(PlayerUniqueId::CertFingerprint)
+        } else {
+            None
+        }
+    }
+
+    fn player_unique_id(&self, auth: &ClientAuth) -> PlayerUniqueId {
+        Self::user_id_to_player_unique_id(&Self::user_id(
+            &self
+                .account_server_certs_downloader
+                .as_ref()
+                .map(|c| c.public_keys())
+                .unwrap_or_default(),
+            auth,
+        ))
+    }
+
+    /// Kicks all clients (including queued ones) that are not whitelisted.
+    fn kick_non_whitelisted_clients(&self) -> String {
+        let clients = self
+            .clients
+            .clients
+            .iter()
+            .map(|(con_id, client)| (con_id, &client.auth))
+            .chain(
+                self.clients
+                    .network_clients
+                    .iter()
+                    .map(|(con_id, client)| (con_id, &client.auth)),
+            )
+            .chain(
+                self.clients
+                    .network_queued_clients
+                    .iter()
+                    .map(|(con_id, client)| (con_id, &client.auth)),
+            );
+        let mut kicked = 0;
+        for (con_id, auth) in clients {
+            if !self.whitelist.is_allowed(&self.player_unique_id(auth)) {
+                self.network.kick(
+                    con_id,
+                    NetworkDisconnectReason::Kicked(self.whitelist.msg.clone()),
+                );
+                kicked += 1;
+            }
+        }
+        let msg = format!("kicked {kicked} clients that are not whitelisted");
+        log::info!(target: "server", "{msg}");
+        msg
+    }
+
+    /// Changes the max player count without a restart.
+    ///
+    /// Connected players are never dropped, unless
+    /// [`game_config::config::ConfigServer::max_players_kick_spectators`] is enabled,
+    /// in which case spectators are kicked (newest first) until the players fit.
+    /// If the count grows, queued clients can join right away.
+    fn set_max_players(&mut self, max_players: usize) -> anyhow::Result<String> {
+        let old_max_players = std::mem::replace(&mut self.max_players, max_players);
+        if max_players > old_max_players {
+            let additional = max_players - old_max_players;
+            self.clients.reserve_players(additional);
+            self.player_ids_pool.reserve(additional);
+            self.player_network_stats_pool.reserve(additional);
+        }
+
+        let player_count = self.player_count_of_all_clients + self.clients.network_clients.len();
+        let mut kicked = 0;
+        if player_count > max_players && self.config_game.sv.max_players_kick_spectators {
+            let spectators = self.spectator_player_ids();
+            let mut overflow = player_count - max_players;
+            for (spec_con_id, client) in self.clients.clients.iter().rev() {
+                if overflow == 0 {
+                    break;
+                }
+                if !client.players.is_empty()
+                    && client.players.keys().all(|id| spectators.contains(id))
+                {
+                    self.network.kick(
+                        spec_con_id,
+                        NetworkDisconnectReason::Kicked(
+                            "the server needed the slot for players".to_string(),
+                        ),
+                    );
+                    overflow = overflow.saturating_sub(client.players.len());
+                    kicked += 1;
+                }
+            }
+        }
+        while !self.clients.network_queued_clients.is_empty() && self.can_another_player_connect() {
+            let (&con_id_queue, queued_client) =
+                self.clients.network_queued_clients.front().unwrap();
+            let timestamp_queue = queued_client.connect_timestamp;
+            let queued_client = self.drop_client_from_queue(&con_id_queue).unwrap();
+            self.try_client_connect(
+                &con_id_queue,
+                &timestamp_queue,
+                queued_client.ip,
+                queued_client.auth.cert,
+                queued_client.network_stats,
+            );
+        }
+        // announce the new slot count to the master server
+        self.last_register_time = None;
+
+        log::info!(
+            target: "server",
+            "max players changed from {old_max_players} to {max_players}, \
+            kicked {kicked} spectator clients"
+        );
+        Ok(format!("max players changed to {max_players}"))
+    }
+
     fn client_snap_ack(client: &mut ServerClient, snap_id: u64) {
         if let Some(snap) = client.client_snap_storage.remove(&snap_id) {
             client.latest_client_snap = Some(ClientSnapshotForDiff {
@@ -988,10 +1669,22 @@ impl Server {
     }
 
     fn send_rcon_commands(&self, con_id: &NetworkConnectionId) {
+        let mut rcon_commands = self.game_server.game.info.rcon_commands.clone();
+        // commands handled by the server itself
+        rcon_commands
+            .cmds
+            .extend(
+                self.rcon_cmds
+                    .rcon_commands()
+                    .cmds
+                    .into_iter()
+                    .filter(|(name, _)| {
+                        name != PRIORITY_SPEAKER_RCON_CMD
+                            || self.game_server.spatial_world.is_some()
+                    }),
+            );
         self.network.send_in_order_to(
-            &GameMessage::ServerToClient(ServerToClientMessage::RconCommands(
-                self.game_server.game.info.rcon_commands.clone(),
-            )),
+            &GameMessage::ServerToClient(ServerToClientMessage::RconCommands(rcon_commands)),
             con_id,
             NetworkInOrderChannel::Custom(
                 7302, // reads as "rcon"
@@ -999,6 +1692,263 @@ impl Server {
         );
     }
 
+    fn default_rcon_cmds() -> RconCmds<Self> {
+        let mut cmds = RconCmds::default();
+        let text_arg = || CommandArg {
+            expected_ty: CommandArgType::Text,
+        };
+        cmds.register(RconCmd {
+            name: WHITELIST_RCON_CMD.to_string(),
+            usage: format!("{WHITELIST_RCON_CMD} <on|off>"),
+            description: "Enables or disables the whitelist.".to_string(),
+            auth_level: AuthLevel::Admin,
+            cmd: Rc::new(|server, _, _, args| server.whitelist.set_enabled(arg_text(args, 0)?)),
+            args: vec![CommandArg {
+                expected_ty: CommandArgType::TextFrom(vec!["on".to_string(), "off".to_string()]),
+            }],
+        });
+        cmds.register(RconCmd {
+            name: WHITELIST_ADD_RCON_CMD.to_string(),
+            usage: format!("{WHITELIST_ADD_RCON_CMD} <acc_<id>|cert_<fingerprint>>"),
+            description: "Adds the player to the whitelist.".to_string(),
+            auth_level: AuthLevel::Admin,
+            cmd: Rc::new(|server, _, _, args| {
+                server.whitelist.change_player(arg_text(args, 0)?, true)
+            }),
+            args: vec![text_arg()],
+        });
+        cmds.register(RconCmd {
+            name: WHITELIST_REMOVE_RCON_CMD.to_string(),
+            usage: format!("{WHITELIST_REMOVE_RCON_CMD} <acc_<id>|cert_<fingerprint>>"),
+            description: "Removes the player from the whitelist.".to_string(),
+            auth_level: AuthLevel::Admin,
+            cmd: Rc::new(|server, _, _, args| {
+                server.whitelist.change_player(arg_text(args, 0)?, false)
+            }),
+            args: vec![text_arg()],
+        });
+        cmds.register(RconCmd {
+            name: WHITELIST_KICK_RCON_CMD.to_string(),
+            usage: WHITELIST_KICK_RCON_CMD.to_string(),
+            description: "Kicks all clients that are not whitelisted.".to_string(),
+            auth_level: AuthLevel::Moderator,
+            cmd: Rc::new(|server, _, _, _| Ok(server.kick_non_whitelisted_clients())),
+            args: vec![],
+        });
+        cmds.register(RconCmd {
+            name: SIM_SPEED_RCON_CMD.to_string(),
+            usage: format!("{SIM_SPEED_RCON_CMD} <speed>"),
+            description: "Changes the speed of the simulation, e.g. 0.5 or 2x.".to_string(),
+            auth_level: AuthLevel::Admin,
+            cmd: Rc::new(|server, _, _, args| server.sim_speed.set_speed(arg_text(args, 0)?)),
+            args: vec![text_arg()],
+        });
+        cmds.register(RconCmd {
+            name: MAX_PLAYERS_RCON_CMD.to_string(),
+            usage: format!("{MAX_PLAYERS_RCON_CMD} <count>"),
+            description: "Changes the max player count without a restart.".to_string(),
+            auth_level: AuthLevel::Admin,
+            cmd: Rc::new(|server, _, _, args| {
+                server.set_max_players(parse_max_players(arg_text(args, 0)?)?)
+            }),
+            args: vec![CommandArg {
+                expected_ty: CommandArgType::Number,
+            }],
+        });
+        cmds.register(RconCmd {
+            name: REPORTS_LIST_RCON_CMD.to_string(),
+            usage: REPORTS_LIST_RCON_CMD.to_string(),
+            description: "Lists the newest player reports.".to_string(),
+            auth_level: AuthLevel::Moderator,
+            cmd: Rc::new(|server, player_id, _, _| server.moderate_reports(player_id, None)),
+            args: vec![],
+        });
+        cmds.register(RconCmd {
+            name: REPORTS_VIEW_RCON_CMD.to_string(),
+            usage: format!("{REPORTS_VIEW_RCON_CMD} <id>"),
+            description: "Shows the details of a player report.".to_string(),
+            auth_level: AuthLevel::Moderator,
+            cmd: Rc::new(|server, player_id, _, args| {
+                let id = arg_text(args, 0)?
+                    .trim_start_matches('#')
+                    .parse::<i64>()
+                    .map_err(|_| anyhow!("expected the id of a report"))?;
+                server.moderate_reports(player_id, Some(id))
+            }),
+            args: vec![text_arg()],
+        });
+        cmds.register(RconCmd {
+            name: CONFIG_EFFECTIVE_RCON_CMD.to_string(),
+            usage: CONFIG_EFFECTIVE_RCON_CMD.to_string(),
+            description: "Shows the config of the mod & which layer set each value.".to_string(),
+            auth_level: AuthLevel::Moderator,
+            cmd: Rc::new(|server, _, _, _| Ok(server.mod_config.format().join("\n"))),
+            args: vec![],
+        });
+        cmds.register(RconCmd {
+            name: PRIORITY_SPEAKER_RCON_CMD.to_string(),
+            usage: format!("{PRIORITY_SPEAKER_RCON_CMD} <acc_<id>|cert_<fingerprint>>"),
+            description: "Toggles the priority of the player in the spatial chat.".to_string(),
+            auth_level: AuthLevel::Moderator,
+            cmd: Rc::new(|server, _, _, args| {
+                anyhow::ensure!(
+                    server.game_server.spatial_world.is_some(),
+                    "the spatial chat is not enabled on this server"
+                );
+                Self::toggle_spatial_chat_priority_speaker(
+                    &mut server.spatial_chat_priority_speakers,
+                    arg_text(args, 0)?,
+                )
+            }),
+            args: vec![text_arg()],
+        });
+        cmds
+    }
+
+    fn default_chat_cmds() -> ChatCmds<Self> {
+        let mut cmds = ChatCmds::default();
+        cmds.register(ChatCmd {
+            name: REPORT_CHAT_CMD.to_string(),
+            usage: format!("{REPORT_CHAT_CMD} <player> \"<reason>\""),
+            description: "Reports a player to the moderators.".to_string(),
+            cmd: Rc::new(|server, player_id, args| {
+                let report = ReportRequest::new(arg_text(args, 0)?, arg_text(args, 1)?)?;
+                server.report_player(player_id, report)
+            }),
+            args: vec![
+                CommandArg {
+                    expected_ty: CommandArgType::Text,
+                },
+                CommandArg {
+                    expected_ty: CommandArgType::Text,
+                },
+            ],
+        });
+        cmds
+    }
+
+    /// Executes the chat command if the server handles it itself,
+    /// else forwards it to the game.
+    ///
+    /// The feedback is only sent to the player that used the command.
+    fn exec_chat_cmd(
+        &mut self,
+        con_id: &NetworkConnectionId,
+        player_id: &GameEntityId,
+        raw: String,
+    ) {
+        let name = raw.split_whitespace().next().unwrap_or_default();
+        let Some(server_cmd) = self.chat_cmds.get(name) else {
+            if name == "help" {
+                let prefix = self
+                    .game_server
+                    .game
+                    .info
+                    .chat_commands
+                    .prefixes
+                    .first()
+                    .copied()
+                    .unwrap_or('/');
+                self.send_chat_msg_to(con_id, player_id, self.chat_cmds.help(prefix));
+            }
+            self.game_server
+                .game
+                .client_command(player_id, ClientCommand::Chat(ClientChatCommand { raw }));
+            return;
+        };
+        let (cb, usage) = (server_cmd.cmd.clone(), server_cmd.usage.clone());
+
+        let cmds = self.chat_cmds.chat_commands(Default::default()).cmds;
+        for cmd in command_parser::parser::parse(&raw, &cmds) {
+            let msg = match cmd {
+                CommandType::Full(cmd) => match cb(self, player_id, &cmd.args) {
+                    Ok(text) => text,
+                    Err(err) => format!("{err}\nUsage: {usage}"),
+                },
+                CommandType::Partial(err @ CommandParseResult::InvalidArg { .. }) => {
+                    format!("{err}\nUsage: {usage}")
+                }
+                CommandType::Partial(err) => err.to_string(),
+            };
+            if !msg.is_empty() {
+                self.send_chat_msg_to(con_id, player_id, msg);
+            }
+        }
+    }
+
+    /// Executes the rcon command if the server handles it itself,
+    /// else forwards it to the game.
+    ///
+    /// The feedback is only sent to the player that used the command.
+    fn exec_rcon_cmd(
+        &mut self,
+        con_id: &NetworkConnectionId,
+        player_id: &GameEntityId,
+        auth: AuthLevel,
+        name: String,
+        args: String,
+    ) {
+        let raw = format!("{name} {args}");
+        let Some(server_cmd) = self.rcon_cmds.get(&name) else {
+            if name == "help" {
+                let help = self.rcon_cmds.help(auth);
+                if !help.is_empty() {
+                    self.send_chat_msg_to(con_id, player_id, help);
+                }
+            }
+            self.game_server.game.client_command(
+                player_id,
+                ClientCommand::Rcon(ClientRconCommand {
+                    raw,
+                    auth_level: auth,
+                }),
+            );
+            return;
+        };
+        if server_cmd.auth_level > auth {
+            self.send_chat_msg_to(
+                con_id,
+                player_id,
+                format!("Unknown command {name}, use help to list all commands."),
+            );
+            return;
+        }
+        let (cb, usage) = (server_cmd.cmd.clone(), server_cmd.usage.clone());
+
+        for cmd in command_parser::parser::parse(&raw, &self.rcon_cmds.rcon_commands().cmds) {
+            let msg = match cmd {
+                CommandType::Full(cmd) => match cb(self, player_id, auth, &cmd.args) {
+                    Ok(text) => text,
+                    Err(err) => format!("{err}\nUsage: {usage}"),
+                },
+                CommandType::Partial(err @ CommandParseResult::InvalidArg { .. }) => {
+                    format!("{err}\nUsage: {usage}")
+                }
+                CommandType::Partial(err) => err.to_string(),
+            };
+            if !msg.is_empty() {
+                self.send_chat_msg_to(con_id, player_id, msg);
+            }
+        }
+    }
+
+    fn toggle_spatial_chat_priority_speaker(
+        priority_speakers: &mut HashSet<PlayerUniqueId>,
+        player: &str,
+    ) -> anyhow::Result<String> {
+        let player_unique_id = Self::parse_player_unique_id(player).ok_or_else(|| {
+            anyhow!("expected acc_<account id> or cert_<fingerprint>, got \"{player}\"")
+        })?;
+        let msg = if priority_speakers.remove(&player_unique_id) {
+            format!("{player} is no priority speaker anymore")
+        } else {
+            priority_speakers.insert(player_unique_id);
+            format!("{player} is a priority speaker now")
+        };
+        log::info!(target: "server", "{msg}");
+        Ok(msg)
+    }
+
     fn handle_msg(
         &mut self,
         timestamp: &Duration,
@@ -1008,6 +1958,20 @@ impl Server {
         match game_msg {
             GameMessage::ClientToServer(client_to_server_msg) => {
                 match client_to_server_msg {
+                    ClientToServerMessage::Version(version) => {
+                        if version != NETWORK_VERSION {
+                            log::info!(
+                                target: "server",
+                                "rejected a client with the network version {version}"
+                            );
+                            self.network.kick(
+                                con_id,
+                                NetworkDisconnectReason::VersionMismatch {
+                                    server_version: NETWORK_VERSION,
+                                },
+                            );
+                        }
+                    }
                     ClientToServerMessage::Ready(ready_info) => {
                         // if client is actually waiting, make it part of the game
                         let account_server_public_keys = self
@@ -1064,13 +2028,7 @@ impl Server {
 
                         if check_vote {
                             // update vote if nessecary
-                            if let Some(vote) = &mut self.game_server.cur_vote {
-                                vote.state.allowed_to_vote_count += 1;
-
-                                let vote_state = vote.state.clone();
-                                let started_at = vote.started_at;
-                                self.send_vote(Some(vote_state), started_at);
-                            }
+                            self.update_vote();
                         }
                     }
                     ClientToServerMessage::AddLocalPlayer(player_info) => {
@@ -1202,20 +2160,16 @@ impl Server {
                         }
                     }
                     ClientToServerMessage::RconExec { name, args } => {
-                        if let Some((auth, Some((player_id, _)))) = self
-                            .clients
-                            .clients
-                            .get(con_id)
-                            .map(|c| (&c.auth.level, c.players.front()))
+                        if let Some((auth, Some(player_id))) =
+                            self.clients.clients.get(con_id).map(|c| {
+                                (
+                                    c.auth.level,
+                                    c.players.front().map(|(player_id, _)| *player_id),
+                                )
+                            })
                         {
                             if matches!(auth, AuthLevel::Moderator | AuthLevel::Admin) {
-                                self.game_server.game.client_command(
-                                    player_id,
-                                    ClientCommand::Rcon(ClientRconCommand {
-                                        raw: format!("{} {}", name, args),
-                                        auth_level: *auth,
-                                    }),
-                                );
+                                self.exec_rcon_cmd(con_id, &player_id, auth, name, args);
                             }
                         }
                     }
@@ -1365,6 +2319,28 @@ impl Server {
                             }
                         }
                     }
+                    ClientToServerMessage::TimeSync { id } => {
+                        if self.clients.clients.contains_key(con_id) {
+                            self.network.send_unordered_to(
+                                &GameMessage::ServerToClient(ServerToClientMessage::TimeSync {
+                                    id,
+                                    server_time: *timestamp,
+                                    overhead: self
+                                        .sys
+                                        .time_get_nanoseconds()
+                                        .saturating_sub(*timestamp),
+                                }),
+                                con_id,
+                            );
+                        }
+                    }
+                    ClientToServerMessage::NetworkSettings(settings) => {
+                        if let Some(client) = self.clients.clients.get_mut(con_id) {
+                            self.packet_compressor
+                                .set_high_compression(con_id, settings.high_compression);
+                            client.network_settings = settings;
+                        }
+                    }
                 }
             }
             _ => {
@@ -1496,9 +2472,10 @@ impl Server {
                     },
                 })
                 .collect::<Vec<_>>(),
-            max_players: self.config_game.sv.max_players,
+            max_players: self.max_players as u32,
             passworded: false, // TODO:
             cert_sha256_fingerprint: self.cert_sha256_fingerprint,
+            icon: self.game_server.icon,
         };
 
         let register_info = loop {
@@ -1614,31 +2591,36 @@ impl Server {
                                 addr,
                             } => {
                                 log::debug!(target: "server", "connect time sv: {}", timestamp.as_nanos());
+                                let tracker =
+                                    ClientNetworkStatsTracker::new(&initial_network_stats);
+                                let network_stats = tracker.stats(initial_network_stats.ping);
+                                self.clients.network_stats_trackers.insert(con_id, tracker);
                                 self.try_client_connect(
                                     &con_id,
                                     &timestamp,
                                     addr.ip(),
                                     cert,
-                                    PlayerNetworkStats {
-                                        ping: initial_network_stats.ping,
-                                        packet_loss: initial_network_stats.packets_lost as f32
-                                            / initial_network_stats.packets_sent.clamp(1, u64::MAX)
-                                                as f32,
-                                    },
+                                    network_stats,
                                 );
                             }
                             NetworkEvent::Disconnected { reason, graceful } => {
                                 log::debug!(target: "server", "got disconnected event from network");
                                 if let Some(players) =
-                                    self.client_disconnect(&con_id, reason.as_str())
+                                    self.client_disconnect(&con_id, &reason.to_string())
                                 {
+                                    let timed_out = !graceful
+                                        && matches!(
+                                            reason,
+                                            NetworkDisconnectReason::Timeout
+                                                | NetworkDisconnectReason::Other(_)
+                                        );
                                     for player_id in players.keys() {
                                         self.game_server.player_drop(
                                             player_id,
-                                            if graceful {
-                                                PlayerDropReason::Disconnect
-                                            } else {
+                                            if timed_out {
                                                 PlayerDropReason::Timeout
+                                            } else {
+                                                PlayerDropReason::Disconnect
                                             },
                                         );
                                     }
@@ -1646,11 +2628,12 @@ impl Server {
                             }
                             NetworkEvent::NetworkStats(stats) => {
                                 log::debug!(target: "server", "server ping: {}", stats.ping.as_millis());
-                                let network_stats = PlayerNetworkStats {
-                                    ping: stats.ping,
-                                    packet_loss: stats.packets_lost as f32
-                                        / stats.packets_sent.clamp(1, u64::MAX) as f32,
-                                };
+                                let network_stats = self
+                                    .clients
+                                    .network_stats_trackers
+                                    .entry(con_id)
+                                    .or_insert_with(|| ClientNetworkStatsTracker::new(&stats))
+                                    .update(&stats);
                                 if let Some(client) = self.clients.clients.get_mut(&con_id) {
                                     client.network_stats = network_stats;
                                 } else if let Some(client) =
@@ -1695,11 +2678,13 @@ impl Server {
             // get time before checking ticks
             cur_time = self.sys.time_get_nanoseconds();
 
+            self.update_sleep(cur_time);
+
             // update vote
             if let Some(vote) = &mut self.game_server.cur_vote {
                 // check if vote is over
-                if vote.state.yes_votes == vote.state.allowed_to_vote_count
-                    || cur_time - vote.started_at > Duration::from_secs(25)
+                if vote.state.yes_votes >= vote.state.allowed_to_vote_count
+                    || cur_time - vote.started_at > SERVER_VOTE_DURATION
                 {
                     let vote = self.game_server.cur_vote.take().unwrap();
                     // fake democracy
@@ -1716,23 +2701,41 @@ impl Server {
                                 } = &vote.extra_vote_info
                                 {
                                     // kick that player
-                                    let ids = self.connection_bans.ban_ip(*ip);
+                                    let ban_minutes = self.config_game.sv.vote.kick_ban_minutes;
+                                    let duration = (ban_minutes > 0)
+                                        .then(|| Duration::from_secs(ban_minutes * 60));
+                                    let reason = NetworkDisconnectReason::Banned {
+                                        reason: "voted out".to_string(),
+                                        duration,
+                                    };
+                                    let ids = self.connection_bans.ban_ip(*ip, duration);
                                     for id in ids {
-                                        self.network.kick(&id);
+                                        self.network.kick(&id, reason.clone());
                                     }
-                                    self.network.kick(to_kick_player);
+                                    self.network.kick(to_kick_player, reason);
                                 }
                             }
-                            VoteType::VoteSpecPlayer { voted_player_id } => todo!(),
-                            VoteType::Misc() => todo!(),
+                            VoteType::VoteSpecPlayer { .. } => {
+                                // started as vote of the game instead
+                            }
+                            VoteType::Misc() => {
+                                // never started, see above
+                            }
                         }
                     }
 
-                    self.send_vote(None, Duration::ZERO);
+                    self.send_vote(None, Duration::ZERO, Duration::ZERO);
                 }
             }
 
-            while is_next_tick(cur_time, &mut self.last_tick_time, ticks_in_a_second) {
+            let mut vote_map = None;
+            while !self.is_sleeping
+                && self.sim_speed.is_next_tick(
+                    cur_time,
+                    &mut self.last_tick_time,
+                    ticks_in_a_second,
+                )
+            {
                 // apply all queued inputs
                 if let Some(mut inputs) = self
                     .game_server
@@ -1770,6 +2773,9 @@ impl Server {
                     "server",
                 );
 
+                self.auto_record_tick(ticks_in_a_second);
+                vote_map = self.handle_game_events().or(vote_map);
+
                 // snap shot building
                 for (con_id, client) in &mut self.clients.clients {
                     let mut player_ids = self.player_ids_pool.new();
@@ -1783,7 +2789,15 @@ impl Server {
                     let snap_id = client.snap_id;
                     client.snap_id += 1;
 
-                    if client.snap_id % self.config_game.sv.ticks_per_snapshot == 0 {
+                    // the client might request less snapshots to save bandwidth,
+                    // but always send at least one snapshot per second.
+                    let ticks_per_snapshot = client
+                        .network_settings
+                        .ticks_per_snapshot
+                        .map(|ticks| ticks.get().min(ticks_in_a_second.get()))
+                        .unwrap_or_default()
+                        .max(self.config_game.sv.ticks_per_snapshot);
+                    if client.snap_id % ticks_per_snapshot == 0 {
                         let mut snap = self.game_server.game.snapshot_for(snap_client);
 
                         // this should be smaller than the number of snapshots saved on the client
@@ -1836,8 +2850,9 @@ impl Server {
                         });
                         self.network.send_unordered_auto_to(
                             &GameMessage::ServerToClient(ServerToClientMessage::Snapshot {
-                                overhead_time: (self.sys.time_get_nanoseconds()
-                                    - self.last_tick_time),
+                                overhead_time: self.sim_speed.to_sim_time(
+                                    self.sys.time_get_nanoseconds() - self.last_tick_time,
+                                ),
                                 snapshot: snap_diff.as_ref().into(),
                                 diff_id,
                                 snap_id_diffed: diff_id
@@ -1859,11 +2874,14 @@ impl Server {
                     // events building
                     let mut player_ids = self.player_ids_pool.new();
                     player_ids.extend(client.players.keys());
-                    let events = self.game_server.game.events_for(EventClientInfo {
+                    let mut events = self.game_server.game.events_for(EventClientInfo {
                         client_player_ids: player_ids,
                         everything: false,
                         other_stages: false,
                     });
+                    if client.network_settings.reduced_events {
+                        Self::reduce_events(&mut events, client);
+                    }
                     if !events.is_empty() {
                         self.network.send_in_order_to(
                             &GameMessage::ServerToClient(ServerToClientMessage::Events {
@@ -1881,7 +2899,11 @@ impl Server {
             }
 
             if let Some(spatial_world) = &mut self.game_server.spatial_world {
-                spatial_world.update(&mut self.network);
+                spatial_world.update(&mut self.network, &self.spatial_chat_priority_speakers);
+            }
+
+            if let Some(map) = vote_map {
+                self.load_map(map.as_str());
             }
 
             // after tick checks
@@ -1892,80 +2914,33 @@ impl Server {
             }
 
             // check db requests
-            self.db_requests_helper.clear();
-            for db_req in self.db_requests.drain(..) {
-                if db_req.is_finished() {
-                    match db_req.get_storage() {
-                        Ok(req) => match req {
-                            GameServerDb::Account(ev) => match ev {
-                                GameServerDbAccount::Rename {
-                                    con_id,
-                                    rename_result,
-                                } => {
-                                    if self.clients.clients.contains_key(&con_id) {
-                                        self.network.send_unordered_to(
-                                            &GameMessage::ServerToClient(
-                                                ServerToClientMessage::AccountRenameRes(
-                                                    rename_result,
-                                                ),
-                                            ),
-                                            &con_id,
-                                        );
-                                    }
-                                }
-                                GameServerDbAccount::Info {
-                                    con_id,
-                                    account_details,
-                                } => {
-                                    if self.clients.clients.contains_key(&con_id) {
-                                        self.network.send_unordered_to(
-                                            &GameMessage::ServerToClient(
-                                                ServerToClientMessage::AccountDetails(
-                                                    account_details,
-                                                ),
-                                            ),
-                                            &con_id,
-                                        );
-                                    }
-                                }
-                                GameServerDbAccount::AutoLogin {
-                                    user_id,
-                                    new_account_was_created,
-                                } => {
-                                    if let Some(account_id) = new_account_was_created
-                                        .then_some(user_id.account_id)
-                                        .flatten()
-                                    {
-                                        // A new account was created, tell the game mod
-                                        self.game_server
-                                            .game
-                                            .account_created(account_id, user_id.public_key);
-                                    }
-                                }
-                            },
-                        },
-                        Err(err) => {
-                            log::error!(target: "server-db-requests", "{err}");
-                        }
-                    }
-                } else {
-                    self.db_requests_helper.push(db_req);
-                }
-            }
-            std::mem::swap(&mut self.db_requests_helper, &mut self.db_requests);
+            self.handle_db_requests();
+
+            self.keep_db_alive(cur_time);
+
+            self.check_idle_map_change(cur_time);
+
+            self.run_scheduled_events();
+
+            self.load_pending_map();
 
             // time and sleeps
             cur_time = self.sys.time_get_nanoseconds();
 
-            if is_next_tick(
+            if self.is_sleeping {
+                // network events wake the server up early,
+                // so connecting clients don't notice the sleep.
+                self.network_notifier
+                    .wait_for_event(Some(SLEEP_WAKE_UP_INTERVAL));
+            } else if self.sim_speed.is_next_tick(
                 cur_time,
                 &mut self.last_tick_time.clone(), /* <-- dummy */
                 ticks_in_a_second,
             ) {
                 std::thread::yield_now();
             } else {
-                let next_tick_time =
-                    time_until_tick(ticks_in_a_second) - (cur_time - self.last_tick_time);
+                let next_tick_time = self.sim_speed.time_until_tick(ticks_in_a_second)
+                    - (cur_time - self.last_tick_time);
 
                 //let mut guard = self.game_event_generator_server.blocking_lock();
                 //guard = guard.ev_cond.wait_timeout(guard.into(), next_tick_time);
@@ -1974,20 +2949,251 @@ impl Server {
         }
     }
 
+    fn handle_db_requests(&mut self) {
+        self.db_requests_helper.clear();
+        for db_req in self.db_requests.drain(..) {
+            if db_req.is_finished() {
+                match db_req.get_storage() {
+                    Ok(req) => match req {
+                        GameServerDb::Account(ev) => match ev {
+                            GameServerDbAccount::Rename {
+                                con_id,
+                                rename_result,
+                            } => {
+                                if self.clients.clients.contains_key(&con_id) {
+                                    self.network.send_unordered_to(
+                                        &GameMessage::ServerToClient(
+                                            ServerToClientMessage::AccountRenameRes(rename_result),
+                                        ),
+                                        &con_id,
+                                    );
+                                }
+                            }
+                            GameServerDbAccount::Info {
+                                con_id,
+                                account_details,
+                            } => {
+                                if self.clients.clients.contains_key(&con_id) {
+                                    self.network.send_unordered_to(
+                                        &GameMessage::ServerToClient(
+                                            ServerToClientMessage::AccountDetails(account_details),
+                                        ),
+                                        &con_id,
+                                    );
+                                }
+                            }
+                            GameServerDbAccount::AutoLogin {
+                                user_id,
+                                new_account_was_created,
+                            } => {
+                                if let Some(account_id) = new_account_was_created
+                                    .then_some(user_id.account_id)
+                                    .flatten()
+                                {
+                                    // A new account was created, tell the game mod
+                                    self.game_server
+                                        .game
+                                        .account_created(account_id, user_id.public_key);
+                                }
+                            }
+                        },
+                        GameServerDb::Reports {
+                            con_id,
+                            player_id,
+                            msgs,
+                        } => {
+                            if self.clients.clients.contains_key(&con_id) {
+                                for msg in msgs {
+                                    self.send_chat_msg_to(&con_id, &player_id, msg);
+                                }
+                            }
+                        }
+                    },
+                    Err(err) => {
+                        log::error!(target: "server-db-requests", "{err}");
+                    }
+                }
+            } else {
+                self.db_requests_helper.push(db_req);
+            }
+        }
+        std::mem::swap(&mut self.db_requests_helper, &mut self.db_requests);
+    }
+
+    /// No client is connected or queued.
+    fn is_empty(&self) -> bool {
+        self.clients.clients.is_empty()
+            && self.clients.network_clients.is_empty()
+            && self.clients.network_queued_clients.is_empty()
+    }
+
+    /// Puts the server to sleep after it was empty for
+    /// `sv.sleep_when_empty_secs` seconds and wakes it up
+    /// as soon as a client connects.
+    ///
+    /// While sleeping the game is not ticked at all and the server loop
+    /// only runs every [`SLEEP_WAKE_UP_INTERVAL`], which keeps idle servers
+    /// cheap, since nothing in the game (incl. its database requests) runs.
+    /// The database keepalive is suspended as well and the database is
+    /// pinged right after waking up, see [`Self::keep_db_alive`].
+    fn update_sleep(&mut self, cur_time: Duration) {
+        if !self.is_empty() || self.config_game.sv.sleep_when_empty_secs == 0 {
+            self.sleep_empty_since = None;
+            if self.is_sleeping {
+                log::info!(target: "server", "waking up from sleep");
+                self.is_sleeping = false;
+                // the ticks that were skipped while sleeping are not caught up
+                self.last_tick_time = cur_time;
+                self.last_db_keepalive = None;
+            }
+            return;
+        }
+        let empty_since = *self.sleep_empty_since.get_or_insert(cur_time);
+        if !self.is_sleeping
+            && cur_time.saturating_sub(empty_since)
+                >= Duration::from_secs(self.config_game.sv.sleep_when_empty_secs)
+        {
+            log::info!(target: "server", "server is empty, going to sleep");
+            self.is_sleeping = true;
+        }
+    }
+
+    /// Pings the database every [`DB_KEEPALIVE_INTERVAL`] while the server is awake.
+    fn keep_db_alive(&mut self, cur_time: Duration) {
+        let Some(db) = self.db.clone() else {
+            return;
+        };
+        if self.is_sleeping
+            || self
+                .last_db_keepalive
+                .is_some_and(|last| cur_time.saturating_sub(last) < DB_KEEPALIVE_INTERVAL)
+        {
+            return;
+        }
+        self.last_db_keepalive = Some(cur_time);
+        self.io.io_batcher.spawn_without_lifetime(async move {
+            let res = db.ping().await;
+            if let Err(err) = &res {
+                log::error!(target: "server", "database keepalive failed: {err}");
+            }
+            res.map(|_| ())
+        });
+    }
+
+    /// Switches to the idle map, if the server was empty for long enough.
+    fn check_idle_map_change(&mut self, cur_time: Duration) {
+        if !self.is_empty() || self.config_game.sv.idle_map_change_minutes == 0 {
+            self.empty_since = None;
+            self.idle_map_loaded = false;
+            return;
+        }
+        if self.idle_map_loaded {
+            return;
+        }
+        let empty_since = *self.empty_since.get_or_insert(cur_time);
+        if cur_time.saturating_sub(empty_since)
+            < Duration::from_secs(self.config_game.sv.idle_map_change_minutes * 60)
+        {
+            return;
+        }
+        self.idle_map_loaded = true;
+
+        let idle_map = if self.config_game.sv.idle_map.is_empty() {
+            self.config_game.sv.map.clone()
+        } else {
+            self.config_game.sv.idle_map.clone()
+        };
+        // the map is also reloaded if the idle map is already active,
+        // so the game state is reset.
+        log::info!(target: "server", "server is idle, switching to map {idle_map}");
+
+        self.pending_map_load = Some(PendingMapLoad::Map(idle_map));
+    }
+
+    /// Executes the scheduled events that are due.
+    fn run_scheduled_events(&mut self) {
+        for action in self.scheduler.update(chrono::Local::now()) {
+            match action {
+                ScheduledAction::Broadcast(msg) => {
+                    self.game_server
+                        .game
+                        .server_command(ServerCommand::Broadcast(msg));
+                }
+                ScheduledAction::ScoreLimit(score_limit) => {
+                    self.game_server
+                        .game
+                        .server_command(ServerCommand::ScoreLimit(score_limit));
+                }
+                ScheduledAction::RotateMap(maps) => {
+                    let Some(map) =
+                        Scheduler::next_map(&maps, self.game_server.map.name.as_str()).cloned()
+                    else {
+                        continue;
+                    };
+                    log::info!(target: "server", "scheduled map rotation to {map}");
+
+                    self.pending_map_load = Some(PendingMapLoad::Map(map));
+                }
+                ScheduledAction::MaxPlayers(max_players) => {
+                    match self.set_max_players(max_players) {
+                        Ok(msg) => log::info!(target: "server", "scheduled: {msg}"),
+                        Err(err) => {
+                            log::error!(target: "server", "scheduled player slots failed: {err}")
+                        }
+                    }
+                }
+                ScheduledAction::Reset(reset) => {
+                    let map = self.game_server.map.name.clone();
+                    log::info!(target: "server", "scheduled {reset:?} reset of {map}");
+
+                    // the demo of the old game is finished & the clients
+                    // load the game again, like on a map change.
+                    self.pending_map_load = Some(match reset {
+                        ScheduledReset::State => PendingMapLoad::Map(map),
+                        ScheduledReset::Map => PendingMapLoad::KeepState,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Loads the pending map once all database requests are finished,
+    /// these belong to the game that is unloaded.
+    /// The requests are checked once per tick by [`Self::handle_db_requests`].
+    fn load_pending_map(&mut self) {
+        if !self.db_requests.is_empty() {
+            return;
+        }
+        match self.pending_map_load.take() {
+            Some(PendingMapLoad::Map(map)) => self.load_map(&map),
+            Some(PendingMapLoad::KeepState) => {
+                let snapshot = self.game_server.game.snapshot_for_hotreload();
+                let map = self.game_server.map.name.clone();
+                self.load_impl(snapshot, &map);
+            }
+            None => {}
+        }
+    }
+
     fn load_impl(&mut self, snapshot: Option<PoolCow<'static, [u8]>>, map: &str) {
+        // the demo belongs to the old map
+        self.auto_record.stop();
         // reload the whole game server, including the map
         let mod_name = Self::config_mod_name(&self.config_game);
-        let config = Self::read_mod_config(&self.io, &mod_name)
+        self.mod_config = LayeredConfig::read(&self.io, &mod_name, map)
             .get_storage()
-            .ok();
+            .unwrap_or_default();
         self.game_server = ServerGame::new(
             map,
             &mod_name,
-            config,
+            self.mod_config.to_bytes(),
+            !self.mod_config.has_overrides(),
             &self.thread_pool,
             &self.io,
             &self.game_db,
             self.config_game.sv.spatial_chat,
+            self.icon.clone(),
+            self.max_players,
         )
         .unwrap();
         if let Some(snapshot) = snapshot {
@@ -1995,6 +3201,19 @@ impl Server {
                 .game
                 .build_from_snapshot_by_hotreload(&snapshot);
         }
+        // scheduled changes stay active for the new game
+        if let Some(score_limit) = self.scheduler.score_limit {
+            self.game_server
+                .game
+                .server_command(ServerCommand::ScoreLimit(score_limit));
+        }
+        // clients that are still loading the old map ignore the load event,
+        // they connect again to load the new map instead
+        let loading: HashSet<_> = self.clients.network_clients.keys().copied().collect();
+        for net_id in &loading {
+            self.network
+                .kick(net_id, NetworkDisconnectReason::MapChange);
+        }
         // put all players back to a loading state
         self.clients.clients.drain().for_each(|(net_id, client)| {
             self.clients.network_clients.insert(
@@ -2021,13 +3240,209 @@ impl Server {
             server_options: self.game_server.game.info.options.clone(),
             spatial_chat: self.config_game.sv.spatial_chat,
         };
-        self.clients.network_clients.keys().for_each(|net_id| {
-            self.network.send_unordered_to(
-                &GameMessage::ServerToClient(ServerToClientMessage::Load(server_info.clone())),
-                net_id,
+        self.clients
+            .network_clients
+            .keys()
+            .filter(|net_id| !loading.contains(net_id))
+            .for_each(|net_id| {
+                self.network.send_unordered_to(
+                    &GameMessage::ServerToClient(ServerToClientMessage::Load(server_info.clone())),
+                    net_id,
+                );
+            });
+        self.last_tick_time = self.sys.time_get_nanoseconds();
+    }
+
+    /// Records the current tick into the demo of the current round,
+    /// see [`AutoRecord`].
+    fn auto_record_tick(&mut self, ticks_in_a_second: NonZeroGameTickType) {
+        let config = &self.config_game.sv.auto_record;
+        // without players there is no round worth recording
+        if !config.enabled || self.game_server.players.is_empty() {
+            self.auto_record.stop();
+            return;
+        }
+        if !self.auto_record.is_recording() {
+            self.auto_record.start(
+                DemoRecorderCreateProps {
+                    map: self.game_server.map.name.as_str().try_into().unwrap(),
+                    map_hash: self.game_server.map_blake3_hash,
+                    game_options: GameStateCreateOptions {
+                        hint_max_characters: None,
+                        config: self.game_server.game.info.config.clone(),
+                    },
+                    required_resources: Default::default(),
+                    physics_module: self.game_server.game_mod.clone(),
+                    render_module: GameModification::Native,
+                    physics_group_name: self
+                        .game_server
+                        .game
+                        .info
+                        .options
+                        .physics_group_name
+                        .clone(),
+                    encryption_passphrase: None,
+                    split: Default::default(),
+                    record_inputs: false,
+                    signing_key: config.sign.then(|| self.demo_signing_key.clone()),
+                    on_saved: None,
+                    io: self.io.clone(),
+                },
+                ticks_in_a_second,
+                config.max_demos,
             );
+        }
+
+        let monotonic_tick = self.game_server.cur_monotonic_tick;
+        if monotonic_tick % self.config_game.sv.ticks_per_snapshot.max(1) == 0 {
+            let snap = self.game_server.game.snapshot_for(SnapshotClientInfo {
+                client_player_ids: self.player_ids_pool.new(),
+                snap_everything: true,
+                snap_other_stages: true,
+            });
+            self.auto_record.add_snapshot(monotonic_tick, snap.to_vec());
+        }
+        let events = self.game_server.game.events_for(EventClientInfo {
+            client_player_ids: self.player_ids_pool.new(),
+            everything: true,
+            other_stages: true,
         });
-        self.last_tick_time = self.sys.time_get_nanoseconds();
+        if self.auto_record.add_events(monotonic_tick, events) {
+            // the next round is recorded into a new demo
+            self.auto_record.stop();
+        }
+    }
+
+    /// Writes the report of a round that the game could not store
+    /// in the database to `round_reports/<report id>.json`.
+    fn write_round_report(&self, report_id: &Hash, report: String) {
+        let fs = self.io.fs.clone();
+        let path = PathBuf::from(format!("round_reports/{}.json", fmt_hash(report_id)));
+        self.io.io_batcher.spawn_without_lifetime(async move {
+            let res = async {
+                fs.create_dir("round_reports".as_ref()).await?;
+                fs.write_file(&path, report.into_bytes()).await
+            }
+            .await;
+            if let Err(err) = &res {
+                log::error!(target: "server", "failed to write round report: {err}");
+            }
+            Ok(res?)
+        });
+    }
+
+    /// Kicks the players the game wants to kick, mirrors the running vote
+    /// of the game to the vote ui of the clients and executes the passed votes
+    /// of the game that the game can't execute itself.
+    /// Returns the map to change to, which must be done after the tick.
+    fn handle_game_events(&mut self) -> Option<String> {
+        let events = self.game_server.game.events_for(EventClientInfo {
+            client_player_ids: self.player_ids_pool.new(),
+            everything: true,
+            other_stages: true,
+        });
+        let mut vote_map = None;
+        for ev in events
+            .worlds
+            .values()
+            .flat_map(|world| world.events.values())
+        {
+            let ev = match ev {
+                GameWorldEvent::Global(GameWorldGlobalEvent::Kick { player_id, reason }) => {
+                    if let Some(player) = self.game_server.players.get(player_id) {
+                        self.network.kick(
+                            &player.network_id,
+                            NetworkDisconnectReason::Kicked(reason.to_string()),
+                        );
+                    }
+                    continue;
+                }
+                GameWorldEvent::Global(GameWorldGlobalEvent::ExportRoundReport {
+                    report_id,
+                    report,
+                }) => {
+                    self.write_round_report(report_id, report.to_string());
+                    continue;
+                }
+                GameWorldEvent::Global(GameWorldGlobalEvent::Vote(ev)) => ev,
+                _ => continue,
+            };
+            let action = match ev {
+                GameWorldVoteEvent::Started {
+                    action, duration, ..
+                } => {
+                    let vote = match action {
+                        GameVoteAction::KickPlayer { player_id } => VoteType::VoteKickPlayer {
+                            voted_player_id: *player_id,
+                        },
+                        GameVoteAction::SpecPlayer { player_id } => VoteType::VoteSpecPlayer {
+                            voted_player_id: *player_id,
+                        },
+                        GameVoteAction::ChangeMap { name } => {
+                            match self.map_votes.iter().find(|map| map.name == *name) {
+                                Some(map) => VoteType::Map(map.clone()),
+                                None => VoteType::Misc(),
+                            }
+                        }
+                    };
+                    self.game_server.cur_game_vote = Some(ServerGameVote {
+                        state: VoteState {
+                            vote,
+                            // filled on the fly instead
+                            remaining_time: Duration::ZERO,
+                            // sent by the game
+                            yes_votes: 0,
+                            no_votes: 0,
+                            allowed_to_vote_count: 0,
+                        },
+                        started_at: self.sys.time_get_nanoseconds(),
+                        duration: *duration,
+                    });
+                    continue;
+                }
+                GameWorldVoteEvent::Votes { yes, no, voters } => {
+                    if let Some(vote) = &mut self.game_server.cur_game_vote {
+                        vote.state.yes_votes = *yes as usize;
+                        vote.state.no_votes = *no as usize;
+                        vote.state.allowed_to_vote_count = *voters as usize;
+                        let (state, started_at, duration) =
+                            (vote.state.clone(), vote.started_at, vote.duration);
+                        self.send_vote(Some(state), started_at, duration);
+                    }
+                    continue;
+                }
+                GameWorldVoteEvent::Failed { .. } => {
+                    self.game_server.cur_game_vote = None;
+                    self.send_vote(None, Duration::ZERO, Duration::ZERO);
+                    continue;
+                }
+                GameWorldVoteEvent::Passed { action } => {
+                    self.game_server.cur_game_vote = None;
+                    self.send_vote(None, Duration::ZERO, Duration::ZERO);
+                    action
+                }
+            };
+            match action {
+                GameVoteAction::KickPlayer { player_id } => {
+                    if let Some(player) = self.game_server.players.get(player_id) {
+                        self.network.kick(
+                            &player.network_id,
+                            NetworkDisconnectReason::Kicked("voted out".to_string()),
+                        );
+                    }
+                }
+                GameVoteAction::SpecPlayer { .. } => {
+                    // already done by the game
+                }
+                GameVoteAction::ChangeMap { name } => {
+                    // only maps that can be voted by the client ui
+                    if self.map_votes.iter().any(|map| map.name == *name) {
+                        vote_map = Some(name.as_str().to_string());
+                    }
+                }
+            }
+        }
+        vote_map
     }
 
     fn reload(&mut self) {
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, net::IpAddr, sync::Arc, time::Duration};
 
 use anyhow::anyhow;
-use base::hash::{fmt_hash, name_and_hash, Hash};
+use base::hash::{fmt_hash, generate_hash_for, name_and_hash, Hash};
 use base_http::http_server::HttpDownloadServer;
 use base_io::io::Io;
 use base_io_traits::fs_traits::FileSystemWatcherItemInterface;
@@ -23,7 +23,11 @@ use game_interface::{
     },
     votes::{VoteState, Voted},
 };
-use shared_base::{network::messages::GameModification, player_input::PlayerInput};
+use shared_base::{
+    network::messages::GameModification,
+    player_input::PlayerInput,
+    server_browser::{server_icon_path, ServerBrowserInfoIcon},
+};
 
 use crate::spatial_chat::SpatialWorld;
 
@@ -178,6 +182,15 @@ pub struct ServerVote {
     pub participating_clients: HashMap<NetworkConnectionId, Voted>,
 }
 
+/// A vote that is run by the game, only mirrored
+/// for the vote ui of the clients.
+#[derive(Debug)]
+pub struct ServerGameVote {
+    pub state: VoteState,
+    pub started_at: Duration,
+    pub duration: Duration,
+}
+
 pub const RESERVED_VANILLA_NAMES: [&str; 4] = ["", "vanilla", "native", "default"];
 pub const RESERVED_DDNET_NAMES: [&str; 1] = ["ddnet"];
 
@@ -192,9 +205,12 @@ pub struct ServerGame {
     game_mod_fs_change_watcher: Option<Box<dyn FileSystemWatcherItemInterface>>,
 
     pub http_server: Option<HttpDownloadServer>,
+    /// the server icon, if the resource server serves one
+    pub icon: Option<ServerBrowserInfoIcon>,
 
     // votes
     pub cur_vote: Option<ServerVote>,
+    pub cur_game_vote: Option<ServerGameVote>,
 
     pub queued_inputs: LinkedHashMap<GameTickType, LinkedHashMap<GameEntityId, PlayerInput>>,
 
@@ -206,10 +222,13 @@ impl ServerGame {
         map_name: &str,
         game_mod: &str,
         config: Option<Vec<u8>>,
+        save_config: bool,
         runtime_thread_pool: &Arc<rayon::ThreadPool>,
         io: &Io,
         db: &Arc<dyn DbInterface>,
         spatial_chat: bool,
+        icon: Option<Vec<u8>>,
+        max_players: usize,
     ) -> anyhow::Result<Self> {
         let map = ServerMap::new(map_name, io, runtime_thread_pool).unwrap();
         let (game_state_mod, game_mod, game_mod_file, game_mod_name, game_mod_blake3_hash) =
@@ -262,7 +281,7 @@ impl ServerGame {
             map.map_file.clone(),
             map.name.clone(),
             GameStateCreateOptions {
-                hint_max_characters: None, // TODO:
+                hint_max_characters: Some(max_players),
                 config,
             },
             io,
@@ -278,7 +297,9 @@ impl ServerGame {
             )
         });
 
-        if let Some(config) = game.info.config.clone() {
+        // the config of the game type is completed by the game mod,
+        // unless it was merged with other layers, that would leak into it.
+        if let Some(config) = game.info.config.clone().filter(|_| save_config) {
             let game_mod_name = game_mod_name.clone();
             let fs = io.fs.clone();
             io.io_batcher.spawn_without_lifetime(async move {
@@ -289,33 +310,46 @@ impl ServerGame {
             });
         }
 
+        let icon = icon.map(|icon| (generate_hash_for(&icon), icon));
+        let icon_info = icon.as_ref().map(|(hash, icon)| (*hash, icon.len()));
+
+        let http_server = HttpDownloadServer::new(
+            vec![(
+                format!("map/maps/{}_{}.twmap", map_name, fmt_hash(&map_hash)),
+                map.map_file.clone(),
+            )]
+            .into_iter()
+            .chain(map.resource_files.clone().into_iter())
+            .chain(
+                icon.map(|(hash, icon)| (server_icon_path(&hash), icon))
+                    .into_iter(),
+            )
+            .chain(
+                game_mod_blake3_hash
+                    .map(|game_mod_blake3_hash| {
+                        (
+                            format!(
+                                "{}/{}_{}.wasm",
+                                STATE_MODS_PATH,
+                                game_mod_name,
+                                fmt_hash(&game_mod_blake3_hash)
+                            ),
+                            game_mod_file,
+                        )
+                    })
+                    .into_iter(),
+            )
+            .collect(),
+        )?;
+        let icon = icon_info.map(|(blake3, size)| ServerBrowserInfoIcon {
+            blake3,
+            size,
+            port: http_server.port,
+        });
+
         Ok(Self {
-            http_server: {
-                Some(HttpDownloadServer::new(
-                    vec![(
-                        format!("map/maps/{}_{}.twmap", map_name, fmt_hash(&map_hash)),
-                        map.map_file.clone(),
-                    )]
-                    .into_iter()
-                    .chain(map.resource_files.clone().into_iter())
-                    .chain(
-                        game_mod_blake3_hash
-                            .map(|game_mod_blake3_hash| {
-                                (
-                                    format!(
-                                        "{}/{}_{}.wasm",
-                                        STATE_MODS_PATH,
-                                        game_mod_name,
-                                        fmt_hash(&game_mod_blake3_hash)
-                                    ),
-                                    game_mod_file,
-                                )
-                            })
-                            .into_iter(),
-                    )
-                    .collect(),
-                )?)
-            },
+            http_server: Some(http_server),
+            icon,
 
             players: Default::default(),
             game,
@@ -328,6 +362,7 @@ impl ServerGame {
 
             // votes
             cur_vote: None,
+            cur_game_vote: None,
 
             queued_inputs: Default::default(),
 
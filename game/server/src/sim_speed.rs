@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use game_interface::types::game::NonZeroGameTickType;
+use shared_base::game_types::time_until_tick;
+
+/// Rcon command that sets the speed of the simulation,
+/// e.g. `0.25` for slow motion or `4` to fast-forward.
+pub const SIM_SPEED_RCON_CMD: &str = "sim_speed";
+
+const SIM_SPEED_MIN: f64 = 0.05;
+const SIM_SPEED_MAX: f64 = 10.0;
+
+/// Runs the simulation at a fraction or a multiple of real time.
+///
+/// Only the time between ticks changes, the ticks themselves are
+/// the same as at normal speed.
+/// The speed is owned by the server, so it is kept across map changes.
+#[derive(Debug)]
+pub struct SimSpeed {
+    speed: f64,
+}
+
+impl Default for SimSpeed {
+    fn default() -> Self {
+        Self { speed: 1.0 }
+    }
+}
+
+impl SimSpeed {
+    /// The real time between two ticks.
+    pub fn time_until_tick(&self, ticks_in_a_second: NonZeroGameTickType) -> Duration {
+        time_until_tick(ticks_in_a_second).div_f64(self.speed)
+    }
+
+    /// Like [`shared_base::game_types::is_next_tick`], but with the scaled tick time.
+    pub fn is_next_tick(
+        &self,
+        cur_time: Duration,
+        last_tick_time: &mut Duration,
+        ticks_in_a_second: NonZeroGameTickType,
+    ) -> bool {
+        let tick_time = self.time_until_tick(ticks_in_a_second);
+        if cur_time >= *last_tick_time + tick_time {
+            *last_tick_time += tick_time;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Converts a real time duration to the time that passed in the simulation.
+    ///
+    /// Times that are sent to clients must be converted,
+    /// so they stay consistent with the tick time the clients expect.
+    pub fn to_sim_time(&self, real_time: Duration) -> Duration {
+        real_time.mul_f64(self.speed)
+    }
+
+    pub fn set_speed(&mut self, arg: &str) -> anyhow::Result<String> {
+        let speed = arg
+            .trim_end_matches('x')
+            .parse::<f64>()
+            .ok()
+            .filter(|speed| speed.is_finite())
+            .ok_or_else(|| anyhow!("expected a number like 0.25 or 4, got \"{arg}\""))?;
+        self.speed = speed.clamp(SIM_SPEED_MIN, SIM_SPEED_MAX);
+        log::info!(
+            target: "server",
+            "the simulation runs at {}x speed now",
+            self.speed
+        );
+        Ok(format!("the simulation runs at {}x speed now", self.speed))
+    }
+}
@@ -6,6 +6,11 @@ use shared_network::messages::{GameMessage, MsgSvSpatialChatOfEntitity, ServerTo
 
 const MAX_ID_REORDER: u64 = 2;
 
+/// Rcon command that toggles whether a player is a priority speaker (e.g. a caster).
+/// The player is identified the same way the client's spatial chat settings
+/// identify players: `acc_<account id>` or `cert_<cert fingerprint>`.
+pub const PRIORITY_SPEAKER_RCON_CMD: &str = "spatial_chat.priority_speaker";
+
 #[derive(Debug)]
 pub struct SpatialClient {
     pending_opus_frames: BTreeMap<u64, Vec<Vec<u8>>>,
@@ -42,7 +47,11 @@ impl SpatialWorld {
         }
     }
 
-    pub fn update(&mut self, network: &mut QuinnNetwork) {
+    pub fn update(
+        &mut self,
+        network: &mut QuinnNetwork,
+        priority_speakers: &HashSet<PlayerUniqueId>,
+    ) {
         let all_clients = self
             .clients
             .keys()
@@ -57,6 +66,7 @@ impl SpatialWorld {
                     MsgSvSpatialChatOfEntitity {
                         latest_opus_frames: client.pending_opus_frames.clone(),
                         player_unique_id: client.player_unique_id,
+                        priority: priority_speakers.contains(&client.player_unique_id),
                     },
                 );
             }
@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+
+use game_config::config::ConfigServerWhitelist;
+use game_interface::types::player_info::PlayerUniqueId;
+
+use crate::server::Server;
+
+/// Rcon command that turns the whitelist `on` or `off`.
+pub const WHITELIST_RCON_CMD: &str = "whitelist";
+/// Rcon command that adds a player to the whitelist.
+pub const WHITELIST_ADD_RCON_CMD: &str = "whitelist.add";
+/// Rcon command that removes a player from the whitelist.
+pub const WHITELIST_REMOVE_RCON_CMD: &str = "whitelist.remove";
+/// Rcon command that kicks all connected players that are not whitelisted.
+pub const WHITELIST_KICK_RCON_CMD: &str = "whitelist.kick";
+
+/// If enabled, only whitelisted players can join the server.
+///
+/// The whitelist is owned by the server, so changes by rcon
+/// commands are kept across map changes (but not saved to the config).
+#[derive(Debug, Default)]
+pub struct Whitelist {
+    pub enabled: bool,
+    players: HashSet<PlayerUniqueId>,
+    /// The message rejected players see.
+    pub msg: String,
+}
+
+impl Whitelist {
+    pub fn new(config: &ConfigServerWhitelist) -> Self {
+        let players = config
+            .players
+            .iter()
+            .filter_map(|player| {
+                let player_unique_id = Server::parse_player_unique_id(player);
+                if player_unique_id.is_none() {
+                    log::error!(
+                        target: "server",
+                        "invalid whitelisted player \"{player}\", \
+                        expected acc_<account id> or cert_<fingerprint>"
+                    );
+                }
+                player_unique_id
+            })
+            .collect();
+        Self {
+            enabled: config.enabled,
+            players,
+            msg: config.msg.clone(),
+        }
+    }
+
+    pub fn is_allowed(&self, player: &PlayerUniqueId) -> bool {
+        !self.enabled || self.players.contains(player)
+    }
+
+    /// Returns the feedback for the rcon command.
+    pub fn set_enabled(&mut self, arg: &str) -> anyhow::Result<String> {
+        match arg {
+            "on" | "1" | "true" => self.enabled = true,
+            "off" | "0" | "false" => self.enabled = false,
+            _ => anyhow::bail!("expected on or off, got \"{arg}\""),
+        }
+        let msg = format!(
+            "the whitelist is {} now",
+            if self.enabled { "enabled" } else { "disabled" }
+        );
+        log::info!(target: "server", "{msg}");
+        Ok(msg)
+    }
+
+    /// Adds (`add` is `true`) or removes a player.
+    ///
+    /// Returns the feedback for the rcon command.
+    pub fn change_player(&mut self, player: &str, add: bool) -> anyhow::Result<String> {
+        let player_unique_id = Server::parse_player_unique_id(player).ok_or_else(|| {
+            anyhow::anyhow!("expected acc_<account id> or cert_<fingerprint>, got \"{player}\"")
+        })?;
+        let msg = if add {
+            self.players.insert(player_unique_id);
+            format!("{player} was added to the whitelist")
+        } else if self.players.remove(&player_unique_id) {
+            format!("{player} was removed from the whitelist")
+        } else {
+            anyhow::bail!("{player} is not whitelisted");
+        };
+        log::info!(target: "server", "{msg}");
+        Ok(msg)
+    }
+}
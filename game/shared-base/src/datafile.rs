@@ -2089,6 +2089,22 @@ impl CDatafileWrapper {
             }
         }
 
+        // tunes only apply inside of a tune layer, but maps that only
+        // use global tunes usually don't have one, so zone 0 covers the whole map
+        if !tune_zones.is_empty() && map.groups.physics.get_tune_layer().is_none() {
+            let attr = &map.groups.physics.attr;
+            let tile_count = attr.width.get() as usize * attr.height.get() as usize;
+            map.groups
+                .physics
+                .layers
+                .push(MapLayerPhysics::Tune(MapLayerTilePhysicsTune {
+                    base: MapLayerTilePhysicsBase {
+                        tiles: vec![TuneTile::default(); tile_count],
+                    },
+                    tune_zones,
+                }));
+        }
+
         Ok(map)
     }
 
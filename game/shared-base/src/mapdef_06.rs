@@ -69,6 +69,11 @@ pub enum EEntityTiles {
     WeaponGrenade,
     PowerupNinja,
     WeaponLaser,
+    /// A tile of a capture zone, connected tiles form one capture point.
+    ///
+    /// The first index after the DDNet entities (see the list below),
+    /// so maps of DDNet never contain it.
+    CapturePoint = 241,
 }
 /*
 //DDRace - Main Lasers
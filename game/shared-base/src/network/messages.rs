@@ -127,8 +127,12 @@ pub enum MsgClChatMsg {
     Global {
         msg: NetworkString<256>,
     },
+    /// To the own side, or the own stage if the game has no sides.
     GameTeam {
-        team: u32, // TODO
+        msg: NetworkString<256>,
+    },
+    /// To the own stage (a.k.a. ddrace team).
+    Stage {
         msg: NetworkString<256>,
     },
     Whisper {
@@ -1,13 +1,42 @@
-use game_interface::types::game::GameEntityId;
+use game_interface::types::{game::GameEntityId, render::scoreboard::ScoreboardPlacement};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum NetChatMsgPlayerChannel {
     Global,
     GameTeam,
+    Stage,
     Whisper(GameEntityId), // sender
 }
 
+impl NetChatMsgPlayerChannel {
+    /// Whether a message of a player at `sender` reaches a player at `receiver`.
+    /// `None` is a player that is not part of the game (yet).
+    ///
+    /// Spectators share their own team & stage.
+    /// Whispers only reach their receiver, which is not known by the placement.
+    pub fn reaches(
+        &self,
+        sender: Option<&ScoreboardPlacement>,
+        receiver: Option<&ScoreboardPlacement>,
+    ) -> bool {
+        match self {
+            Self::Global => true,
+            Self::GameTeam => sender.zip(receiver).is_some_and(|(sender, receiver)| {
+                if sender.side.is_some() {
+                    sender.side == receiver.side
+                } else {
+                    sender.stage_id == receiver.stage_id
+                }
+            }),
+            Self::Stage => sender
+                .zip(receiver)
+                .is_some_and(|(sender, receiver)| sender.stage_id == receiver.stage_id),
+            Self::Whisper(_) => false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NetChatMsg {
     pub player_id: GameEntityId,
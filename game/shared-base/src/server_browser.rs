@@ -1,12 +1,22 @@
+use base::hash::fmt_hash;
 use base::hash::Hash;
 use game_interface::types::character_info::NetworkSkinInfo;
 use game_interface::types::render::character::TeeEye;
 use game_interface::types::resource_key::NetworkResourceKey;
+use image::png::load_png_image;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_with::serde_as;
 use serde_with::DefaultOnError;
 
+/// The maximum file size of a server icon in bytes.
+pub const SERVER_ICON_MAX_SIZE: usize = 64 * 1024;
+/// The maximum width of a server icon in pixels,
+/// wide enough for banners.
+pub const SERVER_ICON_MAX_WIDTH: usize = 512;
+/// The maximum height of a server icon in pixels.
+pub const SERVER_ICON_MAX_HEIGHT: usize = 256;
+
 pub struct ServerBrowserFilter {
     pub search: String,
     pub exclude: String,
@@ -62,6 +72,22 @@ pub struct ServerBrowserInfoMap {
     pub size: usize,
 }
 
+/// An icon or banner the server serves over its resource server.
+#[serde_as]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct ServerBrowserInfoIcon {
+    #[serde(default)]
+    #[serde_as(deserialize_as = "DefaultOnError")]
+    pub blake3: Hash,
+    #[serde(default)]
+    #[serde_as(deserialize_as = "DefaultOnError")]
+    pub size: usize,
+    /// The port of the resource server.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "DefaultOnError")]
+    pub port: u16,
+}
+
 #[serde_as]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ServerBrowserInfo {
@@ -90,6 +116,46 @@ pub struct ServerBrowserInfo {
     #[serde(default)]
     #[serde_as(deserialize_as = "DefaultOnError")]
     pub cert_sha256_fingerprint: Hash,
+    #[serde(default)]
+    #[serde_as(deserialize_as = "DefaultOnError")]
+    pub icon: Option<ServerBrowserInfoIcon>,
+}
+
+/// The path of a server icon, both on the resource server
+/// and inside of the client's cache directory.
+pub fn server_icon_path(hash: &Hash) -> String {
+    format!("server_icons/{}.png", fmt_hash(hash))
+}
+
+/// Decodes a server icon, the image memory is only allocated
+/// with `alloc_mem`, if the icon is within the limits.
+///
+/// The icon is downloaded from servers that are not trusted,
+/// so this must be called before using it.
+pub fn load_server_icon<'a, T>(file: &Vec<u8>, alloc_mem: T) -> anyhow::Result<()>
+where
+    T: FnOnce(usize, usize) -> &'a mut [u8],
+{
+    anyhow::ensure!(
+        file.len() <= SERVER_ICON_MAX_SIZE,
+        "server icon is bigger than {SERVER_ICON_MAX_SIZE} bytes"
+    );
+    let mut too_large = false;
+    let res = load_png_image(file, |width, height, _| {
+        if width > SERVER_ICON_MAX_WIDTH || height > SERVER_ICON_MAX_HEIGHT {
+            // decoding fails without memory
+            too_large = true;
+            &mut []
+        } else {
+            alloc_mem(width, height)
+        }
+    });
+    anyhow::ensure!(
+        !too_large,
+        "server icon is bigger than {SERVER_ICON_MAX_WIDTH}x{SERVER_ICON_MAX_HEIGHT} pixels"
+    );
+    res?;
+    Ok(())
 }
 
 #[derive(Debug)]
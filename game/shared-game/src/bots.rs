@@ -0,0 +1,181 @@
+use std::rc::Rc;
+
+use game_interface::types::{
+    game::{GameEntityId, GameTickCooldown},
+    input::{CharacterInput, CharacterInputCursor},
+    render::game::game_match::MatchSide,
+};
+use hashlink::LinkedHashMap;
+use math::math::{
+    distance_squared,
+    vector::{dvec2, vec2},
+};
+
+use crate::{
+    entities::character::character::{Character, Characters},
+    state::state::TICKS_PER_SECOND,
+};
+
+/// Decides what the character of a bot does, a new behavior
+/// can be added with [`Bots::register_behavior`].
+pub trait BotBehavior {
+    /// Changes the input of the bot's character for the next tick.
+    /// `characters` are all characters of the bot's stage, including its own.
+    fn input(&mut self, character: &Character, characters: &Characters, input: &mut CharacterInput);
+}
+
+/// Stands still.
+#[derive(Debug, Default)]
+pub struct IdleBehavior;
+
+impl BotBehavior for IdleBehavior {
+    fn input(&mut self, _: &Character, _: &Characters, input: &mut CharacterInput) {
+        input.state.dir.set(0);
+        input.state.fire.set(false);
+        input.state.hook.set(false);
+        input.state.jump.set(false);
+    }
+}
+
+/// Walks into one direction, jumps when it gets stuck
+/// and turns around if that does not help.
+#[derive(Debug)]
+pub struct RoamBehavior {
+    dir: i32,
+    stuck_ticks: u64,
+}
+
+impl Default for RoamBehavior {
+    fn default() -> Self {
+        Self {
+            dir: 1,
+            stuck_ticks: 0,
+        }
+    }
+}
+
+impl BotBehavior for RoamBehavior {
+    fn input(&mut self, character: &Character, _: &Characters, input: &mut CharacterInput) {
+        if character.core.core.vel.x.abs() < 0.1 {
+            self.stuck_ticks += 1;
+        } else {
+            self.stuck_ticks = 0;
+        }
+
+        if self.stuck_ticks > TICKS_PER_SECOND {
+            self.dir = -self.dir;
+            self.stuck_ticks = 0;
+        }
+
+        input.state.dir.set(self.dir);
+        input
+            .state
+            .jump
+            .set(self.stuck_ticks > TICKS_PER_SECOND / 5);
+        input
+            .cursor
+            .set(CharacterInputCursor::from_vec2(&dvec2::new(
+                self.dir as f64,
+                0.0,
+            )));
+    }
+}
+
+/// Stands still and shoots at the nearest character in range
+/// that is not in the same team.
+#[derive(Debug, Default)]
+pub struct AimAtNearestBehavior;
+
+impl AimAtNearestBehavior {
+    /// In world units, 32 units are one tile.
+    const RANGE: f32 = 32.0 * 20.0;
+
+    fn nearest_target(character: &Character, characters: &Characters) -> Option<vec2> {
+        let pos = *character.pos.pos();
+        characters
+            .values()
+            .filter(|other| other.base.game_element_id != character.base.game_element_id)
+            .filter(|other| {
+                !matches!(
+                    (character.core.side, other.core.side),
+                    (Some(MatchSide::Red), Some(MatchSide::Red))
+                        | (Some(MatchSide::Blue), Some(MatchSide::Blue))
+                )
+            })
+            .map(|other| *other.pos.pos())
+            .filter(|other_pos| distance_squared(&pos, other_pos) <= Self::RANGE * Self::RANGE)
+            .min_by(|a, b| distance_squared(&pos, a).total_cmp(&distance_squared(&pos, b)))
+    }
+}
+
+impl BotBehavior for AimAtNearestBehavior {
+    fn input(
+        &mut self,
+        character: &Character,
+        characters: &Characters,
+        input: &mut CharacterInput,
+    ) {
+        input.state.dir.set(0);
+        input.state.jump.set(false);
+
+        let Some(target) = Self::nearest_target(character, characters) else {
+            input.state.fire.set(false);
+            return;
+        };
+        let dir = target - *character.pos.pos();
+        let cursor = CharacterInputCursor::from_vec2(&dvec2::new(dir.x as f64, dir.y as f64));
+        input.cursor.set(cursor);
+        input.state.fire.set(true);
+        // the character ignores the clicks while the weapon recoils
+        input.consumable.fire.add(1, cursor);
+    }
+}
+
+pub type BotBehaviorFactory = Rc<dyn Fn() -> Box<dyn BotBehavior>>;
+
+pub struct Bot {
+    pub behavior: Box<dyn BotBehavior>,
+    pub stage_id: GameEntityId,
+
+    /// The side & score of the last character, kept over respawns.
+    pub side: Option<MatchSide>,
+    pub score: i64,
+    /// Ticks until the character is spawned again after it died.
+    pub respawn_in_ticks: GameTickCooldown,
+}
+
+/// Server side characters without a network player.
+/// The game keeps their characters alive, see
+/// [`crate::state::state::GameState::add_bot`].
+pub struct Bots {
+    pub bots: LinkedHashMap<GameEntityId, Bot>,
+    behaviors: LinkedHashMap<String, BotBehaviorFactory>,
+}
+
+impl Default for Bots {
+    fn default() -> Self {
+        let mut bots = Self {
+            bots: Default::default(),
+            behaviors: Default::default(),
+        };
+        bots.register_behavior("idle", Rc::new(|| Box::new(IdleBehavior)));
+        bots.register_behavior("roam", Rc::new(|| Box::new(RoamBehavior::default())));
+        bots.register_behavior("aim", Rc::new(|| Box::new(AimAtNearestBehavior)));
+        bots
+    }
+}
+
+impl Bots {
+    /// Replaces a behavior with the same name.
+    pub fn register_behavior(&mut self, name: &str, factory: BotBehaviorFactory) {
+        self.behaviors.replace(name.to_string(), factory);
+    }
+
+    pub fn behavior(&self, name: &str) -> Option<Box<dyn BotBehavior>> {
+        self.behaviors.get(name).map(|factory| factory())
+    }
+
+    pub fn behavior_names(&self) -> impl Iterator<Item = &str> {
+        self.behaviors.keys().map(|name| name.as_str())
+    }
+}
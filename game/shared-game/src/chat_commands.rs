@@ -0,0 +1,78 @@
+use std::{ops::Range, rc::Rc};
+
+use command_parser::parser::{CommandArg, Syn};
+use game_interface::{chat_commands::ChatCommands, types::game::GameEntityId};
+use hashlink::LinkedHashMap;
+
+use crate::state::state::GameState;
+
+/// Executes the chat command for the player that used it.
+/// The args are already validated by the parser,
+/// see [`crate::rcon`] for helpers to read them.
+/// The returned text is the feedback for the player, if not empty.
+///
+/// `S` is the state the command changes, the game or e.g. the server
+/// for commands the game can't handle.
+pub type ChatCmdCb<S = GameState> =
+    Rc<dyn Fn(&mut S, &GameEntityId, &[(Syn, Range<usize>)]) -> anyhow::Result<String>>;
+
+pub struct ChatCmd<S = GameState> {
+    pub name: String,
+    pub usage: String,
+    pub description: String,
+    pub cmd: ChatCmdCb<S>,
+
+    /// for parsing
+    pub args: Vec<CommandArg>,
+}
+
+/// All chat commands the game supports,
+/// mods can add their own with [`ChatCmds::register`].
+pub struct ChatCmds<S = GameState> {
+    cmds: LinkedHashMap<String, ChatCmd<S>>,
+}
+
+impl<S> Default for ChatCmds<S> {
+    fn default() -> Self {
+        Self {
+            cmds: Default::default(),
+        }
+    }
+}
+
+impl<S> ChatCmds<S> {
+    /// Replaces a command with the same name.
+    pub fn register(&mut self, cmd: ChatCmd<S>) {
+        self.cmds.replace(cmd.name.clone(), cmd);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ChatCmd<S>> {
+        self.cmds.get(name)
+    }
+
+    /// The commands for the parser & the autocompletion of the clients.
+    pub fn chat_commands(&self, prefixes: Vec<char>) -> ChatCommands {
+        ChatCommands {
+            cmds: self
+                .cmds
+                .values()
+                .map(|cmd| (cmd.name.clone(), cmd.args.clone()))
+                .collect(),
+            prefixes,
+            descriptions: self
+                .cmds
+                .values()
+                .map(|cmd| (cmd.name.clone(), cmd.description.clone()))
+                .collect(),
+        }
+    }
+
+    /// The usage & description of all commands, with the given prefix.
+    pub fn help(&self, prefix: char) -> String {
+        self.cmds
+            .values()
+            .map(|cmd| format!("{prefix}{} - {}", cmd.usage, cmd.description))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -1,6 +1,12 @@
 pub mod collision {
+    use hashlink::LinkedHashMap;
     use hiarc::Hiarc;
-    use map::map::groups::layers::tiles::{TileBase, TuneTile};
+    use map::map::groups::layers::{
+        physics::MapLayerTilePhysicsTune,
+        tiles::{
+            rotation_180, rotation_270, TileBase, TileFlags, TuneTile, ROTATION_0, ROTATION_90,
+        },
+    };
     use serde::{Deserialize, Serialize};
     use shared_base::mapdef_06::DdraceTileNum;
 
@@ -39,6 +45,15 @@ pub mod collision {
         pub grenade_curvature: f32,
         pub grenade_speed: f32,
         pub grenade_lifetime: f32,
+        /// How often a projectile bounces off walls before it hits,
+        /// `0` means it hits the first wall.
+        pub gun_bounce_num: f32,
+        /// The fraction of the speed a projectile loses per bounce.
+        pub gun_bounce_loss: f32,
+        pub shotgun_bounce_num: f32,
+        pub shotgun_bounce_loss: f32,
+        pub grenade_bounce_num: f32,
+        pub grenade_bounce_loss: f32,
         pub laser_reach: f32,
         pub laser_bounce_delay: f32,
         pub laser_bounce_num: f32,
@@ -48,7 +63,11 @@ pub mod collision {
         pub player_hooking: f32,
         pub jetpack_strength: f32,
         pub shotgun_strength: f32,
+        /// The damage of explosions & their knockback on other characters.
         pub explosion_strength: f32,
+        /// The knockback of explosions on the character that caused them,
+        /// e.g. for grenade jumps.
+        pub explosion_self_strength: f32,
         pub hammer_strength: f32,
         pub hook_duration: f32,
         pub hammer_fire_delay: f32,
@@ -58,6 +77,17 @@ pub mod collision {
         pub laser_fire_delay: f32,
         pub ninja_fire_delay: f32,
         pub hammer_hit_fire_delay: f32,
+        /// Whether characters of other teams can be hooked.
+        pub hook_enemies: f32,
+        /// If enemies can not be hooked, whether the hook flies through them
+        /// instead of retracting.
+        pub hook_through_enemies: f32,
+        pub hook_drag_character_strength: f32,
+        pub hook_drag_wall_strength: f32,
+        /// Whether the hook flies through walls that have a
+        /// [`DdraceTileNum::Through`] tile on the side the hook comes from,
+        /// on the game or the front layer.
+        pub hook_through_tiles: f32,
     }
 
     impl Default for Tunings {
@@ -89,6 +119,12 @@ pub mod collision {
                 grenade_curvature: 7.0,
                 grenade_speed: 1000.0,
                 grenade_lifetime: 2.0,
+                gun_bounce_num: 0.0,
+                gun_bounce_loss: 0.3,
+                shotgun_bounce_num: 0.0,
+                shotgun_bounce_loss: 0.3,
+                grenade_bounce_num: 0.0,
+                grenade_bounce_loss: 0.3,
                 laser_reach: 800.0,
                 laser_bounce_delay: 150.0,
                 laser_bounce_num: 1000.0,
@@ -99,6 +135,7 @@ pub mod collision {
                 jetpack_strength: 400.0,
                 shotgun_strength: 10.0,
                 explosion_strength: 6.0,
+                explosion_self_strength: 6.0,
                 hammer_strength: 1.0,
                 hook_duration: 1.25,
                 hammer_fire_delay: 125.0,
@@ -108,6 +145,67 @@ pub mod collision {
                 laser_fire_delay: 800.0,
                 ninja_fire_delay: 800.0,
                 hammer_hit_fire_delay: 320.0,
+                hook_enemies: 1.0,
+                hook_through_enemies: 1.0,
+                hook_drag_character_strength: 1.0,
+                hook_drag_wall_strength: 1.0,
+                hook_through_tiles: 1.0,
+            }
+        }
+    }
+
+    /// Modifiers that a tune zone applies to the characters inside of it.
+    #[derive(Debug, Hiarc, Copy, Clone, Serialize, Deserialize, PartialEq)]
+    pub struct ZoneModifiers {
+        /// Scales the gravity, e.g. `0.5` for a low gravity zone.
+        pub gravity_scale: f32,
+        /// Scales the max ground & air speed, e.g. `2.0` for a speed boost zone.
+        pub speed_scale: f32,
+        /// Characters can not fire their hook inside of this zone.
+        pub no_hook: bool,
+    }
+
+    impl Default for ZoneModifiers {
+        fn default() -> Self {
+            Self {
+                gravity_scale: 1.0,
+                speed_scale: 1.0,
+                no_hook: false,
+            }
+        }
+    }
+
+    #[derive(Debug, Hiarc, Default, Copy, Clone)]
+    pub struct TuneZone {
+        pub tunings: Tunings,
+        pub modifiers: ZoneModifiers,
+    }
+
+    impl TuneZone {
+        /// Builds the zone from the tunes of a map's tune zone
+        /// (e.g. `gravity 0.25` or `no_hook 1`).
+        /// Unknown tunes and invalid values are ignored.
+        pub fn from_tunes(tunes: &LinkedHashMap<String, String>) -> Self {
+            let mut modifiers = ZoneModifiers::default();
+            let mut tunings = serde_json::to_value(Tunings::default()).unwrap_or_default();
+            for (name, val) in tunes {
+                let Ok(val) = val.trim().parse::<f32>() else {
+                    continue;
+                };
+                match name.as_str() {
+                    "gravity_scale" => modifiers.gravity_scale = val,
+                    "speed_scale" => modifiers.speed_scale = val,
+                    "no_hook" => modifiers.no_hook = val != 0.0,
+                    name => {
+                        if let Some(tune) = tunings.get_mut(name) {
+                            *tune = val.into();
+                        }
+                    }
+                }
+            }
+            Self {
+                tunings: serde_json::from_value(tunings).unwrap_or_default(),
+                modifiers,
             }
         }
     }
@@ -115,11 +213,12 @@ pub mod collision {
     #[derive(Default)]
     pub struct Collision {
         tiles: Vec<TileBase>,
+        front_tiles: Option<Vec<TileBase>>,
         tune_tiles: Vec<TuneTile>,
         width: u32,
         height: u32,
 
-        tune_zones: Vec<Tunings>,
+        tune_zones: Vec<TuneZone>,
     }
 
     // TODO: use u8 or an enum for tile indices, instead of i32
@@ -128,21 +227,26 @@ pub mod collision {
             width: u32,
             height: u32,
             tiles: &[TileBase],
-            tune_zones_and_tiles: Option<(Vec<Tunings>, &[TuneTile])>,
+            front_tiles: Option<&[TileBase]>,
+            tune_layer: Option<&MapLayerTilePhysicsTune>,
         ) -> Self {
-            let mut tune_zones = vec![Tunings::default()];
-            let tune_tiles: Vec<_> =
-                if let Some((tune_zone_list, tune_tiles)) = tune_zones_and_tiles {
-                    tune_zones = tune_zone_list.to_vec();
-                    tune_tiles.to_vec()
-                } else {
-                    vec![TuneTile::default(); tiles.len()]
-                };
+            let mut tune_zones = vec![TuneZone::default()];
+            let tune_tiles: Vec<_> = if let Some(tune_layer) = tune_layer {
+                // every tile number is a valid zone, zones without tunes use the defaults
+                tune_zones = vec![TuneZone::default(); u8::MAX as usize + 1];
+                for (&index, zone) in tune_layer.tune_zones.iter() {
+                    tune_zones[index as usize] = TuneZone::from_tunes(&zone.tunes);
+                }
+                tune_layer.base.tiles.to_vec()
+            } else {
+                vec![TuneTile::default(); tiles.len()]
+            };
 
             Self {
                 width,
                 height,
                 tiles: tiles.to_vec(),
+                front_tiles: front_tiles.map(|tiles| tiles.to_vec()),
                 tune_tiles,
                 tune_zones,
             }
@@ -231,6 +335,23 @@ pub mod collision {
             }
         }
 
+        /// Reflects `vel` at the wall that was hit while moving from `pos0` to `pos1`.
+        /// Only the axes that are blocked are reflected,
+        /// in a corner both are.
+        pub fn reflect_vel(&self, pos0: &vec2, pos1: &vec2, vel: &vec2) -> vec2 {
+            let blocked_x = self.check_pointf(pos1.x, pos0.y);
+            let blocked_y = self.check_pointf(pos0.x, pos1.y);
+            let (blocked_x, blocked_y) = if !blocked_x && !blocked_y {
+                (true, true)
+            } else {
+                (blocked_x, blocked_y)
+            };
+            vec2::new(
+                if blocked_x { -vel.x } else { vel.x },
+                if blocked_y { -vel.y } else { vel.y },
+            )
+        }
+
         pub fn move_box(
             &self,
             in_out_pos: &mut vec2,
@@ -368,33 +489,37 @@ pub mod collision {
 
         fn is_through(
             &self,
-            _x: i32,
-            _y: i32,
-            _xoff: i32,
-            _yoff: i32,
-            _pos0: &vec2,
-            _pos1: &vec2,
+            x: i32,
+            y: i32,
+            xoff: i32,
+            yoff: i32,
+            pos0: &vec2,
+            pos1: &vec2,
         ) -> bool {
-            /* TODO: let pos = self.GetPureMapIndex(x, y);
-            if (m_pFront
-                && (m_pFront[pos].m_Index == TILE_THROUGH_ALL
-                    || m_pFront[pos].m_Index == TILE_THROUGH_CUT))
-            {
-                return true;
-            }
-            if (m_pFront
-                && m_pFront[pos].m_Index == TILE_THROUGH_DIR
-                && ((m_pFront[pos].m_Flags == ROTATION_0 && pos0.y > pos1.y)
-                    || (m_pFront[pos].m_Flags == ROTATION_90 && pos0.x < pos1.x)
-                    || (m_pFront[pos].m_Flags == ROTATION_180 && pos0.y < pos1.y)
-                    || (m_pFront[pos].m_Flags == ROTATION_270 && pos0.x > pos1.x)))
-            {
-                return true;
+            let off_index = self.tile_index((x + xoff) as f32, (y + yoff) as f32);
+            if let Some(front_tiles) = &self.front_tiles {
+                let tile = &front_tiles[self.tile_index(x as f32, y as f32)];
+                if tile.index == DdraceTileNum::ThroughAll as u8
+                    || tile.index == DdraceTileNum::ThroughCut as u8
+                {
+                    return true;
+                }
+                // only the rotation of the tile gives the direction
+                let rotation =
+                    tile.flags & (TileFlags::XFLIP | TileFlags::YFLIP | TileFlags::ROTATE);
+                if tile.index == DdraceTileNum::ThroughDir as u8
+                    && ((rotation == ROTATION_0 && pos0.y > pos1.y)
+                        || (rotation == ROTATION_90 && pos0.x < pos1.x)
+                        || (rotation == rotation_180() && pos0.y < pos1.y)
+                        || (rotation == rotation_270() && pos0.x > pos1.x))
+                {
+                    return true;
+                }
+                if front_tiles[off_index].index == DdraceTileNum::Through as u8 {
+                    return true;
+                }
             }
-            let offpos = self.GetPureMapIndex(x + xoff, y + yoff);
-            return m_pTiles[offpos].m_Index == TILE_THROUGH
-                || (m_pFront && m_pFront[offpos].m_Index == TILE_THROUGH);*/
-            false
+            self.tiles[off_index].index == DdraceTileNum::Through as u8
         }
 
         fn get_collision_at(&self, x: f32, y: f32) -> i32 {
@@ -447,6 +572,7 @@ pub mod collision {
             let mut dx = 0;
             let mut dy = 0; // Offset for checking the "through" tile
             self.through_offset(pos0, pos1, &mut dx, &mut dy);
+            let hook_through = self.get_tune_at(pos0).hook_through_tiles > 0.0;
             for i in 0..=end {
                 let a = i as f32 / end as f32;
                 let pos = mix(pos0, pos1, a);
@@ -470,7 +596,7 @@ pub mod collision {
 
                 let mut hit = 0;
                 if self.check_pointf(ix as f32, iy as f32) {
-                    if !self.is_through(ix, iy, dx, dy, pos0, pos1) {
+                    if !(hook_through && self.is_through(ix, iy, dx, dy, pos0, pos1)) {
                         hit = self.get_collision_at(ix as f32, iy as f32);
                     }
                 } else if self.is_hook_blocker(ix, iy, pos0, pos1) {
@@ -564,9 +690,17 @@ pub mod collision {
             0
         }
 
-        pub fn get_tune_at(&self, pos: &vec2) -> &Tunings {
+        pub fn get_zone_at(&self, pos: &vec2) -> &TuneZone {
             let tune_tile = &self.tune_tiles[self.tile_index(pos.x, pos.y)];
             &self.tune_zones[tune_tile.number as usize]
         }
+
+        pub fn get_tune_at(&self, pos: &vec2) -> &Tunings {
+            &self.get_zone_at(pos).tunings
+        }
+
+        pub fn get_zone_modifiers_at(&self, pos: &vec2) -> &ZoneModifiers {
+            &self.get_zone_at(pos).modifiers
+        }
     }
 }
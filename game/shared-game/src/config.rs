@@ -1,6 +1,9 @@
 use config::{config_default, ConfigInterface};
+use game_interface::types::weapons::WeaponType;
 use serde::{Deserialize, Serialize};
 
+use crate::spawns::SpawnStrategy;
+
 #[derive(
     Debug,
     Default,
@@ -18,6 +21,50 @@ pub enum ConfigGameType {
     #[default]
     Dm,
     Ctf,
+    /// Team mode where hits freeze the opponents instead of killing them.
+    /// Teammates unfreeze them by standing next to them,
+    /// the round is won when the other team is completely frozen.
+    FreezeTag,
+    /// King of the hill: team mode where the sides capture the
+    /// capture point zones of the map, held points score over time.
+    Koth,
+}
+
+/// How the spawn point of a character is chosen.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, ConfigInterface, PartialEq, Eq)]
+pub enum ConfigSpawnStrategy {
+    /// The default of the game type:
+    /// team side spawns in team games, farthest from enemies otherwise.
+    #[default]
+    GameTypeDefault,
+    /// The spawn farthest away from all enemies.
+    FarthestFromEnemies,
+    /// Prefers the spawns of the own side,
+    /// away from enemies.
+    TeamSide,
+    /// A random spawn, spawns near recent deaths are less likely.
+    RandomWeightedByDeaths,
+}
+
+/// The weapons that can be disabled, the hammer is always available.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, ConfigInterface, PartialEq, Eq)]
+pub enum ConfigWeapon {
+    #[default]
+    Gun,
+    Shotgun,
+    Grenade,
+    Laser,
+}
+
+impl From<ConfigWeapon> for WeaponType {
+    fn from(value: ConfigWeapon) -> Self {
+        match value {
+            ConfigWeapon::Gun => WeaponType::Gun,
+            ConfigWeapon::Shotgun => WeaponType::Shotgun,
+            ConfigWeapon::Grenade => WeaponType::Grenade,
+            ConfigWeapon::Laser => WeaponType::Laser,
+        }
+    }
 }
 
 #[config_default]
@@ -27,4 +74,124 @@ pub struct ConfigVanilla {
     #[default = 100]
     pub score_limit: u64,
     pub allow_stages: bool,
+    /// How long (in seconds) a round lasts at most.
+    /// If no side or player leads by then, the round goes into
+    /// sudden death until someone does. 0 disables the time limit.
+    #[default = 0]
+    pub time_limit_secs: u64,
+    /// How long (in seconds) players can warm up,
+    /// before the first round starts. Scores don't count in the warmup.
+    /// 0 disables the warmup.
+    #[default = 0]
+    pub warmup_secs: u64,
+    /// Rules that new players have to accept (`/accept_rules`),
+    /// before their character spawns.
+    /// If empty, players join the game directly.
+    #[default = Vec::new()]
+    pub rules: Vec<String>,
+    /// How long (in seconds) a player that timed out can reconnect
+    /// and take back control of the character.
+    /// 0 drops the character immediately.
+    #[default = 120]
+    pub timeout_reclaim_secs: u64,
+    /// Kicks players whose character did not get any new input for
+    /// this long (in seconds). 0 disables it.
+    #[default = 0]
+    pub inactivity_kick_secs: u64,
+    /// How long (in seconds) before the inactivity kick
+    /// the player gets warned.
+    #[default = 30]
+    pub inactivity_warning_secs: u64,
+    /// Weapons that are disabled on this server (e.g. for a hammer-only mode).
+    /// Their pickups don't spawn and characters can not get them otherwise.
+    #[default = Vec::new()]
+    pub disabled_weapons: Vec<ConfigWeapon>,
+    /// Up to which health heart pickups can heal a character.
+    /// Everything above 10 is overheal, which decays over time.
+    #[conf_valid(range(min = 10, max = 100))]
+    #[default = 10]
+    pub overheal_max_health: u32,
+    /// Up to which armor shield pickups can give armor to a character.
+    /// Everything above 10 is overheal, which decays over time.
+    #[conf_valid(range(min = 10, max = 100))]
+    #[default = 10]
+    pub overheal_max_armor: u32,
+    /// Every how many seconds one point of overhealed
+    /// health & armor decays. 0 disables the decay.
+    #[default = 1]
+    pub overheal_decay_secs: u64,
+    /// Freeze tag: how long (in seconds) a teammate has to stand next
+    /// to a frozen character to unfreeze it.
+    #[conf_valid(range(min = 1, max = 60))]
+    #[default = 3]
+    pub freeze_tag_unfreeze_secs: u64,
+    /// King of the hill: how long (in seconds) a side has to stand
+    /// alone in a capture zone to capture it.
+    #[conf_valid(range(min = 1, max = 60))]
+    #[default = 5]
+    pub koth_capture_secs: u64,
+    /// King of the hill: every how many seconds a held capture point
+    /// gives its side a point.
+    #[conf_valid(range(min = 1, max = 60))]
+    #[default = 1]
+    pub koth_score_secs: u64,
+    /// Instagib modifier for all game types: characters only have a laser
+    /// that kills with one hit and no pickups spawn.
+    #[default = false]
+    pub instagib: bool,
+    /// How the spawn point of a character is chosen.
+    pub spawn_strategy: ConfigSpawnStrategy,
+    /// Team game types: every how many seconds the dead players
+    /// respawn together in one wave.
+    /// 0 respawns every player on their own.
+    #[default = 0]
+    pub spawn_wave_secs: u64,
+    /// How long (in seconds) the first character of a player that joined
+    /// a running round can not be damaged.
+    /// With spawn waves, such players also wait for the next wave.
+    /// 0 disables the protection.
+    #[conf_valid(range(min = 0, max = 10))]
+    #[default = 2]
+    pub late_join_protection_secs: u64,
+    /// The maximum size (in bytes) of the snapshot of a single client.
+    /// If exceeded, low priority data like the ping of other players
+    /// or the skins of far away characters are dropped.
+    /// 0 disables the budget.
+    #[default = 0]
+    pub snapshot_budget_bytes: u64,
+    /// How long (in seconds) players can vote,
+    /// see [`game_interface::client_commands::ClientCommand::StartVote`].
+    #[conf_valid(range(min = 5, max = 120))]
+    #[default = 25]
+    pub vote_secs: u64,
+    /// A vote passes, if more than this percentage of the players voted yes.
+    /// If the time is up, only the players that voted count.
+    #[conf_valid(range(min = 0, max = 99))]
+    #[default = 50]
+    pub vote_pass_percentage: u64,
+    /// Whether spectators can start votes & vote.
+    pub spectators_can_vote: bool,
+}
+
+impl ConfigVanilla {
+    /// The spawn strategy, resolved for the game type.
+    pub fn spawn_strategy(&self) -> SpawnStrategy {
+        match self.spawn_strategy {
+            ConfigSpawnStrategy::GameTypeDefault => match self.game_type {
+                ConfigGameType::Dm => SpawnStrategy::FarthestFromEnemies,
+                ConfigGameType::Ctf | ConfigGameType::FreezeTag | ConfigGameType::Koth => {
+                    SpawnStrategy::TeamSide
+                }
+            },
+            ConfigSpawnStrategy::FarthestFromEnemies => SpawnStrategy::FarthestFromEnemies,
+            ConfigSpawnStrategy::TeamSide => SpawnStrategy::TeamSide,
+            ConfigSpawnStrategy::RandomWeightedByDeaths => SpawnStrategy::RandomWeightedByDeaths,
+        }
+    }
+
+    pub fn is_weapon_disabled(&self, weapon: WeaponType) -> bool {
+        self.disabled_weapons
+            .iter()
+            .any(|&disabled| WeaponType::from(disabled) == weapon)
+    }
 }
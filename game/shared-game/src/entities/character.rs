@@ -10,7 +10,7 @@ pub mod character {
     use game_interface::{
         events::{
             GameBuffEvent, GameBuffNinjaEvent, GameBuffNinjaEventSound, GameCharacterEventEffect,
-            GameCharacterEventSound, GameWorldActionKillWeapon,
+            GameCharacterEventSound, GameWorldActionKillWeapon, ShotId,
         },
         types::{
             emoticons::{EmoticonType, EnumCount},
@@ -22,6 +22,7 @@ pub mod character {
             render::{
                 character::{CharacterBuff, CharacterDebuff, TeeEye},
                 game::game_match::MatchSide,
+                mod_payload::ModRenderPayload,
             },
             weapons::WeaponType,
         },
@@ -43,6 +44,7 @@ pub mod character {
             SimulationEntityEvents, SimulationEventWorldEntityType, SimulationPipeCharacter,
         },
         state::state::TICKS_PER_SECOND,
+        types::types::GameOptionsOverheal,
         weapons::definitions::weapon_def::Weapon,
     };
 
@@ -58,6 +60,10 @@ pub mod character {
     use super::player::player::Player;
 
     pub const TICKS_UNTIL_RECOIL_ENDED: GameTickType = 7;
+    /// The max health without overheal.
+    pub const MAX_HEALTH: u32 = 10;
+    /// The max armor without overheal.
+    pub const MAX_ARMOR: u32 = 10;
 
     pub enum DamageTypes<'a> {
         Character(&'a GameEntityId),
@@ -86,8 +92,12 @@ pub mod character {
         pub queued_weapon: Option<WeaponType>,
         pub health: u32,
         pub armor: u32,
+        /// Cooldown until the next point of overheal decays.
+        pub overheal_decay: GameTickCooldown,
         pub attack_recoil: GameTickCooldownAndLastActionCounter,
         pub no_ammo_sound: GameTickCooldown,
+        /// The id of the last shot fired by this character.
+        pub last_shot_id: ShotId,
 
         pub emoticon_tick: GameTickCooldownAndLastActionCounter,
         pub cur_emoticon: Option<EmoticonType>,
@@ -95,6 +105,16 @@ pub mod character {
         pub score: i64,
         pub side: Option<MatchSide>,
 
+        /// Freeze tag: hits that would kill the character freeze it instead.
+        pub freeze_tag: bool,
+        /// Freeze tag: how many ticks teammates stood next to
+        /// the frozen character.
+        pub unfreeze_ticks: GameTickType,
+
+        /// The character can not be damaged while active,
+        /// e.g. after joining a running round.
+        pub spawn_protection: GameTickCooldown,
+
         pub eye: TeeEye,
         pub normal_eye_in: GameTickCooldown,
 
@@ -105,6 +125,10 @@ pub mod character {
         /// is timeout e.g. by a network disconnect.
         /// this is a hint, not a logic variable.
         pub is_timeout: bool,
+
+        /// Custom data of mods for their render mod, vanilla only
+        /// passes it to the render info.
+        pub mod_payload: ModRenderPayload,
     }
 
     #[derive(Debug, Hiarc, Serialize, Deserialize, Clone)]
@@ -160,6 +184,8 @@ pub mod character {
     pub enum CharacterDamageResult {
         None,
         Damage,
+        /// The character would have died, but was frozen (freeze tag).
+        Frozen,
         Death,
     }
 
@@ -213,7 +239,7 @@ pub mod character {
         ) -> Self {
             let core = CharacterCore {
                 side,
-                health: 10,
+                health: MAX_HEALTH,
                 armor: 0,
                 input: player_input,
                 ..Default::default()
@@ -402,6 +428,36 @@ pub mod character {
                 .advance_ticks_passed_to_cooldown_len();
         }
 
+        pub fn is_frozen(&self) -> bool {
+            self.reusable_core
+                .debuffs
+                .contains_key(&CharacterDebuff::Freeze)
+        }
+
+        /// Freezes the character until it is unfrozen again,
+        /// it can neither move nor fire while frozen.
+        pub fn freeze(&mut self) {
+            self.core.health = MAX_HEALTH;
+            self.core.unfreeze_ticks = 0;
+            self.core.core.queued_jumps = 0;
+            self.reusable_core.debuffs.insert(
+                CharacterDebuff::Freeze,
+                BuffProps {
+                    remaining_tick: 0.into(),
+                    interact_tick: 0.into(),
+                    interact_cursor_dir: vec2::default(),
+                    interact_val: 0.0,
+                },
+            );
+        }
+
+        pub fn unfreeze(&mut self) {
+            self.core.unfreeze_ticks = 0;
+            self.reusable_core.debuffs.remove(&CharacterDebuff::Freeze);
+            self.core.eye = TeeEye::Happy;
+            self.core.normal_eye_in = (TICKS_PER_SECOND / 2).into();
+        }
+
         fn set_weapon(&mut self, new_weapon: WeaponType) {
             if self.core.active_weapon == new_weapon {
                 return;
@@ -497,8 +553,16 @@ pub mod character {
             from: DamageTypes,
             by: DamageBy,
         ) -> CharacterDamageResult {
+            let is_frozen = self_char.is_frozen();
             let core = &mut self_char.core;
             core.core.vel += *force;
+            // frozen characters in freeze tag can only be pushed around
+            if core.freeze_tag && is_frozen {
+                return CharacterDamageResult::None;
+            }
+            if core.spawn_protection.is_some() {
+                return CharacterDamageResult::None;
+            }
             let _old_health = core.health;
             let _old_armor = core.armor;
             if dmg_amount > 0 {
@@ -536,6 +600,11 @@ pub mod character {
                 }
             }
 
+            if core.health == 0 && core.freeze_tag {
+                self_char.freeze();
+                return CharacterDamageResult::Frozen;
+            }
+
             // check for death
             if core.health == 0 {
                 self_char.die(
@@ -590,6 +659,18 @@ pub mod character {
                     if Self::is_friendly_fire(characters, self_char_id, &from_id) {
                         return CharacterDamageResult::None;
                     }
+                    // in freeze tag only opponents can be frozen
+                    if let (Some(self_char), Some(from_char)) =
+                        (characters.get(self_char_id), characters.get(&from_id))
+                    {
+                        if self_char.core.freeze_tag
+                            && from_id != *self_char_id
+                            && self_char.core.side.is_some()
+                            && self_char.core.side == from_char.core.side
+                        {
+                            return CharacterDamageResult::None;
+                        }
+                    }
 
                     // m_pPlayer only inflicts half damage on self
                     if from_id == *self_char_id {
@@ -616,9 +697,14 @@ pub mod character {
                 from,
                 by,
             );
-            if let (CharacterDamageResult::Death, Some(killer)) =
+            if let (CharacterDamageResult::Death | CharacterDamageResult::Frozen, Some(killer)) =
                 (&res, killer_id.and_then(|id| characters.get_mut(&id)))
             {
+                if res == CharacterDamageResult::Frozen
+                    && killer.base.game_element_id != *self_char_id
+                {
+                    killer.core.score += 1;
+                }
                 killer.core.eye = TeeEye::Happy;
                 killer.core.normal_eye_in = (TICKS_PER_SECOND / 2).into();
             }
@@ -700,6 +786,13 @@ pub mod character {
             let cur_weapon = cur_weapon.unwrap();
             let proj_start_pos = *self.pos.pos() + direction * PHYSICAL_SIZE * 0.75;
 
+            core.last_shot_id += 1;
+            let shot_id = core.last_shot_id;
+            self.entity_events.push(CharacterEvent::ShotFired {
+                shot_id,
+                weapon: core.active_weapon,
+            });
+
             // TODO: check all branches. make sure no code/TODO comments are in, before removing this comment
 
             core.attack_recoil = match core.active_weapon {
@@ -716,6 +809,13 @@ pub mod character {
                         &proj_start_pos,
                         PHYSICAL_SIZE * 0.5,
                         &mut |char, removed_characters| {
+                            // in freeze tag only opponents can be frozen
+                            if char.core.freeze_tag
+                                && char.core.side.is_some()
+                                && char.core.side == core.side
+                            {
+                                return;
+                            }
                             if pipe.collision.intersect_line(
                                 &proj_start_pos,
                                 char.pos.pos(),
@@ -746,7 +846,7 @@ pub mod character {
                             };
 
                             let char_id = char.base.game_element_id;
-                            if Self::take_damage_from(
+                            let res = Self::take_damage_from(
                                 char,
                                 &char_id,
                                 Some(self.base.game_element_id),
@@ -756,10 +856,24 @@ pub mod character {
                                 3,
                                 DamageTypes::Character(&self.base.game_element_id),
                                 DamageBy::Weapon(WeaponType::Hammer),
-                            ) == CharacterDamageResult::Death
-                            {
+                            );
+                            if res != CharacterDamageResult::None {
+                                self.entity_events.push(CharacterEvent::ShotHit {
+                                    shot_id,
+                                    weapon: WeaponType::Hammer,
+                                    victim: char_id,
+                                });
+                            }
+                            if res == CharacterDamageResult::Death {
                                 removed_characters.insert(char.base.game_element_id);
-
+                            }
+                            if res == CharacterDamageResult::Frozen {
+                                core.score += 1;
+                            }
+                            if matches!(
+                                res,
+                                CharacterDamageResult::Death | CharacterDamageResult::Frozen
+                            ) {
                                 core.eye = TeeEye::Happy;
                                 core.normal_eye_in = (TICKS_PER_SECOND / 2).into();
                             }
@@ -784,6 +898,7 @@ pub mod character {
                         dir: direction,
                         ty: WeaponType::Gun,
                         lifetime: tunings.gun_lifetime,
+                        shot_id,
                     });
                     self.entity_events.push(CharacterEvent::Sound {
                         pos: *self.pos.pos() / 32.0,
@@ -815,6 +930,7 @@ pub mod character {
                             dir: vec2::new(a.cos(), a.sin()) * speed,
                             ty: WeaponType::Shotgun,
                             lifetime: tunings.shotgun_lifetime,
+                            shot_id,
                         });
                         /* TODO: new CProjectile(GameWorld(), WEAPON_SHOTGUN,
                         m_pPlayer->GetCID(),
@@ -842,6 +958,7 @@ pub mod character {
                         dir: direction,
                         ty: WeaponType::Grenade,
                         lifetime: tunings.grenade_lifetime,
+                        shot_id,
                     });
                     self.entity_events.push(CharacterEvent::Sound {
                         pos: *self.pos.pos() / 32.0,
@@ -862,6 +979,7 @@ pub mod character {
                         pos: *self.pos.pos(),
                         dir: direction,
                         energy: pipe.collision.get_tune_at(self.pos.pos()).laser_reach,
+                        shot_id,
                     });
                     self.entity_events.push(CharacterEvent::Sound {
                         pos: *self.pos.pos() / 32.0,
@@ -1120,6 +1238,9 @@ pub mod character {
             pipe: &mut SimulationPipeCharacter,
             diff: CharacterInputConsumableDiff,
         ) -> EntityTickResult {
+            if self.is_frozen() {
+                return EntityTickResult::None;
+            }
             self.core.core.queued_jumps = self
                 .core
                 .core
@@ -1140,10 +1261,33 @@ pub mod character {
             EntityTickResult::None
         }
 
+        /// Health & armor above [`MAX_HEALTH`] & [`MAX_ARMOR`] (overheal)
+        /// decays by one point per decay interval.
+        pub fn handle_overheal_decay(&mut self, overheal: &GameOptionsOverheal) {
+            let is_overhealed =
+                |core: &CharacterCore| core.health > MAX_HEALTH || core.armor > MAX_ARMOR;
+            if overheal.decay_ticks == 0 || !is_overhealed(&self.core) {
+                self.core.overheal_decay = Default::default();
+                return;
+            }
+            if self.core.overheal_decay.tick().unwrap_or_default() {
+                if self.core.health > MAX_HEALTH {
+                    self.core.health -= 1;
+                }
+                if self.core.armor > MAX_ARMOR {
+                    self.core.armor -= 1;
+                }
+            }
+            if is_overhealed(&self.core) && self.core.overheal_decay.is_none() {
+                self.core.overheal_decay = overheal.decay_ticks.into();
+            }
+        }
+
         fn handle_ticks(&mut self) {
             self.core.attack_recoil.tick();
             self.core.no_ammo_sound.tick();
             self.core.emoticon_tick.tick();
+            self.core.spawn_protection.tick();
         }
     }
 
@@ -1163,9 +1307,17 @@ pub mod character {
 
             self.handle_weapon_switch(None, None);
 
+            // frozen characters don't move on their own
+            let frozen_input = CharacterInput::default();
             let mut core_pipe = CorePipe {
                 characters: pipe.characters,
-                input: &self.core.input,
+                input: if self.is_frozen() {
+                    &frozen_input
+                } else {
+                    &self.core.input
+                },
+                id: self.base.game_element_id,
+                side: self.core.side,
             };
             self.core.core.physics_tick(
                 &mut self.pos,
@@ -1192,6 +1344,8 @@ pub mod character {
             let mut core_pipe = CorePipe {
                 characters: pipe.characters,
                 input: &self.core.input,
+                id: self.base.game_element_id,
+                side: self.core.side,
             };
             self.core
                 .core
@@ -1297,7 +1451,7 @@ pub mod character {
                                     died_at_pos: *self.pos.pos(),
                                 }
                             } else {
-                                NoCharPlayerType::Spectator
+                                NoCharPlayerType::Spectator { follow: None }
                             },
                             *network_stats,
                         ),
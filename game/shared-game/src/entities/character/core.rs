@@ -6,6 +6,7 @@ pub mod character_core {
         types::{
             game::GameEntityId,
             input::{CharacterInput, CharacterInputState},
+            render::game::game_match::MatchSide,
         },
     };
     use hiarc::Hiarc;
@@ -16,7 +17,7 @@ pub mod character_core {
     };
 
     use crate::{
-        collision::collision::Collision,
+        collision::collision::{Collision, ZoneModifiers},
         entities::character::{
             hook::character_hook::{CharacterHook, Hook, HookState},
             pos::character_pos::CharacterPos,
@@ -137,6 +138,11 @@ pub mod character_core {
         _live_frozen: bool,
 
         move_restrictions: i32,
+
+        /// The modifiers of the zone the character is in.
+        /// Updated from the position every physics tick,
+        /// so prediction always agrees with the server.
+        pub zone: ZoneModifiers,
     }
 
     impl Core {
@@ -183,6 +189,10 @@ pub mod character_core {
     pub struct CorePipe<'a> {
         pub(crate) characters: &'a mut dyn SimulationPipeCharactersGetter,
         pub(crate) input: &'a CharacterInput,
+        /// The id of the character that owns this core
+        pub(crate) id: GameEntityId,
+        /// The side of the character that owns this core
+        pub(crate) side: Option<MatchSide>,
     }
 
     impl<'a> CorePipe<'a> {
@@ -203,6 +213,21 @@ pub mod character_core {
         fn get_other_character_pos_by_id(&self, other_char_id: &GameEntityId) -> &vec2 {
             self.characters.get_other_character_pos_by_id(other_char_id)
         }
+
+        /// The position of the other character, if it is an enemy.
+        /// Characters without a side (e.g. solo play) are always enemies.
+        fn get_enemy_character_pos_by_id(&self, other_char_id: &GameEntityId) -> Option<&vec2> {
+            if *other_char_id == self.id {
+                return None;
+            }
+            let (pos, other_side) = self
+                .characters
+                .get_other_character_pos_and_side_by_id(other_char_id)?;
+            match (self.side, other_side) {
+                (Some(side), Some(other_side)) if side == other_side => None,
+                _ => Some(pos),
+            }
+        }
     }
 
     enum CannotMove {
@@ -269,9 +294,9 @@ pub mod character_core {
             }
         }
 
-        fn get_gravity(collision: &Collision, pos: &vec2) -> f32 {
+        fn get_gravity(&self, collision: &Collision, pos: &vec2) -> f32 {
             let tuning = collision.get_tune_at(pos);
-            tuning.gravity
+            tuning.gravity * self.zone.gravity_scale
         }
 
         pub fn physics_tick(
@@ -306,14 +331,16 @@ pub mod character_core {
             let cursor = vec2::new(cursor_vec.x as f32, cursor_vec.y as f32);
             let target_direction: vec2 = normalize(&cursor);
 
-            self.vel.y += Self::get_gravity(collision, pos.pos());
+            self.zone = *collision.get_zone_modifiers_at(pos.pos());
+
+            self.vel.y += self.get_gravity(collision, pos.pos());
 
             let tuning = collision.get_tune_at(pos.pos());
             let max_speed = if grounded {
                 tuning.ground_control_speed
             } else {
                 tuning.air_control_speed
-            };
+            } * self.zone.speed_scale;
             let accel = if grounded {
                 tuning.ground_control_accel
             } else {
@@ -381,7 +408,7 @@ pub mod character_core {
                 if self.queued_hooks.clicked > 0 || **hook {
                     if let (Hook::None, Some(_)) = (
                         char_hook.hook(),
-                        (self.queued_hooks.clicked > 0).then_some(()),
+                        (self.queued_hooks.clicked > 0 && !self.zone.no_hook).then_some(()),
                     ) {
                         let cursor = self.queued_hooks.cursor;
                         let cursor = vec2::new(cursor.x as f32, cursor.y as f32);
@@ -491,9 +518,33 @@ pub mod character_core {
                         let mut distance_hook = 0.0;
                         let (is_super, solo) = (self.is_super, self.solo);
                         let hook_len = length(&(new_pos - *hook_pos));
-                        let ids = pos
+                        let mut ids = pos
                             .field
                             .by_radiusf(hook_pos, hook_len + (physical_size() + 2.0));
+
+                        // enemies that are not allowed to be hooked are either
+                        // ignored by the hook or make it retract
+                        if tuning.hook_enemies <= 0.0 {
+                            let mut hit_enemy = false;
+                            ids.retain(|id| {
+                                let Some(char_pos) = pipe.get_enemy_character_pos_by_id(id) else {
+                                    return true;
+                                };
+                                let mut closest_point: vec2 = vec2::default();
+                                hit_enemy |= closest_point_on_line(
+                                    hook_pos,
+                                    &new_pos,
+                                    char_pos,
+                                    &mut closest_point,
+                                ) && distance_squared(char_pos, &closest_point)
+                                    < (physical_size() + 2.0).powf(2.0);
+                                false
+                            });
+                            if hit_enemy && tuning.hook_through_enemies <= 0.0 {
+                                going_to_hit_ground = false;
+                                going_to_retract = true;
+                            }
+                        }
                         pipe.get_other_character_id_and_cores_iter_by_ids_mut(
                             &ids,
                             &mut |char_id, char_core, _, char_pos| {
@@ -579,8 +630,9 @@ pub mod character_core {
                     if hooked_char.is_none()
                         && distance_squared(&*hook_pos, pos.pos()) > 46.0 * 46.0
                     {
-                        let mut hook_vel =
-                            normalize(&(*hook_pos - *pos.pos())) * hook_tunings.hook_drag_accel;
+                        let mut hook_vel = normalize(&(*hook_pos - *pos.pos()))
+                            * hook_tunings.hook_drag_accel
+                            * hook_tunings.hook_drag_wall_strength;
                         // the hook as more power to drag you up then down.
                         // this makes it easier to get on top of an platform
                         if hook_vel.y > 0.0 {
@@ -697,6 +749,7 @@ pub mod character_core {
                                 let dist = distance_sqr_pos.sqrt();
                                 if dist > physical_size() * 1.50 {
                                     let hook_accel = other_tunings.hook_drag_accel
+                                        * other_tunings.hook_drag_character_strength
                                         * (dist / other_tunings.hook_length);
                                     let drag_speed = other_tunings.hook_drag_speed;
 
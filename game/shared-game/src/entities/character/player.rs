@@ -1,7 +1,7 @@
 pub mod player {
     use std::marker::PhantomData;
 
-    use game_interface::types::character_info::NetworkCharacterInfo;
+    use game_interface::types::character_info::{CharacterInfoRedaction, NetworkCharacterInfo};
     use game_interface::types::game::{GameEntityId, GameTickCooldown};
     use game_interface::types::input::CharacterInput;
     use game_interface::types::network_stats::PlayerNetworkStats;
@@ -39,6 +39,30 @@ pub mod player {
         pub is_dummy: bool,
     }
 
+    impl PlayerInfo {
+        /// Removes the personal information, see
+        /// [`NetworkCharacterInfo::redacted`].
+        pub fn redact(&mut self, id: &GameEntityId, redaction: &CharacterInfoRedaction) {
+            self.player_info =
+                PoolRc::from_item_without_pool(self.player_info.redacted(id, redaction));
+            self.unique_identifier = PlayerUniqueId::CertFingerprint(Default::default());
+        }
+
+        /// Used for characters that are new to the client, but whose info
+        /// was dropped from the snapshot, until the info arrives.
+        pub fn unknown() -> Self {
+            Self {
+                player_info: PoolRc::from_item_without_pool(
+                    NetworkCharacterInfo::explicit_default(),
+                ),
+                version: 0,
+                unique_identifier: PlayerUniqueId::CertFingerprint(Default::default()),
+                player_index: 0,
+                is_dummy: false,
+            }
+        }
+    }
+
     pub type Player = PlayerCharacterInfo;
 
     /// A slim wrapper around the character info around the player.
@@ -78,6 +102,11 @@ pub mod player {
         pub(in super::super::super::character) fn remove(&mut self, id: &GameEntityId) {
             self.players.remove(id);
         }
+        pub(crate) fn set_network_stats(&mut self, id: &GameEntityId, stats: PlayerNetworkStats) {
+            if let Some(player) = self.players.get_mut(id) {
+                player.network_stats = stats;
+            }
+        }
         pub(crate) fn move_to_back(&mut self, id: &GameEntityId) {
             self.players.to_back(id);
         }
@@ -95,7 +124,11 @@ pub mod player {
 
     #[derive(Debug, Hiarc, Copy, Clone, Serialize, Deserialize)]
     pub enum NoCharPlayerType {
-        Spectator,
+        Spectator {
+            /// The player whose character the spectator follows,
+            /// see [`game_interface::client_commands::ClientCommand::SpectateTarget`].
+            follow: Option<GameEntityId>,
+        },
         Dead {
             respawn_in_ticks: GameTickCooldown,
             side: Option<MatchSide>,
@@ -98,6 +98,23 @@ pub mod flag {
             lerp(&flag1.core.pos, &flag2.core.pos, ratio as f32)
         }
 
+        /// Drops the flag at its current position, if it is carried.
+        pub fn drop_from_carrier(&mut self) {
+            if let Some(carrier) = self.core.carrier.take() {
+                self.simulation_events.push(
+                    Some(carrier),
+                    SimulationEventWorldEntityType::Flag {
+                        id: self.base.game_element_id,
+                        ev: FlagEvent::Sound {
+                            pos: self.core.pos / 32.0,
+                            ev: GameFlagEventSound::Drop,
+                        },
+                    },
+                );
+                self.core.drop_ticks = Some(TICKS_PER_SECOND * 30);
+            }
+        }
+
         pub fn reset(&mut self, is_prediction: bool) {
             // prediction cannot move the flag so much, since that lerps weirdly.
             if !is_prediction {
@@ -124,18 +141,7 @@ pub mod flag {
                 if let Some(character) = pipe.characters.get(&carrier) {
                     self.core.pos = *character.pos.pos();
                 } else {
-                    self.simulation_events.push(
-                        Some(carrier),
-                        SimulationEventWorldEntityType::Flag {
-                            id: self.base.game_element_id,
-                            ev: FlagEvent::Sound {
-                                pos: self.core.pos / 32.0,
-                                ev: GameFlagEventSound::Drop,
-                            },
-                        },
-                    );
-                    self.core.drop_ticks = Some(TICKS_PER_SECOND * 30);
-                    self.core.carrier = None;
+                    self.drop_from_carrier();
                 }
             } else {
                 if pipe.collision.is_death(self.core.pos.x, self.core.pos.y)
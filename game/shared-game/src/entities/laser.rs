@@ -1,5 +1,5 @@
 pub mod laser {
-    use game_interface::events::GameLaserEventSound;
+    use game_interface::events::{GameLaserEventSound, ShotId};
     use game_interface::types::game::{
         GameEntityId, GameTickCooldownAndLastActionCounter, GameTickType, NonZeroGameTickType,
     };
@@ -21,6 +21,7 @@ pub mod laser {
     };
     use crate::entities::entity::entity::{Entity, EntityInterface, EntityTickResult};
     use crate::events::events::LaserEvent;
+    use crate::instagib::instagib;
     use crate::simulation_pipe::simulation_pipe::{
         SimulationEntityEvents, SimulationEventWorldEntityType, SimulationPipeLaser,
     };
@@ -55,6 +56,8 @@ pub mod laser {
 
         pub energy: f32,
         pub bounces: usize,
+        /// The shot that spawned this laser.
+        pub shot_id: ShotId,
         pub next_eval_in: GameTickCooldownAndLastActionCounter,
         // TODO: int m_Owner;
         // TODO: int m_TeamMask;
@@ -85,6 +88,7 @@ pub mod laser {
             pos: &vec2,
             dir: &vec2,
             start_energy: f32,
+            shot_id: ShotId,
 
             can_hit_others: bool,
             can_hit_own: bool,
@@ -99,6 +103,7 @@ pub mod laser {
                 bounces: 0,
                 dir: *dir,
                 energy: start_energy,
+                shot_id,
                 next_eval_in: Default::default(),
 
                 can_hit_others,
@@ -244,18 +249,35 @@ pub mod laser {
                     pHit->Core()->m_Vel = ClampVel(pHit->m_MoveRestrictions, pHit->Core()->m_Vel);
                 }*/
             } else if let LaserType::Rifle = self.core.ty {
-                let dmg_amount = pipe.collision.get_tune_at(&self.core.pos).laser_damage;
+                let dmg_amount = if pipe.instagib {
+                    instagib::LASER_DAMAGE
+                } else {
+                    pipe.collision.get_tune_at(&self.core.pos).laser_damage as u32
+                };
                 let hitted_char_id = char.base.game_element_id;
-                if Character::take_damage(
+                let res = Character::take_damage(
                     pipe.characters_helper.characters,
                     &hitted_char_id,
                     &Default::default(),
                     &Default::default(),
-                    dmg_amount as u32,
+                    dmg_amount,
                     DamageTypes::Character(&pipe.characters_helper.owner_character),
                     DamageBy::Weapon(WeaponType::Laser),
-                ) == CharacterDamageResult::Death
-                {
+                );
+                let owner = pipe.characters_helper.owner_character;
+                if res != CharacterDamageResult::None && hitted_char_id != owner {
+                    self.simulation_events.push(
+                        Some(owner),
+                        SimulationEventWorldEntityType::Laser {
+                            id: self.base.game_element_id,
+                            ev: LaserEvent::Hit {
+                                shot_id: self.core.shot_id,
+                                victim: hitted_char_id,
+                            },
+                        },
+                    );
+                }
+                if res == CharacterDamageResult::Death {
                     pipe.characters_helper.characters.remove(&hitted_char_id);
                 }
             }
@@ -1,5 +1,7 @@
 pub mod pickup {
-    use game_interface::types::{game::GameEntityId, pickup::PickupType};
+    use game_interface::types::{
+        game::GameEntityId, pickup::PickupType, render::mod_payload::ModRenderPayload,
+    };
     use hashlink::LinkedHashMap;
     use hiarc::Hiarc;
     use math::math::{lerp, vector::vec2};
@@ -43,6 +45,10 @@ pub mod pickup {
     pub struct PickupCore {
         pub pos: vec2,
         pub ty: PickupType,
+
+        /// Custom data of mods for their render mod, vanilla only
+        /// passes it to the render info.
+        pub mod_payload: ModRenderPayload,
     }
 
     #[derive(Debug, Hiarc)]
@@ -64,7 +70,11 @@ pub mod pickup {
         ) -> Self {
             Self {
                 base: Entity::new(game_el_id),
-                core: PickupCore { pos: *pos, ty },
+                core: PickupCore {
+                    pos: *pos,
+                    ty,
+                    mod_payload: Default::default(),
+                },
                 reusable_core: pool.pickup_reusable_cores_pool.new(),
 
                 simulation_events: simulation_events.clone(),
@@ -94,7 +104,7 @@ pub mod pickup {
                 // TODO: magic constants
                 match self.core.ty {
                     PickupType::PowerupHealth => {
-                        if char.core.health < 10 {
+                        if char.core.health < pipe.overheal.max_health {
                             char.core.health += 1;
                             self.simulation_events.push(
                                 Some(char.base.game_element_id),
@@ -112,7 +122,7 @@ pub mod pickup {
                         }
                     }
                     PickupType::PowerupArmor => {
-                        if char.core.armor < 10 {
+                        if char.core.armor < pipe.overheal.max_armor {
                             char.core.armor += 1;
                             self.simulation_events.push(
                                 Some(char.base.game_element_id),
@@ -1,6 +1,7 @@
 pub mod projectile {
-    use game_interface::events::{GameGrenadeEventEffect, GameGrenadeEventSound};
+    use game_interface::events::{GameGrenadeEventEffect, GameGrenadeEventSound, ShotId};
     use game_interface::types::game::GameEntityId;
+    use game_interface::types::render::mod_payload::ModRenderPayload;
     use game_interface::types::weapons::WeaponType;
     use hashlink::LinkedHashMap;
     use hiarc::Hiarc;
@@ -56,6 +57,14 @@ pub mod projectile {
         pub force: f32,
         pub is_explosive: bool,
         pub ty: WeaponType,
+        /// The shot that spawned this projectile.
+        pub shot_id: ShotId,
+        /// How often the projectile bounced off walls.
+        pub bounces: u32,
+
+        /// Custom data of mods for their render mod, vanilla only
+        /// passes it to the render info.
+        pub mod_payload: ModRenderPayload,
     }
 
     #[derive(Debug, Hiarc, Clone)]
@@ -86,6 +95,7 @@ pub mod projectile {
             force: f32,
             explosive: bool,
             ty: WeaponType,
+            shot_id: ShotId,
             pool: &ProjectilePool,
             simulation_events: &SimulationEntityEvents,
         ) -> Self {
@@ -98,6 +108,9 @@ pub mod projectile {
                 force,
                 is_explosive: explosive,
                 ty,
+                shot_id,
+                bounces: 0,
+                mod_payload: Default::default(),
             };
             Self {
                 base: Entity::new(game_el_id),
@@ -153,6 +166,34 @@ pub mod projectile {
             calc_pos_and_vel(pos, &mut core.vel, curvature, speed, time)
         }
 
+        /// Bounces the projectile off the wall it hit, if the tuning allows it.
+        ///
+        /// Returns `false` if the projectile can't bounce anymore.
+        fn try_bounce(
+            collision: &Collision,
+            core: &mut ProjectileCore,
+            prev_pos: &vec2,
+            cur_pos: &vec2,
+            before_collision_pos: &vec2,
+        ) -> bool {
+            let tuning = collision.get_tune_at(&core.pos);
+            let (bounce_num, bounce_loss) = match core.ty {
+                WeaponType::Grenade => (tuning.grenade_bounce_num, tuning.grenade_bounce_loss),
+                WeaponType::Shotgun => (tuning.shotgun_bounce_num, tuning.shotgun_bounce_loss),
+                WeaponType::Gun => (tuning.gun_bounce_num, tuning.gun_bounce_loss),
+                _ => return false,
+            };
+            if core.bounces >= bounce_num.max(0.0) as u32 {
+                return false;
+            }
+
+            core.vel = collision.reflect_vel(prev_pos, cur_pos, &core.vel)
+                * (1.0 - bounce_loss).clamp(0.0, 1.0);
+            core.pos = *before_collision_pos;
+            core.bounces += 1;
+            true
+        }
+
         /*
                 Mhh: dont like
                 void CProjectile::LoseOwner()
@@ -164,6 +205,30 @@ pub mod projectile {
                 }
         */
 
+        /// Reports a hit of the shot, hits on the owner don't count.
+        fn push_hit(
+            &self,
+            pipe: &SimulationPipeProjectile,
+            victim: &GameEntityId,
+            res: &CharacterDamageResult,
+        ) {
+            let owner = pipe.characters_helper.owner_character;
+            if *res == CharacterDamageResult::None || *victim == owner {
+                return;
+            }
+            self.simulation_events.push(
+                Some(owner),
+                SimulationEventWorldEntityType::Projectile {
+                    id: self.base.game_element_id,
+                    ev: ProjectileEvent::Hit {
+                        shot_id: self.core.shot_id,
+                        ty: self.core.ty,
+                        victim: *victim,
+                    },
+                },
+            );
+        }
+
         fn create_explosion(&mut self, no_dmg: bool, pipe: &mut SimulationPipeProjectile) {
             // deal damage
             let radius = 135;
@@ -185,26 +250,31 @@ pub mod projectile {
                     force_dir = normalize(&diff);
                 }
                 l = 1.0 - ((l - inner_radius) / (radius as f32 - inner_radius)).clamp(0.0, 1.0);
-                let strength = pipe
-                    .collision
-                    .get_tune_at(&self.core.pos)
-                    .explosion_strength;
-
-                let dmg = strength * l;
-                if dmg <= 0.0 {
+                let tune = pipe.collision.get_tune_at(&self.core.pos);
+
+                let dmg = tune.explosion_strength * l;
+                // the knockback of the owner is tuned separately,
+                // so grenade jumps don't depend on the combat strength
+                let knockback = if id == pipe.characters_helper.owner_character {
+                    tune.explosion_self_strength * l
+                } else {
+                    dmg
+                };
+                if dmg <= 0.0 && knockback <= 0.0 {
                     continue;
                 }
 
-                if Character::take_damage(
+                let res = Character::take_damage(
                     pipe.characters_helper.characters,
                     &id,
-                    &(force_dir * dmg * 2.0),
+                    &(force_dir * knockback * 2.0),
                     &self.core.pos,
                     if no_dmg { 0 } else { dmg as u32 },
                     DamageTypes::Character(&pipe.characters_helper.owner_character),
                     DamageBy::Weapon(self.core.ty),
-                ) == CharacterDamageResult::Death
-                {
+                );
+                self.push_hit(pipe, &id, &res);
+                if res == CharacterDamageResult::Death {
                     pipe.characters_helper.characters.remove(&id);
                 }
             }
@@ -228,13 +298,13 @@ pub mod projectile {
                 &mut cur_pos,
                 1.0 / (ticks_per_second as f32),
             );
-            let mut dummy_pos = Default::default();
+            let mut before_collision_pos = Default::default();
             let mut dummy_tele_nr = Default::default();
             let collide = pipe.collision.intersect_line_tele_hook(
                 &prev_pos,
                 &cur_pos.clone(),
                 &mut cur_pos,
-                &mut dummy_pos,
+                &mut before_collision_pos,
                 &mut dummy_tele_nr,
             );
 
@@ -248,6 +318,20 @@ pub mod projectile {
                 6.0,
             );
 
+            if intersection.is_none()
+                && collide > 0
+                && self.core.life_span >= 0
+                && Self::try_bounce(
+                    pipe.collision,
+                    &mut self.core,
+                    &prev_pos,
+                    &cur_pos,
+                    &before_collision_pos,
+                )
+            {
+                return EntityTickResult::None;
+            }
+
             let res = if intersection.is_some()
                 || collide > 0
                 || self.core.life_span < 0
@@ -281,7 +365,7 @@ pub mod projectile {
                     self.create_explosion(false, pipe);
                 } else if let Some((_, _, intersect_char)) = intersection {
                     let intersect_char_id = intersect_char.base.game_element_id;
-                    if Character::take_damage(
+                    let res = Character::take_damage(
                         pipe.characters_helper.characters,
                         &intersect_char_id,
                         &(self.core.vel * 0.001_f32.max(self.core.force)),
@@ -289,8 +373,9 @@ pub mod projectile {
                         self.core.damage,
                         DamageTypes::Character(&pipe.characters_helper.owner_character),
                         DamageBy::Weapon(self.core.ty),
-                    ) == CharacterDamageResult::Death
-                    {
+                    );
+                    self.push_hit(pipe, &intersect_char_id, &res);
+                    if res == CharacterDamageResult::Death {
                         pipe.characters_helper.characters.remove(&intersect_char_id);
                     }
                 }
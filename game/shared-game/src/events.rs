@@ -3,7 +3,7 @@ pub mod events {
         events::{
             GameBuffEvent, GameCharacterEventEffect, GameCharacterEventSound, GameDebuffEvent,
             GameFlagEventEffect, GameFlagEventSound, GameGrenadeEventEffect, GameGrenadeEventSound,
-            GameLaserEventSound, GameWorldActionKillWeapon,
+            GameLaserEventSound, GameWorldActionKillWeapon, ShotId,
         },
         types::{
             flag::FlagType,
@@ -30,6 +30,12 @@ pub mod events {
             pos: vec2,
             ev: GameGrenadeEventEffect,
         },
+        /// The projectile (or its explosion) hit another character
+        Hit {
+            shot_id: ShotId,
+            ty: WeaponType,
+            victim: GameEntityId,
+        },
     }
 
     #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
@@ -42,6 +48,11 @@ pub mod events {
             pos: vec2,
             ev: GameLaserEventSound,
         },
+        /// The laser hit another character
+        Hit {
+            shot_id: ShotId,
+            victim: GameEntityId,
+        },
     }
 
     #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
@@ -104,11 +115,25 @@ pub mod events {
             dir: vec2,
             ty: WeaponType,
             lifetime: f32,
+            shot_id: ShotId,
         },
         Laser {
             pos: vec2,
             dir: vec2,
             energy: f32,
+            shot_id: ShotId,
+        },
+        /// A weapon was fired, the hits of the shot
+        /// are reported by the entities it spawned.
+        ShotFired {
+            shot_id: ShotId,
+            weapon: WeaponType,
+        },
+        /// A shot hit another character directly (e.g. hammer)
+        ShotHit {
+            shot_id: ShotId,
+            weapon: WeaponType,
+            victim: GameEntityId,
         },
         Sound {
             pos: vec2,
@@ -0,0 +1,65 @@
+pub mod freeze_tag_controller {
+    use base::linked_hash_map_view::LinkedHashMapIterExt;
+    use game_interface::types::render::game::game_match::MatchSide;
+    use hiarc::Hiarc;
+    use math::math::distance;
+
+    use crate::{
+        entities::character::core::character_core, types::types::GameOptionsFreezeTag,
+        world::world::GameWorld,
+    };
+
+    /// How close a teammate has to stand to a frozen character to unfreeze it.
+    const UNFREEZE_RANGE: f32 = character_core::PHYSICAL_SIZE * 2.0;
+
+    #[derive(Debug, Hiarc, Default)]
+    pub struct FreezeTagController {}
+
+    impl FreezeTagController {
+        fn unfreeze_characters(world: &mut GameWorld, options: &GameOptionsFreezeTag) {
+            let mut characters = LinkedHashMapIterExt::new(&mut world.characters);
+            characters.for_each(|(_, (character, other_chars))| {
+                if !character.is_frozen() {
+                    return;
+                }
+                let teammate_nearby = character.core.side.is_some()
+                    && other_chars.iter().any(|(_, other)| {
+                        other.core.side == character.core.side
+                            && !other.is_frozen()
+                            && distance(other.pos.pos(), character.pos.pos())
+                                < UNFREEZE_RANGE + character_core::PHYSICAL_SIZE
+                    });
+                if !teammate_nearby {
+                    // the progress is lost as soon as the teammates leave
+                    character.core.unfreeze_ticks = 0;
+                    return;
+                }
+                character.core.unfreeze_ticks += 1;
+                if character.core.unfreeze_ticks >= options.unfreeze_ticks {
+                    character.unfreeze();
+                }
+            });
+        }
+
+        /// Returns the side whose characters are all frozen.
+        fn frozen_side(world: &GameWorld) -> Option<MatchSide> {
+            [MatchSide::Red, MatchSide::Blue].into_iter().find(|&side| {
+                let mut characters = world
+                    .characters
+                    .values()
+                    .filter(|character| character.core.side == Some(side))
+                    .peekable();
+                characters.peek().is_some() && characters.all(|character| character.is_frozen())
+            })
+        }
+
+        /// Unfreezes characters that teammates stood next to long enough.
+        ///
+        /// Returns the side that lost the round, because all of its
+        /// characters are frozen.
+        pub fn tick(world: &mut GameWorld, options: &GameOptionsFreezeTag) -> Option<MatchSide> {
+            Self::unfreeze_characters(world, options);
+            Self::frozen_side(world)
+        }
+    }
+}
@@ -5,6 +5,8 @@ pub mod game_objects {
     use math::math::vector::ivec2;
     use shared_base::mapdef_06::EEntityTiles;
 
+    use crate::objective_controller::objective_controller::ObjectiveZone;
+
     #[derive(Debug, Hiarc, Default)]
     pub struct GameObjectsPickupDefinitions<V> {
         pub hearts: Vec<V>,
@@ -22,11 +24,14 @@ pub mod game_objects {
     #[derive(Debug, Hiarc)]
     pub struct GameObjectDefinitionsBase<V> {
         pub pickups: GameObjectsPickupDefinitions<V>,
+        /// The capture zones of the map, only used by the definitions.
+        pub objective_zones: Vec<ObjectiveZone>,
     }
 
     impl GameObjectDefinitionsBase<ivec2> {
         pub fn new(game_layer_tiles: &[TileBase], width: u32, height: u32) -> Self {
             let mut pickups = GameObjectsPickupDefinitions::<ivec2>::default();
+            let mut capture_point_tiles = Vec::new();
 
             for y in 0..height {
                 for x in 0..width {
@@ -61,13 +66,19 @@ pub mod game_objects {
                         i if i == EEntityTiles::PowerupNinja as u8 => {
                             pickups.ninjas.push(ivec2::new(x as i32, y as i32));
                         }
+                        i if i == EEntityTiles::CapturePoint as u8 => {
+                            capture_point_tiles.push(ivec2::new(x as i32, y as i32));
+                        }
                         _ => {
                             // not handled
                         }
                     }
                 }
             }
-            Self { pickups }
+            Self {
+                pickups,
+                objective_zones: ObjectiveZone::from_tiles(capture_point_tiles),
+            }
         }
     }
 
@@ -0,0 +1,39 @@
+pub mod instagib {
+    use game_interface::types::weapons::WeaponType;
+
+    use crate::{
+        entities::character::character::Character,
+        game_objects::game_objects::GameObjectsPickupDefinitions,
+        weapons::definitions::weapon_def::Weapon,
+    };
+
+    /// Damage of a laser hit, enough to kill a character
+    /// with full health & armor at once.
+    pub const LASER_DAMAGE: u32 = 20;
+
+    /// Removes all pickups, only the flags stay.
+    pub fn remove_pickups<V>(pickups: &mut GameObjectsPickupDefinitions<V>) {
+        pickups.hearts.clear();
+        pickups.shields.clear();
+        pickups
+            .weapons
+            .iter_mut()
+            .for_each(|weapons| weapons.clear());
+        pickups.ninjas.clear();
+    }
+
+    /// Characters only get a laser with unlimited ammo.
+    pub fn equip_character(character: &mut Character) {
+        character.core.active_weapon = WeaponType::Laser;
+
+        let reusable_core = &mut character.reusable_core;
+        reusable_core.weapons.clear();
+        reusable_core.weapons.insert(
+            WeaponType::Laser,
+            Weapon {
+                cur_ammo: None,
+                next_ammo_regeneration_tick: 0.into(),
+            },
+        );
+    }
+}
@@ -2,14 +2,21 @@
 #![allow(clippy::module_inception)]
 #![allow(clippy::multiple_bound_locations)]
 
+pub mod bots;
+pub mod chat_commands;
 pub mod collision;
 pub mod config;
 pub mod ctf_controller;
 pub mod entities;
 pub mod events;
+pub mod freeze_tag_controller;
 pub mod game_objects;
+pub mod instagib;
 pub mod match_manager;
 pub mod match_state;
+pub mod objective_controller;
+pub mod rcon;
+pub mod round_report;
 pub mod simulation_pipe;
 pub mod snapshot;
 pub mod spawns;
@@ -18,6 +25,7 @@ pub mod sql;
 pub mod stage;
 pub mod state;
 pub mod types;
+pub mod vote;
 pub mod weapons;
 pub mod world;
 
@@ -28,21 +36,85 @@ mod test {
         time::{Duration, Instant},
     };
 
+    use base::hash::generate_hash_for;
     use base_io::{io::create_runtime, io_batcher::IoBatcher};
     use game_database::dummy::DummyDb;
     use game_interface::{
+        chat_commands::ClientChatCommand,
+        client_commands::ClientCommand,
+        events::{
+            EventClientInfo, GameWorldEvent, GameWorldGlobalEvent, GameWorldStatEvent,
+            GameWorldSystemMessage, GameWorldVoteEvent,
+        },
         interface::{GameStateCreate, GameStateCreateOptions, GameStateInterface},
+        rcon_commands::{AuthLevel, ClientRconCommand},
         types::{
             character_info::NetworkCharacterInfo,
             game::GameEntityId,
             input::{CharacterInput, CharacterInputCursor},
             network_stats::PlayerNetworkStats,
-            player_info::{PlayerClientInfo, PlayerUniqueId},
+            player_info::{PlayerClientInfo, PlayerDropReason, PlayerUniqueId},
+            render::{character::PlayerCameraMode, game::game_match::MatchSide},
+            snapshot::SnapshotClientInfo,
+            weapons::WeaponType,
         },
+        votes::{GameVoteAction, Voted},
+    };
+    use hashlink::LinkedHashMap;
+    use map::map::groups::layers::{
+        physics::{MapLayerTilePhysicsBase, MapLayerTilePhysicsTune, MapLayerTilePhysicsTuneZone},
+        tiles::{TileBase, TuneTile},
+    };
+    use math::math::{
+        vector::{dvec2, ivec2, vec2},
+        Rng,
+    };
+    use pool::datatypes::PoolLinkedHashSet;
+    use shared_base::mapdef_06::DdraceTileNum;
+
+    use crate::{
+        collision::collision::{Collision, TuneZone, Tunings, ZoneModifiers},
+        config::{ConfigGameType, ConfigSpawnStrategy, ConfigVanilla, ConfigWeapon},
+        entities::character::character::CharacterCore,
+        freeze_tag_controller::freeze_tag_controller::FreezeTagController,
+        match_state::match_state::{Match, MatchState, MatchType, MatchWinner},
+        objective_controller::objective_controller::{CapturePoint, ObjectiveZone},
+        round_report::round_report::RoundReport,
+        snapshot::snapshot::{Snapshot, SnapshotCharacterPlayerTy, SnapshotFor},
+        spawns::{GameSpawns, SpawnHistory, SpawnStrategy},
+        state::state::{GameState, TICKS_PER_SECOND},
+        types::types::{GameOptionsFreezeTag, GameOptionsObjectives},
+        world::world::GameWorld,
     };
-    use math::math::{vector::dvec2, Rng};
 
-    use crate::state::state::GameState;
+    /// A game on ctf1 without a database.
+    fn test_game(config: ConfigVanilla) -> GameState {
+        let file = include_bytes!("../../../data/map/maps/ctf1.twmap");
+
+        let rt = create_runtime();
+        let io_batcher = IoBatcher::new(rt);
+        let (game, _) = GameState::new(
+            file.to_vec(),
+            "ctf1".to_string(),
+            GameStateCreateOptions {
+                config: serde_json::to_vec(&config).ok(),
+                ..Default::default()
+            },
+            io_batcher,
+            Arc::new(DummyDb),
+        );
+        game
+    }
+
+    fn join_player(game: &mut GameState, account: u64) -> GameEntityId {
+        game.player_join(&PlayerClientInfo {
+            info: NetworkCharacterInfo::explicit_default(),
+            is_dummy: false,
+            player_index: 0,
+            unique_identifier: PlayerUniqueId::Account(account),
+            initial_network_stats: PlayerNetworkStats::default(),
+        })
+    }
 
     #[test]
     fn benchmark() {
@@ -122,4 +194,1150 @@ mod test {
 
         println!("{} t/s", ticks / 5);
     }
+
+    #[test]
+    fn no_orphaned_owners_after_leaving_stage() {
+        let mut game = test_game(ConfigVanilla {
+            allow_stages: true,
+            ..Default::default()
+        });
+
+        let ids: Vec<_> = (0..2).map(|_| join_player(&mut game, 0)).collect();
+        game.tick();
+
+        // spawns a projectile & a laser owned by the character
+        let shoot = |game: &mut GameState, id: &GameEntityId| {
+            let stage_id = game.game.players.player(id).unwrap().stage_id();
+            let projectile_id = game.id_generator.next_id();
+            let laser_id = game.id_generator.next_id();
+            let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+            let pos = *world.characters.get(id).unwrap().pos.pos();
+            world.insert_new_projectile(
+                projectile_id,
+                *id,
+                &pos,
+                &vec2::new(1.0, 0.0),
+                TICKS_PER_SECOND as i32,
+                1,
+                0.0,
+                false,
+                WeaponType::Gun,
+                0,
+            );
+            world.insert_new_laser(
+                laser_id,
+                *id,
+                &pos,
+                &vec2::new(1.0, 0.0),
+                100.0,
+                0,
+                true,
+                false,
+            );
+        };
+        // returns the number of projectiles & lasers of all stages
+        let check_snapshot = |game: &GameState| {
+            let snapshot = game
+                .snap_shot_manager
+                .snapshot_for(game, SnapshotFor::Hotreload);
+            snapshot
+                .stages
+                .values()
+                .fold((0, 0), |(projs, lasers), stage| {
+                    let world = &stage.world;
+                    assert!(world
+                        .projectiles
+                        .values()
+                        .all(|proj| world.characters.contains_key(&proj.owner_game_el_id)));
+                    assert!(world
+                        .lasers
+                        .values()
+                        .all(|laser| world.characters.contains_key(&laser.owner_game_el_id)));
+                    (projs + world.projectiles.len(), lasers + world.lasers.len())
+                })
+        };
+
+        shoot(&mut game, &ids[0]);
+        shoot(&mut game, &ids[1]);
+        assert_eq!(check_snapshot(&game), (2, 2));
+
+        game.client_command(
+            &ids[0],
+            ClientCommand::JoinStage {
+                name: "test".try_into().unwrap(),
+                color: [255, 0, 0],
+            },
+        );
+        assert_eq!(check_snapshot(&game), (1, 1));
+
+        shoot(&mut game, &ids[0]);
+        game.player_drop(&ids[0], PlayerDropReason::Disconnect);
+        assert_eq!(check_snapshot(&game), (1, 1));
+
+        game.client_command(&ids[1], ClientCommand::JoinSpectator);
+        assert_eq!(check_snapshot(&game), (0, 0));
+    }
+
+    #[test]
+    fn disabled_weapons() {
+        let mut game = test_game(ConfigVanilla {
+            disabled_weapons: vec![ConfigWeapon::Gun, ConfigWeapon::Grenade],
+            ..Default::default()
+        });
+
+        assert!(
+            game.game_objects_definitions.pickups.weapons[WeaponType::Grenade as usize].is_empty()
+        );
+
+        let id = join_player(&mut game, 0);
+        let stage_id = game.game.players.player(&id).unwrap().stage_id();
+        let character = game
+            .game
+            .stages
+            .get(&stage_id)
+            .unwrap()
+            .world
+            .characters
+            .get(&id)
+            .unwrap();
+        assert_eq!(character.core.active_weapon, WeaponType::Hammer);
+        assert!(character
+            .reusable_core
+            .weapons
+            .keys()
+            .all(|&weapon| weapon == WeaponType::Hammer));
+    }
+
+    #[test]
+    fn instagib() {
+        let mut game = test_game(ConfigVanilla {
+            game_type: ConfigGameType::Ctf,
+            instagib: true,
+            ..Default::default()
+        });
+
+        let pickups = &game.game_objects_definitions.pickups;
+        assert!(pickups.hearts.is_empty() && pickups.shields.is_empty());
+        assert!(pickups.weapons.iter().all(|weapons| weapons.is_empty()));
+        // flags are not pickups that are affected by instagib
+        assert!(!pickups.red_flags.is_empty());
+
+        let id = join_player(&mut game, 0);
+        let stage_id = game.game.players.player(&id).unwrap().stage_id();
+        let character = game
+            .game
+            .stages
+            .get(&stage_id)
+            .unwrap()
+            .world
+            .characters
+            .get(&id)
+            .unwrap();
+        assert_eq!(character.core.active_weapon, WeaponType::Laser);
+        assert_eq!(character.reusable_core.weapons.len(), 1);
+        assert!(character
+            .reusable_core
+            .weapons
+            .get(&WeaponType::Laser)
+            .is_some_and(|weapon| weapon.cur_ammo.is_none()));
+    }
+
+    #[test]
+    fn overheal_decay() {
+        let mut game = test_game(ConfigVanilla {
+            overheal_max_health: 15,
+            overheal_max_armor: 15,
+            overheal_decay_secs: 1,
+            ..Default::default()
+        });
+
+        let id = join_player(&mut game, 0);
+        let stage_id = game.game.players.player(&id).unwrap().stage_id();
+        fn character<'a>(
+            game: &'a mut GameState,
+            stage_id: &GameEntityId,
+            id: &GameEntityId,
+        ) -> &'a mut CharacterCore {
+            &mut game
+                .game
+                .stages
+                .get_mut(stage_id)
+                .unwrap()
+                .world
+                .characters
+                .get_mut(id)
+                .unwrap()
+                .core
+        }
+        character(&mut game, &stage_id, &id).health = 14;
+        character(&mut game, &stage_id, &id).armor = 12;
+
+        let info = game.collect_character_local_render_info(&id);
+        assert_eq!(info.overheal_health, 4);
+        assert_eq!(info.overheal_armor, 2);
+
+        for _ in 0..TICKS_PER_SECOND * 2 + 1 {
+            game.tick();
+        }
+        assert_eq!(character(&mut game, &stage_id, &id).health, 12);
+        assert_eq!(character(&mut game, &stage_id, &id).armor, 10);
+
+        for _ in 0..TICKS_PER_SECOND * 2 {
+            game.tick();
+        }
+        let info = game.collect_character_local_render_info(&id);
+        assert_eq!(info.health, 10);
+        assert_eq!(info.overheal_health, 0);
+        assert_eq!(info.overheal_armor, 0);
+    }
+
+    #[test]
+    fn spectators_follow_players() {
+        let mut game = test_game(Default::default());
+
+        let ids: Vec<_> = (0..3).map(|i| join_player(&mut game, i)).collect();
+        game.tick();
+
+        let cam_mode = |game: &GameState| {
+            game.collect_characters_info()
+                .get(&ids[0])
+                .and_then(|c| c.player_info.as_ref())
+                .map(|info| info.cam_mode)
+        };
+
+        // only spectators can follow others
+        game.client_command(&ids[0], ClientCommand::SpectateTarget(Some(ids[1])));
+        assert!(matches!(cam_mode(&game), Some(PlayerCameraMode::Default)));
+
+        game.client_command(&ids[0], ClientCommand::JoinSpectator);
+        game.client_command(&ids[0], ClientCommand::SpectateTarget(Some(ids[1])));
+        assert!(matches!(
+            cam_mode(&game),
+            Some(PlayerCameraMode::FollowCharacter(id)) if id == ids[1]
+        ));
+
+        // spectators can't be followed
+        game.client_command(&ids[2], ClientCommand::JoinSpectator);
+        game.client_command(&ids[0], ClientCommand::SpectateTarget(Some(ids[2])));
+        assert!(matches!(cam_mode(&game), Some(PlayerCameraMode::Free)));
+
+        game.client_command(&ids[0], ClientCommand::SpectateTarget(Some(ids[1])));
+        game.player_drop(&ids[1], PlayerDropReason::Disconnect);
+        assert!(matches!(cam_mode(&game), Some(PlayerCameraMode::Free)));
+    }
+
+    #[test]
+    fn votes() {
+        let mut game = test_game(Default::default());
+
+        let ids: Vec<_> = (0..3).map(|i| join_player(&mut game, i)).collect();
+        game.tick();
+
+        // the starter votes yes, which is not more than half of the players
+        game.client_command(
+            &ids[0],
+            ClientCommand::StartVote(GameVoteAction::SpecPlayer { player_id: ids[2] }),
+        );
+        game.tick();
+        assert!(game.game.players.player(&ids[2]).is_some());
+
+        game.client_command(&ids[1], ClientCommand::Vote(Voted::Yes));
+        game.tick();
+        assert!(game.game.players.player(&ids[2]).is_none());
+        assert!(game.game.no_char_players.contains_key(&ids[2]));
+
+        // spectators can't vote by default, so the vote fails
+        game.client_command(
+            &ids[0],
+            ClientCommand::StartVote(GameVoteAction::KickPlayer { player_id: ids[1] }),
+        );
+        game.client_command(&ids[1], ClientCommand::Vote(Voted::No));
+        game.client_command(&ids[2], ClientCommand::Vote(Voted::Yes));
+        game.tick();
+        let vote_events = || {
+            game.events_for(EventClientInfo {
+                client_player_ids: PoolLinkedHashSet::new_without_pool(),
+                everything: true,
+                other_stages: true,
+            })
+            .worlds
+            .values()
+            .flat_map(|world| world.events.values())
+            .filter_map(|ev| match ev {
+                GameWorldEvent::Global(GameWorldGlobalEvent::Vote(ev)) => Some(ev.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+        };
+        assert!(matches!(
+            vote_events().last(),
+            Some(GameWorldVoteEvent::Failed { .. })
+        ));
+    }
+
+    #[test]
+    fn rcon_commands() {
+        let mut game = test_game(ConfigVanilla {
+            game_type: ConfigGameType::Ctf,
+            ..Default::default()
+        });
+
+        let ids: Vec<_> = (0..2).map(|i| join_player(&mut game, i)).collect();
+        let side = |game: &GameState, id: &GameEntityId| {
+            let stage_id = game.game.players.player(id).unwrap().stage_id();
+            game.game
+                .stages
+                .get(&stage_id)
+                .unwrap()
+                .world
+                .characters
+                .get(id)
+                .unwrap()
+                .core
+                .side
+        };
+        let rcon = |game: &mut GameState, raw: String, auth_level| {
+            game.client_command(
+                &ids[0],
+                ClientCommand::Rcon(ClientRconCommand { raw, auth_level }),
+            );
+        };
+
+        let target_side = match side(&game, &ids[1]) {
+            Some(MatchSide::Red) => MatchSide::Blue,
+            _ => MatchSide::Red,
+        };
+        let target_side_name = match target_side {
+            MatchSide::Red => "red",
+            MatchSide::Blue => "blue",
+        };
+        // invalid sides are rejected by the parser
+        rcon(
+            &mut game,
+            format!("set_team {} green", ids[1]),
+            AuthLevel::Moderator,
+        );
+        assert_ne!(side(&game, &ids[1]), Some(target_side));
+        rcon(
+            &mut game,
+            format!("set_team {} {target_side_name}", ids[1]),
+            AuthLevel::Moderator,
+        );
+        assert_eq!(side(&game, &ids[1]), Some(target_side));
+
+        // cheats are for admins only
+        let weapon_count = |game: &GameState| {
+            let stage_id = game.game.players.player(&ids[0]).unwrap().stage_id();
+            game.game
+                .stages
+                .get(&stage_id)
+                .unwrap()
+                .world
+                .characters
+                .get(&ids[0])
+                .unwrap()
+                .reusable_core
+                .weapons
+                .len()
+        };
+        let weapons = weapon_count(&game);
+        rcon(
+            &mut game,
+            "cheat.all_weapons".to_string(),
+            AuthLevel::Moderator,
+        );
+        assert_eq!(weapon_count(&game), weapons);
+        rcon(&mut game, "cheat.all_weapons".to_string(), AuthLevel::Admin);
+        assert!(weapon_count(&game) > weapons);
+    }
+
+    #[test]
+    fn chat_commands() {
+        let mut game = test_game(ConfigVanilla {
+            rules: vec!["be nice".to_string()],
+            ..Default::default()
+        });
+        assert!(game.chat_commands.cmds.contains_key("accept_rules"));
+        assert!(game.chat_commands.descriptions.contains_key("accept_rules"));
+
+        let id = join_player(&mut game, 0);
+        let other_id = join_player(&mut game, 1);
+        assert!(game.game.players.player(&id).is_none());
+        game.clear_events();
+
+        let chat = |game: &mut GameState, raw: &str| {
+            game.client_command(
+                &id,
+                ClientCommand::Chat(ClientChatCommand {
+                    raw: raw.to_string(),
+                }),
+            );
+        };
+        chat(&mut game, "not_a_command");
+        chat(&mut game, "help");
+        assert!(game.game.players.player(&id).is_none());
+
+        // the feedback is only sent to the player that used the commands
+        let feedback = |game: &GameState, player_id: &GameEntityId| {
+            let mut client_player_ids = PoolLinkedHashSet::new_without_pool();
+            client_player_ids.insert(*player_id);
+            game.events_for(EventClientInfo {
+                client_player_ids,
+                everything: false,
+                other_stages: true,
+            })
+            .worlds
+            .values()
+            .flat_map(|world| world.events.values())
+            .filter(|ev| {
+                matches!(
+                    ev,
+                    GameWorldEvent::Global(
+                        GameWorldGlobalEvent::PlayerMessage { .. }
+                            | GameWorldGlobalEvent::System(GameWorldSystemMessage::Custom(_))
+                    )
+                )
+            })
+            .count()
+        };
+        assert_eq!(feedback(&game, &id), 2);
+        assert_eq!(feedback(&game, &other_id), 0);
+        game.clear_events();
+        chat(&mut game, "accept_rules");
+        assert!(game.game.players.player(&id).is_some());
+
+        // accepted rules don't have to be accepted again
+        game.player_drop(&id, PlayerDropReason::Disconnect);
+        let id = join_player(&mut game, 0);
+        assert!(game.game.players.player(&id).is_some());
+    }
+
+    #[test]
+    fn pause_stages() {
+        let mut game = test_game(Default::default());
+
+        join_player(&mut game, 0);
+        game.tick();
+
+        let stage_id = game.stage_0_id;
+        let passed_ticks = |game: &GameState| {
+            game.game
+                .stages
+                .get(&stage_id)
+                .unwrap()
+                .match_manager
+                .game_match
+                .state
+                .passed_ticks()
+        };
+        assert!(game.resume_stage(&stage_id).is_err());
+        game.pause_stage(&stage_id).unwrap();
+        assert!(game.pause_stage(&stage_id).is_err());
+        let paused_at = passed_ticks(&game);
+        for _ in 0..TICKS_PER_SECOND {
+            game.tick();
+        }
+        assert_eq!(passed_ticks(&game), paused_at);
+
+        game.resume_stage(&stage_id).unwrap();
+        game.tick();
+        assert_eq!(passed_ticks(&game), paused_at + 1);
+    }
+
+    #[test]
+    fn bots() {
+        let mut game = test_game(Default::default());
+
+        let stage_id = game.stage_0_id;
+        assert!(game.add_bot(&stage_id, "unknown").is_err());
+        let bot_id = game.add_bot(&stage_id, "roam").unwrap();
+        let has_character = |game: &GameState| {
+            game.game
+                .stages
+                .get(&stage_id)
+                .unwrap()
+                .world
+                .characters
+                .get(&bot_id)
+                .map(|character| character.is_player_character().is_none())
+        };
+        for _ in 0..TICKS_PER_SECOND {
+            game.tick();
+        }
+        assert_eq!(has_character(&game), Some(true));
+
+        // bots respawn like players
+        game.game
+            .stages
+            .get_mut(&stage_id)
+            .unwrap()
+            .world
+            .characters
+            .remove(&bot_id);
+        game.tick();
+        assert_eq!(has_character(&game), None);
+        for _ in 0..TICKS_PER_SECOND {
+            game.tick();
+        }
+        assert_eq!(has_character(&game), Some(true));
+
+        game.remove_bot(&bot_id).unwrap();
+        assert!(game.remove_bot(&bot_id).is_err());
+        for _ in 0..TICKS_PER_SECOND {
+            game.tick();
+        }
+        assert_eq!(has_character(&game), None);
+    }
+
+    #[test]
+    fn late_joiners_spawn_at_next_wave() {
+        let mut game = test_game(ConfigVanilla {
+            game_type: ConfigGameType::Ctf,
+            spawn_wave_secs: 2,
+            late_join_protection_secs: 2,
+            ..Default::default()
+        });
+
+        // the round did not start yet
+        let first = join_player(&mut game, 0);
+        assert!(game.game.players.player(&first).is_some());
+
+        for _ in 0..TICKS_PER_SECOND {
+            game.tick();
+        }
+        let late = join_player(&mut game, 1);
+        assert!(game.game.players.player(&late).is_none());
+        assert!(game.game.no_char_players.contains_key(&late));
+
+        for _ in 0..TICKS_PER_SECOND {
+            game.tick();
+        }
+        let stage_id = game.game.players.player(&late).unwrap().stage_id();
+        let character = game
+            .game
+            .stages
+            .get(&stage_id)
+            .unwrap()
+            .world
+            .characters
+            .get(&late)
+            .unwrap();
+        assert!(character.core.spawn_protection.is_some());
+    }
+
+    #[test]
+    fn warmup_time_limit_and_sudden_death() {
+        let mut game = test_game(ConfigVanilla {
+            game_type: ConfigGameType::Ctf,
+            warmup_secs: 1,
+            time_limit_secs: 1,
+            ..Default::default()
+        });
+        fn game_match(game: &mut GameState) -> &mut Match {
+            let stage_0_id = game.stage_0_id;
+            &mut game
+                .game
+                .stages
+                .get_mut(&stage_0_id)
+                .unwrap()
+                .match_manager
+                .game_match
+        }
+        assert!(matches!(
+            game_match(&mut game).state,
+            MatchState::Warmup { .. }
+        ));
+
+        for _ in 0..TICKS_PER_SECOND {
+            game.tick();
+        }
+        assert!(matches!(
+            game_match(&mut game).state,
+            MatchState::Running {
+                round_ticks_passed: 0
+            }
+        ));
+
+        // a tie when the time is over
+        for _ in 0..TICKS_PER_SECOND {
+            game.tick();
+        }
+        assert!(matches!(
+            game_match(&mut game).state,
+            MatchState::SuddenDeath { .. }
+        ));
+
+        // the next score decides the round
+        game_match(&mut game).ty = MatchType::Sided { scores: [1, 0] };
+        game.tick();
+        assert!(matches!(
+            game_match(&mut game).state,
+            MatchState::GameOver {
+                winner: MatchWinner::Side(MatchSide::Red),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn round_reports_without_database_are_exported_by_the_server() {
+        let mut game = test_game(ConfigVanilla {
+            game_type: ConfigGameType::Ctf,
+            warmup_secs: 1,
+            time_limit_secs: 1,
+            ..Default::default()
+        });
+        let player_id = join_player(&mut game, 0);
+        let global_events = |game: &GameState, everything: bool| {
+            let mut client_player_ids = PoolLinkedHashSet::new_without_pool();
+            client_player_ids.insert(player_id);
+            game.events_for(EventClientInfo {
+                client_player_ids,
+                everything,
+                other_stages: true,
+            })
+            .worlds
+            .values()
+            .flat_map(|world| world.events.values())
+            .filter_map(|ev| match ev {
+                GameWorldEvent::Global(ev) => Some(ev.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+        };
+
+        let stage_0_id = game.stage_0_id;
+        let mut events = Vec::new();
+        let mut client_events = Vec::new();
+        for _ in 0..TICKS_PER_SECOND * 3 {
+            game.tick();
+            let game_match = &mut game
+                .game
+                .stages
+                .get_mut(&stage_0_id)
+                .unwrap()
+                .match_manager
+                .game_match;
+            if matches!(game_match.state, MatchState::SuddenDeath { .. }) {
+                game_match.ty = MatchType::Sided { scores: [1, 0] };
+            }
+            events.extend(global_events(&game, true));
+            client_events.extend(global_events(&game, false));
+            game.clear_events();
+        }
+
+        let stat_report_id = events
+            .iter()
+            .find_map(|ev| match ev {
+                GameWorldGlobalEvent::Stat(GameWorldStatEvent::RoundReport { report_id }) => {
+                    Some(*report_id)
+                }
+                _ => None,
+            })
+            .unwrap();
+        let (report_id, report) = events
+            .iter()
+            .find_map(|ev| match ev {
+                GameWorldGlobalEvent::ExportRoundReport { report_id, report } => {
+                    Some((*report_id, report.to_string()))
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(report_id, stat_report_id);
+        assert_eq!(generate_hash_for(report.as_bytes()), report_id);
+        assert!(serde_json::from_str::<RoundReport>(&report).is_ok());
+
+        // only the server sees the report
+        assert!(!client_events
+            .iter()
+            .any(|ev| matches!(ev, GameWorldGlobalEvent::ExportRoundReport { .. })));
+    }
+
+    #[test]
+    fn spawn_strategies_are_deterministic() {
+        let spawn_positions = |spawn_strategy: ConfigSpawnStrategy| {
+            let mut game = test_game(ConfigVanilla {
+                game_type: ConfigGameType::Ctf,
+                spawn_strategy,
+                ..Default::default()
+            });
+
+            (0..8)
+                .map(|i| {
+                    let id = join_player(&mut game, i);
+                    let stage_id = game.game.players.player(&id).unwrap().stage_id();
+                    let character = game
+                        .game
+                        .stages
+                        .get(&stage_id)
+                        .unwrap()
+                        .world
+                        .characters
+                        .get(&id)
+                        .unwrap();
+                    (*character.pos.pos(), character.core.side)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for strategy in [
+            ConfigSpawnStrategy::GameTypeDefault,
+            ConfigSpawnStrategy::FarthestFromEnemies,
+            ConfigSpawnStrategy::TeamSide,
+            ConfigSpawnStrategy::RandomWeightedByDeaths,
+        ] {
+            let positions = spawn_positions(strategy);
+            assert_eq!(positions, spawn_positions(strategy), "{strategy:?}");
+            if strategy != ConfigSpawnStrategy::RandomWeightedByDeaths {
+                // characters are spread over the spawns
+                assert!(
+                    positions.iter().any(|(pos, _)| *pos != positions[0].0),
+                    "{strategy:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn spawns_are_weighted_away_from_recent_deaths() {
+        let spawns = GameSpawns {
+            spawns: vec![vec2::new(0.0, 0.0), vec2::new(32.0 * 100.0, 0.0)],
+            spawns_red: Vec::new(),
+            spawns_blue: Vec::new(),
+        };
+        let picks = |history: &mut SpawnHistory| {
+            let first = (0..1000)
+                .filter(|_| {
+                    spawns.spawn_pos(SpawnStrategy::RandomWeightedByDeaths, None, &[], history)
+                        == spawns.spawns[0]
+                })
+                .count();
+            (first, 1000 - first)
+        };
+
+        // both spawns are used without deaths
+        let (first, second) = picks(&mut SpawnHistory::default());
+        assert!(first > 0 && second > 0);
+
+        let mut history = SpawnHistory::default();
+        for _ in 0..4 {
+            history.add_death(vec2::new(32.0, 0.0));
+        }
+        let (near_deaths, far_from_deaths) = picks(&mut history);
+        assert!(near_deaths > 0);
+        assert!(far_from_deaths > near_deaths * 3);
+    }
+
+    #[test]
+    fn projectile_bounce_reflection() {
+        // 4x4 tiles, the right column is solid
+        let tiles: Vec<_> = (0..4 * 4)
+            .map(|i| TileBase {
+                index: if i % 4 == 3 {
+                    DdraceTileNum::Solid as u8
+                } else {
+                    0
+                },
+                ..Default::default()
+            })
+            .collect();
+        let collision = Collision::new(4, 4, &tiles, None, None);
+
+        // hitting the wall only reflects the horizontal velocity
+        let vel = collision.reflect_vel(
+            &vec2::new(80.0, 48.0),
+            &vec2::new(100.0, 52.0),
+            &vec2::new(1.0, 0.5),
+        );
+        assert_eq!(vel, vec2::new(-1.0, 0.5));
+
+        // the tunings are configurable per tune zone
+        let tunes = [("grenade_bounce_num", "3"), ("grenade_bounce_loss", "0.5")]
+            .into_iter()
+            .map(|(name, val)| (name.to_string(), val.to_string()))
+            .collect();
+        let zone = TuneZone::from_tunes(&tunes);
+        assert_eq!(zone.tunings.grenade_bounce_num, 3.0);
+        assert_eq!(zone.tunings.grenade_bounce_loss, 0.5);
+    }
+
+    #[test]
+    fn hook_through_tiles() {
+        // 4x4 tiles, the third column is solid,
+        // in the second row with a hook through tile in front of it
+        let tiles: Vec<_> = (0..4 * 4)
+            .map(|i| TileBase {
+                index: if i % 4 == 2 {
+                    DdraceTileNum::Solid as u8
+                } else if i == 4 + 1 {
+                    DdraceTileNum::Through as u8
+                } else {
+                    0
+                },
+                ..Default::default()
+            })
+            .collect();
+        let collision = Collision::new(4, 4, &tiles, None, None);
+
+        let mut hook = |from: vec2, to: vec2| {
+            let (mut col, mut before_col, mut tele_nr) = Default::default();
+            collision.intersect_line_tele_hook(&from, &to, &mut col, &mut before_col, &mut tele_nr)
+        };
+        assert_eq!(
+            hook(vec2::new(16.0, 48.0), vec2::new(112.0, 48.0)),
+            0,
+            "the hook should fly through the wall"
+        );
+        assert_eq!(
+            hook(vec2::new(16.0, 80.0), vec2::new(112.0, 80.0)),
+            DdraceTileNum::Solid as i32
+        );
+
+        // the same hook through tile on the front layer
+        let (game_tiles, front_tiles): (Vec<_>, Vec<_>) = tiles
+            .iter()
+            .map(|tile| {
+                if tile.index == DdraceTileNum::Through as u8 {
+                    (TileBase::default(), *tile)
+                } else {
+                    (*tile, TileBase::default())
+                }
+            })
+            .unzip();
+        let collision = Collision::new(4, 4, &game_tiles, Some(&front_tiles), None);
+        let (mut col, mut before_col, mut tele_nr) = Default::default();
+        assert_eq!(
+            collision.intersect_line_tele_hook(
+                &vec2::new(16.0, 48.0),
+                &vec2::new(112.0, 48.0),
+                &mut col,
+                &mut before_col,
+                &mut tele_nr
+            ),
+            0,
+            "the hook should fly through the wall"
+        );
+    }
+
+    #[test]
+    fn tune_zone_modifiers() {
+        let tunes: LinkedHashMap<_, _> = [
+            ("gravity_scale", "0.5"),
+            ("speed_scale", " 2 "),
+            ("no_hook", "1"),
+            ("gravity", "0.25"),
+            ("ground_control_speed", "fast"),
+            ("not_a_tune", "1"),
+        ]
+        .into_iter()
+        .map(|(name, val)| (name.to_string(), val.to_string()))
+        .collect();
+        let zone = TuneZone::from_tunes(&tunes);
+        assert_eq!(
+            zone.modifiers,
+            ZoneModifiers {
+                gravity_scale: 0.5,
+                speed_scale: 2.0,
+                no_hook: true,
+            }
+        );
+        assert_eq!(zone.tunings.gravity, 0.25);
+        // invalid values keep the default
+        assert_eq!(
+            zone.tunings.ground_control_speed,
+            Tunings::default().ground_control_speed
+        );
+
+        // 3x1 tiles, the second in zone 1, the third in zone 2 that has no tunes
+        let tiles = vec![TileBase::default(); 3];
+        let tune_tiles = (0..3)
+            .map(|number| TuneTile {
+                number,
+                ..Default::default()
+            })
+            .collect();
+        let mut tune_zones = LinkedHashMap::new();
+        tune_zones.insert(
+            1,
+            MapLayerTilePhysicsTuneZone {
+                name: "low gravity".to_string(),
+                tunes: [("gravity_scale".to_string(), "0.5".to_string())]
+                    .into_iter()
+                    .collect(),
+            },
+        );
+        let collision = Collision::new(
+            3,
+            1,
+            &tiles,
+            None,
+            Some(&MapLayerTilePhysicsTune {
+                base: MapLayerTilePhysicsBase { tiles: tune_tiles },
+                tune_zones,
+            }),
+        );
+        assert_eq!(
+            collision.get_zone_modifiers_at(&vec2::new(16.0, 16.0)),
+            &ZoneModifiers::default()
+        );
+        assert_eq!(
+            collision
+                .get_zone_modifiers_at(&vec2::new(48.0, 16.0))
+                .gravity_scale,
+            0.5
+        );
+        assert_eq!(
+            collision.get_zone_modifiers_at(&vec2::new(80.0, 16.0)),
+            &ZoneModifiers::default()
+        );
+    }
+
+    #[test]
+    fn explosion_self_strength_tuning() {
+        // by default the owner gets the same knockback as everyone else
+        let tunings = Tunings::default();
+        assert_eq!(tunings.explosion_self_strength, tunings.explosion_strength);
+
+        // but it can be tuned independently
+        let tunes = [("explosion_self_strength", "12")]
+            .into_iter()
+            .map(|(name, val)| (name.to_string(), val.to_string()))
+            .collect();
+        let zone = TuneZone::from_tunes(&tunes);
+        assert_eq!(zone.tunings.explosion_self_strength, 12.0);
+        assert_eq!(zone.tunings.explosion_strength, 6.0);
+    }
+
+    #[test]
+    fn capture_points() {
+        // two zones, the tiles of the first one only touch diagonally
+        let zones = ObjectiveZone::from_tiles(vec![
+            ivec2::new(10, 10),
+            ivec2::new(1, 1),
+            ivec2::new(2, 2),
+            ivec2::new(11, 10),
+        ]);
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0].tiles, vec![ivec2::new(1, 1), ivec2::new(2, 2)]);
+        assert!(zones[1].contains(&vec2::new(11.0 * 32.0 + 5.0, 10.0 * 32.0 + 5.0)));
+        assert!(!zones[1].contains(&vec2::new(12.0 * 32.0 + 5.0, 10.0 * 32.0 + 5.0)));
+
+        let options = GameOptionsObjectives {
+            capture_ticks: 3,
+            score_ticks: 2,
+        };
+        let mut point = CapturePoint::default();
+
+        // contested points don't progress
+        assert_eq!(point.tick(true, true, &options), None);
+        assert_eq!(point.progress, 0);
+
+        assert_eq!(point.tick(true, false, &options), None);
+        assert_eq!(point.tick(true, false, &options), None);
+        // the other side has to undo the progress first
+        assert_eq!(point.tick(false, true, &options), None);
+        assert_eq!(point.capturing_side, Some(MatchSide::Red));
+        assert_eq!(point.tick(true, false, &options), None);
+        assert_eq!(point.tick(true, false, &options), None);
+        assert_eq!(point.owner, Some(MatchSide::Red));
+
+        // the owner scores over time, also if nobody is in the zone
+        assert_eq!(point.tick(false, false, &options), Some(MatchSide::Red));
+        assert_eq!(point.tick(false, false, &options), None);
+        assert_eq!(point.tick(false, false, &options), Some(MatchSide::Red));
+    }
+
+    #[test]
+    fn snapshot_budget() {
+        let mut game = test_game(Default::default());
+
+        let ids: Vec<_> = (0..8).map(|i| join_player(&mut game, i)).collect();
+        game.tick();
+
+        let snapshot_for = |game: &GameState| {
+            let mut client_player_ids = PoolLinkedHashSet::new_without_pool();
+            client_player_ids.insert(ids[0]);
+            game.snap_shot_manager.snapshot_for(
+                game,
+                SnapshotFor::Client(SnapshotClientInfo {
+                    client_player_ids,
+                    snap_everything: false,
+                    snap_other_stages: false,
+                }),
+            )
+        };
+        let characters = |snapshot: &Snapshot| {
+            snapshot
+                .stages
+                .values()
+                .flat_map(|stage| stage.world.characters.values())
+                .map(|character| {
+                    (
+                        character.game_el_id,
+                        matches!(character.ty, SnapshotCharacterPlayerTy::Player(Some(_))),
+                        character.player_info.is_some(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut snapshot = snapshot_for(&game);
+        let len = bincode::serde::encode_to_vec(&snapshot, bincode::config::standard())
+            .unwrap()
+            .len();
+        assert!(!snapshot.trim_to_budget(len));
+
+        // the network stats are dropped first
+        assert!(snapshot.trim_to_budget(len - 1));
+        let chars = characters(&snapshot);
+        assert_eq!(chars.len(), ids.len());
+        assert!(chars.iter().any(|(_, has_stats, _)| !has_stats));
+        assert!(chars.iter().all(|(_, _, has_info)| *has_info));
+
+        // the info of the local player is always kept
+        let mut snapshot = snapshot_for(&game);
+        assert!(snapshot.trim_to_budget(0));
+        for (id, has_stats, has_info) in characters(&snapshot) {
+            assert!(!has_stats);
+            assert_eq!(has_info, id == ids[0]);
+        }
+    }
+
+    #[test]
+    fn inactive_players_are_kicked() {
+        let mut game = test_game(ConfigVanilla {
+            inactivity_kick_secs: 2,
+            inactivity_warning_secs: 1,
+            ..Default::default()
+        });
+
+        let ids: Vec<_> = (0..2).map(|i| join_player(&mut game, i)).collect();
+        let global_events = |game: &GameState, player_id: &GameEntityId, everything: bool| {
+            let mut client_player_ids = PoolLinkedHashSet::new_without_pool();
+            client_player_ids.insert(*player_id);
+            game.events_for(EventClientInfo {
+                client_player_ids,
+                everything,
+                other_stages: true,
+            })
+            .worlds
+            .values()
+            .flat_map(|world| world.events.values())
+            .filter_map(|ev| match ev {
+                GameWorldEvent::Global(ev) => Some(ev.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+        };
+
+        let mut warnings = 0;
+        let mut kicks = Vec::new();
+        for _ in 0..2 * TICKS_PER_SECOND + 1 {
+            game.tick();
+            for ev in global_events(&game, &ids[1], false) {
+                match ev {
+                    // the warning is only sent to the inactive player itself
+                    GameWorldGlobalEvent::PlayerMessage { player_id, .. } => {
+                        assert_eq!(player_id, ids[1]);
+                        warnings += 1;
+                    }
+                    // kicks are only for the server
+                    GameWorldGlobalEvent::Kick { .. } => panic!("kick sent to a client"),
+                    _ => {}
+                }
+            }
+            kicks.extend(global_events(&game, &ids[1], true).into_iter().filter_map(
+                |ev| match ev {
+                    GameWorldGlobalEvent::Kick { player_id, .. } => Some(player_id),
+                    _ => None,
+                },
+            ));
+            game.clear_events();
+        }
+        assert_eq!(warnings, 1);
+        kicks.sort();
+        let mut expected = ids.clone();
+        expected.sort();
+        assert_eq!(kicks, expected);
+        // the server drops the players once they are disconnected
+        assert!(ids.iter().all(|id| game.game.players.player(id).is_some()));
+    }
+
+    #[test]
+    fn freeze_tag() {
+        let mut game = test_game(ConfigVanilla {
+            game_type: ConfigGameType::FreezeTag,
+            ..Default::default()
+        });
+
+        let ids: Vec<_> = (0..3).map(|i| join_player(&mut game, i)).collect();
+        let stage_id = game.stage_0_id;
+        fn world(game: &mut GameState) -> &mut GameWorld {
+            let stage_id = game.stage_0_id;
+            &mut game.game.stages.get_mut(&stage_id).unwrap().world
+        }
+        // two red characters next to each other, a blue one far away
+        for (id, side, pos) in [
+            (ids[0], MatchSide::Red, vec2::new(320.0, 320.0)),
+            (ids[1], MatchSide::Red, vec2::new(352.0, 320.0)),
+            (ids[2], MatchSide::Blue, vec2::new(3200.0, 320.0)),
+        ] {
+            let character = world(&mut game).characters.get_mut(&id).unwrap();
+            character.core.side = Some(side);
+            character.pos.move_pos(pos);
+        }
+
+        // frozen characters can't kill themselves
+        world(&mut game)
+            .characters
+            .get_mut(&ids[0])
+            .unwrap()
+            .freeze();
+        game.client_command(&ids[0], ClientCommand::Kill);
+        assert!(world(&mut game)
+            .characters
+            .get(&ids[0])
+            .is_some_and(|character| character.is_frozen()));
+
+        // a teammate nearby thaws the character over time
+        let options = GameOptionsFreezeTag { unfreeze_ticks: 2 };
+        assert_eq!(FreezeTagController::tick(world(&mut game), &options), None);
+        assert!(world(&mut game)
+            .characters
+            .get(&ids[0])
+            .unwrap()
+            .is_frozen());
+        assert_eq!(FreezeTagController::tick(world(&mut game), &options), None);
+        assert!(!world(&mut game)
+            .characters
+            .get(&ids[0])
+            .unwrap()
+            .is_frozen());
+
+        // a side wins once all characters of the other side are frozen
+        world(&mut game)
+            .characters
+            .get_mut(&ids[2])
+            .unwrap()
+            .freeze();
+        assert_eq!(
+            FreezeTagController::tick(world(&mut game), &options),
+            Some(MatchSide::Blue)
+        );
+        game.tick();
+        assert!(matches!(
+            game.game
+                .stages
+                .get(&stage_id)
+                .unwrap()
+                .match_manager
+                .game_match
+                .state,
+            MatchState::GameOver {
+                winner: MatchWinner::Side(MatchSide::Red),
+                ..
+            }
+        ));
+    }
 }
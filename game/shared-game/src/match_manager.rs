@@ -1,12 +1,20 @@
 /// everything related to a single match/round/race-run
 pub mod match_manager {
-    use game_interface::{events::GameWorldActionKillWeapon, types::game::GameEntityId};
+    use game_interface::{
+        events::{GameWorldActionKillWeapon, GameWorldGlobalEvent, GameWorldStatEvent},
+        types::{game::GameEntityId, render::game::game_match::MatchSide},
+    };
     use hiarc::{hi_closure, Hiarc};
 
     use crate::{
         ctf_controller::ctf_controller::CtfController,
         events::events::{CharacterEvent, FlagEvent},
+        freeze_tag_controller::freeze_tag_controller::FreezeTagController,
         match_state::match_state::{Match, MatchState, MatchType},
+        objective_controller::objective_controller::{
+            CapturePoint, ObjectiveController, ObjectiveZone,
+        },
+        round_report::round_report::{RoundCapture, RoundStats},
         simulation_pipe::simulation_pipe::{
             SimulationEventWorldEntity, SimulationEventWorldEntityType, SimulationStageEvents,
             SimulationWorldEvent,
@@ -21,6 +29,14 @@ pub mod match_manager {
         simulation_events: SimulationStageEvents,
 
         pub(crate) game_match: Match,
+        /// One per objective zone, empty if the game type has no objectives.
+        pub(crate) capture_points: Vec<CapturePoint>,
+
+        /// Statistics of the running round.
+        round_stats: RoundStats,
+        /// The statistics of the round that just ended,
+        /// taken by the game state to export the round report.
+        pub(crate) finished_round: Option<RoundStats>,
 
         stage_id: GameEntityId,
     }
@@ -30,34 +46,53 @@ pub mod match_manager {
             stage_id: GameEntityId,
             game_options: GameOptions,
             simulation_events: &SimulationStageEvents,
+            objective_zones: &[ObjectiveZone],
+            warmup: bool,
         ) -> Self {
             Self {
-                game_match: Match {
-                    ty: match game_options.ty {
+                game_match: Match::new(
+                    match game_options.ty {
                         GameType::Solo => MatchType::Solo,
                         GameType::Team => MatchType::Sided {
                             scores: Default::default(),
                         },
                     },
-                    state: MatchState::Running {
-                        round_ticks_passed: Default::default(),
-                    },
+                    &game_options,
+                    warmup,
+                ),
+                capture_points: if game_options.objectives.is_some() {
+                    vec![Default::default(); objective_zones.len()]
+                } else {
+                    Vec::new()
                 },
                 game_options,
                 simulation_events: simulation_events.clone(),
+                round_stats: Default::default(),
+                finished_round: None,
                 stage_id,
             }
         }
 
         fn handle_events(&mut self, world: &mut GameWorld) {
+            // kills & captures in the warmup don't count
+            if matches!(self.game_match.state, MatchState::Warmup { .. }) {
+                return;
+            }
             let game_match = &mut self.game_match;
             let game_options = &self.game_options;
+            let round_stats = &mut self.round_stats;
             self.simulation_events
-                .for_each(hi_closure!([game_match: &mut Match, game_options: &GameOptions, world: &mut GameWorld], |ev: &SimulationWorldEvent| -> () {
+                .for_each(hi_closure!([game_match: &mut Match, game_options: &GameOptions, world: &mut GameWorld, round_stats: &mut RoundStats], |ev: &SimulationWorldEvent| -> () {
                     match ev {
                         SimulationWorldEvent::Entity(entity_ev) => match &entity_ev.ev {
                             SimulationEventWorldEntityType::Character { ev, .. } => if let CharacterEvent::Despawn { killer_id, .. } = ev {
+                                if let Some(victim_id) = entity_ev.owner_id {
+                                    round_stats.player(&victim_id).deaths += 1;
+                                }
                                 if let Some(char) = killer_id.and_then(|killer_id| world.characters.get_mut(&killer_id)) {
+                                    if Some(char.base.game_element_id) != entity_ev.owner_id {
+                                        round_stats.player(&char.base.game_element_id).kills += 1;
+                                    }
                                     char.core.score += 1;
                                     if let (MatchType::Sided { scores }, Some(team)) = (&mut game_match.ty, char.core.side) {
                                         scores[team as usize] += 1;
@@ -69,6 +104,12 @@ pub mod match_manager {
                                 match ev {
                                     FlagEvent::Capture { .. } => {
                                         if let Some(char) = entity_ev.owner_id.and_then(|character_id| world.characters.get_mut(&character_id)) {
+                                            round_stats.player(&char.base.game_element_id).captures += 1;
+                                            round_stats.captures.push(RoundCapture {
+                                                tick: game_match.state.passed_ticks(),
+                                                player_name: char.player_info.player_info.name.to_string(),
+                                                side: char.core.side,
+                                            });
                                             char.core.score += 5;
                                             if let (MatchType::Sided { scores }, Some(team)) = (&mut game_match.ty, char.core.side) {
                                                 scores[team as usize] += 100;
@@ -100,31 +141,87 @@ pub mod match_manager {
                 }));
         }
 
+        /// Kills all characters, so that they respawn for the new round.
+        fn start_round(&mut self, world: &mut GameWorld) {
+            self.game_match.state = MatchState::Running {
+                round_ticks_passed: Default::default(),
+            };
+            self.simulation_events
+                .push(SimulationWorldEvent::Global(GameWorldGlobalEvent::Stat(
+                    GameWorldStatEvent::RoundStart,
+                )));
+            world.characters.iter_mut().for_each(|(id, char)| {
+                char.die(None, GameWorldActionKillWeapon::World);
+                for ev in char.entity_events.drain(..) {
+                    self.simulation_events.push(SimulationWorldEvent::Entity(
+                        SimulationEventWorldEntity {
+                            ev: SimulationEventWorldEntityType::Character { ev },
+                            owner_id: Some(*id),
+                        },
+                    ));
+                }
+            });
+        }
+
         /// returns true, if match needs a restart
         #[must_use]
-        pub fn tick(&mut self, world: &mut GameWorld) -> bool {
-            if matches!(self.game_match.ty, MatchType::Sided { .. }) {
+        pub fn tick(&mut self, world: &mut GameWorld, objective_zones: &[ObjectiveZone]) -> bool {
+            // nothing of the match changes while paused
+            if matches!(self.game_match.state, MatchState::Paused { .. }) {
+                return false;
+            }
+            if let MatchState::Warmup { round_starts_in } = &self.game_match.state {
+                if round_starts_in.is_none() {
+                    self.start_round(world);
+                    return true;
+                }
+            }
+
+            if let Some(freeze_tag) = &self.game_options.freeze_tag {
+                if let Some(frozen_side) = FreezeTagController::tick(world, freeze_tag) {
+                    self.game_match.side_won(match frozen_side {
+                        MatchSide::Red => MatchSide::Blue,
+                        MatchSide::Blue => MatchSide::Red,
+                    });
+                }
+            } else if matches!(self.game_match.ty, MatchType::Sided { .. }) {
                 CtfController::tick(world);
             }
 
+            let was_game_over = matches!(self.game_match.state, MatchState::GameOver { .. });
+            // the held capture points score over time
+            let objectives = self
+                .game_options
+                .objectives
+                .filter(|_| self.game_match.state.is_round_running());
+            if let Some(objectives) = &objectives {
+                let gained = ObjectiveController::tick(
+                    world,
+                    objective_zones,
+                    &mut self.capture_points,
+                    objectives,
+                );
+                if let MatchType::Sided { scores } = &mut self.game_match.ty {
+                    scores[0] += gained[0];
+                    scores[1] += gained[1];
+                }
+                if gained != [0; 2] {
+                    self.game_match.win_check(&self.game_options, &[]);
+                }
+            }
             self.handle_events(world);
+            self.game_match
+                .time_limit_check(&self.game_options, &world.characters);
+            if !was_game_over && matches!(self.game_match.state, MatchState::GameOver { .. }) {
+                self.finished_round = Some(std::mem::take(&mut self.round_stats));
+                self.simulation_events.push(SimulationWorldEvent::Global(
+                    GameWorldGlobalEvent::Stat(GameWorldStatEvent::RoundEnd),
+                ));
+            }
 
             if let MatchState::GameOver { new_game_in, .. } = &mut self.game_match.state {
                 if new_game_in.tick().unwrap_or_default() {
-                    self.game_match.state = MatchState::Running {
-                        round_ticks_passed: Default::default(),
-                    };
-                    world.characters.iter_mut().for_each(|(id, char)| {
-                        char.die(None, GameWorldActionKillWeapon::World);
-                        for ev in char.entity_events.drain(..) {
-                            self.simulation_events.push(SimulationWorldEvent::Entity(
-                                SimulationEventWorldEntity {
-                                    ev: SimulationEventWorldEntityType::Character { ev },
-                                    owner_id: Some(*id),
-                                },
-                            ));
-                        }
-                    });
+                    self.start_round(world);
                     true
                 } else {
                     false
@@ -1,17 +1,23 @@
 /// everything related to a single match/round/race-run
 pub mod match_state {
+    use std::cmp::Ordering;
+
     use game_interface::types::{
         game::{GameEntityId, GameTickCooldown, GameTickType},
-        render::game::game_match::MatchSide,
+        render::game::game_match::{MatchRoundTimer, MatchSide},
     };
     use hiarc::Hiarc;
     use serde::{Deserialize, Serialize};
 
     use crate::{
-        entities::character::character::Character, state::state::TICKS_PER_SECOND,
+        entities::character::character::{Character, Characters},
+        state::state::TICKS_PER_SECOND,
         types::types::GameOptions,
     };
 
+    /// How long the game over screen is shown until the next round starts.
+    const TICKS_UNTIL_NEW_GAME: GameTickType = TICKS_PER_SECOND * 4;
+
     #[derive(Debug, Hiarc, Serialize, Deserialize, Clone, Copy)]
     pub enum MatchWinner {
         Player(GameEntityId),
@@ -20,10 +26,19 @@ pub mod match_state {
 
     #[derive(Debug, Hiarc, Serialize, Deserialize, Clone, Copy)]
     pub enum MatchState {
+        /// Players warm up before the round starts,
+        /// scores don't count.
+        Warmup { round_starts_in: GameTickCooldown },
         Running {
             /// How long the game round is running.
             round_ticks_passed: GameTickType,
         },
+        /// The time limit was hit without a leader,
+        /// the round goes on until someone leads.
+        SuddenDeath {
+            /// How long the game round is running.
+            round_ticks_passed: GameTickType,
+        },
         Paused {
             /// How long the game round is running.
             round_ticks_passed: GameTickType,
@@ -39,13 +54,36 @@ pub mod match_state {
     impl MatchState {
         pub fn passed_ticks(&self) -> GameTickType {
             match self {
+                MatchState::Warmup { .. } => 0,
                 MatchState::Running { round_ticks_passed } => *round_ticks_passed,
+                MatchState::SuddenDeath { round_ticks_passed } => *round_ticks_passed,
                 MatchState::Paused { round_ticks_passed } => *round_ticks_passed,
                 MatchState::GameOver {
                     round_ticks_passed, ..
                 } => *round_ticks_passed,
             }
         }
+
+        /// Scores only count in these states.
+        pub fn is_round_running(&self) -> bool {
+            matches!(
+                self,
+                MatchState::Running { .. } | MatchState::SuddenDeath { .. }
+            )
+        }
+
+        pub fn round_timer(&self, time_limit_ticks: GameTickType) -> MatchRoundTimer {
+            match self {
+                MatchState::Warmup { round_starts_in } => MatchRoundTimer::Warmup {
+                    ticks_left: round_starts_in.ticks_left(),
+                },
+                MatchState::SuddenDeath { .. } => MatchRoundTimer::SuddenDeath,
+                state => MatchRoundTimer::Running {
+                    ticks_left: (time_limit_ticks > 0)
+                        .then(|| time_limit_ticks.saturating_sub(state.passed_ticks())),
+                },
+            }
+        }
     }
 
     #[derive(Debug, Hiarc, Serialize, Deserialize, Clone, Copy)]
@@ -62,7 +100,21 @@ pub mod match_state {
     }
 
     impl Match {
-        // TODO: sudden death
+        pub fn new(ty: MatchType, game_options: &GameOptions, warmup: bool) -> Self {
+            Self {
+                ty,
+                state: if warmup && game_options.warmup_ticks > 0 {
+                    MatchState::Warmup {
+                        round_starts_in: game_options.warmup_ticks.into(),
+                    }
+                } else {
+                    MatchState::Running {
+                        round_ticks_passed: Default::default(),
+                    }
+                },
+            }
+        }
+
         pub fn win_check(
             &mut self,
             game_options: &GameOptions,
@@ -70,7 +122,6 @@ pub mod match_state {
         ) {
             let cur_tick = self.state.passed_ticks();
             // TODO: random 4 seconds
-            const TICKS_UNTIL_NEW_GAME: GameTickType = TICKS_PER_SECOND * 4;
             match self.ty {
                 MatchType::Solo => {
                     // check if the character has hit a specific score
@@ -107,9 +158,93 @@ pub mod match_state {
             }
         }
 
+        /// Pauses a running round, returns `false` if no round is running.
+        pub fn pause(&mut self) -> bool {
+            if !self.state.is_round_running() {
+                return false;
+            }
+            self.state = MatchState::Paused {
+                round_ticks_passed: self.state.passed_ticks(),
+            };
+            true
+        }
+
+        /// Resumes a paused round, returns `false` if the round was not paused.
+        /// A round that was in sudden death goes back to it by the next time limit check.
+        pub fn resume(&mut self) -> bool {
+            let MatchState::Paused { round_ticks_passed } = self.state else {
+                return false;
+            };
+            self.state = MatchState::Running { round_ticks_passed };
+            true
+        }
+
+        /// Ends the round with a win of the given side,
+        /// regardless of the score limit (e.g. in freeze tag).
+        pub fn side_won(&mut self, side: MatchSide) {
+            if !self.state.is_round_running() {
+                return;
+            }
+            if let MatchType::Sided { scores } = &mut self.ty {
+                scores[side as usize] += 1;
+            }
+            self.state = MatchState::GameOver {
+                winner: MatchWinner::Side(side),
+                new_game_in: TICKS_UNTIL_NEW_GAME.into(),
+                round_ticks_passed: self.state.passed_ticks(),
+            };
+        }
+
+        /// Once the time limit is hit, the leading side or player wins.
+        /// Without a leader the round goes into sudden death,
+        /// until someone leads.
+        pub fn time_limit_check(&mut self, game_options: &GameOptions, characters: &Characters) {
+            let round_ticks_passed = match self.state {
+                MatchState::Running { round_ticks_passed }
+                    if game_options.time_limit_ticks > 0
+                        && round_ticks_passed >= game_options.time_limit_ticks =>
+                {
+                    round_ticks_passed
+                }
+                MatchState::SuddenDeath { round_ticks_passed } => round_ticks_passed,
+                _ => return,
+            };
+            let winner = match self.ty {
+                MatchType::Solo => {
+                    let best_score = characters.values().map(|char| char.core.score).max();
+                    let mut leaders = characters
+                        .values()
+                        .filter(|char| Some(char.core.score) == best_score);
+                    match (leaders.next(), leaders.next()) {
+                        (Some(leader), None) => {
+                            Some(MatchWinner::Player(leader.base.game_element_id))
+                        }
+                        _ => None,
+                    }
+                }
+                MatchType::Sided { scores } => match scores[0].cmp(&scores[1]) {
+                    Ordering::Greater => Some(MatchWinner::Side(MatchSide::Red)),
+                    Ordering::Less => Some(MatchWinner::Side(MatchSide::Blue)),
+                    Ordering::Equal => None,
+                },
+            };
+            self.state = match winner {
+                Some(winner) => MatchState::GameOver {
+                    winner,
+                    new_game_in: TICKS_UNTIL_NEW_GAME.into(),
+                    round_ticks_passed,
+                },
+                None => MatchState::SuddenDeath { round_ticks_passed },
+            };
+        }
+
         pub fn tick(&mut self) {
             match &mut self.state {
-                MatchState::Running { round_ticks_passed } => {
+                MatchState::Warmup { round_starts_in } => {
+                    round_starts_in.tick();
+                }
+                MatchState::Running { round_ticks_passed }
+                | MatchState::SuddenDeath { round_ticks_passed } => {
                     *round_ticks_passed += 1;
                 }
                 MatchState::Paused { .. } => {
@@ -0,0 +1,177 @@
+pub mod objective_controller {
+    use game_interface::types::{
+        game::GameTickType,
+        render::game::game_match::{CapturePointRenderInfo, MatchSide},
+    };
+    use hiarc::Hiarc;
+    use math::math::vector::{ivec2, vec2};
+    use serde::{Deserialize, Serialize};
+
+    use crate::{types::types::GameOptionsObjectives, world::world::GameWorld};
+
+    /// Connected capture point tiles of the map,
+    /// each zone is a single capture point.
+    #[derive(Debug, Hiarc, Clone)]
+    pub struct ObjectiveZone {
+        pub tiles: Vec<ivec2>,
+    }
+
+    impl ObjectiveZone {
+        /// Groups the tiles to zones, tiles that touch each other
+        /// (also diagonally) belong to the same zone.
+        pub fn from_tiles(mut tiles: Vec<ivec2>) -> Vec<Self> {
+            let mut zones = Vec::new();
+            while let Some(tile) = tiles.pop() {
+                let mut zone = vec![tile];
+                let mut i = 0;
+                while i < zone.len() {
+                    let cur = zone[i];
+                    let (neighbours, rest): (Vec<_>, Vec<_>) = tiles
+                        .into_iter()
+                        .partition(|t| (t.x - cur.x).abs() <= 1 && (t.y - cur.y).abs() <= 1);
+                    tiles = rest;
+                    zone.extend(neighbours);
+                    i += 1;
+                }
+                // deterministic order, independent of the map's tile order
+                zone.sort_by_key(|t| (t.y, t.x));
+                zones.push(Self { tiles: zone });
+            }
+            zones.sort_by_key(|zone| (zone.tiles[0].y, zone.tiles[0].x));
+            zones
+        }
+
+        /// Whether the position (in world units) is inside the zone.
+        pub fn contains(&self, pos: &vec2) -> bool {
+            let tile = ivec2::new((pos.x / 32.0).floor() as i32, (pos.y / 32.0).floor() as i32);
+            self.tiles.contains(&tile)
+        }
+
+        /// The center of the zone in tiles.
+        pub fn center(&self) -> vec2 {
+            let sum = self.tiles.iter().fold(vec2::default(), |sum, tile| {
+                sum + vec2::new(tile.x as f32 + 0.5, tile.y as f32 + 0.5)
+            });
+            sum / self.tiles.len().max(1) as f32
+        }
+    }
+
+    /// The state of a capture point, part of the snapshot.
+    #[derive(Debug, Hiarc, Default, Clone, Copy, Serialize, Deserialize)]
+    pub struct CapturePoint {
+        pub owner: Option<MatchSide>,
+        /// The side that is capturing the point.
+        pub capturing_side: Option<MatchSide>,
+        /// How many ticks [`Self::capturing_side`] stood in the zone.
+        pub progress: GameTickType,
+        /// Both sides stand in the zone.
+        pub contested: bool,
+        /// Ticks since the owner scored the last time.
+        pub score_ticks: GameTickType,
+    }
+
+    impl CapturePoint {
+        /// Advances the capture progress by the sides standing in the zone.
+        /// A contested point keeps its state, the progress of
+        /// a point that nobody captures decays.
+        ///
+        /// Returns the owner, if it scores this tick.
+        pub fn tick(
+            &mut self,
+            red_present: bool,
+            blue_present: bool,
+            options: &GameOptionsObjectives,
+        ) -> Option<MatchSide> {
+            self.contested = red_present && blue_present;
+            let present = match (red_present, blue_present) {
+                (true, false) => Some(MatchSide::Red),
+                (false, true) => Some(MatchSide::Blue),
+                _ => None,
+            };
+            match present {
+                Some(side) if self.owner != Some(side) => {
+                    if self.capturing_side.unwrap_or(side) == side {
+                        self.capturing_side = Some(side);
+                        self.progress += 1;
+                        if self.progress >= options.capture_ticks {
+                            self.owner = Some(side);
+                            self.capturing_side = None;
+                            self.progress = 0;
+                            self.score_ticks = 0;
+                        }
+                    } else {
+                        // the progress of the other side has to be undone first
+                        self.decay();
+                    }
+                }
+                Some(_) => self.decay(),
+                None if !self.contested => self.decay(),
+                None => {
+                    // contested, nothing changes
+                }
+            }
+
+            let owner = self.owner?;
+            self.score_ticks += 1;
+            (self.score_ticks >= options.score_ticks).then(|| {
+                self.score_ticks = 0;
+                owner
+            })
+        }
+
+        fn decay(&mut self) {
+            self.progress = self.progress.saturating_sub(1);
+            if self.progress == 0 {
+                self.capturing_side = None;
+            }
+        }
+
+        pub fn render_info(
+            &self,
+            zone: &ObjectiveZone,
+            options: &GameOptionsObjectives,
+        ) -> CapturePointRenderInfo {
+            CapturePointRenderInfo {
+                pos: zone.center(),
+                owner: self.owner,
+                capturing_side: self.capturing_side,
+                progress: self.progress as f32 / options.capture_ticks.max(1) as f32,
+                contested: self.contested,
+            }
+        }
+    }
+
+    /// Capture points of king of the hill like game types,
+    /// the owners of the points score over time.
+    #[derive(Debug, Hiarc, Default)]
+    pub struct ObjectiveController {}
+
+    impl ObjectiveController {
+        /// Returns the score each side gained this tick.
+        pub fn tick(
+            world: &GameWorld,
+            zones: &[ObjectiveZone],
+            capture_points: &mut [CapturePoint],
+            options: &GameOptionsObjectives,
+        ) -> [i64; 2] {
+            let mut scores = [0; 2];
+            for (zone, capture_point) in zones.iter().zip(capture_points.iter_mut()) {
+                let side_present = |side: MatchSide| {
+                    world.characters.values().any(|character| {
+                        character.core.side == Some(side)
+                            && !character.is_frozen()
+                            && zone.contains(character.pos.pos())
+                    })
+                };
+                if let Some(side) = capture_point.tick(
+                    side_present(MatchSide::Red),
+                    side_present(MatchSide::Blue),
+                    options,
+                ) {
+                    scores[side as usize] += 1;
+                }
+            }
+            scores
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::{ops::Range, rc::Rc, str::FromStr};
+
+use anyhow::anyhow;
+use command_parser::parser::{CommandArg, Syn};
+use game_interface::{
+    rcon_commands::{AuthLevel, RconCommands},
+    types::game::GameEntityId,
+};
+use hashlink::LinkedHashMap;
+
+use crate::state::state::GameState;
+
+/// Executes the command for the player that used it.
+/// The args are already validated by the parser.
+/// The returned text is the feedback for the player, if not empty.
+///
+/// `S` is the state the command changes, the game or e.g. the server
+/// for commands the game can't handle.
+pub type RconCmdCb<S = GameState> =
+    Rc<dyn Fn(&mut S, &GameEntityId, AuthLevel, &[(Syn, Range<usize>)]) -> anyhow::Result<String>>;
+
+pub struct RconCmd<S = GameState> {
+    pub name: String,
+    pub usage: String,
+    pub description: String,
+    /// The lowest auth level that is allowed to use the command.
+    pub auth_level: AuthLevel,
+    pub cmd: RconCmdCb<S>,
+
+    /// for parsing
+    pub args: Vec<CommandArg>,
+}
+
+/// All rcon commands the game supports,
+/// mods can add their own with [`RconCmds::register`].
+pub struct RconCmds<S = GameState> {
+    cmds: LinkedHashMap<String, RconCmd<S>>,
+}
+
+impl<S> Default for RconCmds<S> {
+    fn default() -> Self {
+        Self {
+            cmds: Default::default(),
+        }
+    }
+}
+
+impl<S> RconCmds<S> {
+    /// Replaces a command with the same name.
+    pub fn register(&mut self, cmd: RconCmd<S>) {
+        self.cmds.replace(cmd.name.clone(), cmd);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RconCmd<S>> {
+        self.cmds.get(name)
+    }
+
+    /// The commands for the parser.
+    pub fn rcon_commands(&self) -> RconCommands {
+        RconCommands {
+            cmds: self
+                .cmds
+                .values()
+                .map(|cmd| (cmd.name.clone(), cmd.args.clone()))
+                .collect(),
+        }
+    }
+
+    /// The usage & description of all commands the auth level is allowed to use.
+    pub fn help(&self, auth_level: AuthLevel) -> String {
+        self.cmds
+            .values()
+            .filter(|cmd| cmd.auth_level <= auth_level)
+            .map(|cmd| format!("{} - {}", cmd.usage, cmd.description))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The text of the arg, e.g. a name.
+pub fn arg_text(args: &[(Syn, Range<usize>)], index: usize) -> anyhow::Result<&str> {
+    match args.get(index) {
+        Some((Syn::Text(text) | Syn::Number(text), _)) => Ok(text),
+        _ => Err(anyhow!("argument {} is missing", index + 1)),
+    }
+}
+
+pub fn arg_number<T: FromStr>(args: &[(Syn, Range<usize>)], index: usize) -> anyhow::Result<T> {
+    let text = arg_text(args, index)?;
+    text.parse()
+        .map_err(|_| anyhow!("argument {} is not a valid number: {text}", index + 1))
+}
@@ -0,0 +1,81 @@
+/// statistics of a single round, exported when the round is over
+pub mod round_report {
+    use game_interface::types::{
+        game::{GameEntityId, GameTickType},
+        render::game::game_match::MatchSide,
+    };
+    use hashlink::LinkedHashMap;
+    use hiarc::Hiarc;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Hiarc, Default, Clone, Copy)]
+    pub struct RoundPlayerStats {
+        pub kills: u64,
+        pub deaths: u64,
+        pub captures: u64,
+    }
+
+    #[derive(Debug, Hiarc, Clone)]
+    pub struct RoundCapture {
+        pub tick: GameTickType,
+        pub player_name: String,
+        pub side: Option<MatchSide>,
+    }
+
+    /// Collects the statistics while the round is running.
+    #[derive(Debug, Hiarc, Default)]
+    pub struct RoundStats {
+        pub players: LinkedHashMap<GameEntityId, RoundPlayerStats>,
+        /// All flag captures in the order they happened.
+        pub captures: Vec<RoundCapture>,
+    }
+
+    impl RoundStats {
+        pub fn player(&mut self, id: &GameEntityId) -> &mut RoundPlayerStats {
+            self.players.entry(*id).or_default()
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub enum RoundReportWinner {
+        Player(String),
+        Side(MatchSide),
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct RoundReportPlayer {
+        pub name: String,
+        pub side: Option<MatchSide>,
+        pub score: i64,
+        pub kills: u64,
+        pub deaths: u64,
+        pub captures: u64,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct RoundReportCapture {
+        /// The tick of the round the capture happened in.
+        pub tick: GameTickType,
+        pub player_name: String,
+        pub side: Option<MatchSide>,
+    }
+
+    /// The report of a round that is over.
+    /// It is exported as JSON, so tournament tools can read it.
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct RoundReport {
+        pub map: String,
+        pub stage: String,
+        pub ticks_per_second: GameTickType,
+        /// How many ticks the round lasted.
+        pub round_ticks: GameTickType,
+        pub winner: RoundReportWinner,
+        /// The scores of the red & blue side, if the round was played in teams.
+        pub side_scores: Option<[i64; 2]>,
+        /// The final scores & statistics of all players in the stage,
+        /// sorted by the score.
+        pub players: Vec<RoundReportPlayer>,
+        /// Timeline of the flag captures.
+        pub captures: Vec<RoundReportCapture>,
+    }
+}
@@ -5,6 +5,7 @@ pub mod simulation_pipe {
     use game_interface::events::GameWorldGlobalEvent;
     use game_interface::pooling::GamePooling;
     use game_interface::types::game::GameEntityId;
+    use game_interface::types::render::game::game_match::MatchSide;
     use hashlink::{LinkedHashMap, LinkedHashSet};
     use hiarc::HiFnMut;
     use hiarc::{hiarc_safer_rc_refcell, Hiarc};
@@ -24,6 +25,7 @@ pub mod simulation_pipe {
     use crate::{
         entities::character::character::Characters,
         events::events::CharacterEvent,
+        types::types::{GameOptions, GameOptionsOverheal},
         world::world::{GameWorld, WorldPool},
     };
 
@@ -226,6 +228,8 @@ pub mod simulation_pipe {
         pub stage_id: &'a GameEntityId,
 
         pub world_pool: &'a mut WorldPool,
+
+        pub game_options: &'a GameOptions,
     }
 
     impl<'a> SimulationPipeStage<'a> {
@@ -234,12 +238,14 @@ pub mod simulation_pipe {
             collision: &'a Collision,
             stage_id: &'a GameEntityId,
             world_pool: &'a mut WorldPool,
+            game_options: &'a GameOptions,
         ) -> Self {
             Self {
                 is_prediction,
                 collision,
                 stage_id,
                 world_pool,
+                game_options,
             }
         }
     }
@@ -262,6 +268,12 @@ pub mod simulation_pipe {
             ) -> ControlFlow<()>,
         ) -> ControlFlow<()>;
         fn get_other_character_pos_by_id(&self, other_char_id: &GameEntityId) -> &vec2;
+        /// `None` if there is no other character with this id,
+        /// e.g. for the own or a removed character.
+        fn get_other_character_pos_and_side_by_id(
+            &self,
+            other_char_id: &GameEntityId,
+        ) -> Option<(&vec2, Option<MatchSide>)>;
         fn get_other_character_by_id_mut(&mut self, other_char_id: &GameEntityId)
             -> &mut Character;
         fn kill_character(&mut self, char_id: &GameEntityId);
@@ -337,11 +349,20 @@ pub mod simulation_pipe {
     pub struct SimulationPipePickup<'a> {
         pub characters: &'a mut Characters,
         pub field: &'a CharacterPositionPlayfield,
+        pub overheal: &'a GameOptionsOverheal,
     }
 
     impl<'a> SimulationPipePickup<'a> {
-        pub fn new(characters: &'a mut Characters, field: &'a CharacterPositionPlayfield) -> Self {
-            Self { characters, field }
+        pub fn new(
+            characters: &'a mut Characters,
+            field: &'a CharacterPositionPlayfield,
+            overheal: &'a GameOptionsOverheal,
+        ) -> Self {
+            Self {
+                characters,
+                field,
+                overheal,
+            }
         }
     }
 
@@ -375,6 +396,8 @@ pub mod simulation_pipe {
 
         pub characters_helper: SimulationPipeCharacters<'a>,
         pub field: &'a CharacterPositionPlayfield,
+        /// see [`crate::instagib`]
+        pub instagib: bool,
     }
 
     impl<'a> SimulationPipeLaser<'a> {
@@ -383,6 +406,7 @@ pub mod simulation_pipe {
             characters: &'a mut Characters,
             owner_character: GameEntityId,
             field: &'a CharacterPositionPlayfield,
+            instagib: bool,
         ) -> Self {
             Self {
                 collision,
@@ -391,6 +415,7 @@ pub mod simulation_pipe {
                     owner_character,
                 },
                 field,
+                instagib,
             }
         }
     }
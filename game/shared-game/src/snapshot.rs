@@ -2,6 +2,7 @@ pub mod snapshot {
     use std::{num::NonZeroU16, rc::Rc};
 
     use game_interface::types::{
+        character_info::CharacterInfoRedaction,
         emoticons::EnumCount,
         game::GameEntityId,
         id_gen::IdGenerator,
@@ -12,7 +13,10 @@ pub mod snapshot {
         weapons::WeaponType,
     };
     use hiarc::{hi_closure, Hiarc};
-    use math::math::vector::{ubvec4, vec2};
+    use math::math::{
+        distance_squared,
+        vector::{ubvec4, vec2},
+    };
     use shared_base::reusable::CloneWithCopyableElements;
 
     use crate::{
@@ -35,6 +39,7 @@ pub mod snapshot {
         },
         game_objects::game_objects::GameObjectDefinitions,
         match_state::match_state::{MatchState, MatchType},
+        objective_controller::objective_controller::CapturePoint,
         stage::stage::Stages,
         types::types::GameOptions,
         world::world::{GameObjectWorld, WorldPool},
@@ -61,14 +66,18 @@ pub mod snapshot {
     #[derive(Debug, Serialize, Deserialize)]
     pub enum SnapshotCharacterPlayerTy {
         None,
-        Player(PlayerNetworkStats),
+        /// The network stats are `None`, if they were dropped
+        /// by [`Snapshot::trim_to_budget`].
+        Player(Option<PlayerNetworkStats>),
     }
 
     #[derive(Debug, Serialize, Deserialize)]
     pub struct SnapshotCharacter {
         pub core: CharacterCore,
         pub reusable_core: PoolCharacterReusableCore,
-        pub player_info: PlayerInfo,
+        /// `None`, if it was dropped by [`Snapshot::trim_to_budget`],
+        /// the client keeps the last known info then.
+        pub player_info: Option<PlayerInfo>,
         pub ty: SnapshotCharacterPlayerTy,
         pub pos: vec2,
         pub hook: (Hook, Option<GameEntityId>),
@@ -184,11 +193,20 @@ pub mod snapshot {
     pub struct SnapshotMatchManager {
         ty: MatchType,
         state: MatchState,
+        capture_points: PoolVec<CapturePoint>,
     }
 
     impl SnapshotMatchManager {
-        pub fn new(ty: MatchType, state: MatchState) -> Self {
-            Self { ty, state }
+        pub fn new(
+            ty: MatchType,
+            state: MatchState,
+            capture_points: PoolVec<CapturePoint>,
+        ) -> Self {
+            Self {
+                ty,
+                state,
+                capture_points,
+            }
         }
     }
 
@@ -214,7 +232,8 @@ pub mod snapshot {
         pub no_char_type: NoCharPlayerType,
         pub player_info: PlayerInfo,
         pub player_input: CharacterInput,
-        pub network_stats: PlayerNetworkStats,
+        /// `None`, if it was dropped by [`Snapshot::trim_to_budget`].
+        pub network_stats: Option<PlayerNetworkStats>,
     }
 
     pub struct SnapshotWorldPool {
@@ -258,6 +277,7 @@ pub mod snapshot {
         pub(crate) stages_pool: Pool<LinkedHashMap<GameEntityId, SnapshotStage>>,
         no_char_players_pool: Pool<LinkedHashMap<GameEntityId, SnapshotNoCharPlayer>>,
         local_players_pool: Pool<LinkedHashMap<GameEntityId, SnapshotLocalPlayer>>,
+        capture_points_pool: Pool<Vec<CapturePoint>>,
         string_pool: StringPool,
     }
 
@@ -267,6 +287,7 @@ pub mod snapshot {
                 stages_pool: Pool::with_capacity(max_characters),
                 no_char_players_pool: Pool::with_capacity(max_characters),
                 local_players_pool: Pool::with_capacity(max_local_players),
+                capture_points_pool: Pool::with_capacity(2),
                 string_pool: Pool::with_capacity(8),
             }
         }
@@ -291,6 +312,105 @@ pub mod snapshot {
                 id_generator_id,
             }
         }
+
+        /// Removes the personal information of the players of the redaction.
+        pub fn redact(&mut self, redaction: &CharacterInfoRedaction) {
+            for stage in self.stages.values_mut() {
+                for (id, character) in stage.world.characters.iter_mut() {
+                    if let Some(player_info) = character
+                        .player_info
+                        .as_mut()
+                        .filter(|_| redaction.contains(id))
+                    {
+                        player_info.redact(id, redaction);
+                    }
+                }
+            }
+            for (id, player) in self
+                .no_char_players
+                .iter_mut()
+                .filter(|(id, _)| redaction.contains(id))
+            {
+                player.player_info.redact(id, redaction);
+            }
+        }
+
+        /// Drops low priority data until the encoded snapshot fits into `budget` bytes:
+        /// first the network stats (only used by the scoreboard), then the
+        /// player info (name, skin etc.) of the characters that are the farthest
+        /// away from the local players.
+        ///
+        /// Dropped fields are `None` instead of being removed, so the client
+        /// keeps its last known values.
+        /// Returns `true`, if anything was dropped.
+        pub fn trim_to_budget(&mut self, budget: usize) -> bool {
+            fn encoded_len<T: Serialize>(val: &T) -> usize {
+                bincode::serde::encode_to_vec(val, bincode::config::standard())
+                    .map(|bytes| bytes.len())
+                    .unwrap_or_default()
+            }
+
+            let len = encoded_len(&*self);
+            if len <= budget {
+                return false;
+            }
+            let mut excess = len - budget;
+
+            let network_stats = self
+                .stages
+                .values_mut()
+                .flat_map(|stage| stage.world.characters.values_mut())
+                .filter_map(|character| match &mut character.ty {
+                    SnapshotCharacterPlayerTy::None => None,
+                    SnapshotCharacterPlayerTy::Player(network_stats) => Some(network_stats),
+                })
+                .chain(
+                    self.no_char_players
+                        .values_mut()
+                        .map(|player| &mut player.network_stats),
+                );
+            for network_stats in network_stats {
+                if excess == 0 {
+                    return true;
+                }
+                if let Some(stats) = network_stats.take() {
+                    excess = excess.saturating_sub(encoded_len(&stats));
+                }
+            }
+
+            let local_positions: Vec<vec2> = self
+                .stages
+                .values()
+                .flat_map(|stage| stage.world.characters.values())
+                .filter(|character| self.local_players.contains_key(&character.game_el_id))
+                .map(|character| character.pos)
+                .collect();
+            let mut characters: Vec<(f32, &mut SnapshotCharacter)> = self
+                .stages
+                .values_mut()
+                .flat_map(|stage| stage.world.characters.values_mut())
+                .filter(|character| !self.local_players.contains_key(&character.game_el_id))
+                .map(|character| {
+                    let dist = local_positions
+                        .iter()
+                        .map(|pos| distance_squared(pos, &character.pos))
+                        .reduce(f32::min)
+                        .unwrap_or_default();
+                    (dist, character)
+                })
+                .collect();
+            characters.sort_by(|(dist1, _), (dist2, _)| dist2.total_cmp(dist1));
+            for (_, character) in characters {
+                if excess == 0 {
+                    break;
+                }
+                if let Some(player_info) = character.player_info.take() {
+                    excess = excess.saturating_sub(encoded_len(&player_info));
+                }
+            }
+
+            true
+        }
     }
 
     /// this is closely build like the type [`GameStateCreateOptions`]
@@ -332,11 +452,11 @@ pub mod snapshot {
                         hook: char.hook.get(),
                         game_el_id: char.base.game_element_id,
                         ty: if let Some(network_stats) = char.is_player_character() {
-                            SnapshotCharacterPlayerTy::Player(network_stats)
+                            SnapshotCharacterPlayerTy::Player(Some(network_stats))
                         } else {
                             SnapshotCharacterPlayerTy::None
                         },
-                        player_info: char.player_info.clone(),
+                        player_info: Some(char.player_info.clone()),
                     };
                     snap_char.reusable_core.copy_clone_from(&char.reusable_core);
                     characters.insert(*id, snap_char);
@@ -462,6 +582,13 @@ pub mod snapshot {
                         match_manager: SnapshotMatchManager::new(
                             stage.match_manager.game_match.ty,
                             stage.match_manager.game_match.state,
+                            {
+                                let mut capture_points =
+                                    self.snapshot_pool.capture_points_pool.new();
+                                capture_points
+                                    .extend(stage.match_manager.capture_points.iter().copied());
+                                capture_points
+                            },
                         ),
                         game_el_id: stage.game_element_id,
                         stage_name: self.snapshot_pool.string_pool.new_str(&stage.stage_name),
@@ -495,7 +622,9 @@ pub mod snapshot {
                             SnapshotLocalPlayer {
                                 is_dummy: p.player_info.is_dummy,
                                 input_cam_mode: match p.no_char_type {
-                                    NoCharPlayerType::Spectator => PlayerCameraMode::Free,
+                                    NoCharPlayerType::Spectator { follow } => {
+                                        game.spectator_cam_mode(follow)
+                                    }
                                     NoCharPlayerType::Dead { died_at_pos, .. } => {
                                         PlayerCameraMode::LockedTo(died_at_pos / 32.0)
                                     }
@@ -519,7 +648,7 @@ pub mod snapshot {
                         no_char_type: no_char_player.no_char_type,
                         player_info: no_char_player.player_info,
                         player_input: no_char_player.player_input,
-                        network_stats: no_char_player.network_stats,
+                        network_stats: Some(no_char_player.network_stats),
                     },
                 );
             }
@@ -623,6 +752,10 @@ pub mod snapshot {
                 let match_manager = &mut state_stage.match_manager;
                 match_manager.game_match.ty = snap_stage.match_manager.ty;
                 match_manager.game_match.state = snap_stage.match_manager.state;
+                match_manager.capture_points.clear();
+                match_manager
+                    .capture_points
+                    .extend(snap_stage.match_manager.capture_points.iter().copied());
 
                 // go through all characters of the stage, add missing ones
                 snap_stage.world.characters.values().for_each(|char| {
@@ -634,7 +767,7 @@ pub mod snapshot {
                         state_stage.world.add_character(
                             char.game_el_id,
                             &snap_stage_id,
-                            char.player_info.clone(),
+                            char.player_info.clone().unwrap_or_else(PlayerInfo::unknown),
                             Default::default(),
                             char.core.side,
                             match char.ty {
@@ -643,7 +776,7 @@ pub mod snapshot {
                                     CharacterPlayerTy::Player {
                                         players: players.clone(),
                                         no_char_players: no_char_players.clone(),
-                                        network_stats,
+                                        network_stats: network_stats.unwrap_or_default(),
                                         stage_id: snap_stage_id,
                                     }
                                 }
@@ -661,6 +794,7 @@ pub mod snapshot {
                         .characters
                         .to_back(&char.game_el_id)
                         .unwrap();
+                    let last_network_stats = stage_char.is_player_character();
                     stage_char.update_player_ty(
                         &snap_stage_id,
                         match char.ty {
@@ -669,7 +803,9 @@ pub mod snapshot {
                                 CharacterPlayerTy::Player {
                                     players: players.clone(),
                                     no_char_players: no_char_players.clone(),
-                                    network_stats,
+                                    network_stats: network_stats
+                                        .or(last_network_stats)
+                                        .unwrap_or_default(),
                                     stage_id: snap_stage_id,
                                 }
                             }
@@ -679,7 +815,9 @@ pub mod snapshot {
                     stage_char
                         .reusable_core
                         .copy_clone_from(&char.reusable_core);
-                    stage_char.player_info.clone_from(&char.player_info);
+                    if let Some(player_info) = &char.player_info {
+                        stage_char.player_info.clone_from(player_info);
+                    }
                 });
                 snap_stage.world.characters.values().for_each(|char| {
                     let stage_char = state_stage
@@ -708,6 +846,7 @@ pub mod snapshot {
                             proj.core.force,
                             proj.core.is_explosive,
                             proj.core.ty,
+                            proj.core.shot_id,
                         );
                     }
 
@@ -733,6 +872,7 @@ pub mod snapshot {
                             &proj.core.pos,
                             &proj.core.dir,
                             proj.core.energy,
+                            proj.core.shot_id,
                             proj.core.can_hit_others,
                             proj.core.can_hit_own,
                         );
@@ -873,6 +1013,16 @@ pub mod snapshot {
                     stage.world.characters.remove(&id);
                 }
 
+                let network_stats = p
+                    .network_stats
+                    .or_else(|| {
+                        write_game_state
+                            .game
+                            .no_char_players
+                            .player(&id)
+                            .map(|player| player.network_stats)
+                    })
+                    .unwrap_or_default();
                 write_game_state.game.no_char_players.insert(
                     id,
                     NoCharPlayer::new(
@@ -880,7 +1030,7 @@ pub mod snapshot {
                         p.player_input,
                         &id,
                         p.no_char_type,
-                        p.network_stats,
+                        network_stats,
                     ),
                 );
 
@@ -1,7 +1,160 @@
-use math::math::vector::vec2;
+use std::collections::VecDeque;
+
+use game_interface::types::render::game::game_match::MatchSide;
+use hiarc::Hiarc;
+use math::math::{distance_squared, vector::vec2, Rng};
+
+/// How many of the last deaths are remembered for
+/// [`SpawnStrategy::RandomWeightedByDeaths`].
+const RECENT_DEATHS_MAX: usize = 16;
+/// Deaths within this distance (10 tiles) count as near a spawn.
+const RECENT_DEATH_RANGE: f32 = 32.0 * 10.0;
+
+/// How the spawn point of a character is chosen.
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnStrategy {
+    /// The spawn farthest away from the nearest enemy,
+    /// teammates are ignored.
+    FarthestFromEnemies,
+    /// The spawn farthest away from the nearest character,
+    /// where enemies are considered nearer than teammates.
+    TeamSide,
+    /// A random spawn, spawns near recent deaths are less likely.
+    RandomWeightedByDeaths,
+}
+
+/// A character that influences the spawn evaluation.
+#[derive(Debug, Clone, Copy)]
+pub struct SpawnCharacter<'a> {
+    pub pos: &'a vec2,
+    pub side: Option<MatchSide>,
+}
+
+/// State of a world the spawn strategies depend on.
+#[derive(Debug, Hiarc)]
+pub struct SpawnHistory {
+    recent_deaths: VecDeque<vec2>,
+    /// Seeded the same for every world, so that the
+    /// random spawns are deterministic.
+    rng: Rng,
+}
+
+impl Default for SpawnHistory {
+    fn default() -> Self {
+        Self {
+            recent_deaths: VecDeque::with_capacity(RECENT_DEATHS_MAX),
+            rng: Rng::new(0),
+        }
+    }
+}
+
+impl SpawnHistory {
+    pub fn add_death(&mut self, pos: vec2) {
+        if self.recent_deaths.len() >= RECENT_DEATHS_MAX {
+            self.recent_deaths.pop_front();
+        }
+        self.recent_deaths.push_back(pos);
+    }
+}
 
 pub struct GameSpawns {
     pub spawns: Vec<vec2>,
     pub spawns_red: Vec<vec2>,
     pub spawns_blue: Vec<vec2>,
 }
+
+impl GameSpawns {
+    /// The spawns a character of `side` can use.
+    ///
+    /// On equally good spawns the first one is picked.
+    fn candidates(&self, side: Option<MatchSide>) -> Vec<&vec2> {
+        match side {
+            Some(MatchSide::Red) => self.spawns.iter().chain(self.spawns_red.iter()).collect(),
+            Some(MatchSide::Blue) => self.spawns.iter().chain(self.spawns_blue.iter()).collect(),
+            None => self
+                .spawns_blue
+                .iter()
+                .chain(self.spawns_red.iter())
+                .chain(self.spawns.iter())
+                .collect(),
+        }
+    }
+
+    /// Picks the spawn with the largest distance to the nearest character,
+    /// `weight` scales the (squared) distance per character.
+    fn farthest<'a>(
+        candidates: &[&vec2],
+        characters: &[SpawnCharacter<'a>],
+        weight: impl Fn(&SpawnCharacter<'a>) -> Option<f64>,
+    ) -> vec2 {
+        let nearest_dist = |spawn: &vec2| {
+            characters
+                .iter()
+                .filter_map(|char| {
+                    weight(char).map(|weight| distance_squared(spawn, char.pos) as f64 * weight)
+                })
+                .min_by(|f1, f2| f1.total_cmp(f2))
+                .unwrap_or_default()
+        };
+        // reversed, so that the first is picked if multiple are equally good
+        candidates
+            .iter()
+            .rev()
+            .max_by(|spawn1, spawn2| nearest_dist(spawn1).total_cmp(&nearest_dist(spawn2)))
+            .map(|&&spawn| spawn)
+            .unwrap_or_default()
+    }
+
+    fn random_weighted_by_deaths(candidates: &[&vec2], history: &mut SpawnHistory) -> vec2 {
+        let weights: Vec<f32> = candidates
+            .iter()
+            .map(|spawn| {
+                let near_deaths = history
+                    .recent_deaths
+                    .iter()
+                    .filter(|death| {
+                        distance_squared(spawn, death) <= RECENT_DEATH_RANGE * RECENT_DEATH_RANGE
+                    })
+                    .count();
+                1.0 / (1 + near_deaths) as f32
+            })
+            .collect();
+
+        let mut pick = history.rng.random_float() * weights.iter().sum::<f32>();
+        for (spawn, weight) in candidates.iter().zip(weights) {
+            if pick <= weight {
+                return **spawn;
+            }
+            pick -= weight;
+        }
+        candidates.last().map(|&&spawn| spawn).unwrap_or_default()
+    }
+
+    /// Evaluates the spawn position for a character of `side`.
+    pub fn spawn_pos(
+        &self,
+        strategy: SpawnStrategy,
+        side: Option<MatchSide>,
+        characters: &[SpawnCharacter],
+        history: &mut SpawnHistory,
+    ) -> vec2 {
+        let candidates = self.candidates(side);
+        match strategy {
+            SpawnStrategy::FarthestFromEnemies => Self::farthest(&candidates, characters, |char| {
+                (side.is_none() || char.side != side).then_some(1.0)
+            }),
+            SpawnStrategy::TeamSide => Self::farthest(&candidates, characters, |char| {
+                // multiply by factor so that players of the other side
+                // are considered near.
+                Some(if side.is_some() && char.side != side {
+                    0.5
+                } else {
+                    1.0
+                })
+            }),
+            SpawnStrategy::RandomWeightedByDeaths => {
+                Self::random_weighted_by_deaths(&candidates, history)
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use game_database::{
+    statement::{Statement, StatementBuilder},
+    traits::DbInterface,
+};
+
+/// A single schema change, e.g. creating a table
+/// or adding a column to an existing one.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// The version the schema has after this migration.
+    pub version: i64,
+    /// The MySQL statements of this migration, executed in order.
+    pub mysql: &'static [&'static str],
+}
+
+/// The versioned migrations of the schema of a mod.
+///
+/// The database keeps the version of every schema in its version table,
+/// so on startup only the migrations with a higher version are applied,
+/// all inside a single transaction.
+/// Applied migrations must never change, schema changes are always
+/// added as a new migration with a higher version.
+///
+/// Note that MySQL implicitly commits statements like `ALTER TABLE`,
+/// so a migration should only contain a single such statement
+/// or be written in a way that it can be applied again.
+#[derive(Debug)]
+pub struct Migrations {
+    schema: &'static str,
+    migrations: Vec<Migration>,
+}
+
+impl Migrations {
+    /// `schema` is the name the version is stored under,
+    /// it must be unique across all mods.
+    pub fn new(schema: &'static str) -> Self {
+        Self {
+            schema,
+            migrations: Default::default(),
+        }
+    }
+
+    pub fn with(mut self, version: i64, mysql: &'static [&'static str]) -> Self {
+        self.migrations.push(Migration { version, mysql });
+        self
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        let mut last_version = 0;
+        for migration in &self.migrations {
+            anyhow::ensure!(
+                migration.version > last_version,
+                "migration versions of schema \"{}\" must be ascending and start at 1, \
+                found version {} after version {}",
+                self.schema,
+                migration.version,
+                last_version
+            );
+            last_version = migration.version;
+        }
+        Ok(())
+    }
+
+    /// Brings the schema to the newest version.
+    pub async fn apply(&self, db: Arc<dyn DbInterface>) -> anyhow::Result<()> {
+        self.check()?;
+
+        // the statements must stay prepared until the setup is done
+        let mut stmts: Vec<Statement<(), ()>> = Default::default();
+        let mut versioned_stmts: BTreeMap<i64, Vec<u64>> = Default::default();
+        for migration in &self.migrations {
+            let ids = versioned_stmts.entry(migration.version).or_default();
+            for sql in migration.mysql {
+                let builder = StatementBuilder::<_, (), ()>::mysql(sql, |_| vec![]);
+                let stmt = Statement::new(db.clone(), builder).await?;
+                ids.push(stmt.unique_id);
+                stmts.push(stmt);
+            }
+        }
+
+        db.setup(self.schema, versioned_stmts).await
+    }
+}
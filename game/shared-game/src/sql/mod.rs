@@ -1,2 +1,6 @@
 pub mod account_info;
+pub mod migrations;
+pub mod player_report;
+pub mod round_report;
+pub mod rules_accepted;
 pub mod setup_ddnet;
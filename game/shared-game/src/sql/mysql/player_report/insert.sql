@@ -0,0 +1,4 @@
+INSERT INTO
+    player_report (map, reporter, reported, reason, context)
+VALUES
+    (?, ?, ?, ?, ?);
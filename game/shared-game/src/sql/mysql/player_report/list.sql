@@ -0,0 +1,14 @@
+SELECT
+    id,
+    map,
+    reporter,
+    reported,
+    reason,
+    context,
+    create_time
+FROM
+    player_report
+ORDER BY
+    id DESC
+LIMIT
+    ?;
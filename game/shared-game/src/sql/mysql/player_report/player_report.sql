@@ -0,0 +1,10 @@
+CREATE TABLE player_report (
+    id BIGINT NOT NULL AUTO_INCREMENT,
+    map VARCHAR(128) COLLATE utf8mb4_bin NOT NULL,
+    reporter VARCHAR(64) COLLATE utf8mb4_bin NOT NULL,
+    reported VARCHAR(64) COLLATE utf8mb4_bin NOT NULL,
+    reason VARCHAR(256) COLLATE utf8mb4_bin NOT NULL,
+    context LONGTEXT COLLATE utf8mb4_bin NOT NULL,
+    create_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id)
+);
@@ -0,0 +1,12 @@
+SELECT
+    id,
+    map,
+    reporter,
+    reported,
+    reason,
+    context,
+    create_time
+FROM
+    player_report
+WHERE
+    id = ?;
@@ -0,0 +1,4 @@
+INSERT IGNORE INTO
+    round_report (report_id, map, report)
+VALUES
+    (?, ?, ?);
@@ -0,0 +1,7 @@
+CREATE TABLE round_report (
+    report_id BINARY(32) NOT NULL,
+    map VARCHAR(128) COLLATE utf8mb4_bin NOT NULL,
+    report LONGTEXT COLLATE utf8mb4_bin NOT NULL,
+    create_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (report_id)
+);
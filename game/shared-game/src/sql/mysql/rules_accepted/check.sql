@@ -0,0 +1,7 @@
+SELECT
+    rules_accepted.account_id
+FROM
+    rules_accepted
+WHERE
+    rules_accepted.account_id = ?
+    AND rules_accepted.rules_hash = ?;
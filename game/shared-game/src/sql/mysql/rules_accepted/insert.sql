@@ -0,0 +1,4 @@
+INSERT IGNORE INTO
+    rules_accepted (account_id, rules_hash)
+VALUES
+    (?, ?);
@@ -0,0 +1,6 @@
+CREATE TABLE rules_accepted (
+    account_id BIGINT NOT NULL,
+    rules_hash BINARY(32) NOT NULL,
+    create_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (account_id, rules_hash)
+);
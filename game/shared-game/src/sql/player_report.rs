@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use game_database::{
+    statement::{Statement, StatementBuilder},
+    traits::DbInterface,
+    types::UnixUtcTimestamp,
+    StatementArgs, StatementResult,
+};
+
+use super::migrations::Migrations;
+
+pub fn migrations() -> Migrations {
+    Migrations::new("game-server-player-report")
+        .with(1, &[include_str!("mysql/player_report/player_report.sql")])
+}
+
+pub async fn setup(db: Arc<dyn DbInterface>) -> anyhow::Result<()> {
+    migrations().apply(db).await
+}
+
+#[derive(Debug, StatementArgs)]
+struct StatementInsertArg {
+    map: String,
+    reporter: String,
+    reported: String,
+    reason: String,
+    context: String,
+}
+
+#[derive(Debug, StatementArgs)]
+struct StatementListArg {
+    limit: i64,
+}
+
+#[derive(Debug, StatementArgs)]
+struct StatementViewArg {
+    id: i64,
+}
+
+#[derive(Debug, StatementResult)]
+pub struct StatementResult {
+    pub id: i64,
+    pub map: String,
+    pub reporter: String,
+    pub reported: String,
+    pub reason: String,
+    /// The context of the report, serialized as JSON.
+    pub context: String,
+    pub create_time: UnixUtcTimestamp,
+}
+
+/// The statements of the moderation queue,
+/// reports players send about other players.
+#[derive(Clone)]
+pub struct PlayerReports {
+    insert: Arc<Statement<StatementInsertArg, ()>>,
+    list: Arc<Statement<StatementListArg, StatementResult>>,
+    view: Arc<Statement<StatementViewArg, StatementResult>>,
+}
+
+impl PlayerReports {
+    pub async fn new(db: Arc<dyn DbInterface>) -> anyhow::Result<Self> {
+        let builder = StatementBuilder::<_, StatementInsertArg, ()>::mysql(
+            include_str!("mysql/player_report/insert.sql"),
+            |arg| vec![arg.map, arg.reporter, arg.reported, arg.reason, arg.context],
+        );
+        let insert = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        let builder = StatementBuilder::<_, StatementListArg, StatementResult>::mysql(
+            include_str!("mysql/player_report/list.sql"),
+            |arg| vec![arg.limit],
+        );
+        let list = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        let builder = StatementBuilder::<_, StatementViewArg, StatementResult>::mysql(
+            include_str!("mysql/player_report/view.sql"),
+            |arg| vec![arg.id],
+        );
+        let view = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        Ok(Self { insert, list, view })
+    }
+
+    /// Inserts a report, the context is serialized as JSON.
+    pub async fn insert(
+        &self,
+        map: String,
+        reporter: String,
+        reported: String,
+        reason: String,
+        context: String,
+    ) -> anyhow::Result<()> {
+        self.insert
+            .execute(StatementInsertArg {
+                map,
+                reporter,
+                reported,
+                reason,
+                context,
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// The latest `limit` reports, newest first.
+    pub async fn list(&self, limit: i64) -> anyhow::Result<Vec<StatementResult>> {
+        self.list.fetch_all(StatementListArg { limit }).await
+    }
+
+    pub async fn view(&self, id: i64) -> anyhow::Result<Option<StatementResult>> {
+        self.view.fetch_optional(StatementViewArg { id }).await
+    }
+}
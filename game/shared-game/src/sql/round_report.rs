@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use game_database::{
+    statement::{Statement, StatementBuilder},
+    traits::DbInterface,
+    StatementArgs,
+};
+
+use super::migrations::Migrations;
+
+pub fn migrations() -> Migrations {
+    Migrations::new("game-server-round-report")
+        .with(1, &[include_str!("mysql/round_report/round_report.sql")])
+}
+
+pub async fn setup(db: Arc<dyn DbInterface>) -> anyhow::Result<()> {
+    migrations().apply(db).await
+}
+
+#[derive(Debug, StatementArgs)]
+struct StatementArg {
+    report_id: Vec<u8>,
+    map: String,
+    report: String,
+}
+
+#[derive(Clone)]
+pub struct RoundReportInsert(Arc<Statement<StatementArg, ()>>);
+
+impl RoundReportInsert {
+    pub async fn new(db: Arc<dyn DbInterface>) -> anyhow::Result<Self> {
+        let builder = StatementBuilder::<_, StatementArg, ()>::mysql(
+            include_str!("mysql/round_report/insert.sql"),
+            |arg| vec![arg.report_id, arg.map, arg.report],
+        );
+
+        let stm = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        Ok(Self(stm))
+    }
+
+    /// Inserts the report, serialized as JSON.
+    pub async fn insert(
+        &self,
+        report_id: Vec<u8>,
+        map: String,
+        report: String,
+    ) -> anyhow::Result<()> {
+        self.0
+            .execute(StatementArg {
+                report_id,
+                map,
+                report,
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use accounts_types::account_id::AccountId;
+use base::hash::Hash;
+use game_database::{
+    statement::{Statement, StatementBuilder},
+    traits::DbInterface,
+    StatementArgs, StatementResult,
+};
+
+use super::migrations::Migrations;
+
+pub fn migrations() -> Migrations {
+    Migrations::new("game-server-rules-accepted").with(
+        1,
+        &[include_str!("mysql/rules_accepted/rules_accepted.sql")],
+    )
+}
+
+pub async fn setup(db: Arc<dyn DbInterface>) -> anyhow::Result<()> {
+    migrations().apply(db).await
+}
+
+#[derive(Debug, StatementArgs)]
+struct StatementArg {
+    account_id: AccountId,
+    rules_hash: Vec<u8>,
+}
+
+#[derive(Debug, StatementResult)]
+struct StatementResult {
+    account_id: i64,
+}
+
+/// The accounts that accepted the rules of the server.
+/// The rules are stored as hash, so changed rules
+/// have to be accepted again.
+#[derive(Clone)]
+pub struct RulesAccepted {
+    insert: Arc<Statement<StatementArg, ()>>,
+    check: Arc<Statement<StatementArg, StatementResult>>,
+}
+
+impl RulesAccepted {
+    pub async fn new(db: Arc<dyn DbInterface>) -> anyhow::Result<Self> {
+        let builder = StatementBuilder::<_, StatementArg, ()>::mysql(
+            include_str!("mysql/rules_accepted/insert.sql"),
+            |arg| vec![arg.account_id, arg.rules_hash],
+        );
+        let insert = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        let builder = StatementBuilder::<_, StatementArg, StatementResult>::mysql(
+            include_str!("mysql/rules_accepted/check.sql"),
+            |arg| vec![arg.account_id, arg.rules_hash],
+        );
+        let check = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        Ok(Self { insert, check })
+    }
+
+    pub async fn insert(&self, account_id: AccountId, rules_hash: &Hash) -> anyhow::Result<()> {
+        self.insert
+            .execute(StatementArg {
+                account_id,
+                rules_hash: rules_hash.to_vec(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Whether the account accepted exactly these rules before.
+    pub async fn is_accepted(
+        &self,
+        account_id: AccountId,
+        rules_hash: &Hash,
+    ) -> anyhow::Result<bool> {
+        Ok(self
+            .check
+            .fetch_optional(StatementArg {
+                account_id,
+                rules_hash: rules_hash.to_vec(),
+            })
+            .await?
+            .is_some())
+    }
+}
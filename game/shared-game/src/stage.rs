@@ -51,7 +51,13 @@ pub mod stage {
             let simulation_events = SimulationStageEvents::new();
             Self {
                 world: GameWorld::new(world_pool, game_object_definitions, width, height, id_gen),
-                match_manager: MatchManager::new(game_element_id, game_options, &simulation_events),
+                match_manager: MatchManager::new(
+                    game_element_id,
+                    game_options,
+                    &simulation_events,
+                    &game_object_definitions.objective_zones,
+                    true,
+                ),
                 stage_name,
                 stage_color,
                 simulation_events,
@@ -66,11 +72,19 @@ pub mod stage {
         pub fn tick(&mut self, pipe: &mut SimulationPipeStage) -> SimulationWorldEvents {
             self.match_manager.game_match.tick();
 
-            if let MatchState::Running { .. } = self.match_manager.game_match.state {
+            if let MatchState::Warmup { .. }
+            | MatchState::Running { .. }
+            | MatchState::SuddenDeath { .. } = self.match_manager.game_match.state
+            {
                 self.simulation_events
                     .push_entity_evs(self.world.tick(pipe));
             }
-            if !pipe.is_prediction && self.match_manager.tick(&mut self.world) {
+            if !pipe.is_prediction
+                && self.match_manager.tick(
+                    &mut self.world,
+                    &self.game_object_definitions.objective_zones,
+                )
+            {
                 self.world = GameWorld::new(
                     &self.world.world_pool,
                     &self.game_object_definitions,
@@ -79,8 +93,13 @@ pub mod stage {
                     self.world.id_generator.as_ref(),
                 );
                 let game_options = self.match_manager.game_options;
-                self.match_manager =
-                    MatchManager::new(self.game_element_id, game_options, &self.simulation_events);
+                self.match_manager = MatchManager::new(
+                    self.game_element_id,
+                    game_options,
+                    &self.simulation_events,
+                    &self.game_object_definitions.objective_zones,
+                    false,
+                );
             }
 
             self.simulation_events.take()
@@ -1,4 +1,5 @@
 pub mod state {
+    use std::collections::{HashMap, HashSet};
     use std::fmt::Write;
     use std::num::{NonZero, NonZeroU16, NonZeroU64};
     use std::rc::Rc;
@@ -6,9 +7,10 @@ pub mod state {
     use std::time::Duration;
 
     use accounts_types::account_id::AccountId;
+    use anyhow::anyhow;
     use base::hash::Hash;
     use base_io::io_batcher::{IoBatcher, IoBatcherTask};
-    use command_parser::parser::CommandType;
+    use command_parser::parser::{CommandArg, CommandArgType, CommandParseResult, CommandType};
     use game_database::traits::DbInterface;
     use game_interface::chat_commands::ChatCommands;
     use game_interface::client_commands::ClientCommand;
@@ -19,11 +21,15 @@ pub mod state {
         GamePickupArmorEventSound, GamePickupEvent, GamePickupHeartEvent,
         GamePickupHeartEventSound, GameShotgunEvent, GameShotgunEventSound, GameWorldAction,
         GameWorldEntityEvent, GameWorldEvent, GameWorldEvents, GameWorldGlobalEvent,
-        GameWorldPositionedEvent, GameWorldSystemMessage, KillFlags,
+        GameWorldPositionedEvent, GameWorldStatEvent, GameWorldSystemMessage, GameWorldVoteEvent,
+        KillFlags,
     };
     use game_interface::pooling::GamePooling;
     use game_interface::rcon_commands::{AuthLevel, RconCommands};
-    use game_interface::types::character_info::{NetworkCharacterInfo, NetworkSkinInfo};
+    use game_interface::server_commands::ServerCommand;
+    use game_interface::types::character_info::{
+        CharacterInfoRedaction, NetworkCharacterInfo, NetworkSkinInfo,
+    };
     use game_interface::types::emoticons::EmoticonType;
     use game_interface::types::game::{GameEntityId, GameTickCooldown, GameTickType};
     use game_interface::types::id_gen::IdGenerator;
@@ -31,6 +37,7 @@ pub mod state {
         CharacterInput, CharacterInputConsumableDiff, CharacterPredictionInput,
     };
     use game_interface::types::network_stats::PlayerNetworkStats;
+    use game_interface::types::network_string::NetworkString;
     use game_interface::types::pickup::PickupType;
     use game_interface::types::player_info::{PlayerClientInfo, PlayerDropReason, PlayerUniqueId};
     use game_interface::types::render::game::game_match::{
@@ -40,6 +47,7 @@ pub mod state {
     use game_interface::types::render::stage::StageRenderInfo;
     use game_interface::types::render::world::WorldRenderInfo;
     use game_interface::types::weapons::WeaponType;
+    use game_interface::votes::GameVoteAction;
     use hashlink::LinkedHashMap;
     use hiarc::hi_closure;
     use map::map::Map;
@@ -69,9 +77,10 @@ pub mod state {
     use pool::rc::PoolRc;
     use shared_base::mapdef_06::EEntityTiles;
 
-    use crate::collision::collision::Tunings;
+    use crate::bots::{Bot, BotBehaviorFactory, Bots};
+    use crate::chat_commands::{ChatCmd, ChatCmds};
     use crate::config::{ConfigGameType, ConfigVanilla};
-    use crate::entities::character::character::{self, CharacterPlayerTy};
+    use crate::entities::character::character::{self, CharacterPlayerTy, MAX_ARMOR, MAX_HEALTH};
     use crate::entities::character::player::player::{
         NoCharPlayer, NoCharPlayerType, NoCharPlayers, Player, PlayerInfo, Players,
     };
@@ -83,16 +92,26 @@ pub mod state {
         CharacterEvent, FlagEvent, LaserEvent, PickupEvent, ProjectileEvent,
     };
     use crate::game_objects::game_objects::GameObjectDefinitions;
-    use crate::match_state::match_state::{MatchState, MatchType};
+    use crate::instagib::instagib;
+    use crate::match_state::match_state::{MatchState, MatchType, MatchWinner};
+    use crate::rcon::{arg_text, RconCmd, RconCmds};
+    use crate::round_report::round_report::{
+        RoundReport, RoundReportCapture, RoundReportPlayer, RoundReportWinner, RoundStats,
+    };
     use crate::simulation_pipe::simulation_pipe::{
         SimulationEventWorldEntityType, SimulationEvents, SimulationWorldEvent,
         SimulationWorldEvents,
     };
     use crate::snapshot::snapshot::{Snapshot, SnapshotFor, SnapshotManager, SnapshotStage};
     use crate::sql::account_info::{AccountInfo, StatementResult};
+    use crate::sql::round_report::{self, RoundReportInsert};
+    use crate::sql::rules_accepted::{self, RulesAccepted};
     use crate::sql::setup_ddnet;
     use crate::stage::stage::Stages;
-    use crate::types::types::{GameOptions, GameType};
+    use crate::types::types::{
+        GameOptions, GameOptionsFreezeTag, GameOptionsObjectives, GameOptionsOverheal, GameType,
+    };
+    use crate::vote::{GameVote, GameVoteResult};
     use crate::weapons::definitions::weapon_def::Weapon;
     use crate::world::world::GameWorld;
 
@@ -128,11 +147,22 @@ pub mod state {
             player_id: GameEntityId,
             account_info: StatementResult,
         },
+        RulesAccepted {
+            player_id: GameEntityId,
+            accepted: bool,
+        },
+        /// `report` is only set if it could not be stored.
+        RoundReport {
+            report_id: Hash,
+            report: Option<String>,
+        },
     }
 
     pub struct GameDb {
         pub(crate) io_batcher: IoBatcher,
         pub(crate) account_info: Option<AccountInfo>,
+        pub(crate) round_report: Option<RoundReportInsert>,
+        pub(crate) rules_accepted: Option<RulesAccepted>,
 
         pub(crate) cur_queries: Vec<IoBatcherTask<GameDbQueries>>,
         pub(crate) cur_queries_helper: Vec<IoBatcherTask<GameDbQueries>>,
@@ -166,9 +196,29 @@ pub mod state {
         config: ConfigVanilla,
 
         pub(crate) chat_commands: ChatCommands,
+        chat_cmds: ChatCmds,
         pub(crate) rcon_commands: RconCommands,
+        rcon_cmds: RconCmds,
         map_name: String,
 
+        /// Players that joined, but did not accept the rules yet.
+        /// They stay as spectators until they do so.
+        rules_pending: HashSet<GameEntityId>,
+        /// Players that accepted the rules, so they can skip them when they rejoin.
+        /// Accounts are additionally stored in the database, see [`RulesAccepted`].
+        rules_accepted: HashSet<PlayerUniqueId>,
+        /// Ticks since the character of a player got a new input.
+        inactive_ticks: HashMap<GameEntityId, GameTickType>,
+        /// Ticks since the last spawn wave, see [`ConfigVanilla::spawn_wave_secs`].
+        spawn_wave_ticks: GameTickType,
+        /// Players that joined a running round and did not spawn yet.
+        /// Their first character gets a short spawn protection.
+        late_joiners: HashSet<GameEntityId>,
+        /// The vote that is currently running, see [`ClientCommand::StartVote`].
+        vote: Option<GameVote>,
+        /// Server side characters, see [`Self::add_bot`].
+        bots: Bots,
+
         // db
         game_db: GameDb,
 
@@ -196,13 +246,35 @@ pub mod state {
             let db_task = io_batcher.spawn(async move {
                 setup_ddnet::setup(db.clone()).await?;
 
-                let acc_info = AccountInfo::new(db).await;
+                let acc_info = AccountInfo::new(db.clone()).await;
                 if let Err(err) = &acc_info {
                     log::warn!(
                         target: "sql", 
                         "failed to prepare account info sql: {}", err);
                 }
-                acc_info
+
+                let round_report = async {
+                    round_report::setup(db.clone()).await?;
+                    RoundReportInsert::new(db.clone()).await
+                }
+                .await;
+                if let Err(err) = &round_report {
+                    log::warn!(
+                        target: "sql",
+                        "failed to prepare round report sql: {}", err);
+                }
+
+                let rules_accepted = async {
+                    rules_accepted::setup(db.clone()).await?;
+                    RulesAccepted::new(db).await
+                }
+                .await;
+                if let Err(err) = &rules_accepted {
+                    log::warn!(
+                        target: "sql",
+                        "failed to prepare rules accepted sql: {}", err);
+                }
+                anyhow::Ok((acc_info.ok(), round_report.ok(), rules_accepted.ok()))
             });
 
             let physics_group = Map::read_physics_group(&map).unwrap();
@@ -211,27 +283,16 @@ pub mod state {
             let h = physics_group.attr.height.get() as u32;
 
             let tiles = physics_group.get_game_layer_tiles();
-            let tune_tiles = physics_group.get_tune_layer_tiles();
             let collision = Collision::new(
                 w,
                 h,
                 tiles,
-                tune_tiles.map(|tune_tiles| {
-                    (
-                        {
-                            let mut tune_zones = vec![Tunings::default(); 256];
-                            // TODO: actually implement, not just for fun
-                            tune_zones[1] = Tunings {
-                                grenade_curvature: -70.0,
-                                ..Default::default()
-                            };
-                            tune_zones
-                        },
-                        tune_tiles.as_slice(),
-                    )
-                }),
+                physics_group
+                    .get_front_layer_tiles()
+                    .map(|tiles| tiles.as_slice()),
+                physics_group.get_tune_layer(),
             );
-            let game_objects = GameObjectDefinitions::new(tiles, w, h);
+            let mut game_objects = GameObjectDefinitions::new(tiles, w, h);
 
             let mut spawns: Vec<vec2> = Default::default();
             let mut spawns_red: Vec<vec2> = Default::default();
@@ -255,27 +316,28 @@ pub mod state {
                 .and_then(|config| serde_json::from_slice(&config).ok())
                 .unwrap_or_default();
 
+            // disabled weapons never spawn as pickup
+            for &weapon in &config.disabled_weapons {
+                game_objects.pickups.weapons[WeaponType::from(weapon) as usize].clear();
+            }
+            if config.instagib {
+                instagib::remove_pickups(&mut game_objects.pickups);
+            }
+
             let game_type = match config.game_type {
-                ConfigGameType::Ctf => GameType::Team,
+                ConfigGameType::Ctf | ConfigGameType::FreezeTag | ConfigGameType::Koth => {
+                    GameType::Team
+                }
                 ConfigGameType::Dm => GameType::Solo,
             };
 
-            let account_info = db_task.get_storage().ok();
+            let (account_info, round_report, rules_accepted) =
+                db_task.get_storage().unwrap_or_default();
 
-            let chat_commands = ChatCommands {
-                cmds: vec![("account_info".to_string(), vec![])]
-                    .into_iter()
-                    .collect(),
-                prefixes: vec!['/'],
-            };
-            let rcon_commands = RconCommands {
-                cmds: vec![
-                    ("info".to_string(), vec![]),
-                    ("cheat.all_weapons".to_string(), vec![]),
-                ]
-                .into_iter()
-                .collect(),
-            };
+            let chat_cmds = Self::default_chat_cmds();
+            let chat_commands = chat_cmds.chat_commands(vec!['/']);
+            let rcon_cmds = Self::default_rcon_cmds();
+            let rcon_commands = rcon_cmds.rcon_commands();
 
             let has_accounts = account_info.is_some();
 
@@ -308,19 +370,55 @@ pub mod state {
                 game_objects_definitions: Rc::new(game_objects),
                 pred_game_objects_definitions: Rc::new(GameObjectDefinitions {
                     pickups: Default::default(),
+                    objective_zones: Default::default(),
                 }),
 
                 // game
-                game_options: GameOptions::new(game_type, config.score_limit),
+                game_options: GameOptions::new(
+                    game_type,
+                    config.score_limit,
+                    GameOptionsOverheal {
+                        max_health: config.overheal_max_health,
+                        max_armor: config.overheal_max_armor,
+                        decay_ticks: config.overheal_decay_secs * TICKS_PER_SECOND,
+                    },
+                    matches!(config.game_type, ConfigGameType::FreezeTag).then_some(
+                        GameOptionsFreezeTag {
+                            unfreeze_ticks: config.freeze_tag_unfreeze_secs * TICKS_PER_SECOND,
+                        },
+                    ),
+                    matches!(config.game_type, ConfigGameType::Koth).then_some(
+                        GameOptionsObjectives {
+                            capture_ticks: config.koth_capture_secs * TICKS_PER_SECOND,
+                            score_ticks: config.koth_score_secs * TICKS_PER_SECOND,
+                        },
+                    ),
+                    config.instagib,
+                    config.spawn_strategy(),
+                    config.time_limit_secs * TICKS_PER_SECOND,
+                    config.warmup_secs * TICKS_PER_SECOND,
+                ),
                 config: config.clone(),
                 chat_commands: chat_commands.clone(),
+                chat_cmds,
                 rcon_commands: rcon_commands.clone(),
+                rcon_cmds,
                 map_name,
 
+                rules_pending: Default::default(),
+                rules_accepted: Default::default(),
+                inactive_ticks: Default::default(),
+                spawn_wave_ticks: 0,
+                late_joiners: Default::default(),
+                vote: None,
+                bots: Default::default(),
+
                 // db
                 game_db: GameDb {
                     io_batcher,
                     account_info,
+                    round_report,
+                    rules_accepted,
 
                     cur_queries: Default::default(),
                     cur_queries_helper: Default::default(),
@@ -350,12 +448,17 @@ pub mod state {
                     mod_name: match config.game_type {
                         ConfigGameType::Dm => "dm".try_into().unwrap(),
                         ConfigGameType::Ctf => "ctf".try_into().unwrap(),
+                        ConfigGameType::FreezeTag => "freeze_tag".try_into().unwrap(),
+                        ConfigGameType::Koth => "koth".try_into().unwrap(),
                     },
                     version: "pre-alpha".to_string(),
                     options: GameStateServerOptions {
                         physics_group_name: "vanilla".try_into().unwrap(),
                         allow_stages: config.allow_stages,
-                        use_vanilla_sides: matches!(config.game_type, ConfigGameType::Ctf),
+                        use_vanilla_sides: matches!(
+                            config.game_type,
+                            ConfigGameType::Ctf | ConfigGameType::FreezeTag | ConfigGameType::Koth
+                        ),
                         use_account_name: has_accounts,
                     },
                 },
@@ -441,7 +544,11 @@ pub mod state {
             // TODO: remove this log (move it somewhere)
             log::info!(target: "world", "added a character into side {:?}", side);
 
-            let pos = stage.world.get_spawn_pos(spawns, side);
+            let pos = stage.world.get_spawn_pos(
+                spawns,
+                side,
+                stage.match_manager.game_options.spawn_strategy,
+            );
 
             let char = stage.world.add_character(
                 *character_id,
@@ -490,6 +597,7 @@ pub mod state {
         }
 
         fn tick_impl(&mut self, is_prediction: bool) {
+            let mut finished_rounds = Vec::new();
             for stage in if !is_prediction {
                 &mut self.game.stages
             } else {
@@ -503,23 +611,246 @@ pub mod state {
                     &self.collision,
                     &stage_id,
                     &mut self.world_pool,
+                    &self.game_options,
                 );
 
                 if !is_prediction {
                     self.simulation_events
                         .insert_world_evs(stage_id, stage.tick(&mut sim_pipe));
+                    if let Some(round_stats) = stage.match_manager.finished_round.take() {
+                        finished_rounds.push((stage_id, round_stats));
+                    }
                 } else {
                     // ignore prediction events
                     let _ = stage.tick(&mut sim_pipe);
                 }
             }
+
+            for (stage_id, round_stats) in finished_rounds {
+                self.export_round_report(&stage_id, round_stats);
+            }
+        }
+
+        /// Pauses the running round of the stage, its entities are frozen
+        /// until [`Self::resume_stage`], but snapshots are still sent.
+        pub fn pause_stage(&mut self, stage_id: &GameEntityId) -> anyhow::Result<()> {
+            let stage = self
+                .game
+                .stages
+                .get_mut(stage_id)
+                .ok_or_else(|| anyhow!("the stage does not exist."))?;
+            anyhow::ensure!(
+                stage.match_manager.game_match.pause(),
+                "the round of the stage is not running."
+            );
+            stage.simulation_events.push(SimulationWorldEvent::Global(
+                GameWorldGlobalEvent::System(GameWorldSystemMessage::Custom(
+                    self.game_pools
+                        .mt_string_pool
+                        .new_str("The game is paused."),
+                )),
+            ));
+            Ok(())
+        }
+
+        /// Resumes a round that was paused by [`Self::pause_stage`].
+        pub fn resume_stage(&mut self, stage_id: &GameEntityId) -> anyhow::Result<()> {
+            let stage = self
+                .game
+                .stages
+                .get_mut(stage_id)
+                .ok_or_else(|| anyhow!("the stage does not exist."))?;
+            anyhow::ensure!(
+                stage.match_manager.game_match.resume(),
+                "the stage is not paused."
+            );
+            stage.simulation_events.push(SimulationWorldEvent::Global(
+                GameWorldGlobalEvent::System(GameWorldSystemMessage::Custom(
+                    self.game_pools
+                        .mt_string_pool
+                        .new_str("The game is resumed."),
+                )),
+            ));
+            Ok(())
+        }
+
+        fn round_report(&self, stage_id: &GameEntityId, round_stats: RoundStats) -> RoundReport {
+            let stage = self.game.stages.get(stage_id).unwrap();
+            let game_match = &stage.match_manager.game_match;
+
+            let mut players: Vec<_> = stage
+                .world
+                .characters
+                .iter()
+                .map(|(id, character)| {
+                    (
+                        *id,
+                        character.player_info.player_info.name.to_string(),
+                        character.core.side,
+                        character.core.score,
+                    )
+                })
+                .collect();
+            // dead players are no characters, but still part of the round
+            let mut no_char_players = self.no_char_player_clone_pool.new();
+            self.game
+                .no_char_players
+                .pooled_clone_into(&mut no_char_players);
+            players.extend(no_char_players.iter().filter_map(|(id, player)| {
+                let NoCharPlayerType::Dead {
+                    side,
+                    score,
+                    stage_id: player_stage_id,
+                    ..
+                } = player.no_char_type
+                else {
+                    return None;
+                };
+                (player_stage_id == *stage_id).then(|| {
+                    (
+                        *id,
+                        player.player_info.player_info.name.to_string(),
+                        side,
+                        score,
+                    )
+                })
+            }));
+            players.sort_by(|(_, _, _, score1), (_, _, _, score2)| score2.cmp(score1));
+
+            let (winner, round_ticks) = match game_match.state {
+                MatchState::GameOver {
+                    winner,
+                    round_ticks_passed,
+                    ..
+                } => (Some(winner), round_ticks_passed),
+                state => (None, state.passed_ticks()),
+            };
+            let winner = match winner {
+                Some(MatchWinner::Player(id)) => RoundReportWinner::Player(
+                    players
+                        .iter()
+                        .find(|(player_id, ..)| *player_id == id)
+                        .map(|(_, name, ..)| name.clone())
+                        .unwrap_or_default(),
+                ),
+                Some(MatchWinner::Side(side)) => RoundReportWinner::Side(side),
+                None => RoundReportWinner::Player(Default::default()),
+            };
+
+            RoundReport {
+                map: self.map_name.clone(),
+                stage: stage.stage_name.clone(),
+                ticks_per_second: TICKS_PER_SECOND,
+                round_ticks,
+                winner,
+                side_scores: match game_match.ty {
+                    MatchType::Solo => None,
+                    MatchType::Sided { scores } => Some(scores),
+                },
+                players: players
+                    .into_iter()
+                    .map(|(id, name, side, score)| {
+                        let stats = round_stats.players.get(&id).copied().unwrap_or_default();
+                        RoundReportPlayer {
+                            name,
+                            side,
+                            score,
+                            kills: stats.kills,
+                            deaths: stats.deaths,
+                            captures: stats.captures,
+                        }
+                    })
+                    .collect(),
+                captures: round_stats
+                    .captures
+                    .into_iter()
+                    .map(|capture| RoundReportCapture {
+                        tick: capture.tick,
+                        player_name: capture.player_name,
+                        side: capture.side,
+                    })
+                    .collect(),
+            }
+        }
+
+        /// Writes the report of a round that just ended to the database
+        /// (or lets the server write it to disk) and tells the clients its id.
+        fn export_round_report(&mut self, stage_id: &GameEntityId, round_stats: RoundStats) {
+            let report = self.round_report(stage_id, round_stats);
+            let report = match serde_json::to_string(&report) {
+                Ok(report) => report,
+                Err(err) => {
+                    log::warn!(target: "sql", "failed to serialize round report: {err}");
+                    return;
+                }
+            };
+            let report_id = base::hash::generate_hash_for(report.as_bytes());
+
+            if let Some(round_report_insert) = self.game_db.round_report.clone() {
+                let map = self.map_name.clone();
+                self.game_db
+                    .cur_queries
+                    .push(self.game_db.io_batcher.spawn(async move {
+                        let res = round_report_insert
+                            .insert(report_id.to_vec(), map, report.clone())
+                            .await;
+                        if let Err(err) = &res {
+                            log::warn!(target: "sql", "failed to insert round report: {err}");
+                        }
+                        Ok(GameDbQueries::RoundReport {
+                            report_id,
+                            report: res.is_err().then_some(report),
+                        })
+                    }));
+            } else {
+                self.export_round_report_to_disk(report_id, &report);
+            }
+
+            self.game
+                .stages
+                .get(stage_id)
+                .unwrap()
+                .simulation_events
+                .push(SimulationWorldEvent::Global(GameWorldGlobalEvent::Stat(
+                    GameWorldStatEvent::RoundReport { report_id },
+                )));
+        }
+
+        /// The game has no file system access, so the server writes the report.
+        fn export_round_report_to_disk(&self, report_id: Hash, report: &str) {
+            self.game
+                .stages
+                .get(&self.stage_0_id)
+                .unwrap()
+                .simulation_events
+                .push(SimulationWorldEvent::Global(
+                    GameWorldGlobalEvent::ExportRoundReport {
+                        report_id,
+                        report: self.game_pools.mt_string_pool.new_str(report),
+                    },
+                ));
         }
 
-        fn on_character_spawn(world: &mut GameWorld, character_id: &GameEntityId) {
+        fn on_character_spawn(
+            world: &mut GameWorld,
+            character_id: &GameEntityId,
+            config: &ConfigVanilla,
+        ) {
             let character = world.characters.get_mut(character_id).unwrap();
-            let core = &mut character.core;
+            character.core.freeze_tag = matches!(config.game_type, ConfigGameType::FreezeTag);
+
+            if config.instagib {
+                instagib::equip_character(character);
+                return;
+            }
 
-            core.active_weapon = WeaponType::Gun;
+            let core = &mut character.core;
+            let gun_disabled = config.is_weapon_disabled(WeaponType::Gun);
+            core.active_weapon = if gun_disabled {
+                WeaponType::Hammer
+            } else {
+                WeaponType::Gun
+            };
 
             let gun = Weapon {
                 cur_ammo: Some(10),
@@ -533,20 +864,354 @@ pub mod state {
 
             let reusable_core = &mut character.reusable_core;
             reusable_core.weapons.insert(WeaponType::Hammer, hammer);
-            reusable_core.weapons.insert(WeaponType::Gun, gun);
+            if !gun_disabled {
+                reusable_core.weapons.insert(WeaponType::Gun, gun);
+            }
+        }
+
+        /// The players that are allowed to start votes & vote,
+        /// see [`ConfigVanilla::spectators_can_vote`].
+        fn voters(&self) -> HashSet<GameEntityId> {
+            let mut players = self.player_clone_pool.new();
+            self.game.players.pooled_clone_into(&mut players);
+            let mut no_char_players = self.no_char_player_clone_pool.new();
+            self.game
+                .no_char_players
+                .pooled_clone_into(&mut no_char_players);
+            players
+                .iter()
+                .map(|(id, _)| *id)
+                .chain(
+                    no_char_players
+                        .iter()
+                        .filter(|(id, player)| {
+                            !self.rules_pending.contains(id)
+                                && (self.config.spectators_can_vote
+                                    || matches!(player.no_char_type, NoCharPlayerType::Dead { .. }))
+                        })
+                        .map(|(id, _)| *id),
+                )
+                .collect()
+        }
+
+        /// Votes against players are only possible while they are on the server.
+        fn is_vote_target_valid(&self, action: &GameVoteAction) -> bool {
+            match action {
+                GameVoteAction::KickPlayer { player_id }
+                | GameVoteAction::SpecPlayer { player_id } => {
+                    self.game.players.player(player_id).is_some()
+                        || self.game.no_char_players.contains_key(player_id)
+                }
+                GameVoteAction::ChangeMap { .. } => true,
+            }
+        }
+
+        fn push_vote_event(&self, ev: GameWorldVoteEvent) {
+            self.game
+                .stages
+                .get(&self.stage_0_id)
+                .unwrap()
+                .simulation_events
+                .push(SimulationWorldEvent::Global(GameWorldGlobalEvent::Vote(ev)));
+        }
+
+        fn start_vote(&mut self, player_id: &GameEntityId, action: GameVoteAction) {
+            let against_self = matches!(
+                action,
+                GameVoteAction::KickPlayer { player_id: target }
+                | GameVoteAction::SpecPlayer { player_id: target } if target == *player_id
+            );
+            if self.vote.is_some()
+                || against_self
+                || !self.is_vote_target_valid(&action)
+                || !self.voters().contains(player_id)
+            {
+                return;
+            }
+            self.push_vote_event(GameWorldVoteEvent::Started {
+                action: action.clone(),
+                started_by: *player_id,
+                duration: Duration::from_secs(self.config.vote_secs),
+            });
+            self.vote = Some(GameVote::new(
+                action,
+                *player_id,
+                self.config.vote_secs * TICKS_PER_SECOND,
+            ));
+            self.push_vote_counts();
+        }
+
+        fn push_vote_counts(&self) {
+            if let Some(vote) = &self.vote {
+                let voters = self.voters();
+                let (yes, no) = vote.counts(&voters);
+                self.push_vote_event(GameWorldVoteEvent::Votes {
+                    yes,
+                    no,
+                    voters: voters.len() as u64,
+                });
+            }
+        }
+
+        fn vote_tick(&mut self) {
+            let Some(vote) = &mut self.vote else {
+                return;
+            };
+            let time_is_up = vote.tick();
+            let result = if self.is_vote_target_valid(&vote.action) {
+                vote.result(&self.voters(), self.config.vote_pass_percentage, time_is_up)
+            } else {
+                // e.g. the player left
+                GameVoteResult::Failed
+            };
+            if result == GameVoteResult::Running {
+                return;
+            }
+            let Some(vote) = self.vote.take() else {
+                return;
+            };
+            if result == GameVoteResult::Failed {
+                self.push_vote_event(GameWorldVoteEvent::Failed {
+                    action: vote.action,
+                });
+                return;
+            }
+
+            if let GameVoteAction::SpecPlayer { player_id } = &vote.action {
+                self.client_command(player_id, ClientCommand::JoinSpectator);
+                // dead players would respawn otherwise
+                self.game.no_char_players.handle_mut(
+                    player_id,
+                    hi_closure!([], |player: &mut NoCharPlayer| -> () {
+                        player.no_char_type = NoCharPlayerType::Spectator { follow: None };
+                    }),
+                );
+            }
+            // the server executes the other actions
+            self.push_vote_event(GameWorldVoteEvent::Passed {
+                action: vote.action,
+            });
+        }
+
+        /// Spectators follow their target, as long as it has a character.
+        pub(crate) fn spectator_cam_mode(&self, follow: Option<GameEntityId>) -> PlayerCameraMode {
+            match follow {
+                Some(target) if self.game.players.player(&target).is_some() => {
+                    PlayerCameraMode::FollowCharacter(target)
+                }
+                _ => PlayerCameraMode::Free,
+            }
+        }
+
+        /// The ticks between two spawn waves,
+        /// `None` if players respawn on their own.
+        fn spawn_wave_interval(&self) -> Option<GameTickType> {
+            let is_team_game = matches!(
+                self.config.game_type,
+                ConfigGameType::Ctf | ConfigGameType::FreezeTag | ConfigGameType::Koth
+            );
+            (is_team_game && self.config.spawn_wave_secs > 0)
+                .then_some(self.config.spawn_wave_secs * TICKS_PER_SECOND)
+        }
+
+        /// Whether the round in the first stage already started,
+        /// so that joining players might join into a running fight.
+        fn is_late_join(&self) -> bool {
+            self.game
+                .stages
+                .get(&self.stage_0_id)
+                .is_some_and(|stage| stage.match_manager.game_match.state.passed_ticks() > 0)
+        }
+
+        fn protect_late_joiner(
+            world: &mut GameWorld,
+            character_id: &GameEntityId,
+            config: &ConfigVanilla,
+        ) {
+            if let Some(character) = world.characters.get_mut(character_id) {
+                character.core.spawn_protection =
+                    (config.late_join_protection_secs * TICKS_PER_SECOND).into();
+            }
+        }
+
+        /// Adds a bot with the behavior of the given name to the stage.
+        /// Its character respawns like the one of a player,
+        /// until the bot is removed with [`Self::remove_bot`].
+        pub fn add_bot(
+            &mut self,
+            stage_id: &GameEntityId,
+            behavior: &str,
+        ) -> anyhow::Result<GameEntityId> {
+            anyhow::ensure!(
+                self.game.stages.contains_key(stage_id),
+                GameError::InvalidStage
+            );
+            let behavior = self.bots.behavior(behavior).ok_or_else(|| {
+                anyhow!(
+                    "unknown bot behavior {behavior}, available are: {}.",
+                    self.bots.behavior_names().collect::<Vec<_>>().join(", ")
+                )
+            })?;
+
+            let bot_id = self.id_generator.next_id();
+            self.bots.bots.insert(
+                bot_id,
+                Bot {
+                    behavior,
+                    stage_id: *stage_id,
+                    side: None,
+                    score: 0,
+                    respawn_in_ticks: Default::default(),
+                },
+            );
+            self.spawn_bot(&bot_id);
+            Ok(bot_id)
+        }
+
+        /// Adds a bot behavior or replaces the one with the same name,
+        /// e.g. for mods that build on top of vanilla.
+        pub fn register_bot_behavior(&mut self, name: &str, factory: BotBehaviorFactory) {
+            self.bots.register_behavior(name, factory);
+        }
+
+        pub fn remove_bot(&mut self, bot_id: &GameEntityId) -> anyhow::Result<()> {
+            let bot = self
+                .bots
+                .bots
+                .remove(bot_id)
+                .ok_or_else(|| anyhow!("no bot with the id {bot_id} found."))?;
+            if let Some(stage) = self.game.stages.get_mut(&bot.stage_id) {
+                if let Some(character) = stage.world.characters.get_mut(bot_id) {
+                    character.despawn_completely_silent();
+                    stage.world.characters.remove(bot_id);
+                    stage.world.remove_entities_of_character(bot_id);
+                }
+            }
+            Ok(())
+        }
+
+        fn spawn_bot(&mut self, bot_id: &GameEntityId) {
+            let Some(bot) = self.bots.bots.get_mut(bot_id) else {
+                return;
+            };
+            // the stage of the bot might have been removed in the meantime
+            if !self.game.stages.contains_key(&bot.stage_id) {
+                bot.stage_id = self.stage_0_id;
+            }
+            let stage_id = bot.stage_id;
+            let stage = self.game.stages.get_mut(&stage_id).unwrap();
+
+            let side = match stage.match_manager.game_match.ty {
+                MatchType::Solo => None,
+                MatchType::Sided { .. } => Some(bot.side.unwrap_or_else(|| {
+                    stage
+                        .world
+                        .evaluate_character_side(&self.game.no_char_players, &stage_id)
+                })),
+            };
+            let pos = stage.world.get_spawn_pos(
+                &self.spawns,
+                side,
+                stage.match_manager.game_options.spawn_strategy,
+            );
+
+            let mut info = NetworkCharacterInfo::explicit_default();
+            info.name = NetworkString::new(format!("Bot {bot_id}")).unwrap_or_default();
+            info.clan = Default::default();
+            let player_info = PlayerInfo {
+                player_info: PoolRc::from_item_without_pool(info),
+                version: 1,
+                unique_identifier: PlayerUniqueId::CertFingerprint(Default::default()),
+                player_index: 0,
+                is_dummy: false,
+            };
+
+            let character = stage.world.add_character(
+                *bot_id,
+                &stage_id,
+                player_info,
+                Default::default(),
+                side,
+                CharacterPlayerTy::None,
+                pos,
+            );
+            character.core.score = bot.score;
+            Self::on_character_spawn(&mut stage.world, bot_id, &self.config);
+        }
+
+        /// Lets the behaviors of the bots decide the input of their characters
+        /// & respawns the characters of bots that died.
+        fn bot_tick(&mut self) {
+            let mut respawn_bots = Vec::new();
+            for (bot_id, bot) in self.bots.bots.iter_mut() {
+                let Some(stage) = self
+                    .game
+                    .stages
+                    .get_mut(&bot.stage_id)
+                    .filter(|stage| stage.world.characters.contains_key(bot_id))
+                else {
+                    match bot.respawn_in_ticks.tick() {
+                        // the character just died
+                        None => bot.respawn_in_ticks = (TICKS_PER_SECOND / 2).into(),
+                        Some(true) => respawn_bots.push(*bot_id),
+                        Some(false) => {}
+                    }
+                    continue;
+                };
+
+                let characters = &stage.world.characters;
+                let character = characters.get(bot_id).unwrap();
+                bot.side = character.core.side;
+                bot.score = character.core.score;
+                let mut input = character.core.input;
+                bot.behavior.input(character, characters, &mut input);
+                let diff = input.consumable.diff(&character.core.input.consumable);
+
+                stage.world.characters.get_mut(bot_id).unwrap().core.input = input;
+                if matches!(
+                    stage.match_manager.game_match.state,
+                    MatchState::Warmup { .. }
+                        | MatchState::Running { .. }
+                        | MatchState::SuddenDeath { .. }
+                        | MatchState::Paused { .. }
+                ) {
+                    stage
+                        .world
+                        .handle_character_input_change(&self.collision, bot_id, diff);
+                }
+            }
+
+            for bot_id in respawn_bots {
+                self.spawn_bot(&bot_id);
+            }
         }
 
         pub fn player_tick(&mut self) {
+            // with spawn waves, dead players only respawn together
+            let is_wave = match self.spawn_wave_interval() {
+                Some(interval) => {
+                    self.spawn_wave_ticks += 1;
+                    let is_wave = self.spawn_wave_ticks >= interval;
+                    if is_wave {
+                        self.spawn_wave_ticks = 0;
+                    }
+                    is_wave
+                }
+                None => true,
+            };
+
             let mut characters_to_spawn = self.no_char_player_clone_pool.new();
             let characters_to_spawn = &mut characters_to_spawn;
             self.game.no_char_players.retain_with_order(hi_closure!(
                 [
-                    characters_to_spawn: &mut PoolLinkedHashMap<GameEntityId, NoCharPlayer>
+                    characters_to_spawn: &mut PoolLinkedHashMap<GameEntityId, NoCharPlayer>,
+                    is_wave: bool
                 ],
                 |id: &GameEntityId, no_char_player: &mut NoCharPlayer| -> bool {
                     if let NoCharPlayerType::Dead {respawn_in_ticks, ..} = &mut no_char_player.no_char_type {
+                        respawn_in_ticks.tick();
                         // try to respawn
-                        if respawn_in_ticks.tick().unwrap_or_default() {
+                        if respawn_in_ticks.is_none() && is_wave {
                             characters_to_spawn.insert(
                                 no_char_player.id,
                                 {
@@ -620,10 +1285,11 @@ pub mod state {
                     ),
                 };
 
-                GameState::on_character_spawn(
-                    &mut self.game.stages.get_mut(&stage_id).unwrap().world,
-                    &char_id,
-                );
+                let world = &mut self.game.stages.get_mut(&stage_id).unwrap().world;
+                GameState::on_character_spawn(world, &char_id, &self.config);
+                if self.late_joiners.remove(&player_id) {
+                    Self::protect_late_joiner(world, &char_id, &self.config);
+                }
             }
 
             let mut kick_players = Vec::new();
@@ -635,206 +1301,688 @@ pub mod state {
                     true
                 }
             });
-            for kick_player in kick_players {
-                self.player_drop(&kick_player, PlayerDropReason::Disconnect);
-            }
-        }
-
-        fn query_tick(&mut self) {
-            self.game_db.cur_queries_helper.clear();
-            for query in self.game_db.cur_queries.drain(..) {
-                if query.is_finished() {
-                    if let Ok(query) = query.get_storage() {
-                        match query {
-                            GameDbQueries::AccountInfo {
-                                player_id,
-                                account_info: info,
-                            } => {
-                                self.game
-                                    .stages
-                                    .get(&self.stage_0_id)
-                                    .unwrap()
-                                    .simulation_events
-                                    .push(SimulationWorldEvent::Global(
-                                        GameWorldGlobalEvent::System(
-                                            GameWorldSystemMessage::Custom(
-                                                self.game_pools.mt_string_pool.new_str(&format!(
-                                                "user account information:\n\
-                                                id: {}\n\
-                                                name: {}\n\
-                                                creation: {}",
-                                                        info.id,
-                                                        info.name,
-                                                        <chrono::DateTime<chrono::Utc>>::from_timestamp(
-                                                            info.create_time.secs as i64,
-                                                            info.create_time.subsec_nanos
-                                                        )
-                                                        .unwrap()
-                                                    )),
-                                            ),
-                                        ),
-                                    ));
-                            }
+            for kick_player in kick_players {
+                self.player_drop(&kick_player, PlayerDropReason::Disconnect);
+            }
+
+            self.inactivity_tick();
+        }
+
+        fn inactivity_tick(&mut self) {
+            if self.config.inactivity_kick_secs == 0 {
+                return;
+            }
+            let kick_ticks = self.config.inactivity_kick_secs * TICKS_PER_SECOND;
+            let warning_ticks = kick_ticks.saturating_sub(
+                self.config
+                    .inactivity_warning_secs
+                    .min(self.config.inactivity_kick_secs)
+                    * TICKS_PER_SECOND,
+            );
+
+            let mut players = self.player_clone_pool.new();
+            self.game.players.pooled_clone_into(&mut players);
+
+            let mut kick_players = Vec::new();
+            for (id, player) in players.iter() {
+                let Some(character) = self
+                    .game
+                    .stages
+                    .get(&player.stage_id())
+                    .and_then(|stage| stage.world.characters.get(id))
+                else {
+                    continue;
+                };
+                // timed out players are handled by the reclaim window
+                if character.core.is_timeout {
+                    continue;
+                }
+                let ticks = self.inactive_ticks.entry(*id).or_default();
+                *ticks += 1;
+                if *ticks == warning_ticks && warning_ticks != kick_ticks {
+                    let msg = format!(
+                        "You will be kicked for inactivity in {} seconds.",
+                        (kick_ticks - warning_ticks) / TICKS_PER_SECOND
+                    );
+                    self.send_player_msg(id, &msg);
+                } else if *ticks >= kick_ticks {
+                    kick_players.push(*id);
+                }
+            }
+            for kick_player in kick_players {
+                // the server drops the player, once the client is disconnected
+                self.inactive_ticks.remove(&kick_player);
+                self.game
+                    .stages
+                    .get(&self.stage_0_id)
+                    .unwrap()
+                    .simulation_events
+                    .push(SimulationWorldEvent::Global(GameWorldGlobalEvent::Kick {
+                        player_id: kick_player,
+                        reason: self
+                            .game_pools
+                            .mt_string_pool
+                            .new_str("kicked for inactivity"),
+                    }));
+            }
+        }
+
+        fn query_tick(&mut self) {
+            self.game_db.cur_queries_helper.clear();
+            let mut queries = std::mem::take(&mut self.game_db.cur_queries);
+            for query in queries.drain(..) {
+                if !query.is_finished() {
+                    // keep it for the next tick
+                    self.game_db.cur_queries_helper.push(query);
+                    continue;
+                }
+                if let Ok(query) = query.get_storage() {
+                    match query {
+                        GameDbQueries::AccountInfo {
+                            player_id,
+                            account_info: info,
+                        } => {
+                            self.game
+                                .stages
+                                .get(&self.stage_0_id)
+                                .unwrap()
+                                .simulation_events
+                                .push(SimulationWorldEvent::Global(GameWorldGlobalEvent::System(
+                                    GameWorldSystemMessage::Custom(
+                                        self.game_pools.mt_string_pool.new_str(&format!(
+                                            "user account information:\n\
+                                                id: {}\n\
+                                                name: {}\n\
+                                                creation: {}",
+                                            info.id,
+                                            info.name,
+                                            <chrono::DateTime<chrono::Utc>>::from_timestamp(
+                                                info.create_time.secs as i64,
+                                                info.create_time.subsec_nanos
+                                            )
+                                            .unwrap()
+                                        )),
+                                    ),
+                                )));
+                        }
+                        GameDbQueries::RulesAccepted {
+                            player_id,
+                            accepted,
+                        } => {
+                            if accepted {
+                                self.join_with_accepted_rules(&player_id);
+                            } else if self.rules_pending.contains(&player_id) {
+                                self.send_rules(&player_id);
+                            }
+                        }
+                        GameDbQueries::RoundReport { report_id, report } => {
+                            if let Some(report) = report {
+                                self.export_round_report_to_disk(report_id, &report);
+                            }
+                        }
+                    }
+                }
+            }
+            std::mem::swap(&mut self.game_db.cur_queries_helper, &mut queries);
+            // queries could have been added while handling the results
+            self.game_db.cur_queries.append(&mut queries);
+        }
+
+        fn set_player_inp_impl(
+            &mut self,
+            player_id: &GameEntityId,
+            inp: &CharacterInput,
+            diff: CharacterInputConsumableDiff,
+            intra_tick_ratio: Option<f64>,
+            is_prediction: bool,
+        ) {
+            if let Some(player) = self.game.players.player(player_id) {
+                let stages = if !is_prediction {
+                    &mut self.game.stages
+                } else {
+                    &mut self.pred_game.stages
+                };
+                let character = stages
+                    .get_mut(&player.stage_id())
+                    .unwrap()
+                    .world
+                    .characters
+                    .get_mut(player_id)
+                    .unwrap();
+                character.core.input = *inp;
+                character.core.input_intra_tick_ratio = intra_tick_ratio.unwrap_or_default();
+                if !is_prediction {
+                    self.inactive_ticks.remove(player_id);
+                }
+                let stage = stages.get_mut(&player.stage_id()).unwrap();
+                if matches!(
+                    stage.match_manager.game_match.state,
+                    MatchState::Warmup { .. }
+                        | MatchState::Running { .. }
+                        | MatchState::SuddenDeath { .. }
+                        | MatchState::Paused { .. }
+                ) {
+                    stage
+                        .world
+                        .handle_character_input_change(&self.collision, player_id, diff);
+                }
+            }
+        }
+
+        fn snapshot_for_impl(&self, snap_for: SnapshotFor) -> MtPoolCow<'static, [u8]> {
+            let budget = matches!(snap_for, SnapshotFor::Client(_))
+                .then_some(self.config.snapshot_budget_bytes)
+                .filter(|&budget| budget > 0);
+            let mut snapshot = self.snap_shot_manager.snapshot_for(self, snap_for);
+            if let Some(budget) = budget {
+                snapshot.trim_to_budget(budget as usize);
+            }
+            let mut res = self.game_pools.snapshot_pool.new();
+            let writer: &mut Vec<_> = res.to_mut();
+            bincode::serde::encode_into_std_write(&snapshot, writer, bincode::config::standard())
+                .unwrap();
+            res
+        }
+
+        fn cmd_account_info(game_db: &mut GameDb, player_id: &GameEntityId, character: &Character) {
+            if let (Some(account_info), PlayerUniqueId::Account(account_id)) = (
+                &game_db.account_info,
+                &character.player_info.unique_identifier,
+            ) {
+                let account_info = account_info.clone();
+                let account_id = *account_id;
+                let player_id = *player_id;
+                game_db
+                    .cur_queries
+                    .push(game_db.io_batcher.spawn(async move {
+                        Ok(GameDbQueries::AccountInfo {
+                            player_id,
+                            account_info: account_info.fetch(account_id).await?,
+                        })
+                    }));
+            }
+        }
+
+        fn send_rules(&self, player_id: &GameEntityId) {
+            let mut rules = self.config.rules.join("\n");
+            rules.push_str("\nType /accept_rules to join the game.");
+            self.send_player_msg(player_id, &rules);
+        }
+
+        fn rules_hash(&self) -> Hash {
+            base::hash::generate_hash_for(self.config.rules.join("\n").as_bytes())
+        }
+
+        fn cmd_accept_rules(&mut self, player_id: &GameEntityId) {
+            let Some(unique_identifier) = self.join_with_accepted_rules(player_id) else {
+                return;
+            };
+            if let (Some(rules_accepted), PlayerUniqueId::Account(account_id)) =
+                (self.game_db.rules_accepted.clone(), unique_identifier)
+            {
+                let rules_hash = self.rules_hash();
+                self.game_db.io_batcher.spawn_without_lifetime(async move {
+                    rules_accepted.insert(account_id, &rules_hash).await
+                });
+            }
+        }
+
+        /// Spawns a player that waited for accepting the rules.
+        fn join_with_accepted_rules(&mut self, player_id: &GameEntityId) -> Option<PlayerUniqueId> {
+            if !self.rules_pending.remove(player_id) {
+                return None;
+            }
+            let no_char_player = self.game.no_char_players.remove(player_id)?;
+            let unique_identifier = no_char_player.player_info.unique_identifier;
+            self.rules_accepted.insert(unique_identifier);
+
+            let char_id = Self::add_char_to_stage(
+                &mut self.game.stages,
+                &self.spawns,
+                &self.stage_0_id,
+                player_id,
+                no_char_player.player_info,
+                no_char_player.player_input,
+                self.game.players.clone(),
+                self.game.no_char_players.clone(),
+                no_char_player.network_stats,
+                None,
+                0,
+            )
+            .base
+            .game_element_id;
+            Self::on_character_spawn(
+                &mut self.game.stages.get_mut(&self.stage_0_id).unwrap().world,
+                &char_id,
+                &self.config,
+            );
+            Some(unique_identifier)
+        }
+
+        /// The chat commands vanilla supports.
+        fn default_chat_cmds() -> ChatCmds {
+            let mut cmds = ChatCmds::default();
+            cmds.register(ChatCmd {
+                name: "help".to_string(),
+                usage: "help".to_string(),
+                description: "Lists all chat commands.".to_string(),
+                cmd: Rc::new(|game, _, _| {
+                    let prefix = game.chat_commands.prefixes.first().copied().unwrap_or('/');
+                    Ok(game.chat_cmds.help(prefix))
+                }),
+                args: vec![],
+            });
+            cmds.register(ChatCmd {
+                name: "account_info".to_string(),
+                usage: "account_info".to_string(),
+                description: "Shows information about your account.".to_string(),
+                cmd: Rc::new(|game, player_id, _| {
+                    let character = game
+                        .game
+                        .players
+                        .player(player_id)
+                        .and_then(|server_player| game.game.stages.get(&server_player.stage_id()))
+                        .and_then(|stage| stage.world.characters.get(player_id))
+                        .ok_or_else(|| anyhow!("you need a character for this command."))?;
+                    Self::cmd_account_info(&mut game.game_db, player_id, character);
+                    Ok(String::new())
+                }),
+                args: vec![],
+            });
+            cmds.register(ChatCmd {
+                name: "rules".to_string(),
+                usage: "rules".to_string(),
+                description: "Shows the rules of the server.".to_string(),
+                cmd: Rc::new(|game, player_id, _| {
+                    anyhow::ensure!(!game.config.rules.is_empty(), "this server has no rules.");
+                    game.send_rules(player_id);
+                    Ok(String::new())
+                }),
+                args: vec![],
+            });
+            cmds.register(ChatCmd {
+                name: "accept_rules".to_string(),
+                usage: "accept_rules".to_string(),
+                description: "Accepts the rules to join the game.".to_string(),
+                cmd: Rc::new(|game, player_id, _| {
+                    game.cmd_accept_rules(player_id);
+                    Ok(String::new())
+                }),
+                args: vec![],
+            });
+            cmds
+        }
+
+        /// Adds a chat command or replaces the one with the same name,
+        /// e.g. for mods that build on top of vanilla.
+        ///
+        /// Note that clients only know the commands of the static info,
+        /// so this should be called before the static info is sent.
+        pub fn register_chat_cmd(&mut self, cmd: ChatCmd) {
+            self.chat_cmds.register(cmd);
+            self.chat_commands = self
+                .chat_cmds
+                .chat_commands(std::mem::take(&mut self.chat_commands.prefixes));
+        }
+
+        fn handle_chat_commands(&mut self, player_id: &GameEntityId, cmds: Vec<CommandType>) {
+            for cmd in cmds {
+                let (ident, args) = match cmd {
+                    CommandType::Full(cmd) => (cmd.ident, cmd.args),
+                    CommandType::Partial(err) => {
+                        let usage = match &err {
+                            CommandParseResult::InvalidArg { partial_cmd, .. } => self
+                                .chat_cmds
+                                .get(&partial_cmd.ident)
+                                .map(|cmd| format!("\nUsage: {}", cmd.usage)),
+                            _ => None,
+                        };
+                        self.send_player_msg(
+                            player_id,
+                            &format!("{err}{}", usage.unwrap_or_default()),
+                        );
+                        continue;
+                    }
+                };
+                let Some((cb, usage)) = self
+                    .chat_cmds
+                    .get(&ident)
+                    .map(|cmd| (cmd.cmd.clone(), cmd.usage.clone()))
+                else {
+                    self.send_player_msg(
+                        player_id,
+                        &format!("Unknown command {ident}, use help to list all commands."),
+                    );
+                    continue;
+                };
+                match cb(self, player_id, &args) {
+                    Ok(text) => {
+                        if !text.is_empty() {
+                            self.send_player_msg(player_id, &text);
+                        }
+                    }
+                    Err(err) => {
+                        self.send_player_msg(player_id, &format!("{err}\nUsage: {usage}"));
+                    }
+                }
+            }
+        }
+
+        /// The rcon commands vanilla supports.
+        fn default_rcon_cmds() -> RconCmds {
+            let mut cmds = RconCmds::default();
+            cmds.register(RconCmd {
+                name: "help".to_string(),
+                usage: "help".to_string(),
+                description: "Lists all commands you are allowed to use.".to_string(),
+                auth_level: AuthLevel::Moderator,
+                cmd: Rc::new(|game, _, auth_level, _| Ok(game.rcon_cmds.help(auth_level))),
+                args: vec![],
+            });
+            cmds.register(RconCmd {
+                name: "info".to_string(),
+                usage: "info".to_string(),
+                description: "Shows information about the mod.".to_string(),
+                auth_level: AuthLevel::Moderator,
+                cmd: Rc::new(|_, _, _, _| Ok("You are playing vanilla.".to_string())),
+                args: vec![],
+            });
+            cmds.register(RconCmd {
+                name: "status".to_string(),
+                usage: "status".to_string(),
+                description: "Shows the ids & network stats of all players.".to_string(),
+                auth_level: AuthLevel::Moderator,
+                cmd: Rc::new(|game, _, _, _| Ok(game.rcon_status())),
+                args: vec![],
+            });
+            cmds.register(RconCmd {
+                name: "set_team".to_string(),
+                usage: "set_team <id> <red|blue>".to_string(),
+                description: "Moves the player to the given team.".to_string(),
+                auth_level: AuthLevel::Moderator,
+                cmd: Rc::new(|game, _, _, args| {
+                    let player_id = game.rcon_player_id(arg_text(args, 0)?)?;
+                    let side = match arg_text(args, 1)? {
+                        "red" => MatchSide::Red,
+                        _ => MatchSide::Blue,
+                    };
+                    anyhow::ensure!(
+                        game.game.players.player(&player_id).is_some(),
+                        "the player is not in the game."
+                    );
+                    game.client_command(&player_id, ClientCommand::JoinSide(side));
+                    Ok(String::new())
+                }),
+                args: vec![
+                    CommandArg {
+                        expected_ty: CommandArgType::Number,
+                    },
+                    CommandArg {
+                        expected_ty: CommandArgType::TextFrom(vec![
+                            "red".to_string(),
+                            "blue".to_string(),
+                        ]),
+                    },
+                ],
+            });
+            cmds.register(RconCmd {
+                name: "pause".to_string(),
+                usage: "pause".to_string(),
+                description: "Pauses the round of your stage.".to_string(),
+                auth_level: AuthLevel::Moderator,
+                cmd: Rc::new(|game, player_id, _, _| {
+                    let stage_id = game.rcon_stage_id(player_id);
+                    game.pause_stage(&stage_id)?;
+                    Ok(String::new())
+                }),
+                args: vec![],
+            });
+            cmds.register(RconCmd {
+                name: "resume".to_string(),
+                usage: "resume".to_string(),
+                description: "Resumes the paused round of your stage.".to_string(),
+                auth_level: AuthLevel::Moderator,
+                cmd: Rc::new(|game, player_id, _, _| {
+                    let stage_id = game.rcon_stage_id(player_id);
+                    game.resume_stage(&stage_id)?;
+                    Ok(String::new())
+                }),
+                args: vec![],
+            });
+            cmds.register(RconCmd {
+                name: "pause_stage".to_string(),
+                usage: "pause_stage <name>".to_string(),
+                description: "Pauses the round of the stage with the name.".to_string(),
+                auth_level: AuthLevel::Moderator,
+                cmd: Rc::new(|game, _, _, args| {
+                    let stage_id = game.rcon_stage_id_by_name(arg_text(args, 0)?)?;
+                    game.pause_stage(&stage_id)?;
+                    Ok(String::new())
+                }),
+                args: vec![CommandArg {
+                    expected_ty: CommandArgType::Text,
+                }],
+            });
+            cmds.register(RconCmd {
+                name: "resume_stage".to_string(),
+                usage: "resume_stage <name>".to_string(),
+                description: "Resumes the paused round of the stage with the name.".to_string(),
+                auth_level: AuthLevel::Moderator,
+                cmd: Rc::new(|game, _, _, args| {
+                    let stage_id = game.rcon_stage_id_by_name(arg_text(args, 0)?)?;
+                    game.resume_stage(&stage_id)?;
+                    Ok(String::new())
+                }),
+                args: vec![CommandArg {
+                    expected_ty: CommandArgType::Text,
+                }],
+            });
+            cmds.register(RconCmd {
+                name: "bot.add".to_string(),
+                usage: "bot.add <idle|roam|aim>".to_string(),
+                description: "Adds a bot with the behavior to your stage.".to_string(),
+                auth_level: AuthLevel::Moderator,
+                cmd: Rc::new(|game, player_id, _, args| {
+                    let stage_id = game.rcon_stage_id(player_id);
+                    let bot_id = game.add_bot(&stage_id, arg_text(args, 0)?)?;
+                    Ok(format!("Added bot {bot_id}."))
+                }),
+                args: vec![CommandArg {
+                    expected_ty: CommandArgType::Text,
+                }],
+            });
+            cmds.register(RconCmd {
+                name: "bot.remove".to_string(),
+                usage: "bot.remove <id>".to_string(),
+                description: "Removes the bot with the id.".to_string(),
+                auth_level: AuthLevel::Moderator,
+                cmd: Rc::new(|game, _, _, args| {
+                    let id = arg_text(args, 0)?;
+                    let bot_id = game
+                        .bots
+                        .bots
+                        .keys()
+                        .find(|bot_id| bot_id.to_string() == id)
+                        .copied()
+                        .ok_or_else(|| anyhow!("no bot with the id {id} found."))?;
+                    game.remove_bot(&bot_id)?;
+                    Ok(format!("Removed bot {bot_id}."))
+                }),
+                args: vec![CommandArg {
+                    expected_ty: CommandArgType::Number,
+                }],
+            });
+            cmds.register(RconCmd {
+                name: "cheat.all_weapons".to_string(),
+                usage: "cheat.all_weapons".to_string(),
+                description: "Gives you all weapons that are not disabled.".to_string(),
+                auth_level: AuthLevel::Admin,
+                cmd: Rc::new(|game, player_id, _, _| {
+                    let character = game
+                        .game
+                        .players
+                        .player(player_id)
+                        .and_then(|character_info| {
+                            game.game.stages.get_mut(&character_info.stage_id())
+                        })
+                        .and_then(|stage| stage.world.characters.get_mut(player_id))
+                        .ok_or_else(|| anyhow!("you need a character for this command."))?;
+                    let reusable_core = &mut character.reusable_core;
+                    let gun = Weapon {
+                        cur_ammo: Some(10),
+                        next_ammo_regeneration_tick: 0.into(),
+                    };
+                    for weapon in [
+                        WeaponType::Gun,
+                        WeaponType::Shotgun,
+                        WeaponType::Grenade,
+                        WeaponType::Laser,
+                    ] {
+                        if !game.config.is_weapon_disabled(weapon) {
+                            reusable_core.weapons.insert(weapon, gun);
                         }
                     }
-                }
-            }
-            std::mem::swap(
-                &mut self.game_db.cur_queries_helper,
-                &mut self.game_db.cur_queries,
-            );
+                    Ok(String::new())
+                }),
+                args: vec![],
+            });
+            cmds
         }
 
-        fn set_player_inp_impl(
-            &mut self,
-            player_id: &GameEntityId,
-            inp: &CharacterInput,
-            diff: CharacterInputConsumableDiff,
-            intra_tick_ratio: Option<f64>,
-            is_prediction: bool,
-        ) {
-            if let Some(player) = self.game.players.player(player_id) {
-                let stages = if !is_prediction {
-                    &mut self.game.stages
-                } else {
-                    &mut self.pred_game.stages
-                };
-                let character = stages
-                    .get_mut(&player.stage_id())
-                    .unwrap()
-                    .world
-                    .characters
-                    .get_mut(player_id)
-                    .unwrap();
-                character.core.input = *inp;
-                character.core.input_intra_tick_ratio = intra_tick_ratio.unwrap_or_default();
-                let stage = stages.get_mut(&player.stage_id()).unwrap();
-                if matches!(
-                    stage.match_manager.game_match.state,
-                    MatchState::Running { .. } | MatchState::Paused { .. }
-                ) {
-                    stage
-                        .world
-                        .handle_character_input_change(&self.collision, player_id, diff);
-                }
-            }
+        /// Adds a rcon command or replaces the one with the same name,
+        /// e.g. for mods that build on top of vanilla.
+        ///
+        /// Note that clients only know the commands of the static info,
+        /// so this should be called before the static info is sent.
+        pub fn register_rcon_cmd(&mut self, cmd: RconCmd) {
+            self.rcon_cmds.register(cmd);
+            self.rcon_commands = self.rcon_cmds.rcon_commands();
         }
 
-        fn snapshot_for_impl(&self, snap_for: SnapshotFor) -> MtPoolCow<'static, [u8]> {
-            let snapshot = self.snap_shot_manager.snapshot_for(self, snap_for);
-            let mut res = self.game_pools.snapshot_pool.new();
-            let writer: &mut Vec<_> = res.to_mut();
-            bincode::serde::encode_into_std_write(&snapshot, writer, bincode::config::standard())
-                .unwrap();
-            res
+        /// The player with the id as shown by the status command.
+        fn rcon_player_id(&self, id: &str) -> anyhow::Result<GameEntityId> {
+            let mut players = self.player_clone_pool.new();
+            self.game.players.pooled_clone_into(&mut players);
+            let mut no_char_players = self.no_char_player_clone_pool.new();
+            self.game
+                .no_char_players
+                .pooled_clone_into(&mut no_char_players);
+            players
+                .iter()
+                .map(|(id, _)| *id)
+                .chain(no_char_players.iter().map(|(id, _)| *id))
+                .find(|player_id| player_id.to_string() == id)
+                .ok_or_else(|| anyhow!("no player with the id {id} found."))
         }
 
-        fn cmd_account_info(game_db: &mut GameDb, player_id: &GameEntityId, character: &Character) {
-            if let (Some(account_info), PlayerUniqueId::Account(account_id)) = (
-                &game_db.account_info,
-                &character.player_info.unique_identifier,
-            ) {
-                let account_info = account_info.clone();
-                let account_id = *account_id;
-                let player_id = *player_id;
-                game_db
-                    .cur_queries
-                    .push(game_db.io_batcher.spawn(async move {
-                        Ok(GameDbQueries::AccountInfo {
-                            player_id,
-                            account_info: account_info.fetch(account_id).await?,
-                        })
-                    }));
-            }
+        /// The stage of the player's character, the first stage for spectators.
+        fn rcon_stage_id(&self, player_id: &GameEntityId) -> GameEntityId {
+            self.game
+                .players
+                .player(player_id)
+                .map(|player| player.stage_id())
+                .unwrap_or(self.stage_0_id)
         }
 
-        fn handle_chat_commands(&mut self, player_id: &GameEntityId, cmds: Vec<CommandType>) {
-            let Some(server_player) = self.game.players.player(player_id) else {
-                return;
-            };
-            let Some(character) = self
-                .game
+        fn rcon_stage_id_by_name(&self, name: &str) -> anyhow::Result<GameEntityId> {
+            self.game
                 .stages
-                .get(&server_player.stage_id())
-                .and_then(|stage| stage.world.characters.get(player_id))
-            else {
-                return;
+                .iter()
+                .find(|(_, stage)| stage.stage_name == name)
+                .map(|(id, _)| *id)
+                .ok_or_else(|| anyhow!("no stage with the name {name} found."))
+        }
+
+        fn rcon_status(&self) -> String {
+            let mut status = String::new();
+            let mut add_status = |id: &GameEntityId, name: &str, stats: &PlayerNetworkStats| {
+                status.push_str(&format!(
+                    "{id}: {name}: ping {}ms, loss {:.1}%, jitter {}ms\n",
+                    stats.ping.as_millis(),
+                    stats.packet_loss * 100.0,
+                    stats.jitter.as_millis()
+                ));
             };
-            for cmd in cmds {
-                match cmd {
-                    CommandType::Full(cmd) => {
-                        match cmd.ident.as_str() {
-                            "account_info" => {
-                                Self::cmd_account_info(&mut self.game_db, player_id, character);
-                            }
-                            _ => {
-                                // TODO: send command not found text
-                            }
-                        }
-                    }
-                    CommandType::Partial(_) => {
-                        // TODO: ignore for now
-                        // send back feedback to user
+            for stage in self.game.stages.values() {
+                for (id, character) in stage.world.characters.iter() {
+                    if let Some(stats) = character.is_player_character() {
+                        add_status(id, &character.player_info.player_info.name, &stats);
                     }
                 }
             }
+            let mut no_char_players = self.no_char_player_clone_pool.new();
+            self.game
+                .no_char_players
+                .pooled_clone_into(&mut no_char_players);
+            for (id, player) in no_char_players.iter() {
+                add_status(
+                    id,
+                    &player.player_info.player_info.name,
+                    &player.network_stats,
+                );
+            }
+            status.trim_end().to_string()
+        }
+
+        /// Only the player receives the message.
+        fn send_player_msg(&self, player_id: &GameEntityId, text: &str) {
+            self.game
+                .stages
+                .get(&self.stage_0_id)
+                .unwrap()
+                .simulation_events
+                .push(SimulationWorldEvent::Global(
+                    GameWorldGlobalEvent::PlayerMessage {
+                        player_id: *player_id,
+                        msg: self.game_pools.mt_string_pool.new_str(text),
+                    },
+                ));
         }
 
         fn handle_rcon_commands(
             &mut self,
             player_id: &GameEntityId,
-            _auth: AuthLevel,
+            auth: AuthLevel,
             cmds: Vec<CommandType>,
         ) {
-            let Some(character_info) = self.game.players.player(player_id) else {
-                return;
-            };
             for cmd in cmds {
-                match cmd {
-                    CommandType::Full(cmd) => {
-                        match cmd.ident.as_str() {
-                            "info" => {
-                                self.game
-                                    .stages
-                                    .get(&self.stage_0_id)
-                                    .unwrap()
-                                    .simulation_events
-                                    .push(SimulationWorldEvent::Global(
-                                        GameWorldGlobalEvent::System(
-                                            GameWorldSystemMessage::Custom(
-                                                self.game_pools
-                                                    .mt_string_pool
-                                                    .new_str("You are playing vanilla."),
-                                            ),
-                                        ),
-                                    ));
-                            }
-                            "cheat.all_weapons" => {
-                                if let Some(character) = self
-                                    .game
-                                    .stages
-                                    .get_mut(&character_info.stage_id())
-                                    .and_then(|stage| stage.world.characters.get_mut(player_id))
-                                {
-                                    let reusable_core = &mut character.reusable_core;
-                                    let gun = Weapon {
-                                        cur_ammo: Some(10),
-                                        next_ammo_regeneration_tick: 0.into(),
-                                    };
-                                    reusable_core.weapons.insert(WeaponType::Gun, gun);
-                                    reusable_core.weapons.insert(WeaponType::Shotgun, gun);
-                                    reusable_core.weapons.insert(WeaponType::Grenade, gun);
-                                    reusable_core.weapons.insert(WeaponType::Laser, gun);
-                                }
-                            }
-                            _ => {
-                                // TODO: send command not found text
-                            }
+                let (ident, args) = match cmd {
+                    CommandType::Full(cmd) => (cmd.ident, cmd.args),
+                    CommandType::Partial(err) => {
+                        let usage = match &err {
+                            CommandParseResult::InvalidArg { partial_cmd, .. } => self
+                                .rcon_cmds
+                                .get(&partial_cmd.ident)
+                                .map(|cmd| format!("\nUsage: {}", cmd.usage)),
+                            _ => None,
+                        };
+                        self.send_player_msg(
+                            player_id,
+                            &format!("{err}{}", usage.unwrap_or_default()),
+                        );
+                        continue;
+                    }
+                };
+                let Some((cb, usage)) = self
+                    .rcon_cmds
+                    .get(&ident)
+                    .filter(|cmd| cmd.auth_level <= auth)
+                    .map(|cmd| (cmd.cmd.clone(), cmd.usage.clone()))
+                else {
+                    self.send_player_msg(
+                        player_id,
+                        &format!("Unknown command {ident}, use help to list all commands."),
+                    );
+                    continue;
+                };
+                match cb(self, player_id, auth, &args) {
+                    Ok(text) => {
+                        if !text.is_empty() {
+                            self.send_player_msg(player_id, &text);
                         }
                     }
-                    CommandType::Partial(_) => {
-                        // TODO: ignore for now
-                        // send back feedback to user
+                    Err(err) => {
+                        self.send_player_msg(player_id, &format!("{err}\nUsage: {usage}"));
                     }
                 }
             }
@@ -883,6 +2031,7 @@ pub mod state {
                             ratio,
                         ) / 32.0,
                         owner_id: Some(pred_proj.character_id),
+                        mod_payload: proj.projectile.core.mod_payload,
                     },
                 ))
             }));
@@ -986,6 +2135,7 @@ pub mod state {
                         ty: pickup.core.ty,
                         pos: Pickup::lerped_pos(pickup, pred_pickup, ratio) / 32.0,
                         owner_id: None,
+                        mod_payload: pickup.core.mod_payload,
                     },
                 ))
             }));
@@ -1071,6 +2221,12 @@ pub mod state {
                                 CharacterDebuff::Freeze,
                                 CharacterDebuffInfo {
                                     remaining_time: None,
+                                    progress: stage.match_manager.game_options.freeze_tag.map(
+                                        |freeze_tag| {
+                                            character.core.unfreeze_ticks as f32
+                                                / freeze_tag.unfreeze_ticks.max(1) as f32
+                                        },
+                                    ),
                                 },
                             ),
                         }
@@ -1089,6 +2245,8 @@ pub mod state {
                         .action_ticks()
                         .map(|tick| (tick, emoticon))
                 }),
+
+                mod_payload: character.core.mod_payload,
             }
         }
 
@@ -1137,8 +2295,8 @@ pub mod state {
                         &player.player_info,
                     ),
                     Some(match player.no_char_type {
-                        NoCharPlayerType::Spectator => CharacterPlayerInfo {
-                            cam_mode: PlayerCameraMode::Free,
+                        NoCharPlayerType::Spectator { follow } => CharacterPlayerInfo {
+                            cam_mode: self.spectator_cam_mode(follow),
                         },
                         NoCharPlayerType::Dead { died_at_pos, .. } => CharacterPlayerInfo {
                             cam_mode: PlayerCameraMode::LockedTo(died_at_pos / 32.0),
@@ -1450,8 +2608,31 @@ pub mod state {
                                 MatchType::Sided { scores } => MatchStandings::Sided {
                                     score_red: scores[0],
                                     score_blue: scores[1],
+                                    capture_points: {
+                                        let mut capture_points =
+                                            self.game_pools.capture_point_render_info_pool.new();
+                                        if let Some(objectives) =
+                                            &stage.match_manager.game_options.objectives
+                                        {
+                                            capture_points.extend(
+                                                self.game_objects_definitions
+                                                    .objective_zones
+                                                    .iter()
+                                                    .zip(stage.match_manager.capture_points.iter())
+                                                    .map(|(zone, capture_point)| {
+                                                        capture_point.render_info(zone, objectives)
+                                                    }),
+                                            );
+                                        }
+                                        capture_points
+                                    },
                                 },
                             },
+                            round_timer: stage
+                                .match_manager
+                                .game_match
+                                .state
+                                .round_timer(stage.match_manager.game_options.time_limit_ticks),
                         },
                     },
                 );
@@ -1478,6 +2659,8 @@ pub mod state {
                 LocalCharacterRenderInfo {
                     health: player_char.core.health,
                     armor: player_char.core.armor,
+                    overheal_health: player_char.core.health.saturating_sub(MAX_HEALTH),
+                    overheal_armor: player_char.core.armor.saturating_sub(MAX_ARMOR),
                     ammo_of_weapon: player_char.reusable_core.weapons
                         [&player_char.core.active_weapon]
                         .cur_ammo,
@@ -1486,6 +2669,8 @@ pub mod state {
                 LocalCharacterRenderInfo {
                     health: 0,
                     armor: 0,
+                    overheal_health: 0,
+                    overheal_armor: 0,
                     ammo_of_weapon: None,
                 }
             }
@@ -1537,19 +2722,84 @@ pub mod state {
                     },
                 )));
 
+            let player_info = PlayerInfo {
+                player_info: PoolRc::from_item_without_pool(client_player_info.info.clone()),
+                version: 1,
+                unique_identifier: client_player_info.unique_identifier,
+                player_index: client_player_info.player_index,
+                is_dummy: client_player_info.is_dummy,
+            };
+
+            // new players have to accept the rules first, until then they stay spectators
+            if !self.config.rules.is_empty()
+                && !self
+                    .rules_accepted
+                    .contains(&client_player_info.unique_identifier)
+            {
+                self.game.no_char_players.insert(
+                    player_id,
+                    NoCharPlayer::new(
+                        player_info,
+                        Default::default(),
+                        &player_id,
+                        NoCharPlayerType::Spectator { follow: None },
+                        client_player_info.initial_network_stats,
+                    ),
+                );
+                self.rules_pending.insert(player_id);
+                // accounts might have accepted the rules on a previous run of the server
+                if let (Some(rules_accepted), PlayerUniqueId::Account(account_id)) = (
+                    self.game_db.rules_accepted.clone(),
+                    client_player_info.unique_identifier,
+                ) {
+                    let rules_hash = self.rules_hash();
+                    let query = self.game_db.io_batcher.spawn(async move {
+                        let accepted = rules_accepted.is_accepted(account_id, &rules_hash).await;
+                        if let Err(err) = &accepted {
+                            log::warn!(target: "sql", "failed to check the accepted rules: {err}");
+                        }
+                        Ok(GameDbQueries::RulesAccepted {
+                            player_id,
+                            accepted: accepted.unwrap_or_default(),
+                        })
+                    });
+                    self.game_db.cur_queries.push(query);
+                } else {
+                    self.send_rules(&player_id);
+                }
+                return player_id;
+            }
+
+            let is_late_join = self.is_late_join();
+            // late joiners wait for the next spawn wave, like dead players
+            if is_late_join && self.spawn_wave_interval().is_some() {
+                self.game.no_char_players.insert(
+                    player_id,
+                    NoCharPlayer::new(
+                        player_info,
+                        Default::default(),
+                        &player_id,
+                        NoCharPlayerType::Dead {
+                            respawn_in_ticks: Default::default(),
+                            side: None,
+                            score: 0,
+                            stage_id: stage_0_id,
+                            died_at_pos: Default::default(),
+                        },
+                        client_player_info.initial_network_stats,
+                    ),
+                );
+                self.late_joiners.insert(player_id);
+                return player_id;
+            }
+
             // spawn and send character info
             let char_id = Self::add_char_to_stage(
                 &mut self.game.stages,
                 &self.spawns,
                 &stage_0_id,
                 &player_id,
-                PlayerInfo {
-                    player_info: PoolRc::from_item_without_pool(client_player_info.info.clone()),
-                    version: 1,
-                    unique_identifier: client_player_info.unique_identifier,
-                    player_index: client_player_info.player_index,
-                    is_dummy: client_player_info.is_dummy,
-                },
+                player_info,
                 Default::default(),
                 self.game.players.clone(),
                 self.game.no_char_players.clone(),
@@ -1559,15 +2809,48 @@ pub mod state {
             )
             .base
             .game_element_id;
-            Self::on_character_spawn(
-                &mut self.game.stages.get_mut(&self.stage_0_id).unwrap().world,
-                &char_id,
-            );
+            let world = &mut self.game.stages.get_mut(&self.stage_0_id).unwrap().world;
+            Self::on_character_spawn(world, &char_id, &self.config);
+            if is_late_join {
+                Self::protect_late_joiner(world, &char_id, &self.config);
+            }
 
             player_id
         }
 
-        fn player_drop(&mut self, player_id: &GameEntityId, _reason: PlayerDropReason) {
+        fn player_drop(&mut self, player_id: &GameEntityId, reason: PlayerDropReason) {
+            self.inactive_ticks.remove(player_id);
+
+            // players that timed out keep their character for a while,
+            // so they can take it back when they reconnect.
+            if matches!(reason, PlayerDropReason::Timeout) && self.config.timeout_reclaim_secs > 0 {
+                if let Some(server_player) = self.game.players.player(player_id) {
+                    let character = self
+                        .game
+                        .stages
+                        .get_mut(&server_player.stage_id())
+                        .unwrap()
+                        .world
+                        .characters
+                        .get_mut(player_id)
+                        .unwrap();
+                    character.core.is_timeout = true;
+                    self.game.timeout_players.insert(
+                        (
+                            character.player_info.unique_identifier,
+                            character.player_info.player_index,
+                        ),
+                        (
+                            *player_id,
+                            (TICKS_PER_SECOND * self.config.timeout_reclaim_secs).into(),
+                        ),
+                    );
+                    return;
+                }
+            }
+
+            self.rules_pending.remove(player_id);
+            self.late_joiners.remove(player_id);
             let name = if let Some(server_player) = self.game.players.player(player_id) {
                 let stage = self.game.stages.get_mut(&server_player.stage_id()).unwrap();
 
@@ -1580,6 +2863,7 @@ pub mod state {
 
                 character.despawn_completely_silent();
                 stage.world.characters.remove(player_id);
+                stage.world.remove_entities_of_character(player_id);
 
                 Some((name, server_player.stage_id()))
             } else if let Some(no_char_player) = self.game.no_char_players.remove(player_id) {
@@ -1671,12 +2955,30 @@ pub mod state {
                         );
                 }
             }
+            // remaining players have no character (e.g. spectators)
+            for (id, stats) in stats.drain() {
+                self.game.no_char_players.set_network_stats(&id, stats);
+            }
         }
 
         fn client_command(&mut self, player_id: &GameEntityId, cmd: ClientCommand) {
             match cmd {
                 ClientCommand::Kill => {
                     if let Some(server_player) = self.game.players.player(player_id) {
+                        let is_frozen = self
+                            .game
+                            .stages
+                            .get(&server_player.stage_id())
+                            .and_then(|stage| stage.world.characters.get(player_id))
+                            .is_some_and(|character| character.is_frozen());
+                        if is_frozen {
+                            // frozen characters can only be freed by their team
+                            self.send_player_msg(
+                                player_id,
+                                "You can't kill yourself while frozen.",
+                            );
+                            return;
+                        }
                         self.game
                             .stages
                             .get_mut(&server_player.stage_id())
@@ -1715,6 +3017,7 @@ pub mod state {
                                 let player_input = character.core.input;
                                 let network_stats = character.is_player_character().unwrap();
                                 drop(character);
+                                stage.world.remove_entities_of_character(player_id);
 
                                 if player.stage_id() != self.stage_0_id
                                     && !stage
@@ -1748,13 +3051,17 @@ pub mod state {
                                 Self::on_character_spawn(
                                     &mut self.game.stages.get_mut(&stage_id).unwrap().world,
                                     player_id,
+                                    &self.config,
                                 );
                             }
                         }
                     }
                 }
                 ClientCommand::JoinSide(side) => {
-                    if matches!(self.config.game_type, ConfigGameType::Ctf) {
+                    if matches!(
+                        self.config.game_type,
+                        ConfigGameType::Ctf | ConfigGameType::FreezeTag | ConfigGameType::Koth
+                    ) {
                         if let Some(player) = self.game.players.player(player_id) {
                             if let Some(character) = self
                                 .game
@@ -1772,22 +3079,81 @@ pub mod state {
                 }
                 ClientCommand::JoinSpectator => {
                     if let Some(player) = self.game.players.player(player_id) {
-                        if let Some(mut character) = self
-                            .game
-                            .stages
-                            .get_mut(&player.stage_id())
-                            .unwrap()
-                            .world
-                            .characters
-                            .remove(player_id)
-                        {
+                        let world =
+                            &mut self.game.stages.get_mut(&player.stage_id()).unwrap().world;
+                        if let Some(mut character) = world.characters.remove(player_id) {
                             character.despawn_to_join_spectators();
+                            drop(character);
+                            world.remove_entities_of_character(player_id);
                         }
+                        self.inactive_ticks.remove(player_id);
                     }
                 }
                 ClientCommand::SwitchToFreeCam(_) => {
                     // unsupported by vanilla
                 }
+                ClientCommand::StartVote(action) => {
+                    self.start_vote(player_id, action);
+                }
+                ClientCommand::Vote(voted) => {
+                    // only the votes of allowed voters count
+                    if let Some(vote) = &mut self.vote {
+                        vote.vote(*player_id, voted);
+                        self.push_vote_counts();
+                    }
+                }
+                ClientCommand::SpectateTarget(target) => {
+                    // only players with a character can be followed
+                    let target = target.filter(|target| {
+                        target != player_id && self.game.players.player(target).is_some()
+                    });
+                    self.game.no_char_players.handle_mut(
+                        player_id,
+                        hi_closure!(
+                            [target: Option<GameEntityId>],
+                            |player: &mut NoCharPlayer| -> () {
+                                if let NoCharPlayerType::Spectator { follow } =
+                                    &mut player.no_char_type
+                                {
+                                    *follow = target;
+                                }
+                            }
+                        ),
+                    );
+                }
+            }
+        }
+
+        fn server_command(&mut self, cmd: ServerCommand) {
+            match cmd {
+                ServerCommand::Broadcast(msg) => {
+                    self.game
+                        .stages
+                        .get(&self.stage_0_id)
+                        .unwrap()
+                        .simulation_events
+                        .push(SimulationWorldEvent::Global(GameWorldGlobalEvent::System(
+                            GameWorldSystemMessage::Custom(
+                                self.game_pools.mt_string_pool.new_str(&msg),
+                            ),
+                        )));
+                }
+                ServerCommand::ScoreLimit(score_limit) => {
+                    self.game_options = GameOptions::new(
+                        self.game_options.ty,
+                        score_limit,
+                        self.game_options.overheal,
+                        self.game_options.freeze_tag,
+                        self.game_options.objectives,
+                        self.game_options.instagib,
+                        self.game_options.spawn_strategy,
+                        self.game_options.time_limit_ticks,
+                        self.game_options.warmup_ticks,
+                    );
+                    for stage in self.game.stages.values_mut() {
+                        stage.match_manager.game_options = self.game_options;
+                    }
+                }
             }
         }
 
@@ -1838,9 +3204,11 @@ pub mod state {
         }
 
         fn tick(&mut self) {
+            self.bot_tick();
             self.tick_impl(false);
 
             self.player_tick();
+            self.vote_tick();
             self.query_tick();
         }
 
@@ -1869,6 +3237,22 @@ pub mod state {
             self.snapshot_for_impl(SnapshotFor::Client(client))
         }
 
+        fn redact_snapshot(
+            &self,
+            snapshot: &MtPoolCow<'static, [u8]>,
+            redaction: &CharacterInfoRedaction,
+        ) -> MtPoolCow<'static, [u8]> {
+            let (mut snapshot, _): (Snapshot, _) =
+                bincode::serde::decode_from_slice(snapshot, bincode::config::standard()).unwrap();
+            snapshot.redact(redaction);
+
+            let mut res = self.game_pools.snapshot_pool.new();
+            let writer: &mut Vec<_> = res.to_mut();
+            bincode::serde::encode_into_std_write(&snapshot, writer, bincode::config::standard())
+                .unwrap();
+            res
+        }
+
         fn build_from_snapshot(
             &mut self,
             snapshot: &MtPoolCow<'static, [u8]>,
@@ -1904,7 +3288,10 @@ pub mod state {
                                 character.player_info.unique_identifier,
                                 character.player_info.player_index,
                             ),
-                            (*id, (TICKS_PER_SECOND * 120).into()),
+                            (
+                                *id,
+                                (TICKS_PER_SECOND * self.config.timeout_reclaim_secs).into(),
+                            ),
                         );
                     }
                 }
@@ -2029,10 +3416,12 @@ pub mod state {
 
             let game_pools = &self.game_pools;
             let event_id_generator = &self.event_id_generator;
+            let client = &client;
 
             self.simulation_events.for_each(hi_closure!([
                 game_pools: &GamePooling,
                 event_id_generator: &EventIdGenerator,
+                client: &EventClientInfo,
                 worlds_events_ref: &mut MtPoolLinkedHashMap<GameEntityId, GameWorldEvents>,
             ], |world_id: &GameEntityId, evs: &SimulationWorldEvents|
              -> () {
@@ -2045,6 +3434,39 @@ pub mod state {
                                 | CharacterEvent::Laser { .. } => {
                                     // ignored
                                 }
+                                CharacterEvent::ShotFired { shot_id, weapon } => {
+                                    if let Some(shooter) = entity.owner_id {
+                                        world_events.insert(
+                                            event_id_generator.next_id(),
+                                            GameWorldEvent::Global(GameWorldGlobalEvent::Stat(
+                                                GameWorldStatEvent::ShotFired {
+                                                    shooter,
+                                                    shot_id,
+                                                    weapon,
+                                                },
+                                            )),
+                                        );
+                                    }
+                                }
+                                CharacterEvent::ShotHit {
+                                    shot_id,
+                                    weapon,
+                                    victim,
+                                } => {
+                                    if let Some(shooter) = entity.owner_id {
+                                        world_events.insert(
+                                            event_id_generator.next_id(),
+                                            GameWorldEvent::Global(GameWorldGlobalEvent::Stat(
+                                                GameWorldStatEvent::ShotHit {
+                                                    shooter,
+                                                    shot_id,
+                                                    weapon,
+                                                    victim,
+                                                },
+                                            )),
+                                        );
+                                    }
+                                }
                                 CharacterEvent::Despawn { killer_id, weapon } => {
                                     world_events.insert(
                                         event_id_generator.next_id(),
@@ -2134,6 +3556,25 @@ pub mod state {
                                             ),
                                         );
                                     }
+                                    ProjectileEvent::Hit {
+                                        shot_id,
+                                        ty,
+                                        victim,
+                                    } => {
+                                        if let Some(shooter) = entity.owner_id {
+                                            world_events.insert(
+                                                event_id_generator.next_id(),
+                                                GameWorldEvent::Global(GameWorldGlobalEvent::Stat(
+                                                    GameWorldStatEvent::ShotHit {
+                                                        shooter,
+                                                        shot_id,
+                                                        weapon: ty,
+                                                        victim,
+                                                    },
+                                                )),
+                                            );
+                                        }
+                                    }
                                     ProjectileEvent::GrenadeEffect { pos, ev } => {
                                         world_events.insert(
                                             event_id_generator.next_id(),
@@ -2188,6 +3629,21 @@ pub mod state {
                                     LaserEvent::Despawn { .. } => {
                                         // do nothing
                                     }
+                                    LaserEvent::Hit { shot_id, victim } => {
+                                        if let Some(shooter) = entity.owner_id {
+                                            world_events.insert(
+                                                event_id_generator.next_id(),
+                                                GameWorldEvent::Global(GameWorldGlobalEvent::Stat(
+                                                    GameWorldStatEvent::ShotHit {
+                                                        shooter,
+                                                        shot_id,
+                                                        weapon: WeaponType::Laser,
+                                                        victim,
+                                                    },
+                                                )),
+                                            );
+                                        }
+                                    }
                                     LaserEvent::Sound { pos, ev } => {
                                         world_events.insert(
                                             event_id_generator.next_id(),
@@ -2206,10 +3662,22 @@ pub mod state {
                             }
                         },
                         SimulationWorldEvent::Global(ev) => {
-                            world_events.insert(
-                                event_id_generator.next_id(),
-                                GameWorldEvent::Global(ev.clone()),
-                            );
+                            let is_for_client = match ev {
+                                GameWorldGlobalEvent::PlayerMessage { player_id, .. } => {
+                                    client.client_player_ids.contains(player_id)
+                                }
+                                GameWorldGlobalEvent::Kick { .. }
+                                | GameWorldGlobalEvent::ExportRoundReport { .. } => {
+                                    client.everything
+                                }
+                                _ => true,
+                            };
+                            if is_for_client {
+                                world_events.insert(
+                                    event_id_generator.next_id(),
+                                    GameWorldEvent::Global(ev.clone()),
+                                );
+                            }
                         }
                     }
                 }
@@ -1,9 +1,12 @@
 pub mod types {
     use std::ops::Deref;
 
+    use game_interface::types::game::GameTickType;
     use hiarc::Hiarc;
     use serde::{Deserialize, Serialize};
 
+    use crate::spawns::SpawnStrategy;
+
     #[derive(Debug, Hiarc, Clone, Copy, Default, Serialize, Deserialize)]
     pub enum GameType {
         #[default]
@@ -11,18 +14,75 @@ pub mod types {
         Team,
     }
 
+    /// Health & armor above the normal max of 10 (overheal).
+    #[derive(Debug, Hiarc, Clone, Copy)]
+    pub struct GameOptionsOverheal {
+        pub max_health: u32,
+        pub max_armor: u32,
+        /// 0 means no decay
+        pub decay_ticks: GameTickType,
+    }
+
+    /// Hits freeze instead of killing, see [`crate::freeze_tag_controller`].
+    #[derive(Debug, Hiarc, Clone, Copy)]
+    pub struct GameOptionsFreezeTag {
+        /// How many ticks a teammate has to stand next to
+        /// a frozen character to unfreeze it.
+        pub unfreeze_ticks: GameTickType,
+    }
+
+    /// Capture points that score over time,
+    /// see [`crate::objective_controller`].
+    #[derive(Debug, Hiarc, Clone, Copy)]
+    pub struct GameOptionsObjectives {
+        /// How many ticks a side has to stand alone in a zone to capture it.
+        pub capture_ticks: GameTickType,
+        /// Every how many ticks a held capture point gives its owner a point.
+        pub score_ticks: GameTickType,
+    }
+
     #[derive(Debug, Hiarc, Clone, Copy)]
     pub struct GameOptionsInner {
         pub ty: GameType,
         pub score_limit: u64,
+        pub overheal: GameOptionsOverheal,
+        pub freeze_tag: Option<GameOptionsFreezeTag>,
+        pub objectives: Option<GameOptionsObjectives>,
+        /// See [`crate::instagib`].
+        pub instagib: bool,
+        pub spawn_strategy: SpawnStrategy,
+        /// 0 means no time limit.
+        pub time_limit_ticks: GameTickType,
+        /// 0 means no warmup.
+        pub warmup_ticks: GameTickType,
     }
 
     #[derive(Debug, Hiarc, Clone, Copy)]
     pub struct GameOptions(GameOptionsInner);
 
     impl GameOptions {
-        pub fn new(ty: GameType, score_limit: u64) -> Self {
-            Self(GameOptionsInner { ty, score_limit })
+        pub fn new(
+            ty: GameType,
+            score_limit: u64,
+            overheal: GameOptionsOverheal,
+            freeze_tag: Option<GameOptionsFreezeTag>,
+            objectives: Option<GameOptionsObjectives>,
+            instagib: bool,
+            spawn_strategy: SpawnStrategy,
+            time_limit_ticks: GameTickType,
+            warmup_ticks: GameTickType,
+        ) -> Self {
+            Self(GameOptionsInner {
+                ty,
+                score_limit,
+                overheal,
+                freeze_tag,
+                objectives,
+                instagib,
+                spawn_strategy,
+                time_limit_ticks,
+                warmup_ticks,
+            })
         }
     }
 
@@ -0,0 +1,90 @@
+use std::collections::{HashMap, HashSet};
+
+use game_interface::{
+    types::game::{GameEntityId, GameTickCooldown, GameTickType},
+    votes::{GameVoteAction, Voted},
+};
+use hiarc::Hiarc;
+
+/// The outcome of a [`GameVote`], see [`GameVote::result`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameVoteResult {
+    Running,
+    Passed,
+    Failed,
+}
+
+/// A vote that is run by the game, e.g. to kick a player.
+///
+/// Only the votes of players that are allowed to vote when the result
+/// is evaluated count, so players that left don't block the vote.
+#[derive(Debug, Hiarc)]
+pub struct GameVote {
+    pub action: GameVoteAction,
+    pub started_by: GameEntityId,
+    votes: HashMap<GameEntityId, Voted>,
+    ends_in: GameTickCooldown,
+}
+
+impl GameVote {
+    /// The player that started the vote votes yes.
+    pub fn new(action: GameVoteAction, started_by: GameEntityId, ticks: GameTickType) -> Self {
+        Self {
+            action,
+            started_by,
+            votes: [(started_by, Voted::Yes)].into_iter().collect(),
+            ends_in: ticks.max(1).into(),
+        }
+    }
+
+    /// Players can change their vote while the vote is running.
+    pub fn vote(&mut self, player_id: GameEntityId, voted: Voted) {
+        self.votes.insert(player_id, voted);
+    }
+
+    /// Returns `true` once the time of the vote is up.
+    pub fn tick(&mut self) -> bool {
+        self.ends_in.tick();
+        self.ends_in.is_none()
+    }
+
+    /// The yes & no votes of the `voters`.
+    pub fn counts(&self, voters: &HashSet<GameEntityId>) -> (u64, u64) {
+        self.votes
+            .iter()
+            .filter(|(id, _)| voters.contains(id))
+            .fold((0, 0), |(yes, no), (_, voted)| match voted {
+                Voted::Yes => (yes + 1, no),
+                Voted::No => (yes, no + 1),
+            })
+    }
+
+    /// The vote passes as soon as more than `pass_percentage` of all `voters`
+    /// voted yes and fails as soon as that is not possible anymore.
+    /// If the time is up, only the voters that voted decide.
+    pub fn result(
+        &self,
+        voters: &HashSet<GameEntityId>,
+        pass_percentage: u64,
+        time_is_up: bool,
+    ) -> GameVoteResult {
+        let (yes, no) = self.counts(voters);
+        let passes = |yes: u64, total: u64| yes * 100 > pass_percentage * total;
+
+        let total = voters.len() as u64;
+        if passes(yes, total) {
+            GameVoteResult::Passed
+        } else if !passes(total - no, total) {
+            // even if everyone else votes yes
+            GameVoteResult::Failed
+        } else if time_is_up {
+            if passes(yes, yes + no) {
+                GameVoteResult::Passed
+            } else {
+                GameVoteResult::Failed
+            }
+        } else {
+            GameVoteResult::Running
+        }
+    }
+}
@@ -7,7 +7,7 @@ pub mod world {
     use hashlink::LinkedHashSet;
     use hiarc::Hiarc;
     use math::math::{
-        closest_point_on_line, distance, distance_squared,
+        closest_point_on_line, distance,
         vector::{ivec2, vec2},
     };
     use pool::{
@@ -15,6 +15,7 @@ pub mod world {
         pool::Pool,
     };
 
+    use game_interface::events::ShotId;
     use game_interface::types::{
         flag::FlagType,
         game::{GameEntityId, GameTickType},
@@ -50,7 +51,7 @@ pub mod world {
             SimulationPipeFlag, SimulationPipeLaser, SimulationPipePickup,
             SimulationPipeProjectile,
         },
-        spawns::GameSpawns,
+        spawns::{GameSpawns, SpawnCharacter, SpawnHistory, SpawnStrategy},
         state::state::TICKS_PER_SECOND,
     };
 
@@ -116,6 +117,18 @@ pub mod world {
             self.other_characters.get(other_char_id).unwrap().pos.pos()
         }
 
+        fn get_other_character_pos_and_side_by_id(
+            &self,
+            other_char_id: &GameEntityId,
+        ) -> Option<(&vec2, Option<MatchSide>)> {
+            if self.removed_characters.contains(other_char_id) {
+                return None;
+            }
+            self.other_characters
+                .get(other_char_id)
+                .map(|char| (char.pos.pos(), char.core.side))
+        }
+
         fn get_other_character_by_id_mut(
             &mut self,
             other_char_id: &GameEntityId,
@@ -197,6 +210,7 @@ pub mod world {
         pub simulation_events: SimulationEntityEvents,
         pub(crate) play_field: CharacterPositionPlayfield,
         pub(crate) hooks: HookedCharacters,
+        spawn_history: SpawnHistory,
     }
 
     impl GameWorld {
@@ -210,6 +224,7 @@ pub mod world {
             let simulation_events = SimulationEntityEvents::new();
             let mut inactive_game_objects = GameObjectsWorld {
                 pickups: Default::default(),
+                objective_zones: Default::default(),
             };
 
             let mut red_flags = world_pool.flag_pool.flag_pool.new();
@@ -292,6 +307,7 @@ pub mod world {
                 simulation_events,
                 play_field: CharacterPositionPlayfield::new(width, height),
                 hooks: Default::default(),
+                spawn_history: Default::default(),
             }
         }
 
@@ -483,6 +499,7 @@ pub mod world {
             force: f32,
             explosive: bool,
             ty: WeaponType,
+            shot_id: ShotId,
         ) {
             let projectile = Projectile::new(
                 &projectile_id,
@@ -493,6 +510,7 @@ pub mod world {
                 force,
                 explosive,
                 ty,
+                shot_id,
                 &self.world_pool.projectile_pool,
                 &self.simulation_events,
             );
@@ -513,6 +531,7 @@ pub mod world {
             pos: &vec2,
             dir: &vec2,
             start_energy: f32,
+            shot_id: ShotId,
 
             can_hit_others: bool,
             can_hit_own: bool,
@@ -522,6 +541,7 @@ pub mod world {
                 pos,
                 dir,
                 start_energy,
+                shot_id,
                 can_hit_others,
                 can_hit_own,
                 &self.world_pool.laser_pool,
@@ -549,6 +569,24 @@ pub mod world {
             );
         }
 
+        /// Removes all entities that are owned by the given character
+        /// and drops the flags it carries.
+        ///
+        /// Must be called whenever a character leaves this world without dying,
+        /// e.g. when switching the stage or when the player drops,
+        /// so that no entity refers to a character that does not exist anymore.
+        pub(crate) fn remove_entities_of_character(&mut self, character_id: &GameEntityId) {
+            self.projectiles
+                .retain_with_order(|_, proj| proj.character_id != *character_id);
+            self.lasers
+                .retain_with_order(|_, laser| laser.character_id != *character_id);
+            self.red_flags
+                .values_mut()
+                .chain(self.blue_flags.values_mut())
+                .filter(|flag| flag.core.carrier == Some(*character_id))
+                .for_each(|flag| flag.drop_from_carrier());
+        }
+
         fn tick_projectiles(&mut self, pipe: &mut SimulationPipeStage) {
             self.projectiles.retain_with_order(|_, proj| {
                 if self.characters.contains_key(&proj.character_id) {
@@ -614,6 +652,7 @@ pub mod world {
                 pickup.tick(&mut SimulationPipePickup::new(
                     &mut self.characters,
                     &self.play_field,
+                    &pipe.game_options.overheal,
                 )) != EntityTickResult::RemoveEntity
             });
         }
@@ -623,6 +662,7 @@ pub mod world {
                 pickup.tick_deferred(&mut SimulationPipePickup::new(
                     &mut self.characters,
                     &self.play_field,
+                    &pipe.game_options.overheal,
                 )) != EntityTickResult::RemoveEntity
             });
         }
@@ -635,6 +675,7 @@ pub mod world {
                         &mut self.characters,
                         laser.character_id,
                         &self.play_field,
+                        pipe.game_options.instagib,
                     )) != EntityTickResult::RemoveEntity
             });
         }
@@ -646,6 +687,7 @@ pub mod world {
                     &mut self.characters,
                     laser.character_id,
                     &self.play_field,
+                    pipe.game_options.instagib,
                 )) != EntityTickResult::RemoveEntity
             });
         }
@@ -675,6 +717,8 @@ pub mod world {
                 ));
                 if EntityTickResult::RemoveEntity == res {
                     self.removed_characters_helper.insert(*id);
+                } else {
+                    character.handle_overheal_decay(&pipe.game_options.overheal);
                 }
 
                 // handle the entity events
@@ -682,12 +726,16 @@ pub mod world {
                     match &ev {
                         CharacterEvent::Despawn { .. } => {
                             self.removed_characters_helper.insert(*id);
+                            if !pipe.is_prediction {
+                                self.spawn_history.add_death(*character.pos.pos());
+                            }
                         }
                         CharacterEvent::Projectile {
                             pos,
                             dir,
                             ty,
                             lifetime,
+                            shot_id,
                         } => {
                             if let Some(id_generator) = &self.id_generator {
                                 let proj_id = id_generator.next_id();
@@ -706,6 +754,7 @@ pub mod world {
                                         WeaponType::Grenade => true,
                                     },
                                     *ty,
+                                    *shot_id,
                                     &pipe.world_pool.projectile_pool,
                                     &self.simulation_events,
                                 );
@@ -718,7 +767,12 @@ pub mod world {
                                 );
                             }
                         }
-                        CharacterEvent::Laser { pos, dir, energy } => {
+                        CharacterEvent::Laser {
+                            pos,
+                            dir,
+                            energy,
+                            shot_id,
+                        } => {
                             if let Some(id_generator) = &self.id_generator {
                                 let id = id_generator.next_id();
                                 let laser = Laser::new(
@@ -726,6 +780,7 @@ pub mod world {
                                     pos,
                                     dir,
                                     *energy,
+                                    *shot_id,
                                     true,  // TODO:
                                     false, // TODO:
                                     &pipe.world_pool.laser_pool,
@@ -793,84 +848,21 @@ pub mod world {
             });
         }
 
-        pub(crate) fn get_spawn_pos(&self, spawns: &GameSpawns, side: Option<MatchSide>) -> vec2 {
-            match side {
-                Some(side) => {
-                    fn eval_spawn<'a>(
-                        characters: &Characters,
-                        filter_side: MatchSide,
-                        spawn: impl Iterator<Item = &'a vec2>,
-                    ) -> vec2 {
-                        let max_by = |&spawn1: &&vec2, &spawn2: &&vec2| {
-                            let sum_dist_spawn = |spawn: &vec2| {
-                                characters
-                                    .values()
-                                    .map(|char| {
-                                        // multiply by factor so that players of the other side
-                                        // are considered near.
-                                        distance_squared(spawn, char.pos.pos()) as f64
-                                            * if char.core.side == Some(filter_side) {
-                                                0.5
-                                            } else {
-                                                1.0
-                                            }
-                                    })
-                                    .min_by(|f1, f2| f1.total_cmp(f2))
-                                    .unwrap_or_default()
-                            };
-
-                            let dist1 = sum_dist_spawn(spawn1);
-                            let dist2 = sum_dist_spawn(spawn2);
-                            dist1.total_cmp(&dist2)
-                        };
-                        spawn.max_by(max_by).cloned().unwrap_or(vec2::default())
-                    }
-                    match side {
-                        MatchSide::Red => eval_spawn(
-                            &self.characters,
-                            MatchSide::Blue,
-                            spawns
-                                .spawns_red
-                                .iter()
-                                .rev()
-                                .chain(spawns.spawns.iter().rev()),
-                        ),
-                        MatchSide::Blue => eval_spawn(
-                            &self.characters,
-                            MatchSide::Red,
-                            spawns
-                                .spawns_blue
-                                .iter()
-                                .rev()
-                                .chain(spawns.spawns.iter().rev()),
-                        ),
-                    }
-                }
-                None => {
-                    // find spawn furthest away from all players
-                    // reverse iterators bcs if multiple are found the first should be
-                    // picked, not the last
-                    spawns
-                        .spawns
-                        .iter()
-                        .rev()
-                        .chain(spawns.spawns_red.iter().rev())
-                        .chain(spawns.spawns_blue.iter().rev())
-                        .max_by(|&spawn1, &spawn2| {
-                            let sum_dist_spawn = |spawn: &vec2| {
-                                self.characters
-                                    .values()
-                                    .map(|char| distance_squared(spawn, char.pos.pos()) as f64)
-                                    .min_by(|f1, f2| f1.total_cmp(f2))
-                                    .unwrap_or_default()
-                            };
-
-                            sum_dist_spawn(spawn1).total_cmp(&sum_dist_spawn(spawn2))
-                        })
-                        .cloned()
-                        .unwrap_or(vec2::default())
-                }
-            }
+        pub(crate) fn get_spawn_pos(
+            &mut self,
+            spawns: &GameSpawns,
+            side: Option<MatchSide>,
+            strategy: SpawnStrategy,
+        ) -> vec2 {
+            let characters: Vec<_> = self
+                .characters
+                .values()
+                .map(|char| SpawnCharacter {
+                    pos: char.pos.pos(),
+                    side: char.core.side,
+                })
+                .collect();
+            spawns.spawn_pos(strategy, side, &characters, &mut self.spawn_history)
         }
 
         fn handle_simulation_events(&mut self, events: &[SimulationEventWorldEntity]) {
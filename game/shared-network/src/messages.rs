@@ -26,6 +26,10 @@ use shared_base::network::messages::{
     MsgSvChatMsg, MsgSvServerInfo,
 };
 
+/// The version of the network messages, see [`ClientToServerMessage::Version`].
+/// Must be increased whenever the messages change incompatibly.
+pub const NETWORK_VERSION: u64 = 1;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct MsgSvInputAck {
     pub id: u64,
@@ -48,6 +52,24 @@ pub enum MsgSvLoadVotes {
 pub struct MsgSvSpatialChatOfEntitity {
     pub latest_opus_frames: BTreeMap<u64, Vec<Vec<u8>>>,
     pub player_unique_id: PlayerUniqueId,
+    /// The player was granted priority by the server (e.g. a caster),
+    /// other voices are lowered while a priority speaker talks.
+    pub priority: bool,
+}
+
+/// Settings the client can request to reduce its bandwidth usage,
+/// e.g. on mobile connections.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MsgClNetworkSettings {
+    /// Request a snapshot only every n-th tick.
+    /// The server never sends snapshots more often than its own setting.
+    /// `None` uses the server's setting.
+    pub ticks_per_snapshot: Option<NonZeroU64>,
+    /// Packets to this client should be compressed with a higher level.
+    pub high_compression: bool,
+    /// Only send events relevant for the game play, skip
+    /// purely visual events of other characters.
+    pub reduced_events: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -104,6 +126,15 @@ pub enum ServerToClientMessage<'a> {
     SpatialChat {
         entities: HashMap<GameEntityId, MsgSvSpatialChatOfEntitity>,
     },
+    /// Response to [`ClientToServerMessage::TimeSync`].
+    TimeSync {
+        /// The id of the request
+        id: u64,
+        /// The server time when the request arrived.
+        server_time: Duration,
+        /// The time the server needed until it responded.
+        overhead: Duration,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -113,6 +144,8 @@ pub enum ClientToServerPlayerMessage {
     Kill,
     JoinSpectator,
     SwitchToFreeCam(ClientFreeCamMode),
+    /// See [`game_interface::client_commands::ClientCommand::SpectateTarget`].
+    SpectateTarget(Option<GameEntityId>),
     StartVote(VoteType),
     Voted(Voted),
     Emoticon(EmoticonType),
@@ -133,6 +166,9 @@ pub enum ClientToServerPlayerMessage {
 
 #[derive(Serialize, Deserialize)]
 pub enum ClientToServerMessage<'a> {
+    /// The first message of the client, with its [`NETWORK_VERSION`].
+    /// Must stay the first variant, so that every version can read it.
+    Version(u64),
     Ready(MsgClReady),
     AddLocalPlayer(MsgClAddLocalPlayer),
     PlayerMsg((GameEntityId, ClientToServerPlayerMessage)),
@@ -160,6 +196,14 @@ pub enum ClientToServerMessage<'a> {
     /// Notify the server that the clients wants no
     /// more spatial chat packets.
     SpatialChatDeactivated,
+    /// Request the current server time, used to
+    /// estimate the clock drift between client and server.
+    TimeSync {
+        /// Ever increasing id of the request
+        id: u64,
+    },
+    /// Change the network settings for this client.
+    NetworkSettings(MsgClNetworkSettings),
 }
 
 #[derive(Serialize, Deserialize)]
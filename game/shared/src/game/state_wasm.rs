@@ -14,7 +14,8 @@ pub mod state_wasm {
     use game_interface::client_commands::ClientCommand;
     use game_interface::events::{EventClientInfo, EventId, GameEvents};
     use game_interface::interface::{GameStateCreate, GameStateCreateOptions, GameStateStaticInfo};
-    use game_interface::types::character_info::NetworkCharacterInfo;
+    use game_interface::server_commands::ServerCommand;
+    use game_interface::types::character_info::{CharacterInfoRedaction, NetworkCharacterInfo};
     use game_interface::types::emoticons::EmoticonType;
     use game_interface::types::game::GameEntityId;
     use game_interface::types::input::{
@@ -120,6 +121,9 @@ pub mod state_wasm {
         #[wasm_func_auto_call]
         fn client_command(&mut self, player_id: &GameEntityId, cmd: ClientCommand) {}
 
+        #[wasm_func_auto_call]
+        fn server_command(&mut self, cmd: ServerCommand) {}
+
         #[wasm_func_auto_call]
         fn collect_characters_info(&self) -> PoolLinkedHashMap<GameEntityId, CharacterInfo> {}
 
@@ -170,6 +174,14 @@ pub mod state_wasm {
         ) -> SnapshotLocalPlayers {
         }
 
+        #[wasm_func_auto_call]
+        fn redact_snapshot(
+            &self,
+            snapshot: &MtPoolCow<'static, [u8]>,
+            redaction: &CharacterInfoRedaction,
+        ) -> MtPoolCow<'static, [u8]> {
+        }
+
         #[wasm_func_auto_call]
         fn snapshot_for_hotreload(&self) -> Option<MtPoolCow<'static, [u8]>> {}
 
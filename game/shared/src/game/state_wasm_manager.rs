@@ -14,7 +14,8 @@ use game_interface::events::{EventClientInfo, EventId, GameEvents};
 use game_interface::interface::{
     GameStateCreate, GameStateCreateOptions, GameStateServerOptions, GameStateStaticInfo,
 };
-use game_interface::types::character_info::NetworkCharacterInfo;
+use game_interface::server_commands::ServerCommand;
+use game_interface::types::character_info::{CharacterInfoRedaction, NetworkCharacterInfo};
 use game_interface::types::emoticons::EmoticonType;
 use game_interface::types::game::{GameEntityId, GameTickType, NonZeroGameTickType};
 use game_interface::types::input::{
@@ -230,6 +231,10 @@ impl GameStateInterface for GameStateWasmManager {
         self.state.as_mut().client_command(player_id, cmd)
     }
 
+    fn server_command(&mut self, cmd: ServerCommand) {
+        self.state.as_mut().server_command(cmd)
+    }
+
     fn set_player_input(
         &mut self,
         player_id: &GameEntityId,
@@ -263,6 +268,14 @@ impl GameStateInterface for GameStateWasmManager {
         self.state.as_mut().build_from_snapshot(snapshot)
     }
 
+    fn redact_snapshot(
+        &self,
+        snapshot: &MtPoolCow<'static, [u8]>,
+        redaction: &CharacterInfoRedaction,
+    ) -> MtPoolCow<'static, [u8]> {
+        self.state.as_ref().redact_snapshot(snapshot, redaction)
+    }
+
     fn snapshot_for_hotreload(&self) -> Option<MtPoolCow<'static, [u8]>> {
         self.state.as_ref().snapshot_for_hotreload()
     }
@@ -44,18 +44,31 @@ impl AsMut<dyn RenderGameInterface + 'static> for RenderGameWrapper {
 
 pub struct RenderGameWasmManager {
     state: RenderGameWrapper,
+    render_mod: String,
     fs_change_watcher: Box<dyn FileSystemWatcherItemInterface>,
     canvas_handle: GraphicsCanvasHandle,
     window_props: WindowProps,
 }
 
-const MODS_PATH: &str = "mods/render";
+pub const RENDER_MODS_PATH: &str = "mods/render";
+/// The render module in [`RENDER_MODS_PATH`] (without `.wasm`),
+/// if the user did not choose another one.
+pub const RENDER_MODS_DEFAULT: &str = "render_game";
 
 impl RenderGameWasmManager {
-    pub async fn load_module(fs: &Arc<dyn FileSystemInterface>) -> anyhow::Result<Vec<u8>> {
-        let cache = Arc::new(Cache::<0>::new(MODS_PATH, fs));
+    /// Loads the render module `render_mod` (without `.wasm`) from [`RENDER_MODS_PATH`].
+    /// An empty name always fails, so the built-in renderer is used.
+    pub async fn load_module(
+        fs: &Arc<dyn FileSystemInterface>,
+        render_mod: &str,
+    ) -> anyhow::Result<Vec<u8>> {
+        anyhow::ensure!(
+            !render_mod.is_empty(),
+            "no render module set, the built-in renderer is used."
+        );
+        let cache = Arc::new(Cache::<0>::new(RENDER_MODS_PATH, fs));
         // check if loading was finished
-        let path_str = MODS_PATH.to_string() + "/render_game.wasm";
+        let path_str = format!("{RENDER_MODS_PATH}/{render_mod}.wasm");
 
         cache
             .load(&path_str, |wasm_bytes| {
@@ -75,12 +88,14 @@ impl RenderGameWasmManager {
         sys: &System,
         map_file: Vec<u8>,
         config: &ConfigEngine,
+        render_mod: &str,
         wasm_module: Option<Vec<u8>>,
         props: RenderGameCreateOptions,
     ) -> Self {
-        let fs_change_watcher = io
-            .fs
-            .watch_for_change(MODS_PATH.as_ref(), Some("render_game.wasm".as_ref())); // TODO: even tho watching individual files makes more sense, it should still make sure it's the same the server watches
+        let fs_change_watcher = io.fs.watch_for_change(
+            RENDER_MODS_PATH.as_ref(),
+            Some(format!("{render_mod}.wasm").as_ref()),
+        ); // TODO: even tho watching individual files makes more sense, it should still make sure it's the same the server watches
 
         let state = if let Some(wasm_module) = wasm_module {
             let state = RenderWasm::new(
@@ -109,6 +124,7 @@ impl RenderGameWasmManager {
         };
         Self {
             state,
+            render_mod: render_mod.to_string(),
             fs_change_watcher,
             window_props: graphics.canvas_handle.window_props(),
             canvas_handle: graphics.canvas_handle.clone(),
@@ -118,6 +134,12 @@ impl RenderGameWasmManager {
     pub fn should_reload(&self) -> bool {
         self.fs_change_watcher.has_file_change()
     }
+
+    /// The name of the render module that was requested,
+    /// see [`RenderGameWasmManager::load_module`].
+    pub fn render_mod(&self) -> &str {
+        &self.render_mod
+    }
 }
 
 impl RenderGameInterface for RenderGameWasmManager {
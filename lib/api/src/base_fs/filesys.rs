@@ -1,4 +1,5 @@
 use std::{
+    ops::Range,
     path::{Path, PathBuf},
     sync::atomic::AtomicU64,
 };
@@ -67,6 +68,14 @@ impl FileSystemInterface for FileSystem {
         todo!("not implemented")
     }
 
+    async fn read_file_range(
+        &self,
+        _file_path: &Path,
+        _range: Range<u64>,
+    ) -> std::io::Result<Vec<u8>> {
+        todo!("not implemented")
+    }
+
     async fn file_exists(&self, _file_path: &Path) -> bool {
         todo!("not implemented")
     }
@@ -79,6 +88,10 @@ impl FileSystemInterface for FileSystem {
         todo!("not implemented")
     }
 
+    async fn remove_file(&self, _file_path: &Path) -> std::io::Result<()> {
+        todo!("not implemented")
+    }
+
     async fn entries_in_dir(
         &self,
         path: &Path,
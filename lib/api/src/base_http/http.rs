@@ -79,6 +79,22 @@ impl HttpClientInterface for HttpClient {
         res.unwrap()
     }
 
+    async fn download_binary_limited(
+        &self,
+        url: Url,
+        hash: &Hash,
+        max_size: usize,
+    ) -> anyhow::Result<Bytes, HttpError> {
+        // the host downloads the whole file anyway
+        let res = self.download_binary(url, hash).await?;
+        if res.len() > max_size {
+            return Err(HttpError::Other(format!(
+                "file is bigger than {max_size} bytes"
+            )));
+        }
+        Ok(res)
+    }
+
     async fn post_json(&self, url: Url, data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpError> {
         let mut res;
         let id = self.id.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
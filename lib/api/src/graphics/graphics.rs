@@ -6,7 +6,7 @@ use std::{
 use graphics_backend_traits::{
     plugin::GraphicsObjectRewriteFunc,
     traits::{GraphicsBackendInterface, GraphicsBackendMtInterface},
-    types::BackendCommands,
+    types::{BackendCommands, GraphicsBackendStats},
 };
 use graphics_base_traits::traits::GraphicsStreamedData;
 use graphics_types::{
@@ -117,8 +117,17 @@ impl GraphicsBackendInterface for GraphicsBackend {
             },
             cur: CurGpu {
                 msaa_sampling_count: 1,
+                max_texture_size: u32::MAX,
+                max_texture_array_layers: u32::MAX,
+                max_offscreen_width: u32::MAX,
+                max_offscreen_height: u32::MAX,
                 name: "wasm".to_string(),
             },
         })
     }
+
+    fn stats(&self) -> GraphicsBackendStats {
+        // the commands are run by the host
+        GraphicsBackendStats::default()
+    }
 }
@@ -299,7 +299,14 @@ pub struct EncoderSettings {
     /// "Constant Rate Factor" for x264.
     /// Where 0 is lossless and 51 is the worst.
     /// 18 is default.
+    /// Only used if [`Self::bitrate`] is `0`.
     pub crf: u8,
+    /// Target bitrate of the video in kbit/s.
+    /// `0` uses a constant quality ([`Self::crf`]) instead.
+    pub bitrate: u64,
+    /// The video codec, `h264` or `hevc`.
+    /// Unknown codecs fall back to `h264`.
+    pub codec: String,
     /// Width of the video
     pub width: u32,
     /// Height of the video
@@ -152,11 +152,16 @@ impl Encoder {
         settings: &EncoderSettings,
         global_header: bool,
     ) -> anyhow::Result<VideoEncoder, ffmpeg_next::Error> {
-        let codec = match settings.hw_accel.as_str() {
-            "vaapi" => "h264_vaapi",
-            "cuda" => "h264_nvenc",
-            "amf" => "h264_amf",
-            _ => "libx264",
+        let is_hevc = settings.codec == "hevc";
+        let codec = match (settings.hw_accel.as_str(), is_hevc) {
+            ("vaapi", false) => "h264_vaapi",
+            ("cuda", false) => "h264_nvenc",
+            ("amf", false) => "h264_amf",
+            ("vaapi", true) => "hevc_vaapi",
+            ("cuda", true) => "hevc_nvenc",
+            ("amf", true) => "hevc_amf",
+            (_, false) => "libx264",
+            (_, true) => "libx265",
         };
         let codec = encoder::find_by_name(codec).ok_or(ffmpeg_next::Error::EncoderNotFound)?;
         let mut encoder = codec::context::Context::new_with_codec(codec)
@@ -219,8 +224,14 @@ impl Encoder {
         }
         let mut options = ffmpeg_next::Dictionary::new();
         options.set("preset", "ultrafast");
-        options.set("crf", &settings.crf.to_string());
-        options.set("x264-params", "bframes=8");
+        if settings.bitrate > 0 {
+            encoder.set_bit_rate(settings.bitrate as usize * 1000);
+        } else {
+            options.set("crf", &settings.crf.to_string());
+        }
+        if !is_hevc {
+            options.set("x264-params", "bframes=8");
+        }
         let encoder = encoder.open_with(options)?;
 
         let mut ost = octx.add_stream_with(&encoder)?;
@@ -1,4 +1,6 @@
 use std::{
+    io::SeekFrom,
+    ops::Range,
     path::{Path, PathBuf},
     sync::{atomic::AtomicBool, mpsc::channel, Arc, Mutex, RwLock},
     thread::JoinHandle,
@@ -14,6 +16,7 @@ use directories::ProjectDirs;
 use hashlink::LinkedHashMap;
 use notify::{event::RenameMode, recommended_watcher, RecommendedWatcher, RecursiveMode, Watcher};
 use path_slash::PathBufExt;
+use tokio::io::AsyncSeekExt;
 use virtual_fs::{host_fs, mem_fs, AsyncReadExt, AsyncWriteExt, DirEntry, OpenOptionsConfig};
 
 #[derive(Debug)]
@@ -564,6 +567,33 @@ impl FileSystemInterface for FileSystem {
         Ok(file_res)
     }
 
+    async fn read_file_range(
+        &self,
+        file_path: &Path,
+        range: Range<u64>,
+    ) -> std::io::Result<Vec<u8>> {
+        let fs = self.get_scoped_fs(FileSystemPath::OfType(FileSystemType::ReadWrite));
+        let file_path = fs.get_path(file_path);
+        let mut file = fs.fs.open(
+            &file_path,
+            &OpenOptionsConfig {
+                read: true,
+                write: false,
+                create_new: false,
+                create: false,
+                append: false,
+                truncate: false,
+            },
+        )?;
+        file.seek(SeekFrom::Start(range.start)).await?;
+        let mut file_res: Vec<_> = Default::default();
+        (&mut file)
+            .take(range.end.saturating_sub(range.start))
+            .read_to_end(&mut file_res)
+            .await?;
+        Ok(file_res)
+    }
+
     async fn file_exists(&self, file_path: &Path) -> bool {
         let fs = self.get_scoped_fs(FileSystemPath::OfType(FileSystemType::ReadWrite));
         let file_path = fs.get_path(file_path);
@@ -634,6 +664,12 @@ impl FileSystemInterface for FileSystem {
         Ok(())
     }
 
+    async fn remove_file(&self, file_path: &Path) -> std::io::Result<()> {
+        let fs = self.get_scoped_fs(FileSystemPath::OfType(FileSystemType::ReadWrite));
+        fs.fs.remove_file(&fs.get_path(file_path))?;
+        Ok(())
+    }
+
     async fn entries_in_dir(
         &self,
         path: &Path,
@@ -42,9 +42,17 @@ impl HttpClient {
         })
     }
 
-    async fn to_bytes_limited(r: reqwest::Response) -> Result<Bytes, HttpError> {
+    /// The maximum size of any download.
+    const MAX_DOWNLOAD_SIZE: usize = 1024 * 1024 * 1024;
+
+    async fn to_bytes_limited(r: reqwest::Response, max_size: usize) -> Result<Bytes, HttpError> {
+        if r.content_length().is_some_and(|len| len > max_size as u64) {
+            return Err(HttpError::Other(format!(
+                "file is bigger than {max_size} bytes"
+            )));
+        }
         let res = http::Response::from(r);
-        let limited = Limited::new(res, 1024 * 1024 * 1024);
+        let limited = Limited::new(res, max_size);
         Ok(limited
             .collect()
             .await
@@ -59,7 +67,11 @@ impl HttpClientInterface for HttpClient {
         (url.scheme() == "https")
             .then_some(())
             .ok_or_else(|| HttpError::Other("url must be https".into()))?;
-        let res = Self::to_bytes_limited(Self::map_err(self.client.get(url).send().await)?).await?;
+        let res = Self::to_bytes_limited(
+            Self::map_err(self.client.get(url).send().await)?,
+            Self::MAX_DOWNLOAD_SIZE,
+        )
+        .await?;
         String::from_utf8(res.to_vec()).map_err(|err| HttpError::Other(err.to_string()))
     }
 
@@ -68,15 +80,28 @@ impl HttpClientInterface for HttpClient {
             .then_some(())
             .ok_or_else(|| HttpError::Other("url must be https".into()))?;
         let res = Self::map_err(self.client.get(url).send().await)?;
-        Self::to_bytes_limited(res).await
+        Self::to_bytes_limited(res, Self::MAX_DOWNLOAD_SIZE).await
     }
 
     async fn download_binary(&self, url: Url, hash: &Hash) -> anyhow::Result<Bytes, HttpError> {
+        self.download_binary_limited(url, hash, Self::MAX_DOWNLOAD_SIZE)
+            .await
+    }
+
+    async fn download_binary_limited(
+        &self,
+        url: Url,
+        hash: &Hash,
+        max_size: usize,
+    ) -> anyhow::Result<Bytes, HttpError> {
         (url.scheme() == "https" || url.scheme() == "http")
             .then_some(())
             .ok_or_else(|| HttpError::Other("url must be http or https".into()))?;
-        let res = Self::to_bytes_limited(Self::map_err(self.client.get(url.clone()).send().await)?)
-            .await?;
+        let res = Self::to_bytes_limited(
+            Self::map_err(self.client.get(url.clone()).send().await)?,
+            max_size.min(Self::MAX_DOWNLOAD_SIZE),
+        )
+        .await?;
 
         generate_hash_for(&res)
             .eq(hash)
@@ -188,6 +213,18 @@ impl HttpClientInterface for LazyHttpClient {
         self.get_client().await.download_binary(url, hash).await
     }
 
+    async fn download_binary_limited(
+        &self,
+        url: Url,
+        hash: &Hash,
+        max_size: usize,
+    ) -> anyhow::Result<Bytes, HttpError> {
+        self.get_client()
+            .await
+            .download_binary_limited(url, hash, max_size)
+            .await
+    }
+
     async fn post_json(&self, url: Url, data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpError> {
         self.get_client().await.post_json(url, data).await
     }
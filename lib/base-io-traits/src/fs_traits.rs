@@ -2,6 +2,7 @@ pub use rustc_hash::FxHashMap as HashMap;
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::Debug,
+    ops::Range,
     path::{Path, PathBuf},
 };
 
@@ -50,12 +51,22 @@ pub trait FileSystemInterface: Debug + Sync + Send {
         path: FileSystemPath,
     ) -> std::io::Result<Vec<u8>>;
 
+    /// Read a range of bytes of a file in the read-write file system.
+    /// The range may exceed the end of the file, only the existing bytes are read.
+    async fn read_file_range(
+        &self,
+        file_path: &Path,
+        range: Range<u64>,
+    ) -> std::io::Result<Vec<u8>>;
+
     /// Does the given file exist in the read-write file system
     async fn file_exists(&self, file_path: &Path) -> bool;
     /// Write a file to the read-write file system
     async fn write_file(&self, file_path: &Path, data: Vec<u8>) -> std::io::Result<()>;
     /// Create a directory recursively to the read-write file system
     async fn create_dir(&self, dir_path: &Path) -> std::io::Result<()>;
+    /// Remove a file from the read-write file system
+    async fn remove_file(&self, file_path: &Path) -> std::io::Result<()>;
 
     /// Get's the name of all entries in a directory, that also includes directories.
     async fn entries_in_dir(
@@ -56,6 +56,15 @@ pub trait HttpClientInterface: Debug + Send + Sync {
     /// Downloads binary data. This only allows reading binary data where the hash is already known
     async fn download_binary(&self, url: Url, hash: &Hash) -> anyhow::Result<Bytes, HttpError>;
 
+    /// Like [`Self::download_binary`], but stops the download as soon as
+    /// it is known to be bigger than `max_size` bytes.
+    async fn download_binary_limited(
+        &self,
+        url: Url,
+        hash: &Hash,
+        max_size: usize,
+    ) -> anyhow::Result<Bytes, HttpError>;
+
     /// Post a json body and return arbitrary bytes returned as a response.
     async fn post_json(&self, url: Url, data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpError>;
 
@@ -159,6 +159,9 @@ pub struct ConfigDebug {
     // show various app related debug elements (e.g. different ui elements)
     #[default = false]
     pub app: bool,
+    // show the draw calls, uploads & canvas switches per frame below the fps
+    #[default = false]
+    pub gfx_stats: bool,
     #[default = false]
     pub untrusted_cert: bool,
 }
@@ -9,8 +9,9 @@ use graphics_types::{
 use pool::{mixed_pool::PoolSyncPoint, mt_datatypes::PoolVec};
 
 use crate::{
-    frame_fetcher_plugin::BackendFrameFetcher, plugin::GraphicsObjectRewriteFunc,
-    types::BackendCommands,
+    frame_fetcher_plugin::BackendFrameFetcher,
+    plugin::GraphicsObjectRewriteFunc,
+    types::{BackendCommands, GraphicsBackendStats},
 };
 
 pub trait DriverBackendInterface: Debug {
@@ -54,6 +55,10 @@ pub trait GraphicsBackendInterface: Debug + 'static {
 
     fn gpus(&self) -> Arc<Gpus>;
 
+    /// Statistics of the last finished frame (until the last swap),
+    /// e.g. to find out which component causes many draw calls or uploads.
+    fn stats(&self) -> GraphicsBackendStats;
+
     fn get_backend_mt(&self) -> Arc<dyn GraphicsBackendMtInterface + Sync + Send + 'static>;
 }
 
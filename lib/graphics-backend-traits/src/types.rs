@@ -1,4 +1,4 @@
-use graphics_types::commands::AllCommands;
+use graphics_types::commands::{AllCommands, CommandsMisc};
 use hiarc::{hiarc_safer_rc_refcell, Hiarc};
 
 #[hiarc_safer_rc_refcell]
@@ -34,3 +34,40 @@ impl BackendCommands {
         std::mem::swap(&mut self.cmds, swap);
     }
 }
+
+/// What the backend did in the last finished frame,
+/// see [`crate::traits::GraphicsBackendInterface::stats`].
+#[derive(Debug, Hiarc, Default, Clone, Copy)]
+pub struct GraphicsBackendStats {
+    /// Render commands, each of them is a draw call.
+    pub draw_calls: u64,
+    /// Created, recreated & updated buffer objects.
+    pub buffer_uploads: u64,
+    /// Created & updated textures.
+    pub texture_uploads: u64,
+    /// Switches between the onscreen & offscreen canvases.
+    pub canvas_switches: u64,
+    /// Texture memory in bytes that is currently in use.
+    pub texture_memory_usage: u64,
+    /// Buffer object memory in bytes that is currently in use.
+    pub buffer_memory_usage: u64,
+}
+
+impl GraphicsBackendStats {
+    /// Counts the command into the stats of the current frame.
+    pub fn count_cmd(&mut self, cmd: &AllCommands) {
+        match cmd {
+            AllCommands::Render(_) => self.draw_calls += 1,
+            AllCommands::Misc(
+                CommandsMisc::CreateBufferObject(_)
+                | CommandsMisc::RecreateBufferObject(_)
+                | CommandsMisc::UpdateBufferObject(_),
+            ) => self.buffer_uploads += 1,
+            AllCommands::Misc(CommandsMisc::TextureCreate(_) | CommandsMisc::TextureUpdate(_)) => {
+                self.texture_uploads += 1
+            }
+            AllCommands::Misc(CommandsMisc::SwitchCanvas(_)) => self.canvas_switches += 1,
+            AllCommands::Misc(_) => {}
+        }
+    }
+}
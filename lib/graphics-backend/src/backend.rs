@@ -11,7 +11,7 @@ use graphics_backend_traits::{
     frame_fetcher_plugin::BackendFrameFetcher,
     plugin::{BackendCustomPipeline, GraphicsObjectRewriteFunc},
     traits::{GraphicsBackendInterface, GraphicsBackendMtInterface},
-    types::BackendCommands,
+    types::{BackendCommands, GraphicsBackendStats},
 };
 use graphics_base_traits::traits::{GraphicsStreamVertices, GraphicsStreamedData};
 use hiarc::Hiarc;
@@ -134,6 +134,11 @@ pub struct GraphicsBackendBase {
     backend_mt: Arc<GraphicsBackendMultiThreaded>,
 
     backend_cmds_in_use: Vec<AllCommands>,
+    /// how many render commands were executed since the backend was created
+    render_cmd_count: u64,
+    /// the stats of the frame that is currently built
+    cur_frame_stats: GraphicsBackendStats,
+    last_frame_stats: GraphicsBackendStats,
 
     window_props: WindowProps,
     memory_usage: GraphicsBackendMemoryUsage,
@@ -212,6 +217,9 @@ impl GraphicsBackendBase {
             backend_mt,
 
             backend_cmds_in_use: Default::default(),
+            render_cmd_count: 0,
+            cur_frame_stats: Default::default(),
+            last_frame_stats: Default::default(),
 
             window_props: WindowProps {
                 window_width,
@@ -237,6 +245,18 @@ impl GraphicsBackendBase {
     ) -> anyhow::Result<()> {
         self.backend_cmds_in_use.clear();
         buffer.replace(&mut self.backend_cmds_in_use);
+        self.render_cmd_count += self
+            .backend_cmds_in_use
+            .iter()
+            .filter(|cmd| matches!(cmd, AllCommands::Render(_)))
+            .count() as u64;
+        for cmd in self.backend_cmds_in_use.iter() {
+            if let AllCommands::Misc(CommandsMisc::Swap) = cmd {
+                self.last_frame_stats = std::mem::take(&mut self.cur_frame_stats);
+            } else {
+                self.cur_frame_stats.count_cmd(cmd);
+            }
+        }
 
         self.backend
             .run_cmds(stream_data, &mut self.backend_cmds_in_use)?;
@@ -378,6 +398,13 @@ impl GraphicsBackend {
         self.0.borrow().memory_usage.clone()
     }
 
+    /// How many render commands (draw calls) were executed
+    /// since the backend was created.
+    #[must_use]
+    pub fn render_cmd_count(&self) -> u64 {
+        self.0.borrow().render_cmd_count
+    }
+
     pub fn window_created_ntfy(
         &self,
         window: BackendWindow,
@@ -432,4 +459,19 @@ impl GraphicsBackendInterface for GraphicsBackend {
     fn gpus(&self) -> Arc<Gpus> {
         self.0.borrow().backend_mt.backend_mt.gpus()
     }
+
+    fn stats(&self) -> GraphicsBackendStats {
+        let base = self.0.borrow();
+        GraphicsBackendStats {
+            texture_memory_usage: base
+                .memory_usage
+                .texture_memory_usage
+                .load(std::sync::atomic::Ordering::Relaxed),
+            buffer_memory_usage: base
+                .memory_usage
+                .buffer_memory_usage
+                .load(std::sync::atomic::Ordering::Relaxed),
+            ..base.last_frame_stats
+        }
+    }
 }
@@ -34,6 +34,10 @@ impl GraphicsBackendMtType {
                 gpus: Default::default(),
                 cur: CurGpu {
                     msaa_sampling_count: 1,
+                    max_texture_size: u32::MAX,
+                    max_texture_array_layers: u32::MAX,
+                    max_offscreen_width: u32::MAX,
+                    max_offscreen_height: u32::MAX,
                     name: "null".to_string(),
                 },
             }),
@@ -248,6 +248,9 @@ impl PhyDevice {
         limits.optimal_image_copy_mem_alignment =
             device_prop.limits.optimal_buffer_copy_offset_alignment;
         limits.max_texture_size = device_prop.limits.max_image_dimension2_d;
+        limits.max_texture_array_layers = device_prop.limits.max_image_array_layers;
+        limits.max_framebuffer_width = device_prop.limits.max_framebuffer_width;
+        limits.max_framebuffer_height = device_prop.limits.max_framebuffer_height;
         limits.max_sampler_anisotropy = device_prop.limits.max_sampler_anisotropy as u32;
 
         limits.min_uniform_align = device_prop.limits.min_uniform_buffer_offset_alignment as u32;
@@ -269,6 +272,12 @@ impl PhyDevice {
                 limits.min_uniform_align,
                 limits.max_multi_sample.as_raw()
             );
+            info!(
+                "device prop: max texture array layers: {}, max framebuffer size: {}x{}",
+                limits.max_texture_array_layers,
+                limits.max_framebuffer_width,
+                limits.max_framebuffer_height
+            );
         }
 
         let cur_device = device_list[found_device_index];
@@ -311,6 +320,12 @@ impl PhyDevice {
                 cur: CurGpu {
                     name: renderer_name.clone(),
                     msaa_sampling_count: limits.max_multi_sample.as_raw(),
+                    max_texture_size: limits.max_texture_size,
+                    max_texture_array_layers: limits.max_texture_array_layers,
+                    max_offscreen_width: limits.max_framebuffer_width.min(limits.max_texture_size),
+                    max_offscreen_height: limits
+                        .max_framebuffer_height
+                        .min(limits.max_texture_size),
                 },
             }),
             limits,
@@ -1291,17 +1291,27 @@ impl VulkanBackend {
     }
 
     fn cmd_switch_canvas_mode(&mut self, cmd: CommandSwitchCanvasMode) -> anyhow::Result<()> {
+        // fall back to a sample count the gpu supports, instead of failing to create the canvas
+        let multi_sampling = match &cmd.mode {
+            CommandSwitchCanvasModeType::Onscreen => None,
+            CommandSwitchCanvasModeType::Offscreen {
+                has_multi_sampling, ..
+            } => has_multi_sampling
+                .map(|samples| {
+                    Device::get_sample_count(
+                        samples,
+                        &self.props.device.ash_vk.device.phy_device.limits,
+                    )
+                    .as_raw()
+                })
+                .filter(|&samples| samples > 1),
+        };
         let (canvas_index, has_multi_sampling) = match &cmd.mode {
             // even if onscreen has multi-sampling. this is not allowed
             CommandSwitchCanvasModeType::Onscreen => (FrameCanvasIndex::Onscreen, false),
-            CommandSwitchCanvasModeType::Offscreen {
-                id,
-                has_multi_sampling,
-                ..
-            } => (
-                FrameCanvasIndex::Offscreen(*id),
-                has_multi_sampling.is_some(),
-            ),
+            CommandSwitchCanvasModeType::Offscreen { id, .. } => {
+                (FrameCanvasIndex::Offscreen(*id), multi_sampling.is_some())
+            }
         };
         self.new_command_group(
             canvas_index,
@@ -1321,32 +1331,31 @@ impl VulkanBackend {
         drop(frame_g);
         match &cmd.mode {
             CommandSwitchCanvasModeType::Offscreen {
-                id,
-                width,
-                height,
-                has_multi_sampling,
-                ..
-            } => self.render.switch_canvas(CanvasMode::Offscreen {
-                id: *id,
-                device: &self.props.device.ash_vk.device,
-                layouts: &self.props.device.layouts,
-                custom_pipes: &self.props.custom_pipes.pipes,
-                pipeline_cache: &self
-                    .pipeline_cache
-                    .as_ref()
-                    .map(|cache| cache.inner.clone()),
-                standard_texture_descr_pool: &self.props.device.standard_texture_descr_pool,
-                mem_allocator: &self.props.device.mem_allocator,
-                runtime_threadpool: &self.runtime_threadpool,
-                options: &RenderSetupOptions {
-                    offscreen_extent: vk::Extent2D {
-                        width: *width,
-                        height: *height,
-                    },
-                },
-                frame_resources: &mut self.current_frame_resources,
-                has_multi_sampling: *has_multi_sampling,
-            })?,
+                id, width, height, ..
+            } => {
+                // fall back to a size the gpu supports, instead of failing to create the canvas
+                let limits = &self.props.device.ash_vk.device.phy_device.limits;
+                let offscreen_extent = vk::Extent2D {
+                    width: (*width).clamp(1, limits.max_framebuffer_width.max(1)),
+                    height: (*height).clamp(1, limits.max_framebuffer_height.max(1)),
+                };
+                self.render.switch_canvas(CanvasMode::Offscreen {
+                    id: *id,
+                    device: &self.props.device.ash_vk.device,
+                    layouts: &self.props.device.layouts,
+                    custom_pipes: &self.props.custom_pipes.pipes,
+                    pipeline_cache: &self
+                        .pipeline_cache
+                        .as_ref()
+                        .map(|cache| cache.inner.clone()),
+                    standard_texture_descr_pool: &self.props.device.standard_texture_descr_pool,
+                    mem_allocator: &self.props.device.mem_allocator,
+                    runtime_threadpool: &self.runtime_threadpool,
+                    options: &RenderSetupOptions { offscreen_extent },
+                    frame_resources: &mut self.current_frame_resources,
+                    has_multi_sampling: multi_sampling,
+                })?
+            }
             CommandSwitchCanvasModeType::Onscreen => {
                 self.render.switch_canvas(CanvasMode::Onscreen)?
             }
@@ -2788,6 +2797,10 @@ impl VulkanBackend {
 
         let multi_sampling_count = options.gl.msaa_samples & 0xFFFFFFFE; // ignore the uneven bit, only even multi sampling works
 
+        // fall back to a sample count the gpu supports, instead of failing to create the render setup
+        let multi_sampling_count =
+            Device::get_sample_count(multi_sampling_count, &phy_gpu.limits).as_raw() & 0xFFFFFFFE;
+
         let render = RenderSetup::new(
             &loading.props.device.ash_vk.device,
             &loading.props.device.layouts,
@@ -548,6 +548,7 @@ impl VulkanAllocator {
         if width as u32 > self.limits.max_texture_size
             || height as u32 > self.limits.max_texture_size
             || depth as u32 > self.limits.max_texture_size
+            || (is_3d_tex && depth as u32 > self.limits.max_texture_array_layers)
             || (width * height * depth)
                 > (self.limits.max_texture_size as usize * self.limits.max_texture_size as usize)
         {
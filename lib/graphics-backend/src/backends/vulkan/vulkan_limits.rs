@@ -7,6 +7,9 @@ pub struct Limits {
     pub optimal_image_copy_mem_alignment: vk::DeviceSize,
 
     pub max_texture_size: u32,
+    pub max_texture_array_layers: u32,
+    pub max_framebuffer_width: u32,
+    pub max_framebuffer_height: u32,
     pub max_sampler_anisotropy: u32,
     #[hiarc_skip_unsafe]
     pub max_multi_sample: vk::SampleCountFlags,
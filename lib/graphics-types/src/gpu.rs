@@ -21,7 +21,40 @@ pub struct Gpu {
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub struct CurGpu {
     pub name: String,
+    /// Bit set of the supported multi sampling counts,
+    /// e.g. `0b101` supports 1 & 4 samples.
     pub msaa_sampling_count: u32,
+    /// Max width & height of a texture.
+    pub max_texture_size: u32,
+    /// Max layers of a 2d texture array (used for tile layers).
+    pub max_texture_array_layers: u32,
+    /// Max width of an offscreen canvas.
+    pub max_offscreen_width: u32,
+    /// Max height of an offscreen canvas.
+    pub max_offscreen_height: u32,
+}
+
+impl CurGpu {
+    /// The highest supported multi sampling count,
+    /// 1 if multi sampling is not supported.
+    pub fn max_msaa_samples(&self) -> u32 {
+        if self.msaa_sampling_count == 0 {
+            1
+        } else {
+            1 << (31 - self.msaa_sampling_count.leading_zeros())
+        }
+    }
+
+    /// Rounds the requested multi sampling count down to a supported
+    /// count, 0 (multi sampling off) if the request is not supported at all.
+    pub fn supported_msaa_samples(&self, samples: u32) -> u32 {
+        let samples = samples.min(self.max_msaa_samples());
+        (1..=samples.checked_ilog2().unwrap_or_default())
+            .rev()
+            .map(|exp| 1 << exp)
+            .find(|&count| self.msaa_sampling_count & count != 0)
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
@@ -2,7 +2,9 @@ pub mod backend {
     use std::{rc::Rc, sync::Arc};
 
     use graphics_backend_traits::{
-        plugin::GraphicsObjectRewriteFunc, traits::GraphicsBackendInterface, types::BackendCommands,
+        plugin::GraphicsObjectRewriteFunc,
+        traits::GraphicsBackendInterface,
+        types::{BackendCommands, GraphicsBackendStats},
     };
     use graphics_base_traits::traits::GraphicsStreamedData;
     use graphics_types::{
@@ -105,6 +107,11 @@ pub mod backend {
         pub fn gpus(&self) -> Arc<Gpus> {
             self.backend.gpus()
         }
+
+        /// See [`GraphicsBackendInterface::stats`].
+        pub fn stats(&self) -> GraphicsBackendStats {
+            self.backend.stats()
+        }
     }
 
     impl GraphicsBackendHandle {
@@ -143,6 +143,21 @@ impl<T: Debug + Clone + Hash + PartialEq + Eq> Binds<T> {
         self.process_impl(true)
     }
 
+    /// Whether any bind uses the key, alone or in a key chain.
+    pub fn is_bound(&self, key: &BindKey) -> bool {
+        fn contains<F>(keys: &KeyTarget<F>, key: &BindKey) -> bool {
+            keys.iter().any(|(bind_key, target)| {
+                bind_key == key
+                    || match target {
+                        BindTarget::Scancode(inner)
+                        | BindTarget::ScancodeAndActions((inner, _)) => contains(inner, key),
+                        BindTarget::Actions(_) => false,
+                    }
+            })
+        }
+        contains(&self.keys, key)
+    }
+
     pub fn register_bind(&mut self, bind_keys: &[BindKey], action: T) {
         let keys = &mut self.keys;
 
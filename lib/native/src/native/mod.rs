@@ -40,6 +40,10 @@ pub trait NativeImpl {
     fn window_options(&self) -> NativeWindowOptions;
     fn quit(&self);
     fn start_arguments(&self) -> &Vec<String>;
+    /// The keyboard layout, detected from the characters of the keys
+    /// that were pressed so far.
+    /// `None` if none of the pressed keys allowed to detect it yet.
+    fn keyboard_layout(&self) -> Option<KeyboardLayout>;
 }
 
 pub trait FromNativeImpl: InputEventHandler {
@@ -65,6 +69,14 @@ where
     fn new(loading: L, native: &mut dyn NativeImpl) -> anyhow::Result<Self>;
 }
 
+/// The keyboard layouts that can be told apart by the native layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyboardLayout {
+    Qwerty,
+    Azerty,
+    Qwertz,
+}
+
 #[derive(Debug)]
 pub struct NativeWindowMonitorDetails {
     pub name: String,
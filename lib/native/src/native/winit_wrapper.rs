@@ -7,7 +7,9 @@ use raw_window_handle::HasDisplayHandle;
 use winit::{
     application::ApplicationHandler,
     dpi::{PhysicalSize, Size},
+    event::KeyEvent,
     event_loop::EventLoop,
+    keyboard::{Key, KeyCode, PhysicalKey},
     monitor::{MonitorHandle, VideoModeHandle},
     window::{CursorGrabMode, Fullscreen, Window, WindowAttributes},
 };
@@ -15,8 +17,8 @@ use winit::{
 use crate::native::app::{MIN_WINDOW_HEIGHT, MIN_WINDOW_WIDTH};
 
 use super::{
-    app::NativeApp, FromNativeImpl, FromNativeLoadingImpl, NativeCreateOptions, NativeImpl,
-    NativeWindowMonitorDetails, NativeWindowOptions,
+    app::NativeApp, FromNativeImpl, FromNativeLoadingImpl, KeyboardLayout, NativeCreateOptions,
+    NativeImpl, NativeWindowMonitorDetails, NativeWindowOptions,
 };
 
 struct WindowMouse {
@@ -85,9 +87,35 @@ pub(crate) struct WinitWindowWrapper {
 
     destroy: Cell<bool>,
     start_arguments: Vec<String>,
+
+    keyboard_layout: Option<KeyboardLayout>,
 }
 
 impl WinitWindowWrapper {
+    /// Compares the character of a key with the character the key has
+    /// on a qwerty keyboard. Only keys that differ between the supported
+    /// layouts are considered.
+    fn detect_keyboard_layout(&mut self, event: &KeyEvent) {
+        let (PhysicalKey::Code(code), Key::Character(text)) =
+            (event.physical_key, &event.logical_key)
+        else {
+            return;
+        };
+        let layout = match (code, text.to_lowercase().as_str()) {
+            (KeyCode::KeyQ, "a") | (KeyCode::KeyA, "q") | (KeyCode::KeyW, "z") => {
+                KeyboardLayout::Azerty
+            }
+            (KeyCode::KeyZ, "w") => KeyboardLayout::Azerty,
+            (KeyCode::KeyY, "z") | (KeyCode::KeyZ, "y") => KeyboardLayout::Qwertz,
+            (KeyCode::KeyZ, "z") => KeyboardLayout::Qwerty,
+            _ => return,
+        };
+        if self.keyboard_layout != Some(layout) {
+            log::info!(target: "native", "detected {layout:?} keyboard layout");
+            self.keyboard_layout = Some(layout);
+        }
+    }
+
     fn find_monitor_and_video_mode(
         available_monitors: impl Fn() -> Box<dyn Iterator<Item = MonitorHandle>>,
         primary_monitor: Option<MonitorHandle>,
@@ -257,6 +285,9 @@ impl NativeImpl for WinitWindowWrapper {
     fn start_arguments(&self) -> &Vec<String> {
         &self.start_arguments
     }
+    fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        self.keyboard_layout
+    }
 }
 
 #[derive(Hash, PartialEq, Eq)]
@@ -407,6 +438,7 @@ impl WinitWrapper {
                             internal_events: Default::default(),
                             destroy: Default::default(),
                             start_arguments: native_options.start_arguments,
+                            keyboard_layout: None,
                         };
                         window.window.request_redraw();
                         let user = F::new(native_user_loading, &mut window).unwrap();
@@ -452,6 +484,10 @@ impl WinitWrapper {
                     window,
                 } = self
                 {
+                    // also detect the layout if the ui consumes the key
+                    if let winit::event::WindowEvent::KeyboardInput { event, .. } = &event {
+                        window.detect_keyboard_layout(event);
+                    }
                     if !native_user.raw_window_event(&window.window, &event) {
                         match event {
                             winit::event::WindowEvent::Resized(new_size) => {
@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     net::{IpAddr, SocketAddr},
+    time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
@@ -12,6 +13,8 @@ use super::{connection::NetworkConnectionId, plugins::NetworkPluginConnection};
 pub struct BanState {
     ipv4_bans: iprange::IpRange<ipnet::Ipv4Net>,
     ipv6_bans: iprange::IpRange<ipnet::Ipv6Net>,
+    /// bans that end at the given time
+    timed_bans: HashMap<IpAddr, Instant>,
     active_connections: HashMap<IpAddr, HashSet<NetworkConnectionId>>,
 }
 
@@ -24,20 +27,14 @@ pub struct ConnectionBans {
 #[async_trait]
 impl NetworkPluginConnection for ConnectionBans {
     async fn on_incoming(&self, remote_addr: &SocketAddr) -> anyhow::Result<bool> {
-        let should_drop_connection = match remote_addr.ip() {
-            IpAddr::V4(ip) => self
-                .state
-                .lock()
-                .await
-                .ipv4_bans
-                .contains(&ipnet::Ipv4Net::from(ip)),
-            IpAddr::V6(ip) => self
-                .state
-                .lock()
-                .await
-                .ipv6_bans
-                .contains(&ipnet::Ipv6Net::from(ip)),
-        };
+        let mut state = self.state.lock().await;
+        let now = Instant::now();
+        state.timed_bans.retain(|_, until| *until > now);
+        let should_drop_connection = state.timed_bans.contains_key(&remote_addr.ip())
+            || match remote_addr.ip() {
+                IpAddr::V4(ip) => state.ipv4_bans.contains(&ipnet::Ipv4Net::from(ip)),
+                IpAddr::V6(ip) => state.ipv6_bans.contains(&ipnet::Ipv6Net::from(ip)),
+            };
 
         Ok(!should_drop_connection)
     }
@@ -62,9 +59,11 @@ impl NetworkPluginConnection for ConnectionBans {
 }
 
 impl ConnectionBans {
+    /// Without a duration the ban is permanent.
+    ///
     /// Returns all network ids for that ip.
     #[must_use]
-    pub fn ban_ip(&self, ip: IpAddr) -> HashSet<NetworkConnectionId> {
+    pub fn ban_ip(&self, ip: IpAddr, duration: Option<Duration>) -> HashSet<NetworkConnectionId> {
         let mut state = self.state.blocking_lock();
         let ids = state
             .active_connections
@@ -72,11 +71,14 @@ impl ConnectionBans {
             .cloned()
             .unwrap_or_default();
 
-        match ip {
-            IpAddr::V4(ip) => {
+        match (ip, duration) {
+            (_, Some(duration)) => {
+                state.timed_bans.insert(ip, Instant::now() + duration);
+            }
+            (IpAddr::V4(ip), None) => {
                 state.ipv4_bans.add(ip.into());
             }
-            IpAddr::V6(ip) => {
+            (IpAddr::V6(ip), None) => {
                 state.ipv6_bans.add(ip.into());
             }
         }
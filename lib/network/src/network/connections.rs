@@ -13,7 +13,7 @@ use tokio::sync::Mutex as TokioMutex;
 
 use super::{
     connection::{NetworkConnection, NetworkConnectionId},
-    event::NetworkEvent,
+    event::{NetworkDisconnectReason, NetworkEvent},
     event_generator::InternalGameEventGenerator,
     network::{NetworkConnectingInterface, NetworkConnectionInterface},
     plugins::{NetworkPluginConnection, NetworkPluginPacket},
@@ -92,7 +92,7 @@ impl<C: NetworkConnectionInterface + Send + Sync + Clone + 'static> NetworkConne
         connection: &C,
         sys: &Arc<SystemTime>,
         game_event_generator: &mut InternalGameEventGenerator,
-        reason: String,
+        reason: NetworkDisconnectReason,
         graceful: bool,
         all_packets_in_order: &Arc<TokioMutex<NetworkInOrderPackets>>,
         connection_plugins: &Arc<Vec<Arc<dyn NetworkPluginConnection>>>,
@@ -420,8 +420,14 @@ impl<C: NetworkConnectionInterface + Send + Sync + Clone + 'static> NetworkConne
                 };
 
                 let (reason, graceful) = match res {
-                    Ok(_) => ("".to_string(), true),
-                    Err(err) => (err.to_string(), false),
+                    Ok(_) => (NetworkDisconnectReason::Left, true),
+                    Err(err) => (
+                        connection
+                            .conn
+                            .close_reason()
+                            .unwrap_or_else(|| NetworkDisconnectReason::Other(err.to_string())),
+                        false,
+                    ),
                 };
                 Self::disconnect_connection(
                     &connection_identifier,
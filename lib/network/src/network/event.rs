@@ -1,4 +1,6 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{fmt::Display, net::SocketAddr, sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
 
 use super::connection::ConnectionStats;
 
@@ -15,10 +17,95 @@ pub enum NetworkEvent {
         initial_network_stats: NetworkStats,
     },
     Disconnected {
-        reason: String,
+        reason: NetworkDisconnectReason,
         /// if false, then the disconnect happened because of an error
         graceful: bool,
     },
     ConnectingFailed(String),
     NetworkStats(NetworkStats),
 }
+
+/// Why a connection was closed.
+///
+/// The side that closes the connection sends the reason
+/// to the other side as part of the close message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetworkDisconnectReason {
+    /// The other side closed the connection without a specific reason,
+    /// e.g. the client left the server.
+    Left,
+    /// Kicked with an optional message.
+    Kicked(String),
+    /// Banned with an optional message,
+    /// without a duration the ban is permanent.
+    Banned {
+        reason: String,
+        duration: Option<Duration>,
+    },
+    ServerShutdown,
+    /// The server changed the map while the client was still loading,
+    /// the client has to connect again to load the new map.
+    MapChange,
+    /// The other side did not respond in time.
+    Timeout,
+    /// The client's network version is not supported by the server.
+    VersionMismatch {
+        server_version: u64,
+    },
+    /// Any other error, e.g. by the network implementation.
+    Other(String),
+}
+
+impl NetworkDisconnectReason {
+    /// Whether the client should try to connect again automatically,
+    /// because the reason is likely temporary.
+    pub fn should_reconnect(&self) -> bool {
+        matches!(self, Self::ServerShutdown | Self::MapChange | Self::Timeout)
+    }
+
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).unwrap_or_default()
+    }
+
+    /// An empty reason is a close without a specific reason.
+    pub(crate) fn decode(reason: &[u8]) -> Option<Self> {
+        if reason.is_empty() {
+            return Some(Self::Left);
+        }
+        bincode::serde::decode_from_slice(reason, bincode::config::standard())
+            .ok()
+            .map(|(reason, _)| reason)
+    }
+}
+
+impl Display for NetworkDisconnectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left => write!(f, "the connection was closed"),
+            Self::Kicked(reason) if reason.is_empty() => write!(f, "kicked from the server"),
+            Self::Kicked(reason) => write!(f, "kicked from the server: {reason}"),
+            Self::Banned { reason, duration } => {
+                match duration {
+                    Some(duration) => write!(
+                        f,
+                        "banned from the server for {} minutes",
+                        duration.as_secs().div_ceil(60)
+                    )?,
+                    None => write!(f, "banned from the server")?,
+                }
+                if !reason.is_empty() {
+                    write!(f, ": {reason}")?;
+                }
+                Ok(())
+            }
+            Self::ServerShutdown => write!(f, "the server shut down"),
+            Self::MapChange => write!(f, "the server changed the map"),
+            Self::Timeout => write!(f, "timed out"),
+            Self::VersionMismatch { server_version } => write!(
+                f,
+                "the server runs an incompatible version ({server_version})"
+            ),
+            Self::Other(err) => write!(f, "{err}"),
+        }
+    }
+}
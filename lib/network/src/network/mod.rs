@@ -38,17 +38,18 @@ pub mod tests {
             NetworkServerCertMode,
         },
         packet_compressor::DefaultNetworkPacketCompressor,
-        plugins::{NetworkPluginPacket, NetworkPlugins},
+        plugins::{NetworkPluginConnection, NetworkPluginPacket, NetworkPlugins},
         quinn_network::{
             QuinnEndpointWrapper, QuinnNetworkConnectingWrapper, QuinnNetworkConnectionWrapper,
         },
         types::NetworkInOrderChannel,
-        utils::create_certifified_keys,
+        utils::{create_certifified_keys, local_route_ip},
     };
 
     use super::{
         connection::NetworkConnectionId,
-        event::NetworkEvent,
+        connection_ban::ConnectionBans,
+        event::{NetworkDisconnectReason, NetworkEvent},
         event_generator::NetworkEventToGameEventGenerator,
         network::{
             NetworkClientInitOptions, NetworkConnectingInterface, NetworkConnectionInterface,
@@ -691,6 +692,32 @@ pub mod tests {
         >();
     }
 
+    #[test]
+    fn disconnect_reason_round_trip() {
+        let reasons = [
+            NetworkDisconnectReason::Kicked("spamming".to_string()),
+            NetworkDisconnectReason::Banned {
+                reason: "cheating".to_string(),
+                duration: Some(Duration::from_secs(60 * 30)),
+            },
+            NetworkDisconnectReason::ServerShutdown,
+            NetworkDisconnectReason::MapChange,
+            NetworkDisconnectReason::Timeout,
+            NetworkDisconnectReason::VersionMismatch { server_version: 1 },
+        ];
+        for reason in reasons {
+            assert_eq!(
+                NetworkDisconnectReason::decode(&reason.encode()),
+                Some(reason)
+            );
+        }
+        // closing without a reason
+        assert_eq!(
+            NetworkDisconnectReason::decode(&[]),
+            Some(NetworkDisconnectReason::Left)
+        );
+    }
+
     #[test]
     fn max_datagram_size_tests() {
         let (client_cert, client_private_key) = create_certifified_keys();
@@ -1111,6 +1138,31 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn timed_connection_bans() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let bans = ConnectionBans::default();
+        let allowed = |addr: &str| {
+            runtime
+                .block_on(bans.on_incoming(&addr.parse().unwrap()))
+                .unwrap()
+        };
+
+        let _ = bans.ban_ip("10.0.0.1".parse().unwrap(), None);
+        let _ = bans.ban_ip(
+            "10.0.0.2".parse().unwrap(),
+            Some(Duration::from_secs(60 * 60)),
+        );
+        let _ = bans.ban_ip("10.0.0.3".parse().unwrap(), Some(Duration::ZERO));
+        assert!(!allowed("10.0.0.1:8303"));
+        assert!(!allowed("10.0.0.2:8303"));
+        // the ban is over already
+        assert!(allowed("10.0.0.3:8303"));
+        assert!(allowed("10.0.0.4:8303"));
+    }
+
     #[test]
     fn ip_tests_works() {
         // ipv4
@@ -1170,4 +1222,52 @@ pub mod tests {
                 .unwrap()
         ));
     }
+
+    #[test]
+    fn local_addr_migration() {
+        let server_addr: std::net::SocketAddr = "127.0.0.1:8303".parse().unwrap();
+        assert!(local_route_ip(server_addr).unwrap().is_loopback());
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let (server_cert, _) = create_certifified_keys();
+            let (client_cert, client_private_key) = create_certifified_keys();
+            let endpoint = QuinnEndpointWrapper::make_client_endpoint(
+                "127.0.0.1:0".parse().unwrap(),
+                &NetworkClientInitOptions::new(
+                    NetworkClientCertCheckMode::CheckByCert {
+                        cert: server_cert.to_der().unwrap().into(),
+                    },
+                    NetworkClientCertMode::FromCertAndPrivateKey {
+                        cert: client_cert,
+                        private_key: client_private_key,
+                    },
+                ),
+            )
+            .unwrap();
+
+            // the endpoint moves to a new socket
+            let old_addr = endpoint.sock_addr().unwrap();
+            endpoint.rebind("127.0.0.1:0".parse().unwrap()).unwrap();
+            assert_ne!(endpoint.sock_addr().unwrap(), old_addr);
+
+            // the watcher stops as soon as the client drops the sender
+            let (shutdown, shutdown_recv) = tokio::sync::oneshot::channel();
+            let watcher = tokio::spawn(QuinnNetwork::migrate_on_local_addr_change(
+                endpoint.clone(),
+                server_addr,
+                shutdown_recv,
+            ));
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert!(!watcher.is_finished());
+            drop(shutdown);
+            tokio::time::timeout(Duration::from_secs(5), watcher)
+                .await
+                .expect("the watcher did not stop")
+                .unwrap();
+        });
+    }
 }
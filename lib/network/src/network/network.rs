@@ -2,7 +2,7 @@ use std::{
     borrow::Cow,
     future::Future,
     marker::PhantomData,
-    net::SocketAddr,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
     num::NonZeroUsize,
     ops::DerefMut,
     sync::{Arc, Mutex as StdMutex},
@@ -19,22 +19,29 @@ use pool::{mt_datatypes::PoolVec, mt_pool::Pool};
 use quinn::VarInt;
 use serde::Serialize;
 use thiserror::Error;
-use tokio::{sync::Mutex as TokioMutex, task::JoinHandle};
+use tokio::{
+    sync::{oneshot, Mutex as TokioMutex},
+    task::JoinHandle,
+};
 
 use std::sync::mpsc::{Receiver, SyncSender as Sender};
 
 use super::{
     connection::{ConnectionStats, NetworkConnectionId},
     connections::{NetworkConnectionIdCounter, NetworkConnections},
-    event::NetworkEvent,
+    event::{NetworkDisconnectReason, NetworkEvent},
     event_generator::{InternalGameEventGenerator, NetworkEventToGameEventGenerator},
     notifier::NetworkEventNotifier,
     plugins::NetworkPlugins,
     types::{
         NetworkEventSendType, NetworkInOrderChannel, NetworkInOrderPackets, NetworkLogicEvent,
     },
+    utils::local_route_ip,
 };
 
+/// How often the client checks if the local address changed.
+const LOCAL_ADDR_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Default)]
 pub struct NetworkSharedInitOptions {
     pub debug_printing: Option<bool>,
@@ -256,6 +263,11 @@ where
     fn connect(&self, addr: SocketAddr, server_name: &str) -> anyhow::Result<Z>;
     async fn accept(&self) -> Option<I>;
     fn sock_addr(&self) -> anyhow::Result<SocketAddr>;
+    /// Moves the endpoint to a new socket bound to `bind_addr`,
+    /// existing connections migrate to the new socket.
+    ///
+    /// Must be called inside of the network runtime.
+    fn rebind(&self, bind_addr: SocketAddr) -> anyhow::Result<()>;
 
     fn make_server_endpoint(
         bind_addr: SocketAddr,
@@ -300,6 +312,9 @@ pub enum UnreliableUnorderedError {
 #[async_trait::async_trait]
 pub trait NetworkConnectionInterface: Clone + Send + Sync + 'static {
     async fn close(&self, error_code: VarInt, reason: &[u8]);
+    /// The reason the other side gave when it closed the connection,
+    /// if the connection was closed and the implementation supports it.
+    fn close_reason(&self) -> Option<NetworkDisconnectReason>;
 
     async fn send_unreliable_unordered(
         &self,
@@ -385,19 +400,65 @@ where
     I: NetworkIncomingInterface<Z>,
     E: NetworkEndpointInterface<Z, I>,
 {
+    /// Watches the local address used to reach the server
+    /// and moves the endpoint to a new socket if it changes,
+    /// e.g. when switching from Wi-Fi to ethernet.
+    ///
+    /// If the migration fails, the connection times out and the client
+    /// connects again, which the server handles like any other timeout.
+    ///
+    /// Runs until `shutdown` resolves, which happens as soon as its sender
+    /// is dropped, i.e. when the client disconnects or connects again.
+    pub(crate) async fn migrate_on_local_addr_change(
+        endpoint: E,
+        server_addr: SocketAddr,
+        mut shutdown: oneshot::Receiver<()>,
+    ) {
+        let mut local_ip = local_route_ip(server_addr).ok();
+        let mut interval = tokio::time::interval(LOCAL_ADDR_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = &mut shutdown => break,
+            }
+            // without any route there is nothing to migrate to (yet)
+            let Ok(new_local_ip) = local_route_ip(server_addr) else {
+                continue;
+            };
+            if local_ip == Some(new_local_ip) {
+                continue;
+            }
+            log::info!(
+                target: "network",
+                "local address changed from {local_ip:?} to {new_local_ip}, migrating connection"
+            );
+            local_ip = Some(new_local_ip);
+
+            let bind_addr = match endpoint.sock_addr() {
+                Ok(SocketAddr::V6(_)) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+                _ => SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+            };
+            if let Err(err) = endpoint.rebind(bind_addr) {
+                log::info!(target: "network", "failed to migrate connection: {err}");
+            }
+        }
+    }
+
     fn run(
         thread: &mut NetworkThread<E, C>,
         events: Receiver<NetworkLogicEvent>,
         runtime: &Arc<tokio::runtime::Runtime>,
     ) {
         let pool = thread.packet_pool.clone();
+        // stops the local address watcher of the client, once dropped
+        let mut migrate_shutdown: Option<oneshot::Sender<()>> = None;
         while let Ok(event) = events.recv() {
             match event {
                 NetworkLogicEvent::Connect(con_id, addr) => {
                     log::debug!(target: "network", "connecting to {addr}");
-                    let conn_res = thread
-                        .endpoint
-                        .connect(addr.as_str().parse().unwrap(), "localhost");
+                    let addr: SocketAddr = addr.as_str().parse().unwrap();
+                    let conn_res = thread.endpoint.connect(addr, "localhost");
                     match conn_res {
                         Ok(conn) => {
                             let connections = thread.connections.clone();
@@ -435,6 +496,14 @@ where
                                         )
                                         .await;
                                 });
+                            } else {
+                                let (shutdown, shutdown_recv) = oneshot::channel();
+                                migrate_shutdown = Some(shutdown);
+                                tokio::spawn(Self::migrate_on_local_addr_change(
+                                    thread.endpoint.clone(),
+                                    addr,
+                                    shutdown_recv,
+                                ));
                             }
                         }
                         Err(conn) => {
@@ -454,6 +523,7 @@ where
                 }
                 NetworkLogicEvent::Disconnect(connection_id) => {
                     log::debug!("disconnecting");
+                    drop(migrate_shutdown.take());
                     let connections_ = thread.connections.clone();
                     let con_id = connection_id;
                     // handle the disconnect sync (since it's client side only)
@@ -471,7 +541,7 @@ where
                     // the end of this connection
                     return;
                 }
-                NetworkLogicEvent::Kick(connection_id) => {
+                NetworkLogicEvent::Kick(connection_id, reason) => {
                     log::debug!("kick {connection_id:?}");
                     let connections_ = thread.connections.clone();
                     let con_id = connection_id;
@@ -483,7 +553,7 @@ where
                         let con = connections.remove(&con_id);
                         drop(connections_guard);
                         if let Some(conn) = con {
-                            conn.conn.close(VarInt::default(), &[]).await;
+                            conn.conn.close(VarInt::default(), &reason.encode()).await;
                         }
                     });
                 }
@@ -900,7 +970,14 @@ where
         if run_thread.unwrap().join().is_err() {
             // TODO logging
         }
-        self.endpoint.close(VarInt::default(), &[]);
+        if self.is_server {
+            self.endpoint.close(
+                VarInt::default(),
+                &NetworkDisconnectReason::ServerShutdown.encode(),
+            );
+        } else {
+            self.endpoint.close(VarInt::default(), &[]);
+        }
     }
 
     fn disconnect(&mut self, connection_id: &NetworkConnectionId) {
@@ -918,9 +995,11 @@ where
             .unwrap();
     }
 
-    pub fn kick(&self, connection_id: &NetworkConnectionId) {
+    /// Kicks the connection, the reason is part of
+    /// the disconnect message the other side receives.
+    pub fn kick(&self, connection_id: &NetworkConnectionId, reason: NetworkDisconnectReason) {
         self.events_send
-            .send(NetworkLogicEvent::Kick(*connection_id))
+            .send(NetworkLogicEvent::Kick(*connection_id, reason))
             .unwrap();
     }
 
@@ -1,4 +1,8 @@
-use std::io::{Read, Write};
+use std::{
+    collections::HashSet,
+    io::{Read, Write},
+    sync::RwLock,
+};
 
 use anyhow::anyhow;
 use async_trait::async_trait;
@@ -20,6 +24,9 @@ pub struct ZstdNetworkPacketCompressor {
 
     send_dict: Option<Vec<u8>>,
     recv_dict: Option<Vec<u8>>,
+
+    /// connections that prefer a smaller packet size over speed
+    high_compression: RwLock<HashSet<NetworkConnectionId>>,
 }
 
 impl Default for ZstdNetworkPacketCompressor {
@@ -34,6 +41,7 @@ impl ZstdNetworkPacketCompressor {
             helper_pool: Pool::with_capacity(64),
             send_dict: None,
             recv_dict: None,
+            high_compression: Default::default(),
         }
     }
 
@@ -42,6 +50,18 @@ impl ZstdNetworkPacketCompressor {
             helper_pool: Pool::with_capacity(64),
             send_dict: Some(send_dict),
             recv_dict: Some(recv_dict),
+            high_compression: Default::default(),
+        }
+    }
+
+    /// Use a higher compression level for packets sent to the given connection.
+    /// Costs more cpu time, but saves bandwidth.
+    pub fn set_high_compression(&self, id: &NetworkConnectionId, active: bool) {
+        let mut high_compression = self.high_compression.write().unwrap();
+        if active {
+            high_compression.insert(*id);
+        } else {
+            high_compression.remove(id);
         }
     }
 }
@@ -50,17 +70,23 @@ impl ZstdNetworkPacketCompressor {
 impl NetworkPluginPacket for ZstdNetworkPacketCompressor {
     async fn prepare_write(
         &self,
-        _id: &NetworkConnectionId,
+        id: &NetworkConnectionId,
         buffer: &mut Vec<u8>,
     ) -> anyhow::Result<()> {
         let mut helper = self.helper_pool.new();
         let helper: &mut Vec<_> = helper.as_mut();
 
         const COMPRESSION_LEVEL: i32 = 0;
+        const HIGH_COMPRESSION_LEVEL: i32 = 12;
+        let level = if self.high_compression.read().unwrap().contains(id) {
+            HIGH_COMPRESSION_LEVEL
+        } else {
+            COMPRESSION_LEVEL
+        };
         let mut encoder = if let Some(dict) = &self.send_dict {
-            zstd::Encoder::with_dictionary(&mut *helper, COMPRESSION_LEVEL, dict)?
+            zstd::Encoder::with_dictionary(&mut *helper, level, dict)?
         } else {
-            zstd::Encoder::new(&mut *helper, COMPRESSION_LEVEL)?
+            zstd::Encoder::new(&mut *helper, level)?
         };
         encoder.write_all(buffer)?;
         encoder.finish()?;
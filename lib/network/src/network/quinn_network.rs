@@ -14,6 +14,7 @@ use tokio::io::AsyncWriteExt;
 
 use super::{
     connection::ConnectionStats,
+    event::NetworkDisconnectReason,
     network::{
         Network, NetworkClientInitOptions, NetworkConnectingInterface, NetworkConnectionInterface,
         NetworkEndpointInterface, NetworkIncomingInterface, NetworkServerCertMode,
@@ -251,6 +252,16 @@ impl NetworkConnectionInterface for QuinnNetworkConnectionWrapper {
         self.con.closed().await;
     }
 
+    fn close_reason(&self) -> Option<NetworkDisconnectReason> {
+        match self.con.close_reason()? {
+            quinn::ConnectionError::ApplicationClosed(close) => {
+                NetworkDisconnectReason::decode(&close.reason)
+            }
+            quinn::ConnectionError::TimedOut => Some(NetworkDisconnectReason::Timeout),
+            _ => None,
+        }
+    }
+
     fn remote_addr(&self) -> SocketAddr {
         self.con.remote_address()
     }
@@ -383,6 +394,14 @@ impl NetworkEndpointInterface<QuinnNetworkConnectingWrapper, QuinnNetworkIncomin
     fn sock_addr(&self) -> anyhow::Result<SocketAddr> {
         Ok(self.endpoint.local_addr()?)
     }
+
+    fn rebind(&self, bind_addr: SocketAddr) -> anyhow::Result<()> {
+        // QUIC connections are not bound to an address,
+        // the server validates the new path and continues the connection.
+        Ok(self
+            .endpoint
+            .rebind(std::net::UdpSocket::bind(bind_addr)?)?)
+    }
 }
 
 pub type QuinnNetwork = Network<
@@ -19,6 +19,7 @@ use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
 
 use super::{
     connection::ConnectionStats,
+    event::NetworkDisconnectReason,
     network::{
         Network, NetworkClientInitOptions, NetworkConnectingInterface, NetworkConnectionInterface,
         NetworkEndpointInterface, NetworkIncomingInterface, NetworkServerCertMode,
@@ -180,6 +181,11 @@ impl NetworkConnectionInterface for TungsteniteNetworkConnectionWrapper {
         }
     }
 
+    fn close_reason(&self) -> Option<NetworkDisconnectReason> {
+        // websockets don't send a reason
+        None
+    }
+
     fn remote_addr(&self) -> SocketAddr {
         self.addr
     }
@@ -392,6 +398,12 @@ impl
     fn sock_addr(&self) -> anyhow::Result<SocketAddr> {
         Ok(self.endpoint.local_addr()?)
     }
+
+    fn rebind(&self, _bind_addr: SocketAddr) -> anyhow::Result<()> {
+        Err(anyhow!(
+            "websocket connections can not be migrated to a new address"
+        ))
+    }
 }
 
 pub type TungsteniteNetwork = Network<
@@ -7,7 +7,7 @@ use pool::mt_datatypes::PoolVec;
 
 use tokio::sync::Mutex as TokioMutex;
 
-use super::connection::NetworkConnectionId;
+use super::{connection::NetworkConnectionId, event::NetworkDisconnectReason};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum NetworkInOrderChannel {
@@ -32,7 +32,8 @@ pub(crate) enum NetworkLogicEvent {
     Connect(NetworkConnectionId, String),
     Disconnect(NetworkConnectionId),
     Send((NetworkConnectionId, PoolVec<u8>, NetworkEventSendType)),
-    Kick(NetworkConnectionId),
+    /// Kicks the connection, the reason is sent to the other side.
+    Kick(NetworkConnectionId, NetworkDisconnectReason),
 }
 
 pub(crate) type NetworkPacket = PoolVec<u8>;
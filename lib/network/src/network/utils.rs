@@ -1,3 +1,5 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+
 use ed25519_dalek::{pkcs8::EncodePrivateKey, SigningKey};
 use rcgen::{CertificateParams, KeyPair, PKCS_ED25519};
 use spki::der::{pem::LineEnding, Decode};
@@ -19,3 +21,17 @@ pub fn create_certifified_keys() -> (x509_cert::Certificate, SigningKey) {
         private_key,
     )
 }
+
+/// The local ip the OS currently uses to reach `addr`.
+///
+/// Connecting an UDP socket does not send any packets,
+/// it only resolves the route, so this is cheap to call periodically.
+pub fn local_route_ip(addr: SocketAddr) -> std::io::Result<IpAddr> {
+    let bind_addr = match addr {
+        SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+        SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+    };
+    let sock = UdpSocket::bind(bind_addr)?;
+    sock.connect(addr)?;
+    Ok(sock.local_addr()?.ip())
+}
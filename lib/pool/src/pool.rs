@@ -49,6 +49,12 @@ impl<T: Recyclable> Pool<T> {
         }
     }
 
+    /// Reserves space for at least `additional` more recycled items,
+    /// e.g. if more items are expected to be in use at once.
+    pub fn reserve(&self, additional: usize) {
+        self.pool.borrow_mut().reserve(additional);
+    }
+
     pub fn items_in_pool(&self) -> usize {
         self.pool.borrow().len()
     }
@@ -23,7 +23,7 @@ use kira::{
         SoundData,
     },
     spatial::scene::{SpatialSceneHandle, SpatialSceneSettings},
-    track::{TrackBuilder, TrackHandle, TrackId},
+    track::{TrackBuilder, TrackHandle, TrackId, TrackRoutes},
     tween::Value,
     ResourceLimitReached,
 };
@@ -98,6 +98,7 @@ pub struct Instance {
     clock_handle: ClockHandle,
 
     main_track: TrackHandle,
+    off_air_id: Option<u64>,
 }
 
 struct OnAirTrack {
@@ -209,9 +210,17 @@ impl Instance {
             manager,
 
             main_track: fetcher_track,
+            off_air_id: off_air_props.map(|props| props.id),
         })
     }
 
+    /// Adds a track that is routed into the main track.
+    pub fn add_sub_track(&mut self) -> anyhow::Result<TrackHandle> {
+        Ok(self
+            .manager
+            .add_sub_track(TrackBuilder::new().routes(TrackRoutes::parent(self.track())))?)
+    }
+
     pub fn clock_time(&self) -> ClockTime {
         self.clock_handle.time()
     }
@@ -241,6 +250,10 @@ impl Instance {
         matches!(self.manager, AudioManagerTy::OnAir(_))
     }
 
+    pub fn off_air_id(&self) -> Option<u64> {
+        self.off_air_id
+    }
+
     pub fn process_samples(&mut self, samples: u32) -> anyhow::Result<Vec<kira::Frame>> {
         let AudioManagerTy::OffAir(manager) = &mut self.manager else {
             return Err(anyhow!("Not a off-air scene."));
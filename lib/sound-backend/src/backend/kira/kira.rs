@@ -146,10 +146,31 @@ impl SoundBackendDriverInterface for SoundBackendKira {
                                         match props.air_mode {
                                             SceneAirMode::OnAir => self.main_instance.clone(),
                                             SceneAirMode::OffAir { id, sample_rate } => {
-                                                Instance::new(
-                                                    self.frame_fetchers.clone(),
-                                                    Some(InstanceOffAirProps { id, sample_rate }),
-                                                )?
+                                                // like on-air scenes, all off-air scenes with
+                                                // the same id are mixed into one output
+                                                let shared_instance = self
+                                                    .scenes
+                                                    .values()
+                                                    .chain(
+                                                        self.inactive_scenes
+                                                            .values()
+                                                            .map(|(scene, _)| scene),
+                                                    )
+                                                    .map(|scene| &scene.instance)
+                                                    .find(|instance| {
+                                                        instance.off_air_id() == Some(id)
+                                                    })
+                                                    .cloned();
+                                                match shared_instance {
+                                                    Some(instance) => instance,
+                                                    None => Instance::new(
+                                                        self.frame_fetchers.clone(),
+                                                        Some(InstanceOffAirProps {
+                                                            id,
+                                                            sample_rate,
+                                                        }),
+                                                    )?,
+                                                }
                                             }
                                         },
                                         self.cur_sound_frame,
@@ -213,6 +234,15 @@ impl SoundBackendDriverInterface for SoundBackendKira {
 
                             scene.instance.process_samples(samples)?;
                         }
+                        SoundCommandSoundScene::SetVolume { id, volume } => {
+                            let scene = self
+                                .scenes
+                                .get_mut(&id)
+                                .or(self.inactive_scenes.get_mut(&id).map(|(scene, _)| scene))
+                                .ok_or_else(|| anyhow!("scene did not exist"))?;
+
+                            scene.set_volume(volume);
+                        }
                     },
                     SoundCommandState::SoundObject(cmd) => match cmd {
                         SoundCommandSoundObject::Create { id, scene_id, mem } => {
@@ -263,10 +293,7 @@ impl SoundBackendDriverInterface for SoundBackendKira {
 
                             let res = scene
                                 .listeners
-                                .insert(
-                                    id,
-                                    Listener::new(&mut scene.instance, &mut scene.handle, pos)?,
-                                )
+                                .insert(id, Listener::new(scene.track(), &mut scene.handle, pos)?)
                                 .is_none();
                             anyhow::ensure!(res, "listener with id {id} already existed.");
                         }
@@ -1,14 +1,15 @@
 use std::fmt::Debug;
 
 use hiarc::Hiarc;
-use kira::spatial::{
-    listener::{ListenerHandle, ListenerSettings},
-    scene::SpatialSceneHandle,
+use kira::{
+    spatial::{
+        listener::{ListenerHandle, ListenerSettings},
+        scene::SpatialSceneHandle,
+    },
+    track::TrackId,
 };
 use math::math::vector::vec2;
 
-use super::instance::Instance;
-
 #[derive(Hiarc)]
 pub(super) struct Listener {
     // keep for RAII
@@ -22,11 +23,7 @@ impl Debug for Listener {
 }
 
 impl Listener {
-    pub fn new(
-        instance: &mut Instance,
-        scene: &mut SpatialSceneHandle,
-        pos: vec2,
-    ) -> anyhow::Result<Self> {
+    pub fn new(track: TrackId, scene: &mut SpatialSceneHandle, pos: vec2) -> anyhow::Result<Self> {
         let handle = scene.add_listener(
             mint::Vector3 {
                 x: pos.x,
@@ -41,7 +38,7 @@ impl Listener {
                     z: 0.0,
                 },
             },
-            ListenerSettings::new().track(instance.track()),
+            ListenerSettings::new().track(track),
         )?;
 
         Ok(Self { handle })
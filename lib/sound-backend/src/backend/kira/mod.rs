@@ -5,3 +5,4 @@ pub mod mem_allocator;
 pub mod scene;
 pub mod sound;
 pub mod stream;
+mod time_stretch;
@@ -1,8 +1,14 @@
-use std::fmt::Debug;
-
 use hashlink::{LinkedHashMap, LinkedHashSet};
 use hiarc::Hiarc;
-use kira::{sound::PlaybackState, spatial::scene::SpatialSceneHandle, tween::Tween};
+use std::fmt::Debug;
+use std::time::Duration;
+
+use kira::{
+    sound::PlaybackState,
+    spatial::scene::SpatialSceneHandle,
+    track::{TrackHandle, TrackId},
+    tween::Tween,
+};
 use sound::types::{SoundPlayProps, StreamPlayProps};
 
 use super::{instance::Instance, listener::Listener, sound::Sound, stream::Stream};
@@ -36,6 +42,8 @@ pub(super) struct Scene {
     pub(super) streams: LinkedHashMap<u128, Stream>,
     pub(super) listeners: LinkedHashMap<u128, Listener>,
     pub(super) handle: SpatialSceneHandle,
+    /// The bus of this scene, all listeners output into this track.
+    track: TrackHandle,
 
     pub(super) last_active_sound_frame: u64,
 
@@ -45,11 +53,13 @@ pub(super) struct Scene {
 impl Scene {
     pub fn new(instance: Instance, cur_sound_frame: u64) -> anyhow::Result<Self> {
         let scene = instance.add_spatial_scene()?;
+        let track = instance.add_sub_track()?;
         Ok(Self {
             sounds: Default::default(),
             streams: Default::default(),
             listeners: Default::default(),
             handle: scene,
+            track,
             last_active_sound_frame: cur_sound_frame,
 
             instance,
@@ -104,6 +114,20 @@ impl Scene {
         Ok(())
     }
 
+    pub fn track(&self) -> TrackId {
+        self.track.id()
+    }
+
+    pub fn set_volume(&mut self, volume: f64) {
+        self.track.set_volume(
+            volume,
+            Tween {
+                duration: Duration::from_millis(150),
+                ..Default::default()
+            },
+        );
+    }
+
     pub fn is_onair(&self) -> bool {
         self.instance.is_onair()
     }
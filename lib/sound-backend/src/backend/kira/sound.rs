@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use anyhow::anyhow;
 use hashlink::LinkedHashMap;
@@ -19,13 +19,18 @@ use sound::{
     types::{SoundPlayBaseProps, SoundPlayProps},
 };
 
-use super::{instance::Instance, mem_allocator::MemoryAllocator};
+use super::{instance::Instance, mem_allocator::MemoryAllocator, time_stretch::time_stretch};
+
+/// How many time-stretched versions of a sound are kept.
+const MAX_STRETCHED_SOUNDS: usize = 4;
 
 #[derive(Hiarc)]
 /// actually playing sound in the scene
 pub(super) struct SoundPlay {
     pub(super) handle: StaticSoundHandle,
     pub(super) emitter: EmitterHandle,
+    /// The sound was time-stretched, so it plays at the normal rate.
+    pub(super) stretched: bool,
     //_start_time: Duration,
 }
 
@@ -38,6 +43,8 @@ impl Debug for SoundPlay {
 #[derive(Hiarc)]
 pub(super) struct Sound {
     pub(super) sound_data: StaticSoundData,
+    /// Time-stretched versions of the sound by their playback speed.
+    stretched_sound_data: LinkedHashMap<u64, StaticSoundData>,
 
     pub(super) plays: LinkedHashMap<u128, SoundPlay>,
 }
@@ -48,10 +55,30 @@ impl Sound {
 
         Ok(Self {
             sound_data,
+            stretched_sound_data: Default::default(),
             plays: Default::default(),
         })
     }
 
+    fn stretched_sound_data(&mut self, playback_speed: f64) -> StaticSoundData {
+        let key = playback_speed.to_bits();
+        if let Some(sound_data) = self.stretched_sound_data.get(&key) {
+            return sound_data.clone();
+        }
+
+        let mut sound_data = self.sound_data.clone();
+        sound_data.frames = Arc::from(time_stretch(
+            &self.sound_data.frames,
+            self.sound_data.sample_rate,
+            playback_speed,
+        ));
+        if self.stretched_sound_data.len() >= MAX_STRETCHED_SOUNDS {
+            self.stretched_sound_data.pop_front();
+        }
+        self.stretched_sound_data.insert(key, sound_data.clone());
+        sound_data
+    }
+
     pub fn play(
         &mut self,
         play_id: u128,
@@ -76,18 +103,25 @@ impl Sound {
                 .persist_until_sounds_finish(false),
         )?;
 
+        let stretched = props.base.preserve_pitch && props.base.playback_speed != 1.0;
+        let (sound_data, playback_rate) = if stretched {
+            (self.stretched_sound_data(props.base.playback_speed), 1.0)
+        } else {
+            (self.sound_data.clone(), props.base.playback_speed)
+        };
+
         let mut sound_settings = StaticSoundSettings::new()
             .start_time(instance.clock_time() + props.start_time_delay.as_millis() as u64)
             .volume(props.base.volume)
             .panning(props.base.panning)
-            .playback_rate(props.base.playback_speed)
+            .playback_rate(playback_rate)
             .output_destination(OutputDestination::Emitter(emitter.id()));
 
         if props.base.looped {
             sound_settings = sound_settings.loop_region(..);
         }
 
-        let sound_data = self.sound_data.with_settings(sound_settings);
+        let sound_data = sound_data.with_settings(sound_settings);
         let mut play = instance.play(sound_data)?;
         if paused {
             play.pause(Default::default());
@@ -99,6 +133,7 @@ impl Sound {
                 SoundPlay {
                     handle: play,
                     emitter,
+                    stretched,
                 },
             )
             .is_none();
@@ -145,8 +180,10 @@ impl Sound {
 
         play.handle.set_volume(props.volume, Default::default());
         play.handle.set_panning(props.panning, Default::default());
-        play.handle
-            .set_playback_rate(props.playback_speed, Default::default());
+        if !play.stretched {
+            play.handle
+                .set_playback_rate(props.playback_speed, Default::default());
+        }
         if props.looped {
             play.handle.set_loop_region(..);
         } else {
@@ -0,0 +1,133 @@
+use kira::Frame;
+
+/// Length of the overlapping windows in seconds.
+const WINDOW_SECS: f64 = 0.02;
+
+/// Changes the duration of the frames by `1 / speed` without changing their pitch.
+///
+/// Implements WSOLA (waveform similarity overlap-add): windows of the input
+/// are cross-faded into the output, each window is picked close to its
+/// nominal position so that it continues the waveform of the previous one.
+pub fn time_stretch(frames: &[Frame], sample_rate: u32, speed: f64) -> Vec<Frame> {
+    let window_len = ((sample_rate as f64 * WINDOW_SECS) as usize).max(4);
+    if speed <= 0.0 || frames.len() < window_len * 2 {
+        return frames.to_vec();
+    }
+
+    let synthesis_hop = window_len / 2;
+    let analysis_hop = synthesis_hop as f64 * speed;
+    let tolerance = window_len / 4;
+    let last_start = frames.len() - window_len;
+
+    let window: Vec<f32> = (0..window_len)
+        .map(|i| {
+            let phase = i as f32 / window_len as f32;
+            0.5 - 0.5 * (2.0 * std::f32::consts::PI * phase).cos()
+        })
+        .collect();
+    let mono = |frame: &Frame| frame.left + frame.right;
+
+    let out_len = (frames.len() as f64 / speed) as usize + window_len;
+    let mut out = vec![Frame::ZERO; out_len];
+    let mut weights = vec![0.0f32; out_len];
+
+    let mut prev_start = 0;
+    for segment in 0.. {
+        let out_start = segment * synthesis_hop;
+        let nominal = (segment as f64 * analysis_hop).round() as usize;
+        if nominal > last_start || out_start + window_len > out_len {
+            break;
+        }
+
+        let start = if segment == 0 {
+            0
+        } else {
+            // the input that naturally follows the previous window
+            let natural = (prev_start + synthesis_hop).min(last_start);
+            let search_start = nominal.saturating_sub(tolerance);
+            let search_end = (nominal + tolerance).min(last_start);
+            (search_start..=search_end)
+                .map(|candidate| {
+                    // only every 4th frame, the similarity does not need to be exact
+                    let similarity = (0..window_len)
+                        .step_by(4)
+                        .map(|i| mono(&frames[candidate + i]) * mono(&frames[natural + i]))
+                        .sum::<f32>();
+                    (candidate, similarity)
+                })
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(candidate, _)| candidate)
+                .unwrap_or(nominal)
+        };
+
+        for (i, weight) in window.iter().enumerate() {
+            let frame = &frames[start + i];
+            let out_frame = &mut out[out_start + i];
+            out_frame.left += frame.left * weight;
+            out_frame.right += frame.right * weight;
+            weights[out_start + i] += weight;
+        }
+        prev_start = start;
+    }
+
+    let len = weights
+        .iter()
+        .rposition(|weight| *weight > 0.0)
+        .map(|i| i + 1)
+        .unwrap_or_default();
+    out.truncate(len);
+    out.iter_mut().zip(weights).for_each(|(frame, weight)| {
+        if weight > 1e-3 {
+            frame.left /= weight;
+            frame.right /= weight;
+        }
+    });
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use kira::Frame;
+
+    use super::time_stretch;
+
+    fn sine(len: usize, sample_rate: u32, freq: f32) -> Vec<Frame> {
+        (0..len)
+            .map(|i| {
+                let val = (i as f32 / sample_rate as f32 * freq * std::f32::consts::TAU).sin();
+                Frame::new(val, val)
+            })
+            .collect()
+    }
+
+    fn zero_crossings(frames: &[Frame]) -> usize {
+        frames
+            .windows(2)
+            .filter(|f| (f[0].left < 0.0) != (f[1].left < 0.0))
+            .count()
+    }
+
+    #[test]
+    fn keeps_the_pitch() {
+        let sample_rate = 44100;
+        let frames = sine(sample_rate as usize, sample_rate, 440.0);
+
+        for speed in [0.5, 0.75, 1.5, 2.0] {
+            let stretched = time_stretch(&frames, sample_rate, speed);
+            let expected_len = frames.len() as f64 / speed;
+            assert!(
+                (stretched.len() as f64 - expected_len).abs() < expected_len * 0.05,
+                "speed {speed}: {} frames instead of {expected_len}",
+                stretched.len()
+            );
+
+            // the frequency is the amount of zero crossings per second
+            let freq = zero_crossings(&stretched) as f64 / 2.0 * sample_rate as f64
+                / stretched.len() as f64;
+            assert!(
+                (freq - 440.0).abs() < 440.0 * 0.05,
+                "speed {speed}: {freq} Hz"
+            );
+        }
+    }
+}
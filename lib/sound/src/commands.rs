@@ -47,6 +47,12 @@ pub enum SoundCommandSoundScene {
         id: u128,
         samples: u32,
     },
+    /// Sets the volume of the bus all sounds & streams
+    /// of this scene are mixed into.
+    SetVolume {
+        id: u128,
+        volume: f64,
+    },
 }
 
 /// commands related to a sound object
@@ -60,8 +60,25 @@ impl SceneObject {
             )));
     }
 
+    /// Sets the volume of the whole scene, e.g. to duck all
+    /// sounds of this scene, while other sounds are more important.
+    /// Changes are faded in smoothly.
+    pub fn set_volume(&self, volume: f64) {
+        self.inner
+            .backend_handle
+            .add_cmd(SoundCommand::State(SoundCommandState::SoundScene(
+                SoundCommandSoundScene::SetVolume {
+                    id: self.inner.id,
+                    volume,
+                },
+            )));
+    }
+
     /// Only call this function on off-air sound scenes.
     /// Processes the next batch of audio samples.
+    ///
+    /// All off-air scenes with the same id share their output,
+    /// so this processes the samples of those scenes, too.
     pub fn process_off_air(&self, samples: u32) {
         self.inner
             .backend_handle
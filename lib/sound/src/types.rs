@@ -15,6 +15,10 @@ pub struct SoundPlayBaseProps {
     pub panning: f64,
     /// 1.0 is default
     pub playback_speed: f64,
+    /// Time-stretch the sound to the playback speed instead of resampling it,
+    /// so its pitch does not change.
+    /// The speed of a playing sound that is time-stretched can't be changed anymore.
+    pub preserve_pitch: bool,
 }
 
 #[derive(Debug, Hiarc, Serialize, Deserialize)]
@@ -44,6 +48,7 @@ impl SoundPlayProps {
                 looped: false,
                 panning: 0.5,
                 playback_speed: 1.0,
+                preserve_pitch: false,
             },
             start_time_delay: Duration::ZERO,
             min_distance: 1.0,
@@ -60,6 +65,10 @@ impl SoundPlayProps {
         self.base.playback_speed = playback_speed;
         self
     }
+    pub fn with_preserve_pitch(mut self, preserve_pitch: bool) -> Self {
+        self.base.preserve_pitch = preserve_pitch;
+        self
+    }
     pub fn with_volume(mut self, volume: f64) -> Self {
         self.base.volume = volume;
         self
@@ -109,4 +118,8 @@ impl StreamPlayProps {
         self.spartial = spartial;
         self
     }
+    pub fn with_volume(mut self, volume: f64) -> Self {
+        self.base.volume = volume;
+        self
+    }
 }
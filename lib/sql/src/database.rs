@@ -1,8 +1,10 @@
+use std::time::{Duration, Instant};
+
 use sqlx::{
     any::{AnyArguments, AnyPoolOptions, AnyRow},
     mysql::MySqlConnectOptions,
     query::QueryAs,
-    Any, FromRow, Pool,
+    Any, Connection, FromRow, Pool,
 };
 
 #[derive(Debug, Clone)]
@@ -53,6 +55,14 @@ impl Database {
         })
     }
 
+    /// The round trip time of a ping to the database.
+    pub async fn ping(&self) -> anyhow::Result<Duration> {
+        let mut connection = self.pool.acquire().await?;
+        let start = Instant::now();
+        connection.ping().await?;
+        Ok(start.elapsed())
+    }
+
     pub fn get_query<'a, F>(str: &'a str) -> QueryAs<'a, Any, F, AnyArguments<'a>>
     where
         F: for<'r> FromRow<'r, AnyRow>,
@@ -79,6 +79,11 @@ impl SoundCheckerApi {
                             anyhow::ensure!(self.scenes.contains_key(id));
                             anyhow::ensure!(*samples <= 1024 * 16);
                         }
+                        SoundCommandSoundScene::SetVolume { id, volume } => {
+                            *id += self.id_offset;
+                            anyhow::ensure!(self.scenes.contains_key(id));
+                            anyhow::ensure!(volume.is_finite() && *volume >= 0.0);
+                        }
                     },
                     SoundCommandState::SoundObject(cmd) => match cmd {
                         SoundCommandSoundObject::Create { id, scene_id, .. } => {
@@ -1,4 +1,9 @@
-use std::{num::NonZeroUsize, rc::Rc, sync::Arc, time::Duration};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    rc::Rc,
+    sync::Arc,
+    time::Duration,
+};
 
 use base::{
     benchmark::Benchmark,
@@ -52,7 +57,9 @@ use config::config::{ConfigEngine, ConfigMonitor};
 use demo::recorder::DemoRecorder;
 use editor::editor::{EditorInterface, EditorResult};
 use egui::CursorIcon;
-use game_config::config::{Config, ConfigGame, ConfigMap};
+use game_config::config::{
+    Config, ConfigGame, ConfigKeyboardLayout, ConfigMap, ConfigPredictionMargin,
+};
 use graphics::graphics::graphics::Graphics;
 use graphics_backend::{
     backend::{
@@ -87,7 +94,7 @@ use native::{
         InputEventHandler,
     },
     native::{
-        app::NativeApp, FromNativeImpl, FromNativeLoadingImpl, KeyCode, Native,
+        app::NativeApp, FromNativeImpl, FromNativeLoadingImpl, KeyCode, KeyboardLayout, Native,
         NativeCreateOptions, NativeImpl, NativeWindowMonitorDetails, NativeWindowOptions,
         PhysicalKey, PhysicalSize, WindowEvent,
     },
@@ -97,6 +104,7 @@ use pool::{
     datatypes::{PoolLinkedHashMap, StringPool},
     pool::Pool,
 };
+use prediction_timer::prediction_timing::PredictionMarginStrategy;
 use raw_window_handle::RawDisplayHandle;
 use rayon::ThreadPool;
 use server::server::Server;
@@ -131,15 +139,22 @@ use shared_base::{
     player_input::PlayerInput,
 };
 
-use shared_network::messages::{ClientToServerMessage, ClientToServerPlayerMessage, GameMessage};
+use shared_network::messages::{
+    ClientToServerMessage, ClientToServerPlayerMessage, GameMessage, MsgClNetworkSettings,
+};
 
 use super::{
     components::{
         client_stats::{ClientStats, ClientStatsRenderPipe},
         debug_hud::{DebugHud, DebugHudRenderPipe},
+        notifications::{ClientNotifications, ClientNotificationsRenderPipe},
+        recording_control::RecordingControl,
+        render_benchmark::RenderBenchmark,
     },
+    crash_reporter::{self, CrashReporter},
     game::{DisconnectAutoCleanup, ServerCertMode},
     game_events::{GameEventPipeline, GameEventsClient},
+    graphics_calibration::calibrate_graphics_config,
     input::input_handling::{InputHandling, InputHandlingEvent},
     spatial_chat::spatial_chat::{self, SpatialChatGameWorldTy, SpatialChatGameWorldTyRef},
 };
@@ -158,6 +173,7 @@ pub fn ddnet_main(
 ) -> anyhow::Result<()> {
     let io =
         IoFileSys::new(|rt| Arc::new(FileSystem::new(rt, "org", "", "DDNet", "DDNet-Accounts")));
+    crash_reporter::install(io.fs.get_save_path());
 
     let config_engine = config_fs::load(&io);
 
@@ -249,8 +265,12 @@ struct ClientNativeImpl {
     cert: Vec<u8>,
     connect_info: ConnectMode,
     demo_player: Option<DemoViewer>,
+    render_benchmark: Option<RenderBenchmark>,
     client_stats: ClientStats,
+    notifications: ClientNotifications,
     debug_hud: DebugHud,
+    recording_control: RecordingControl,
+    crash_reporter: CrashReporter,
     thread_pool: Arc<ThreadPool>,
     io: Io,
     config: Config,
@@ -281,6 +301,8 @@ struct ClientNativeImpl {
     menu_map: ClientMapLoading,
 
     global_binds: Binds<BindActionsHotkey>,
+    /// The last keyboard layout the native layer detected.
+    keyboard_layout: Option<KeyboardLayout>,
 
     // pools & helpers
     string_pool: StringPool,
@@ -337,13 +359,57 @@ impl ClientNativeImpl {
     }
 
     fn render_game(&mut self, native: &mut dyn NativeImpl) {
+        self.recording_control.update(
+            &self.io,
+            &self.config.game.cl.recording_control,
+            match &self.game {
+                Game::Active(game) => Some(game.demo_recorder_props.map.as_str()),
+                _ => None,
+            },
+        );
+
         if let Game::Active(game) = &mut self.game {
             // prepare input
             let events = std::mem::replace(&mut game.events, game.events_pool.new());
 
+            let local_players: Vec<_> = game.game_data.local_players.keys().copied().collect();
+            game.highlights.on_events(
+                events
+                    .values()
+                    .filter(|(_, by_prediction)| !*by_prediction)
+                    .map(|(events, _)| events),
+                &local_players,
+                &self.config.game.cl.highlights,
+                self.cur_time,
+            );
+            self.recording_control.on_events(
+                events
+                    .values()
+                    .filter(|(_, by_prediction)| !*by_prediction)
+                    .map(|(events, _)| events),
+                &local_players,
+            );
+            game.chat_templates.on_events(
+                events
+                    .values()
+                    .filter(|(_, by_prediction)| !*by_prediction)
+                    .map(|(events, _)| events),
+                &local_players,
+            );
+
+            // the render module can be changed in the settings while playing
+            let render_mod = &self.config.game.cl.render_mod;
+            if game.map.render.render_mod() != render_mod
+                && game.map.next_render_mod() != Some(render_mod.as_str())
+            {
+                game.map.switch_render_mod(render_mod);
+            }
+            game.map.continue_render_mod_switch(&self.config.engine.dbg);
+
             let GameMap {
                 render,
                 game: game_state,
+                ..
             } = &mut game.map;
             let is_menu_open = self.ui_manager.ui.ui_state.is_ui_open;
 
@@ -415,12 +481,28 @@ impl ClientNativeImpl {
                 settings: RenderGameSettings {
                     spartial_sound: self.config.game.snd.spartial,
                     sound_playback_speed: 1.0,
+                    sound_preserve_pitch: false,
                     nameplates: self.config.game.cl.nameplates,
                     nameplate_own: self.config.game.cl.own_nameplate,
                     ingame_sound_volume: self.config.game.snd.ingame_sound_volume
                         * self.config.game.snd.global_volume,
                     map_sound_volume: self.config.game.snd.map_sound_volume
                         * self.config.game.snd.global_volume,
+                    ducking_volume: spatial_chat::SpatialChat::game_sound_volume(
+                        &game.spatial_world,
+                        &self.config.game,
+                    ),
+                    hud_layout: (&self.config.game.cl.hud_layout).into(),
+                    texture_memory_budget: self.config.game.cl.texture_memory_budget,
+                    resource_download_size_limit: if self.config.game.cl.low_bandwidth.active {
+                        self.config
+                            .game
+                            .cl
+                            .low_bandwidth
+                            .resource_download_size_limit
+                    } else {
+                        0
+                    },
                 },
             };
 
@@ -445,6 +527,31 @@ impl ClientNativeImpl {
                         player.cursor_pos = client_player.input.inp.cursor.to_vec2();
                     }
 
+                    let free_cam_pos = vec2::new(
+                        client_player.free_cam_pos.x as f32,
+                        client_player.free_cam_pos.y as f32,
+                    );
+                    let cam_mode = match character_info.and_then(|c| c.player_info.as_ref()) {
+                        Some(info) => match info.cam_mode {
+                            PlayerCameraMode::Default => RenderPlayerCameraMode::Default,
+                            PlayerCameraMode::Free => RenderPlayerCameraMode::AtPos(free_cam_pos),
+                            PlayerCameraMode::LockedTo(pos) => RenderPlayerCameraMode::AtPos(pos),
+                            PlayerCameraMode::FollowCharacter(id) => {
+                                RenderPlayerCameraMode::AtPos(
+                                    character_infos
+                                        .get(&id)
+                                        .and_then(|c| c.stage_id)
+                                        .and_then(|stage_id| stages_render_infos.get(&stage_id))
+                                        .and_then(|s| s.world.characters.get(&id))
+                                        .map(|c| c.lerped_pos)
+                                        // not yet in the snapshot
+                                        .unwrap_or(free_cam_pos),
+                                )
+                            }
+                        },
+                        None => RenderPlayerCameraMode::Default,
+                    };
+
                     (
                         player_id,
                         RenderGameForPlayer {
@@ -464,30 +571,14 @@ impl ClientNativeImpl {
                                 } else {
                                     None
                                 },
+                                recent_emoticons: client_player.recent_emoticons.to_vec(),
                                 chat_show_all: client_player.show_chat_all,
                                 scoreboard_active: client_player.show_scoreboard,
 
                                 local_player_info: local_player_render_info,
 
                                 zoom: client_player.zoom,
-                                cam_mode: match character_info.and_then(|c| c.player_info.as_ref())
-                                {
-                                    Some(info) => match info.cam_mode {
-                                        PlayerCameraMode::Default => {
-                                            RenderPlayerCameraMode::Default
-                                        }
-                                        PlayerCameraMode::Free => {
-                                            RenderPlayerCameraMode::AtPos(vec2::new(
-                                                client_player.free_cam_pos.x as f32,
-                                                client_player.free_cam_pos.y as f32,
-                                            ))
-                                        }
-                                        PlayerCameraMode::LockedTo(pos) => {
-                                            RenderPlayerCameraMode::AtPos(pos)
-                                        }
-                                    },
-                                    None => RenderPlayerCameraMode::Default,
-                                },
+                                cam_mode,
                             },
                             observed_players: game.render_observers_pool.new(),
                             observed_anchored_size_props: ObservedAnchoredSize {
@@ -514,12 +605,15 @@ impl ClientNativeImpl {
                     )
                 };
 
+            // in split screen the dummies are rendered like the main player,
+            // the render game splits the screen between all players.
+            let split_screen = self.config.game.cl.dummy.split_screen;
             let mut requires_scoreboard = false;
             let ids = game
                 .game_data
                 .local_players
                 .iter_mut()
-                .filter(|(_, player)| !player.is_dummy);
+                .filter(|(_, player)| split_screen || !player.is_dummy);
             ids.for_each(|client_player| {
                 let (player_id, render_for_player) = fill_for_player(
                     client_player,
@@ -539,7 +633,7 @@ impl ClientNativeImpl {
             render_game_input.dummies.extend(dummies);
 
             // if miniscreens of the dummies should show up, add additional infor for player.
-            if self.config.game.cl.dummy.mini_screen {
+            if self.config.game.cl.dummy.mini_screen && !split_screen {
                 if let Some((_, player)) = render_game_input.players.iter_mut().next() {
                     player
                         .observed_players
@@ -568,13 +662,16 @@ impl ClientNativeImpl {
                     match player_event {
                         PlayerFeedbackEvent::Chat(ev) => match ev {
                             ChatEvent::MsgSend(msg) => {
+                                let msg = NetworkString::new(&msg).unwrap();
                                 game.network.send_in_order_to_server(
                                     &GameMessage::ClientToServer(ClientToServerMessage::PlayerMsg(
                                         (
                                             player_id,
                                             ClientToServerPlayerMessage::Chat(
-                                                MsgClChatMsg::Global {
-                                                    msg: NetworkString::new(&msg).unwrap(),
+                                                if local_player.chat_team {
+                                                    MsgClChatMsg::GameTeam { msg }
+                                                } else {
+                                                    MsgClChatMsg::Global { msg }
                                                 },
                                             ),
                                         ),
@@ -606,10 +703,20 @@ impl ClientNativeImpl {
                 self.debug_hud.render(&mut DebugHudRenderPipe {
                     graphics: &self.graphics,
                     prediction_timer: &game.game_data.prediction_timer,
+                    time_sync: &game.game_data.time_sync.stats(self.cur_time),
                     byte_stats: &game.game_data.net_byte_stats,
                     ingame_timer: &game.game_data.last_game_tick,
                 });
             }
+
+            game.highlights.update(
+                &self.graphics,
+                &self.io,
+                &self.config.game.cl.highlights,
+                &game.demo_recorder_props,
+                game.map.game.game_tick_speed(),
+                self.cur_time,
+            );
         } else {
             // menu background map
             self.render_menu_background_map();
@@ -662,7 +769,7 @@ impl ClientNativeImpl {
             // if demo viewer is active, render it
             if let Some(demo_player) = &mut self.demo_player {
                 if let Some(demo_viewer) = demo_player.try_get_mut() {
-                    if demo_viewer
+                    let demo_ended = demo_viewer
                         .render(
                             if self.local_console.ui.ui_state.is_ui_open
                                 || self.game.remote_console_open()
@@ -673,12 +780,23 @@ impl ClientNativeImpl {
                             },
                         )
                         .is_err()
-                        || demo_viewer.is_closed()
-                    {
+                        || demo_viewer.is_closed();
+                    let benchmark_ended = self.render_benchmark.as_mut().is_some_and(|benchmark| {
+                        benchmark.on_frame(
+                            self.sys.time_get_nanoseconds(),
+                            self.graphics_backend.render_cmd_count(),
+                            &self.graphics_backend.memory_usage(),
+                        ) || demo_ended
+                            || demo_viewer.is_finished()
+                    });
+                    if demo_ended {
                         self.demo_player = None;
                     }
+                    if benchmark_ended {
+                        self.finish_render_benchmark(native);
+                    }
                 } else {
-                    demo_player
+                    let loaded = demo_player
                         .continue_loading(
                             &self.sound,
                             &self.graphics,
@@ -688,7 +806,14 @@ impl ClientNativeImpl {
                             &self.sys,
                             &self.ui_creator,
                         )
-                        .unwrap();
+                        .map(|_| ());
+                    if let Err(err) = loaded {
+                        self.notifications
+                            .handle()
+                            .error(format!("Failed to load the demo: {err}"));
+                        self.demo_player = None;
+                        self.render_benchmark = None;
+                    }
                 }
             } else if self.ui_manager.ui.ui_state.is_ui_open {
                 // render ui last
@@ -717,28 +842,51 @@ impl ClientNativeImpl {
                 let ui_events = self.ui_events.take();
                 for ui_event in ui_events {
                     match ui_event {
-                        UiEvent::PlayDemo { name } => {
+                        UiEvent::PlayDemo { name, key } => {
                             self.demo_player = Some(DemoViewer::new(
                                 &self.io,
                                 &self.thread_pool,
                                 name.as_ref(),
                                 self.font_data.clone(),
                                 None,
+                                key,
+                                self.config.game.cl.demo_resource_server.parse().ok(),
                             ));
                         }
-                        UiEvent::EncodeDemoToVideo { name, video_name } => {
+                        UiEvent::EncodeDemoToVideo {
+                            name,
+                            video_name,
+                            key,
+                        } => {
+                            // the video is rendered offscreen, so it must fit the canvas limits
+                            let gpus = self.graphics.backend_handle.gpus();
                             self.demo_player = Some(DemoViewer::new(
                                 &self.io,
                                 &self.thread_pool,
                                 name.as_ref(),
                                 self.font_data.clone(),
                                 Some(DemoVideoEncodeProperties {
-                                    file_name: format!("videos/{}.mp4", video_name).into(),
+                                    file_name: std::path::Path::new(
+                                        &self.config.game.cl.recorder.path,
+                                    )
+                                    .join(format!("{}.mp4", video_name)),
                                     pixels_per_point: self.config.game.cl.recorder.pixels_per_point,
                                     encoder_settings: EncoderSettings {
                                         fps: self.config.game.cl.recorder.fps,
-                                        width: self.config.game.cl.recorder.width,
-                                        height: self.config.game.cl.recorder.height,
+                                        width: self
+                                            .config
+                                            .game
+                                            .cl
+                                            .recorder
+                                            .width
+                                            .min(gpus.cur.max_offscreen_width),
+                                        height: self
+                                            .config
+                                            .game
+                                            .cl
+                                            .recorder
+                                            .height
+                                            .min(gpus.cur.max_offscreen_height),
                                         hw_accel: self.config.game.cl.recorder.hw_accel.clone(),
                                         max_threads: std::thread::available_parallelism()
                                             .map(|v| v.get() + 2)
@@ -747,17 +895,27 @@ impl ClientNativeImpl {
                                             as u64,
                                         sample_rate: self.config.game.cl.recorder.sample_rate,
                                         crf: self.config.game.cl.recorder.crf,
+                                        bitrate: self.config.game.cl.recorder.bitrate,
+                                        codec: self.config.game.cl.recorder.codec.clone(),
                                     },
                                 }),
+                                key,
+                                self.config.game.cl.demo_resource_server.parse().ok(),
                             ));
                         }
                         UiEvent::RecordDemo => {
                             if let Game::Active(game) = &mut self.game {
-                                game.demo_recorder = Some(DemoRecorder::new(
+                                match DemoRecorder::new(
                                     game.demo_recorder_props.clone(),
                                     game.map.game.game_tick_speed(),
                                     None,
-                                ));
+                                ) {
+                                    Ok(recorder) => game.demo_recorder = Some(recorder),
+                                    Err(err) => self
+                                        .notifications
+                                        .handle()
+                                        .error(format!("Failed to record the demo: {err}")),
+                                }
                             }
                         }
                         UiEvent::StartEditor => {
@@ -1160,10 +1318,18 @@ impl ClientNativeImpl {
                 .handle_platform_output(native, platform_output, false);
         }
 
+        // toasts above the game
+        self.notifications
+            .render(&mut ClientNotificationsRenderPipe {
+                graphics: &self.graphics,
+                sys: &self.sys,
+            });
+
         // fps (& debug)
         self.client_stats.render(&mut ClientStatsRenderPipe {
             graphics: &self.graphics,
             sys: &self.sys,
+            gfx_stats: self.config.engine.dbg.gfx_stats,
         });
 
         self.sound.swap();
@@ -1184,9 +1350,40 @@ impl ClientNativeImpl {
         for event in events {
             match event {
                 LocalConsoleEvent::Quit => native.quit(),
+                LocalConsoleEvent::NotificationHistory => {
+                    self.notifications.show_history = !self.notifications.show_history;
+                }
+                LocalConsoleEvent::CrashReports => {
+                    let path = self.io.fs.get_save_path().join(crash_reporter::CRASHES_DIR);
+                    if let Err(err) = crash_reporter::open_dir(&path) {
+                        log::error!(target: "crash", "could not open {}: {err}", path.display());
+                    }
+                }
+                LocalConsoleEvent::Benchmark { demo, seconds } => {
+                    self.demo_player = Some(DemoViewer::new(
+                        &self.io,
+                        &self.thread_pool,
+                        demo.as_ref(),
+                        self.font_data.clone(),
+                        None,
+                        None,
+                        self.config.game.cl.demo_resource_server.parse().ok(),
+                    ));
+                    self.render_benchmark = Some(RenderBenchmark::new(demo, seconds));
+                }
             }
         }
     }
+
+    /// Writes the report of the benchmark mode & quits the client.
+    fn finish_render_benchmark(&mut self, native: &mut dyn NativeImpl) {
+        if let Some(benchmark) = self.render_benchmark.take() {
+            if let Err(err) = benchmark.write_report(&self.io) {
+                log::error!(target: "benchmark", "failed to write the report: {err}");
+            }
+            native.quit();
+        }
+    }
 }
 
 impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
@@ -1237,6 +1434,8 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
         let mut ui_creator = UiCreator::default();
         // prepare network stuff while waiting for io
         let client_stats = ClientStats::new(&loading.sys, &ui_creator);
+        let notifications = ClientNotifications::new(&ui_creator);
+        let crash_reporter = CrashReporter::new(&io);
 
         let sound_backend = SoundBackend::new(&loading.config_engine.snd)?;
         let sound = SoundManager::new(sound_backend.clone())?;
@@ -1305,6 +1504,15 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
         let graphics_backend = GraphicsBackend::new(graphics_backend);
         let mut graphics = Graphics::new(graphics_backend.clone(), stream_data, window_props);
 
+        // make sure the next start only requests modes the gpu supports
+        if calibrate_graphics_config(
+            &mut loading.config_engine.gl,
+            &graphics.backend_handle.gpus(),
+        ) {
+            config_fs::save(&loading.config_engine, &io);
+        }
+        benchmark.bench("calibrating graphics config");
+
         let graphics_memory_usage = graphics_backend.memory_usage();
         let debug_hud = DebugHud::new(
             &loading.sys,
@@ -1371,6 +1579,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
                 fonts: font_data.clone(),
                 sound_props: Default::default(),
             },
+            &loading.config_game.cl.render_mod,
         );
         benchmark.bench("menu map");
 
@@ -1414,7 +1623,12 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
             player_settings_sync.clone(),
             &loading.config_game,
         ));
-        let connecting_menu = Box::new(ConnectingUi::new(connect_info.clone(), ui_events.clone()));
+        let connecting_menu = Box::new(ConnectingUi::new(
+            &graphics,
+            io.clone(),
+            connect_info.clone(),
+            ui_events.clone(),
+        ));
         let ingame_menu = Box::new(IngameMenuUi::new(
             &graphics,
             &sound,
@@ -1491,6 +1705,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
         );
 
         local_console.ui.ui_state.is_ui_open = false;
+        local_console.load_history(&io, "local");
 
         benchmark.bench("finish init of client");
 
@@ -1513,8 +1728,12 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
             cert: loading.cert,
             connect_info,
             demo_player: None,
+            render_benchmark: None,
             client_stats,
+            notifications,
             debug_hud,
+            recording_control: Default::default(),
+            crash_reporter,
             thread_pool,
             io,
             config: Config::new(loading.config_game, loading.config_engine),
@@ -1542,6 +1761,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
             scene,
 
             global_binds,
+            keyboard_layout: None,
             inp_manager,
 
             // pools & helpers
@@ -1659,11 +1879,38 @@ impl FromNativeImpl for ClientNativeImpl {
             &mut self.debug_hud.ui,
             &self.graphics,
             &self.io,
+            self.notifications.handle(),
         );
 
+        // the detected layout is only used while the user selected `Auto`,
+        // see `ConfigClient::active_keyboard_layout`
+        let keyboard_layout = native.keyboard_layout();
+        if keyboard_layout != self.keyboard_layout {
+            self.keyboard_layout = keyboard_layout;
+            if let Some(keyboard_layout) = keyboard_layout {
+                self.config.game.cl.detected_keyboard_layout = match keyboard_layout {
+                    KeyboardLayout::Qwerty => ConfigKeyboardLayout::Qwerty,
+                    KeyboardLayout::Azerty => ConfigKeyboardLayout::Azerty,
+                    KeyboardLayout::Qwertz => ConfigKeyboardLayout::Qwertz,
+                };
+            }
+        }
+
         let sys = &mut self.sys;
         self.cur_time = sys.time_get_nanoseconds();
 
+        self.crash_reporter.update(
+            self.cur_time,
+            &self.config.engine,
+            &self.config.game,
+            match &self.game {
+                Game::Active(game) => game.demo_recorder.as_ref().map(|demo| demo.name()),
+                _ => None,
+            },
+            &self.io,
+            self.notifications.handle(),
+        );
+
         self.game.update(
             &self.graphics,
             &self.graphics_backend,
@@ -1672,6 +1919,7 @@ impl FromNativeImpl for ClientNativeImpl {
             &self.config.game,
             sys,
             &self.ui_creator,
+            self.notifications.handle(),
         );
 
         GameEventsClient::update(&mut GameEventPipeline {
@@ -1690,6 +1938,7 @@ impl FromNativeImpl for ClientNativeImpl {
             account_info: &self.account_info,
             spatial_chat: &mut self.spatial_chat,
             spatial_chat_scene: &self.scene,
+            notifications: self.notifications.handle(),
         });
         let has_input = !self.ui_manager.ui.ui_state.is_ui_open
             && !self.local_console.ui.ui_state.is_ui_open
@@ -1789,6 +2038,37 @@ impl FromNativeImpl for ClientNativeImpl {
                                     )),
                                 ));
                         }
+                        InputHandlingEvent::ChatTemplate {
+                            local_player_id,
+                            name,
+                        } => {
+                            let Some(template) = self.config.game.cl.chat_templates.get(&name)
+                            else {
+                                log::info!(target: "chat", "no chat template named {name}");
+                                continue;
+                            };
+                            if !game.chat_templates.try_send(self.cur_time) {
+                                continue;
+                            }
+                            let msg = game.chat_templates.expand(
+                                template,
+                                &local_player_id,
+                                &game.map.game.collect_characters_info(),
+                            );
+                            if let Ok(msg) = NetworkString::new(msg) {
+                                game.network.send_in_order_to_server(
+                                    &GameMessage::ClientToServer(ClientToServerMessage::PlayerMsg(
+                                        (
+                                            local_player_id,
+                                            ClientToServerPlayerMessage::Chat(
+                                                MsgClChatMsg::Global { msg },
+                                            ),
+                                        ),
+                                    )),
+                                    NetworkInOrderChannel::Global,
+                                );
+                            }
+                        }
                         InputHandlingEvent::ChangeEyes {
                             local_player_id,
                             eye,
@@ -1811,10 +2091,41 @@ impl FromNativeImpl for ClientNativeImpl {
                 }
             }
 
+            game.game_data.prediction_timer.set_margin(
+                match self.config.game.cl.prediction_margin {
+                    ConfigPredictionMargin::Aggressive => PredictionMarginStrategy::Aggressive,
+                    ConfigPredictionMargin::Balanced => PredictionMarginStrategy::Balanced,
+                    ConfigPredictionMargin::Safe => PredictionMarginStrategy::Safe,
+                },
+                self.config.game.cl.prediction_margin_offset as f64 / 1000.0,
+            );
             game.game_data.prediction_timer.add_frametime(
                 self.cur_time.saturating_sub(game.game_data.last_frame_time),
                 self.cur_time,
             );
+            if let Some(id) = game.game_data.time_sync.try_request(self.cur_time) {
+                game.network
+                    .send_unordered_to_server(&GameMessage::ClientToServer(
+                        ClientToServerMessage::TimeSync { id },
+                    ));
+            }
+            let low_bandwidth = &self.config.game.cl.low_bandwidth;
+            let network_settings = if low_bandwidth.active {
+                MsgClNetworkSettings {
+                    ticks_per_snapshot: NonZeroU64::new(low_bandwidth.ticks_per_snapshot),
+                    high_compression: low_bandwidth.high_compression,
+                    reduced_events: low_bandwidth.reduced_events,
+                }
+            } else {
+                Default::default()
+            };
+            if game.game_data.network_settings != network_settings {
+                game.game_data.network_settings = network_settings;
+                game.network
+                    .send_unordered_to_server(&GameMessage::ClientToServer(
+                        ClientToServerMessage::NetworkSettings(network_settings),
+                    ));
+            }
             game.game_data.last_frame_time = self.cur_time;
             let game_state = &mut game.map.game;
 
@@ -1895,10 +2206,14 @@ impl FromNativeImpl for ClientNativeImpl {
                                 });
                             }
                         } else {
-                            player_input_chains.push(PlayerInputChainable {
+                            let inp = PlayerInputChainable {
                                 for_monotonic_tick: tick_of_inp,
                                 inp: *net_inp,
-                            });
+                            };
+                            player_input_chains.push(inp);
+                            if let Some(demo_recorder) = &mut game.demo_recorder {
+                                demo_recorder.add_input(*local_player_id, inp);
+                            }
                         }
                     }
                 }
@@ -2115,7 +2430,8 @@ impl FromNativeImpl for ClientNativeImpl {
 
             game.game_data.last_game_tick = Duration::from_secs_f64(
                 (game.game_data.last_game_tick.as_secs_f64()
-                    + game.game_data.prediction_timer.smooth_adjustment_time())
+                    + game.game_data.prediction_timer.smooth_adjustment_time()
+                    + game.game_data.time_sync.correction(self.cur_time))
                 .clamp(0.0, f64::MAX),
             );
         }
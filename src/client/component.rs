@@ -8,7 +8,7 @@ use client_types::console::ConsoleEntry;
 use client_ui::ingame_menu::account_info::AccountInfo;
 use config::config::ConfigEngine;
 
-use demo::recorder::DemoRecorder;
+use demo::{recorder::DemoRecorder, replay::DemoReplayBuffer};
 use game_config::config::ConfigGame;
 use game_interface::{events::GameEvents, types::game::GameTickType};
 use network::network::quinn_network::QuinnNetwork;
@@ -22,6 +22,8 @@ use super::spatial_chat::spatial_chat::{SpatialChat, SpatialChatGameWorld};
 
 pub struct GameMsgPipeline<'a> {
     pub demo_recorder: &'a mut Option<DemoRecorder>,
+    /// See [`crate::client::components::highlights::Highlights::replay`].
+    pub replay: &'a mut DemoReplayBuffer,
     pub network: &'a mut QuinnNetwork,
     pub runtime_thread_pool: &'a Arc<rayon::ThreadPool>,
     pub io: &'a Io,
@@ -0,0 +1,110 @@
+use std::{collections::HashMap, time::Duration};
+
+use game_interface::{
+    events::{GameEvents, GameWorldAction, GameWorldEvent, GameWorldGlobalEvent},
+    types::{game::GameEntityId, render::character::CharacterInfo},
+};
+use pool::datatypes::PoolLinkedHashMap;
+
+/// Chat messages that binds send by name (`chat_template <name>`),
+/// see `cl.chat_templates`.
+///
+/// Variables in curly braces are replaced when the message is sent:
+/// - `{name}`: the name of the local player
+/// - `{last_killer}`: who killed the local player the last time
+/// - `{last_victim}`: who the local player killed the last time
+#[derive(Debug, Default)]
+pub struct ChatTemplates {
+    last_killer: Option<GameEntityId>,
+    last_victim: Option<GameEntityId>,
+    /// time the last template was sent
+    last_sent: Option<Duration>,
+}
+
+impl ChatTemplates {
+    /// Templates can be sent at most once in this time,
+    /// so that a stuck or spammed key does not flood the chat.
+    const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn on_events<'a>(
+        &mut self,
+        events: impl Iterator<Item = &'a GameEvents>,
+        local_players: &[GameEntityId],
+    ) {
+        let is_local = |id: &GameEntityId| local_players.contains(id);
+        for ev in events.flat_map(|events| events.worlds.values().flat_map(|w| w.events.values())) {
+            if let GameWorldEvent::Global(GameWorldGlobalEvent::Action(GameWorldAction::Kill {
+                killer: Some(killer),
+                victims,
+                ..
+            })) = ev
+            {
+                if is_local(killer) {
+                    if let Some(victim) = victims.iter().find(|id| !is_local(id)) {
+                        self.last_victim = Some(*victim);
+                    }
+                } else if victims.iter().any(is_local) {
+                    self.last_killer = Some(*killer);
+                }
+            }
+        }
+    }
+
+    /// Replaces the variables of the template.
+    /// Unknown variables are kept as they are,
+    /// variables without a value (e.g. nobody was killed yet) are empty.
+    pub fn expand(
+        &self,
+        template: &str,
+        local_player_id: &GameEntityId,
+        characters: &PoolLinkedHashMap<GameEntityId, CharacterInfo>,
+    ) -> String {
+        let name = |id: Option<&GameEntityId>| {
+            id.and_then(|id| characters.get(id))
+                .map(|character| character.info.name.to_string())
+                .unwrap_or_default()
+        };
+        let vars: HashMap<&str, String> = [
+            ("name", name(Some(local_player_id))),
+            ("last_killer", name(self.last_killer.as_ref())),
+            ("last_victim", name(self.last_victim.as_ref())),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut res = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            res.push_str(&rest[..start]);
+            let var = &rest[start + 1..];
+            match var
+                .find('}')
+                .and_then(|end| Some((vars.get(&var[..end])?, end)))
+            {
+                Some((val, end)) => {
+                    res.push_str(val);
+                    rest = &var[end + 1..];
+                }
+                None => {
+                    res.push('{');
+                    rest = var;
+                }
+            }
+        }
+        res.push_str(rest);
+        res
+    }
+
+    /// Whether a template may be sent now, see [`Self::MIN_INTERVAL`].
+    /// Counts as sent if so.
+    pub fn try_send(&mut self, cur_time: Duration) -> bool {
+        if self
+            .last_sent
+            .is_some_and(|last_sent| cur_time.saturating_sub(last_sent) < Self::MIN_INTERVAL)
+        {
+            return false;
+        }
+        self.last_sent = Some(cur_time);
+        true
+    }
+}
@@ -5,6 +5,7 @@ use egui_extras::StripBuilder;
 use fixed::{types::extra::U16, FixedI64};
 
 use graphics::graphics::graphics::Graphics;
+use graphics_backend_traits::types::GraphicsBackendStats;
 
 use ui_base::{
     style::default_style,
@@ -33,7 +34,7 @@ impl ClientStatsData {
     pub fn render_stats(
         &mut self,
         ui: &mut egui::Ui,
-        pipe: &mut UiRenderPipe<()>,
+        pipe: &mut UiRenderPipe<Option<GraphicsBackendStats>>,
         _ui_state: &mut UiState,
     ) {
         ui.set_style(default_style());
@@ -49,9 +50,14 @@ impl ClientStatsData {
             FixedI64::from_num(0.1),
         );
 
+        let gfx_stats = *pipe.user_data;
         StripBuilder::new(ui)
             .size(egui_extras::Size::remainder())
-            .size(egui_extras::Size::exact(60.0))
+            .size(egui_extras::Size::exact(if gfx_stats.is_some() {
+                180.0
+            } else {
+                60.0
+            }))
             .horizontal(|mut strip| {
                 strip.cell(|_| {});
                 strip.cell(|ui| {
@@ -59,6 +65,19 @@ impl ClientStatsData {
                         Color32::from_rgb(255, 0, 255),
                         format!("{}", self.fps.floor()),
                     );
+                    if let Some(stats) = gfx_stats {
+                        let mib = |bytes: u64| bytes as f64 / 1024.0 / 1024.0;
+                        for line in [
+                            format!("draw calls: {}", stats.draw_calls),
+                            format!("buffer uploads: {}", stats.buffer_uploads),
+                            format!("texture uploads: {}", stats.texture_uploads),
+                            format!("canvas switches: {}", stats.canvas_switches),
+                            format!("texture memory: {:.2} MiB", mib(stats.texture_memory_usage)),
+                            format!("buffer memory: {:.2} MiB", mib(stats.buffer_memory_usage)),
+                        ] {
+                            ui.colored_label(Color32::from_rgb(255, 0, 255), line);
+                        }
+                    }
                 })
             });
     }
@@ -67,6 +86,8 @@ impl ClientStatsData {
 pub struct ClientStatsRenderPipe<'a> {
     pub graphics: &'a Graphics,
     pub sys: &'a system::System,
+    /// Also show the [`GraphicsBackendStats`] of the last frame.
+    pub gfx_stats: bool,
 }
 
 /// This component collects various client statistics and displays them optionally
@@ -94,7 +115,10 @@ impl ClientStats {
             window_height,
             window_pixels_per_point,
             |ui, pipe, ui_state| self.data.render_stats(ui, pipe, ui_state),
-            &mut UiRenderPipe::new(pipe.sys.time_get_nanoseconds(), &mut ()),
+            &mut UiRenderPipe::new(
+                pipe.sys.time_get_nanoseconds(),
+                &mut pipe.gfx_stats.then(|| pipe.graphics.backend_handle.stats()),
+            ),
             Default::default(),
             false,
         );
@@ -9,7 +9,7 @@ use egui_extras::StripBuilder;
 use graphics::graphics::graphics::Graphics;
 
 use math::math::vector::luffixed;
-use prediction_timer::prediction_timing::PredictionTimer;
+use prediction_timer::{prediction_timing::PredictionTimer, time_sync::TimeSyncStats};
 use ui_base::{
     style::default_style,
     types::UiRenderPipe,
@@ -106,11 +106,50 @@ impl DebugHudData {
                                 / 1000.0
                         ),
                     );
+                    ui.label("Prediction margin:");
+                    ui.colored_label(
+                        Color32::from_rgb(255, 0, 255),
+                        format!(
+                            "{:?} ({:+.0} ms)",
+                            timing.margin_strategy,
+                            timing.margin_offset * 1000.0
+                        ),
+                    );
+                    ui.label("Extra latency by margin (ms):");
+                    ui.colored_label(
+                        Color32::from_rgb(255, 0, 255),
+                        format!("{:.2}", timing.extra_latency.as_micros() as f64 / 1000.0),
+                    );
+                    ui.label("Mispredicted snaps %:");
+                    let misprediction_rate = pipe.user_data.prediction_timer.misprediction_rate();
+                    ui.colored_label(
+                        if misprediction_rate > 0.1 {
+                            Color32::RED
+                        } else {
+                            Color32::from_rgb(255, 0, 255)
+                        },
+                        format!("{:.2}", misprediction_rate * 100.0),
+                    );
                     ui.label("Prediction adjustment smooth (ms):");
                     ui.colored_label(
                         Color32::from_rgb(255, 0, 255),
                         format!("{:.4}", timing.smooth_adjustment_time * 1000.0),
                     );
+                    ui.label("Clock offset (ms):");
+                    ui.colored_label(
+                        Color32::from_rgb(255, 0, 255),
+                        format!("{:.4}", pipe.user_data.time_sync.offset * 1000.0),
+                    );
+                    ui.label("Clock drift (ms/min):");
+                    ui.colored_label(
+                        Color32::from_rgb(255, 0, 255),
+                        format!("{:.4}", pipe.user_data.time_sync.drift * 1000.0 * 60.0),
+                    );
+                    ui.label("Clock correction (ms):");
+                    ui.colored_label(
+                        Color32::from_rgb(255, 0, 255),
+                        format!("{:.4}", pipe.user_data.time_sync.applied_offset * 1000.0),
+                    );
                     ui.label("Ingame time (ms):");
                     ui.colored_label(
                         Color32::from_rgb(255, 0, 255),
@@ -211,6 +250,7 @@ impl DebugHudData {
 pub struct DebugHudRenderPipe<'a> {
     pub graphics: &'a Graphics,
     pub prediction_timer: &'a PredictionTimer,
+    pub time_sync: &'a TimeSyncStats,
     pub byte_stats: &'a NetworkByteStats,
     pub ingame_timer: &'a Duration,
 }
@@ -0,0 +1,191 @@
+use std::{collections::VecDeque, time::Duration};
+
+use base_io::io::Io;
+use demo::{recorder::DemoRecorderCreateProps, replay::DemoReplayBuffer};
+use game_config::config::ConfigHighlights;
+use game_interface::{
+    events::{
+        GameEvents, GameFlagEvent, GameFlagEventSound, GameWorldAction, GameWorldEntityEvent,
+        GameWorldEvent, GameWorldGlobalEvent,
+    },
+    types::game::{GameEntityId, NonZeroGameTickType},
+};
+use graphics::graphics::graphics::{Graphics, ScreenshotCb};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightEvent {
+    MultiKill { count: usize },
+    FlagCapture,
+    Finish,
+}
+
+impl HighlightEvent {
+    fn file_name_part(&self) -> String {
+        match self {
+            HighlightEvent::MultiKill { count } => format!("multikill_{count}"),
+            HighlightEvent::FlagCapture => "flag_capture".to_string(),
+            HighlightEvent::Finish => "finish".to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct HighlightScreenshot {
+    io: Io,
+    name: String,
+}
+
+impl ScreenshotCb for HighlightScreenshot {
+    fn on_screenshot(&self, png: anyhow::Result<Vec<u8>>) {
+        match png {
+            Ok(png) => {
+                let fs = self.io.fs.clone();
+                let path = format!("screenshots/highlights/{}.png", self.name);
+                self.io.io_batcher.spawn_without_lifetime(async move {
+                    fs.create_dir("screenshots/highlights".as_ref()).await?;
+                    fs.write_file(path.as_ref(), png).await?;
+                    Ok(())
+                });
+            }
+            Err(err) => {
+                log::error!(target: "highlights", "{err}");
+            }
+        }
+    }
+}
+
+/// Watches the game events for notable moments of the local players
+/// (multi kills, flag captures, finishes) and automatically
+/// saves a screenshot of them, and optionally a demo of the last seconds.
+#[derive(Debug, Default)]
+pub struct Highlights {
+    /// The last seconds of the game, filled while [`ConfigHighlights::replay`] is set.
+    pub replay: DemoReplayBuffer,
+    /// time of recent kills of the local players
+    kills: VecDeque<Duration>,
+    /// the highlight is captured with a small delay,
+    /// so that e.g. the kill feed is already visible.
+    pending: Option<(Duration, HighlightEvent)>,
+}
+
+impl Highlights {
+    /// Kills within this time count as a multi kill.
+    const MULTI_KILL_WINDOW: Duration = Duration::from_secs(4);
+    const CAPTURE_DELAY: Duration = Duration::from_millis(500);
+
+    pub fn on_events<'a>(
+        &mut self,
+        events: impl Iterator<Item = &'a GameEvents>,
+        local_players: &[GameEntityId],
+        config: &ConfigHighlights,
+        cur_time: Duration,
+    ) {
+        let is_local = |id: &GameEntityId| local_players.contains(id);
+        let mut highlight = None;
+        for ev in events.flat_map(|events| events.worlds.values().flat_map(|w| w.events.values())) {
+            match ev {
+                GameWorldEvent::Positioned(ev) => {
+                    if let GameWorldEntityEvent::Flag {
+                        ev: GameFlagEvent::Sound(GameFlagEventSound::Capture),
+                    } = &ev.ev
+                    {
+                        if config.flag_capture && ev.owner_id.as_ref().is_some_and(is_local) {
+                            highlight = Some(HighlightEvent::FlagCapture);
+                        }
+                    }
+                }
+                GameWorldEvent::Global(GameWorldGlobalEvent::Action(action)) => match action {
+                    GameWorldAction::Kill {
+                        killer, victims, ..
+                    } => {
+                        if killer.as_ref().is_some_and(is_local) {
+                            for _ in victims.iter().filter(|id| !is_local(*id)) {
+                                self.kills.push_back(cur_time);
+                            }
+                        }
+                    }
+                    GameWorldAction::RaceFinish { character, .. } => {
+                        if config.finish && is_local(character) {
+                            highlight = Some(HighlightEvent::Finish);
+                        }
+                    }
+                    GameWorldAction::RaceTeamFinish { characters, .. } => {
+                        if config.finish && characters.iter().any(is_local) {
+                            highlight = Some(HighlightEvent::Finish);
+                        }
+                    }
+                    GameWorldAction::Custom(_) => {}
+                },
+                GameWorldEvent::Global(GameWorldGlobalEvent::System(_))
+                | GameWorldEvent::Global(GameWorldGlobalEvent::Stat(_))
+                | GameWorldEvent::Global(GameWorldGlobalEvent::Vote(_))
+                | GameWorldEvent::Global(GameWorldGlobalEvent::PlayerMessage { .. })
+                | GameWorldEvent::Global(GameWorldGlobalEvent::Kick { .. })
+                | GameWorldEvent::Global(GameWorldGlobalEvent::ExportRoundReport { .. }) => {}
+            }
+        }
+
+        while self
+            .kills
+            .front()
+            .is_some_and(|time| cur_time.saturating_sub(*time) > Self::MULTI_KILL_WINDOW)
+        {
+            self.kills.pop_front();
+        }
+        if config.multi_kill && self.kills.len() >= config.multi_kill_count.max(2) as usize {
+            highlight = Some(HighlightEvent::MultiKill {
+                count: self.kills.len(),
+            });
+            self.kills.clear();
+        }
+
+        if let Some(highlight) = highlight {
+            self.pending = Some((cur_time + Self::CAPTURE_DELAY, highlight));
+        }
+    }
+
+    /// Takes the screenshot (and saves the replay) of a pending highlight, if it's time.
+    pub fn update(
+        &mut self,
+        graphics: &Graphics,
+        io: &Io,
+        config: &ConfigHighlights,
+        demo_recorder_props: &DemoRecorderCreateProps,
+        ticks_per_second: NonZeroGameTickType,
+        cur_time: Duration,
+    ) {
+        let Some((at, highlight)) = self.pending else {
+            return;
+        };
+        if cur_time < at {
+            return;
+        }
+        self.pending = None;
+
+        let name = format!(
+            "{}_{}_{}",
+            demo_recorder_props.map.as_str(),
+            highlight.file_name_part(),
+            chrono::Local::now().format("%Y_%m_%d_%H_%M_%S")
+        );
+        if config.replay {
+            let props = DemoRecorderCreateProps {
+                split: Default::default(),
+                record_inputs: false,
+                ..demo_recorder_props.clone()
+            };
+            if let Err(err) = self
+                .replay
+                .save(props, ticks_per_second, format!("highlight_{name}"))
+            {
+                log::error!(target: "highlights", "{err}");
+            }
+        }
+        if let Err(err) = graphics.do_screenshot(HighlightScreenshot {
+            io: io.clone(),
+            name,
+        }) {
+            log::error!(target: "highlights", "{err}");
+        }
+    }
+}
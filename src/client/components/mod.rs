@@ -1,3 +1,8 @@
+pub mod chat_templates;
 pub mod client_stats;
 pub mod debug_hud;
+pub mod highlights;
 pub mod network_logic;
+pub mod notifications;
+pub mod recording_control;
+pub mod render_benchmark;
@@ -115,6 +115,16 @@ impl NetworkLogic {
 
                 let GameMap { game, .. } = pipe.map;
                 let ticks_per_second = game.game_tick_speed();
+                let highlights = &pipe.config_game.cl.highlights;
+                if highlights.replay {
+                    pipe.replay.add_snapshot(
+                        game_monotonic_tick,
+                        snapshot.as_ref().to_vec(),
+                        highlights.replay_secs * ticks_per_second.get(),
+                    );
+                } else {
+                    pipe.replay.clear();
+                }
                 let tick_time = time_until_tick(ticks_per_second);
                 let monotonic_tick = game_monotonic_tick;
 
@@ -133,6 +143,27 @@ impl NetworkLogic {
                     );
 
                     pipe.game_data.handled_snap_id = Some(snap_id);
+
+                    if let Some(demo_recorder) = pipe.demo_recorder {
+                        if demo_recorder.should_capture_scoreboard(monotonic_tick) {
+                            demo_recorder.add_event(
+                                monotonic_tick,
+                                DemoEvent::Scoreboard(game.collect_scoreboard_info()),
+                            );
+                            // also capture the active vote, so seeking inside
+                            // the demo can restore it.
+                            if let Some((vote, voted, vote_timestamp)) = &pipe.game_data.vote {
+                                let mut vote = (**vote).clone();
+                                vote.remaining_time = vote
+                                    .remaining_time
+                                    .saturating_sub(timestamp.saturating_sub(*vote_timestamp));
+                                demo_recorder.add_event(
+                                    monotonic_tick,
+                                    DemoEvent::Vote(Some((vote, *voted))),
+                                );
+                            }
+                        }
+                    }
                     if as_diff {
                         // this should be higher than the number of snapshots saved on the server
                         // (since reordering of packets etc.)
@@ -254,6 +285,10 @@ impl NetworkLogic {
                 if let Some(demo_recorder) = pipe.demo_recorder {
                     demo_recorder.add_event(game_monotonic_tick, DemoEvent::Game(events.clone()));
                 }
+                if pipe.config_game.cl.highlights.replay {
+                    pipe.replay
+                        .add_event(game_monotonic_tick, DemoEvent::Game(events.clone()));
+                }
 
                 let event_id = events.event_id;
                 pipe.events.insert(game_monotonic_tick, (events, false));
@@ -272,6 +307,12 @@ impl NetworkLogic {
                         DemoEvent::Chat(chat_msg.msg.clone()),
                     );
                 }
+                if pipe.config_game.cl.highlights.replay {
+                    pipe.replay.add_event(
+                        pipe.map.game.predicted_game_monotonic_tick,
+                        DemoEvent::Chat(chat_msg.msg.clone()),
+                    );
+                }
 
                 pipe.game_data.chat_msgs.push_back(chat_msg.msg);
             }
@@ -281,6 +322,12 @@ impl NetworkLogic {
                     .vote
                     .as_ref()
                     .and_then(|(_, voted, _)| *voted);
+                if let Some(demo_recorder) = pipe.demo_recorder {
+                    demo_recorder.add_event(
+                        pipe.map.game.predicted_game_monotonic_tick,
+                        DemoEvent::Vote(vote_state.clone().map(|v| (v, voted))),
+                    );
+                }
                 pipe.game_data.vote =
                     vote_state.map(|v| (PoolRc::from_item_without_pool(v), voted, *timestamp));
             }
@@ -323,6 +370,15 @@ impl NetworkLogic {
                     pipe.account_info.fill_last_action_response(Some(Some(err)));
                 }
             },
+            ServerToClientMessage::TimeSync {
+                id,
+                server_time,
+                overhead,
+            } => {
+                pipe.game_data
+                    .time_sync
+                    .add_response(id, server_time, overhead, *timestamp);
+            }
             ServerToClientMessage::SpatialChat { entities } => {
                 pipe.spatial_chat.on_input(
                     pipe.spatial_world
@@ -0,0 +1,129 @@
+use std::{collections::VecDeque, time::Duration};
+
+use client_ui::notifications::{
+    main_frame,
+    notifications::{Notification, NotificationKind, Notifications},
+    user_data::UserData,
+};
+use egui::Color32;
+use graphics::graphics::graphics::Graphics;
+use ui_base::{
+    style::default_style,
+    types::UiRenderPipe,
+    ui::{UiContainer, UiCreator},
+    ui_render::render_ui,
+};
+
+use base::system::{self, SystemTimeInterface};
+
+pub struct ClientNotificationsRenderPipe<'a> {
+    pub graphics: &'a Graphics,
+    pub sys: &'a system::System,
+}
+
+/// Shows the notifications of all subsystems as toasts above the game,
+/// see [`Notifications`].
+pub struct ClientNotifications {
+    notifications: Notifications,
+
+    toasts: VecDeque<(Duration, Notification)>,
+    history: VecDeque<Notification>,
+    pub show_history: bool,
+
+    ui: UiContainer,
+}
+
+impl ClientNotifications {
+    /// How long a toast is shown.
+    const TOAST_DURATION: Duration = Duration::from_secs(5);
+    const TOASTS_MAX: usize = 5;
+    const HISTORY_MAX: usize = 50;
+
+    pub fn new(creator: &UiCreator) -> Self {
+        let mut ui = UiContainer::new(creator);
+        ui.set_main_panel_color(&Color32::TRANSPARENT);
+        Self {
+            notifications: Default::default(),
+
+            toasts: Default::default(),
+            history: Default::default(),
+            show_history: false,
+
+            ui,
+        }
+    }
+
+    /// The handle subsystems push their notifications to.
+    pub fn handle(&self) -> &Notifications {
+        &self.notifications
+    }
+
+    pub fn render(&mut self, pipe: &mut ClientNotificationsRenderPipe) {
+        let cur_time = pipe.sys.time_get_nanoseconds();
+        for notification in self.notifications.take() {
+            match notification.kind {
+                NotificationKind::Info => {
+                    log::info!(target: "notification", "{}", notification.msg)
+                }
+                NotificationKind::Warning => {
+                    log::warn!(target: "notification", "{}", notification.msg)
+                }
+                NotificationKind::Error => {
+                    log::error!(target: "notification", "{}", notification.msg)
+                }
+            }
+            if self.history.len() >= Self::HISTORY_MAX {
+                self.history.pop_front();
+            }
+            self.history.push_back(notification.clone());
+            if self.toasts.len() >= Self::TOASTS_MAX {
+                self.toasts.pop_front();
+            }
+            self.toasts
+                .push_back((cur_time + Self::TOAST_DURATION, notification));
+        }
+        while self
+            .toasts
+            .front()
+            .is_some_and(|(until, _)| *until <= cur_time)
+        {
+            self.toasts.pop_front();
+        }
+        if self.toasts.is_empty() && !self.show_history {
+            return;
+        }
+
+        let window_width = pipe.graphics.canvas_handle.window_width();
+        let window_height = pipe.graphics.canvas_handle.window_height();
+        let window_pixels_per_point = pipe.graphics.canvas_handle.window_pixels_per_point();
+        let (screen_rect, full_output, zoom_level) = self.ui.render(
+            window_width,
+            window_height,
+            window_pixels_per_point,
+            |ui, pipe, _| {
+                ui.set_style(default_style());
+                main_frame::render(ui, pipe)
+            },
+            &mut UiRenderPipe::new(
+                cur_time,
+                &mut UserData {
+                    toasts: &self.toasts,
+                    history: &self.history,
+                    show_history: self.show_history,
+                },
+            ),
+            Default::default(),
+            false,
+        );
+        render_ui(
+            &mut self.ui,
+            full_output,
+            &screen_rect,
+            zoom_level,
+            &pipe.graphics.backend_handle,
+            &pipe.graphics.texture_handle,
+            &pipe.graphics.stream_handle,
+            false,
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use std::net::Ipv4Addr;
+
+use base_io::{io::Io, io_batcher::IoBatcherTask};
+use futures_util::{SinkExt, StreamExt};
+use game_config::config::ConfigRecordingControl;
+use game_interface::{
+    events::{
+        GameEvents, GameWorldAction, GameWorldEvent, GameWorldGlobalEvent, GameWorldStatEvent,
+    },
+    types::game::GameEntityId,
+};
+use tokio::{
+    net::TcpListener,
+    sync::broadcast::{self, error::RecvError},
+};
+use tokio_tungstenite::tungstenite::{
+    handshake::server::{ErrorResponse, Request, Response},
+    http::{header::ORIGIN, StatusCode},
+    Message,
+};
+use url::{Host, Url};
+
+/// How many events are buffered for slow websocket clients.
+const EVENTS_BUFFER: usize = 64;
+
+/// Browsers send the `Origin` of the page that opens a websocket,
+/// only pages of localhost (e.g. a local overlay) are allowed,
+/// so that any other web page can't listen to the events.
+/// Streaming software & scripts don't send an origin at all.
+fn is_allowed_origin(origin: Option<&str>) -> bool {
+    let Some(origin) = origin else {
+        return true;
+    };
+    Url::parse(origin).is_ok_and(|url| match url.host() {
+        Some(Host::Domain(domain)) => domain == "localhost",
+        Some(Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    })
+}
+
+#[derive(Debug)]
+struct RecordingControlServer {
+    port: u16,
+    events: broadcast::Sender<String>,
+    /// aborts the websocket server on drop
+    task: Option<IoBatcherTask<()>>,
+}
+
+impl RecordingControlServer {
+    fn new(io: &Io, port: u16) -> Self {
+        let (events, _) = broadcast::channel(EVENTS_BUFFER);
+        let task = io
+            .io_batcher
+            .spawn(Self::serve(port, events.clone()))
+            .cancelable();
+        Self {
+            port,
+            events,
+            task: Some(task),
+        }
+    }
+
+    async fn serve(port: u16, events: broadcast::Sender<String>) -> anyhow::Result<()> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await?;
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let mut events = events.subscribe();
+            tokio::spawn(async move {
+                let check_origin = |req: &Request, res: Response| {
+                    let origin = req
+                        .headers()
+                        .get(ORIGIN)
+                        .map(|origin| origin.to_str().unwrap_or_default());
+                    if is_allowed_origin(origin) {
+                        Ok(res)
+                    } else {
+                        log::info!(
+                            target: "recording_control",
+                            "rejected a websocket client from {origin:?}"
+                        );
+                        let mut res = ErrorResponse::new(Some("origin not allowed".to_string()));
+                        *res.status_mut() = StatusCode::FORBIDDEN;
+                        Err(res)
+                    }
+                };
+                let Ok(mut ws) = tokio_tungstenite::accept_hdr_async(stream, check_origin).await
+                else {
+                    return;
+                };
+                loop {
+                    tokio::select! {
+                        ev = events.recv() => match ev {
+                            Ok(ev) => {
+                                if ws.send(Message::Text(ev)).await.is_err() {
+                                    break;
+                                }
+                            }
+                            Err(RecvError::Lagged(_)) => {}
+                            Err(RecvError::Closed) => break,
+                        },
+                        // incoming messages are ignored, but must be read
+                        // to answer pings & notice closed connections.
+                        msg = ws.next() => {
+                            if !matches!(msg, Some(Ok(_))) {
+                                break;
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Sends notable game events of the local players over a local websocket,
+/// so that streaming software (e.g. OBS through a script)
+/// can switch scenes or start & stop recordings automatically.
+///
+/// Every event is a JSON object with an `event` field:
+/// `match_start` (with the `map`) when the client entered a game,
+/// `match_end` when it left the game again,
+/// `round_start`, `round_end` & `death` (of a local player).
+#[derive(Debug, Default)]
+pub struct RecordingControl {
+    server: Option<RecordingControlServer>,
+    /// the map of the game the client is currently in
+    map: Option<String>,
+}
+
+impl RecordingControl {
+    fn send(&self, ev: serde_json::Value) {
+        if let Some(server) = &self.server {
+            // no connected websocket clients is not an error
+            let _ = server.events.send(ev.to_string());
+        }
+    }
+
+    /// Starts or stops the websocket server according to the config
+    /// and sends the match start & end events.
+    ///
+    /// `map` is the map of the active game, if any.
+    pub fn update(&mut self, io: &Io, config: &ConfigRecordingControl, map: Option<&str>) {
+        let port = config.port as u16;
+        if !config.enabled {
+            self.server = None;
+        } else if !matches!(&self.server, Some(server) if server.port == port) {
+            self.server = Some(RecordingControlServer::new(io, port));
+        }
+        if let Some(server) = &mut self.server {
+            if server.task.as_ref().is_some_and(|task| task.is_finished()) {
+                if let Err(err) = server.task.take().unwrap().get_storage() {
+                    log::warn!(target: "recording_control", "websocket server stopped: {err}");
+                }
+            }
+        }
+
+        if self.map.as_deref() != map {
+            if self.map.is_some() {
+                self.send(serde_json::json!({ "event": "match_end" }));
+            }
+            self.map = map.map(|map| map.to_string());
+            if let Some(map) = map {
+                self.send(serde_json::json!({ "event": "match_start", "map": map }));
+            }
+        }
+    }
+
+    pub fn on_events<'a>(
+        &self,
+        events: impl Iterator<Item = &'a GameEvents>,
+        local_players: &[GameEntityId],
+    ) {
+        if self.server.is_none() {
+            return;
+        }
+        for ev in events.flat_map(|events| events.worlds.values().flat_map(|w| w.events.values())) {
+            match ev {
+                GameWorldEvent::Global(GameWorldGlobalEvent::Action(GameWorldAction::Kill {
+                    victims,
+                    ..
+                })) => {
+                    if victims.iter().any(|id| local_players.contains(id)) {
+                        self.send(serde_json::json!({ "event": "death" }));
+                    }
+                }
+                GameWorldEvent::Global(GameWorldGlobalEvent::Stat(
+                    GameWorldStatEvent::RoundStart,
+                )) => {
+                    self.send(serde_json::json!({ "event": "round_start" }));
+                }
+                GameWorldEvent::Global(GameWorldGlobalEvent::Stat(
+                    GameWorldStatEvent::RoundEnd,
+                )) => {
+                    self.send(serde_json::json!({ "event": "round_end" }));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_allowed_origin;
+
+    #[test]
+    fn allowed_origins() {
+        assert!(is_allowed_origin(None));
+        assert!(is_allowed_origin(Some("http://localhost:8080")));
+        assert!(is_allowed_origin(Some("http://LOCALHOST")));
+        assert!(is_allowed_origin(Some("http://127.0.0.1:3000")));
+        assert!(is_allowed_origin(Some("http://[::1]")));
+
+        assert!(!is_allowed_origin(Some("https://example.com")));
+        assert!(!is_allowed_origin(Some("http://localhost.example.com")));
+        assert!(!is_allowed_origin(Some("http://192.168.0.2")));
+        assert!(!is_allowed_origin(Some("null")));
+        assert!(!is_allowed_origin(Some("")));
+    }
+}
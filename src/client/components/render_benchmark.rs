@@ -0,0 +1,204 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use base_io::io::Io;
+use graphics_backend::backend::GraphicsBackendMemoryUsage;
+use serde::Serialize;
+
+#[derive(Debug, Default, Serialize)]
+pub struct RenderBenchmarkFrameTimes {
+    pub avg_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct RenderBenchmarkMemory {
+    pub texture: u64,
+    pub buffer: u64,
+    pub stream: u64,
+    pub staging: u64,
+}
+
+/// The machine readable result of a benchmark run.
+#[derive(Debug, Serialize)]
+pub struct RenderBenchmarkReport {
+    pub demo: String,
+    pub date: String,
+    pub version: String,
+    /// The time that was actually benchmarked, in seconds.
+    /// Shorter than requested, if the demo ended earlier.
+    pub duration: f64,
+    pub frames: usize,
+    pub avg_fps: f64,
+    pub frame_times: RenderBenchmarkFrameTimes,
+    pub avg_render_cmds_per_frame: f64,
+    pub max_render_cmds_per_frame: u64,
+    /// The peak memory usage of the graphics backend in bytes.
+    pub peak_memory: RenderBenchmarkMemory,
+}
+
+/// Plays back a demo for a fixed time and collects render statistics,
+/// so that performance regressions of the render pipeline can be
+/// tracked between builds.
+///
+/// Started by the `benchmark` console command, usually as start argument,
+/// e.g. together with `gfx.backend null` for a headless run.
+/// The report is written as JSON to `benchmarks/`.
+#[derive(Debug)]
+pub struct RenderBenchmark {
+    pub demo: String,
+    duration: Duration,
+
+    /// time of the first frame the demo was rendered
+    start: Option<Duration>,
+    last_frame: Duration,
+    last_render_cmd_count: u64,
+
+    frame_times: Vec<Duration>,
+    render_cmds: Vec<u64>,
+    peak_memory: RenderBenchmarkMemory,
+}
+
+impl RenderBenchmark {
+    pub fn new(demo: String, seconds: u64) -> Self {
+        Self {
+            demo,
+            duration: Duration::from_secs(seconds),
+
+            start: None,
+            last_frame: Duration::ZERO,
+            last_render_cmd_count: 0,
+
+            frame_times: Default::default(),
+            render_cmds: Default::default(),
+            peak_memory: Default::default(),
+        }
+    }
+
+    /// Collects the statistics of a frame in which the demo was rendered.
+    ///
+    /// Returns `true` if the benchmark time is over.
+    pub fn on_frame(
+        &mut self,
+        cur_time: Duration,
+        render_cmd_count: u64,
+        memory_usage: &GraphicsBackendMemoryUsage,
+    ) -> bool {
+        let Some(start) = self.start else {
+            // the first frame only starts the measurement,
+            // it usually includes the loading of the demo.
+            self.start = Some(cur_time);
+            self.last_frame = cur_time;
+            self.last_render_cmd_count = render_cmd_count;
+            return false;
+        };
+
+        self.frame_times
+            .push(cur_time.saturating_sub(self.last_frame));
+        self.render_cmds
+            .push(render_cmd_count.saturating_sub(self.last_render_cmd_count));
+        self.last_frame = cur_time;
+        self.last_render_cmd_count = render_cmd_count;
+
+        let peak = |peak: &mut u64, usage: &AtomicU64| {
+            *peak = (*peak).max(usage.load(Ordering::Relaxed));
+        };
+        peak(
+            &mut self.peak_memory.texture,
+            &memory_usage.texture_memory_usage,
+        );
+        peak(
+            &mut self.peak_memory.buffer,
+            &memory_usage.buffer_memory_usage,
+        );
+        peak(
+            &mut self.peak_memory.stream,
+            &memory_usage.stream_memory_usage,
+        );
+        peak(
+            &mut self.peak_memory.staging,
+            &memory_usage.staging_memory_usage,
+        );
+
+        cur_time.saturating_sub(start) >= self.duration
+    }
+
+    fn percentile(sorted: &[Duration], percentile: f64) -> Duration {
+        if sorted.is_empty() {
+            return Duration::ZERO;
+        }
+        let index = ((sorted.len() - 1) as f64 * percentile).round() as usize;
+        sorted[index.min(sorted.len() - 1)]
+    }
+
+    pub fn report(&self) -> RenderBenchmarkReport {
+        let mut sorted = self.frame_times.clone();
+        sorted.sort();
+        let total: Duration = sorted.iter().sum();
+        let frames = sorted.len();
+        let ms = |time: Duration| time.as_secs_f64() * 1000.0;
+
+        RenderBenchmarkReport {
+            demo: self.demo.clone(),
+            date: chrono::Local::now().to_rfc3339(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            duration: total.as_secs_f64(),
+            frames,
+            avg_fps: if total.is_zero() {
+                0.0
+            } else {
+                frames as f64 / total.as_secs_f64()
+            },
+            frame_times: RenderBenchmarkFrameTimes {
+                avg_ms: ms(total) / frames.max(1) as f64,
+                p50_ms: ms(Self::percentile(&sorted, 0.5)),
+                p90_ms: ms(Self::percentile(&sorted, 0.9)),
+                p99_ms: ms(Self::percentile(&sorted, 0.99)),
+                max_ms: ms(sorted.last().copied().unwrap_or_default()),
+            },
+            avg_render_cmds_per_frame: self.render_cmds.iter().sum::<u64>() as f64
+                / self.render_cmds.len().max(1) as f64,
+            max_render_cmds_per_frame: self.render_cmds.iter().copied().max().unwrap_or_default(),
+            peak_memory: self.peak_memory.clone(),
+        }
+    }
+
+    /// Writes the report to `benchmarks/`.
+    ///
+    /// Blocks until the file is written, since the client quits afterwards.
+    pub fn write_report(&self, io: &Io) -> anyhow::Result<()> {
+        let report = self.report();
+        log::info!(
+            target: "benchmark",
+            "{}: {} frames, {:.1} fps, frame times p50 {:.2} ms, p99 {:.2} ms",
+            report.demo,
+            report.frames,
+            report.avg_fps,
+            report.frame_times.p50_ms,
+            report.frame_times.p99_ms
+        );
+
+        let fs = io.fs.clone();
+        let path = format!(
+            "benchmarks/{}_{}.json",
+            std::path::Path::new(&self.demo)
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy(),
+            chrono::Local::now().format("%Y_%m_%d_%H_%M_%S")
+        );
+        io.io_batcher
+            .spawn(async move {
+                let report = serde_json::to_vec_pretty(&report)?;
+                fs.create_dir("benchmarks".as_ref()).await?;
+                fs.write_file(path.as_ref(), report).await?;
+                Ok(())
+            })
+            .get_storage()
+    }
+}
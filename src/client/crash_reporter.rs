@@ -0,0 +1,344 @@
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use base_io::{io::Io, io_batcher::IoBatcherTask};
+use client_ui::notifications::notifications::Notifications;
+use config::config::ConfigEngine;
+use demo::{
+    cut::{cut_demo, DemoCutFilters},
+    utils::read_demo_header,
+};
+use game_config::config::ConfigGame;
+
+/// The directory in the save path, the crash bundles are written to.
+pub const CRASHES_DIR: &str = "crashes";
+/// Contains the name of the bundle of the last crash,
+/// until the next start picked it up.
+const LAST_CRASH_FILE: &str = "last_crash";
+const LOG_LINES_MAX: usize = 1000;
+/// How much of the demo that was recorded during the crash is kept.
+const DEMO_TAIL: Duration = Duration::from_secs(30);
+
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static STATE: Mutex<CrashState> = Mutex::new(CrashState {
+    config: String::new(),
+    demo: None,
+});
+
+struct CrashState {
+    /// json of the config at the time of the last update
+    config: String,
+    /// the demo that is currently recorded
+    demo: Option<String>,
+}
+
+/// Logs like `env_logger`, but also keeps the most recent
+/// lines for the crash bundle.
+struct CrashLogger {
+    inner: env_logger::Logger,
+}
+
+impl log::Log for CrashLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.inner.matches(record) {
+            return;
+        }
+        self.inner.log(record);
+        // never block or panic inside the logger, e.g. while the panic hook logs
+        if let Ok(mut logs) = RECENT_LOGS.try_lock() {
+            if logs.len() >= LOG_LINES_MAX {
+                logs.pop_front();
+            }
+            logs.push_back(format!(
+                "[{} {} {}] {}",
+                chrono::Local::now().format("%H:%M:%S%.3f"),
+                record.level(),
+                record.target(),
+                record.args()
+            ));
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Replaces `env_logger::init`, so the crash bundle contains the recent logs.
+pub fn init_logger() {
+    let inner = env_logger::Builder::from_default_env().build();
+    let max_level = inner.filter();
+    if log::set_boxed_logger(Box::new(CrashLogger { inner })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Writes a crash bundle to [`CRASHES_DIR`] when the client panics:
+/// the panic & its backtrace, the recent logs, the config and
+/// the name of the demo that was recorded.
+///
+/// The demo is only finished while unwinding, so its last seconds
+/// are cut on the next start, see [`CrashReporter::new`].
+pub fn install(save_path: PathBuf) {
+    let prev_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        match write_bundle(&save_path, info) {
+            Ok(path) => eprintln!("a crash report was written to {}", path.display()),
+            Err(err) => eprintln!("the crash report could not be written: {err}"),
+        }
+        prev_hook(info);
+    }));
+}
+
+fn write_bundle(save_path: &Path, info: &dyn std::fmt::Display) -> anyhow::Result<PathBuf> {
+    let crashes_path = save_path.join(CRASHES_DIR);
+    let name = chrono::Local::now()
+        .format("crash_%Y_%m_%d_%H_%M_%S")
+        .to_string();
+    let path = crashes_path.join(&name);
+    std::fs::create_dir_all(&path)?;
+
+    std::fs::write(
+        path.join("panic.txt"),
+        format!(
+            "{info}\n\nthread: {}\n\n{}",
+            std::thread::current().name().unwrap_or("unnamed"),
+            std::backtrace::Backtrace::force_capture()
+        ),
+    )?;
+    let logs = RECENT_LOGS
+        .try_lock()
+        .map(|logs| logs.iter().map(|line| format!("{line}\n")).collect())
+        .unwrap_or_else(|_| "the logs were locked during the crash.\n".to_string());
+    std::fs::write(path.join("logs.txt"), logs)?;
+    if let Ok(state) = STATE.try_lock() {
+        std::fs::write(path.join("config.json"), &state.config)?;
+        if let Some(demo) = &state.demo {
+            std::fs::write(path.join("demo.txt"), demo)?;
+        }
+    }
+
+    std::fs::write(crashes_path.join(LAST_CRASH_FILE), &name)?;
+    Ok(path)
+}
+
+/// The bundle of the crash of the last run.
+#[derive(Debug)]
+pub struct LastCrash {
+    /// Relative to the save path.
+    pub path: PathBuf,
+    /// The name & file of the demo that was recorded during the crash.
+    pub demo: Option<(String, Vec<u8>)>,
+}
+
+/// Keeps the state the crash bundle is written from up to date
+/// and finishes the bundle of the last crash.
+#[derive(Debug, Default)]
+pub struct CrashReporter {
+    last_config_update: Option<Duration>,
+    last_crash: Option<IoBatcherTask<Option<LastCrash>>>,
+    demo_tail: Option<IoBatcherTask<()>>,
+}
+
+impl CrashReporter {
+    /// The config is serialized at most this often.
+    const CONFIG_UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+
+    /// Starts to load the bundle of the last crash, see [`take_last_crash`].
+    pub fn new(io: &Io) -> Self {
+        Self {
+            last_crash: Some(take_last_crash(io)),
+            ..Default::default()
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        cur_time: Duration,
+        config_engine: &ConfigEngine,
+        config_game: &ConfigGame,
+        demo: Option<&str>,
+        io: &Io,
+        notifications: &Notifications,
+    ) {
+        if self
+            .last_crash
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            if let Some(task) = self.last_crash.take() {
+                match task.get_storage() {
+                    Ok(Some(last_crash)) => {
+                        notifications.warning(format!(
+                            "The client crashed the last time, a crash report was saved to {}. \
+                            Use `crash_reports` in the console to open it.",
+                            io.fs.get_save_path().join(&last_crash.path).display()
+                        ));
+                        self.demo_tail = Some(save_demo_tail(
+                            io,
+                            last_crash,
+                            &config_game.cl.demo_passphrase,
+                        ));
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        log::error!(target: "crash", "could not read the last crash report: {err}")
+                    }
+                }
+            }
+        }
+        if self
+            .demo_tail
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            if let Some(Err(err)) = self.demo_tail.take().map(|task| task.get_storage()) {
+                log::error!(target: "crash", "could not save the demo tail of the last crash: {err}");
+            }
+        }
+
+        let Ok(mut state) = STATE.lock() else {
+            return;
+        };
+        if state.demo.as_deref() != demo {
+            state.demo = demo.map(|demo| demo.to_string());
+        }
+        if self
+            .last_config_update
+            .is_some_and(|time| cur_time.saturating_sub(time) < Self::CONFIG_UPDATE_INTERVAL)
+        {
+            return;
+        }
+        self.last_config_update = Some(cur_time);
+        let mut config = serde_json::json!({
+            "engine": config_engine,
+            "game": config_game,
+        });
+        redact_secrets(&mut config);
+        state.config = serde_json::to_string_pretty(&config).unwrap_or_default();
+    }
+}
+
+/// The crash bundle is meant to be attached to bug reports,
+/// so passwords, passphrases, secrets & tokens never end up in it.
+fn redact_secrets(value: &mut serde_json::Value) {
+    const SECRET_KEYS: [&str; 4] = ["password", "passphrase", "secret", "token"];
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_lowercase();
+                if SECRET_KEYS.iter().any(|secret| key.contains(secret)) {
+                    *value = serde_json::Value::String("<redacted>".to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// Loads the bundle of the crash of the last run, if the client crashed,
+/// together with the demo that was recorded during the crash.
+fn take_last_crash(io: &Io) -> IoBatcherTask<Option<LastCrash>> {
+    let fs = io.fs.clone();
+    io.io_batcher.spawn(async move {
+        let crashes_path = Path::new(CRASHES_DIR);
+        let last_crash_path = crashes_path.join(LAST_CRASH_FILE);
+        if !fs.file_exists(&last_crash_path).await {
+            return Ok(None);
+        }
+        let name = String::from_utf8(fs.read_file(&last_crash_path).await?)?;
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Ok(None);
+        }
+        // the fs has no remove, an empty marker means no crash
+        fs.write_file(&last_crash_path, Vec::new()).await?;
+        let path = crashes_path.join(&name);
+
+        let demo_txt = path.join("demo.txt");
+        let demo = if fs.file_exists(&demo_txt).await {
+            let demo = String::from_utf8(fs.read_file(&demo_txt).await?)?;
+            let demo = demo.trim().to_string();
+            // the demo might never have been written to disk
+            fs.read_file(&Path::new("demos").join(format!("{demo}.twdemo")))
+                .await
+                .ok()
+                .map(|file| (demo, file))
+        } else {
+            None
+        };
+
+        Ok(Some(LastCrash { path, demo }))
+    })
+}
+
+/// The demo is only finished while unwinding, so its last seconds
+/// are cut into the demo `<bundle name>` & copied into the bundle
+/// as `demo_tail.twdemo`.
+fn save_demo_tail(io: &Io, last_crash: LastCrash, demo_passphrase: &str) -> IoBatcherTask<()> {
+    let fs = io.fs.clone();
+    let LastCrash { path, demo } = last_crash;
+    let cut_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let cut = demo.map(|(demo, file)| {
+        let res = read_demo_header(&file).and_then(|(header, _)| {
+            cut_demo(
+                io,
+                &file,
+                (!demo_passphrase.is_empty()).then(|| demo_passphrase.to_string()),
+                cut_name.clone(),
+                header.len.saturating_sub(DEMO_TAIL)..=header.len,
+                &DemoCutFilters::default(),
+            )
+        });
+        (demo, res)
+    });
+    io.io_batcher.spawn(async move {
+        let Some((demo, res)) = cut else {
+            return Ok(());
+        };
+        let note = match res {
+            Ok(()) => {
+                let tail = fs
+                    .read_file(&Path::new("demos").join(format!("{cut_name}.twdemo")))
+                    .await?;
+                fs.write_file(&path.join("demo_tail.twdemo"), tail).await?;
+                format!(
+                    "the last {} seconds of {demo} were saved as demo_tail.twdemo \
+                    and as the demo {cut_name}",
+                    DEMO_TAIL.as_secs(),
+                )
+            }
+            Err(err) => format!("the demo {demo} could not be cut: {err}"),
+        };
+        fs.write_file(&path.join("demo.txt"), note.into_bytes())
+            .await?;
+        Ok(())
+    })
+}
+
+/// Opens the directory with the file manager of the OS.
+pub fn open_dir(path: &Path) -> anyhow::Result<()> {
+    let cmd = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    std::process::Command::new(cmd).arg(path).spawn()?;
+    Ok(())
+}
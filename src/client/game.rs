@@ -12,9 +12,7 @@ use base::{
     system::{System, SystemTimeInterface},
 };
 use base_io::{io::Io, io_batcher::IoBatcherTask};
-use binds::binds::{
-    gen_local_player_action_hash_map, syn_to_bind, BindActions, BindActionsLocalPlayer,
-};
+use binds::binds::{gen_local_player_action_hash_map, syn_to_bind};
 use client_accounts::accounts::Accounts;
 use client_console::console::remote_console::{RemoteConsole, RemoteConsoleBuilder};
 use client_map::client_map::{ClientMapFile, ClientMapLoading, GameMap};
@@ -31,11 +29,12 @@ use client_ui::{
         votes::Votes,
     },
     main_menu::{player_settings_ntfy::PlayerSettingsSync, spatial_chat},
+    notifications::notifications::Notifications,
 };
 use command_parser::parser::{self, CommandType};
 use config::config::ConfigEngine;
-use demo::recorder::{DemoRecorder, DemoRecorderCreateProps};
-use game_config::config::{ConfigDummyProfile, ConfigGame, ConfigPlayer};
+use demo::recorder::{DemoRecorder, DemoRecorderCreateProps, DemoRecorderSplit, DemoSavedCb};
+use game_config::config::{ConfigDemoSplit, ConfigDummyProfile, ConfigGame, ConfigPlayer};
 use game_interface::{
     events::GameEvents,
     interface::GameStateCreateOptions,
@@ -46,7 +45,6 @@ use game_interface::{
         network_string::NetworkString,
         resource_key::NetworkResourceKey,
         snapshot::SnapshotLocalPlayers,
-        weapons::WeaponType,
     },
     votes::{MapVote, VoteState, Voted},
 };
@@ -55,10 +53,6 @@ use graphics_backend::backend::GraphicsBackend;
 use hashlink::{LinkedHashMap, LinkedHashSet};
 use log::info;
 use math::math::vector::{luffixed, vec2};
-use native::{
-    input::binds::{BindKey, MouseExtra},
-    native::{KeyCode, MouseButton, PhysicalKey},
-};
 use network::network::{
     network::{NetworkClientCertCheckMode, NetworkClientCertMode, NetworkClientInitOptions},
     packet_compressor::DefaultNetworkPacketCompressor,
@@ -72,6 +66,7 @@ use pool::{
     rc::PoolRc,
 };
 use prediction_timer::prediction_timing::PredictionTimer;
+use prediction_timer::time_sync::TimeSync;
 use shared_base::{
     network::{
         messages::{
@@ -86,7 +81,8 @@ use shared_base::{
 use shared_network::{
     game_event_generator::GameEventGenerator,
     messages::{
-        ClientToServerMessage, ClientToServerPlayerMessage, GameMessage, ServerToClientMessage,
+        ClientToServerMessage, ClientToServerPlayerMessage, GameMessage, MsgClNetworkSettings,
+        ServerToClientMessage,
     },
 };
 use sound::{scene_object::SceneObject, sound::SoundManager};
@@ -98,7 +94,9 @@ use crate::localplayer::{ClientPlayer, LocalPlayers};
 use super::{
     client::ClientPlayerInputPerTick,
     component::GameMsgPipeline,
-    components::network_logic::NetworkLogic,
+    components::{
+        chat_templates::ChatTemplates, highlights::Highlights, network_logic::NetworkLogic,
+    },
     input::input_handling::DeviceToLocalPlayerIndex,
     spatial_chat::spatial_chat::{SpatialChat, SpatialChatGameWorldTy},
 };
@@ -172,6 +170,10 @@ pub struct GameData {
     pub sent_input_ids: BTreeMap<u64, Duration>,
 
     pub prediction_timer: PredictionTimer,
+    /// Estimates & corrects the clock drift between client & server.
+    pub time_sync: TimeSync,
+    /// The network settings that were last sent to the server.
+    pub network_settings: MsgClNetworkSettings,
     pub net_byte_stats: NetworkByteStats,
 
     pub last_game_tick: Duration,
@@ -208,6 +210,8 @@ impl GameData {
 
             handled_snap_id: None,
             prediction_timer,
+            time_sync: TimeSync::new(),
+            network_settings: Default::default(),
             net_byte_stats: Default::default(),
 
             last_game_tick: cur_time,
@@ -244,163 +248,52 @@ impl GameData {
                 let binds = &mut local_player.binds;
                 let map = gen_local_player_action_hash_map();
 
-                if snap_player.is_dummy {
-                    if let Some((player, dummy)) = config
+                let bind_player = if snap_player.is_dummy {
+                    config
                         .players
                         .get(config.profiles.main as usize)
                         .zip(config.players.get(config.profiles.dummy.index as usize))
-                    {
-                        let bind_player = if config.profiles.dummy.copy_binds_from_main {
-                            player
-                        } else {
-                            dummy
-                        };
-                        for bind in &bind_player.binds {
-                            let cmds = parser::parse(bind, &entries_to_parser(console_entries));
-                            for cmd in &cmds {
-                                if let CommandType::Full(cmd) = cmd {
-                                    let (keys, actions) = syn_to_bind(&cmd.args, &map).unwrap();
-
-                                    binds.register_bind(&keys, actions);
-                                }
+                        .map(|(player, dummy)| {
+                            // in split screen both players share the keyboard,
+                            // so the dummy needs its own binds
+                            if config.profiles.dummy.copy_binds_from_main
+                                && !config.cl.dummy.split_screen
+                            {
+                                player
+                            } else {
+                                dummy
                             }
-                        }
-                    }
-                } else if let Some(player) = config.players.get(config.profiles.main as usize) {
-                    for bind in &player.binds {
-                        let cmds = parser::parse(bind, &entries_to_parser(console_entries));
-                        for cmd in &cmds {
-                            if let CommandType::Full(cmd) = cmd {
-                                let (keys, actions) = syn_to_bind(&cmd.args, &map).unwrap();
-
-                                binds.register_bind(&keys, actions);
+                        })
+                } else {
+                    config.players.get(config.profiles.main as usize)
+                };
+                // keys without a bind of the profile (e.g. before the bind setup
+                // was finished) use the default binds.
+                // In split screen the dummy has its own defaults, so the players
+                // don't react to the same keys.
+                let layout = config.cl.active_keyboard_layout();
+                let default_binds = if snap_player.is_dummy && config.cl.dummy.split_screen {
+                    ConfigPlayer::split_screen_binds()
+                } else {
+                    ConfigPlayer::default_binds(layout)
+                };
+                let player_binds = match bind_player {
+                    Some(player) => player.binds_over(default_binds),
+                    None => default_binds,
+                };
+                for bind in &player_binds {
+                    let cmds = parser::parse(bind, &entries_to_parser(console_entries));
+                    for cmd in &cmds {
+                        if let CommandType::Full(cmd) = cmd {
+                            match syn_to_bind(&cmd.args, &map) {
+                                Ok((keys, actions)) => binds.register_bind(&keys, actions),
+                                Err(err) => {
+                                    info!(target: "binds", "invalid bind \"{bind}\": {err}")
+                                }
                             }
                         }
                     }
                 }
-
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::KeyA))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::MoveLeft)],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::KeyD))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::MoveRight)],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::Space))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::Jump)],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::Escape))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::OpenMenu)],
-                );
-                binds.register_bind(
-                    &[BindKey::Mouse(MouseButton::Left)],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::Fire)],
-                );
-                binds.register_bind(
-                    &[BindKey::Mouse(MouseButton::Right)],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::Hook)],
-                );
-                binds.register_bind(
-                    &[BindKey::Extra(MouseExtra::WheelDown)],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::PrevWeapon)],
-                );
-                binds.register_bind(
-                    &[BindKey::Extra(MouseExtra::WheelUp)],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::NextWeapon)],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::Digit1))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::Weapon(
-                        WeaponType::Hammer,
-                    ))],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::Digit2))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::Weapon(
-                        WeaponType::Gun,
-                    ))],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::Digit3))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::Weapon(
-                        WeaponType::Shotgun,
-                    ))],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::Digit4))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::Weapon(
-                        WeaponType::Grenade,
-                    ))],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::Digit5))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::Weapon(
-                        WeaponType::Laser,
-                    ))],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::KeyG))],
-                    vec![BindActions::LocalPlayer(
-                        BindActionsLocalPlayer::ToggleDummyCopyMoves,
-                    )],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::Enter))],
-                    vec![BindActions::LocalPlayer(
-                        BindActionsLocalPlayer::ActivateChatInput,
-                    )],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::KeyT))],
-                    vec![BindActions::LocalPlayer(
-                        BindActionsLocalPlayer::ActivateChatInput,
-                    )],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::Tab))],
-                    vec![BindActions::LocalPlayer(
-                        BindActionsLocalPlayer::ShowScoreboard,
-                    )],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::KeyU))],
-                    vec![BindActions::LocalPlayer(
-                        BindActionsLocalPlayer::ShowChatHistory,
-                    )],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::ShiftLeft))],
-                    vec![BindActions::LocalPlayer(
-                        BindActionsLocalPlayer::ShowEmoteWheel,
-                    )],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::KeyQ))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::Kill)],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::F3))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::VoteYes)],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::F4))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::VoteNo)],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::NumpadSubtract))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::ZoomOut)],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::NumpadAdd))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::ZoomIn)],
-                );
-                binds.register_bind(
-                    &[BindKey::Key(PhysicalKey::Code(KeyCode::NumpadMultiply))],
-                    vec![BindActions::LocalPlayer(BindActionsLocalPlayer::ZoomReset)],
-                );
                 self.local_players.insert(*id, local_player);
             }
             // sort
@@ -423,6 +316,8 @@ pub struct ActiveGame {
     pub demo_recorder_props: DemoRecorderCreateProps,
 
     pub game_data: GameData,
+    pub highlights: Highlights,
+    pub chat_templates: ChatTemplates,
 
     pub events: PoolBTreeMap<GameTickType, (GameEvents, bool)>,
 
@@ -626,6 +521,11 @@ impl Game {
         hint_start_camera_pos: vec2,
         ui: &mut UiState,
         config: &mut ConfigEngine,
+        demo_passphrase: &str,
+        demo_split: &ConfigDemoSplit,
+        demo_record_inputs: bool,
+        render_mod: &str,
+        notifications: &Notifications,
         addr: SocketAddr,
         game_options: GameStateCreateOptions,
         rcon_secret: Option<[u8; 32]>,
@@ -649,6 +549,20 @@ impl Game {
             render_module: GameModification::Native,
             io: io.clone(),
             physics_group_name: props.physics_group_name.clone(),
+            encryption_passphrase: (!demo_passphrase.is_empty())
+                .then(|| demo_passphrase.to_string()),
+            split: DemoRecorderSplit {
+                max_duration: (demo_split.max_minutes > 0)
+                    .then(|| Duration::from_secs(demo_split.max_minutes * 60)),
+                max_size: (demo_split.max_size_mib > 0)
+                    .then(|| demo_split.max_size_mib * 1024 * 1024),
+            },
+            record_inputs: demo_record_inputs,
+            signing_key: None,
+            on_saved: Some(DemoSavedCb({
+                let notifications = notifications.clone();
+                Arc::new(move |name| notifications.info(format!("Demo {name} saved")))
+            })),
         };
         Self::Loading(LoadingGame {
             network,
@@ -664,6 +578,7 @@ impl Game {
                 false,
                 game_options,
                 props,
+                render_mod,
             ),
             ping,
             prediction_timer: PredictionTimer::new(ping, timestamp),
@@ -737,6 +652,7 @@ impl Game {
         config_game: &ConfigGame,
         sys: &System,
         ui_creator: &UiCreator,
+        notifications: &Notifications,
     ) {
         let mut selfi = Self::None;
         std::mem::swap(&mut selfi, self);
@@ -868,10 +784,17 @@ impl Game {
                         demo_recorder_props.clone(),
                         map.game.game_tick_speed(),
                         None,
-                    );
+                    )
+                    .map_err(|err| notifications.error(format!("Failed to record the demo: {err}")))
+                    .ok();
 
                     let mut remote_console = RemoteConsoleBuilder::build(ui_creator);
                     remote_console.ui.ui_state.is_ui_open = false;
+                    // one history per server
+                    remote_console.load_history(
+                        &demo_recorder_props.io,
+                        &format!("remote_{}", addr.to_string().replace([':', '[', ']'], "_")),
+                    );
 
                     let events_pool = Pool::with_capacity(4);
 
@@ -881,9 +804,11 @@ impl Game {
                         game_event_generator_client,
                         has_new_events_client,
                         map,
-                        demo_recorder: Some(demo_recorder),
+                        demo_recorder,
                         demo_recorder_props,
                         game_data: GameData::new(sys.time_get_nanoseconds(), prediction_timer),
+                        highlights: Default::default(),
+                        chat_templates: Default::default(),
 
                         events: events_pool.new(),
                         map_votes_loaded: Default::default(),
@@ -951,6 +876,7 @@ impl Game {
         account_info: &AccountInfo,
         spatial_chat: &mut SpatialChat,
         spatial_chat_scene: &SceneObject,
+        notifications: &Notifications,
     ) {
         let mut selfi = Self::None;
         std::mem::swap(&mut selfi, self);
@@ -981,6 +907,11 @@ impl Game {
                         info.hint_start_camera_pos,
                         ui,
                         config,
+                        &config_game.cl.demo_passphrase,
+                        &config_game.cl.demo_split,
+                        config_game.cl.demo_record_inputs,
+                        &config_game.cl.render_mod,
+                        notifications,
                         connecting.addr,
                         GameStateCreateOptions {
                             hint_max_characters: None, // TODO: get from server
@@ -1041,6 +972,11 @@ impl Game {
                         info.hint_start_camera_pos,
                         ui,
                         config,
+                        &config_game.cl.demo_passphrase,
+                        &config_game.cl.demo_split,
+                        config_game.cl.demo_record_inputs,
+                        &config_game.cl.render_mod,
+                        notifications,
                         game.addr,
                         GameStateCreateOptions {
                             hint_max_characters: None, // TODO: get from server
@@ -1104,6 +1040,7 @@ impl Game {
                         msg,
                         &mut GameMsgPipeline {
                             demo_recorder: &mut game.demo_recorder,
+                            replay: &mut game.highlights.replay,
                             network: &mut game.network,
                             runtime_thread_pool: tp,
                             io,
@@ -3,18 +3,22 @@ use std::{sync::Arc, time::Duration};
 use base_io::io::Io;
 use client_types::console::ConsoleEntry;
 use client_ui::{
-    connect::user_data::ConnectModes,
+    connect::user_data::{ConnectModes, DisconnectReason},
     ingame_menu::{account_info::AccountInfo, server_info::GameServerInfo},
+    notifications::notifications::Notifications,
 };
 use game_config::config::ConfigGame;
 use math::math::vector::luffixed;
 use pool::datatypes::StringPool;
 use shared_base::network::server_info::ServerInfo;
-use shared_network::{game_event_generator::GameEvents, messages::GameMessage};
+use shared_network::{
+    game_event_generator::GameEvents,
+    messages::{ClientToServerMessage, GameMessage, NETWORK_VERSION},
+};
 
 use base::system::System;
 use config::config::ConfigEngine;
-use network::network::event::NetworkEvent;
+use network::network::event::{NetworkDisconnectReason, NetworkEvent};
 use sound::scene_object::SceneObject;
 use ui_base::{font_data::UiFontData, types::UiState};
 
@@ -38,10 +42,28 @@ pub struct GameEventPipeline<'a> {
     pub fonts: &'a Arc<UiFontData>,
     pub spatial_chat: &'a mut SpatialChat,
     pub spatial_chat_scene: &'a SceneObject,
+    pub notifications: &'a Notifications,
 }
 
 pub struct GameEventsClient {}
 
+fn disconnect_reason(reason: NetworkDisconnectReason) -> DisconnectReason {
+    match reason {
+        NetworkDisconnectReason::Left => DisconnectReason::Left,
+        NetworkDisconnectReason::Kicked(reason) => DisconnectReason::Kicked(reason),
+        NetworkDisconnectReason::Banned { reason, duration } => {
+            DisconnectReason::Banned { reason, duration }
+        }
+        NetworkDisconnectReason::ServerShutdown => DisconnectReason::ServerShutdown,
+        NetworkDisconnectReason::MapChange => DisconnectReason::MapChange,
+        NetworkDisconnectReason::Timeout => DisconnectReason::Timeout,
+        NetworkDisconnectReason::VersionMismatch { server_version } => {
+            DisconnectReason::VersionMismatch { server_version }
+        }
+        NetworkDisconnectReason::Other(err) => DisconnectReason::Other(err),
+    }
+}
+
 impl GameEventsClient {
     pub fn update(pipe: &mut GameEventPipeline<'_>) {
         let event_gen = match pipe.client {
@@ -73,14 +95,32 @@ impl GameEventsClient {
             for (_, timestamp, event) in events {
                 match event {
                     GameEvents::NetworkEvent(net_ev) => match net_ev {
-                        NetworkEvent::Connected { .. } => {}
+                        NetworkEvent::Connected { .. } => {
+                            // the server kicks clients with an incompatible network version
+                            if let Game::Connecting(game) = pipe.client {
+                                game.network.send_unordered_to_server(
+                                    &GameMessage::ClientToServer(ClientToServerMessage::Version(
+                                        NETWORK_VERSION,
+                                    )),
+                                );
+                            }
+                        }
                         NetworkEvent::Disconnected { graceful, reason } => {
                             if graceful {
                                 pipe.config.ui.path.route("");
                             } else {
-                                if let Game::Active(game) = pipe.client {
-                                    game.connect_info
-                                        .set(ConnectModes::DisconnectErr { msg: reason });
+                                let connect_info = match pipe.client {
+                                    Game::Connecting(game) => Some(&game.connect_info),
+                                    Game::Loading(game) => Some(&game.connect_info),
+                                    Game::Active(game) => Some(&game.connect_info),
+                                    _ => None,
+                                };
+                                if let Some(connect_info) = connect_info {
+                                    let reconnect = reason.should_reconnect();
+                                    connect_info.set(ConnectModes::DisconnectErr {
+                                        reason: disconnect_reason(reason),
+                                        reconnect,
+                                    });
                                 }
                                 pipe.config.ui.path.route("connect");
                             }
@@ -163,6 +203,7 @@ impl GameEventsClient {
                                     pipe.account_info,
                                     pipe.spatial_chat,
                                     pipe.spatial_chat_scene,
+                                    pipe.notifications,
                                 );
                             }
                             _ => {
@@ -0,0 +1,67 @@
+use config::config::ConfigBackend;
+use graphics_types::gpu::Gpus;
+
+/// The layers of the texture arrays of tile layers, one per tile.
+const TILE_LAYER_ARRAY_LAYERS: u32 = 256;
+
+/// Validates the graphics config against the capabilities the backend
+/// reported for the current gpu.
+///
+/// Unsupported settings are replaced by the closest supported ones,
+/// so the next start does not request them again.
+/// Returns `true` if the config was changed.
+pub fn calibrate_graphics_config(config: &mut ConfigBackend, gpus: &Gpus) -> bool {
+    let mut changed = false;
+
+    if config.msaa_samples > 1 {
+        let samples = gpus.cur.supported_msaa_samples(config.msaa_samples);
+        if samples != config.msaa_samples {
+            log::info!(
+                target: "graphics",
+                "{} msaa samples are not supported by {}, falling back to {}",
+                config.msaa_samples,
+                gpus.cur.name,
+                samples
+            );
+            config.msaa_samples = samples;
+            changed = true;
+        }
+    }
+
+    if config.gpu != "auto"
+        && gpus.cur.max_texture_array_layers < TILE_LAYER_ARRAY_LAYERS
+        && gpus.auto.name != gpus.cur.name
+    {
+        log::info!(
+            target: "graphics",
+            "{} only supports texture arrays with {} layers, tile layers need {}, \
+            falling back to auto selection",
+            gpus.cur.name,
+            gpus.cur.max_texture_array_layers,
+            TILE_LAYER_ARRAY_LAYERS
+        );
+        config.gpu = "auto".to_string();
+        changed = true;
+    } else if gpus.cur.max_texture_array_layers < TILE_LAYER_ARRAY_LAYERS {
+        log::warn!(
+            target: "graphics",
+            "{} only supports texture arrays with {} layers, tile layers need {}, \
+            maps and entities might fail to load",
+            gpus.cur.name,
+            gpus.cur.max_texture_array_layers,
+            TILE_LAYER_ARRAY_LAYERS
+        );
+    }
+
+    if config.gpu != "auto" && !gpus.gpus.iter().any(|gpu| gpu.name == config.gpu) {
+        log::info!(
+            target: "graphics",
+            "gpu \"{}\" was not found, falling back to auto selection",
+            config.gpu
+        );
+        config.gpu = "auto".to_string();
+        changed = true;
+    }
+
+    changed
+}
@@ -4,9 +4,12 @@ use base_io::io::Io;
 use binds::binds::{
     gen_local_player_action_hash_map, BindActions, BindActionsHotkey, BindActionsLocalPlayer,
 };
+use client_console::console::local_console::CHAT_TEMPLATE_CMD;
 use client_render_base::map::render_tools::RenderTools;
 use client_ui::console::utils::try_apply_config_val;
 use client_ui::emote_wheel::user_data::EmoteWheelEvent;
+use client_ui::notifications::notifications::Notifications;
+use command_parser::parser::Syn;
 use config::config::ConfigEngine;
 use egui::{Context, CursorIcon};
 use game_config::config::ConfigGame;
@@ -101,6 +104,11 @@ pub enum InputHandlingEvent {
     },
     VoteYes,
     VoteNo,
+    /// Send the chat template with this name.
+    ChatTemplate {
+        local_player_id: GameEntityId,
+        name: String,
+    },
 }
 
 pub struct InputHandling {
@@ -221,10 +229,14 @@ impl InputHandling {
                     BindActionsLocalPlayer::ShowEmoteWheel => {
                         next_show_emote_wheel = true;
                     }
+                    BindActionsLocalPlayer::Emoticon(_) => {
+                        // only listen for click
+                    }
                     BindActionsLocalPlayer::OpenMenu => {
                         // only listen for click
                     }
-                    BindActionsLocalPlayer::ActivateChatInput => {
+                    BindActionsLocalPlayer::ActivateChatInput
+                    | BindActionsLocalPlayer::ActivateTeamChatInput => {
                         // only listen for click
                     }
                     BindActionsLocalPlayer::Kill => {
@@ -276,6 +288,11 @@ impl InputHandling {
                     }
                     BindActionsLocalPlayer::ActivateChatInput => {
                         local_player.chat_input_active = true;
+                        local_player.chat_team = false;
+                    }
+                    BindActionsLocalPlayer::ActivateTeamChatInput => {
+                        local_player.chat_input_active = true;
+                        local_player.chat_team = true;
                     }
                     BindActionsLocalPlayer::Kill => evs.push(InputHandlingEvent::Kill {
                         local_player_id: *local_player_id,
@@ -286,6 +303,13 @@ impl InputHandling {
                     BindActionsLocalPlayer::VoteNo => {
                         evs.push(InputHandlingEvent::VoteNo);
                     }
+                    BindActionsLocalPlayer::Emoticon(emoticon) => {
+                        local_player.recent_emoticons.add(*emoticon);
+                        evs.push(InputHandlingEvent::Emoticon {
+                            local_player_id: *local_player_id,
+                            emoticon: *emoticon,
+                        });
+                    }
                     _ => {}
                 };
                 match action {
@@ -295,6 +319,13 @@ impl InputHandling {
                     BindActions::Command(cmd) => {
                         if let Some(action) = bind_cmds.get(cmd.ident.as_str()) {
                             handle_action(action);
+                        } else if cmd.ident == CHAT_TEMPLATE_CMD {
+                            if let Some((Syn::Text(name), _)) = cmd.args.first() {
+                                evs.push(InputHandlingEvent::ChatTemplate {
+                                    local_player_id: *local_player_id,
+                                    name: name.clone(),
+                                });
+                            }
                         } else {
                             // TODO: show errors somewhere?
                             let _ = try_apply_config_val(
@@ -326,6 +357,7 @@ impl InputHandling {
             let ev = local_player.last_emote_wheel_selection.unwrap();
             match ev {
                 EmoteWheelEvent::EmoticonSelected(emoticon) => {
+                    local_player.recent_emoticons.add(emoticon);
                     evs.push(InputHandlingEvent::Emoticon {
                         local_player_id: *local_player_id,
                         emoticon,
@@ -362,6 +394,7 @@ impl InputHandling {
         debug_hud_state: &mut UiState,
 
         io: &Io,
+        notifications: &Notifications,
     ) {
         let actions = global_binds.process();
         for action in actions.click_actions.iter() {
@@ -371,15 +404,21 @@ impl InputHandling {
                     #[derive(Debug)]
                     struct Screenshot {
                         io: Io,
+                        notifications: Notifications,
                     }
                     impl ScreenshotCb for Screenshot {
                         fn on_screenshot(&self, png: anyhow::Result<Vec<u8>>) {
                             match png {
                                 Ok(png) => {
                                     let fs = self.io.fs.clone();
+                                    let notifications = self.notifications.clone();
 
                                     self.io.io_batcher.spawn_without_lifetime(async move {
-                                        fs.write_file("test.png".as_ref(), png).await?;
+                                        match fs.write_file("test.png".as_ref(), png).await {
+                                            Ok(()) => notifications.info("Screenshot saved"),
+                                            Err(err) => notifications
+                                                .error(format!("Screenshot failed: {err}")),
+                                        }
                                         Ok(())
                                     });
                                 }
@@ -389,7 +428,12 @@ impl InputHandling {
                             }
                         }
                     }
-                    graphics.do_screenshot(Screenshot { io }).unwrap();
+                    graphics
+                        .do_screenshot(Screenshot {
+                            io,
+                            notifications: notifications.clone(),
+                        })
+                        .unwrap();
                 }
                 BindActionsHotkey::LocalConsole => {
                     local_console_state.is_ui_open = !local_console_state.is_ui_open;
@@ -474,6 +518,7 @@ impl InputHandling {
         debug_hud_ui: &mut UiContainer,
         graphics: &Graphics,
         io: &Io,
+        notifications: &Notifications,
     ) {
         for ev in &self.inp.evs {
             match ev {
@@ -495,6 +540,7 @@ impl InputHandling {
                                 remote_console_ui.as_mut().map(|ui| &mut ui.ui_state),
                                 &mut debug_hud_ui.ui_state,
                                 io,
+                                notifications,
                             );
                             global_binds.handle_key_up(&key_ev.key);
                         }
@@ -506,6 +552,7 @@ impl InputHandling {
                         remote_console_ui.as_mut().map(|ui| &mut ui.ui_state),
                         &mut debug_hud_ui.ui_state,
                         io,
+                        notifications,
                     );
                 }
                 InputEv::Move(_) => {}
@@ -525,6 +572,7 @@ impl InputHandling {
     ) -> Vec<InputHandlingEvent> {
         let mut res = Vec::new();
 
+        let split_screen = config_game.cl.dummy.split_screen && local_players.len() > 1;
         self.inp.evs.retain(|ev| {
             if device_to_local_player
                 .get(ev.device())
@@ -533,8 +581,27 @@ impl InputHandling {
                 < local_players.len()
                 || local_players.len() == 1
             {
-                let (local_player_id, local_player) = local_players.iter_mut().next().unwrap();
-                if !local_player.chat_input_active {
+                // in split screen the keyboard is shared by all local players,
+                // a key goes to the other players if they bind it, else to the first one.
+                // The mouse always belongs to the first player.
+                let player_index = match ev {
+                    InputEv::Key(InputKeyEv {
+                        key: key @ BindKey::Key(_),
+                        ..
+                    }) if split_screen => local_players
+                        .values()
+                        .skip(1)
+                        .position(|local_player| local_player.binds.is_bound(key))
+                        .map(|index| index + 1)
+                        .unwrap_or(0),
+                    _ => 0,
+                };
+                if let Some((local_player_id, local_player)) =
+                    local_players.iter_mut().nth(player_index)
+                {
+                    if local_player.chat_input_active {
+                        return true;
+                    }
                     match ev {
                         InputEv::Key(key_ev) => match &key_ev.key {
                             BindKey::Key(_) | BindKey::Mouse(_) => {
@@ -612,17 +679,16 @@ impl InputHandling {
                                         .set(CharacterInputCursor::from_vec2(&new));
                                     local_player.free_cam_pos = new;
                                 }
-                                PlayerCameraMode::LockedTo(_) => {
+                                PlayerCameraMode::LockedTo(_)
+                                | PlayerCameraMode::FollowCharacter(_) => {
                                     // don't alter the cursor
                                 }
                             }
                         }
                     }
-
-                    false
-                } else {
-                    true
                 }
+
+                false
             } else {
                 true
             }
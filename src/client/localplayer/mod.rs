@@ -1,8 +1,12 @@
-use std::{collections::BTreeMap, time::Duration};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Duration,
+};
 
 use binds::binds::BindActions;
 use client_ui::emote_wheel::user_data::EmoteWheelEvent;
 use game_interface::types::{
+    emoticons::EmoticonType,
     game::{GameEntityId, GameTickType},
     render::character::PlayerCameraMode,
 };
@@ -17,6 +21,27 @@ pub struct ServerInputForDiff {
     pub inp: PlayerInputChainable,
 }
 
+/// How many recently used emoticons are remembered.
+const EMOTICON_HISTORY_LEN: usize = 5;
+
+/// The emoticons a player used most recently, newest first.
+#[derive(Debug, Default)]
+pub struct EmoticonHistory {
+    recent: VecDeque<EmoticonType>,
+}
+
+impl EmoticonHistory {
+    pub fn add(&mut self, emoticon: EmoticonType) {
+        self.recent.retain(|&recent| recent != emoticon);
+        self.recent.push_front(emoticon);
+        self.recent.truncate(EMOTICON_HISTORY_LEN);
+    }
+
+    pub fn to_vec(&self) -> Vec<EmoticonType> {
+        self.recent.iter().copied().collect()
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct ClientPlayer {
     pub input: PlayerInput,
@@ -28,6 +53,8 @@ pub struct ClientPlayer {
     pub binds: Binds<Vec<BindActions>>,
 
     pub chat_input_active: bool,
+    /// The chat input sends to the own team instead of everyone.
+    pub chat_team: bool,
     pub chat_msg: String,
 
     /// show a longer chat history
@@ -36,6 +63,7 @@ pub struct ClientPlayer {
 
     pub emote_wheel_active: bool,
     pub last_emote_wheel_selection: Option<EmoteWheelEvent>,
+    pub recent_emoticons: EmoticonHistory,
 
     // dummy controls
     pub dummy_copy_moves: bool,
@@ -1,8 +1,10 @@
 pub mod client;
 mod component;
 mod components;
+pub mod crash_reporter;
 pub mod game;
 mod game_events;
+mod graphics_calibration;
 mod input;
 pub mod localplayer;
 pub mod spatial_chat;
@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap},
     ops::ControlFlow,
+    time::{Duration, Instant},
 };
 
 use base::hash::fmt_hash;
@@ -41,6 +42,7 @@ pub struct PlayerEntity {
     ent: StreamEntity,
 
     cur_settings: ConfigSpatialChatPerPlayerOptions,
+    priority: bool,
 }
 
 /// Keep alive RAII objects
@@ -56,6 +58,7 @@ pub struct SettingsStream {
 pub struct PendingEntity {
     pub opus_frames: BTreeMap<u64, Vec<Vec<u8>>>,
     pub settings: ConfigSpatialChatPerPlayerOptions,
+    pub priority: bool,
 }
 
 /// Keep alive RAII objects
@@ -70,6 +73,11 @@ pub struct SpatialChatGameWorld {
     pending_entities: HashMap<GameEntityId, PendingEntity>,
     entities_positions: HashMap<GameEntityId, vec2>,
 
+    /// The last time any player spoke.
+    last_voice: Option<Instant>,
+    /// The last time a priority speaker spoke.
+    last_priority_voice: Option<Instant>,
+
     // entities last
     entities: HashMap<GameEntityId, PlayerEntity>,
 }
@@ -108,6 +116,23 @@ impl SpatialChatGameWorldTy {
     }
 }
 
+/// Opus compresses silence (e.g. the output of a closed noise gate)
+/// to a few bytes, bigger frames contain voice.
+const VOICE_OPUS_FRAME_MIN_SIZE: usize = 16;
+/// How long sounds stay lowered after someone stopped speaking.
+const DUCKING_HOLD_TIME: Duration = Duration::from_millis(500);
+
+fn has_voice(opus_frames: &BTreeMap<u64, Vec<Vec<u8>>>) -> bool {
+    opus_frames
+        .values()
+        .flatten()
+        .any(|frame| frame.len() >= VOICE_OPUS_FRAME_MIN_SIZE)
+}
+
+fn is_speaking(last_voice: Option<Instant>) -> bool {
+    last_voice.is_some_and(|last_voice| last_voice.elapsed() < DUCKING_HOLD_TIME)
+}
+
 pub struct SpatialChat {
     pub spatial_chat: spatial_chat::SpatialChat,
     microphone: MicrophoneManager,
@@ -257,6 +282,8 @@ impl SpatialChat {
                     entities: Default::default(),
                     pending_entities: Default::default(),
                     entities_positions: Default::default(),
+                    last_voice: None,
+                    last_priority_voice: None,
                 })
             })
             .unwrap_or(SpatialChatGameWorldTy::ClientSideDeactivated)
@@ -365,13 +392,22 @@ impl SpatialChat {
                         game.listener.update(*pos);
                     }
 
+                    let now = Instant::now();
+                    for entity in game.pending_entities.values() {
+                        if has_voice(&entity.opus_frames) {
+                            game.last_voice = Some(now);
+                            if entity.priority {
+                                game.last_priority_voice = Some(now);
+                            }
+                        }
+                    }
+                    let priority_speaking = is_speaking(game.last_priority_voice);
+
                     // update current world
                     for (entity_id, mut entity) in game.pending_entities.drain() {
-                        if game
-                            .entities
-                            .get(&entity_id)
-                            .is_some_and(|p| p.cur_settings != entity.settings)
-                        {
+                        if game.entities.get(&entity_id).is_some_and(|p| {
+                            p.cur_settings != entity.settings || p.priority != entity.priority
+                        }) {
                             game.entities.remove(&entity_id);
                         }
 
@@ -396,11 +432,21 @@ impl SpatialChat {
                                 sender,
                                 last_id: None,
                                 cur_settings: entity.settings,
+                                priority: entity.priority,
                             }
                         });
 
                         if let Some(pos) = game.entities_positions.get(&entity_id) {
-                            player.ent.obj.update(StreamPlayProps::with_pos(*pos).base);
+                            // lower all other voices while a priority speaker speaks
+                            let volume = if priority_speaking && !player.priority {
+                                config.cl.spatial_chat.priority_speaker_ducking
+                            } else {
+                                1.0
+                            };
+                            player
+                                .ent
+                                .obj
+                                .update(StreamPlayProps::with_pos(*pos).with_volume(volume).base);
                         }
 
                         while entity
@@ -472,7 +518,10 @@ impl SpatialChat {
     ) {
         if let Some((world, world_entities)) = world {
             // drop all entities that are not part of the packet
-            world.entities.retain(|id, _| entities.contains_key(id));
+            // or that are muted by muting all players
+            world.entities.retain(|id, entity| {
+                entities.contains_key(id) && (!config.cl.spatial_chat.mute_all || entity.priority)
+            });
 
             self.spatial_chat.update_entities(
                 entities
@@ -486,6 +535,7 @@ impl SpatialChat {
                                     .map(|e| e.info.name.to_string())
                                     .unwrap_or_else(|| "unknown player".to_string()),
                                 unique_id: p.player_unique_id,
+                                priority: p.priority,
                             },
                         )
                     })
@@ -519,13 +569,27 @@ impl SpatialChat {
                     Some((id, entity, Default::default()))
                 }
             }) {
+                if config.cl.spatial_chat.mute_all && !entity.priority {
+                    continue;
+                }
                 let entry = world.pending_entities.entry(id).or_default();
                 entry.settings = settings;
+                entry.priority = entity.priority;
                 entry.opus_frames.extend(entity.latest_opus_frames);
             }
         }
     }
 
+    /// The volume of the game sounds, which are lowered while someone speaks.
+    pub fn game_sound_volume(world: &SpatialChatGameWorldTy, config: &ConfigGame) -> f64 {
+        match world {
+            SpatialChatGameWorldTy::World(world) if is_speaking(world.last_voice) => {
+                config.cl.spatial_chat.game_sound_ducking
+            }
+            _ => 1.0,
+        }
+    }
+
     pub fn on_entity_positions(
         world: Option<&mut SpatialChatGameWorld>,
         entities_positions: HashMap<GameEntityId, vec2>,
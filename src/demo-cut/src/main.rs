@@ -0,0 +1,53 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use base_fs::filesys::FileSystem;
+use base_http::http::HttpClient;
+use base_io::io::{Io, IoFileSys};
+use clap::Parser;
+use demo::cut::{cut_demo, DemoCutFilters};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// name of the cut demo, it is saved in the demos directory
+    name: String,
+    /// file name of the demo to cut
+    demo: PathBuf,
+    /// start of the cut in seconds, relative to the start of the demo
+    #[arg(short, long, default_value_t = 0.0)]
+    start: f64,
+    /// end of the cut in seconds, relative to the start of the demo
+    #[arg(short, long)]
+    end: f64,
+    /// remove all chat messages from the cut demo
+    #[arg(long)]
+    remove_chat: bool,
+    /// passphrase of an encrypted demo, the cut demo is encrypted with it too
+    #[arg(short, long)]
+    key: Option<String>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    anyhow::ensure!(
+        args.start >= 0.0 && args.end.is_finite() && args.start <= args.end,
+        "the start of the cut must be before its end."
+    );
+
+    let io = Io::from(
+        IoFileSys::new(|rt| Arc::new(FileSystem::new(rt, "org", "", "DDNet", "DDNet-Accounts"))),
+        Arc::new(HttpClient::new()),
+    );
+
+    cut_demo(
+        &io,
+        &std::fs::read(&args.demo)?,
+        args.key,
+        args.name,
+        Duration::from_secs_f64(args.start)..=Duration::from_secs_f64(args.end),
+        &DemoCutFilters {
+            remove_chat: args.remove_chat,
+        },
+    )
+}
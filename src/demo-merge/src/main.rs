@@ -0,0 +1,46 @@
+use std::{path::PathBuf, sync::Arc};
+
+use base_fs::filesys::FileSystem;
+use base_http::http::HttpClient;
+use base_io::io::{Io, IoFileSys};
+use clap::Parser;
+use demo::merge::{merge_demos, DemoMergeInput};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// name of the merged demo, it is saved in the demos directory
+    name: String,
+    /// file names of the demos to merge, the first one is the main point of view
+    #[arg(required = true, num_args = 2..)]
+    demos: Vec<PathBuf>,
+    /// passphrase of encrypted demos, the merged demo is encrypted with it too
+    #[arg(short, long)]
+    key: Option<String>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let io = Io::from(
+        IoFileSys::new(|rt| Arc::new(FileSystem::new(rt, "org", "", "DDNet", "DDNet-Accounts"))),
+        Arc::new(HttpClient::new()),
+    );
+
+    let demos = args
+        .demos
+        .iter()
+        .map(|path| {
+            Ok(DemoMergeInput {
+                name: path
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                file: std::fs::read(path)?,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    merge_demos(&io, demos, args.key, args.name)
+}
@@ -65,7 +65,7 @@ fn main() {
     if std::env::var("RUST_LOG").is_err() {
         unsafe { std::env::set_var("RUST_LOG", "info,symphonia=warn,df::tract=error") };
     }
-    env_logger::init();
+    client::crash_reporter::init_logger();
     #[cfg(not(target_os = "android"))]
     main_impl(())
 }